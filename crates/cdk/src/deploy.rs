@@ -4,15 +4,17 @@ use std::{
     ffi::OsStr,
     fs::{File, Permissions},
     io::Write,
+    process::{Command, Stdio},
+    time::Duration,
 };
 
 use anyhow::{Result, Context, anyhow};
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use tempfile::TempDir;
 use tracing::{debug, trace};
 
 use cargo_builder::package::PackageInfo;
-use fluvio_connector_deployer::{Deployment, DeploymentType, LogLevel};
+use fluvio_connector_deployer::{Deployment, DeploymentType, LogLevel, Restart, RestartPolicy};
 use fluvio_connector_package::metadata::ConnectorMetadata;
 use fluvio_connector_package::config::ConnectorConfig;
 
@@ -45,6 +47,11 @@ enum DeployOperationCmd {
     List(DeployListCmd),
     #[command(flatten)]
     Log(DeployLogCmd),
+    #[command(flatten)]
+    Status(DeployStatusCmd),
+    /// Run the connector under supervisor, started in background by `start --restart`
+    #[command(name = "supervise", hide = true)]
+    Supervise(DeploySuperviseCmd),
 }
 
 #[derive(Debug, Subcommand)]
@@ -68,9 +75,32 @@ enum DeployStartCmd {
         /// Log level for the connector process
         #[arg(long, value_name = "LOG_LEVEL", default_value_t)]
         log_level: LogLevel,
+
+        #[command(flatten)]
+        restart: RestartOpts,
     },
 }
 
+#[derive(Debug, Clone, Args)]
+struct RestartOpts {
+    /// Run the connector under supervisor, which restarts it by the given policy
+    /// and keeps track of its status and health. Without it, the connector process is not watched
+    #[arg(long, value_name = "POLICY")]
+    restart: Option<Restart>,
+
+    /// Stop restarting the connector after this many restarts. Unlimited by default
+    #[arg(long, value_name = "COUNT", requires = "restart")]
+    max_restarts: Option<u32>,
+
+    /// Delay before restart of a crashed connector, doubled on each consecutive crash
+    #[arg(long, value_name = "SECONDS", default_value_t = 1)]
+    min_backoff_secs: u64,
+
+    /// Maximum delay before restart of a crashed connector
+    #[arg(long, value_name = "SECONDS", default_value_t = 60)]
+    max_backoff_secs: u64,
+}
+
 #[derive(Debug, Subcommand)]
 enum DeployShutdownCmd {
     /// Shutdown the Connector's deployment
@@ -100,6 +130,65 @@ enum DeployListCmd {
     Local,
 }
 
+#[derive(Debug, Subcommand)]
+enum DeployStatusCmd {
+    /// Print the status of a supervised connector
+    // As long as there is only one deployment type, we omit to specify its name
+    #[command(name = "status")]
+    #[clap(group(
+        clap::ArgGroup::new("name-source")
+        .required(true)
+        .args(&["config", "name"]),
+    ))]
+    Local {
+        /// Path to configuration file in YAML format
+        #[arg(short, long, conflicts_with = &"name", value_name = "PATH")]
+        config: Option<PathBuf>,
+
+        /// Name of the connector
+        #[arg(short, long, conflicts_with = &"config", value_name = "CONNECTOR_NAME")]
+        name: Option<String>,
+    },
+}
+
+#[derive(Debug, Args)]
+struct DeploySuperviseCmd {
+    /// Path to connector executable
+    #[arg(long, value_name = "PATH")]
+    executable: PathBuf,
+
+    /// Path to connector metadata, Connector.toml
+    #[arg(long, value_name = "PATH")]
+    metadata: PathBuf,
+
+    /// Path to configuration file in YAML format
+    #[arg(long, value_name = "PATH")]
+    config: PathBuf,
+
+    /// Path to file with secrets
+    #[arg(long, value_name = "PATH")]
+    secrets: Option<PathBuf>,
+
+    /// Log level for the connector process
+    #[arg(long, value_name = "LOG_LEVEL", default_value_t)]
+    log_level: LogLevel,
+
+    /// File the connector output is appended to
+    #[arg(long, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+
+    /// Tmp dir of ipkg deployment
+    #[arg(long, value_name = "PATH")]
+    tmp_dir: Option<PathBuf>,
+
+    /// Directory where connector status is kept
+    #[arg(long, value_name = "PATH")]
+    status_dir: PathBuf,
+
+    #[command(flatten)]
+    restart: RestartOpts,
+}
+
 #[derive(Debug, Subcommand)]
 enum DeployLogCmd {
     /// Print the connector's logs
@@ -139,6 +228,8 @@ impl DeployOperationCmd {
             Self::Shutdown(deployment_type) => deployment_type.process(package),
             Self::List(deployment_type) => deployment_type.process(),
             Self::Log(deployment_type) => deployment_type.process(package),
+            Self::Status(deployment_type) => deployment_type.process(package),
+            Self::Supervise(supervise) => supervise.process(),
         }
     }
 }
@@ -151,7 +242,8 @@ impl DeployStartCmd {
                 secrets,
                 ipkg_file,
                 log_level,
-            } => deploy_local(package, config, secrets, ipkg_file, log_level),
+                restart,
+            } => deploy_local(package, config, secrets, ipkg_file, log_level, restart),
         }
     }
 }
@@ -180,12 +272,70 @@ impl DeployLogCmd {
     }
 }
 
+impl DeployStatusCmd {
+    pub(crate) fn process(self, package: PackageCmd) -> Result<()> {
+        match self {
+            Self::Local { config, name } => print_local_status(package, config, name),
+        }
+    }
+}
+
+impl DeploySuperviseCmd {
+    pub(crate) fn process(self) -> Result<()> {
+        let mut builder = Deployment::builder();
+        builder
+            .executable(self.executable)
+            .config(self.config)
+            .secrets(self.secrets)
+            .pkg(ConnectorMetadata::from_toml_file(&self.metadata)?)
+            .log_level(self.log_level)
+            .deployment_type(DeploymentType::Supervised {
+                output_file: self.log_file,
+                tmp_dir: self.tmp_dir,
+                status_dir: self.status_dir,
+                restart_policy: self.restart.policy().unwrap_or_default(),
+            });
+        builder.deploy()?;
+        Ok(())
+    }
+}
+
+impl RestartOpts {
+    /// restart policy, if connector should be supervised
+    fn policy(&self) -> Option<RestartPolicy> {
+        self.restart.map(|restart| RestartPolicy {
+            restart,
+            max_restarts: self.max_restarts,
+            min_backoff: Duration::from_secs(self.min_backoff_secs),
+            max_backoff: Duration::from_secs(self.max_backoff_secs),
+        })
+    }
+
+    /// arguments passing these options to `supervise` command
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--min-backoff-secs".to_owned(),
+            self.min_backoff_secs.to_string(),
+            "--max-backoff-secs".to_owned(),
+            self.max_backoff_secs.to_string(),
+        ];
+        if let Some(restart) = self.restart {
+            args.extend(["--restart".to_owned(), restart.to_string()]);
+        }
+        if let Some(max_restarts) = self.max_restarts {
+            args.extend(["--max-restarts".to_owned(), max_restarts.to_string()]);
+        }
+        args
+    }
+}
+
 fn deploy_local(
     package_cmd: PackageCmd,
     config: PathBuf,
     secrets: Option<PathBuf>,
     ipkg_file: Option<PathBuf>,
     log_level: LogLevel,
+    restart: RestartOpts,
 ) -> Result<()> {
     let opt = package_cmd.as_opt();
     let mut tmp_dir: Option<PathBuf> = None;
@@ -213,6 +363,19 @@ fn deploy_local(
     log_path.push(metaconfig.name());
     log_path.set_extension("log");
 
+    if restart.policy().is_some() {
+        return deploy_supervised(SupervisedDeployment {
+            executable,
+            metadata: connector_metadata,
+            config,
+            secrets,
+            log_level,
+            log_file: log_path,
+            tmp_dir,
+            restart,
+        });
+    }
+
     let mut builder = Deployment::builder();
     builder
         .executable(executable)
@@ -228,6 +391,78 @@ fn deploy_local(
     local_index::store(result)
 }
 
+struct SupervisedDeployment {
+    executable: PathBuf,
+    metadata: ConnectorMetadata,
+    config: PathBuf,
+    secrets: Option<PathBuf>,
+    log_level: LogLevel,
+    log_file: PathBuf,
+    tmp_dir: Option<PathBuf>,
+    restart: RestartOpts,
+}
+
+/// start supervisor of the connector as a background process
+fn deploy_supervised(deployment: SupervisedDeployment) -> Result<()> {
+    let config_file = File::open(&deployment.config).with_context(|| {
+        format!(
+            "Could not open connector config at: {}",
+            deployment.config.display()
+        )
+    })?;
+    let name = deployment
+        .metadata
+        .validate_config(config_file)?
+        .meta()
+        .name()
+        .to_owned();
+    local_index::check_not_deployed(&name)?;
+
+    let status_dir = local_index::status_dir(&name);
+    std::fs::create_dir_all(&status_dir)?;
+    let metadata_path = status_dir.join(CONNECTOR_METADATA_FILE_NAME);
+    deployment.metadata.to_toml_file(&metadata_path)?;
+
+    let mut cmd = Command::new(std::env::current_exe()?);
+    cmd.args(["deploy", "supervise"]);
+    cmd.arg("--executable").arg(&deployment.executable);
+    cmd.arg("--metadata").arg(&metadata_path);
+    cmd.arg("--config")
+        .arg(std::fs::canonicalize(&deployment.config)?);
+    if let Some(secrets) = &deployment.secrets {
+        cmd.arg("--secrets").arg(std::fs::canonicalize(secrets)?);
+    }
+    cmd.arg("--log-level").arg(deployment.log_level.to_string());
+    cmd.arg("--log-file").arg(&deployment.log_file);
+    if let Some(tmp_dir) = &deployment.tmp_dir {
+        cmd.arg("--tmp-dir").arg(tmp_dir);
+    }
+    cmd.arg("--status-dir").arg(&status_dir);
+    cmd.args(deployment.restart.to_args());
+
+    // supervisor reports restarts into connector log
+    let log_file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&deployment.log_file)?;
+    cmd.env("RUST_LOG", deployment.log_level.to_string());
+    cmd.stdin(Stdio::null());
+    cmd.stdout(Stdio::null());
+    cmd.stderr(log_file);
+    let supervisor = cmd.spawn()?;
+
+    println!("Log file: {}", deployment.log_file.display());
+    println!("Supervisor runs with process id: {}", supervisor.id());
+    println!("Started connector `{name}` under supervisor");
+    local_index::store_supervised(
+        supervisor.id(),
+        name,
+        Some(deployment.log_file),
+        deployment.tmp_dir,
+        status_dir,
+    )
+}
+
 fn shutdown_local(
     package_cmd: PackageCmd,
     config: Option<PathBuf>,
@@ -256,6 +491,20 @@ fn print_local_log(
     local_index::print_log(&name)
 }
 
+fn print_local_status(
+    package_cmd: PackageCmd,
+    config: Option<PathBuf>,
+    name: Option<String>,
+) -> Result<()> {
+    let name = match (config, name) {
+        (Some(config_path), None) => connector_name_from_config(package_cmd, config_path)?,
+        (None, Some(name)) => name,
+        _ => return Err(anyhow!("Either name or config must be specified")),
+    };
+
+    local_index::print_status(&name)
+}
+
 fn connector_name_from_config(package_cmd: PackageCmd, config: PathBuf) -> Result<String> {
     let opt = package_cmd.as_opt();
     let package_info = PackageInfo::from_options(&opt)?;
@@ -358,11 +607,12 @@ mod local_index {
     use serde::{Serialize, Deserialize};
 
     use anyhow::{anyhow, Result};
-    use fluvio_connector_deployer::DeploymentResult;
+    use fluvio_connector_deployer::{DeploymentResult, SupervisedState, SupervisorStatus};
     use sysinfo::{Pid, Signal};
     use tracing::debug;
 
     const LOCAL_INDEX_FILE_NAME: &str = "fluvio_cdk_deploy_index.toml";
+    const SUPERVISOR_DIR_NAME: &str = "fluvio_cdk_supervisor";
     const LIST_TABLE_HEADERS: [&str; 2] = ["NAME", "STATUS"];
 
     #[derive(Debug, Serialize, Deserialize, Default)]
@@ -383,11 +633,22 @@ mod local_index {
             log_file: Option<PathBuf>,
             tmp_dir: Option<PathBuf>,
         },
+        Supervised {
+            supervisor_id: u32,
+            name: String,
+            log_file: Option<PathBuf>,
+            tmp_dir: Option<PathBuf>,
+            status_dir: PathBuf,
+        },
     }
 
     enum ConnectorStatus {
         Running,
         Stopped,
+        Supervised {
+            state: SupervisedState,
+            restarts: u32,
+        },
     }
 
     trait ConnectorOperator: Default {
@@ -421,12 +682,11 @@ mod local_index {
         }
 
         fn insert(&mut self, entry: Entry) -> Result<()> {
-            match entry {
-                Entry::Local { ref name, .. } => {
-                    if let Some((_, _)) = self.find_by_name(name) {
-                        return Err(anyhow!("Connector with name {} already exists", name));
-                    }
-                }
+            if let Some((_, _)) = self.find_by_name(entry.name()) {
+                return Err(anyhow!(
+                    "Connector with name {} already exists",
+                    entry.name()
+                ));
             }
 
             self.entries.push(entry);
@@ -436,32 +696,30 @@ mod local_index {
         fn remove(&mut self, index: usize) -> Result<()> {
             let entry = self.entries.remove(index);
             self.operator.kill(&entry)?;
-            match entry {
-                Entry::Local {
-                    process_id: _,
-                    name: _,
-                    log_file: _,
-                    tmp_dir: Some(ref tmp_dir),
-                } => {
+            if let Entry::Supervised { status_dir, .. } = &entry {
+                std::fs::remove_dir_all(status_dir).map_err(|e| {
+                    anyhow!(
+                        "could not clean up connector status dir {}: {e}",
+                        status_dir.display()
+                    )
+                })?;
+            }
+            match entry.tmp_dir() {
+                Some(tmp_dir) => {
                     // clean up tmp dir used with ipkg
                     std::fs::remove_dir_all(tmp_dir).map_err(|e| {
                         anyhow!("could not clean up ipkg dir {}: {e}", tmp_dir.display())
                     })
                 }
-                _ => Ok(()),
+                None => Ok(()),
             }
         }
 
         fn find_by_name(&self, connector_name: &str) -> Option<(usize, &Entry)> {
-            self.entries.iter().enumerate().find(|(_, entry)| {
-                let Entry::Local {
-                    process_id: _,
-                    name,
-                    log_file: _,
-                    tmp_dir: _,
-                } = entry;
-                name.eq(connector_name)
-            })
+            self.entries
+                .iter()
+                .enumerate()
+                .find(|(_, entry)| entry.name().eq(connector_name))
         }
 
         fn flush(&mut self) -> Result<()> {
@@ -486,51 +744,97 @@ mod local_index {
             system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
             for connector in self.entries {
                 let status = self.operator.status(&connector)?;
-                let Entry::Local {
-                    process_id: _,
-                    name,
-                    log_file: _,
-                    tmp_dir: _,
-                } = connector;
-                table.add_row(vec![name, status.to_string()]);
+                table.add_row(vec![connector.name().to_owned(), status.to_string()]);
             }
             writeln!(writer, "{table}")?;
             Ok(())
         }
     }
 
+    impl Entry {
+        fn name(&self) -> &str {
+            match self {
+                Self::Local { name, .. } | Self::Supervised { name, .. } => name,
+            }
+        }
+
+        fn log_file(&self) -> Option<&PathBuf> {
+            match self {
+                Self::Local { log_file, .. } | Self::Supervised { log_file, .. } => {
+                    log_file.as_ref()
+                }
+            }
+        }
+
+        fn tmp_dir(&self) -> Option<&PathBuf> {
+            match self {
+                Self::Local { tmp_dir, .. } | Self::Supervised { tmp_dir, .. } => tmp_dir.as_ref(),
+            }
+        }
+    }
+
     impl ConnectorOperator for LocalProcesses {
         fn status(&self, entry: &Entry) -> Result<ConnectorStatus> {
-            let Entry::Local {
-                process_id,
-                name: _,
-                log_file: _,
-                tmp_dir: _,
-            } = entry;
-            let status = if self.system.process(Pid::from_u32(*process_id)).is_some() {
-                ConnectorStatus::Running
-            } else {
-                ConnectorStatus::Stopped
+            let status = match entry {
+                Entry::Local { process_id, .. } => {
+                    if self.is_running(*process_id) {
+                        ConnectorStatus::Running
+                    } else {
+                        ConnectorStatus::Stopped
+                    }
+                }
+                Entry::Supervised {
+                    supervisor_id,
+                    status_dir,
+                    ..
+                } => match SupervisorStatus::load(status_dir) {
+                    Ok(status) if status.state.is_final() || self.is_running(*supervisor_id) => {
+                        ConnectorStatus::Supervised {
+                            state: status.state,
+                            restarts: status.restarts,
+                        }
+                    }
+                    _ => ConnectorStatus::Stopped,
+                },
             };
             Ok(status)
         }
 
         fn kill(&self, entry: &Entry) -> Result<()> {
-            let Entry::Local {
-                process_id,
-                name: _,
-                log_file: _,
-                tmp_dir: _,
-            } = entry;
-
-            if let Some(process) = self.system.process(Pid::from_u32(*process_id)) {
-                process.kill_with(Signal::Term);
+            match entry {
+                Entry::Local { process_id, .. } => self.terminate(*process_id),
+                Entry::Supervised {
+                    supervisor_id,
+                    status_dir,
+                    ..
+                } => {
+                    // stop supervisor first, so connector is not restarted
+                    self.terminate(*supervisor_id);
+                    if let Some(process_id) = SupervisorStatus::load(status_dir)
+                        .ok()
+                        .and_then(|status| status.process_id)
+                    {
+                        self.terminate(process_id);
+                    }
+                }
             }
 
             Ok(())
         }
     }
 
+    impl LocalProcesses {
+        fn is_running(&self, process_id: u32) -> bool {
+            self.system.process(Pid::from_u32(process_id)).is_some()
+        }
+
+        fn terminate(&self, process_id: u32) {
+            if let Some(process) = self.system.process(Pid::from_u32(process_id)) {
+                process.kill_with(Signal::Term);
+            }
+        }
+    }
+
     impl Default for LocalProcesses {
         fn default() -> Self {
             let mut system: sysinfo::System = Default::default();
@@ -553,6 +857,19 @@ mod local_index {
                     log_file,
                     tmp_dir,
                 },
+                DeploymentResult::Supervised {
+                    name,
+                    log_file,
+                    tmp_dir,
+                    status_dir,
+                    status,
+                } => Entry::Supervised {
+                    supervisor_id: status.supervisor_id,
+                    name,
+                    log_file,
+                    tmp_dir,
+                    status_dir,
+                },
             }
         }
     }
@@ -562,6 +879,13 @@ mod local_index {
             let str = match self {
                 Self::Running => "Running",
                 Self::Stopped => "Stopped",
+                Self::Supervised { state, restarts } => {
+                    return match restarts {
+                        0 => write!(f, "{state}"),
+                        1 => write!(f, "{state} (1 restart)"),
+                        _ => write!(f, "{state} ({restarts} restarts)"),
+                    };
+                }
             };
             write!(f, "{str}")
         }
@@ -573,6 +897,42 @@ mod local_index {
         index.flush()
     }
 
+    pub(super) fn store_supervised(
+        supervisor_id: u32,
+        name: String,
+        log_file: Option<PathBuf>,
+        tmp_dir: Option<PathBuf>,
+        status_dir: PathBuf,
+    ) -> Result<()> {
+        let mut index = load()?;
+        index.insert(Entry::Supervised {
+            supervisor_id,
+            name,
+            log_file,
+            tmp_dir,
+            status_dir,
+        })?;
+        index.flush()
+    }
+
+    pub(super) fn check_not_deployed(connector_name: &str) -> Result<()> {
+        match load()?.find_by_name(connector_name) {
+            Some(_) => Err(anyhow!(
+                "Connector with name {} already exists",
+                connector_name
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// directory where supervisor of the connector keeps its status
+    pub(super) fn status_dir(connector_name: &str) -> PathBuf {
+        let mut status_dir = std::env::temp_dir();
+        status_dir.push(SUPERVISOR_DIR_NAME);
+        status_dir.push(connector_name);
+        status_dir
+    }
+
     pub(super) fn print() -> Result<()> {
         let index = load()?;
         index.print_table(std::io::stdout())
@@ -582,16 +942,12 @@ mod local_index {
         let mut index = load()?;
 
         match index.find_by_name(connector_name) {
-            Some((
-                i,
-                Entry::Local {
-                    process_id,
-                    name,
-                    log_file,
-                    tmp_dir: _,
-                },
-            )) => {
-                let log_file = match log_file {
+            Some((i, entry)) => {
+                let process_id = match entry {
+                    Entry::Local { process_id, .. } => process_id,
+                    Entry::Supervised { supervisor_id, .. } => supervisor_id,
+                };
+                let log_file = match entry.log_file() {
                     Some(path) => format!("{}", path.display()),
                     None => "Not found".to_string(),
                 };
@@ -600,7 +956,9 @@ mod local_index {
                     "Shutting down connector: {} \
                     \npid: {} \
                     \nLog File: {}",
-                    name, process_id, log_file
+                    entry.name(),
+                    process_id,
+                    log_file
                 );
                 index.remove(i)?;
             }
@@ -612,15 +970,9 @@ mod local_index {
 
     pub(super) fn print_log(connector_name: &str) -> Result<()> {
         let index = load()?;
-        if let Some((
-            _,
-            Entry::Local {
-                process_id: _,
-                name: _,
-                log_file: Some(log_file),
-                tmp_dir: _,
-            },
-        )) = index.find_by_name(connector_name)
+        if let Some(log_file) = index
+            .find_by_name(connector_name)
+            .and_then(|(_, entry)| entry.log_file())
         {
             let mut buf_reader = std::io::BufReader::new(std::fs::File::open(log_file)?);
             std::io::copy(&mut buf_reader, &mut std::io::stdout())?;
@@ -629,6 +981,36 @@ mod local_index {
         Ok(())
     }
 
+    pub(super) fn print_status(connector_name: &str) -> Result<()> {
+        let index = load()?;
+        match index.find_by_name(connector_name) {
+            Some((_, entry @ Entry::Supervised { status_dir, .. })) => {
+                let status = SupervisorStatus::load(status_dir)?;
+                let process_id = match status.process_id {
+                    Some(process_id) => process_id.to_string(),
+                    None => "-".to_string(),
+                };
+                println!("Name: {}", status.name);
+                println!("Status: {}", index.operator.status(entry)?);
+                println!("Healthy: {}", status.healthy);
+                println!("Restarts: {}", status.restarts);
+                println!("Last exit: {}", status.last_exit.as_deref().unwrap_or("-"));
+                println!("Supervisor pid: {}", status.supervisor_id);
+                println!("Connector pid: {process_id}");
+                if let Some(metrics) = &status.metrics {
+                    println!("Metrics: {}", serde_json::to_string_pretty(metrics)?);
+                }
+            }
+            Some((_, entry)) => println!(
+                "Connector {} is not supervised, status: {}",
+                connector_name,
+                index.operator.status(entry)?
+            ),
+            None => println!("Connector not found: {}", connector_name),
+        }
+        Ok(())
+    }
+
     fn load() -> Result<LocalIndex<LocalProcesses>> {
         let mut index_path = std::env::temp_dir();
         index_path.push(LOCAL_INDEX_FILE_NAME);
@@ -828,6 +1210,59 @@ mod local_index {
             Ok(())
         }
 
+        #[test]
+        fn test_supervised_entry() -> Result<()> {
+            //given
+            let file_path = TestFile::new();
+
+            //when
+            let mut index: LocalIndex<NoopOperator> = LocalIndex::load(&file_path)?;
+            index.insert(Entry::Supervised {
+                supervisor_id: 1,
+                name: "test_connector".to_owned(),
+                log_file: None,
+                tmp_dir: None,
+                status_dir: PathBuf::from("/tmp/test_connector"),
+            })?;
+            index.flush()?;
+
+            //then
+            assert!(
+                index
+                    .insert(Entry::Local {
+                        process_id: 2,
+                        name: "test_connector".to_owned(),
+                        log_file: None,
+                        tmp_dir: None,
+                    })
+                    .is_err()
+            );
+            assert_eq!(
+                std::fs::read_to_string(&file_path)?,
+                "[[entries]]\ntype = \"supervised\"\nsupervisor_id = 1\nname = \"test_connector\"\nstatus_dir = \"/tmp/test_connector\"\n"
+            );
+
+            let index: LocalIndex<NoopOperator> = LocalIndex::load(&file_path)?;
+            assert!(index.find_by_name("test_connector").is_some());
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_supervised_status_display() {
+            let status = ConnectorStatus::Supervised {
+                state: SupervisedState::Running,
+                restarts: 0,
+            };
+            assert_eq!(status.to_string(), "Running");
+
+            let status = ConnectorStatus::Supervised {
+                state: SupervisedState::Backoff,
+                restarts: 3,
+            };
+            assert_eq!(status.to_string(), "Backoff (3 restarts)");
+        }
+
         #[derive(Default)]
         struct NoopOperator;
 
@@ -2,6 +2,7 @@ mod policy;
 mod error;
 
 pub mod root;
+pub mod rbac;
 pub mod x509;
 
 pub use policy::*;
@@ -17,6 +17,10 @@ pub enum TypeAction {
 pub enum InstanceAction {
     Delete,
     Update,
+    /// write records to topic
+    Produce,
+    /// read records from topic
+    Consume,
 }
 
 #[async_trait]
@@ -35,6 +39,11 @@ pub trait AuthContext: Debug + Send + Sync + 'static {
         action: InstanceAction,
         key: &str,
     ) -> Result<bool, AuthError>;
+
+    /// principal of authenticated identity, if any
+    fn principal(&self) -> Option<&str> {
+        None
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,380 @@
+//!
+//! # Role Based Access Control
+//!
+//! Authorization backed by [`PolicySpec`].
+//! Effective policy is a merge of policy loaded from file and policies managed by SC,
+//! both sources can be replaced at runtime without restarting the server.
+//!
+
+use std::fs::read;
+use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use tracing::{debug, error, info, instrument};
+
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_controlplane_metadata::policy::{PolicyAction, PolicySpec};
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_socket::FluvioSocket;
+
+use crate::x509::X509Identity;
+use crate::{AuthContext, AuthError, Authorization, InstanceAction, TypeAction};
+
+/// how often policy file is checked for changes
+const POLICY_FILE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+impl From<TypeAction> for PolicyAction {
+    fn from(action: TypeAction) -> Self {
+        match action {
+            TypeAction::Create => PolicyAction::Create,
+            TypeAction::Read => PolicyAction::Read,
+        }
+    }
+}
+
+impl From<InstanceAction> for PolicyAction {
+    fn from(action: InstanceAction) -> Self {
+        match action {
+            InstanceAction::Delete => PolicyAction::Delete,
+            InstanceAction::Update => PolicyAction::Update,
+            InstanceAction::Produce => PolicyAction::Produce,
+            InstanceAction::Consume => PolicyAction::Consume,
+        }
+    }
+}
+
+/// read policy in json format
+pub fn read_policy_file(path: &Path) -> Result<PolicySpec, IoError> {
+    debug!(?path, "reading policy");
+    let file = read(path)?;
+    let policy: PolicySpec = serde_json::from_slice(&file)?;
+    policy
+        .validate()
+        .map_err(|err| IoError::new(std::io::ErrorKind::InvalidData, err))?;
+    Ok(policy)
+}
+
+#[derive(Debug, Default)]
+struct PolicySources {
+    file: Option<PolicySpec>,
+    managed: Vec<PolicySpec>,
+    effective: PolicySpec,
+}
+
+impl PolicySources {
+    fn rebuild(&mut self) {
+        let mut effective = self.file.clone().unwrap_or_default();
+        for policy in &self.managed {
+            effective.merge(policy);
+        }
+        self.effective = effective;
+    }
+}
+
+/// Shared policy which can be swapped while server is running.
+/// Until policy is loaded from file or received from SC, everything is denied.
+#[derive(Debug, Default)]
+pub struct RbacPolicy {
+    sources: RwLock<PolicySources>,
+}
+
+impl RbacPolicy {
+    pub fn new(file_policy: PolicySpec) -> Self {
+        let policy = Self::default();
+        policy.set_file_policy(file_policy);
+        policy
+    }
+
+    /// replace policy loaded from file
+    pub fn set_file_policy(&self, policy: PolicySpec) {
+        let mut sources = self.sources.write().expect("policy lock poisoned");
+        sources.file = Some(policy);
+        sources.rebuild();
+    }
+
+    /// replace all policies managed by SC
+    pub fn set_managed_policies(&self, policies: impl IntoIterator<Item = PolicySpec>) {
+        let mut sources = self.sources.write().expect("policy lock poisoned");
+        sources.managed = policies.into_iter().collect();
+        sources.rebuild();
+    }
+
+    pub fn is_allowed(
+        &self,
+        identity: &X509Identity,
+        object: &ObjectType,
+        action: PolicyAction,
+        instance: Option<&str>,
+    ) -> bool {
+        let sources = self.sources.read().expect("policy lock poisoned");
+        sources.effective.is_allowed(
+            &identity.principal,
+            identity.scopes(),
+            object,
+            action,
+            instance,
+        )
+    }
+
+    /// reload file policy whenever file is modified.
+    /// Invalid file is ignored and previous policy is kept.
+    pub fn watch_file<F>(self: &Arc<Self>, path: PathBuf, load: F)
+    where
+        F: Fn(&Path) -> Result<PolicySpec, IoError> + Send + Sync + 'static,
+    {
+        let policy = self.clone();
+        spawn(async move {
+            let mut last_modified = modified_time(&path);
+            info!(?path, "watching policy file");
+            loop {
+                sleep(POLICY_FILE_POLL_INTERVAL).await;
+                let modified = modified_time(&path);
+                if modified == last_modified {
+                    continue;
+                }
+                last_modified = modified;
+                match load(&path) {
+                    Ok(file_policy) => {
+                        info!(?path, "policy file reloaded");
+                        policy.set_file_policy(file_policy);
+                    }
+                    Err(err) => {
+                        error!(?path, %err, "invalid policy file, keeping previous policy");
+                    }
+                }
+            }
+        });
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Authorization of X.509 identities using [`RbacPolicy`]
+#[derive(Debug, Clone)]
+pub struct RbacAuthorization {
+    policy: Arc<RbacPolicy>,
+}
+
+impl RbacAuthorization {
+    pub fn new(policy: Arc<RbacPolicy>) -> Self {
+        Self { policy }
+    }
+}
+
+#[async_trait]
+impl Authorization for RbacAuthorization {
+    type Context = RbacAuthContext;
+
+    #[instrument(level = "trace", skip(self, socket))]
+    async fn create_auth_context(
+        &self,
+        socket: &mut FluvioSocket,
+    ) -> Result<Self::Context, AuthError> {
+        let identity = X509Identity::create_from_connection(socket)
+            .await
+            .map_err(|err| {
+                error!(%err, "failed to create x509 identity");
+                err
+            })?;
+        Ok(RbacAuthContext {
+            identity,
+            policy: self.policy.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct RbacAuthContext {
+    identity: X509Identity,
+    policy: Arc<RbacPolicy>,
+}
+
+impl RbacAuthContext {
+    pub fn new(identity: X509Identity, policy: Arc<RbacPolicy>) -> Self {
+        Self { identity, policy }
+    }
+}
+
+#[async_trait]
+impl AuthContext for RbacAuthContext {
+    async fn allow_type_action(
+        &self,
+        ty: ObjectType,
+        action: TypeAction,
+    ) -> Result<bool, AuthError> {
+        Ok(self
+            .policy
+            .is_allowed(&self.identity, &ty, action.into(), None))
+    }
+
+    async fn allow_instance_action(
+        &self,
+        ty: ObjectType,
+        action: InstanceAction,
+        key: &str,
+    ) -> Result<bool, AuthError> {
+        Ok(self
+            .policy
+            .is_allowed(&self.identity, &ty, action.into(), Some(key)))
+    }
+
+    fn principal(&self) -> Option<&str> {
+        Some(&self.identity.principal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::Arc;
+
+    use fluvio_controlplane_metadata::extended::ObjectType;
+    use fluvio_controlplane_metadata::policy::{
+        Permission, PolicyAction, PolicyRole, PolicySpec, RoleBinding,
+    };
+
+    use crate::x509::X509Identity;
+    use crate::{AuthContext, InstanceAction, TypeAction};
+
+    use super::{RbacAuthContext, RbacPolicy, read_policy_file};
+
+    fn role(name: &str, actions: Vec<PolicyAction>, resource: &str) -> PolicyRole {
+        PolicyRole {
+            name: name.to_owned(),
+            permissions: vec![Permission {
+                object: ObjectType::Topic,
+                actions,
+                resources: vec![resource.into()],
+            }],
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_rbac_produce_consume() {
+        let policy = Arc::new(RbacPolicy::new(PolicySpec {
+            roles: vec![
+                role("producer", vec![PolicyAction::Produce], "orders-*"),
+                role("reader", vec![PolicyAction::Read], "*"),
+            ],
+            bindings: vec![RoleBinding {
+                principal: "svc-*".into(),
+                roles: vec!["producer".to_owned()],
+            }],
+        }));
+
+        let ctx = RbacAuthContext::new(
+            X509Identity::new("svc-orders".to_owned(), vec!["reader".to_owned()]),
+            policy.clone(),
+        );
+
+        let allowed = |action, topic| ctx.allow_instance_action(ObjectType::Topic, action, topic);
+        assert!(allowed(InstanceAction::Produce, "orders-eu").await.unwrap());
+        assert!(!allowed(InstanceAction::Produce, "audit").await.unwrap());
+        assert!(!allowed(InstanceAction::Consume, "orders-eu").await.unwrap());
+        assert!(
+            ctx.allow_type_action(ObjectType::Topic, TypeAction::Read)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !ctx.allow_type_action(ObjectType::Topic, TypeAction::Create)
+                .await
+                .unwrap()
+        );
+
+        // managed policies are merged with file policy
+        policy.set_managed_policies(vec![PolicySpec {
+            roles: vec![role("consumer", vec![PolicyAction::Consume], "orders-eu")],
+            bindings: vec![RoleBinding {
+                principal: "svc-orders".into(),
+                roles: vec!["consumer".to_owned()],
+            }],
+        }]);
+        assert!(allowed(InstanceAction::Consume, "orders-eu").await.unwrap());
+        assert!(!allowed(InstanceAction::Consume, "orders-us").await.unwrap());
+
+        policy.set_managed_policies(vec![]);
+        assert!(!allowed(InstanceAction::Consume, "orders-eu").await.unwrap());
+
+        // file policy is replaced
+        policy.set_file_policy(PolicySpec::default());
+        assert!(!allowed(InstanceAction::Produce, "orders-eu").await.unwrap());
+    }
+
+    #[fluvio_future::test]
+    async fn test_rbac_managed_policies_only() {
+        let policy = Arc::new(RbacPolicy::default());
+        let ctx = RbacAuthContext::new(
+            X509Identity::new("svc-orders".to_owned(), vec![]),
+            policy.clone(),
+        );
+        let allowed = |action, topic| ctx.allow_instance_action(ObjectType::Topic, action, topic);
+
+        // no policy yet
+        assert!(!allowed(InstanceAction::Produce, "orders-eu").await.unwrap());
+
+        policy.set_managed_policies(vec![PolicySpec {
+            roles: vec![role("producer", vec![PolicyAction::Produce], "orders-*")],
+            bindings: vec![RoleBinding {
+                principal: "svc-*".into(),
+                roles: vec!["producer".to_owned()],
+            }],
+        }]);
+        assert!(allowed(InstanceAction::Produce, "orders-eu").await.unwrap());
+        assert!(!allowed(InstanceAction::Produce, "audit").await.unwrap());
+
+        // last managed policy is deleted
+        policy.set_managed_policies(vec![]);
+        assert!(!allowed(InstanceAction::Produce, "orders-eu").await.unwrap());
+    }
+
+    #[test]
+    fn test_read_policy_file() {
+        let dir = tempfile::tempdir().expect("temp dir created");
+        let path = dir.path().join("policy.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "roles": [
+                    {
+                        "name": "producer",
+                        "permissions": [
+                            { "object": "Topic", "actions": ["Produce", "Read"], "resources": ["orders-*"] }
+                        ]
+                    }
+                ],
+                "bindings": [ { "principal": "svc-*", "roles": ["producer"] } ]
+            }"#,
+        )
+        .expect("write policy");
+
+        let policy = read_policy_file(&path).expect("parse policy");
+        assert_eq!(policy.roles.len(), 1);
+        assert_eq!(
+            policy.roles[0].permissions[0].actions,
+            vec![PolicyAction::Produce, PolicyAction::Read]
+        );
+        assert!(policy.is_allowed(
+            "svc-orders",
+            &[],
+            &ObjectType::Topic,
+            PolicyAction::Produce,
+            Some("orders-eu")
+        ));
+
+        std::fs::write(
+            &path,
+            r#"{ "roles": [ { "name": "bad", "permissions": [ { "object": "Topic", "actions": ["Read"], "resources": ["*orders"] } ] } ] }"#,
+        )
+        .expect("write policy");
+        assert!(read_policy_file(&path).is_err());
+    }
+}
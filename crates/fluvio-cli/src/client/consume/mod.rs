@@ -15,7 +15,7 @@ pub use cmd::ConsumeOpt;
 mod cmd {
 
     use std::sync::atomic::{AtomicBool, Ordering};
-    use std::time::{UNIX_EPOCH, Duration};
+    use std::time::{UNIX_EPOCH, Duration, SystemTime};
     use std::{io::Error as IoError, path::PathBuf};
     use std::io::{self, ErrorKind, IsTerminal, Stdout};
     use std::collections::BTreeMap;
@@ -44,7 +44,9 @@ mod cmd {
     use fluvio_protocol::record::NO_TIMESTAMP;
     use fluvio::metadata::tableformat::TableFormatSpec;
     use fluvio::{Fluvio, Offset, FluvioError};
-    use fluvio::consumer::{ConsumerConfigExt, ConsumerStream, OffsetManagementStrategy};
+    use fluvio::consumer::{
+        AssignmentStrategy, ConsumerConfigExt, ConsumerStream, OffsetManagementStrategy,
+    };
 
     use fluvio::consumer::Record;
     use fluvio_spu_schema::Isolation;
@@ -108,8 +110,9 @@ mod cmd {
         /// Provide a template string to print records with a custom format.
         /// See --help for details.
         ///
-        /// Template strings may include the variables {{key}}, {{value}}, {{offset}}, {{partition}} and {{time}}
-        /// which will have each record's contents substituted in their place.
+        /// Template strings may include the variables {{key}}, {{value}}, {{offset}}, {{partition}}, {{time}}
+        /// and {{headers}} which will have each record's contents substituted in their place.
+        /// A single header value can be printed with {{header.<name>}}.
         /// Note that timestamp is displayed using RFC3339, is always UTC and ignores system timezone.
         ///
         /// For example, the following template string:
@@ -142,6 +145,14 @@ mod cmd {
         #[arg(long, value_name = "integer", conflicts_with_all = &["beginning", "head", "tail"])]
         pub start: Option<u32>,
 
+        /// Consume records produced within <time> before now, e.g. 1h, 30m
+        #[arg(long, value_name = "time", value_parser = humantime::parse_duration, conflicts_with_all = &["beginning", "head", "tail", "start", "start_time"])]
+        pub since: Option<Duration>,
+
+        /// Consume records produced at or after <time> in RFC3339 format (UTC), e.g. 2024-01-31T10:00:00Z
+        #[arg(long, value_name = "time", value_parser = humantime::parse_rfc3339_weak, conflicts_with_all = &["beginning", "head", "tail", "start", "since"])]
+        pub start_time: Option<SystemTime>,
+
         /// Consume records until end offset (inclusive)
         #[arg(long, value_name = "integer")]
         pub end: Option<u32>,
@@ -227,6 +238,15 @@ mod cmd {
         /// Consumer id
         #[arg(short, long)]
         pub consumer: Option<String>,
+
+        /// Consumer group to join, partitions are assigned by group coordinator.
+        /// Offsets are saved with group name as consumer id, unless consumer id is set
+        #[arg(short = 'G', long, conflicts_with_all = &["partition", "all_partitions", "mirror"])]
+        pub group: Option<String>,
+
+        /// Assignment strategy of consumer group: range, round-robin or sticky
+        #[arg(long, value_name = "strategy", requires = "group")]
+        pub group_strategy: Option<AssignmentStrategy>,
     }
 
     #[async_trait]
@@ -321,6 +341,15 @@ mod cmd {
                 builder.offset_flush(DEFAULT_OFFSET_FLUSH_INTERVAL);
             }
 
+            if let Some(group) = &self.group {
+                builder.group(group.clone());
+                builder.offset_flush(DEFAULT_OFFSET_FLUSH_INTERVAL);
+            }
+
+            if let Some(strategy) = self.group_strategy {
+                builder.group_strategy(strategy);
+            }
+
             if let Some(mirror) = &self.mirror {
                 builder.mirror(mirror.clone());
             }
@@ -391,6 +420,15 @@ mod cmd {
             debug!("consume config: {:#?}", consume_config);
 
             self.print_status();
+            if self.group.is_some() {
+                let mut stream = fluvio.consumer_group(consume_config).await?;
+                self.consume_records_stream(&mut stream, stop_signal, tableformat)
+                    .await?;
+                stream.offset_commit().await?;
+                stream.offset_flush().await?;
+                return Ok(());
+            }
+
             let mut stream = fluvio.consumer_with_config(consume_config).await?;
             self.consume_records_stream(&mut stream, stop_signal, tableformat)
                 .await?;
@@ -666,12 +704,34 @@ mod cmd {
                         )
                     };
 
+                    let headers = record
+                        .headers()
+                        .iter()
+                        .map(|header| {
+                            format!("{}={}", header.key, header.value.as_utf8_lossy_string())
+                        })
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    let header_values: serde_json::Map<String, serde_json::Value> = record
+                        .headers()
+                        .iter()
+                        .rev() // first header wins for duplicated keys
+                        .map(|header| {
+                            (
+                                header.key.clone(),
+                                header.value.as_utf8_lossy_string().into(),
+                            )
+                        })
+                        .collect();
+
                     let object = serde_json::json!({
                         "key": formatted_key,
                         "value": value,
                         "offset": record.offset(),
                         "partition": record.partition(),
                         "time": timestamp_rfc3339,
+                        "headers": headers,
+                        "header": header_values,
                     });
                     templates.render(USER_TEMPLATE, &object).ok()
                 }
@@ -714,6 +774,16 @@ mod cmd {
                 format!(" starting at offset {offset}")
             } else if let Some(offset) = self.tail {
                 format!(" starting {offset} from the end of log")
+            } else if let Some(since) = self.since {
+                format!(
+                    " produced in the last {}",
+                    humantime::format_duration(since)
+                )
+            } else if let Some(start_time) = self.start_time {
+                format!(
+                    " starting at time {}",
+                    humantime::format_rfc3339_seconds(start_time)
+                )
             } else {
                 "".to_string()
             };
@@ -773,6 +843,10 @@ mod cmd {
                 Offset::absolute(offset as i64).unwrap()
             } else if let Some(offset) = self.tail {
                 Offset::from_end(offset)
+            } else if let Some(since) = self.since {
+                Offset::from_timestamp(timestamp_millis(SystemTime::now() - since))
+            } else if let Some(start_time) = self.start_time {
+                Offset::from_timestamp(timestamp_millis(start_time))
             } else {
                 Offset::end()
             };
@@ -781,6 +855,13 @@ mod cmd {
         }
     }
 
+    /// milliseconds since Unix epoch, same unit as record timestamp
+    fn timestamp_millis(time: SystemTime) -> i64 {
+        time.duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or_default()
+    }
+
     // Uses clap::ArgEnum to choose possible variables
     #[derive(ValueEnum, Debug, Clone, Eq, PartialEq)]
     #[allow(non_camel_case_types)]
@@ -802,6 +883,8 @@ mod cmd {
     }
     #[cfg(test)]
     mod tests {
+        use std::time::{Duration, SystemTime};
+
         use fluvio::Offset;
 
         use super::ConsumeOpt;
@@ -820,6 +903,8 @@ mod cmd {
                 start: Default::default(),
                 head: Default::default(),
                 tail: Default::default(),
+                since: Default::default(),
+                start_time: Default::default(),
                 end: Default::default(),
                 max_bytes: Default::default(),
                 suppress_unknown: Default::default(),
@@ -834,6 +919,8 @@ mod cmd {
                 transforms_line: Default::default(),
                 truncate: Default::default(),
                 consumer: Default::default(),
+                group: Default::default(),
+                group_strategy: Default::default(),
             }
         }
         #[test]
@@ -887,6 +974,22 @@ mod cmd {
                 "Consuming records from 'TOPIC_NAME' starting 1 from the end of log until offset 2 (inclusive)",
             );
 
+            // --start-time
+            let mut opt = get_opt();
+            opt.start_time = Some(humantime::parse_rfc3339("2024-01-31T10:00:00Z").unwrap());
+            assert_eq!(
+                opt.format_status_string(),
+                "Consuming records from 'TOPIC_NAME' starting at time 2024-01-31T10:00:00Z",
+            );
+
+            // --since
+            let mut opt = get_opt();
+            opt.since = Some(Duration::from_secs(3600));
+            assert_eq!(
+                opt.format_status_string(),
+                "Consuming records from 'TOPIC_NAME' produced in the last 1h",
+            );
+
             // base case
             let mut opt = get_opt();
             assert_eq!(
@@ -930,6 +1033,23 @@ mod cmd {
             opt.start = Some(1);
             let offset = opt.calculate_offset().unwrap();
             assert_eq!(offset, Offset::absolute(1).unwrap());
+
+            // --start-time
+            let mut opt = get_opt();
+            opt.start_time = Some(humantime::parse_rfc3339("2024-01-31T10:00:00Z").unwrap());
+            let offset = opt.calculate_offset().unwrap();
+            assert_eq!(offset, Offset::from_timestamp(1_706_695_200_000));
+
+            // --since
+            let mut opt = get_opt();
+            opt.since = Some(Duration::from_secs(3600));
+            let before = SystemTime::now() - Duration::from_secs(3600);
+            let offset = opt.calculate_offset().unwrap();
+            let after = SystemTime::now() - Duration::from_secs(3600);
+            assert!(
+                (super::timestamp_millis(before)..=super::timestamp_millis(after))
+                    .any(|timestamp| offset == Offset::from_timestamp(timestamp))
+            );
         }
     }
 }
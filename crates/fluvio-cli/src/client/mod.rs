@@ -4,6 +4,9 @@ mod hub;
 mod produce;
 mod partition;
 mod tableformat;
+mod schema;
+mod policy;
+mod quota;
 mod smartmodule;
 mod smartmodule_invocation;
 mod consumer;
@@ -57,6 +60,9 @@ mod cmd {
     use super::topic::TopicCmd;
     use super::partition::PartitionCmd;
     use super::tableformat::TableFormatCmd;
+    use super::schema::SchemaCmd;
+    use super::policy::PolicyCmd;
+    use super::quota::QuotaCmd;
     use super::hub::HubCmd;
 
     #[async_trait]
@@ -137,6 +143,27 @@ mod cmd {
         #[command(subcommand, name = "table-format", visible_alias = "tf")]
         TableFormat(TableFormatCmd),
 
+        /// Manage schemas of records
+        ///
+        /// Schemas are versioned Avro, JSON Schema or Protobuf definitions.
+        /// Topics can require records to be produced with a registered schema.
+        #[command(subcommand, name = "schema")]
+        Schema(SchemaCmd),
+
+        /// Manage access policies
+        ///
+        /// Policies define roles with permissions on objects matching name patterns,
+        /// and bind roles to principals of client certificates.
+        #[command(subcommand, name = "policy")]
+        Policy(PolicyCmd),
+
+        /// Manage throughput quotas
+        ///
+        /// Quotas limit produce and consume byte rates and request rates
+        /// of a client id, principal or topic. SPUs throttle clients exceeding them.
+        #[command(subcommand, name = "quota")]
+        Quota(QuotaCmd),
+
         /// Work with the SmartModule Hub
         #[command(subcommand, name = "hub")]
         Hub(HubCmd),
@@ -180,6 +207,15 @@ mod cmd {
                 Self::TableFormat(tableformat) => {
                     tableformat.process(out, target).await?;
                 }
+                Self::Schema(schema) => {
+                    schema.process(out, target).await?;
+                }
+                Self::Policy(policy) => {
+                    policy.process(out, target).await?;
+                }
+                Self::Quota(quota) => {
+                    quota.process(out, target).await?;
+                }
                 Self::Hub(hub) => {
                     hub.process(out, target).await?;
                 }
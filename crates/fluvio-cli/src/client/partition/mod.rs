@@ -1,4 +1,5 @@
 mod list;
+mod reassign;
 
 pub use cmd::PartitionCmd;
 
@@ -18,6 +19,7 @@ mod cmd {
     use crate::common::FluvioExtensionMetadata;
 
     use super::list::ListPartitionOpt;
+    use super::reassign::ReassignPartitionOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "partition", about = "Partition operations")]
@@ -28,6 +30,13 @@ mod cmd {
             help_template = crate::common::COMMAND_TEMPLATE,
         )]
         List(ListPartitionOpt),
+
+        /// Move replicas of a Partition to other SPUs
+        #[command(
+            name = "reassign",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        Reassign(ReassignPartitionOpt),
     }
 
     #[async_trait]
@@ -41,6 +50,9 @@ mod cmd {
                 Self::List(list) => {
                     list.process(out, fluvio).await?;
                 }
+                Self::Reassign(reassign) => {
+                    reassign.process(fluvio).await?;
+                }
             }
 
             Ok(())
@@ -0,0 +1,51 @@
+//!
+//! # Reassign replicas of a Topic partition
+//!
+//! CLI tree to move partition replicas to other SPUs.
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio_sc_schema::topic::{ReassignPartition, TopicSpec, UpdateTopicAction};
+use fluvio_types::SpuId;
+use fluvio::Fluvio;
+
+/// Option for Reassigning Partition
+#[derive(Debug, Parser)]
+pub struct ReassignPartitionOpt {
+    /// Topic name
+    topic: String,
+    /// Partition to reassign
+    #[arg(short, long, default_value = "0")]
+    partition: u32,
+    /// SPUs to move replicas to, first one is preferred leader
+    #[arg(long, value_name = "spu ids", value_delimiter = ',', required = true)]
+    replicas: Vec<SpuId>,
+    /// Max bytes per second replicated to each new replica while it catches up, unlimited if 0
+    #[arg(long, value_name = "bytes", default_value = "0")]
+    throttle_bytes_per_sec: u64,
+}
+
+impl ReassignPartitionOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+
+        let request = ReassignPartition {
+            partition: self.partition,
+            replicas: self.replicas.clone(),
+            throttle_bytes_per_sec: self.throttle_bytes_per_sec,
+        };
+
+        let action = UpdateTopicAction::ReassignPartition(request);
+        admin
+            .update::<TopicSpec>(self.topic.clone(), action)
+            .await?;
+
+        println!(
+            "reassigning topic: \"{}\" partition: {} to replicas: {:?}",
+            self.topic, self.partition, self.replicas
+        );
+
+        Ok(())
+    }
+}
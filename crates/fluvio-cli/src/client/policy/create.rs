@@ -0,0 +1,43 @@
+//!
+//! # Create Policy
+//!
+//! CLI tree to create access policy from file
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+
+use super::read_policy_spec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreatePolicyOpt {
+    /// The name of the policy
+    name: String,
+
+    /// Path to the policy with roles and bindings in JSON format
+    #[arg(short, long)]
+    file: PathBuf,
+}
+
+impl CreatePolicyOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let spec = read_policy_spec(&self.file)?;
+
+        debug!("creating policy: {} spec: {:#?}", self.name, spec);
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!("policy \"{}\" created", self.name);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+//!
+//! # Delete Policy
+//!
+//! CLI tree to delete access policy
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::policy::PolicySpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DeletePolicyOpt {
+    /// The name of the policy to delete
+    name: String,
+}
+
+impl DeletePolicyOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.delete::<PolicySpec>(&self.name).await?;
+        println!("policy \"{}\" deleted", self.name);
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+//! # List Policies CLI
+//!
+//! CLI tree and processing to list access policies
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::policy::PolicySpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListPoliciesOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListPoliciesOpt {
+    /// Process list policies cli request
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let lists = admin.all::<PolicySpec>().await?;
+
+        output::policies_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio SC - output processing
+    //!
+
+    use comfy_table::{Row, Cell};
+    use comfy_table::CellAlignment;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::policy::PolicySpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListPolicies(Vec<Metadata<PolicySpec>>);
+
+    // -----------------------------------
+    // Format Output
+    // -----------------------------------
+
+    /// Format Policy list
+    pub fn policies_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_policies: Vec<Metadata<PolicySpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("policies: {:#?}", list_policies);
+
+        if !list_policies.is_empty() {
+            let policies = ListPolicies(list_policies);
+            out.render_list(&policies, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no policies");
+            Ok(())
+        }
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for ListPolicies {
+        /// policy header implementation
+        fn header(&self) -> Row {
+            Row::from(["NAME", "ROLES", "BINDINGS"])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    let spec = &r.spec;
+                    let roles = spec
+                        .roles
+                        .iter()
+                        .map(|role| role.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let bindings = spec
+                        .bindings
+                        .iter()
+                        .map(|binding| {
+                            format!("{}: {}", binding.principal, binding.roles.join(", "))
+                        })
+                        .collect::<Vec<_>>()
+                        .join("; ");
+
+                    Row::from([
+                        Cell::new(&r.name).set_alignment(CellAlignment::Left),
+                        Cell::new(roles).set_alignment(CellAlignment::Left),
+                        Cell::new(bindings).set_alignment(CellAlignment::Left),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
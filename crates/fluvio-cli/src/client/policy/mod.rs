@@ -0,0 +1,91 @@
+mod create;
+mod update;
+mod delete;
+mod list;
+
+pub use cmd::PolicyCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::create::CreatePolicyOpt;
+    use super::update::UpdatePolicyOpt;
+    use super::delete::DeletePolicyOpt;
+    use super::list::ListPoliciesOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum PolicyCmd {
+        /// Create a new policy from JSON file
+        #[command(
+            name = "create",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Create(CreatePolicyOpt),
+
+        /// Replace roles and bindings of existing policy
+        #[command(
+            name = "update",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Update(UpdatePolicyOpt),
+
+        /// Delete a policy
+        #[command(
+            name = "delete",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Delete(DeletePolicyOpt),
+
+        /// List all policies
+        #[command(
+            name = "list",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        List(ListPoliciesOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for PolicyCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Create(create) => {
+                    create.process(fluvio).await?;
+                }
+                Self::Update(update) => {
+                    update.process(fluvio).await?;
+                }
+                Self::Delete(delete) => {
+                    delete.process(fluvio).await?;
+                }
+                Self::List(list) => {
+                    list.process(out, fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// read policy spec in JSON format
+fn read_policy_spec(
+    path: &std::path::Path,
+) -> anyhow::Result<fluvio::metadata::policy::PolicySpec> {
+    let file = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&file)?)
+}
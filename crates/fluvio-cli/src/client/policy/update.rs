@@ -0,0 +1,43 @@
+//!
+//! # Update Policy
+//!
+//! CLI tree to replace roles and bindings of access policy
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::policy::{PolicySpec, UpdatePolicyAction};
+
+use super::read_policy_spec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct UpdatePolicyOpt {
+    /// The name of the policy
+    name: String,
+
+    /// Path to the policy with roles and bindings in JSON format
+    #[arg(short, long)]
+    file: PathBuf,
+}
+
+impl UpdatePolicyOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let spec = read_policy_spec(&self.file)?;
+
+        let admin = fluvio.admin().await;
+        admin
+            .update::<PolicySpec>(self.name.clone(), UpdatePolicyAction::Replace(spec))
+            .await?;
+        println!("policy \"{}\" updated", self.name);
+
+        Ok(())
+    }
+}
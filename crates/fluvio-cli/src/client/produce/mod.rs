@@ -122,6 +122,16 @@ mod cmd {
         #[arg(long, default_value = "at-least-once")]
         pub delivery_semantic: DeliverySemantic,
 
+        /// Stamp batches with producer id and sequence numbers, so retried batches are not written twice.
+        /// Requires at-least-once delivery semantic.
+        #[arg(long)]
+        pub idempotent: bool,
+
+        /// Id of registered schema to stamp on produced batches.
+        /// Required by topics which enforce a schema.
+        #[arg(long, value_name = "integer")]
+        pub schema_id: Option<u32>,
+
         /// Name of the smartmodule
         #[arg(
             long,
@@ -220,6 +230,10 @@ mod cmd {
             if let Some(isolation) = self.isolation {
                 config_builder.isolation(isolation);
             }
+            // Schema id
+            if let Some(schema_id) = self.schema_id {
+                config_builder.schema_id(schema_id);
+            }
             // Delivery Semantic
             if self.delivery_semantic == DeliverySemantic::AtMostOnce && self.isolation.is_some() {
                 warn!("Isolation is ignored for AtMostOnce delivery semantic");
@@ -281,6 +295,7 @@ mod cmd {
 
             let config = config_builder
                 .delivery_semantic(self.delivery_semantic)
+                .idempotent(self.idempotent)
                 .build()
                 .map_err(FluvioError::from)?;
 
@@ -0,0 +1,42 @@
+//!
+//! # Create Quota
+//!
+//! CLI tree to create throughput quota
+//!
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::{anyhow, Result};
+
+use fluvio::Fluvio;
+use fluvio::metadata::quota::QuotaSpec;
+
+use super::spec::QuotaSpecOpt;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateQuotaOpt {
+    /// The name of the quota
+    name: String,
+
+    #[clap(flatten)]
+    spec: QuotaSpecOpt,
+}
+
+impl CreateQuotaOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let spec = QuotaSpec::from(self.spec);
+        spec.validate().map_err(|err| anyhow!(err))?;
+
+        debug!("creating quota: {} spec: {:#?}", self.name, spec);
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!("quota \"{}\" created", self.name);
+
+        Ok(())
+    }
+}
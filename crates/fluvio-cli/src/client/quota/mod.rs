@@ -0,0 +1,147 @@
+mod create;
+mod update;
+mod delete;
+mod list;
+
+pub use cmd::QuotaCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::create::CreateQuotaOpt;
+    use super::update::UpdateQuotaOpt;
+    use super::delete::DeleteQuotaOpt;
+    use super::list::ListQuotasOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum QuotaCmd {
+        /// Create a new quota
+        #[command(
+            name = "create",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Create(CreateQuotaOpt),
+
+        /// Replace entity and rates of existing quota
+        #[command(
+            name = "update",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Update(UpdateQuotaOpt),
+
+        /// Delete a quota
+        #[command(
+            name = "delete",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Delete(DeleteQuotaOpt),
+
+        /// List all quotas
+        #[command(
+            name = "list",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        List(ListQuotasOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for QuotaCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Create(create) => {
+                    create.process(fluvio).await?;
+                }
+                Self::Update(update) => {
+                    update.process(fluvio).await?;
+                }
+                Self::Delete(delete) => {
+                    delete.process(fluvio).await?;
+                }
+                Self::List(list) => {
+                    list.process(out, fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+mod spec {
+
+    use clap::Args;
+
+    use fluvio::metadata::quota::{QuotaEntity, QuotaSpec};
+
+    /// Traffic to which quota applies and its limits
+    #[derive(Debug, Args)]
+    pub struct QuotaSpecOpt {
+        #[clap(flatten)]
+        entity: QuotaEntityOpt,
+
+        /// Bytes per second written by producers
+        #[arg(long, value_name = "bytes")]
+        produce_byte_rate: Option<u64>,
+
+        /// Bytes per second read by consumers
+        #[arg(long, value_name = "bytes")]
+        consume_byte_rate: Option<u64>,
+
+        /// Produce and fetch requests per second
+        #[arg(long, value_name = "requests")]
+        request_rate: Option<u64>,
+    }
+
+    #[derive(Debug, Args)]
+    #[group(required = true, multiple = false)]
+    struct QuotaEntityOpt {
+        /// Limit all connections using client id
+        #[arg(long)]
+        client_id: Option<String>,
+
+        /// Limit identities with principal
+        #[arg(long)]
+        principal: Option<String>,
+
+        /// Limit all traffic of topic
+        #[arg(long)]
+        topic: Option<String>,
+    }
+
+    impl From<QuotaSpecOpt> for QuotaSpec {
+        fn from(opt: QuotaSpecOpt) -> Self {
+            let QuotaEntityOpt {
+                client_id,
+                principal,
+                topic,
+            } = opt.entity;
+            let entity = match (client_id, principal, topic) {
+                (Some(client_id), _, _) => QuotaEntity::ClientId(client_id),
+                (_, Some(principal), _) => QuotaEntity::Principal(principal),
+                (_, _, Some(topic)) => QuotaEntity::Topic(topic),
+                _ => QuotaEntity::default(),
+            };
+            Self {
+                entity,
+                produce_byte_rate: opt.produce_byte_rate,
+                consume_byte_rate: opt.consume_byte_rate,
+                request_rate: opt.request_rate,
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//!
+//! # Update Quota
+//!
+//! CLI tree to replace entity and rates of quota
+//!
+
+use clap::Parser;
+use anyhow::{anyhow, Result};
+
+use fluvio::Fluvio;
+use fluvio::metadata::quota::{QuotaSpec, UpdateQuotaAction};
+
+use super::spec::QuotaSpecOpt;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct UpdateQuotaOpt {
+    /// The name of the quota
+    name: String,
+
+    #[clap(flatten)]
+    spec: QuotaSpecOpt,
+}
+
+impl UpdateQuotaOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let spec = QuotaSpec::from(self.spec);
+        spec.validate().map_err(|err| anyhow!(err))?;
+
+        let admin = fluvio.admin().await;
+        admin
+            .update::<QuotaSpec>(self.name.clone(), UpdateQuotaAction::Replace(spec))
+            .await?;
+        println!("quota \"{}\" updated", self.name);
+
+        Ok(())
+    }
+}
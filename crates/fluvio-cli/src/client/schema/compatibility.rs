@@ -0,0 +1,41 @@
+//!
+//! # Set Schema Compatibility
+//!
+//! CLI tree to change compatibility mode of schema subject
+//!
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::schema::{SchemaSpec, CompatibilityMode, UpdateSchemaAction};
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct SetCompatibilityOpt {
+    /// The name of the schema subject
+    name: String,
+
+    /// Compatibility mode: none, backward, forward or full
+    mode: CompatibilityMode,
+}
+
+impl SetCompatibilityOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin
+            .update::<SchemaSpec>(
+                self.name.clone(),
+                UpdateSchemaAction::SetCompatibility(self.mode),
+            )
+            .await?;
+        println!(
+            "schema \"{}\" compatibility set to {}",
+            self.name, self.mode
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+//!
+//! # Create Schema
+//!
+//! CLI tree to create schema subject from definition file
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::schema::{SchemaSpec, SchemaType, CompatibilityMode};
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateSchemaOpt {
+    /// The name of the schema subject
+    name: String,
+
+    /// Type of schema: avro, json or protobuf
+    #[arg(
+        short = 't',
+        long = "type",
+        value_name = "type",
+        default_value = "avro"
+    )]
+    schema_type: SchemaType,
+
+    /// Path to the schema definition
+    #[arg(short, long)]
+    file: PathBuf,
+
+    /// Compatibility required from new versions: none, backward, forward or full
+    #[arg(short, long, default_value = "backward")]
+    compatibility: CompatibilityMode,
+}
+
+impl CreateSchemaOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let definition = std::fs::read_to_string(&self.file)?;
+        let spec = SchemaSpec::new(self.schema_type, self.compatibility, definition);
+
+        debug!("creating schema: {} spec: {:#?}", self.name, spec);
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!("schema \"{}\" created", self.name);
+
+        Ok(())
+    }
+}
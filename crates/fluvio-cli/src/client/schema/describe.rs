@@ -0,0 +1,98 @@
+//!
+//! # Describe Schema CLI
+//!
+//! CLI to show versions of schema subject or definition of single version
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::{anyhow, Result};
+
+use fluvio::Fluvio;
+use fluvio::metadata::schema::SchemaSpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DescribeSchemaOpt {
+    /// The name of the schema subject
+    name: String,
+
+    /// Print definition of this version instead of list of versions
+    #[arg(long, value_name = "integer")]
+    version: Option<u32>,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl DescribeSchemaOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let schema = admin
+            .list::<SchemaSpec, _>(vec![self.name.clone()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("schema \"{}\" not found", self.name))?;
+
+        if let Some(version) = self.version {
+            let version = schema
+                .spec
+                .versions
+                .iter()
+                .find(|v| v.version == version)
+                .ok_or_else(|| anyhow!("schema \"{}\" has no version {version}", self.name))?;
+            println!("{}", version.definition);
+            return Ok(());
+        }
+
+        out.render_list(&output::SchemaVersions(schema.spec), self.output.format)?;
+        Ok(())
+    }
+}
+
+mod output {
+
+    use comfy_table::{Row, Cell};
+    use comfy_table::CellAlignment;
+    use serde::Serialize;
+
+    use fluvio::metadata::schema::SchemaSpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+
+    #[derive(Serialize)]
+    pub(super) struct SchemaVersions(pub(super) SchemaSpec);
+
+    impl TableOutputHandler for SchemaVersions {
+        fn header(&self) -> Row {
+            Row::from(["VERSION", "ID", "TYPE", "COMPATIBILITY"])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn content(&self) -> Vec<Row> {
+            let spec = &self.0;
+            spec.versions
+                .iter()
+                .map(|version| {
+                    Row::from([
+                        Cell::new(version.version).set_alignment(CellAlignment::Right),
+                        Cell::new(version.id).set_alignment(CellAlignment::Right),
+                        Cell::new(spec.schema_type.to_string()).set_alignment(CellAlignment::Left),
+                        Cell::new(spec.compatibility.to_string())
+                            .set_alignment(CellAlignment::Left),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
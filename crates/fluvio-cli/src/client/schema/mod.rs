@@ -0,0 +1,107 @@
+mod create;
+mod register;
+mod compatibility;
+mod delete;
+mod describe;
+mod list;
+
+pub use cmd::SchemaCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::create::CreateSchemaOpt;
+    use super::register::RegisterSchemaOpt;
+    use super::compatibility::SetCompatibilityOpt;
+    use super::delete::DeleteSchemaOpt;
+    use super::describe::DescribeSchemaOpt;
+    use super::list::ListSchemasOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum SchemaCmd {
+        /// Create a new schema subject with its first version
+        #[command(
+            name = "create",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Create(CreateSchemaOpt),
+
+        /// Register new version of existing schema subject
+        #[command(
+            name = "register",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Register(RegisterSchemaOpt),
+
+        /// Change compatibility mode of schema subject
+        #[command(
+            name = "set-compatibility",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        SetCompatibility(SetCompatibilityOpt),
+
+        /// Delete a schema subject with all its versions
+        #[command(
+            name = "delete",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Delete(DeleteSchemaOpt),
+
+        /// Show versions of a schema subject
+        #[command(
+            name = "describe",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Describe(DescribeSchemaOpt),
+
+        /// List all schema subjects
+        #[command(
+            name = "list",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        List(ListSchemasOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for SchemaCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Create(create) => {
+                    create.process(fluvio).await?;
+                }
+                Self::Register(register) => {
+                    register.process(fluvio).await?;
+                }
+                Self::SetCompatibility(compatibility) => {
+                    compatibility.process(fluvio).await?;
+                }
+                Self::Delete(delete) => {
+                    delete.process(fluvio).await?;
+                }
+                Self::Describe(describe) => {
+                    describe.process(out, fluvio).await?;
+                }
+                Self::List(list) => {
+                    list.process(out, fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//!
+//! # Register Schema Version
+//!
+//! CLI tree to register new version of schema subject
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::schema::{SchemaSpec, UpdateSchemaAction};
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct RegisterSchemaOpt {
+    /// The name of the schema subject
+    name: String,
+
+    /// Path to the schema definition, must be compatible with latest version of subject
+    #[arg(short, long)]
+    file: PathBuf,
+}
+
+impl RegisterSchemaOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let definition = std::fs::read_to_string(&self.file)?;
+
+        let admin = fluvio.admin().await;
+        admin
+            .update::<SchemaSpec>(self.name.clone(), UpdateSchemaAction::Register(definition))
+            .await?;
+
+        let latest = admin
+            .list::<SchemaSpec, _>(vec![self.name.clone()])
+            .await?
+            .into_iter()
+            .next()
+            .and_then(|schema| schema.spec.latest().cloned());
+        match latest {
+            Some(version) => println!(
+                "schema \"{}\" version {} registered with id {}",
+                self.name, version.version, version.id
+            ),
+            None => println!("schema \"{}\" registered", self.name),
+        }
+
+        Ok(())
+    }
+}
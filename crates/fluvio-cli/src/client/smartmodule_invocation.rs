@@ -24,6 +24,8 @@ pub(crate) fn create_smartmodule(
         kind: SmartModuleKind::Generic(ctx),
         params: params.into(),
         name: Some(name.to_string()),
+        error_policy: Default::default(),
+        timeout: None,
     }
 }
 
@@ -50,6 +52,8 @@ pub(crate) fn create_smartmodule_from_path(
         kind: SmartModuleKind::Generic(ctx),
         params: params.into(),
         name: Some(name),
+        error_policy: Default::default(),
+        timeout: None,
     })
 }
 
@@ -77,6 +81,8 @@ pub(crate) fn create_smartmodule_list(
                 t.lookback.map(Into::into),
             ),
             name: Some(name.clone()),
+            error_policy: t.on_error.into(),
+            timeout: t.timeout,
         })
         .collect())
 }
@@ -15,6 +15,7 @@ use anyhow::Result;
 use fluvio_types::PartitionCount;
 use fluvio_types::ReplicationFactor;
 use fluvio::metadata::topic::CleanupPolicy;
+use fluvio::metadata::topic::CompactPolicy;
 use fluvio::metadata::topic::ReplicaSpec;
 use fluvio::metadata::topic::SegmentBasedPolicy;
 use fluvio::metadata::topic::TopicStorageConfig;
@@ -230,10 +231,23 @@ impl CreateTopicOpt {
         };
 
         let mut topic_spec: TopicSpec = replica_spec.into();
-        if let Some(retention) = self.setting.retention_time {
-            topic_spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
+        let segment_policy = self
+            .setting
+            .retention_time
+            .map(|retention| SegmentBasedPolicy {
                 time_in_seconds: retention.as_secs() as u32,
-            }));
+            });
+        if self.setting.compact {
+            let mut policy = CompactPolicy {
+                delete: segment_policy,
+                ..Default::default()
+            };
+            if let Some(tombstone_retention) = self.setting.tombstone_retention {
+                policy.tombstone_retention_secs = tombstone_retention.as_secs() as u32;
+            }
+            topic_spec.set_cleanup_policy(CleanupPolicy::Compact(policy));
+        } else if let Some(segment_policy) = segment_policy {
+            topic_spec.set_cleanup_policy(CleanupPolicy::Segment(segment_policy));
         }
 
         if let Some(compression_type) = self.setting.compression_type {
@@ -262,6 +276,16 @@ impl CreateTopicOpt {
 
         topic_spec.set_system(self.setting.system);
 
+        if let Some(schema) = self.setting.schema {
+            topic_spec.set_schema(Some(schema));
+        }
+
+        if let Some(min_in_sync_replicas) = self.setting.min_in_sync_replicas {
+            topic_spec.set_min_in_sync_replicas(Some(min_in_sync_replicas));
+        }
+
+        topic_spec.set_unclean_leader_election(self.setting.unclean_leader_election);
+
         if self.setting.segment_size.is_some() || self.setting.max_partition_size.is_some() {
             let mut storage = TopicStorageConfig::default();
 
@@ -312,6 +336,17 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "time",value_parser=parse_duration)]
     retention_time: Option<Duration>,
 
+    /// Compact records by key, keeping only the latest record for each key
+    ///
+    /// If retention time is also set, old segments are deleted as well
+    #[arg(long)]
+    compact: bool,
+
+    /// How long records with empty value (tombstones) are kept in compacted topic
+    /// Ex: '1h', '2d 10s', '1 day' (default)
+    #[arg(long, value_name = "time", value_parser=parse_duration, requires = "compact")]
+    tombstone_retention: Option<Duration>,
+
     /// Segment size (by default measured in bytes)
     /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
     #[arg(long, value_name = "bytes")]
@@ -343,6 +378,21 @@ pub struct TopicConfigOpt {
     /// System topics are for internal operations
     #[arg(long, short = 's', hide = true)]
     system: bool,
+
+    /// Schema subject which records produced to the topic must use.
+    /// Batches without id of registered version of the schema are rejected
+    #[arg(long, value_name = "subject")]
+    schema: Option<String>,
+
+    /// Minimum number of in-sync replicas to accept produce waiting for commit.
+    /// Produce with `--isolation read_committed` is rejected when fewer replicas are in sync
+    #[arg(long, value_name = "integer")]
+    min_in_sync_replicas: Option<u16>,
+
+    /// Allow replica which is not in sync to become leader when no in-sync replica is online.
+    /// Committed records which the new leader doesn't have are lost
+    #[arg(long)]
+    unclean_leader_election: bool,
 }
 
 /// module to load partitions maps from file
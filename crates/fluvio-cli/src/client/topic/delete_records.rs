@@ -0,0 +1,46 @@
+//!
+//! # Delete Records of a Topic partition
+//!
+//! CLI tree to move log start offset of a partition forward.
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio_sc_schema::topic::{DeleteRecords, TopicSpec, UpdateTopicAction};
+use fluvio::Fluvio;
+
+/// Option for Deleting Records
+#[derive(Debug, Parser)]
+pub struct DeleteRecordsOpt {
+    /// Topic name
+    topic: String,
+    /// Partition to delete records from
+    #[arg(short, long, default_value = "0")]
+    partition: u32,
+    /// Records before this offset are deleted
+    #[arg(long, value_name = "offset")]
+    before_offset: i64,
+}
+
+impl DeleteRecordsOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+
+        let request = DeleteRecords {
+            partition: self.partition,
+            before_offset: self.before_offset,
+        };
+
+        let action = UpdateTopicAction::DeleteRecords(request);
+        admin
+            .update::<TopicSpec>(self.topic.clone(), action)
+            .await?;
+
+        println!(
+            "deleted records before offset: {} from topic: \"{}\" partition: {}",
+            self.before_offset, self.topic, self.partition
+        );
+
+        Ok(())
+    }
+}
@@ -165,6 +165,24 @@ mod display {
                 ));
             };
 
+            if let Some(schema) = spec.get_schema() {
+                key_values.push(("Schema".to_owned(), Some(schema.clone())));
+            }
+
+            if let Some(min_in_sync_replicas) = spec.get_min_in_sync_replicas() {
+                key_values.push((
+                    "Min In-Sync Replicas".to_owned(),
+                    Some(min_in_sync_replicas.to_string()),
+                ));
+            }
+
+            if spec.is_unclean_leader_election() {
+                key_values.push((
+                    "Unclean Leader Election".to_owned(),
+                    Some("enabled".to_owned()),
+                ));
+            }
+
             key_values.push((
                 "Status".to_owned(),
                 Some(status.resolution.resolution_label().to_string()),
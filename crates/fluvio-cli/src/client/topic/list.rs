@@ -54,7 +54,7 @@ mod display {
     use serde::Serialize;
 
     use fluvio::metadata::objects::Metadata;
-    use fluvio::metadata::topic::TopicSpec;
+    use fluvio::metadata::topic::{CleanupPolicy, TopicSpec};
 
     use crate::common::output::{OutputType, TableOutputHandler, Terminal, OutputError};
     use crate::common::t_println;
@@ -116,9 +116,7 @@ mod display {
                         Cell::new(topic.type_label()),
                         Cell::new(topic.partitions_display()).set_alignment(CellAlignment::Left),
                         Cell::new(topic.replication_factor_display()),
-                        Cell::new(format_duration(Duration::from_secs(
-                            topic.retention_secs() as u64
-                        ))),
+                        Cell::new(retention_display(topic)),
                         Cell::new(topic.get_compression_type()),
                         Cell::new(
                             topic
@@ -133,4 +131,17 @@ mod display {
                 .collect()
         }
     }
+
+    fn retention_display(topic: &TopicSpec) -> String {
+        match topic.get_clean_policy() {
+            Some(CleanupPolicy::Compact(policy)) => match &policy.delete {
+                Some(delete) => format!(
+                    "compact, {}",
+                    format_duration(Duration::from_secs(delete.retention_secs() as u64))
+                ),
+                None => "compact".to_string(),
+            },
+            _ => format_duration(Duration::from_secs(topic.retention_secs() as u64)).to_string(),
+        }
+    }
 }
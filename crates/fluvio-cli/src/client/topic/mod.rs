@@ -4,6 +4,7 @@ mod describe;
 mod list;
 mod add_partition;
 mod add_mirror;
+mod delete_records;
 
 pub use cmd::TopicCmd;
 
@@ -27,6 +28,7 @@ mod cmd {
     use super::add_partition::AddPartitionOpt;
     use super::create::CreateTopicOpt;
     use super::delete::DeleteTopicOpt;
+    use super::delete_records::DeleteRecordsOpt;
     use super::describe::DescribeTopicsOpt;
     use super::list::ListTopicsOpt;
 
@@ -74,6 +76,13 @@ mod cmd {
             help_template = COMMAND_TEMPLATE,
         )]
         AddMirror(AddMirrorOpt),
+
+        /// Delete records of a Partition before an offset
+        #[command(
+            name = "delete-records",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        DeleteRecords(DeleteRecordsOpt),
     }
 
     #[async_trait]
@@ -102,6 +111,9 @@ mod cmd {
                 Self::AddMirror(add_mirror) => {
                     add_mirror.process(fluvio).await?;
                 }
+                Self::DeleteRecords(delete_records) => {
+                    delete_records.process(fluvio).await?;
+                }
             }
 
             Ok(())
@@ -69,6 +69,13 @@ pub async fn consumer_stream_from_config(
     if let Some(max_bytes) = config.meta().consumer().and_then(|c| c.max_bytes) {
         builder.max_bytes(max_bytes.as_u64() as i32);
     }
+    if let Some(dead_letter_topic) = config
+        .meta()
+        .consumer()
+        .and_then(|c| c.dead_letter_topic.as_ref())
+    {
+        builder.dead_letter_topic(dead_letter_topic);
+    }
     if let Some(smartmodules) = smartmodule_vec_from_config(config) {
         builder.smartmodule(smartmodules);
     }
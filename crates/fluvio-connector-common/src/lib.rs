@@ -13,7 +13,7 @@ pub use fluvio_connector_derive::connector;
 use fluvio::{Offset, metadata::topic::TopicSpec};
 use futures::stream::LocalBoxStream;
 use async_trait::async_trait;
-use ::tracing::{info, error};
+use ::tracing::{info, error, warn};
 
 pub type Error = anyhow::Error;
 pub type Result<T> = std::result::Result<T, Error>;
@@ -41,31 +41,39 @@ pub trait Sink<I> {
     async fn connect(self, offset: Option<Offset>) -> Result<LocalBoxSink<I>>;
 }
 
+/// Create the connector topic with settings of the connector config, or, if topic exists,
+/// report settings of the topic which differ from the config.
 pub async fn ensure_topic_exists(config: &config::ConnectorConfig) -> Result<()> {
     let topic = config.meta().topic().to_string();
     let admin = fluvio::FluvioAdmin::connect().await?;
     let topics = admin.list::<TopicSpec, String>(vec![topic.clone()]).await?;
-    let topic_exists = topics.iter().any(|t| t.name.eq(&topic));
-    if !topic_exists {
-        match admin
-            .create(
-                topic.to_owned(),
-                false,
-                config
-                    .meta()
-                    .topic_config()
-                    .cloned()
-                    .map(TopicSpec::from)
-                    .unwrap_or(TopicSpec::new_computed(1, 1, Some(false))),
-            )
-            .await
-        {
-            Ok(_) => info!(topic, "successfully created"),
-            Err(err) => {
-                error!("unable to create topic {topic}: {err}");
-                return Err(err);
+    if let Some(existing) = topics.into_iter().find(|t| t.name.eq(&topic)) {
+        if let Some(topic_config) = config.meta().topic_config() {
+            for drift in topic_config.drift(&existing.spec) {
+                warn!(topic, %drift, "topic differs from connector config");
             }
         }
+        return Ok(());
+    }
+
+    match admin
+        .create(
+            topic.to_owned(),
+            false,
+            config
+                .meta()
+                .topic_config()
+                .cloned()
+                .map(TopicSpec::from)
+                .unwrap_or(TopicSpec::new_computed(1, 1, Some(false))),
+        )
+        .await
+    {
+        Ok(_) => info!(topic, "successfully created"),
+        Err(err) => {
+            error!("unable to create topic {topic}: {err}");
+            return Err(err);
+        }
     }
     Ok(())
 }
@@ -33,6 +33,17 @@ pub async fn producer_from_config(config: &ConnectorConfig) -> Result<(Fluvio, T
         .topic_producer_with_config(config.meta().topic(), producer_config)
         .await?;
 
+    let producer = match config
+        .meta()
+        .producer()
+        .and_then(|p| p.dead_letter_topic.as_ref())
+    {
+        Some(dead_letter_topic) => {
+            producer.with_dead_letter(fluvio.topic_producer(dead_letter_topic).await?)
+        }
+        None => producer,
+    };
+
     if let Some(chain) = smartmodule_chain_from_config(config).await? {
         Ok((fluvio, producer.with_chain(chain).await?))
     } else {
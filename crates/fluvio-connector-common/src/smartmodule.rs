@@ -43,17 +43,23 @@ pub fn smartmodule_vec_from_config(config: &ConnectorConfig) -> Option<Vec<Smart
     Some(
         transforms
             .iter()
-            .map(|s| SmartModuleInvocation {
-                wasm: fluvio::SmartModuleInvocationWasm::Predefined(s.uses.clone()),
-                kind: SmartModuleKind::Generic(Default::default()),
-                params: SmartModuleExtraParams::new(
+            .map(|s| {
+                let mut params = SmartModuleExtraParams::new(
                     s.with
                         .iter()
                         .map(|(k, v)| (k.clone(), v.clone().into()))
                         .collect::<std::collections::BTreeMap<String, String>>(),
                     s.lookback.map(Into::into),
-                ),
-                name: Some(s.uses.clone()),
+                );
+                params.set_window(s.window.map(Into::into));
+                SmartModuleInvocation {
+                    wasm: fluvio::SmartModuleInvocationWasm::Predefined(s.uses.clone()),
+                    kind: SmartModuleKind::Generic(Default::default()),
+                    params,
+                    name: Some(s.uses.clone()),
+                    error_policy: s.on_error.into(),
+                    timeout: s.timeout,
+                }
             })
             .collect(),
     )
@@ -65,7 +71,7 @@ mod tests {
 
     use fluvio::SmartModuleInvocationWasm;
     use fluvio_connector_package::config::ConnectorConfigV1;
-    use fluvio_smartengine::transformation::{TransformationStep, Lookback};
+    use fluvio_smartengine::transformation::{TransformationStep, Lookback, Window, WindowKind};
 
     use super::*;
 
@@ -80,6 +86,12 @@ mod tests {
                     last: 2,
                     age: Some(Duration::from_secs(10)),
                 }),
+                window: Some(Window {
+                    kind: WindowKind::Tumbling {
+                        size: Duration::from_secs(60),
+                    },
+                    grace: None,
+                }),
                 ..Default::default()
             }],
         });
@@ -103,5 +115,9 @@ mod tests {
             inv.params.lookback().unwrap().age,
             Some(Duration::from_secs(10))
         );
+        assert_eq!(
+            inv.params.window(),
+            Some(&fluvio::Window::tumbling(Duration::from_secs(60)))
+        );
     }
 }
@@ -1,4 +1,5 @@
 mod local;
+mod supervisor;
 
 use std::path::PathBuf;
 
@@ -8,6 +9,7 @@ use derive_builder::Builder;
 use fluvio_connector_package::metadata::ConnectorMetadata;
 
 pub use local::LogLevel;
+pub use supervisor::{Restart, RestartPolicy, SupervisedState, SupervisorStatus};
 
 #[derive(Clone)]
 pub enum DeploymentType {
@@ -17,6 +19,18 @@ pub enum DeploymentType {
         // Some(path) if a tmp dir for ipkg should be cleaned up on shutdown
         tmp_dir: Option<PathBuf>,
     },
+    /// Run connector in the current process under supervisor, which restarts it by
+    /// the policy and reports its status into `status_dir`. Returns once connector is
+    /// not restarted anymore.
+    Supervised {
+        output_file: Option<PathBuf>,
+
+        // Some(path) if a tmp dir for ipkg should be cleaned up on shutdown
+        tmp_dir: Option<PathBuf>,
+
+        status_dir: PathBuf,
+        restart_policy: RestartPolicy,
+    },
 }
 
 /// Describe deployment configuration
@@ -46,6 +60,13 @@ pub enum DeploymentResult {
         log_file: Option<PathBuf>,
         tmp_dir: Option<PathBuf>,
     },
+    Supervised {
+        name: String,
+        log_file: Option<PathBuf>,
+        tmp_dir: Option<PathBuf>,
+        status_dir: PathBuf,
+        status: SupervisorStatus,
+    },
 }
 
 impl DeploymentBuilder {
@@ -83,6 +104,28 @@ impl DeploymentBuilder {
                     tmp_dir,
                 })
             }
+            DeploymentType::Supervised {
+                output_file,
+                tmp_dir,
+                status_dir,
+                restart_policy,
+            } => {
+                let name = config.meta().name().to_owned();
+                let status = supervisor::supervise(
+                    &deployment,
+                    output_file.as_ref(),
+                    status_dir,
+                    restart_policy,
+                    &name,
+                )?;
+                Ok(DeploymentResult::Supervised {
+                    name,
+                    log_file: output_file.clone(),
+                    tmp_dir: tmp_dir.clone(),
+                    status_dir: status_dir.clone(),
+                    status,
+                })
+            }
         }
     }
 }
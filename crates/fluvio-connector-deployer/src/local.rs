@@ -34,6 +34,21 @@ pub(crate) fn deploy_local<P: AsRef<Path>>(
         (Stdio::inherit(), Stdio::inherit(), true)
     };
 
+    let mut cmd = command(deployment)?;
+    cmd.stdin(Stdio::null());
+    cmd.stdout(stdout);
+    cmd.stderr(stderr);
+    let mut child = cmd.spawn()?;
+    println!("Connector runs with process id: {}", child.id());
+    println!("Started connector `{}`", name);
+    if wait {
+        child.wait()?;
+    }
+    Ok(child.id())
+}
+
+/// command running connector executable with config and secrets of the deployment
+pub(crate) fn command(deployment: &Deployment) -> Result<Command> {
     let executable = canonicalize(&deployment.executable).context(format!(
         "Executable file path ({}) is invalid or file does not exist",
         deployment.executable.to_string_lossy()
@@ -42,9 +57,6 @@ pub(crate) fn deploy_local<P: AsRef<Path>>(
     let mut cmd = Command::new(executable);
 
     cmd.env("RUST_LOG", deployment.log_level.to_string());
-    cmd.stdin(Stdio::null());
-    cmd.stdout(stdout);
-    cmd.stderr(stderr);
     cmd.arg("--config");
     cmd.arg(
         canonicalize(&deployment.config)
@@ -56,11 +68,5 @@ pub(crate) fn deploy_local<P: AsRef<Path>>(
             canonicalize(secrets).context("Secrets file path is invalid or file does not exist")?,
         );
     }
-    let mut child = cmd.spawn()?;
-    println!("Connector runs with process id: {}", child.id());
-    println!("Started connector `{}`", name);
-    if wait {
-        child.wait()?;
-    }
-    Ok(child.id())
+    Ok(cmd)
 }
@@ -0,0 +1,386 @@
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use enum_display::EnumDisplay;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::Deployment;
+use crate::local::command;
+
+const STATUS_FILE_NAME: &str = "status.json";
+const METRICS_SOCKET_FILE_NAME: &str = "metrics.sock";
+/// how often supervisor checks whether connector process is still alive
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// how often connector metrics are collected into the status
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const METRICS_READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// When supervisor restarts the connector process
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default, EnumDisplay)]
+#[clap(rename_all = "kebab-case")]
+#[enum_display(case = "Kebab")]
+pub enum Restart {
+    /// Never restart, only report status
+    Never,
+    /// Restart when connector exits with an error
+    #[default]
+    OnFailure,
+    /// Restart whenever connector exits
+    Always,
+}
+
+/// Restart policy of a supervised connector.
+///
+/// Restarts are delayed by exponential backoff, starting at `min_backoff` and doubling on
+/// each consecutive crash up to `max_backoff`. Connector which ran for at least `max_backoff`
+/// before exiting is considered to have recovered, and the backoff starts over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestartPolicy {
+    pub restart: Restart,
+    /// give up after this many restarts, unlimited if None
+    pub max_restarts: Option<u32>,
+    pub min_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            restart: Restart::default(),
+            max_restarts: None,
+            min_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// whether connector which exited should be started again, after `restarts` restarts
+    pub fn should_restart(&self, success: bool, restarts: u32) -> bool {
+        let restart = match self.restart {
+            Restart::Never => false,
+            Restart::OnFailure => !success,
+            Restart::Always => true,
+        };
+        restart && self.max_restarts.is_none_or(|max| restarts < max)
+    }
+
+    /// delay before restart, after `crashes` consecutive crashes
+    pub fn backoff(&self, crashes: u32) -> Duration {
+        self.min_backoff
+            .saturating_mul(2u32.saturating_pow(crashes.saturating_sub(1)))
+            .min(self.max_backoff)
+    }
+}
+
+/// State of a supervised connector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumDisplay)]
+#[serde(rename_all = "snake_case")]
+pub enum SupervisedState {
+    Starting,
+    Running,
+    /// waiting before connector is restarted
+    Backoff,
+    /// connector exited successfully and is not restarted
+    Stopped,
+    /// connector exited with an error and is not restarted
+    Failed,
+}
+
+impl SupervisedState {
+    /// supervisor no longer runs in this state
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Stopped | Self::Failed)
+    }
+}
+
+/// Status of a supervised connector, kept up to date by supervisor in its status directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SupervisorStatus {
+    pub name: String,
+    pub state: SupervisedState,
+    /// process id of the supervisor
+    pub supervisor_id: u32,
+    /// process id of the connector, while it runs
+    pub process_id: Option<u32>,
+    pub restarts: u32,
+    /// exit status of the last connector process
+    pub last_exit: Option<String>,
+    /// whether connector answered the last health check
+    pub healthy: bool,
+    /// last update, in milliseconds since epoch
+    pub updated_at: u64,
+    /// metrics reported by connector on the last health check
+    pub metrics: Option<serde_json::Value>,
+}
+
+impl SupervisorStatus {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            state: SupervisedState::Starting,
+            supervisor_id: std::process::id(),
+            process_id: None,
+            restarts: 0,
+            last_exit: None,
+            healthy: false,
+            updated_at: 0,
+            metrics: None,
+        }
+    }
+
+    /// read status written by supervisor into `status_dir`
+    pub fn load(status_dir: &Path) -> Result<Self> {
+        let path = status_dir.join(STATUS_FILE_NAME);
+        let content = std::fs::read(&path)
+            .with_context(|| format!("Could not read connector status {}", path.display()))?;
+        Ok(serde_json::from_slice(&content)?)
+    }
+
+    fn store(&mut self, status_dir: &Path) -> Result<()> {
+        self.updated_at = now_millis();
+        // write whole file at once, so readers never see partial status
+        let tmp_path = status_dir.join(format!("{STATUS_FILE_NAME}.tmp"));
+        std::fs::write(&tmp_path, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(tmp_path, status_dir.join(STATUS_FILE_NAME))?;
+        Ok(())
+    }
+}
+
+/// Run connector and restart it according to the policy, until it is not restarted anymore.
+/// Connector output is appended to `output_file`, so logs of crashed runs are kept.
+pub(crate) fn supervise(
+    deployment: &Deployment,
+    output_file: Option<&PathBuf>,
+    status_dir: &Path,
+    policy: &RestartPolicy,
+    name: &str,
+) -> Result<SupervisorStatus> {
+    std::fs::create_dir_all(status_dir).with_context(|| {
+        format!(
+            "Could not create connector status dir {}",
+            status_dir.display()
+        )
+    })?;
+    let metrics_socket = status_dir.join(METRICS_SOCKET_FILE_NAME);
+    let mut status = SupervisorStatus::new(name);
+    let mut crashes = 0;
+
+    loop {
+        status.state = SupervisedState::Starting;
+        status.store(status_dir)?;
+
+        let mut cmd = command(deployment)?;
+        cmd.env("FLUVIO_METRIC_CONNECTOR", &metrics_socket);
+        cmd.stdin(Stdio::null());
+        if let Some(log_path) = output_file {
+            let log_file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(log_path)?;
+            cmd.stdout(log_file.try_clone()?);
+            cmd.stderr(log_file);
+        }
+        let mut child = cmd.spawn()?;
+        let started = Instant::now();
+        info!(name, process_id = child.id(), "connector started");
+        status.state = SupervisedState::Running;
+        status.process_id = Some(child.id());
+        status.store(status_dir)?;
+
+        let mut last_check: Option<Instant> = None;
+        let exit = loop {
+            if let Some(exit) = child.try_wait()? {
+                break exit;
+            }
+            if last_check.is_none_or(|last| last.elapsed() >= HEALTH_CHECK_INTERVAL) {
+                status.metrics = read_metrics(&metrics_socket);
+                status.healthy = status.metrics.is_some();
+                status.store(status_dir)?;
+                last_check = Some(Instant::now());
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        };
+
+        status.process_id = None;
+        status.healthy = false;
+        status.last_exit = Some(exit.to_string());
+        if exit.success() || started.elapsed() >= policy.max_backoff {
+            crashes = 0;
+        }
+        if !exit.success() {
+            crashes += 1;
+        }
+
+        if !policy.should_restart(exit.success(), status.restarts) {
+            status.state = final_state(&exit);
+            status.store(status_dir)?;
+            info!(name, %exit, restarts = status.restarts, "connector is not restarted");
+            return Ok(status);
+        }
+
+        let delay = policy.backoff(crashes);
+        warn!(name, %exit, ?delay, "connector exited, restarting");
+        status.state = SupervisedState::Backoff;
+        status.restarts += 1;
+        status.store(status_dir)?;
+        std::thread::sleep(delay);
+    }
+}
+
+fn final_state(exit: &ExitStatus) -> SupervisedState {
+    if exit.success() {
+        SupervisedState::Stopped
+    } else {
+        SupervisedState::Failed
+    }
+}
+
+/// metrics served by connector monitoring, None if connector does not respond
+#[cfg(unix)]
+fn read_metrics(socket: &Path) -> Option<serde_json::Value> {
+    use std::io::Read;
+    use std::os::unix::net::UnixStream;
+
+    let read = || -> Result<serde_json::Value> {
+        let mut stream = UnixStream::connect(socket)?;
+        stream.set_read_timeout(Some(METRICS_READ_TIMEOUT))?;
+        let mut content = Vec::new();
+        stream.read_to_end(&mut content)?;
+        Ok(serde_json::from_slice(&content)?)
+    };
+    match read() {
+        Ok(metrics) => Some(metrics),
+        Err(err) => {
+            debug!(?socket, %err, "connector health check failed");
+            None
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn read_metrics(_socket: &Path) -> Option<serde_json::Value> {
+    None
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_restart() {
+        let policy = RestartPolicy {
+            max_restarts: Some(2),
+            ..Default::default()
+        };
+        assert!(policy.should_restart(false, 0));
+        assert!(policy.should_restart(false, 1));
+        assert!(!policy.should_restart(false, 2));
+        assert!(!policy.should_restart(true, 0));
+
+        let always = RestartPolicy {
+            restart: Restart::Always,
+            ..Default::default()
+        };
+        assert!(always.should_restart(true, 100));
+
+        let never = RestartPolicy {
+            restart: Restart::Never,
+            ..Default::default()
+        };
+        assert!(!never.should_restart(false, 0));
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RestartPolicy {
+            min_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(10),
+            ..Default::default()
+        };
+        assert_eq!(policy.backoff(0), Duration::from_secs(1));
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(4), Duration::from_secs(8));
+        assert_eq!(policy.backoff(5), Duration::from_secs(10));
+        assert_eq!(policy.backoff(100), Duration::from_secs(10));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_supervise_restarts_crashed_connector() {
+        use fluvio_connector_package::metadata::ConnectorMetadata;
+
+        use crate::DeploymentType;
+
+        let dir = std::env::temp_dir().join(format!("fluvio-supervisor-test-{}", now_millis()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let executable = dir.join("connector.sh");
+        std::fs::write(&executable, "#!/bin/sh\necho running\nexit 3\n").expect("executable");
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&executable, std::fs::Permissions::from_mode(0o744))
+                .expect("permissions");
+        }
+        let config = dir.join("config.yaml");
+        std::fs::write(&config, "").expect("config");
+        let log_file = dir.join("connector.log");
+        let status_dir = dir.join("status");
+
+        let policy = RestartPolicy {
+            max_restarts: Some(2),
+            min_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(20),
+            ..Default::default()
+        };
+        let deployment = Deployment::builder()
+            .executable(executable)
+            .config(config)
+            .pkg(ConnectorMetadata::default())
+            .deployment_type(DeploymentType::Local {
+                output_file: None,
+                tmp_dir: None,
+            })
+            .build()
+            .expect("deployment");
+
+        let status = supervise(
+            &deployment,
+            Some(&log_file),
+            &status_dir,
+            &policy,
+            "test-connector",
+        )
+        .expect("supervise");
+
+        assert_eq!(status.state, SupervisedState::Failed);
+        assert_eq!(status.restarts, 2);
+        assert_eq!(status.process_id, None);
+        assert!(
+            status
+                .last_exit
+                .as_deref()
+                .unwrap_or_default()
+                .contains('3')
+        );
+        assert_eq!(SupervisorStatus::load(&status_dir).expect("load"), status);
+        assert_eq!(
+            std::fs::read_to_string(&log_file).expect("log"),
+            "running\nrunning\nrunning\n"
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
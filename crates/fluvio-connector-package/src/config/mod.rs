@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize, Deserializer, Serializer};
 pub use bytesize::ByteSize;
 
 pub use fluvio_controlplane_metadata::topic::config as topic_config;
-pub use fluvio_smartengine::transformation::TransformationStep;
+pub use fluvio_smartengine::transformation::{TransformationStep, ErrorPolicy};
 pub use fluvio_types::PartitionId;
 pub use fluvio_types::compression::Compression;
 
@@ -36,6 +36,7 @@ const IMAGE_PREFFIX: &str = "infinyon/fluvio-connect";
 /// for example on the CLI create command.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 #[serde(tag = "apiVersion")]
+#[allow(clippy::large_enum_variant)]
 pub enum ConnectorConfig {
     // V0 is the version of the config that was used before we introduced the versioning.
     #[serde(rename = "0.0.0")]
@@ -290,6 +291,13 @@ pub struct ConsumerParameters {
     pub id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub offset: Option<ConsumerOffsetConfig>,
+    /// topic receiving records which transforms with `dead-letter` error policy fail on
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        alias = "dead_letter_topic"
+    )]
+    pub dead_letter_topic: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
@@ -311,6 +319,15 @@ pub struct ProducerParameters {
     )]
     #[schemars(skip)]
     pub batch_size: Option<ByteSize>,
+
+    /// topic receiving records which transforms with `dead-letter` error policy fail on
+    #[serde(
+        rename = "dead-letter-topic",
+        alias = "dead_letter_topic",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dead_letter_topic: Option<String>,
 }
 #[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Hash, JsonSchema)]
 pub struct SecretConfig {
@@ -604,6 +621,7 @@ impl ConnectorConfig {
     pub fn config_from_str(config_str: &str) -> Result<Self> {
         let connector_config: Self = serde_yaml::from_str(config_str)?;
         connector_config.validate_secret_names()?;
+        connector_config.validate_dead_letter_topic()?;
 
         debug!("Using connector config {connector_config:#?}");
         Ok(connector_config)
@@ -616,6 +634,30 @@ impl ConnectorConfig {
         Ok(())
     }
 
+    /// records which transforms fail on are sent to dead-letter topic of producer for source connector,
+    /// of consumer otherwise
+    fn validate_dead_letter_topic(&self) -> Result<()> {
+        if !self
+            .transforms()
+            .iter()
+            .any(|step| step.on_error == ErrorPolicy::DeadLetter)
+        {
+            return Ok(());
+        }
+        let meta = self.meta();
+        let dead_letter_topic = if self.direction().is_source() {
+            meta.producer().and_then(|p| p.dead_letter_topic.as_ref())
+        } else {
+            meta.consumer().and_then(|c| c.dead_letter_topic.as_ref())
+        };
+        if dead_letter_topic.is_none() {
+            anyhow::bail!(
+                "dead-letter-topic is required when transforms use dead-letter error policy"
+            );
+        }
+        Ok(())
+    }
+
     pub fn meta(&self) -> MetaConfig {
         match self {
             Self::V0_0_0(inner) => MetaConfig::V0_1_0(&inner.meta),
@@ -627,6 +669,7 @@ impl ConnectorConfig {
     pub fn from_value(value: serde_yaml::Value) -> Result<Self> {
         let connector_config: Self = serde_yaml::from_value(value)?;
         connector_config.validate_secret_names()?;
+        connector_config.validate_dead_letter_topic()?;
 
         debug!("Using connector config {connector_config:#?}");
         Ok(connector_config)
@@ -686,7 +729,7 @@ mod tests {
         },
         CompressionAlgorithm, Deduplication, Bounds, Filter, Transform,
     };
-    use fluvio_smartengine::transformation::{TransformationStep, Lookback};
+    use fluvio_smartengine::transformation::{TransformationStep, Lookback, ErrorPolicy};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -702,12 +745,14 @@ mod tests {
                     linger: Some(Duration::from_millis(1)),
                     compression: Some(Compression::Gzip),
                     batch_size: Some(ByteSize::mb(44)),
+                    dead_letter_topic: None,
                 }),
                 consumer: Some(ConsumerParameters {
                     partition: ConsumerPartitionConfig::One(10),
                     max_bytes: Some(ByteSize::mb(1)),
                     id: None,
                     offset: None,
+                    dead_letter_topic: None,
                 }),
                 secrets: Some(vec![SecretConfig {
                     name: "secret1".parse().unwrap(),
@@ -723,6 +768,7 @@ mod tests {
                     ),
                     ("param".to_string(), "param_value".into()),
                 ]),
+                ..Default::default()
             }],
         });
 
@@ -753,10 +799,13 @@ mod tests {
                         replication: Some(2),
                         ignore_rack_assignment: Some(true),
                         maps: None,
+                        min_in_sync_replicas: None,
+                        unclean_leader_election: None,
                     },
                     retention: RetentionConfig {
                         time: Some(Duration::from_secs(120)),
                         segment_size: Some(bytesize::ByteSize(2000)),
+                        compaction: None,
                     },
                     compression: CompressionConfig {
                         type_: CompressionAlgorithm::Lz4,
@@ -774,12 +823,14 @@ mod tests {
                             },
                         },
                     }),
+                    schema: Some("mqtt-events".to_string()),
                 },
                 version: "0.1.0".to_string(),
                 producer: Some(ProducerParameters {
                     linger: Some(Duration::from_millis(1)),
                     compression: Some(Compression::Gzip),
                     batch_size: Some(ByteSize::mb(44)),
+                    dead_letter_topic: None,
                 }),
                 consumer: Some(ConsumerParameters {
                     partition: ConsumerPartitionConfig::One(10),
@@ -790,6 +841,7 @@ mod tests {
                         strategy: OffsetStrategyConfig::Auto,
                         flush_period: Some(Duration::from_secs(160)),
                     }),
+                    dead_letter_topic: None,
                 }),
                 secrets: Some(vec![SecretConfig {
                     name: "secret1".parse().unwrap(),
@@ -805,6 +857,7 @@ mod tests {
                     ),
                     ("param".to_string(), "param_value".into()),
                 ]),
+                ..Default::default()
             }],
         });
 
@@ -898,6 +951,14 @@ mod tests {
             "apiVersion: unknown variant `v1`, expected one of `0.0.0`, `0.1.0`, `0.2.0` at line 1 column 13",
             format!("{connector_cfg_err}")
         );
+
+        let connector_cfg_err =
+            ConnectorConfig::from_file("test-data/connectors/error-dead-letter-topic.yaml")
+                .expect_err("This yaml should error");
+        assert_eq!(
+            "dead-letter-topic is required when transforms use dead-letter error policy",
+            format!("{connector_cfg_err}")
+        );
     }
 
     #[test]
@@ -991,12 +1052,14 @@ mod tests {
                     linger: None,
                     compression: None,
                     batch_size: Some(ByteSize::b(1600)),
+                    dead_letter_topic: None,
                 }),
                 consumer: Some(ConsumerParameters {
                     max_bytes: Some(ByteSize::b(1400)),
                     partition: Default::default(),
                     id: None,
                     offset: None,
+                    dead_letter_topic: None,
                 }),
                 secrets: None,
             },
@@ -1084,6 +1147,17 @@ mod tests {
 
         assert_eq!(connector_spec.transforms()[0].with,
                        BTreeMap::from([("mapping".to_string(), "{\"map-columns\":{\"device_id\":{\"json-key\":\"device.device_id\",\"value\":{\"default\":0,\"required\":true,\"type\":\"int\"}},\"record\":{\"json-key\":\"$\",\"value\":{\"required\":true,\"type\":\"jsonb\"}}},\"table\":\"topic_message\"}".into())]));
+        assert_eq!(
+            connector_spec.transforms()[0].on_error,
+            ErrorPolicy::DeadLetter
+        );
+        assert_eq!(
+            connector_spec
+                .meta()
+                .consumer()
+                .and_then(|c| c.dead_letter_topic.as_deref()),
+            Some("my-mqtt-dlq")
+        );
     }
 
     #[test]
@@ -1215,12 +1289,14 @@ mod tests {
                     linger: None,
                     compression: None,
                     batch_size: Some(ByteSize::b(1600)),
+                    dead_letter_topic: None,
                 }),
                 consumer: Some(ConsumerParameters {
                     max_bytes: Some(ByteSize::b(1400)),
                     partition: Default::default(),
                     id: None,
                     offset: None,
+                    dead_letter_topic: None,
                 }),
                 secrets: None,
             },
@@ -1307,12 +1383,14 @@ mod tests {
             max_bytes: Default::default(),
             id: None,
             offset: None,
+            dead_letter_topic: None,
         };
         let many = ConsumerParameters {
             partition: ConsumerPartitionConfig::Many(vec![2, 3]),
             max_bytes: Default::default(),
             id: None,
             offset: None,
+            dead_letter_topic: None,
         };
 
         let all = ConsumerParameters {
@@ -1320,6 +1398,7 @@ mod tests {
             max_bytes: Default::default(),
             id: None,
             offset: None,
+            dead_letter_topic: None,
         };
 
         //when
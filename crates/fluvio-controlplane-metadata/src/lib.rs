@@ -7,6 +7,9 @@ pub mod tableformat;
 pub mod message;
 pub mod mirror;
 pub mod mirroring;
+pub mod schema;
+pub mod policy;
+pub mod quota;
 
 pub use fluvio_stream_model::core;
 
@@ -24,21 +27,40 @@ pub use fluvio_stream_model::k8_types;
 
 pub mod extended {
 
+    use fluvio_protocol::{Encoder, Decoder};
+
     use super::core::Spec;
 
-    #[derive(Debug, Clone, PartialEq, Hash, Eq)]
+    #[derive(Debug, Clone, PartialEq, Hash, Eq, Default, Encoder, Decoder)]
     #[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum ObjectType {
+        #[default]
+        #[fluvio(tag = 0)]
         Spu,
+        #[fluvio(tag = 1)]
         CustomSpu,
+        #[fluvio(tag = 2)]
         SpuGroup,
+        #[fluvio(tag = 3)]
         Topic,
+        #[fluvio(tag = 4)]
         Partition,
+        #[fluvio(tag = 5)]
         ManagedConnector,
+        #[fluvio(tag = 6)]
         SmartModule,
+        #[fluvio(tag = 7)]
         TableFormat,
+        #[fluvio(tag = 8)]
         DerivedStream,
+        #[fluvio(tag = 9)]
         Mirror,
+        #[fluvio(tag = 10)]
+        Schema,
+        #[fluvio(tag = 11)]
+        Policy,
+        #[fluvio(tag = 12)]
+        Quota,
     }
 
     pub trait SpecExt: Spec {
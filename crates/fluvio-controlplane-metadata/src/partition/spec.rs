@@ -33,6 +33,28 @@ pub struct PartitionSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 14)]
     pub mirror: Option<PartitionMirrorConfig>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 21)]
+    pub schema: Option<String>,
+    /// records before this offset are deleted, only moves forward
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 22)]
+    pub log_start_offset: i64,
+    /// set while replicas are moved to other SPUs
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 24)]
+    pub reassignment: Option<PartitionReassignment>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 25)]
+    pub min_in_sync_replicas: Option<u16>,
+    /// replica which is not in sync may be elected as leader
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 26)]
+    pub unclean_leader_election: bool,
+    /// incremented every time leader changes, leader stamps it on batches it writes
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 26)]
+    pub leader_epoch: i32,
 }
 
 impl PartitionSpec {
@@ -61,6 +83,12 @@ impl PartitionSpec {
             compression_type: topic.get_compression_type().clone(),
             deduplication: topic.get_deduplication().cloned(),
             system: topic.is_system(),
+            schema: topic.get_schema().cloned(),
+            log_start_offset: 0,
+            reassignment: None,
+            min_in_sync_replicas: topic.get_min_in_sync_replicas(),
+            unclean_leader_election: topic.is_unclean_leader_election(),
+            leader_epoch: 0,
         }
     }
 
@@ -68,6 +96,53 @@ impl PartitionSpec {
         self.replicas.contains(spu)
     }
 
+    /// change leader and start new leader epoch
+    pub fn set_leader(&mut self, leader: SpuId) {
+        if self.leader != leader {
+            self.leader = leader;
+            self.leader_epoch += 1;
+        }
+    }
+
+    /// first replica is preferred leader, leadership moves back to it once it is in sync
+    pub fn preferred_leader(&self) -> Option<SpuId> {
+        self.replicas.first().copied()
+    }
+
+    /// Start moving replicas to target SPUs.
+    /// New replicas are added to current ones, so they can catch up before current replicas are dropped.
+    /// Reassignment in progress is replaced.
+    pub fn start_reassignment(&mut self, target_replicas: Vec<SpuId>, throttle_bytes_per_sec: u64) {
+        let current: Vec<SpuId> = match self.reassignment.take() {
+            Some(reassignment) => self
+                .replicas
+                .iter()
+                .filter(|spu| !reassignment.adding_replicas.contains(spu))
+                .copied()
+                .collect(),
+            None => self.replicas.clone(),
+        };
+        let adding_replicas: Vec<SpuId> = target_replicas
+            .iter()
+            .filter(|spu| !current.contains(spu))
+            .copied()
+            .collect();
+        self.replicas = current;
+        self.replicas.extend(adding_replicas.iter().copied());
+        self.reassignment = Some(PartitionReassignment {
+            target_replicas,
+            adding_replicas,
+            throttle_bytes_per_sec,
+        });
+    }
+
+    /// drop replicas not in target, leader must be one of target replicas
+    pub fn finish_reassignment(&mut self) {
+        if let Some(reassignment) = self.reassignment.take() {
+            self.replicas = reassignment.target_replicas;
+        }
+    }
+
     /// follower replicas
     pub fn followers(&self) -> Vec<SpuId> {
         self.replicas
@@ -112,6 +187,22 @@ impl From<Vec<SpuId>> for PartitionSpec {
     }
 }
 
+/// Move of partition replicas to other SPUs
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PartitionReassignment {
+    /// replicas once reassignment is done, first one is preferred leader
+    pub target_replicas: Vec<SpuId>,
+    /// new replicas catching up with leader, they don't count for high watermark
+    pub adding_replicas: Vec<SpuId>,
+    /// max bytes per second sent by leader to each adding replica, unlimited if 0
+    pub throttle_bytes_per_sec: u64,
+}
+
 /// Setting applied to a replica
 #[derive(Decoder, Encoder, Debug, Eq, PartialEq, Clone, Default)]
 pub struct PartitionConfig {
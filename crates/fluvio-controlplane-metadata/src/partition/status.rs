@@ -41,6 +41,10 @@ pub struct PartitionStatus {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 16)]
     pub base_offset: i64,
+    /// batches of leader replica which failed crc check and were not repaired yet
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 23)]
+    pub corrupt_batches: u32,
 }
 
 impl Default for PartitionStatus {
@@ -53,6 +57,7 @@ impl Default for PartitionStatus {
             replicas: Default::default(),
             is_being_deleted: Default::default(),
             base_offset: Default::default(),
+            corrupt_batches: Default::default(),
         }
     }
 }
@@ -138,6 +143,16 @@ impl PartitionStatus {
         self.replicas.iter()
     }
 
+    /// leader or follower which has replicated all committed records
+    pub fn is_in_sync(&self, spu: SpuId) -> bool {
+        if self.leader.spu == spu {
+            return true;
+        }
+        self.replicas
+            .iter()
+            .any(|replica| replica.spu == spu && replica.leo >= 0 && replica.leo >= self.leader.hw)
+    }
+
     pub fn live_replicas(&self) -> Vec<SpuId> {
         self.replicas.iter().map(|lrs| lrs.spu).collect()
     }
@@ -0,0 +1,24 @@
+use fluvio_stream_model::k8_types::{Crd, GROUP, V1, CrdNames, Spec, Status, DefaultHeader};
+
+use super::PolicySpec;
+use super::PolicyStatus;
+
+const POLICY_API: Crd = Crd {
+    group: GROUP,
+    version: V1,
+    names: CrdNames {
+        kind: "Policy",
+        plural: "policies",
+        singular: "policy",
+    },
+};
+
+impl Spec for PolicySpec {
+    type Header = DefaultHeader;
+    type Status = PolicyStatus;
+    fn metadata() -> &'static Crd {
+        &POLICY_API
+    }
+}
+
+impl Status for PolicyStatus {}
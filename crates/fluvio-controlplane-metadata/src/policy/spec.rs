@@ -0,0 +1,450 @@
+//!
+//! # Policy Spec
+//!
+//! Role based access control policy.
+//! Roles grant actions on objects whose names match resource patterns,
+//! bindings assign roles to principals of authenticated (X.509) identities.
+//!
+
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::extended::ObjectType;
+
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PolicySpec {
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub roles: Vec<PolicyRole>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub bindings: Vec<RoleBinding>,
+}
+
+impl PolicySpec {
+    pub fn role(&self, name: &str) -> Option<&PolicyRole> {
+        self.roles.iter().find(|role| role.name == name)
+    }
+
+    /// names of roles bound to principal
+    pub fn bound_roles<'a>(&'a self, principal: &'a str) -> impl Iterator<Item = &'a str> {
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.principal.matches(Some(principal)))
+            .flat_map(|binding| binding.roles.iter().map(|role| role.as_str()))
+    }
+
+    /// add roles and bindings of other policy.
+    /// permissions of roles with same name are combined
+    pub fn merge(&mut self, other: &PolicySpec) {
+        for role in &other.roles {
+            match self.roles.iter_mut().find(|r| r.name == role.name) {
+                Some(existing) => existing
+                    .permissions
+                    .extend(role.permissions.iter().cloned()),
+                None => self.roles.push(role.clone()),
+            }
+        }
+        self.bindings.extend(other.bindings.iter().cloned());
+    }
+
+    /// reject roles and patterns that can never match
+    pub fn validate(&self) -> Result<(), String> {
+        for role in &self.roles {
+            if role.name.is_empty() {
+                return Err("role name can't be empty".to_owned());
+            }
+            for permission in &role.permissions {
+                if permission.actions.is_empty() {
+                    return Err(format!(
+                        "role '{}' has permission without actions",
+                        role.name
+                    ));
+                }
+                if let Some(pattern) = permission.resources.iter().find(|p| !p.is_valid()) {
+                    return Err(format!(
+                        "role '{}' has invalid resource pattern '{pattern}'",
+                        role.name
+                    ));
+                }
+            }
+        }
+        for binding in &self.bindings {
+            if !binding.principal.is_valid() {
+                return Err(format!("invalid principal pattern '{}'", binding.principal));
+            }
+        }
+        Ok(())
+    }
+
+    /// check if principal with scopes is allowed to perform action.
+    /// Scopes are treated as role names in addition to roles bound to principal.
+    /// If instance is not known, only permissions covering any resource are considered.
+    pub fn is_allowed(
+        &self,
+        principal: &str,
+        scopes: &[String],
+        object: &ObjectType,
+        action: PolicyAction,
+        instance: Option<&str>,
+    ) -> bool {
+        scopes
+            .iter()
+            .map(|scope| scope.as_str())
+            .chain(self.bound_roles(principal))
+            .filter_map(|name| self.role(name))
+            .any(|role| role.allows(object, action, instance))
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PolicyRole {
+    pub name: String,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub permissions: Vec<Permission>,
+}
+
+impl PolicyRole {
+    pub fn allows(
+        &self,
+        object: &ObjectType,
+        action: PolicyAction,
+        instance: Option<&str>,
+    ) -> bool {
+        self.permissions
+            .iter()
+            .any(|permission| permission.allows(object, action, instance))
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct Permission {
+    pub object: ObjectType,
+    pub actions: Vec<PolicyAction>,
+    /// names of objects, permission covers any object if empty
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub resources: Vec<ResourcePattern>,
+}
+
+impl Permission {
+    pub fn allows(
+        &self,
+        object: &ObjectType,
+        action: PolicyAction,
+        instance: Option<&str>,
+    ) -> bool {
+        &self.object == object
+            && self
+                .actions
+                .iter()
+                .any(|allowed| *allowed == PolicyAction::All || *allowed == action)
+            && (self.resources.is_empty()
+                || self
+                    .resources
+                    .iter()
+                    .any(|pattern| pattern.matches(instance)))
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PolicyAction {
+    #[default]
+    #[fluvio(tag = 0)]
+    Create,
+    #[fluvio(tag = 1)]
+    Read,
+    #[fluvio(tag = 2)]
+    Update,
+    #[fluvio(tag = 3)]
+    Delete,
+    /// write records to topic
+    #[fluvio(tag = 4)]
+    Produce,
+    /// read records from topic
+    #[fluvio(tag = 5)]
+    Consume,
+    #[fluvio(tag = 6)]
+    All,
+}
+
+impl fmt::Display for PolicyAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Name pattern: `*` matches anything, `prefix*` matches names starting with prefix,
+/// otherwise name must be equal
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct ResourcePattern(String);
+
+impl ResourcePattern {
+    pub const ANY: &'static str = "*";
+
+    pub fn any() -> Self {
+        Self(Self::ANY.to_owned())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// wildcard is only allowed at the end
+    pub fn is_valid(&self) -> bool {
+        match self.0.find('*') {
+            Some(pos) => pos == self.0.len() - 1,
+            None => !self.0.is_empty(),
+        }
+    }
+
+    /// unknown name is only matched by `*`
+    pub fn matches(&self, name: Option<&str>) -> bool {
+        if self.0 == Self::ANY {
+            return true;
+        }
+        let Some(name) = name else {
+            return false;
+        };
+        match self.0.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => self.0 == name,
+        }
+    }
+}
+
+impl From<&str> for ResourcePattern {
+    fn from(pattern: &str) -> Self {
+        Self(pattern.to_owned())
+    }
+}
+
+impl From<String> for ResourcePattern {
+    fn from(pattern: String) -> Self {
+        Self(pattern)
+    }
+}
+
+impl fmt::Display for ResourcePattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct RoleBinding {
+    /// principal of identity, can be pattern
+    pub principal: ResourcePattern,
+    pub roles: Vec<String>,
+}
+
+/// Changes of existing policy
+#[derive(Encoder, Decoder, Debug, Eq, PartialEq, Clone)]
+pub enum UpdatePolicyAction {
+    /// replace roles and bindings
+    #[fluvio(tag = 0)]
+    Replace(PolicySpec),
+}
+
+impl Default for UpdatePolicyAction {
+    fn default() -> Self {
+        Self::Replace(PolicySpec::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn policy() -> PolicySpec {
+        PolicySpec {
+            roles: vec![
+                PolicyRole {
+                    name: "orders-writer".to_owned(),
+                    permissions: vec![
+                        Permission {
+                            object: ObjectType::Topic,
+                            actions: vec![PolicyAction::Produce, PolicyAction::Read],
+                            resources: vec!["orders-*".into()],
+                        },
+                        Permission {
+                            object: ObjectType::Topic,
+                            actions: vec![PolicyAction::Consume],
+                            resources: vec!["audit".into()],
+                        },
+                    ],
+                },
+                PolicyRole {
+                    name: "admin".to_owned(),
+                    permissions: vec![Permission {
+                        object: ObjectType::Topic,
+                        actions: vec![PolicyAction::All],
+                        resources: vec![],
+                    }],
+                },
+            ],
+            bindings: vec![RoleBinding {
+                principal: "svc-orders*".into(),
+                roles: vec!["orders-writer".to_owned()],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_resource_pattern() {
+        assert!(ResourcePattern::any().matches(None));
+        assert!(ResourcePattern::any().matches(Some("topic")));
+
+        let prefix = ResourcePattern::from("orders-*");
+        assert!(prefix.matches(Some("orders-eu")));
+        assert!(prefix.matches(Some("orders-")));
+        assert!(!prefix.matches(Some("order")));
+        assert!(!prefix.matches(None));
+
+        let exact = ResourcePattern::from("orders");
+        assert!(exact.matches(Some("orders")));
+        assert!(!exact.matches(Some("orders-eu")));
+        assert!(!exact.matches(None));
+    }
+
+    #[test]
+    fn test_policy_validate() {
+        assert!(policy().validate().is_ok());
+
+        let mut invalid = policy();
+        invalid.roles[0].permissions[0].resources = vec!["*orders".into()];
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = policy();
+        invalid.bindings[0].principal = "".into();
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = policy();
+        invalid.roles[1].permissions[0].actions.clear();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_policy_bindings() {
+        let policy = policy();
+        let topic = ObjectType::Topic;
+
+        assert!(policy.is_allowed(
+            "svc-orders-1",
+            &[],
+            &topic,
+            PolicyAction::Produce,
+            Some("orders-eu")
+        ));
+        assert!(!policy.is_allowed(
+            "svc-orders-1",
+            &[],
+            &topic,
+            PolicyAction::Produce,
+            Some("audit")
+        ));
+        assert!(policy.is_allowed(
+            "svc-orders-1",
+            &[],
+            &topic,
+            PolicyAction::Consume,
+            Some("audit")
+        ));
+        assert!(!policy.is_allowed(
+            "svc-orders-1",
+            &[],
+            &topic,
+            PolicyAction::Consume,
+            Some("orders-eu")
+        ));
+        // listing is not covered by prefix
+        assert!(!policy.is_allowed("svc-orders-1", &[], &topic, PolicyAction::Read, None));
+        assert!(!policy.is_allowed(
+            "svc-billing",
+            &[],
+            &topic,
+            PolicyAction::Produce,
+            Some("orders-eu")
+        ));
+        assert!(!policy.is_allowed(
+            "svc-orders-1",
+            &[],
+            &ObjectType::Spu,
+            PolicyAction::Read,
+            None
+        ));
+    }
+
+    #[test]
+    fn test_policy_scopes() {
+        let policy = policy();
+        let topic = ObjectType::Topic;
+        let scopes = vec!["admin".to_owned()];
+
+        assert!(policy.is_allowed("alice", &scopes, &topic, PolicyAction::Delete, Some("any")));
+        assert!(policy.is_allowed("alice", &scopes, &topic, PolicyAction::Create, None));
+        assert!(!policy.is_allowed(
+            "alice",
+            &["unknown".to_owned()],
+            &topic,
+            PolicyAction::Create,
+            None
+        ));
+    }
+
+    #[test]
+    fn test_policy_merge() {
+        let mut policy = policy();
+        policy.merge(&PolicySpec {
+            roles: vec![PolicyRole {
+                name: "orders-writer".to_owned(),
+                permissions: vec![Permission {
+                    object: ObjectType::Topic,
+                    actions: vec![PolicyAction::Produce],
+                    resources: vec!["audit".into()],
+                }],
+            }],
+            bindings: vec![RoleBinding {
+                principal: "bob".into(),
+                roles: vec!["orders-writer".to_owned()],
+            }],
+        });
+
+        assert_eq!(policy.roles.len(), 2);
+        let topic = ObjectType::Topic;
+        assert!(policy.is_allowed("bob", &[], &topic, PolicyAction::Produce, Some("audit")));
+        assert!(policy.is_allowed(
+            "svc-orders",
+            &[],
+            &topic,
+            PolicyAction::Produce,
+            Some("audit")
+        ));
+        assert!(policy.is_allowed("bob", &[], &topic, PolicyAction::Produce, Some("orders-us")));
+    }
+}
@@ -0,0 +1,22 @@
+//!
+//! # Policy Status
+//!
+//! Policy is fully described by its spec, status carries no information.
+//!
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+#[derive(Default, Decoder, Encoder, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct PolicyStatus;
+
+impl fmt::Display for PolicyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PolicyStatus")
+    }
+}
@@ -0,0 +1,209 @@
+//!
+//! # Quota Spec
+//!
+//! Throughput limits applied by SPU to a client id, principal or topic.
+//! Rates are per second, unset rate is not limited.
+//!
+
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct QuotaSpec {
+    /// traffic to which quota applies
+    pub entity: QuotaEntity,
+    /// bytes per second written by produce requests
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub produce_byte_rate: Option<u64>,
+    /// bytes per second read by fetch and stream fetch requests
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub consume_byte_rate: Option<u64>,
+    /// produce and fetch requests per second
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub request_rate: Option<u64>,
+}
+
+impl QuotaSpec {
+    pub fn rate(&self, kind: QuotaKind) -> Option<u64> {
+        match kind {
+            QuotaKind::ProduceBytes => self.produce_byte_rate,
+            QuotaKind::ConsumeBytes => self.consume_byte_rate,
+            QuotaKind::Requests => self.request_rate,
+        }
+    }
+
+    /// reject quotas that can never be satisfied or limit nothing
+    pub fn validate(&self) -> Result<(), String> {
+        if self.entity.name().is_empty() {
+            return Err(format!("{} can't be empty", self.entity.kind()));
+        }
+        if self.produce_byte_rate.is_none()
+            && self.consume_byte_rate.is_none()
+            && self.request_rate.is_none()
+        {
+            return Err("at least one rate must be set".to_owned());
+        }
+        for kind in [
+            QuotaKind::ProduceBytes,
+            QuotaKind::ConsumeBytes,
+            QuotaKind::Requests,
+        ] {
+            if self.rate(kind) == Some(0) {
+                return Err(format!("{kind} rate must be greater than 0"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Traffic identity to which quota applies
+#[derive(Encoder, Decoder, Debug, Eq, PartialEq, Clone, Hash)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum QuotaEntity {
+    /// client id sent in request header, shared by all connections using it
+    #[fluvio(tag = 0)]
+    ClientId(String),
+    /// principal of authenticated identity
+    #[fluvio(tag = 1)]
+    Principal(String),
+    /// all traffic of topic
+    #[fluvio(tag = 2)]
+    Topic(String),
+}
+
+impl Default for QuotaEntity {
+    fn default() -> Self {
+        Self::ClientId(String::new())
+    }
+}
+
+impl QuotaEntity {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ClientId(_) => "client-id",
+            Self::Principal(_) => "principal",
+            Self::Topic(_) => "topic",
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::ClientId(name) | Self::Principal(name) | Self::Topic(name) => name,
+        }
+    }
+
+    /// true if traffic of client to topic is subject to this entity
+    pub fn matches(&self, client_id: &str, principal: Option<&str>, topic: Option<&str>) -> bool {
+        match self {
+            Self::ClientId(name) => name == client_id,
+            Self::Principal(name) => principal == Some(name.as_str()),
+            Self::Topic(name) => topic == Some(name.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for QuotaEntity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}={}", self.kind(), self.name())
+    }
+}
+
+/// Resource limited by quota
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+pub enum QuotaKind {
+    ProduceBytes,
+    ConsumeBytes,
+    Requests,
+}
+
+impl fmt::Display for QuotaKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ProduceBytes => write!(f, "produce byte"),
+            Self::ConsumeBytes => write!(f, "consume byte"),
+            Self::Requests => write!(f, "request"),
+        }
+    }
+}
+
+/// Changes of existing quota
+#[derive(Encoder, Decoder, Debug, Eq, PartialEq, Clone)]
+pub enum UpdateQuotaAction {
+    /// replace entity and rates
+    #[fluvio(tag = 0)]
+    Replace(QuotaSpec),
+}
+
+impl Default for UpdateQuotaAction {
+    fn default() -> Self {
+        Self::Replace(QuotaSpec::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_quota_validate() {
+        let quota = QuotaSpec {
+            entity: QuotaEntity::ClientId("noisy".to_owned()),
+            produce_byte_rate: Some(1024),
+            ..Default::default()
+        };
+        assert!(quota.validate().is_ok());
+
+        let no_rates = QuotaSpec {
+            entity: QuotaEntity::Topic("orders".to_owned()),
+            ..Default::default()
+        };
+        assert!(no_rates.validate().is_err());
+
+        let zero_rate = QuotaSpec {
+            request_rate: Some(0),
+            ..quota.clone()
+        };
+        assert!(zero_rate.validate().is_err());
+
+        let no_name = QuotaSpec {
+            entity: QuotaEntity::Principal(String::new()),
+            ..quota
+        };
+        assert!(no_name.validate().is_err());
+    }
+
+    #[test]
+    fn test_quota_entity_matches() {
+        let client = QuotaEntity::ClientId("app".to_owned());
+        assert!(client.matches("app", None, Some("orders")));
+        assert!(!client.matches("other", Some("app"), Some("app")));
+
+        let principal = QuotaEntity::Principal("svc".to_owned());
+        assert!(principal.matches("app", Some("svc"), None));
+        assert!(!principal.matches("svc", None, None));
+
+        let topic = QuotaEntity::Topic("orders".to_owned());
+        assert!(topic.matches("app", None, Some("orders")));
+        assert!(!topic.matches("orders", None, None));
+    }
+}
@@ -0,0 +1,22 @@
+//!
+//! # Quota Status
+//!
+//! Quota is fully described by its spec, status carries no information.
+//!
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+#[derive(Default, Decoder, Encoder, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct QuotaStatus;
+
+impl fmt::Display for QuotaStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "QuotaStatus")
+    }
+}
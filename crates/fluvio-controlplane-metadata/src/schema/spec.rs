@@ -0,0 +1,187 @@
+#![allow(clippy::assign_op_pattern)]
+
+//!
+//! # Schema Spec
+//!
+//! Schema is registered under a subject, which is the name of the object.
+//! Every registered definition is a new version of the subject and gets id which is unique in the cluster.
+//! Batches refer to schema by this id.
+//!
+
+use std::fmt;
+use std::str::FromStr;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SchemaSpec {
+    pub schema_type: SchemaType,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub compatibility: CompatibilityMode,
+    /// registered versions, oldest first
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub versions: Vec<SchemaVersion>,
+}
+
+impl SchemaSpec {
+    /// spec with a single definition, id and version are assigned by SC
+    pub fn new(
+        schema_type: SchemaType,
+        compatibility: CompatibilityMode,
+        definition: impl Into<String>,
+    ) -> Self {
+        Self {
+            schema_type,
+            compatibility,
+            versions: vec![SchemaVersion {
+                definition: definition.into(),
+                ..Default::default()
+            }],
+        }
+    }
+
+    pub fn latest(&self) -> Option<&SchemaVersion> {
+        self.versions.last()
+    }
+
+    /// find version by schema id
+    pub fn by_id(&self, id: u32) -> Option<&SchemaVersion> {
+        self.versions.iter().find(|version| version.id == id)
+    }
+
+    pub fn is_registered(&self, id: u32) -> bool {
+        self.by_id(id).is_some()
+    }
+
+    /// highest schema id used by this subject
+    pub fn max_id(&self) -> u32 {
+        self.versions
+            .iter()
+            .map(|version| version.id)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SchemaVersion {
+    /// id written to batch header, unique in the cluster
+    pub id: u32,
+    /// version of subject starting from 1
+    pub version: u32,
+    pub definition: String,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum SchemaType {
+    #[default]
+    #[fluvio(tag = 0)]
+    Avro,
+    /// JSON Schema
+    #[fluvio(tag = 1)]
+    Json,
+    #[fluvio(tag = 2)]
+    Protobuf,
+}
+
+impl fmt::Display for SchemaType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Avro => write!(f, "avro"),
+            Self::Json => write!(f, "json"),
+            Self::Protobuf => write!(f, "protobuf"),
+        }
+    }
+}
+
+impl FromStr for SchemaType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "avro" => Ok(Self::Avro),
+            "json" | "jsonschema" | "json-schema" => Ok(Self::Json),
+            "protobuf" | "proto" => Ok(Self::Protobuf),
+            other => Err(format!("unknown schema type: {other}")),
+        }
+    }
+}
+
+/// Which readers must be able to read data written with new version of schema
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum CompatibilityMode {
+    /// any change is allowed
+    #[fluvio(tag = 0)]
+    None,
+    /// consumers using new version can read data written with previous version
+    #[default]
+    #[fluvio(tag = 1)]
+    Backward,
+    /// consumers using previous version can read data written with new version
+    #[fluvio(tag = 2)]
+    Forward,
+    /// both backward and forward
+    #[fluvio(tag = 3)]
+    Full,
+}
+
+impl fmt::Display for CompatibilityMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Backward => write!(f, "backward"),
+            Self::Forward => write!(f, "forward"),
+            Self::Full => write!(f, "full"),
+        }
+    }
+}
+
+impl FromStr for CompatibilityMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "backward" => Ok(Self::Backward),
+            "forward" => Ok(Self::Forward),
+            "full" => Ok(Self::Full),
+            other => Err(format!("unknown compatibility mode: {other}")),
+        }
+    }
+}
+
+/// Changes of existing schema subject
+#[derive(Encoder, Decoder, Debug, Eq, PartialEq, Clone)]
+pub enum UpdateSchemaAction {
+    /// register definition as new version of subject
+    #[fluvio(tag = 0)]
+    Register(String),
+    #[fluvio(tag = 1)]
+    SetCompatibility(CompatibilityMode),
+}
+
+impl Default for UpdateSchemaAction {
+    fn default() -> Self {
+        Self::Register(String::new())
+    }
+}
@@ -4,7 +4,8 @@ use derive_builder::Builder;
 use fluvio_types::{ReplicationFactor, TopicName, PartitionCount, IgnoreRackAssignment};
 
 use crate::topic::{
-    ReplicaSpec, TopicReplicaParam, SegmentBasedPolicy, CleanupPolicy, TopicStorageConfig,
+    ReplicaSpec, TopicReplicaParam, SegmentBasedPolicy, CleanupPolicy, CompactPolicy,
+    TopicStorageConfig,
 };
 
 use super::{TopicSpec, PartitionMap, CompressionAlgorithm, deduplication::Deduplication};
@@ -46,6 +47,14 @@ pub struct TopicConfig {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub deduplication: Option<Deduplication>,
+
+    /// schema subject which batches produced to topic must use
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub schema: Option<String>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -97,6 +106,20 @@ pub struct PartitionConfig {
         serde(skip_serializing_if = "Option::is_none", default)
     )]
     pub maps: Option<Vec<PartitionMap>>,
+
+    /// replicas which must be in sync to accept produce waiting for commit
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub min_in_sync_replicas: Option<u16>,
+
+    /// allow replica which is not in sync to become leader
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub unclean_leader_election: Option<bool>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -124,6 +147,34 @@ pub struct RetentionConfig {
         schemars(with = "Option::<String>")
     )]
     pub segment_size: Option<bytesize::ByteSize>,
+
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub compaction: Option<CompactionConfig>,
+}
+
+/// Enables key based compaction. If retention `time` is also set, segments are deleted by age as well.
+#[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    derive(schemars::JsonSchema),
+    serde(rename_all = "kebab-case")
+)]
+pub struct CompactionConfig {
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(
+            skip_serializing_if = "Option::is_none",
+            with = "humantime_serde",
+            default
+        ),
+        schemars(with = "Option::<String>")
+    )]
+    #[builder(default)]
+    pub tombstone_retention: Option<Duration>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -138,11 +189,168 @@ pub struct CompressionConfig {
     pub type_: CompressionAlgorithm,
 }
 
+/// Setting of an existing topic which differs from its [`TopicConfig`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicConfigDrift {
+    pub setting: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl std::fmt::Display for TopicConfigDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is {}, config expects {}",
+            self.setting, self.actual, self.expected
+        )
+    }
+}
+
 impl TopicConfig {
     #[cfg(feature = "use_serde")]
     pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> anyhow::Result<Self> {
         Ok(serde_yaml::from_reader(std::fs::File::open(path)?)?)
     }
+
+    /// Settings of existing topic which differ from this config.
+    /// Only settings which config sets are checked.
+    pub fn drift(&self, spec: &TopicSpec) -> Vec<TopicConfigDrift> {
+        let expected = TopicSpec::from(self.clone());
+        let mut drift = Vec::new();
+        let mut check = |setting: &'static str, expected: String, actual: String| {
+            if expected != actual {
+                drift.push(TopicConfigDrift {
+                    setting,
+                    expected,
+                    actual,
+                });
+            }
+        };
+
+        match expected.replicas() {
+            ReplicaSpec::Assigned(maps) => check(
+                "partition maps",
+                maps.to_string(),
+                match spec.replicas() {
+                    ReplicaSpec::Assigned(maps) => maps.to_string(),
+                    replicas => replicas.to_string(),
+                },
+            ),
+            // missing partition section deserializes to defaults, which are not checked
+            _ if self.partition == PartitionConfig::default() => {}
+            _ => {
+                if let Some(count) = self.partition.count {
+                    check(
+                        "partitions",
+                        count.to_string(),
+                        spec.partitions().to_string(),
+                    );
+                }
+                if let Some(replication) = self.partition.replication {
+                    check(
+                        "replication",
+                        replication.to_string(),
+                        display_option(spec.replication_factor()),
+                    );
+                }
+            }
+        }
+
+        if let Some(policy) = expected.get_clean_policy() {
+            check(
+                "retention",
+                display_cleanup_policy(Some(policy)),
+                display_cleanup_policy(spec.get_clean_policy()),
+            );
+        }
+
+        let actual_storage = spec.get_storage().cloned().unwrap_or_default();
+        if let Some(storage) = expected.get_storage() {
+            if let Some(segment_size) = storage.segment_size {
+                check(
+                    "segment size",
+                    segment_size.to_string(),
+                    display_option(actual_storage.segment_size),
+                );
+            }
+            if let Some(max_partition_size) = storage.max_partition_size {
+                check(
+                    "max partition size",
+                    max_partition_size.to_string(),
+                    display_option(actual_storage.max_partition_size),
+                );
+            }
+        }
+
+        if self.compression.type_ != CompressionAlgorithm::Any {
+            check(
+                "compression",
+                self.compression.type_.to_string(),
+                spec.get_compression_type().to_string(),
+            );
+        }
+
+        if let Some(deduplication) = expected.get_deduplication() {
+            check(
+                "deduplication",
+                display_deduplication(Some(deduplication)),
+                display_deduplication(spec.get_deduplication()),
+            );
+        }
+
+        if let Some(schema) = expected.get_schema() {
+            check("schema", schema.clone(), display_option(spec.get_schema()));
+        }
+
+        if let Some(min_in_sync_replicas) = expected.get_min_in_sync_replicas() {
+            check(
+                "min in-sync replicas",
+                min_in_sync_replicas.to_string(),
+                display_option(spec.get_min_in_sync_replicas()),
+            );
+        }
+
+        if expected.is_unclean_leader_election() {
+            check(
+                "unclean leader election",
+                true.to_string(),
+                spec.is_unclean_leader_election().to_string(),
+            );
+        }
+
+        drift
+    }
+}
+
+fn display_option<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "none".to_owned(), |value| value.to_string())
+}
+
+fn display_cleanup_policy(policy: Option<&CleanupPolicy>) -> String {
+    match policy {
+        None => "none".to_owned(),
+        Some(CleanupPolicy::Segment(segment)) => {
+            format!("delete after {}s", segment.time_in_seconds)
+        }
+        Some(CleanupPolicy::Compact(compact)) => {
+            let mut display = format!(
+                "compact with tombstones kept {}s",
+                compact.tombstone_retention_secs
+            );
+            if let Some(delete) = &compact.delete {
+                display.push_str(&format!(", delete after {}s", delete.time_in_seconds));
+            }
+            display
+        }
+    }
+}
+
+fn display_deduplication(deduplication: Option<&Deduplication>) -> String {
+    match deduplication {
+        None => "none".to_owned(),
+        Some(deduplication) => format!("{deduplication:?}"),
+    }
 }
 
 #[cfg(feature = "use_serde")]
@@ -162,6 +370,8 @@ impl Default for PartitionConfig {
             ignore_rack_assignment: Some(DEFAULT_IGNORE_RACK_ASSIGMENT),
             max_size: Default::default(),
             maps: Default::default(),
+            min_in_sync_replicas: Default::default(),
+            unclean_leader_election: Default::default(),
         }
     }
 }
@@ -186,14 +396,36 @@ impl From<TopicConfig> for TopicSpec {
             }),
         };
         let mut topic_spec: TopicSpec = replica_spec.into();
-        if let Some(retention_time) = config.retention.time {
-            topic_spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
+        let segment_policy = config
+            .retention
+            .time
+            .map(|retention_time| SegmentBasedPolicy {
                 time_in_seconds: retention_time.as_secs() as u32,
-            }));
+            });
+        match (config.retention.compaction, segment_policy) {
+            (Some(compaction), delete) => {
+                let mut policy = CompactPolicy {
+                    delete,
+                    ..Default::default()
+                };
+                if let Some(tombstone_retention) = compaction.tombstone_retention {
+                    policy.tombstone_retention_secs = tombstone_retention.as_secs() as u32;
+                }
+                topic_spec.set_cleanup_policy(CleanupPolicy::Compact(policy));
+            }
+            (None, Some(segment_policy)) => {
+                topic_spec.set_cleanup_policy(CleanupPolicy::Segment(segment_policy));
+            }
+            (None, None) => {}
         };
 
         topic_spec.set_compression_type(config.compression.type_);
         topic_spec.set_deduplication(config.deduplication);
+        topic_spec.set_schema(config.schema);
+        topic_spec.set_min_in_sync_replicas(config.partition.min_in_sync_replicas);
+        topic_spec.set_unclean_leader_election(
+            config.partition.unclean_leader_election.unwrap_or_default(),
+        );
 
         if segment_size.is_some() || max_partition_size.is_some() {
             topic_spec.set_storage(TopicStorageConfig {
@@ -345,6 +577,35 @@ compression:
         assert_eq!(spec, test_spec);
     }
 
+    #[cfg(feature = "use_serde")]
+    #[test]
+    fn test_compaction_config_to_spec() {
+        //given
+        let input = r#"meta:
+  name: test_topic
+retention:
+  time: 2m
+  compaction:
+    tombstone-retention: 1h
+"#;
+
+        //when
+        use std::str::FromStr;
+
+        let spec: TopicSpec = TopicConfig::from_str(input).expect("deserialized").into();
+
+        //then
+        assert_eq!(
+            spec.get_clean_policy(),
+            Some(&CleanupPolicy::Compact(CompactPolicy {
+                tombstone_retention_secs: 3600,
+                delete: Some(SegmentBasedPolicy {
+                    time_in_seconds: 120
+                }),
+            }))
+        );
+    }
+
     fn test_config() -> TopicConfig {
         TopicConfig {
             version: "0.1.1".to_string(),
@@ -361,18 +622,110 @@ compression:
                     replicas: vec![1, 2],
                     ..Default::default()
                 }]),
+                min_in_sync_replicas: None,
+                unclean_leader_election: None,
             },
             retention: RetentionConfig {
                 time: Some(Duration::from_secs(120)),
                 segment_size: Some(bytesize::ByteSize(2000)),
+                compaction: None,
             },
             compression: CompressionConfig {
                 type_: CompressionAlgorithm::Lz4,
             },
             deduplication: Some(test_deduplication()),
+            schema: None,
         }
     }
 
+    #[test]
+    fn test_drift() {
+        //given
+        let config = test_config();
+        let mut spec: TopicSpec = config.clone().into();
+        assert!(config.drift(&spec).is_empty());
+
+        //when
+        spec.set_replicas(ReplicaSpec::new_computed(2, 1, None));
+        spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
+            time_in_seconds: 60,
+        }));
+        spec.set_compression_type(CompressionAlgorithm::Gzip);
+        spec.set_deduplication(None);
+
+        //then
+        let drift = config.drift(&spec);
+        assert_eq!(
+            drift.iter().map(|drift| drift.setting).collect::<Vec<_>>(),
+            vec![
+                "partition maps",
+                "retention",
+                "compression",
+                "deduplication"
+            ]
+        );
+        assert_eq!(
+            drift[1].to_string(),
+            "retention is delete after 60s, config expects delete after 120s"
+        );
+        assert_eq!(
+            drift[2].to_string(),
+            "compression is gzip, config expects lz4"
+        );
+    }
+
+    #[test]
+    fn test_drift_of_computed_replicas() {
+        //given
+        let config = TopicConfig {
+            partition: PartitionConfig {
+                count: Some(3),
+                replication: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        //when
+        let drift = config.drift(&TopicSpec::new_computed(1, 2, None));
+
+        //then
+        assert_eq!(
+            drift,
+            vec![TopicConfigDrift {
+                setting: "partitions",
+                expected: "3".to_owned(),
+                actual: "1".to_owned(),
+            }]
+        );
+
+        // retention not set in config is not checked
+        let mut spec = TopicSpec::new_computed(3, 2, None);
+        spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
+            time_in_seconds: 60,
+        }));
+        assert!(config.drift(&spec).is_empty());
+    }
+
+    #[test]
+    fn test_drift_of_unset_settings() {
+        //given
+        let config = TopicConfig {
+            partition: PartitionConfig {
+                count: Some(3),
+                replication: None,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut spec = TopicSpec::new_computed(3, 2, None);
+        spec.set_compression_type(CompressionAlgorithm::Gzip);
+
+        //then
+        assert!(config.drift(&spec).is_empty());
+        assert!(TopicConfig::default().drift(&spec).is_empty());
+    }
+
     fn test_deduplication() -> Deduplication {
         Deduplication {
             bounds: Bounds {
@@ -5,7 +5,7 @@ use anyhow::{anyhow, Result};
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_types::defaults::{
     STORAGE_RETENTION_SECONDS, SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN, STORAGE_RETENTION_SECONDS_MIN,
-    SPU_PARTITION_MAX_BYTES_MIN, SPU_LOG_SEGMENT_MAX_BYTES,
+    SPU_PARTITION_MAX_BYTES_MIN, SPU_LOG_SEGMENT_MAX_BYTES, STORAGE_TOMBSTONE_RETENTION_SECONDS,
 };
 use fluvio_types::SpuId;
 use fluvio_types::{PartitionId, PartitionCount, ReplicationFactor, IgnoreRackAssignment};
@@ -39,6 +39,22 @@ pub struct TopicSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 13)]
     system: bool,
+    /// schema subject which batches produced to topic must use
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[fluvio(min_version = 21)]
+    schema: Option<String>,
+    /// replicas which must be in sync to accept produce waiting for commit.
+    /// Records are committed once all in-sync replicas have them
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[fluvio(min_version = 25)]
+    min_in_sync_replicas: Option<u16>,
+    /// allow replica which is not in sync to become leader when no in-sync replica is online.
+    /// Committed records which the new leader doesn't have are lost
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 26)]
+    unclean_leader_election: bool,
 }
 
 impl From<ReplicaSpec> for TopicSpec {
@@ -147,6 +163,30 @@ impl TopicSpec {
         self.system = system;
     }
 
+    pub fn get_schema(&self) -> Option<&String> {
+        self.schema.as_ref()
+    }
+
+    pub fn set_schema(&mut self, schema: Option<String>) {
+        self.schema = schema;
+    }
+
+    pub fn get_min_in_sync_replicas(&self) -> Option<u16> {
+        self.min_in_sync_replicas
+    }
+
+    pub fn set_min_in_sync_replicas(&mut self, min_in_sync_replicas: Option<u16>) {
+        self.min_in_sync_replicas = min_in_sync_replicas;
+    }
+
+    pub fn is_unclean_leader_election(&self) -> bool {
+        self.unclean_leader_election
+    }
+
+    pub fn set_unclean_leader_election(&mut self, unclean_leader_election: bool) {
+        self.unclean_leader_election = unclean_leader_election;
+    }
+
     /// get retention secs that can be displayed
     pub fn retention_secs(&self) -> u32 {
         self.get_clean_policy()
@@ -189,6 +229,19 @@ impl TopicSpec {
             }
         }
 
+        if let Some(min_in_sync_replicas) = self.min_in_sync_replicas {
+            if min_in_sync_replicas == 0 {
+                return Some("min_in_sync_replicas must be at least 1".to_owned());
+            }
+            if let Some(replication) = self.replicas.replication_factor() {
+                if min_in_sync_replicas as ReplicationFactor > replication {
+                    return Some(format!(
+                        "min_in_sync_replicas {min_in_sync_replicas} is greater than replication factor {replication}"
+                    ));
+                }
+            }
+        }
+
         None
     }
 }
@@ -944,6 +997,9 @@ pub enum CleanupPolicy {
     #[cfg_attr(feature = "use_serde", serde(rename = "segment"))]
     #[fluvio(tag = 0)]
     Segment(SegmentBasedPolicy),
+    #[cfg_attr(feature = "use_serde", serde(rename = "compact"))]
+    #[fluvio(tag = 1, min_version = 20)]
+    Compact(CompactPolicy),
 }
 
 impl Default for CleanupPolicy {
@@ -956,8 +1012,14 @@ impl CleanupPolicy {
     pub fn retention_secs(&self) -> u32 {
         match self {
             CleanupPolicy::Segment(policy) => policy.retention_secs(),
+            CleanupPolicy::Compact(policy) => policy.retention_secs(),
         }
     }
+
+    /// true if records are compacted by key
+    pub fn is_compact(&self) -> bool {
+        matches!(self, CleanupPolicy::Compact(_))
+    }
 }
 
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
@@ -976,6 +1038,47 @@ impl SegmentBasedPolicy {
     }
 }
 
+/// Key based compaction. Only the latest record for each key is kept in closed segments.
+/// Records with empty value (tombstones) remove the key after `tombstone_retention_secs`.
+/// If `delete` is set, segments are also removed by age as in [`SegmentBasedPolicy`].
+#[derive(Decoder, Encoder, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct CompactPolicy {
+    pub tombstone_retention_secs: u32,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub delete: Option<SegmentBasedPolicy>,
+}
+
+impl Default for CompactPolicy {
+    fn default() -> Self {
+        Self {
+            tombstone_retention_secs: STORAGE_TOMBSTONE_RETENTION_SECONDS,
+            delete: None,
+        }
+    }
+}
+
+impl CompactPolicy {
+    /// time based retention, compacted only topic never expires segments
+    pub fn retention_secs(&self) -> u32 {
+        self.delete
+            .as_ref()
+            .map(|policy| policy.retention_secs())
+            .unwrap_or(u32::MAX)
+    }
+
+    pub fn is_delete(&self) -> bool {
+        self.delete.is_some()
+    }
+}
+
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(
     feature = "use_serde",
@@ -1261,6 +1364,59 @@ mod test {
         assert!(topic_spec_decoded.deduplication.is_none());
     }
 
+    #[test]
+    fn test_encode_decode_compact_cleanup_policy() {
+        //given
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((1, 1, false).into()).into();
+        topic_spec.set_cleanup_policy(CleanupPolicy::Compact(CompactPolicy {
+            tombstone_retention_secs: 60,
+            delete: Some(SegmentBasedPolicy {
+                time_in_seconds: 3600,
+            }),
+        }));
+
+        //when
+        let mut dest = vec![];
+        topic_spec.encode(&mut dest, 20).expect("encoded");
+        let mut topic_spec_decoded = TopicSpec::default();
+        topic_spec_decoded
+            .decode(&mut Cursor::new(&dest), 20)
+            .expect("decoded");
+
+        //then
+        assert_eq!(topic_spec_decoded, topic_spec);
+        assert_eq!(topic_spec_decoded.retention_secs(), 3600);
+        assert!(
+            topic_spec_decoded
+                .get_clean_policy()
+                .expect("policy")
+                .is_compact()
+        );
+    }
+
+    #[test]
+    fn test_compact_only_never_expires_segments() {
+        let policy = CleanupPolicy::Compact(CompactPolicy::default());
+        assert_eq!(policy.retention_secs(), u32::MAX);
+
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((1, 1, false).into()).into();
+        topic_spec.set_cleanup_policy(policy);
+        assert!(topic_spec.validate_config().is_none());
+    }
+
+    #[test]
+    fn test_validate_min_in_sync_replicas() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((1, 2, false).into()).into();
+        topic_spec.set_min_in_sync_replicas(Some(2));
+        assert!(topic_spec.validate_config().is_none());
+
+        topic_spec.set_min_in_sync_replicas(Some(3));
+        assert!(topic_spec.validate_config().is_some());
+
+        topic_spec.set_min_in_sync_replicas(Some(0));
+        assert!(topic_spec.validate_config().is_some());
+    }
+
     #[test]
     fn test_partition_map_str() {
         // Test multiple
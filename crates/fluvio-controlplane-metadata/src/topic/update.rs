@@ -1,4 +1,5 @@
 use fluvio_protocol::{Decoder, Encoder};
+use fluvio_types::SpuId;
 
 #[derive(Debug, Default, Encoder, Decoder, Clone)]
 pub struct AddPartition {
@@ -12,12 +13,32 @@ pub struct AddMirror {
     pub home_to_mirror: bool,
 }
 
+/// delete records of partition before offset by moving its log start offset forward
+#[derive(Debug, Default, Encoder, Decoder, Clone)]
+pub struct DeleteRecords {
+    pub partition: u32,
+    pub before_offset: i64,
+}
+
+/// move replicas of partition to other SPUs, first replica is preferred leader
+#[derive(Debug, Default, Encoder, Decoder, Clone)]
+pub struct ReassignPartition {
+    pub partition: u32,
+    pub replicas: Vec<SpuId>,
+    /// max bytes per second replicated to each new replica while it catches up, unlimited if 0
+    pub throttle_bytes_per_sec: u64,
+}
+
 #[derive(Debug, Encoder, Decoder, Clone)]
 pub enum UpdateTopicAction {
     #[fluvio(tag = 0)]
     AddPartition(AddPartition),
     #[fluvio(tag = 1)]
     AddMirror(AddMirror),
+    #[fluvio(tag = 2)]
+    DeleteRecords(DeleteRecords),
+    #[fluvio(tag = 3)]
+    ReassignPartition(ReassignPartition),
 }
 
 impl Default for UpdateTopicAction {
@@ -0,0 +1,60 @@
+//! API call from SC to peer SC
+
+use std::io::Error as IoError;
+use std::convert::TryInto;
+
+use fluvio_protocol::api::api_decode;
+use fluvio_protocol::api::ApiMessage;
+use fluvio_protocol::api::RequestHeader;
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::bytes::Buf;
+use fluvio_protocol::derive::Encoder;
+use fluvio_protocol::derive::Decoder;
+
+use super::append::AppendRequest;
+use super::vote::VoteRequest;
+
+#[repr(u16)]
+#[derive(Eq, PartialEq, Debug, Encoder, Decoder, Clone, Copy)]
+#[fluvio(encode_discriminant)]
+#[derive(Default)]
+pub enum HaPeerKey {
+    #[default]
+    Vote = 3000,
+    Append = 3001,
+}
+
+/// Request made to SC from peer SC
+#[derive(Debug, Encoder)]
+pub enum HaPeerRequest {
+    #[fluvio(tag = 0)]
+    VoteRequest(RequestMessage<VoteRequest>),
+    #[fluvio(tag = 1)]
+    AppendRequest(RequestMessage<AppendRequest>),
+}
+
+impl Default for HaPeerRequest {
+    fn default() -> HaPeerRequest {
+        HaPeerRequest::VoteRequest(RequestMessage::default())
+    }
+}
+
+impl ApiMessage for HaPeerRequest {
+    type ApiKey = HaPeerKey;
+
+    fn decode_with_header<T>(src: &mut T, header: RequestHeader) -> Result<Self, IoError>
+    where
+        Self: Default + Sized,
+        Self::ApiKey: Sized,
+        T: Buf,
+    {
+        match header.api_key().try_into()? {
+            HaPeerKey::Vote => {
+                api_decode!(HaPeerRequest, VoteRequest, src, header)
+            }
+            HaPeerKey::Append => {
+                api_decode!(HaPeerRequest, AppendRequest, src, header)
+            }
+        }
+    }
+}
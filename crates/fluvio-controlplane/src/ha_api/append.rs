@@ -0,0 +1,78 @@
+//!
+//! # Append
+//!
+//! Active SC replicates changes of the local metadata files to standby SCs.
+//! Empty request serves as heartbeat. Standby which can't continue from `prev_log_index`
+//! is sent entries following its last entry, or full snapshot of the metadata files
+//! if those entries are no longer kept or the standby diverged.
+//!
+use std::fmt;
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::Encoder;
+
+use super::api::HaPeerKey;
+
+/// Change of single metadata file
+#[derive(Decoder, Encoder, Debug, Default, Clone, PartialEq, Eq)]
+pub struct MetadataFileChange {
+    pub kind: String,
+    pub name: String,
+    /// content of the file, `None` if file was removed
+    pub content: Option<String>,
+}
+
+/// Entry of replicated log. Entry without changes is appended by newly elected SC.
+#[derive(Decoder, Encoder, Debug, Default, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub index: u64,
+    pub term: u64,
+    pub changes: Vec<MetadataFileChange>,
+}
+
+/// All metadata files as of `last_index`
+#[derive(Decoder, Encoder, Debug, Default, Clone, PartialEq, Eq)]
+pub struct MetadataSnapshot {
+    pub last_index: u64,
+    pub last_term: u64,
+    pub files: Vec<MetadataFileChange>,
+}
+
+#[derive(Decoder, Encoder, Debug, Default, Clone, PartialEq, Eq)]
+pub struct AppendRequest {
+    pub term: u64,
+    pub leader_id: u32,
+    pub prev_log_index: u64,
+    pub prev_log_term: u64,
+    pub entries: Vec<LogEntry>,
+    pub leader_commit: u64,
+    pub snapshot: Option<MetadataSnapshot>,
+}
+
+impl fmt::Display for AppendRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "append term: {}, prev: {}, entries: {}, snapshot: {}",
+            self.term,
+            self.prev_log_index,
+            self.entries.len(),
+            self.snapshot.is_some()
+        )
+    }
+}
+
+impl Request for AppendRequest {
+    const API_KEY: u16 = HaPeerKey::Append as u16;
+    type Response = AppendResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct AppendResponse {
+    pub term: u64,
+    pub success: bool,
+    /// last log index of standby after processing the request
+    pub last_log_index: u64,
+    pub last_log_term: u64,
+}
@@ -0,0 +1,4 @@
+//! API between SC instances running in highly-available mode
+pub mod api;
+pub mod append;
+pub mod vote;
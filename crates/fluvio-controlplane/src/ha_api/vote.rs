@@ -0,0 +1,30 @@
+//!
+//! # Vote
+//!
+//! Candidate SC asks peers to vote for it as active SC for the term.
+//! Vote is granted only if candidate's metadata log is at least as up to date as the voter's.
+//!
+use fluvio_protocol::api::Request;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::Encoder;
+
+use super::api::HaPeerKey;
+
+#[derive(Decoder, Encoder, Debug, Default, Clone, PartialEq, Eq)]
+pub struct VoteRequest {
+    pub term: u64,
+    pub candidate_id: u32,
+    pub last_log_index: u64,
+    pub last_log_term: u64,
+}
+
+impl Request for VoteRequest {
+    const API_KEY: u16 = HaPeerKey::Vote as u16;
+    type Response = VoteResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct VoteResponse {
+    pub term: u64,
+    pub granted: bool,
+}
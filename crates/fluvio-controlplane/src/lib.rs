@@ -1,4 +1,5 @@
 pub mod sc_api;
+pub mod ha_api;
 pub mod spu_api;
 pub mod replica;
 pub mod message;
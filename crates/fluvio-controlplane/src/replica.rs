@@ -6,7 +6,7 @@ use fluvio_controlplane_metadata::{
     topic::{CleanupPolicy, TopicStorageConfig, CompressionAlgorithm, Deduplication},
     core::MetadataItem,
     store::MetadataStoreObject,
-    partition::{PartitionSpec, PartitionMirrorConfig},
+    partition::{PartitionSpec, PartitionMirrorConfig, PartitionReassignment},
 };
 use fluvio_protocol::{Encoder, Decoder, record::ReplicaKey};
 use fluvio_types::SpuId;
@@ -25,6 +25,21 @@ pub struct Replica {
     pub storage: Option<TopicStorageConfig>,
     pub compression_type: CompressionAlgorithm,
     pub deduplication: Option<Deduplication>,
+    /// schema subject required by topic
+    #[fluvio(min_version = 21)]
+    pub schema: Option<String>,
+    /// records before this offset are deleted
+    #[fluvio(min_version = 22)]
+    pub log_start_offset: i64,
+    /// set while replicas are moved to other SPUs
+    #[fluvio(min_version = 24)]
+    pub reassignment: Option<PartitionReassignment>,
+    /// replicas which must be in sync to accept produce waiting for commit
+    #[fluvio(min_version = 25)]
+    pub min_in_sync_replicas: Option<u16>,
+    /// epoch of current leader, stamped on batches written by leader
+    #[fluvio(min_version = 26)]
+    pub leader_epoch: i32,
 }
 
 impl Replica {
@@ -46,6 +61,14 @@ impl Replica {
             ..Default::default()
         }
     }
+
+    /// replicas catching up with leader while partition is reassigned
+    pub fn adding_replicas(&self) -> &[SpuId] {
+        self.reassignment
+            .as_ref()
+            .map(|reassignment| reassignment.adding_replicas.as_slice())
+            .unwrap_or_default()
+    }
 }
 
 impl<C> From<PartitionMetadata<C>> for Replica
@@ -69,6 +92,11 @@ where
             storage: spec.storage,
             compression_type: spec.compression_type,
             deduplication: spec.deduplication,
+            schema: spec.schema,
+            log_start_offset: spec.log_start_offset,
+            reassignment: spec.reassignment,
+            min_in_sync_replicas: spec.min_in_sync_replicas,
+            leader_epoch: spec.leader_epoch,
         }
     }
 }
@@ -44,7 +44,7 @@ impl fmt::Display for UpdateLrsRequest {
 impl Request for UpdateLrsRequest {
     const API_KEY: u16 = InternalScKey::UpdateLrs as u16;
     type Response = UpdateLrsResponse;
-    const DEFAULT_API_VERSION: i16 = 1;
+    const DEFAULT_API_VERSION: i16 = 2;
 }
 
 #[derive(Decoder, Encoder, Debug, Default, Clone)]
@@ -65,6 +65,9 @@ pub struct LrsRequest {
     pub size: i64,
     #[fluvio(min_version = 1)]
     pub base_offset: i64,
+    /// batches of leader replica which failed crc check and were not repaired yet
+    #[fluvio(min_version = 2)]
+    pub corrupt_batches: u32,
 }
 
 impl PartialEq for LrsRequest {
@@ -102,6 +105,12 @@ impl LrsRequest {
             replicas,
             size,
             base_offset,
+            corrupt_batches: 0,
         }
     }
+
+    pub fn with_corrupt_batches(mut self, corrupt_batches: u32) -> Self {
+        self.corrupt_batches = corrupt_batches;
+        self
+    }
 }
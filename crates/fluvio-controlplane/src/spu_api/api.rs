@@ -10,6 +10,9 @@ use fluvio_protocol::Encoder;
 use fluvio_protocol::Decoder;
 
 use super::update_mirror::UpdateMirrorRequest;
+use super::update_schema::UpdateSchemaRequest;
+use super::update_policy::UpdatePolicyRequest;
+use super::update_quota::UpdateQuotaRequest;
 use super::update_spu::UpdateSpuRequest;
 use super::update_replica::UpdateReplicaRequest;
 use super::update_smartmodule::UpdateSmartModuleRequest;
@@ -23,6 +26,9 @@ pub enum InternalSpuApi {
     UpdateSmartModule = 1003,
     // UpdateDerivedStream = 1004,
     UpdateMirror = 1004,
+    UpdateSchema = 1005,
+    UpdatePolicy = 1006,
+    UpdateQuota = 1007,
 }
 
 impl Default for InternalSpuApi {
@@ -41,6 +47,12 @@ pub enum InternalSpuRequest {
     UpdateSmartModuleRequest(RequestMessage<UpdateSmartModuleRequest>),
     #[fluvio(tag = 3)]
     UpdateMirrorRequest(RequestMessage<UpdateMirrorRequest>),
+    #[fluvio(tag = 4)]
+    UpdateSchemaRequest(RequestMessage<UpdateSchemaRequest>),
+    #[fluvio(tag = 5)]
+    UpdatePolicyRequest(RequestMessage<UpdatePolicyRequest>),
+    #[fluvio(tag = 6)]
+    UpdateQuotaRequest(RequestMessage<UpdateQuotaRequest>),
 }
 
 // Added to satisfy Encoder/Decoder traits
@@ -74,6 +86,15 @@ impl ApiMessage for InternalSpuRequest {
             InternalSpuApi::UpdateMirror => {
                 api_decode!(Self, UpdateMirrorRequest, src, header)
             }
+            InternalSpuApi::UpdateSchema => {
+                api_decode!(Self, UpdateSchemaRequest, src, header)
+            }
+            InternalSpuApi::UpdatePolicy => {
+                api_decode!(Self, UpdatePolicyRequest, src, header)
+            }
+            InternalSpuApi::UpdateQuota => {
+                api_decode!(Self, UpdateQuotaRequest, src, header)
+            }
         }
     }
 }
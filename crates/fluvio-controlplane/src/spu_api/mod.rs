@@ -3,3 +3,6 @@ pub mod update_replica;
 pub mod update_smartmodule;
 pub mod update_spu;
 pub mod update_mirror;
+pub mod update_schema;
+pub mod update_policy;
+pub mod update_quota;
@@ -0,0 +1,42 @@
+use fluvio_controlplane_metadata::{
+    core::MetadataItem,
+    message::{Message, Messages},
+    policy::PolicySpec,
+    store::MetadataStoreObject,
+};
+use fluvio_protocol::{Encoder, Decoder, api::Request};
+
+use crate::requests::ControlPlaneRequest;
+
+use super::api::InternalSpuApi;
+
+/// Access policy as seen by SPU, used to authorize produce and consume
+#[derive(Decoder, Encoder, Debug, Eq, PartialEq, Clone, Default)]
+pub struct Policy {
+    pub name: String,
+    pub spec: PolicySpec,
+}
+
+pub type UpdatePolicyRequest = ControlPlaneRequest<Policy>;
+
+impl Request for UpdatePolicyRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdatePolicy as u16;
+    type Response = UpdatePolicyResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdatePolicyResponse {}
+
+pub type PolicyMsg = Message<Policy>;
+pub type PolicyMsgs = Messages<Policy>;
+
+impl<C> From<MetadataStoreObject<PolicySpec, C>> for Policy
+where
+    C: MetadataItem,
+{
+    fn from(mso: MetadataStoreObject<PolicySpec, C>) -> Self {
+        let name = mso.key;
+        let spec = mso.spec;
+        Self { name, spec }
+    }
+}
@@ -20,6 +20,11 @@ pub trait Log<E = Vec<u8>> {
     }
 
     async fn append_batch(&mut self, entries: Vec<E>) -> Result<()>;
+
+    /// make appended entries durable
+    async fn sync(&mut self) -> Result<()> {
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -95,6 +100,11 @@ where
         self.log.append_batch(records).await
     }
 
+    /// make changes durable in the log
+    pub async fn sync(&mut self) -> Result<()> {
+        self.log.sync().await
+    }
+
     async fn replay(&mut self) -> Result<()> {
         let mut new_cache = HashMap::new();
 
@@ -163,6 +173,10 @@ impl<L: Log> Log<Entry> for EntriesLog<L> {
         let entries = entries.iter().map(encode).collect::<Result<Vec<_>, _>>()?;
         self.inner_log.append_batch(entries).await
     }
+
+    async fn sync(&mut self) -> Result<()> {
+        self.inner_log.sync().await
+    }
 }
 
 impl Log for &mut Vec<Vec<u8>> {
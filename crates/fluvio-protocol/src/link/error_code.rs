@@ -63,6 +63,36 @@ pub enum ErrorCode {
     #[fluvio(tag = 73)]
     #[error("Partition is short-circuited")]
     PartitionShortCircuited,
+    #[fluvio(tag = 74)]
+    #[error("out of order producer sequence, expected {expected}, received {received}")]
+    OutOfOrderSequence { expected: i32, received: i32 },
+    #[fluvio(tag = 75)]
+    #[error("producer epoch is older than the last seen epoch")]
+    InvalidProducerEpoch,
+    #[fluvio(tag = 76)]
+    #[error("invalid transaction state: {0}")]
+    InvalidTxnState(String),
+    #[fluvio(tag = 77)]
+    #[error("this SPU is not the transaction coordinator of the producer")]
+    NotTxnCoordinator,
+    #[fluvio(tag = 78)]
+    #[error("this SPU is not the coordinator of consumer groups")]
+    NotGroupCoordinator,
+    #[fluvio(tag = 79)]
+    #[error("consumer is not a member of the group, it must join again")]
+    UnknownGroupMember,
+    #[fluvio(tag = 80)]
+    #[error("inconsistent consumer group protocol: {0}")]
+    InconsistentGroupProtocol(String),
+    #[fluvio(tag = 81)]
+    #[error("record batch at offset {offset} is corrupt, its crc doesn't match")]
+    CorruptRecordBatch { offset: i64 },
+    #[fluvio(tag = 82)]
+    #[error("not enough in-sync replicas: {in_sync} of required {min_in_sync}")]
+    NotEnoughReplicas { in_sync: u16, min_in_sync: u16 },
+    #[fluvio(tag = 83)]
+    #[error("producer batches were already written, their offsets are no longer known")]
+    DuplicateSequenceNumber,
 
     // Spu errors
     #[fluvio(tag = 1000)]
@@ -117,6 +147,9 @@ pub enum ErrorCode {
     #[fluvio(tag = 3001)]
     #[error("the partition is not a leader")]
     PartitionNotLeader,
+    #[fluvio(tag = 3006)]
+    #[error("the partition was not found")]
+    PartitionNotFound,
 
     // Stream Fetch error
     #[fluvio(tag = 3002)]
@@ -177,6 +210,9 @@ pub enum ErrorCode {
         "SmartModule memory limit exceeded: requested {requested} bytes, max allowed {max} bytes"
     )]
     SmartModuleMemoryLimitExceeded { requested: u64, max: u64 },
+    #[fluvio(tag = 6009)]
+    #[error("SmartModule exceeded time limit of {timeout_ms} ms")]
+    SmartModuleTimeout { timeout_ms: u64 },
 
     // TableFormat Errors
     #[fluvio(tag = 7000)]
@@ -242,6 +278,51 @@ pub enum ErrorCode {
     #[fluvio(tag = 12002)]
     #[error("system {kind} '{name}' can only be updated forcibly")]
     SystemSpecUpdatingAttempt { kind: String, name: String },
+
+    // Schema Registry
+    #[fluvio(tag = 13001)]
+    #[error("the schema was not found")]
+    SchemaNotFound,
+    #[fluvio(tag = 13002)]
+    #[error("the schema already exists")]
+    SchemaAlreadyExists,
+    #[fluvio(tag = 13003)]
+    #[error("invalid schema: {0}")]
+    SchemaInvalid(String),
+    #[fluvio(tag = 13004)]
+    #[error("schema is not compatible with previous version: {0}")]
+    SchemaIncompatible(String),
+    #[fluvio(tag = 13005)]
+    #[error("topic requires records with schema of subject '{subject}'")]
+    SchemaRequired { subject: String },
+    #[fluvio(tag = 13006)]
+    #[error("schema id {id} is not registered for subject '{subject}'")]
+    SchemaNotRegistered { id: u32, subject: String },
+    #[fluvio(tag = 13007)]
+    #[error("the schema is required by topic '{topic}'")]
+    SchemaInUse { topic: String },
+
+    // Access Policy
+    #[fluvio(tag = 14001)]
+    #[error("the policy was not found")]
+    PolicyNotFound,
+    #[fluvio(tag = 14002)]
+    #[error("the policy already exists")]
+    PolicyAlreadyExists,
+    #[fluvio(tag = 14003)]
+    #[error("invalid policy: {0}")]
+    PolicyInvalid(String),
+
+    // Quota
+    #[fluvio(tag = 15001)]
+    #[error("the quota was not found")]
+    QuotaNotFound,
+    #[fluvio(tag = 15002)]
+    #[error("the quota already exists")]
+    QuotaAlreadyExists,
+    #[fluvio(tag = 15003)]
+    #[error("invalid quota: {0}")]
+    QuotaInvalid(String),
 }
 
 impl ErrorCode {
@@ -314,6 +395,30 @@ mod test {
         assert_tag!(ErrorCode::MessageTooLarge, 10, 0);
         assert_tag!(ErrorCode::PermissionDenied, 13, 0);
         assert_tag!(ErrorCode::StorageError, 56, 0);
+        assert_tag!(
+            ErrorCode::OutOfOrderSequence {
+                expected: 1,
+                received: 3
+            },
+            74,
+            0
+        );
+        assert_tag!(ErrorCode::InvalidProducerEpoch, 75, 0);
+        assert_tag!(ErrorCode::InvalidTxnState("".to_string()), 76, 0);
+        assert_tag!(ErrorCode::NotTxnCoordinator, 77, 0);
+        assert_tag!(ErrorCode::NotGroupCoordinator, 78, 0);
+        assert_tag!(ErrorCode::UnknownGroupMember, 79, 0);
+        assert_tag!(ErrorCode::InconsistentGroupProtocol("".to_string()), 80, 0);
+        assert_tag!(ErrorCode::CorruptRecordBatch { offset: 0 }, 81, 0);
+        assert_tag!(
+            ErrorCode::NotEnoughReplicas {
+                in_sync: 0,
+                min_in_sync: 0
+            },
+            82,
+            0
+        );
+        assert_tag!(ErrorCode::DuplicateSequenceNumber, 83, 0);
 
         // Spu errors
         assert_tag!(ErrorCode::SpuError, 1000, 0);
@@ -333,9 +438,48 @@ mod test {
         // Partition errors
         assert_tag!(ErrorCode::PartitionPendingInitialization, 3000, 0);
         assert_tag!(ErrorCode::PartitionNotLeader, 3001, 0);
+        assert_tag!(ErrorCode::PartitionNotFound, 3006, 0);
 
         // Stream Fetch error
         assert_tag!(ErrorCode::FetchSessionNotFoud, 3002, 0);
+
+        // Schema errors
+        assert_tag!(ErrorCode::SchemaNotFound, 13001, 0);
+        assert_tag!(ErrorCode::SchemaAlreadyExists, 13002, 0);
+        assert_tag!(ErrorCode::SchemaInvalid("".to_string()), 13003, 0);
+        assert_tag!(ErrorCode::SchemaIncompatible("".to_string()), 13004, 0);
+        assert_tag!(
+            ErrorCode::SchemaRequired {
+                subject: "".to_string()
+            },
+            13005,
+            0
+        );
+        assert_tag!(
+            ErrorCode::SchemaNotRegistered {
+                id: 0,
+                subject: "".to_string()
+            },
+            13006,
+            0
+        );
+        assert_tag!(
+            ErrorCode::SchemaInUse {
+                topic: "".to_string()
+            },
+            13007,
+            0
+        );
+
+        // Policy errors
+        assert_tag!(ErrorCode::PolicyNotFound, 14001, 0);
+        assert_tag!(ErrorCode::PolicyAlreadyExists, 14002, 0);
+        assert_tag!(ErrorCode::PolicyInvalid("".to_string()), 14003, 0);
+
+        // Quota errors
+        assert_tag!(ErrorCode::QuotaNotFound, 15001, 0);
+        assert_tag!(ErrorCode::QuotaAlreadyExists, 15002, 0);
+        assert_tag!(ErrorCode::QuotaInvalid("".to_string()), 15003, 0);
     }
 
     #[test]
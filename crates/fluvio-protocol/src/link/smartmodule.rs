@@ -102,6 +102,22 @@ impl fmt::Display for SmartModuleKind {
     }
 }
 
+/// What happens to a record when SmartModule returns an error for it
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Encoder, Decoder)]
+pub enum SmartModuleErrorPolicy {
+    /// Stop processing at the failing record and return the error
+    #[default]
+    #[fluvio(tag = 0)]
+    Fail,
+    /// Log the error, drop the failing record and continue with next one
+    #[fluvio(tag = 1)]
+    Skip,
+    /// Drop the failing record and continue, record and error are reported
+    /// so they can be routed to a dead-letter topic
+    #[fluvio(tag = 2)]
+    DeadLetter,
+}
+
 /// Deprecated. A type representing the possible errors that may occur during DerivedStream execution.
 #[derive(thiserror::Error, Debug, Clone, Eq, PartialEq, Encoder, Decoder)]
 pub enum LegacySmartModuleError {
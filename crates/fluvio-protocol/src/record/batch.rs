@@ -21,6 +21,8 @@ use super::Record;
 use super::Offset;
 
 const ATTR_SCHEMA_PRESENT: i16 = 0x10;
+const ATTR_TRANSACTIONAL: i16 = 0x20;
+const ATTR_CONTROL: i16 = 0x40;
 const ATTR_COMPRESSION_CODEC_MASK: i16 = 0x07;
 pub const NO_TIMESTAMP: i64 = -1;
 
@@ -83,6 +85,19 @@ impl Decoder for SchemaId {
     }
 }
 
+impl SchemaId {
+    /// id of schema in registry
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for SchemaId {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct Batch<R = MemoryRecords> {
     pub base_offset: Offset,
@@ -400,7 +415,12 @@ where
     R: BatchRecords,
 {
     fn write_size(&self, version: Version) -> usize {
-        BATCH_FILE_HEADER_SIZE + self.records.write_size(version)
+        let schema_len = if self.header.has_schema() {
+            size_of::<SchemaId>()
+        } else {
+            0
+        };
+        BATCH_FILE_HEADER_SIZE + schema_len + self.records.write_size(version)
     }
 
     fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), Error>
@@ -486,7 +506,42 @@ impl BatchHeader {
     pub fn set_schema_id(&mut self) {
         self.attributes |= ATTR_SCHEMA_PRESENT;
     }
+
+    /// true if batch was written as part of transaction
+    pub fn is_transactional(&self) -> bool {
+        self.attributes & ATTR_TRANSACTIONAL != 0
+    }
+
+    pub fn set_transactional(&mut self) {
+        self.attributes |= ATTR_TRANSACTIONAL;
+    }
+
+    /// true if batch contains control record instead of user data
+    pub fn is_control(&self) -> bool {
+        self.attributes & ATTR_CONTROL != 0
+    }
+
+    pub fn set_control(&mut self) {
+        self.attributes |= ATTR_CONTROL;
+    }
+
+    /// true if batch was stamped by idempotent producer
+    pub fn is_idempotent(&self) -> bool {
+        self.producer_id >= 0 && self.producer_epoch >= 0 && self.first_sequence >= 0
+    }
+
+    /// sequence number of the last record in the batch
+    pub fn last_sequence(&self) -> i32 {
+        increment_sequence(self.first_sequence, self.last_offset_delta)
+    }
 }
+
+/// Advance producer sequence number by `delta` records.
+/// Sequence numbers are never negative, they wrap around to 0 after `i32::MAX`.
+pub fn increment_sequence(sequence: i32, delta: i32) -> i32 {
+    ((sequence as i64 + delta as i64) % (i32::MAX as i64 + 1)) as i32
+}
+
 impl Default for BatchHeader {
     fn default() -> Self {
         BatchHeader {
@@ -621,6 +676,7 @@ mod test {
         batch.set_schema_id(sid);
 
         let bytes = batch.as_bytes(0)?;
+        assert_eq!(bytes.len(), batch.write_size(0));
         println!(
             "batch raw bytes (len {}): {:#X?}",
             bytes.len(),
@@ -988,4 +1044,20 @@ mod test {
         assert_eq!(batch[2].value.as_ref(), b"c");
         assert_eq!(batch.len(), 3);
     }
+
+    #[test]
+    fn test_increment_sequence() {
+        assert_eq!(increment_sequence(0, 5), 5);
+        assert_eq!(increment_sequence(i32::MAX, 1), 0);
+        assert_eq!(increment_sequence(i32::MAX - 1, 3), 1);
+
+        let mut batch = Batch::from(vec![Record::new("a"), Record::new("b")]);
+        assert!(!batch.header.is_idempotent());
+        batch.header.producer_id = 1;
+        assert!(!batch.header.is_idempotent());
+        batch.header.producer_epoch = 0;
+        batch.header.first_sequence = 10;
+        assert!(batch.header.is_idempotent());
+        assert_eq!(batch.header.last_sequence(), 11);
+    }
 }
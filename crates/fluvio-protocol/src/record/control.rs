@@ -0,0 +1,116 @@
+//!
+//! # Control batches
+//!
+//! Control batch contains single control record which marks end of producer transaction in the log.
+//! Key of control record encodes version and type of marker, value is empty.
+//!
+
+use fluvio_types::Timestamp;
+
+use super::{Batch, MemoryRecords, RawRecords, Record, RecordData};
+
+const CONTROL_RECORD_VERSION: i16 = 0;
+const CONTROL_KEY_SIZE: usize = 4;
+
+/// Transaction marker stored in control record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlRecordType {
+    Abort = 0,
+    Commit = 1,
+}
+
+impl ControlRecordType {
+    fn key(self) -> Vec<u8> {
+        let mut key = Vec::with_capacity(CONTROL_KEY_SIZE);
+        key.extend_from_slice(&CONTROL_RECORD_VERSION.to_be_bytes());
+        key.extend_from_slice(&(self as i16).to_be_bytes());
+        key
+    }
+
+    fn from_key(key: &[u8]) -> Option<Self> {
+        if key.len() != CONTROL_KEY_SIZE {
+            return None;
+        }
+        match i16::from_be_bytes([key[2], key[3]]) {
+            0 => Some(Self::Abort),
+            1 => Some(Self::Commit),
+            _ => None,
+        }
+    }
+}
+
+impl Batch {
+    /// create control batch with transaction marker for producer
+    pub fn control(
+        producer_id: i64,
+        producer_epoch: i16,
+        marker: ControlRecordType,
+        timestamp: Timestamp,
+    ) -> Self {
+        let record = Record::new_key_value(marker.key(), RecordData::default());
+        let mut batch = Batch::from(vec![record]);
+        let header = batch.get_mut_header();
+        header.set_control();
+        header.set_transactional();
+        header.producer_id = producer_id;
+        header.producer_epoch = producer_epoch;
+        header.first_timestamp = timestamp;
+        header.max_time_stamp = timestamp;
+        batch
+    }
+
+    /// marker type if this is control batch
+    pub fn control_record_type(&self) -> Option<ControlRecordType> {
+        if !self.header.is_control() {
+            return None;
+        }
+        control_record_type(self.records())
+    }
+}
+
+impl Batch<RawRecords> {
+    /// marker type if this is control batch
+    pub fn control_record_type(&self) -> Option<ControlRecordType> {
+        if !self.header.is_control() {
+            return None;
+        }
+        control_record_type(&self.memory_records().ok()?)
+    }
+}
+
+fn control_record_type(records: &MemoryRecords) -> Option<ControlRecordType> {
+    let key = records.first()?.key()?;
+    ControlRecordType::from_key(key.as_ref())
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_control_batch() {
+        let batch = Batch::control(5, 1, ControlRecordType::Commit, 100);
+        assert!(batch.header.is_control());
+        assert!(batch.header.is_transactional());
+        assert_eq!(batch.header.producer_id, 5);
+        assert_eq!(batch.records_len(), 1);
+        assert_eq!(batch.control_record_type(), Some(ControlRecordType::Commit));
+
+        let raw: Batch<RawRecords> = batch.try_into().expect("raw");
+        assert_eq!(raw.control_record_type(), Some(ControlRecordType::Commit));
+
+        let abort: Batch<RawRecords> = Batch::control(5, 1, ControlRecordType::Abort, 100)
+            .try_into()
+            .expect("raw");
+        assert_eq!(abort.control_record_type(), Some(ControlRecordType::Abort));
+
+        let data: Batch<RawRecords> = Batch::from(vec![Record::new_key_value(
+            ControlRecordType::Commit.key(),
+            "a",
+        )])
+        .try_into()
+        .expect("raw");
+        assert_eq!(data.control_record_type(), None);
+    }
+}
@@ -414,12 +414,216 @@ impl RecordHeader {
     }
 }
 
+/// A single key/value header attached to a [`Record`].
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
+pub struct Header {
+    pub key: String,
+    pub value: RecordData,
+}
+
+impl Header {
+    pub fn new(key: impl Into<String>, value: impl Into<RecordData>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl Encoder for Header {
+    fn write_size(&self, version: Version) -> usize {
+        let key_len = self.key.len() as i64;
+        key_len.var_write_size() + self.key.len() + self.value.write_size(version)
+    }
+
+    fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), Error>
+    where
+        T: BufMut,
+    {
+        let key_len = self.key.len() as i64;
+        key_len.encode_varint(dest)?;
+        dest.put_slice(self.key.as_bytes());
+        self.value.encode(dest, version)
+    }
+}
+
+impl Decoder for Header {
+    fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), Error>
+    where
+        T: Buf,
+    {
+        let mut key_len: i64 = 0;
+        key_len.decode_varint(src)?;
+        if key_len < 0 || src.remaining() < key_len as usize {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "not enough bytes for header key",
+            ));
+        }
+        let mut key = vec![0; key_len as usize];
+        src.copy_to_slice(&mut key);
+        self.key = String::from_utf8(key)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err.utf8_error()))?;
+        self.value.decode(src, version)
+    }
+}
+
+/// Ordered list of record headers.
+///
+/// Keys are not required to be unique, the order in which headers were added is preserved.
+///
+/// # Examples
+///
+/// ```
+/// # use fluvio_protocol::record::RecordHeaders;
+/// let mut headers = RecordHeaders::default();
+/// headers.insert("content-type", "application/json");
+/// headers.insert("trace-id", vec![1, 2, 3]);
+/// assert_eq!(headers.len(), 2);
+/// assert_eq!(headers.get("content-type").unwrap().as_ref(), b"application/json");
+/// ```
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
+pub struct RecordHeaders(Vec<Header>);
+
+impl RecordHeaders {
+    /// append header, existing headers with same key are kept
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<RecordData>) {
+        self.0.push(Header::new(key, value));
+    }
+
+    /// return value of first header matching the key
+    pub fn get(&self, key: &str) -> Option<&RecordData> {
+        self.0
+            .iter()
+            .find(|header| header.key == key)
+            .map(|header| &header.value)
+    }
+
+    /// return values of all headers matching the key
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a RecordData> + 'a {
+        self.0
+            .iter()
+            .filter(move |header| header.key == key)
+            .map(|header| &header.value)
+    }
+
+    /// remove all headers matching the key
+    pub fn remove(&mut self, key: &str) {
+        self.0.retain(|header| header.key != key);
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Header> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for RecordHeaders
+where
+    K: Into<String>,
+    V: Into<RecordData>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(key, value)| Header::new(key, value))
+                .collect(),
+        )
+    }
+}
+
+impl From<Vec<Header>> for RecordHeaders {
+    fn from(headers: Vec<Header>) -> Self {
+        Self(headers)
+    }
+}
+
+impl IntoIterator for RecordHeaders {
+    type Item = Header;
+    type IntoIter = std::vec::IntoIter<Header>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Min API version of records with key/value headers.
+/// Older clients read headers as single varint, so headers are dropped for them.
+/// Version 0 is used for records at rest and always includes headers.
+pub const RECORD_HEADERS_VERSION: Version = 33;
+
+fn has_headers(version: Version) -> bool {
+    version == 0 || version >= RECORD_HEADERS_VERSION
+}
+
+// encoded as varint count followed by headers, same as kafka record headers.
+// empty headers are encoded as single 0x00 which is compatible with old records
+impl Encoder for RecordHeaders {
+    fn write_size(&self, version: Version) -> usize {
+        if !has_headers(version) {
+            return 0i64.var_write_size();
+        }
+        let count = self.0.len() as i64;
+        self.0.iter().fold(count.var_write_size(), |sum, header| {
+            sum + header.write_size(version)
+        })
+    }
+
+    fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), Error>
+    where
+        T: BufMut,
+    {
+        if !has_headers(version) {
+            return 0i64.encode_varint(dest);
+        }
+        let count = self.0.len() as i64;
+        count.encode_varint(dest)?;
+        for header in &self.0 {
+            header.encode(dest, version)?;
+        }
+        Ok(())
+    }
+}
+
+impl Decoder for RecordHeaders {
+    fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), Error>
+    where
+        T: Buf,
+    {
+        let mut count: i64 = 0;
+        count.decode_varint(src)?;
+        self.0.clear();
+        if !has_headers(version) {
+            return Ok(());
+        }
+        if count < 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid header count: {count}"),
+            ));
+        }
+        for _ in 0..count {
+            let mut header = Header::default();
+            header.decode(src, version)?;
+            self.0.push(header);
+        }
+        Ok(())
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct Record<B = RecordData> {
     pub preamble: RecordHeader,
     pub key: Option<B>,
     pub value: B,
-    pub headers: i64,
+    pub headers: RecordHeaders,
 }
 
 impl<B: Default> Record<B> {
@@ -457,6 +661,16 @@ impl<B: Default> Record<B> {
     pub fn into_key(self) -> Option<B> {
         self.key
     }
+
+    /// Returns a reference to the record headers
+    pub fn headers(&self) -> &RecordHeaders {
+        &self.headers
+    }
+
+    /// Returns a mutable reference to the record headers
+    pub fn headers_mut(&mut self) -> &mut RecordHeaders {
+        &mut self.headers
+    }
 }
 
 impl Record {
@@ -476,6 +690,18 @@ impl Record {
         }
     }
 
+    /// Replace headers of this record
+    pub fn with_headers(mut self, headers: impl Into<RecordHeaders>) -> Self {
+        self.headers = headers.into();
+        self
+    }
+
+    /// Append single header to this record
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<RecordData>) -> Self {
+        self.headers.insert(key, value);
+        self
+    }
+
     pub fn timestamp_delta(&self) -> Timestamp {
         self.preamble.timestamp_delta
     }
@@ -514,7 +740,7 @@ where
         let inner_size = self.preamble.write_size(version)
             + self.key.write_size(version)
             + self.value.write_size(version)
-            + self.headers.var_write_size();
+            + self.headers.write_size(version);
         let len: i64 = inner_size as i64;
         len.var_write_size() + inner_size
     }
@@ -527,7 +753,7 @@ where
         self.preamble.encode(&mut out, version)?;
         self.key.encode(&mut out, version)?;
         self.value.encode(&mut out, version)?;
-        self.headers.encode_varint(&mut out)?;
+        self.headers.encode(&mut out, version)?;
         let len: i64 = out.len() as i64;
         trace!("record encode as {} bytes", len);
         len.encode_varint(dest)?;
@@ -556,11 +782,17 @@ where
                 "not enough for record",
             ));
         }
-        self.preamble.decode(src, version)?;
+        let mut buf = src.take(len as usize);
+        self.preamble.decode(&mut buf, version)?;
         trace!("offset delta: {}", self.preamble.offset_delta);
-        self.key.decode(src, version)?;
-        self.value.decode(src, version)?;
-        self.headers.decode_varint(src)?;
+        self.key.decode(&mut buf, version)?;
+        self.value.decode(&mut buf, version)?;
+        self.headers.decode(&mut buf, version)?;
+        // skip unknown trailing bytes so next record is aligned
+        if buf.remaining() > 0 {
+            trace!(remaining = buf.remaining(), "skipping unread record bytes");
+            buf.advance(buf.remaining());
+        }
 
         Ok(())
     }
@@ -620,6 +852,11 @@ impl ConsumerRecord {
         self.inner().value().as_ref()
     }
 
+    /// Returns the headers of this Record
+    pub fn headers(&self) -> &RecordHeaders {
+        self.inner().headers()
+    }
+
     /// Return the timestamp of the Record
     pub fn timestamp(&self) -> Timestamp {
         if self.timestamp_base <= 0 {
@@ -729,6 +966,92 @@ mod test {
         assert_eq!(record.value.as_ref(), decoded.value.as_ref());
     }
 
+    #[test]
+    fn test_record_headers_encoding() {
+        let record = Record::new_key_value("key", "value")
+            .with_header("content-type", "application/json")
+            .with_header("trace-id", vec![0xde, 0xad, 0xbe, 0xef])
+            .with_header("trace-id", "second");
+
+        let mut encoded = Vec::new();
+        record.encode(&mut encoded, 0).unwrap();
+        assert_eq!(encoded.len(), record.write_size(0));
+
+        let decoded = Record::<RecordData>::decode_from(&mut Cursor::new(encoded), 0).unwrap();
+        assert_eq!(decoded.headers(), record.headers());
+        assert_eq!(decoded.headers().len(), 3);
+        assert_eq!(
+            decoded.headers().get("trace-id").unwrap().as_ref(),
+            &[0xde, 0xad, 0xbe, 0xef]
+        );
+        assert_eq!(decoded.headers().get_all("trace-id").count(), 2);
+        assert!(decoded.headers().get("missing").is_none());
+        assert_eq!(decoded.value.as_ref(), b"value");
+    }
+
+    #[test]
+    fn test_record_without_headers_is_compatible() {
+        // empty headers must be encoded as single zero varint, same as old `headers: i64` placeholder
+        let mut record = Record::new("dog");
+        record.preamble.set_offset_delta(1);
+        let mut encoded = Vec::new();
+        record.encode(&mut encoded, 0).unwrap();
+        assert_eq!(
+            encoded,
+            vec![0x12, 0x0, 0x0, 0x2, 0x0, 0x6, 0x64, 0x6f, 0x67, 0x0]
+        );
+    }
+
+    #[test]
+    fn test_decode_records_with_headers_in_sequence() {
+        let records = vec![
+            Record::new("a").with_header("h1", "v1"),
+            Record::new("b"),
+            Record::new("c")
+                .with_header("h2", "v2")
+                .with_header("h3", ""),
+        ];
+        let mut encoded = Vec::new();
+        records.encode(&mut encoded, 0).unwrap();
+
+        let decoded = Vec::<Record>::decode_from(&mut Cursor::new(encoded), 0).unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[0].headers().get("h1").unwrap().as_ref(), b"v1");
+        assert!(decoded[1].headers().is_empty());
+        assert_eq!(decoded[2].value.as_ref(), b"c");
+        assert_eq!(decoded[2].headers().get("h3").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_record_headers_dropped_for_older_version() {
+        let record = Record::new("dog").with_header("trace-id", "abc");
+        let version = RECORD_HEADERS_VERSION - 1;
+        let mut encoded = Vec::new();
+        record.encode(&mut encoded, version).unwrap();
+        assert_eq!(encoded.len(), record.write_size(version));
+        assert_eq!(encoded, {
+            let mut plain = Vec::new();
+            Record::new("dog").encode(&mut plain, 0).unwrap();
+            plain
+        });
+
+        // records with headers are skipped over when decoded by older version
+        let records = vec![record.clone(), Record::new("cat")];
+        let mut encoded = Vec::new();
+        records.encode(&mut encoded, 0).unwrap();
+        let decoded = Vec::<Record>::decode_from(&mut Cursor::new(encoded), version).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert!(decoded[0].headers().is_empty());
+        assert_eq!(decoded[1].value.as_ref(), b"cat");
+
+        let mut encoded = Vec::new();
+        record.encode(&mut encoded, RECORD_HEADERS_VERSION).unwrap();
+        let decoded =
+            Record::<RecordData>::decode_from(&mut Cursor::new(encoded), RECORD_HEADERS_VERSION)
+                .unwrap();
+        assert_eq!(decoded.headers(), record.headers());
+    }
+
     // Test Specification:
     //
     // A record was encoded and written to a file, using the following code:
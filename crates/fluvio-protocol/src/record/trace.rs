@@ -0,0 +1,207 @@
+//!
+//! # Trace Context
+//!
+//! W3C trace context carried in `traceparent` record header,
+//! so spans of producers, SPU and consumers can be linked into one trace.
+//! See <https://www.w3.org/TR/trace-context/#traceparent-header>
+//!
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::{ConsumerRecord, Record};
+
+/// record header holding trace context
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+const VERSION: u8 = 0;
+const SAMPLED_FLAG: u8 = 0x01;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    flags: u8,
+}
+
+impl TraceContext {
+    /// trace and span ids must not be all zeros
+    pub fn new(trace_id: [u8; 16], span_id: [u8; 8], sampled: bool) -> Option<Self> {
+        if trace_id == [0; 16] || span_id == [0; 8] {
+            return None;
+        }
+        Some(Self {
+            trace_id,
+            span_id,
+            flags: if sampled { SAMPLED_FLAG } else { 0 },
+        })
+    }
+
+    pub fn trace_id(&self) -> [u8; 16] {
+        self.trace_id
+    }
+
+    /// id of span which produced the record
+    pub fn span_id(&self) -> [u8; 8] {
+        self.span_id
+    }
+
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    pub fn is_sampled(&self) -> bool {
+        self.flags & SAMPLED_FLAG != 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct InvalidTraceparent;
+
+impl fmt::Display for InvalidTraceparent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid traceparent")
+    }
+}
+
+impl std::error::Error for InvalidTraceparent {}
+
+impl FromStr for TraceContext {
+    type Err = InvalidTraceparent;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut parts = value.trim().split('-');
+        let version = parts.next().ok_or(InvalidTraceparent)?;
+        let trace_id = parts.next().ok_or(InvalidTraceparent)?;
+        let span_id = parts.next().ok_or(InvalidTraceparent)?;
+        let flags = parts.next().ok_or(InvalidTraceparent)?;
+
+        let [version] = decode_hex::<1>(version)?;
+        // future versions may append fields, but version ff is forbidden
+        if version == 0xff || (version == VERSION && parts.next().is_some()) {
+            return Err(InvalidTraceparent);
+        }
+        let [flags] = decode_hex::<1>(flags)?;
+        let context = Self::new(decode_hex(trace_id)?, decode_hex(span_id)?, false)
+            .ok_or(InvalidTraceparent)?;
+        Ok(Self { flags, ..context })
+    }
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{VERSION:02x}-")?;
+        for byte in self.trace_id {
+            write!(f, "{byte:02x}")?;
+        }
+        write!(f, "-")?;
+        for byte in self.span_id {
+            write!(f, "{byte:02x}")?;
+        }
+        write!(f, "-{:02x}", self.flags)
+    }
+}
+
+/// decode lower case hex string of exactly N bytes
+fn decode_hex<const N: usize>(value: &str) -> Result<[u8; N], InvalidTraceparent> {
+    let value = value.as_bytes();
+    if value.len() != N * 2 {
+        return Err(InvalidTraceparent);
+    }
+    let digit = |ch: u8| match ch {
+        b'0'..=b'9' => Ok(ch - b'0'),
+        b'a'..=b'f' => Ok(ch - b'a' + 10),
+        _ => Err(InvalidTraceparent),
+    };
+    let mut bytes = [0; N];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = digit(value[index * 2])? << 4 | digit(value[index * 2 + 1])?;
+    }
+    Ok(bytes)
+}
+
+impl Record {
+    /// trace context of `traceparent` header, invalid header is ignored
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        let value = self.headers.get(TRACEPARENT_HEADER)?;
+        std::str::from_utf8(value.as_ref()).ok()?.parse().ok()
+    }
+
+    /// replace `traceparent` header
+    pub fn set_trace_context(&mut self, context: &TraceContext) {
+        self.headers.remove(TRACEPARENT_HEADER);
+        self.headers
+            .insert(TRACEPARENT_HEADER, context.to_string().into_bytes());
+    }
+
+    /// Attach trace context to this record
+    pub fn with_trace_context(mut self, context: &TraceContext) -> Self {
+        self.set_trace_context(context);
+        self
+    }
+}
+
+impl ConsumerRecord {
+    /// trace context attached by producer, to link consumer spans
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        self.inner().trace_context()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn test_traceparent_round_trip() {
+        let context: TraceContext = TRACEPARENT.parse().expect("parse");
+        assert!(context.is_sampled());
+        assert_eq!(
+            context.span_id(),
+            [0, 0xf0, 0x67, 0xaa, 0x0b, 0xa9, 0x02, 0xb7]
+        );
+        assert_eq!(context.to_string(), TRACEPARENT);
+
+        // unknown flags are preserved
+        let context: TraceContext = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-02"
+            .parse()
+            .expect("parse");
+        assert!(!context.is_sampled());
+        assert_eq!(context.flags(), 2);
+    }
+
+    #[test]
+    fn test_invalid_traceparent() {
+        for invalid in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-00",
+        ] {
+            assert!(invalid.parse::<TraceContext>().is_err(), "{invalid}");
+        }
+        // later versions may add fields
+        assert!(
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra"
+                .parse::<TraceContext>()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_record_trace_context() {
+        let context: TraceContext = TRACEPARENT.parse().expect("parse");
+        let mut record = Record::new("value").with_header(TRACEPARENT_HEADER, "garbage");
+        assert_eq!(record.trace_context(), None);
+
+        record.set_trace_context(&context);
+        assert_eq!(record.headers().len(), 1);
+        assert_eq!(record.trace_context(), Some(context));
+    }
+}
@@ -10,6 +10,9 @@ pub mod shared;
 pub mod tableformat;
 pub mod mirror;
 pub mod mirroring;
+pub mod schema;
+pub mod policy;
+pub mod quota;
 
 pub mod remote_file;
 
@@ -77,6 +80,24 @@ mod error {
                 ApiError::Code(ErrorCode::TableFormatNotFound, _) => {
                     write!(f, "TableFormat not found")
                 }
+                ApiError::Code(ErrorCode::SchemaAlreadyExists, _) => {
+                    write!(f, "Schema already exists")
+                }
+                ApiError::Code(ErrorCode::SchemaNotFound, _) => {
+                    write!(f, "Schema not found")
+                }
+                ApiError::Code(ErrorCode::PolicyAlreadyExists, _) => {
+                    write!(f, "Policy already exists")
+                }
+                ApiError::Code(ErrorCode::PolicyNotFound, _) => {
+                    write!(f, "Policy not found")
+                }
+                ApiError::Code(ErrorCode::QuotaAlreadyExists, _) => {
+                    write!(f, "Quota already exists")
+                }
+                ApiError::Code(ErrorCode::QuotaNotFound, _) => {
+                    write!(f, "Quota not found")
+                }
                 ApiError::Code(_, Some(msg)) => {
                     write!(f, "{msg}")
                 }
@@ -427,6 +427,9 @@ mod create {
     use crate::smartmodule::SmartModuleSpec;
     use crate::tableformat::TableFormatSpec;
     use crate::spg::SpuGroupSpec;
+    use crate::schema::SchemaSpec;
+    use crate::policy::PolicySpec;
+    use crate::quota::QuotaSpec;
 
     #[derive(Debug, Default, Encoder, Decoder)]
     pub struct ClassicObjectApiCreateRequest {
@@ -674,4 +677,13 @@ mod create {
             }
         }
     }
+
+    /// schemas were introduced after classic protocol, they can only be created as dynamic objects
+    impl ClassicCreatableAdminSpec for SchemaSpec {}
+
+    /// policies were introduced after classic protocol, they can only be created as dynamic objects
+    impl ClassicCreatableAdminSpec for PolicySpec {}
+
+    /// quotas were introduced after classic protocol, they can only be created as dynamic objects
+    impl ClassicCreatableAdminSpec for QuotaSpec {}
 }
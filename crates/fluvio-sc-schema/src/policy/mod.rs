@@ -0,0 +1,22 @@
+pub use fluvio_controlplane_metadata::policy::*;
+
+mod convert {
+
+    use crate::{CreatableAdminSpec, DeletableAdminSpec, UpdatableAdminSpec};
+
+    use crate::AdminSpec;
+    use super::{PolicySpec, UpdatePolicyAction};
+
+    impl AdminSpec for PolicySpec {}
+
+    impl CreatableAdminSpec for PolicySpec {}
+
+    impl DeletableAdminSpec for PolicySpec {
+        type DeleteKey = String;
+    }
+
+    impl UpdatableAdminSpec for PolicySpec {
+        type UpdateKey = String;
+        type UpdateAction = UpdatePolicyAction;
+    }
+}
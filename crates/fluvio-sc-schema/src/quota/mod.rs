@@ -0,0 +1,22 @@
+pub use fluvio_controlplane_metadata::quota::*;
+
+mod convert {
+
+    use crate::{CreatableAdminSpec, DeletableAdminSpec, UpdatableAdminSpec};
+
+    use crate::AdminSpec;
+    use super::{QuotaSpec, UpdateQuotaAction};
+
+    impl AdminSpec for QuotaSpec {}
+
+    impl CreatableAdminSpec for QuotaSpec {}
+
+    impl DeletableAdminSpec for QuotaSpec {
+        type DeleteKey = String;
+    }
+
+    impl UpdatableAdminSpec for QuotaSpec {
+        type UpdateKey = String;
+        type UpdateAction = UpdateQuotaAction;
+    }
+}
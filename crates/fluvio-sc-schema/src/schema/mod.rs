@@ -0,0 +1,22 @@
+pub use fluvio_controlplane_metadata::schema::*;
+
+mod convert {
+
+    use crate::{CreatableAdminSpec, DeletableAdminSpec, UpdatableAdminSpec};
+
+    use crate::AdminSpec;
+    use super::{SchemaSpec, UpdateSchemaAction};
+
+    impl AdminSpec for SchemaSpec {}
+
+    impl CreatableAdminSpec for SchemaSpec {}
+
+    impl DeletableAdminSpec for SchemaSpec {
+        type DeleteKey = String;
+    }
+
+    impl UpdatableAdminSpec for SchemaSpec {
+        type UpdateKey = String;
+        type UpdateAction = UpdateSchemaAction;
+    }
+}
@@ -11,9 +11,9 @@
 use std::path::Path;
 use std::process;
 use std::path::PathBuf;
-use std::convert::TryFrom;
+use std::collections::BTreeMap;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use clap::Args;
 use tracing::info;
 use tracing::debug;
@@ -24,10 +24,13 @@ use fluvio_types::defaults::TLS_SERVER_SECRET_NAME;
 use fluvio_future::openssl::TlsAcceptor;
 use fluvio_future::openssl::SslVerifyMode;
 
-use crate::services::auth::basic::BasicRbacPolicy;
+use fluvio_controlplane_metadata::policy::PolicySpec;
+
+use crate::services::auth::basic::read_policy;
 use crate::config::ScConfig;
+use crate::ha::{HaConfig, ScId};
 
-type Config = (ScConfig, Option<BasicRbacPolicy>);
+type Config = (ScConfig, Option<PolicySpec>);
 
 /// cli options
 #[derive(Debug, Parser)]
@@ -68,6 +71,38 @@ pub struct ScOpt {
     /// only allow white list of controllers
     #[arg(long)]
     white_list: Vec<String>,
+
+    /// Serve OpenMetrics over HTTP on this address
+    #[arg(long, value_name = "host:port", env = "FLV_METRICS_ADDR")]
+    metrics_addr: Option<String>,
+
+    /// Id of this SC in highly-available local mode
+    #[arg(long, value_name = "id", requires_all = ["ha_bind", "ha_peer"])]
+    ha_id: Option<ScId>,
+
+    /// Address for connections from peer SCs in highly-available local mode
+    #[arg(long, value_name = "host:port", requires = "ha_id")]
+    ha_bind: Option<String>,
+
+    /// Peer SC in highly-available local mode, repeat for every peer
+    #[arg(
+        long,
+        value_name = "id=host:port",
+        value_parser = parse_ha_peer,
+        requires = "ha_id"
+    )]
+    ha_peer: Vec<(ScId, String)>,
+}
+
+fn parse_ha_peer(value: &str) -> Result<(ScId, String)> {
+    let (id, addr) = value
+        .split_once('=')
+        .ok_or_else(|| anyhow!("peer must be in form id=host:port"))?;
+    let id = id
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("invalid peer id: {id}"))?;
+    Ok((id, addr.trim().to_string()))
 }
 
 #[derive(Debug, Args)]
@@ -107,6 +142,30 @@ impl ScOpt {
         }
     }
 
+    /// configuration of highly-available mode, `None` if SC runs standalone
+    pub fn ha_config(&self) -> Result<Option<HaConfig>> {
+        let (Some(id), Some(bind)) = (self.ha_id, &self.ha_bind) else {
+            return Ok(None);
+        };
+        if self.run_mode.local.is_none() {
+            bail!("highly-available mode requires local metadata");
+        }
+        let mut peers = BTreeMap::new();
+        for (peer, addr) in &self.ha_peer {
+            if *peer == id {
+                bail!("peer id {peer} is the same as id of this SC");
+            }
+            if peers.insert(*peer, addr.clone()).is_some() {
+                bail!("duplicate peer id {peer}");
+            }
+        }
+        Ok(Some(HaConfig {
+            id,
+            bind: bind.clone(),
+            peers,
+        }))
+    }
+
     /// as sc configuration, 2nd part of tls configuration(proxy addr, tls config)
     /// 3rd part is path to read only metadata config
     #[allow(clippy::wrong_self_convention)]
@@ -129,15 +188,17 @@ impl ScOpt {
         config.x509_auth_scopes = self.x509_auth_scopes;
         config.white_list = self.white_list.into_iter().collect();
         config.read_only_metadata = self.run_mode.read_only.is_some();
+        config.metrics_addr = self.metrics_addr;
 
         // Set Configuration Authorization Policy
 
-        let policy = match self.auth_policy {
+        let policy = match &self.auth_policy {
             // Lookup a policy from a path
-            Some(p) => Some(BasicRbacPolicy::try_from(p)?),
+            Some(p) => Some(read_policy(p)?),
             // Use root-only default policy if no policy path is found;
             None => None,
         };
+        config.auth_policy = self.auth_policy;
 
         let mut tls = self.tls;
 
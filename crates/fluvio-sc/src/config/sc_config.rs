@@ -28,7 +28,11 @@ pub struct ScConfig {
     pub private_endpoint: String,
     pub namespace: String,
     pub x509_auth_scopes: Option<PathBuf>,
+    /// policy file, reloaded on change
+    pub auth_policy: Option<PathBuf>,
     pub white_list: HashSet<String>,
+    /// http address of OpenMetrics endpoint
+    pub metrics_addr: Option<String>,
 }
 
 impl ::std::default::Default for ScConfig {
@@ -39,7 +43,9 @@ impl ::std::default::Default for ScConfig {
             private_endpoint: format!("0.0.0.0:{SC_PRIVATE_PORT}"),
             namespace: DEFAULT_NAMESPACE.to_owned(),
             x509_auth_scopes: None,
+            auth_policy: None,
             white_list: HashSet::new(),
+            metrics_addr: None,
         }
     }
 }
@@ -2,7 +2,8 @@
 //! # Partition Controller
 //!
 
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use fluvio_controlplane_metadata::store::ChangeListener;
 use fluvio_future::timer::sleep;
@@ -12,9 +13,11 @@ use fluvio_future::task::spawn;
 use fluvio_controlplane_metadata::core::MetadataItem;
 use fluvio_controlplane_metadata::store::k8::K8MetaItem;
 
+use crate::core::ScMetrics;
 use crate::stores::StoreContext;
 use crate::stores::partition::PartitionSpec;
 use crate::stores::spu::SpuSpec;
+use crate::stores::topic::TopicSpec;
 
 use super::reducer::PartitionReducer;
 
@@ -23,18 +26,31 @@ use super::reducer::PartitionReducer;
 pub struct PartitionController<C: MetadataItem = K8MetaItem> {
     partitions: StoreContext<PartitionSpec, C>,
     spus: StoreContext<SpuSpec, C>,
+    topics: StoreContext<TopicSpec, C>,
     reducer: PartitionReducer<C>,
+    metrics: Arc<ScMetrics>,
 }
 
 impl<C> PartitionController<C>
 where
     C: MetadataItem + 'static,
 {
-    pub fn start(partitions: StoreContext<PartitionSpec, C>, spus: StoreContext<SpuSpec, C>) {
+    pub fn start(
+        partitions: StoreContext<PartitionSpec, C>,
+        spus: StoreContext<SpuSpec, C>,
+        topics: StoreContext<TopicSpec, C>,
+        metrics: Arc<ScMetrics>,
+    ) {
         let controller = Self {
-            reducer: PartitionReducer::new(partitions.store().clone(), spus.store().clone()),
+            reducer: PartitionReducer::new(
+                partitions.store().clone(),
+                spus.store().clone(),
+                topics.store().clone(),
+            ),
             partitions,
             spus,
+            topics,
+            metrics,
         };
 
         spawn(controller.dispatch_loop());
@@ -70,8 +86,10 @@ where
         debug!("finish initializing listeners");
 
         loop {
+            let start = Instant::now();
             self.sync_spu_changes(&mut spu_status_listener).await;
             self.sync_partition_changes(&mut partition_listener).await;
+            self.metrics.record_reconcile("partition", start.elapsed());
 
             trace!("waiting for events");
 
@@ -97,22 +115,26 @@ where
             return;
         }
 
-        // we only care about delete timestamp changes which are in metadata only
-        let changes = listener.sync_meta_changes().await;
+        // delete timestamp is in metadata, replica progress of reassignment and election is in status
+        let changes = listener.sync_changes().await;
         if changes.is_empty() {
-            debug!("no partition metadata changes");
+            debug!("no partition changes");
             return;
         }
 
         let (updates, _) = changes.parts();
-        trace!(meta_changes = &*format!("{updates:#?}"), "metadata changes");
+        trace!(changes = &*format!("{updates:#?}"), "partition changes");
 
         let actions = self.reducer.process_partition_update(updates).await;
+        let topic_actions = self.reducer.process_finished_reassignments(&actions).await;
 
         debug!("generated partition actions: {}", actions.len());
         for action in actions.into_iter() {
             self.partitions.send_action(action).await;
         }
+        for action in topic_actions.into_iter() {
+            self.topics.send_action(action).await;
+        }
     }
 
     /// sync spu states to partition
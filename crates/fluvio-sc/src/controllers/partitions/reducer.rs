@@ -3,22 +3,26 @@
 //!
 //! Partition metadata information on cached in the local Controller.
 //!
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 
 use fluvio_controlplane::PartitionMetadata;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, warn, instrument};
 
 use fluvio_controlplane_metadata::store::k8::K8MetaItem;
 use fluvio_controlplane_metadata::core::MetadataItem;
+use fluvio_types::SpuId;
 
 use crate::stores::partition::{
-    PartitionSpec, PartitionResolution, PartitionLocalStore, SimplePolicy, PartitonStatusExtension,
-    ElectionPolicy,
+    PartitionSpec, PartitionResolution, PartitionLocalStore, SimplePolicy, UncleanPolicy,
+    PartitonStatusExtension, ElectionPolicy,
 };
 use crate::stores::actions::WSAction;
 use crate::stores::spu::{SpuLocalStorePolicy, SpuLocalStore, SpuMetadata};
+use crate::stores::topic::{ReplicaSpec, TopicLocalStore, TopicMetadata, TopicSpec};
 
 type PartitionWSAction<C = K8MetaItem> = WSAction<PartitionSpec, C>;
+type TopicWSAction<C = K8MetaItem> = WSAction<TopicSpec, C>;
 
 /// Given This is a generated partition from TopicController, It will try to allocate assign replicas
 /// to live SPU.
@@ -58,6 +62,7 @@ type PartitionWSAction<C = K8MetaItem> = WSAction<PartitionSpec, C>;
 pub struct PartitionReducer<C: MetadataItem = K8MetaItem> {
     partition_store: Arc<PartitionLocalStore<C>>,
     spu_store: Arc<SpuLocalStore<C>>,
+    topic_store: Arc<TopicLocalStore<C>>,
 }
 
 impl<C: MetadataItem> Default for PartitionReducer<C> {
@@ -65,6 +70,7 @@ impl<C: MetadataItem> Default for PartitionReducer<C> {
         Self {
             partition_store: PartitionLocalStore::new_shared(),
             spu_store: SpuLocalStore::new_shared(),
+            topic_store: TopicLocalStore::new_shared(),
         }
     }
 }
@@ -73,10 +79,12 @@ impl<C: MetadataItem> PartitionReducer<C> {
     pub fn new(
         partition_store: impl Into<Arc<PartitionLocalStore<C>>>,
         spu_store: impl Into<Arc<SpuLocalStore<C>>>,
+        topic_store: impl Into<Arc<TopicLocalStore<C>>>,
     ) -> Self {
         Self {
             partition_store: partition_store.into(),
             spu_store: spu_store.into(),
+            topic_store: topic_store.into(),
         }
     }
 
@@ -85,21 +93,88 @@ impl<C: MetadataItem> PartitionReducer<C> {
         &self,
         updates: Vec<PartitionMetadata<C>>,
     ) -> Vec<PartitionWSAction<C>> {
-        // reconcile delete timestamp in the metadata with delete status
-        updates
-            .into_iter()
-            .filter_map(|partition| {
-                if partition.ctx().item().is_being_deleted() && !partition.status.is_being_deleted {
+        let online_spus = self.spu_store.online_status().await;
+        let mut actions = vec![];
+        for partition in updates {
+            // reconcile delete timestamp in the metadata with delete status
+            if partition.ctx().item().is_being_deleted() {
+                if !partition.status.is_being_deleted {
                     debug!(partition = ?partition.key(), "set partition to delete");
-                    Some(PartitionWSAction::UpdateStatus((
+                    actions.push(PartitionWSAction::UpdateStatus((
                         partition.key,
                         partition.status.set_to_delete(),
-                    )))
-                } else {
-                    None
+                    )));
                 }
-            })
-            .collect()
+                continue;
+            }
+
+            if let Some(action) = reconcile_replicas(partition, &online_spus) {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+
+    /// Update topics of partitions whose reassignment is finished by partition actions.
+    /// Replica map in topic status is set to new replicas of partition, as well as partition maps
+    /// of topic with assigned replicas, so topic is not left pointing to dropped replicas.
+    #[instrument(skip(self, actions))]
+    pub async fn process_finished_reassignments(
+        &self,
+        actions: &[PartitionWSAction<C>],
+    ) -> Vec<TopicWSAction<C>> {
+        let mut topics: BTreeMap<String, TopicMetadata<C>> = BTreeMap::new();
+        for action in actions {
+            let PartitionWSAction::UpdateSpec((key, spec)) = action else {
+                continue;
+            };
+            if spec.reassignment.is_some() {
+                continue;
+            }
+            let Some(partition) = self.partition_store.value(key).await else {
+                continue;
+            };
+            if partition.inner().spec.reassignment.is_none() {
+                continue;
+            }
+
+            if !topics.contains_key(&key.topic) {
+                let Some(topic) = self.topic_store.value(&key.topic).await else {
+                    warn!(partition = %key, "topic of reassigned partition not found");
+                    continue;
+                };
+                topics.insert(key.topic.clone(), topic.inner_owned());
+            }
+            let Some(topic) = topics.get_mut(&key.topic) else {
+                continue;
+            };
+
+            topic
+                .status
+                .replica_map
+                .insert(key.partition, spec.replicas.clone());
+            if let ReplicaSpec::Assigned(maps) = topic.spec.replicas() {
+                let mut maps = maps.maps().clone();
+                for map in maps.iter_mut().filter(|map| map.id == key.partition) {
+                    map.replicas = spec.replicas.clone();
+                }
+                topic.spec.set_replicas(ReplicaSpec::Assigned(maps.into()));
+            }
+            info!(
+                partition = %key,
+                replicas = ?spec.replicas,
+                "updating topic with reassigned replicas",
+            );
+        }
+
+        let mut actions = vec![];
+        for (name, topic) in topics {
+            if matches!(topic.spec.replicas(), ReplicaSpec::Assigned(_)) {
+                actions.push(TopicWSAction::UpdateSpec((name.clone(), topic.spec)));
+            }
+            actions.push(TopicWSAction::UpdateStatus((name, topic.status)));
+        }
+        actions
     }
 
     ///
@@ -148,18 +223,44 @@ impl<C: MetadataItem> PartitionReducer<C> {
         let spu_status = self.spu_store.online_status().await;
 
         let policy = SimplePolicy::new();
+        let unclean_policy = UncleanPolicy::new();
 
         // go thru each partitions whose leader matches offline spu.
         for partition_kv_epoch in self.partition_store.read().await.values() {
             let partition_kv = partition_kv_epoch.inner();
             // find partition who's leader is same as offline spu
             if partition_kv.spec.leader == offline_leader_spu_id {
-                // find suitable leader
-                if let Some(candidate_leader) =
-                    partition_kv.status.candidate_leader(&spu_status, &policy)
-                {
+                // find suitable leader, out of sync replica is only considered if topic allows it
+                let candidate = partition_kv
+                    .status
+                    .candidate_leader(&spu_status, &policy)
+                    .or_else(|| {
+                        if partition_kv.spec.unclean_leader_election {
+                            partition_kv
+                                .status
+                                .candidate_leader(&spu_status, &unclean_policy)
+                        } else {
+                            None
+                        }
+                    });
+                if let Some(candidate_leader) = candidate {
+                    if let Some(candidate_status) = partition_kv
+                        .status
+                        .replica_iter()
+                        .find(|status| status.spu == candidate_leader)
+                    {
+                        if candidate_status.leo < partition_kv.status.leader.hw {
+                            warn!(
+                                partition = %partition_kv.key(),
+                                candidate_leader,
+                                leo = candidate_status.leo,
+                                hw = partition_kv.status.leader.hw,
+                                "unclean leader election, committed records may be lost",
+                            );
+                        }
+                    }
                     let mut part_kv_change = partition_kv.clone();
-                    part_kv_change.spec.leader = candidate_leader;
+                    part_kv_change.spec.set_leader(candidate_leader);
 
                     // we only change leader, status happens next cycle
                     actions.push(PartitionWSAction::UpdateSpec((
@@ -211,6 +312,7 @@ impl<C: MetadataItem> PartitionReducer<C> {
         let online_leader_spu_id = online_spu.spec.id;
 
         let policy = SimplePolicy::new();
+        let unclean_policy = UncleanPolicy::new();
         // go thru each partitions which are not online and try to promote given online spu
 
         for partition_kv_epoch in self.partition_store.read().await.values() {
@@ -219,13 +321,16 @@ impl<C: MetadataItem> PartitionReducer<C> {
                 if partition_kv.spec.leader != online_leader_spu_id {
                     // switch leader if online leader is different
                     for replica_status in partition_kv.status.replica_iter() {
-                        if replica_status.spu == online_leader_spu_id
-                            && policy
+                        let score = if partition_kv.spec.unclean_leader_election {
+                            unclean_policy
+                                .potential_leader_score(replica_status, &partition_kv.status.leader)
+                        } else {
+                            policy
                                 .potential_leader_score(replica_status, &partition_kv.status.leader)
-                                .is_suitable()
-                        {
+                        };
+                        if replica_status.spu == online_leader_spu_id && score.is_suitable() {
                             let mut part_kv_change = partition_kv.clone();
-                            part_kv_change.spec.leader = online_leader_spu_id;
+                            part_kv_change.spec.set_leader(online_leader_spu_id);
                             actions.push(PartitionWSAction::UpdateSpec((
                                 part_kv_change.key_owned(),
                                 part_kv_change.spec,
@@ -258,6 +363,87 @@ impl<C: MetadataItem> PartitionReducer<C> {
     }
 }
 
+/// Move partition replicas and leader toward their assignment, based on replica status reported by leader:
+/// * reassignment is finished once all new replicas are in sync, leader is moved first if it is being dropped
+/// * status of dropped replicas is removed
+/// * leader is moved back to preferred leader once it is online and in sync
+fn reconcile_replicas<C: MetadataItem>(
+    partition: PartitionMetadata<C>,
+    online_spus: &HashSet<SpuId>,
+) -> Option<PartitionWSAction<C>> {
+    let spec = &partition.spec;
+    let status = &partition.status;
+
+    // wait until leader has reported its status
+    if status.leader.spu != spec.leader || status.is_being_deleted {
+        return None;
+    }
+
+    let candidate = |spu: &SpuId| online_spus.contains(spu) && status.is_in_sync(*spu);
+
+    if let Some(reassignment) = &spec.reassignment {
+        if !reassignment
+            .adding_replicas
+            .iter()
+            .all(|spu| status.is_in_sync(*spu))
+        {
+            return None;
+        }
+
+        let mut new_spec = spec.clone();
+        if reassignment.target_replicas.contains(&spec.leader) {
+            info!(
+                partition = %partition.key(),
+                replicas = ?reassignment.target_replicas,
+                "reassignment finished",
+            );
+            new_spec.finish_reassignment();
+        } else {
+            let leader = reassignment
+                .target_replicas
+                .iter()
+                .find(|spu| candidate(spu))?;
+            info!(
+                partition = %partition.key(),
+                leader,
+                "moving leader to reassigned replica",
+            );
+            new_spec.set_leader(*leader);
+        }
+        return Some(PartitionWSAction::UpdateSpec((
+            partition.key_owned(),
+            new_spec,
+        )));
+    }
+
+    if status.replica_iter().any(|lrs| !spec.has_spu(&lrs.spu)) {
+        let mut new_status = status.clone();
+        new_status.replicas.retain(|lrs| spec.has_spu(&lrs.spu));
+        debug!(partition = %partition.key(), "removing status of dropped replicas");
+        return Some(PartitionWSAction::UpdateStatus((
+            partition.key_owned(),
+            new_status,
+        )));
+    }
+
+    let preferred = spec.preferred_leader()?;
+    if preferred != spec.leader && status.is_online() && candidate(&preferred) {
+        info!(
+            partition = %partition.key(),
+            leader = preferred,
+            "moving leader back to preferred replica",
+        );
+        let mut new_spec = spec.clone();
+        new_spec.set_leader(preferred);
+        return Some(PartitionWSAction::UpdateSpec((
+            partition.key_owned(),
+            new_spec,
+        )));
+    }
+
+    None
+}
+
 // -----------------------------------
 //  Unit Tests
 //      >> utils::init_logger();
@@ -267,6 +453,230 @@ impl<C: MetadataItem> PartitionReducer<C> {
 #[cfg(test)]
 pub mod test {
 
+    use fluvio_controlplane_metadata::partition::{PartitionStatus, ReplicaStatus};
+
+    use crate::stores::partition::PartitionAdminMd;
+    use crate::stores::spu::SpuMd;
+    use crate::stores::topic::{TopicAdminMd, TopicStatus};
+
+    use super::*;
+
+    fn reducer(spus: Vec<(SpuId, bool, Option<String>)>) -> PartitionReducer<K8MetaItem> {
+        PartitionReducer::new(
+            PartitionLocalStore::new_shared(),
+            SpuLocalStore::quick(spus),
+            TopicLocalStore::new_shared(),
+        )
+    }
+
+    fn partition(spec: PartitionSpec, status: PartitionStatus) -> PartitionAdminMd {
+        PartitionAdminMd::new(("topic1", 0), spec, status)
+    }
+
+    #[fluvio_future::test]
+    async fn test_reassignment_waits_for_new_replicas() {
+        let reducer = reducer(vec![(0, true, None), (1, true, None), (2, true, None)]);
+        let mut spec: PartitionSpec = vec![0, 1].into();
+        spec.start_reassignment(vec![0, 2], 0);
+        assert_eq!(spec.replicas, vec![0, 1, 2]);
+
+        // new replica is behind leader
+        let status = PartitionStatus::new(
+            (0, 10, 10),
+            vec![ReplicaStatus::new(1, 10, 10), ReplicaStatus::new(2, 5, 5)],
+        );
+        let actions = reducer
+            .process_partition_update(vec![partition(spec.clone(), status)])
+            .await;
+        assert!(actions.is_empty());
+
+        // new replica caught up, old replica is dropped
+        let status = PartitionStatus::new(
+            (0, 10, 10),
+            vec![ReplicaStatus::new(1, 10, 10), ReplicaStatus::new(2, 10, 10)],
+        );
+        let actions = reducer
+            .process_partition_update(vec![partition(spec.clone(), status.clone())])
+            .await;
+        let mut expected = spec.clone();
+        expected.finish_reassignment();
+        assert_eq!(expected.replicas, vec![0, 2]);
+        assert_eq!(
+            actions,
+            vec![PartitionWSAction::UpdateSpec((
+                ("topic1", 0).into(),
+                expected.clone()
+            ))]
+        );
+
+        // status of dropped replica is removed
+        let actions = reducer
+            .process_partition_update(vec![partition(expected, status)])
+            .await;
+        assert_eq!(
+            actions,
+            vec![PartitionWSAction::UpdateStatus((
+                ("topic1", 0).into(),
+                PartitionStatus::new((0, 10, 10), vec![ReplicaStatus::new(2, 10, 10)])
+            ))]
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_finished_reassignment_updates_topic() {
+        let mut spec: PartitionSpec = vec![0, 1].into();
+        spec.start_reassignment(vec![0, 2], 0);
+        let status = PartitionStatus::new(
+            (0, 10, 10),
+            vec![ReplicaStatus::new(1, 10, 10), ReplicaStatus::new(2, 10, 10)],
+        );
+        let topic_status = TopicStatus {
+            replica_map: BTreeMap::from([(0, vec![0, 1])]),
+            ..Default::default()
+        };
+        let reducer = PartitionReducer::new(
+            PartitionLocalStore::bulk_new(vec![partition(spec.clone(), status.clone())]),
+            SpuLocalStore::quick(vec![(0, true, None), (1, true, None), (2, true, None)]),
+            TopicLocalStore::bulk_new(vec![TopicAdminMd::new(
+                "topic1",
+                TopicSpec::new_assigned(vec![(0, vec![0, 1])]),
+                topic_status,
+            )]),
+        );
+
+        let actions = reducer
+            .process_partition_update(vec![partition(spec, status)])
+            .await;
+        let topic_actions = reducer.process_finished_reassignments(&actions).await;
+
+        let expected_status = TopicStatus {
+            replica_map: BTreeMap::from([(0, vec![0, 2])]),
+            ..Default::default()
+        };
+        assert_eq!(
+            topic_actions,
+            vec![
+                TopicWSAction::UpdateSpec((
+                    "topic1".to_owned(),
+                    TopicSpec::new_assigned(vec![(0, vec![0, 2])])
+                )),
+                TopicWSAction::UpdateStatus(("topic1".to_owned(), expected_status)),
+            ]
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_reassignment_moves_leader_first() {
+        let reducer = reducer(vec![(0, true, None), (1, true, None), (2, true, None)]);
+        let mut spec: PartitionSpec = vec![0, 1].into();
+        spec.start_reassignment(vec![2, 1], 1000);
+        let status = PartitionStatus::new(
+            (0, 10, 10),
+            vec![ReplicaStatus::new(1, 10, 10), ReplicaStatus::new(2, 10, 10)],
+        );
+
+        let actions = reducer
+            .process_partition_update(vec![partition(spec.clone(), status)])
+            .await;
+        let mut expected = spec.clone();
+        expected.set_leader(2);
+        assert_eq!(
+            actions,
+            vec![PartitionWSAction::UpdateSpec((
+                ("topic1", 0).into(),
+                expected
+            ))]
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_preferred_leader_election() {
+        let spec = PartitionSpec {
+            leader: 1,
+            ..vec![0, 1].into()
+        };
+        let mut status = PartitionStatus::new((1, 10, 10), vec![ReplicaStatus::new(0, 10, 10)]);
+        status.resolution = PartitionResolution::Online;
+
+        // preferred leader is offline
+        let actions = reducer(vec![(0, false, None), (1, true, None)])
+            .process_partition_update(vec![partition(spec.clone(), status.clone())])
+            .await;
+        assert!(actions.is_empty());
+
+        // preferred leader is online but not in sync
+        let mut lagging = status.clone();
+        lagging.replicas = vec![ReplicaStatus::new(0, 8, 8)];
+        let reducer = reducer(vec![(0, true, None), (1, true, None)]);
+        let actions = reducer
+            .process_partition_update(vec![partition(spec.clone(), lagging)])
+            .await;
+        assert!(actions.is_empty());
+
+        let actions = reducer
+            .process_partition_update(vec![partition(spec.clone(), status)])
+            .await;
+        let mut expected = spec;
+        expected.set_leader(0);
+        assert_eq!(
+            actions,
+            vec![PartitionWSAction::UpdateSpec((
+                ("topic1", 0).into(),
+                expected
+            ))]
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_unclean_leader_election() {
+        let mut offline = SpuMetadata::quick(("spu-0", 0, false, None));
+        offline.status.set_offline();
+        // replica 1 is missing committed records
+        let status = PartitionStatus::new((0, 10, 10), vec![ReplicaStatus::new(1, 5, 5)]);
+        let spu_store = Arc::new(SpuLocalStore::quick(vec![
+            (0, false, None),
+            (1, true, None),
+        ]));
+
+        // out of sync replica is not elected, partition goes offline
+        let mut spec: PartitionSpec = vec![0, 1].into();
+        let mut online_status = status.clone();
+        online_status.resolution = PartitionResolution::Online;
+        let reducer = PartitionReducer::new(
+            PartitionLocalStore::bulk_new(vec![partition(spec.clone(), online_status)]),
+            spu_store.clone(),
+            TopicLocalStore::new_shared(),
+        );
+        let actions = reducer
+            .update_election_from_spu_changes(vec![offline.clone()])
+            .await;
+        assert!(matches!(
+            actions.as_slice(),
+            [PartitionWSAction::UpdateStatus((_, status))] if status.resolution == PartitionResolution::LeaderOffline
+        ));
+
+        // topic allows unclean leader election
+        spec.unclean_leader_election = true;
+        let reducer = PartitionReducer::new(
+            PartitionLocalStore::bulk_new(vec![partition(spec.clone(), status)]),
+            spu_store,
+            TopicLocalStore::new_shared(),
+        );
+        let actions = reducer
+            .update_election_from_spu_changes(vec![offline])
+            .await;
+        let mut expected = spec;
+        expected.set_leader(1);
+        assert_eq!(expected.leader_epoch, 1);
+        assert_eq!(
+            actions,
+            vec![PartitionWSAction::UpdateSpec((
+                ("topic1", 0).into(),
+                expected
+            ))]
+        );
+    }
+
     /*
     #[fluvio_future::test]
     async fn test_process_partition_actions_without_partitions()  {
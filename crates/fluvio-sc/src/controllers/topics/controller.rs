@@ -5,6 +5,8 @@
 
 use std::cmp::min;
 use std::ops::Add;
+use std::sync::Arc;
+use std::time::Instant;
 
 use fluvio_controlplane_metadata::spu::SpuSpec;
 use fluvio_controlplane_metadata::topic::CleanupPolicy;
@@ -19,7 +21,7 @@ use tracing::{info, instrument, trace, debug};
 
 use fluvio_future::task::spawn;
 
-use crate::core::SharedContext;
+use crate::core::{ScMetrics, SharedContext};
 use crate::stores::topic::TopicSpec;
 use crate::stores::partition::PartitionSpec;
 use crate::stores::StoreContext;
@@ -37,6 +39,7 @@ pub struct TopicController<C: MetadataItem = K8MetaItem> {
     topics: StoreContext<TopicSpec, C>,
     partitions: StoreContext<PartitionSpec, C>,
     reducer: TopicReducer<C>,
+    metrics: Arc<ScMetrics>,
 }
 
 impl<C> TopicController<C>
@@ -59,6 +62,7 @@ where
             topics,
             partitions,
             spus,
+            metrics: ctx.metrics().clone(),
         };
 
         spawn(controller.dispatch_loop());
@@ -79,8 +83,10 @@ impl<C: MetadataItem> TopicController<C> {
         let mut spus_listener = self.spus.change_listener();
 
         loop {
+            let start = Instant::now();
             self.sync_topics(&mut topics_listener).await;
             self.sync_spus(&mut spus_listener).await;
+            self.metrics.record_reconcile("topic", start.elapsed());
 
             select! {
 
@@ -7,14 +7,19 @@ use std::sync::Arc;
 
 use fluvio_sc_schema::mirror::MirrorSpec;
 use fluvio_stream_model::core::MetadataItem;
+use fluvio_types::event::StickyEvent;
 
 use crate::config::ScConfig;
+use crate::core::ScMetrics;
 use crate::stores::spu::*;
 use crate::stores::partition::*;
 use crate::stores::topic::*;
 use crate::stores::spg::*;
 use crate::stores::smartmodule::*;
 use crate::stores::tableformat::*;
+use crate::stores::schema::*;
+use crate::stores::policy::*;
+use crate::stores::quota::*;
 use crate::stores::*;
 
 pub type SharedContext<C> = Arc<Context<C>>;
@@ -31,7 +36,12 @@ pub struct Context<C: MetadataItem> {
     smartmodules: StoreContext<SmartModuleSpec, C>,
     tableformats: StoreContext<TableFormatSpec, C>,
     mirrors: StoreContext<MirrorSpec, C>,
+    schemas: StoreContext<SchemaSpec, C>,
+    policies: StoreContext<PolicySpec, C>,
+    quotas: StoreContext<QuotaSpec, C>,
     health: SharedHealthCheck,
+    metrics: Arc<ScMetrics>,
+    shutdown: Arc<StickyEvent>,
     config: ScConfig,
 }
 
@@ -54,7 +64,12 @@ impl<C: MetadataItem> Context<C> {
             smartmodules: StoreContext::new(),
             tableformats: StoreContext::new(),
             mirrors: StoreContext::new(),
+            schemas: StoreContext::new(),
+            policies: StoreContext::new(),
+            quotas: StoreContext::new(),
             health: HealthCheck::shared(),
+            metrics: Arc::new(ScMetrics::default()),
+            shutdown: StickyEvent::shared(),
             config,
         }
     }
@@ -90,11 +105,32 @@ impl<C: MetadataItem> Context<C> {
         &self.mirrors
     }
 
+    pub fn schemas(&self) -> &StoreContext<SchemaSpec, C> {
+        &self.schemas
+    }
+
+    pub fn policies(&self) -> &StoreContext<PolicySpec, C> {
+        &self.policies
+    }
+
+    pub fn quotas(&self) -> &StoreContext<QuotaSpec, C> {
+        &self.quotas
+    }
+
     /// spu health channel
     pub fn health(&self) -> &SharedHealthCheck {
         &self.health
     }
 
+    pub fn metrics(&self) -> &Arc<ScMetrics> {
+        &self.metrics
+    }
+
+    /// notified when SC stops serving, servers and open connections are closed
+    pub fn shutdown(&self) -> &Arc<StickyEvent> {
+        &self.shutdown
+    }
+
     /// reference to config
     pub fn config(&self) -> &ScConfig {
         &self.config
@@ -0,0 +1,108 @@
+//!
+//! # SC Metrics
+//!
+//! Counters of SC activity, exported over OpenMetrics endpoint.
+//!
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use fluvio_service::metrics::{MetricKind, MetricsEncoder};
+
+#[derive(Debug, Default)]
+pub struct ScMetrics {
+    connections: AtomicU64,
+    reconciles: Mutex<BTreeMap<&'static str, ReconcileStats>>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ReconcileStats {
+    pub count: u64,
+    pub seconds: f64,
+}
+
+impl ScMetrics {
+    /// count connection as open until guard is dropped
+    pub fn track_connection(self: &Arc<Self>) -> ConnectionGuard {
+        self.connections.fetch_add(1, Ordering::SeqCst);
+        ConnectionGuard(self.clone())
+    }
+
+    /// number of open public connections
+    pub fn connections(&self) -> u64 {
+        self.connections.load(Ordering::SeqCst)
+    }
+
+    /// record single reconcile pass of controller
+    pub fn record_reconcile(&self, controller: &'static str, elapsed: Duration) {
+        let mut reconciles = self.reconciles.lock().expect("metrics lock poisoned");
+        let stats = reconciles.entry(controller).or_default();
+        stats.count += 1;
+        stats.seconds += elapsed.as_secs_f64();
+    }
+
+    pub fn reconciles(&self) -> BTreeMap<&'static str, ReconcileStats> {
+        self.reconciles
+            .lock()
+            .expect("metrics lock poisoned")
+            .clone()
+    }
+
+    /// encode metrics in OpenMetrics text format
+    pub fn render(&self) -> String {
+        let mut encoder = MetricsEncoder::default();
+        encoder
+            .family(
+                "fluvio_sc_connections",
+                MetricKind::Gauge,
+                "Open client connections",
+            )
+            .sample(&[], self.connections());
+        let mut family = encoder.family(
+            "fluvio_sc_reconcile_seconds",
+            MetricKind::Summary,
+            "Time spent in controller reconcile passes",
+        );
+        for (controller, stats) in self.reconciles() {
+            family.summary(&[("controller", controller)], stats.count, stats.seconds);
+        }
+        encoder.finish()
+    }
+}
+
+pub struct ConnectionGuard(Arc<ScMetrics>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.connections.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::ScMetrics;
+
+    #[test]
+    fn test_sc_metrics_render() {
+        let metrics = Arc::new(ScMetrics::default());
+        let connection = metrics.track_connection();
+        metrics.record_reconcile("topic", Duration::from_millis(250));
+        metrics.record_reconcile("topic", Duration::from_millis(250));
+        metrics.record_reconcile("spu", Duration::from_secs(1));
+
+        let text = metrics.render();
+        assert!(text.contains("fluvio_sc_connections 1\n"));
+        assert!(text.contains("fluvio_sc_reconcile_seconds_count{controller=\"topic\"} 2\n"));
+        assert!(text.contains("fluvio_sc_reconcile_seconds_sum{controller=\"topic\"} 0.5\n"));
+        assert!(text.contains("fluvio_sc_reconcile_seconds_count{controller=\"spu\"} 1\n"));
+        assert!(text.ends_with("# EOF\n"));
+
+        drop(connection);
+        assert_eq!(metrics.connections(), 0);
+    }
+}
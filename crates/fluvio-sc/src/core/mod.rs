@@ -1,2 +1,4 @@
 mod context;
+mod metrics;
 pub use self::context::*;
+pub use self::metrics::*;
@@ -0,0 +1,928 @@
+//!
+//! # Consensus among SC instances
+//!
+//! Simplified Raft where the local metadata files are the replicated state.
+//! Active SC keeps only the recent part of the log in memory. Standby which is behind
+//! continues from its last entry, unless the log was compacted past it. Standby can't undo
+//! changes already written to its files, so diverged one receives full snapshot instead.
+//!
+//! Consensus state is kept in memory only. Caller persists the state and updates
+//! the metadata files outside of the lock guarding it.
+//!
+//! Active SC writes its changes to the metadata files only after they are committed.
+//! Changes not yet written are part of the persisted state, and are written to the files
+//! when state is loaded or active SC steps down, so files always contain the last entry.
+//!
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use fluvio_controlplane::ha_api::append::{
+    AppendRequest, AppendResponse, LogEntry, MetadataFileChange, MetadataSnapshot,
+};
+use fluvio_controlplane::ha_api::vote::{VoteRequest, VoteResponse};
+use fluvio_stream_dispatcher::metadata::local::{LocalMetadataChange, LocalMetadataStorage};
+
+use super::ScId;
+
+const STATE_FILE: &str = "ha-state.json";
+const MAX_LOG_ENTRIES: usize = 1000;
+
+/// state which must survive restart
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersistentState {
+    term: u64,
+    voted_for: Option<ScId>,
+    last_index: u64,
+    last_term: u64,
+    /// changes of active SC by log index, which may not be written to its metadata files yet
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    unapplied: BTreeMap<u64, Vec<LocalMetadataChange>>,
+}
+
+#[derive(Debug)]
+enum Role {
+    Follower,
+    Candidate { votes: BTreeSet<ScId> },
+    Leader(LeaderState),
+}
+
+#[derive(Debug)]
+struct LeaderState {
+    /// first index of the leader's term
+    start_index: u64,
+    base_index: u64,
+    base_term: u64,
+    /// entries after `base_index`
+    log: Vec<LogEntry>,
+    followers: BTreeMap<ScId, FollowerProgress>,
+}
+
+impl LeaderState {
+    /// term of entry at `index`, `None` if it's not in the log
+    fn term_at(&self, index: u64) -> Option<u64> {
+        if index == self.base_index {
+            Some(self.base_term)
+        } else if index > self.base_index {
+            self.log
+                .get((index - self.base_index - 1) as usize)
+                .map(|entry| entry.term)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FollowerProgress {
+    next_index: u64,
+    match_index: u64,
+    /// standby has entries which are not in the log
+    diverged: bool,
+    last_ack: Instant,
+}
+
+/// outcome of append request from active SC
+#[derive(Debug)]
+pub enum AppendAction {
+    Reject(AppendResponse),
+    /// metadata files must be updated by [`apply_append`] before calling [`Consensus::appended`]
+    Apply(PendingAppend),
+}
+
+/// outcome of entry appended by active SC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitState {
+    Pending,
+    Committed,
+    /// SC stopped being active before entry was committed, new active SC may still commit it
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct PendingAppend {
+    snapshot: Option<Vec<LocalMetadataChange>>,
+    changes: Vec<LocalMetadataChange>,
+    last_index: u64,
+    last_term: u64,
+    leader_commit: u64,
+}
+
+impl PendingAppend {
+    pub fn has_changes(&self) -> bool {
+        self.snapshot.is_some() || !self.changes.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub struct Consensus {
+    id: ScId,
+    peers: Vec<ScId>,
+    state: PersistentState,
+    /// state as last returned by `take_unpersisted`
+    persisted: PersistentState,
+    role: Role,
+    commit_index: u64,
+    /// last entry of active SC written to its metadata files
+    applied_index: u64,
+    applied_term: u64,
+    /// term and commit index at the time this SC stopped being active
+    resigned: Option<(u64, u64)>,
+    leader_id: Option<ScId>,
+    last_contact: Instant,
+}
+
+impl Consensus {
+    /// load consensus state stored in the metadata folder
+    pub fn load(id: ScId, peers: Vec<ScId>, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        std::fs::create_dir_all(path)?;
+        let state_file = path.join(STATE_FILE);
+        let mut state = if state_file.exists() {
+            serde_json::from_slice(&std::fs::read(&state_file)?)
+                .with_context(|| format!("loading {}", state_file.display()))?
+        } else {
+            PersistentState::default()
+        };
+        debug!(id, ?state, "loaded consensus state");
+        if !state.unapplied.is_empty() {
+            info!(
+                id,
+                count = state.unapplied.len(),
+                "writing unapplied changes to metadata files"
+            );
+            apply_changes(path, state.unapplied.values().flatten())?;
+            state.unapplied.clear();
+            persist_state(path, &state)?;
+        }
+        Ok(Self {
+            id,
+            peers,
+            persisted: state.clone(),
+            state,
+            role: Role::Follower,
+            commit_index: 0,
+            applied_index: 0,
+            applied_term: 0,
+            resigned: None,
+            leader_id: None,
+            last_contact: Instant::now(),
+        })
+    }
+
+    pub fn id(&self) -> ScId {
+        self.id
+    }
+
+    pub fn term(&self) -> u64 {
+        self.state.term
+    }
+
+    pub fn last_index(&self) -> u64 {
+        self.state.last_index
+    }
+
+    pub fn commit_index(&self) -> u64 {
+        self.commit_index
+    }
+
+    pub fn leader_id(&self) -> Option<ScId> {
+        self.leader_id
+    }
+
+    pub fn is_leader(&self) -> bool {
+        matches!(self.role, Role::Leader(_))
+    }
+
+    /// leader whose first entry of the term is committed
+    pub fn is_ready(&self) -> bool {
+        match &self.role {
+            Role::Leader(leader) => self.commit_index >= leader.start_index,
+            _ => false,
+        }
+    }
+
+    /// state changed since the last call, which must be persisted before responding to peers
+    pub fn take_unpersisted(&mut self) -> Option<PersistentState> {
+        if self.state == self.persisted {
+            None
+        } else {
+            self.persisted = self.state.clone();
+            Some(self.state.clone())
+        }
+    }
+
+    /// changes of former active SC which must be written to the metadata files
+    /// before state is persisted
+    pub fn take_unapplied(&mut self) -> Vec<LocalMetadataChange> {
+        if self.is_leader() || self.state.unapplied.is_empty() {
+            return vec![];
+        }
+        std::mem::take(&mut self.state.unapplied)
+            .into_values()
+            .flatten()
+            .collect()
+    }
+
+    fn quorum(&self) -> usize {
+        (self.peers.len() + 1) / 2 + 1
+    }
+
+    /// standby didn't hear from active SC within timeout
+    pub fn election_due(&self, now: Instant, timeout: Duration) -> bool {
+        !self.is_leader() && now.duration_since(self.last_contact) >= timeout
+    }
+
+    /// active SC got responses from majority within timeout
+    pub fn has_quorum_contact(&self, now: Instant, timeout: Duration) -> bool {
+        match &self.role {
+            Role::Leader(leader) => {
+                let acked = leader
+                    .followers
+                    .values()
+                    .filter(|progress| now.duration_since(progress.last_ack) < timeout)
+                    .count();
+                acked + 1 >= self.quorum()
+            }
+            _ => false,
+        }
+    }
+
+    pub fn start_election(&mut self) -> VoteRequest {
+        self.state.term += 1;
+        self.state.voted_for = Some(self.id);
+        self.role = Role::Candidate {
+            votes: BTreeSet::from([self.id]),
+        };
+        self.leader_id = None;
+        self.last_contact = Instant::now();
+        info!(id = self.id, term = self.state.term, "starting election");
+        if self.quorum() == 1 {
+            self.become_leader();
+        }
+        VoteRequest {
+            term: self.state.term,
+            candidate_id: self.id,
+            last_log_index: self.state.last_index,
+            last_log_term: self.state.last_term,
+        }
+    }
+
+    pub fn handle_vote_request(&mut self, request: &VoteRequest) -> VoteResponse {
+        if request.term > self.state.term {
+            self.step_down(request.term);
+        }
+        let log_up_to_date = (request.last_log_term, request.last_log_index)
+            >= (self.state.last_term, self.state.last_index);
+        let can_vote = self
+            .state
+            .voted_for
+            .is_none_or(|voted_for| voted_for == request.candidate_id);
+        let granted = request.term == self.state.term && can_vote && log_up_to_date;
+        if granted {
+            self.state.voted_for = Some(request.candidate_id);
+            self.last_contact = Instant::now();
+        }
+        debug!(?request, granted, "vote request");
+        VoteResponse {
+            term: self.state.term,
+            granted,
+        }
+    }
+
+    pub fn handle_vote_response(&mut self, from: ScId, response: &VoteResponse) {
+        if response.term > self.state.term {
+            self.step_down(response.term);
+            return;
+        }
+        let quorum = self.quorum();
+        let elected = match &mut self.role {
+            Role::Candidate { votes } if response.term == self.state.term && response.granted => {
+                votes.insert(from);
+                votes.len() >= quorum
+            }
+            _ => false,
+        };
+        if elected {
+            self.become_leader();
+        }
+    }
+
+    fn become_leader(&mut self) {
+        let now = Instant::now();
+        let next_index = self.state.last_index + 1;
+        let followers = self
+            .peers
+            .iter()
+            .map(|peer| {
+                (
+                    *peer,
+                    FollowerProgress {
+                        next_index,
+                        match_index: 0,
+                        diverged: false,
+                        last_ack: now,
+                    },
+                )
+            })
+            .collect();
+        self.role = Role::Leader(LeaderState {
+            start_index: next_index,
+            base_index: self.state.last_index,
+            base_term: self.state.last_term,
+            log: vec![],
+            followers,
+        });
+        self.leader_id = Some(self.id);
+        info!(id = self.id, term = self.state.term, "elected as active SC");
+        // entry of the new term, committing it commits everything before
+        self.append_changes(vec![]);
+        self.applied_index = self.state.last_index;
+        self.applied_term = self.state.last_term;
+    }
+
+    fn step_down(&mut self, term: u64) {
+        if let Role::Leader(leader) = &self.role {
+            warn!(id = self.id, term, "stepping down as active SC");
+            // entries neither committed nor written to the metadata files are discarded
+            let kept_index = self.commit_index.max(self.applied_index);
+            let kept_term = leader
+                .term_at(kept_index)
+                .filter(|_| kept_index < self.state.last_index);
+            if let Some(kept_term) = kept_term {
+                self.state.last_index = kept_index;
+                self.state.last_term = kept_term;
+            }
+            self.state.unapplied.retain(|index, _| *index <= kept_index);
+            self.resigned = Some((self.state.term, self.commit_index));
+        }
+        if term > self.state.term {
+            self.state.term = term;
+            self.state.voted_for = None;
+            self.leader_id = None;
+        }
+        if !matches!(self.role, Role::Follower) {
+            self.role = Role::Follower;
+            self.last_contact = Instant::now();
+        }
+    }
+
+    /// step down because active SC lost contact with majority
+    pub fn resign(&mut self) {
+        self.step_down(self.state.term);
+    }
+
+    /// append changes made by active SC to the log, returns index of the entry
+    pub fn append_changes(&mut self, changes: Vec<LocalMetadataChange>) -> Option<u64> {
+        let index = self.state.last_index + 1;
+        let term = self.state.term;
+        let Role::Leader(leader) = &mut self.role else {
+            warn!(id = self.id, "metadata changed while not active");
+            return None;
+        };
+        if !changes.is_empty() {
+            self.state.unapplied.insert(index, changes.clone());
+        }
+        leader.log.push(LogEntry {
+            index,
+            term,
+            changes: changes.into_iter().map(into_file_change).collect(),
+        });
+        if leader.log.len() > MAX_LOG_ENTRIES {
+            let removed = leader.log.len() - MAX_LOG_ENTRIES;
+            let last_removed = &leader.log[removed - 1];
+            leader.base_index = last_removed.index;
+            leader.base_term = last_removed.term;
+            leader.log.drain(..removed);
+        }
+        self.state.last_index = index;
+        self.state.last_term = term;
+        self.advance_commit();
+        Some(index)
+    }
+
+    /// state of entry appended at `index` while active in `term`
+    pub fn commit_state(&self, index: u64, term: u64) -> CommitState {
+        if self.is_leader() && self.state.term == term {
+            if self.commit_index >= index {
+                CommitState::Committed
+            } else {
+                CommitState::Pending
+            }
+        } else if self
+            .resigned
+            .is_some_and(|(resigned_term, commit)| resigned_term == term && commit >= index)
+        {
+            CommitState::Committed
+        } else {
+            CommitState::Unknown
+        }
+    }
+
+    /// changes of the entry at `index` were written to the metadata files of active SC
+    pub fn mark_applied(&mut self, index: u64) {
+        let Role::Leader(leader) = &self.role else {
+            return;
+        };
+        if let Some(term) = leader.term_at(index).filter(|_| index > self.applied_index) {
+            self.applied_index = index;
+            self.applied_term = term;
+            self.state
+                .unapplied
+                .retain(|unapplied_index, _| *unapplied_index > index);
+        }
+    }
+
+    /// next request to replicate log to `peer`, `None` if not active.
+    /// Files of the snapshot are filled by [`read_snapshot_files`].
+    pub fn append_request_for(&self, peer: ScId) -> Option<AppendRequest> {
+        let Role::Leader(leader) = &self.role else {
+            return None;
+        };
+        let progress = leader.followers.get(&peer)?;
+        let mut request = AppendRequest {
+            term: self.state.term,
+            leader_id: self.id,
+            leader_commit: self.commit_index,
+            ..Default::default()
+        };
+        if progress.diverged || progress.next_index <= leader.base_index {
+            // files may already contain newer changes, which are sent later again
+            request.snapshot = Some(MetadataSnapshot {
+                last_index: self.applied_index,
+                last_term: self.applied_term,
+                files: vec![],
+            });
+        } else {
+            request.prev_log_index = progress.next_index - 1;
+            request.prev_log_term = leader.term_at(request.prev_log_index).unwrap_or_default();
+            request.entries =
+                leader.log[(progress.next_index - leader.base_index - 1) as usize..].to_vec();
+        }
+        Some(request)
+    }
+
+    pub fn handle_append_response(&mut self, peer: ScId, response: &AppendResponse) {
+        if response.term > self.state.term {
+            self.step_down(response.term);
+            return;
+        }
+        let Role::Leader(leader) = &mut self.role else {
+            return;
+        };
+        let Some(progress) = leader.followers.get_mut(&peer) else {
+            return;
+        };
+        progress.last_ack = Instant::now();
+        if response.success {
+            progress.match_index = progress.match_index.max(response.last_log_index);
+            progress.next_index = progress.match_index + 1;
+            progress.diverged = false;
+        } else if response.last_log_index < leader.base_index
+            || leader.term_at(response.last_log_index) == Some(response.last_log_term)
+        {
+            // continue after last entry of standby, snapshot is sent if it's no longer in the log
+            debug!(
+                peer,
+                last_log_index = response.last_log_index,
+                "standby is behind"
+            );
+            progress.next_index = response.last_log_index + 1;
+        } else {
+            debug!(peer, "standby diverged, sending snapshot");
+            progress.diverged = true;
+        }
+        self.advance_commit();
+    }
+
+    /// commit highest index replicated to majority in current term
+    fn advance_commit(&mut self) {
+        let Role::Leader(leader) = &self.role else {
+            return;
+        };
+        let mut matches: Vec<u64> = leader
+            .followers
+            .values()
+            .map(|progress| progress.match_index)
+            .chain(std::iter::once(self.state.last_index))
+            .collect();
+        matches.sort_unstable_by(|a, b| b.cmp(a));
+        let replicated = matches[self.quorum() - 1];
+        if replicated > self.commit_index && replicated >= leader.start_index {
+            debug!(commit_index = replicated, "committed");
+            self.commit_index = replicated;
+        }
+    }
+
+    pub fn handle_append(&mut self, request: AppendRequest) -> AppendAction {
+        if request.term < self.state.term {
+            return AppendAction::Reject(self.append_response(false));
+        }
+        self.step_down(request.term);
+        self.leader_id = Some(request.leader_id);
+        self.last_contact = Instant::now();
+
+        let mut pending = if let Some(snapshot) = request.snapshot {
+            info!(
+                leader = request.leader_id,
+                last_index = snapshot.last_index,
+                "installing metadata snapshot"
+            );
+            PendingAppend {
+                snapshot: Some(snapshot.files.into_iter().map(from_file_change).collect()),
+                changes: vec![],
+                last_index: snapshot.last_index,
+                last_term: snapshot.last_term,
+                leader_commit: request.leader_commit,
+            }
+        } else if (request.prev_log_index, request.prev_log_term)
+            != (self.state.last_index, self.state.last_term)
+        {
+            return AppendAction::Reject(self.append_response(false));
+        } else {
+            PendingAppend {
+                snapshot: None,
+                changes: vec![],
+                last_index: self.state.last_index,
+                last_term: self.state.last_term,
+                leader_commit: request.leader_commit,
+            }
+        };
+
+        for entry in request.entries {
+            if entry.index != pending.last_index + 1 {
+                break;
+            }
+            pending
+                .changes
+                .extend(entry.changes.into_iter().map(from_file_change));
+            pending.last_index = entry.index;
+            pending.last_term = entry.term;
+        }
+        AppendAction::Apply(pending)
+    }
+
+    /// metadata files were updated by the pending append
+    pub fn appended(&mut self, pending: PendingAppend) -> AppendResponse {
+        // installing snapshot may take longer than election timeout
+        self.last_contact = Instant::now();
+        self.state.last_index = pending.last_index;
+        self.state.last_term = pending.last_term;
+        self.commit_index = pending.leader_commit.min(self.state.last_index);
+        self.append_response(true)
+    }
+
+    fn append_response(&self, success: bool) -> AppendResponse {
+        AppendResponse {
+            term: self.state.term,
+            success,
+            last_log_index: self.state.last_index,
+            last_log_term: self.state.last_term,
+        }
+    }
+}
+
+/// store consensus state in the metadata folder
+pub fn persist_state(path: &Path, state: &PersistentState) -> Result<()> {
+    let state_file = path.join(STATE_FILE);
+    let tmp_file = state_file.with_extension("tmp");
+    std::fs::write(&tmp_file, serde_json::to_vec(state)?)?;
+    std::fs::rename(&tmp_file, &state_file)?;
+    Ok(())
+}
+
+/// write changes of the pending append to the metadata folder
+pub fn apply_append(path: &Path, pending: &PendingAppend) -> Result<()> {
+    if let Some(snapshot) = &pending.snapshot {
+        LocalMetadataStorage::install_snapshot(path, snapshot)?;
+    }
+    apply_changes(path, &pending.changes)
+}
+
+/// write changes to the metadata folder in order
+pub fn apply_changes<'a>(
+    path: &Path,
+    changes: impl IntoIterator<Item = &'a LocalMetadataChange>,
+) -> Result<()> {
+    for change in changes {
+        LocalMetadataStorage::apply_change(path, change)?;
+    }
+    Ok(())
+}
+
+/// read content of the metadata folder into snapshot of the request
+pub fn read_snapshot_files(path: &Path, request: &mut AppendRequest) -> Result<()> {
+    if let Some(snapshot) = &mut request.snapshot {
+        snapshot.files = LocalMetadataStorage::snapshot(path)?
+            .into_iter()
+            .map(into_file_change)
+            .collect();
+    }
+    Ok(())
+}
+
+fn into_file_change(change: LocalMetadataChange) -> MetadataFileChange {
+    MetadataFileChange {
+        kind: change.kind,
+        name: change.name,
+        content: change.content,
+    }
+}
+
+fn from_file_change(change: MetadataFileChange) -> LocalMetadataChange {
+    LocalMetadataChange {
+        kind: change.kind,
+        name: change.name,
+        content: change.content,
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn node(id: ScId, peers: &[ScId]) -> (Consensus, TempDir) {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let consensus = Consensus::load(id, peers.to_vec(), dir.path()).expect("loaded");
+        (consensus, dir)
+    }
+
+    fn change(name: &str, content: Option<&str>) -> LocalMetadataChange {
+        LocalMetadataChange {
+            kind: "topic".to_string(),
+            name: name.to_string(),
+            content: content.map(|c| c.to_string()),
+        }
+    }
+
+    /// process append request like standby SC, including update of its metadata folder
+    fn append(consensus: &mut Consensus, dir: &TempDir, request: AppendRequest) -> AppendResponse {
+        let response = match consensus.handle_append(request) {
+            AppendAction::Reject(response) => response,
+            AppendAction::Apply(pending) => {
+                apply_append(dir.path(), &pending).expect("applied");
+                consensus.appended(pending)
+            }
+        };
+        if let Some(state) = consensus.take_unpersisted() {
+            persist_state(dir.path(), &state).expect("persisted");
+        }
+        response
+    }
+
+    /// next append request of active SC with snapshot read from its metadata folder
+    fn request_for(leader: &Consensus, dir: &TempDir, peer: ScId) -> AppendRequest {
+        let mut request = leader.append_request_for(peer).expect("leader");
+        read_snapshot_files(dir.path(), &mut request).expect("snapshot");
+        request
+    }
+
+    /// elect first node as leader with the vote of the second one
+    fn elect(leader: &mut Consensus, voter: &mut Consensus) {
+        let request = leader.start_election();
+        let response = voter.handle_vote_request(&request);
+        assert!(response.granted);
+        leader.handle_vote_response(voter.id(), &response);
+        assert!(leader.is_leader());
+    }
+
+    #[test]
+    fn test_vote_granted_once_per_term() {
+        let (mut sc1, _d1) = node(1, &[2, 3]);
+        let (mut sc2, _d2) = node(2, &[1, 3]);
+        let (mut sc3, _d3) = node(3, &[1, 2]);
+
+        let request1 = sc1.start_election();
+        let request3 = sc3.start_election();
+
+        assert!(sc2.handle_vote_request(&request1).granted);
+        assert!(!sc2.handle_vote_request(&request3).granted);
+        // same candidate may ask again
+        assert!(sc2.handle_vote_request(&request1).granted);
+        assert_eq!(sc2.term(), 1);
+    }
+
+    #[test]
+    fn test_vote_rejected_for_stale_log() {
+        let (mut sc1, d1) = node(1, &[2, 3]);
+        let (mut sc2, d2) = node(2, &[1, 3]);
+        let (mut sc3, _d3) = node(3, &[1, 2]);
+
+        elect(&mut sc1, &mut sc2);
+        let request = request_for(&sc1, &d1, 2);
+        append(&mut sc2, &d2, request);
+
+        // sc3 missed the entry of term 1
+        let request = sc3.start_election();
+        sc3.start_election();
+        let response = sc2.handle_vote_request(&VoteRequest {
+            term: sc3.term(),
+            ..request
+        });
+        assert!(!response.granted);
+        assert_eq!(response.term, 2);
+    }
+
+    #[test]
+    fn test_replicate_changes_and_commit() {
+        let (mut sc1, d1) = node(1, &[2, 3]);
+        let (mut sc2, d2) = node(2, &[1, 3]);
+
+        elect(&mut sc1, &mut sc2);
+        assert!(!sc1.is_ready());
+        assert_eq!(
+            sc1.append_changes(vec![change("t1", Some("spec"))]),
+            Some(2)
+        );
+        assert_eq!(sc1.commit_index(), 0);
+
+        let request = request_for(&sc1, &d1, 2);
+        assert_eq!(request.entries.len(), 2);
+        let response = append(&mut sc2, &d2, request);
+        assert!(response.success);
+        assert_eq!(response.last_log_index, 2);
+        assert_eq!(
+            std::fs::read_to_string(d2.path().join("topic").join("t1.yaml")).expect("file"),
+            "spec"
+        );
+
+        sc1.handle_append_response(2, &response);
+        assert_eq!(sc1.commit_index(), 2);
+        assert!(sc1.is_ready());
+        assert_eq!(sc2.leader_id(), Some(1));
+
+        // state survives restart
+        let restarted = Consensus::load(2, vec![1, 3], d2.path()).expect("loaded");
+        assert_eq!(restarted.term(), 1);
+        assert_eq!(restarted.last_index(), 2);
+    }
+
+    #[test]
+    fn test_standby_behind_receives_entries() {
+        let (mut sc1, d1) = node(1, &[2, 3]);
+        let (mut sc2, d2) = node(2, &[1, 3]);
+
+        elect(&mut sc1, &mut sc2);
+        sc1.append_changes(vec![change("t1", Some("spec"))]);
+
+        // response to the first request is lost
+        let request = request_for(&sc1, &d1, 2);
+        assert!(append(&mut sc2, &d2, request).success);
+        sc1.append_changes(vec![change("t2", Some("spec"))]);
+
+        let request = request_for(&sc1, &d1, 2);
+        let response = append(&mut sc2, &d2, request);
+        assert!(!response.success);
+        assert_eq!(response.last_log_index, 2);
+        sc1.handle_append_response(2, &response);
+
+        let request = request_for(&sc1, &d1, 2);
+        assert!(request.snapshot.is_none());
+        assert_eq!(request.prev_log_index, 2);
+        assert_eq!(request.entries.len(), 1);
+        let response = append(&mut sc2, &d2, request);
+        assert!(response.success);
+        assert!(d2.path().join("topic").join("t2.yaml").exists());
+
+        sc1.handle_append_response(2, &response);
+        assert_eq!(sc1.commit_index(), 3);
+    }
+
+    #[test]
+    fn test_diverged_standby_receives_snapshot() {
+        let (mut sc1, d1) = node(1, &[2, 3]);
+        let (mut sc2, _d2) = node(2, &[1, 3]);
+        let (mut sc3, d3) = node(3, &[1, 2]);
+
+        // sc3 has uncommitted entry from an old term
+        LocalMetadataStorage::apply_change(d3.path(), &change("stale", Some("old")))
+            .expect("applied");
+        sc3.state.last_index = 1;
+        sc3.state.last_term = 1;
+
+        sc1.state.term = 1;
+        elect(&mut sc1, &mut sc2);
+        sc1.append_changes(vec![change("t1", Some("spec"))]);
+        LocalMetadataStorage::apply_change(d1.path(), &change("t1", Some("spec")))
+            .expect("applied");
+        sc1.mark_applied(2);
+
+        let request = request_for(&sc1, &d1, 3);
+        let response = append(&mut sc3, &d3, request);
+        assert!(!response.success);
+        sc1.handle_append_response(3, &response);
+
+        let request = request_for(&sc1, &d1, 3);
+        assert!(request.snapshot.is_some());
+        let response = append(&mut sc3, &d3, request);
+        assert!(response.success);
+        assert_eq!(response.last_log_index, 2);
+        assert!(!d3.path().join("topic").join("stale.yaml").exists());
+        assert!(d3.path().join("topic").join("t1.yaml").exists());
+
+        sc1.handle_append_response(3, &response);
+        assert_eq!(sc1.commit_index(), 2);
+    }
+
+    #[test]
+    fn test_restarted_leader_writes_committed_changes() {
+        let (mut sc1, d1) = node(1, &[2, 3]);
+        let (mut sc2, d2) = node(2, &[1, 3]);
+
+        elect(&mut sc1, &mut sc2);
+        sc1.append_changes(vec![change("t1", Some("spec"))]);
+        let request = request_for(&sc1, &d1, 2);
+        let response = append(&mut sc2, &d2, request);
+        sc1.handle_append_response(2, &response);
+        assert_eq!(sc1.commit_index(), 2);
+        let state = sc1.take_unpersisted().expect("changed");
+        persist_state(d1.path(), &state).expect("persisted");
+
+        // crashed after commit, before change was written to the metadata files
+        assert!(!d1.path().join("topic").join("t1.yaml").exists());
+        drop(sc1);
+
+        let mut sc1 = Consensus::load(1, vec![2, 3], d1.path()).expect("loaded");
+        assert_eq!(sc1.last_index(), 2);
+        assert_eq!(
+            std::fs::read_to_string(d1.path().join("topic").join("t1.yaml")).expect("file"),
+            "spec"
+        );
+        assert!(
+            Consensus::load(1, vec![2, 3], d1.path())
+                .expect("loaded")
+                .state
+                .unapplied
+                .is_empty()
+        );
+
+        // snapshot of the restarted SC contains the change
+        let request = sc1.start_election();
+        let response = sc2.handle_vote_request(&request);
+        sc1.handle_vote_response(2, &response);
+        assert!(sc1.is_leader());
+        sc1.handle_append_response(
+            3,
+            &AppendResponse {
+                term: sc1.term(),
+                success: false,
+                last_log_index: 1,
+                last_log_term: 5,
+            },
+        );
+        let request = request_for(&sc1, &d1, 3);
+        let snapshot = request.snapshot.expect("snapshot");
+        assert_eq!(snapshot.last_index, 3);
+        assert!(snapshot.files.iter().any(|file| file.name == "t1"));
+    }
+
+    #[test]
+    fn test_leader_steps_down_on_higher_term() {
+        let (mut sc1, _d1) = node(1, &[2, 3]);
+        let (mut sc2, _d2) = node(2, &[1, 3]);
+
+        elect(&mut sc1, &mut sc2);
+        sc1.handle_append_response(
+            2,
+            &AppendResponse {
+                term: 5,
+                success: false,
+                ..Default::default()
+            },
+        );
+        assert!(!sc1.is_leader());
+        assert_eq!(sc1.term(), 5);
+        assert!(sc1.append_request_for(2).is_none());
+    }
+
+    #[test]
+    fn test_resigned_leader_discards_unapplied_entries() {
+        let (mut sc1, d1) = node(1, &[2, 3]);
+        let (mut sc2, d2) = node(2, &[1, 3]);
+
+        elect(&mut sc1, &mut sc2);
+        sc1.append_changes(vec![change("t1", Some("spec"))]);
+        let request = request_for(&sc1, &d1, 2);
+        let response = append(&mut sc2, &d2, request);
+        sc1.handle_append_response(2, &response);
+        assert_eq!(sc1.commit_state(2, 1), CommitState::Committed);
+
+        // neither replicated nor written to the metadata files
+        sc1.append_changes(vec![change("t2", Some("spec"))]);
+        assert_eq!(sc1.commit_state(3, 1), CommitState::Pending);
+
+        sc1.resign();
+        assert_eq!(sc1.last_index(), 2);
+        // committed change is written to the metadata files of former active SC
+        assert_eq!(sc1.take_unapplied(), vec![change("t1", Some("spec"))]);
+        assert_eq!(sc1.commit_state(2, 1), CommitState::Committed);
+        assert_eq!(sc1.commit_state(3, 1), CommitState::Unknown);
+    }
+}
@@ -0,0 +1,380 @@
+//!
+//! # Highly-available SC
+//!
+//! Several SC instances running in local mode replicate the metadata folder among themselves.
+//! Only elected active SC binds public and private endpoints, so SPUs and clients
+//! configured with all SC endpoints connect to whichever instance is active.
+//! Active SC which loses majority steps down, closing its endpoints and connections,
+//! and continues as standby. Metadata storage of the previous term rejects all changes.
+//!
+mod consensus;
+mod peer;
+mod storage;
+
+pub use storage::ReplicatedMetadataStorage;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use async_lock::Mutex as AsyncMutex;
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use futures_util::stream::FuturesUnordered;
+use rand::{Rng, thread_rng};
+use tracing::{debug, error, info, warn};
+
+use fluvio_controlplane::ha_api::append::{AppendRequest, AppendResponse};
+use fluvio_future::future::timeout;
+use fluvio_future::task::{spawn, spawn_blocking};
+use fluvio_future::timer::sleep;
+use fluvio_service::FluvioApiServer;
+use fluvio_stream_dispatcher::metadata::local::{LocalChangeObserver, LocalMetadataChange};
+use fluvio_types::event::StickyEvent;
+use fluvio_types::event::offsets::{OffsetPublisher, SharedOffsetPublisher};
+
+use consensus::{
+    AppendAction, CommitState, Consensus, apply_append, apply_changes, persist_state,
+    read_snapshot_files,
+};
+use peer::{HaPeerService, PeerClient};
+
+pub type ScId = u32;
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_millis(100);
+const ELECTION_TIMEOUT_MIN_MS: u64 = 1000;
+const ELECTION_TIMEOUT_MAX_MS: u64 = 2000;
+
+/// Configuration of SC in highly-available mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HaConfig {
+    pub id: ScId,
+    /// address for peer SC connections
+    pub bind: String,
+    /// peer SC ids and their addresses
+    pub peers: BTreeMap<ScId, String>,
+}
+
+pub type SharedHaNode = Arc<HaNode>;
+
+/// SC instance participating in consensus
+#[derive(Debug)]
+pub struct HaNode {
+    config: HaConfig,
+    path: PathBuf,
+    consensus: Mutex<Consensus>,
+    /// orders writes of consensus state and metadata files, which are done outside of the lock
+    io: AsyncMutex<()>,
+    /// index of the last staged change of the metadata
+    staged: Mutex<Option<u64>>,
+    /// last committed log index
+    commit: SharedOffsetPublisher,
+    /// last appended log index, wakes up replication
+    appended: SharedOffsetPublisher,
+    /// set while SC main loop is running on this instance, notified when it loses leadership
+    active: Mutex<Option<Arc<StickyEvent>>>,
+}
+
+impl HaNode {
+    /// load consensus state from metadata folder and start participating in elections
+    pub fn start(config: HaConfig, path: impl AsRef<Path>) -> Result<SharedHaNode> {
+        let path = path.as_ref().to_path_buf();
+        let consensus = Consensus::load(config.id, config.peers.keys().copied().collect(), &path)?;
+        let node = Arc::new(Self {
+            path,
+            commit: OffsetPublisher::shared(0),
+            appended: OffsetPublisher::shared(consensus.last_index() as i64),
+            consensus: Mutex::new(consensus),
+            io: AsyncMutex::new(()),
+            staged: Mutex::new(None),
+            active: Mutex::new(None),
+            config,
+        });
+
+        info!(id = node.config.id, bind = %node.config.bind, "starting SC peer service");
+        FluvioApiServer::new(node.config.bind.clone(), node.clone(), HaPeerService::new()).run();
+        spawn(election_loop(node.clone()));
+        for (peer, addr) in &node.config.peers {
+            spawn(replication_loop(
+                node.clone(),
+                PeerClient::new(*peer, addr.clone()),
+            ));
+        }
+        Ok(node)
+    }
+
+    pub fn id(&self) -> ScId {
+        self.config.id
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// id of the active SC known to this instance
+    pub fn leader_id(&self) -> Option<ScId> {
+        self.consensus().leader_id()
+    }
+
+    fn consensus(&self) -> MutexGuard<'_, Consensus> {
+        self.consensus
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// run `f` on consensus state, persist it and publish progress.
+    /// Notifies SC main loop if active SC lost leadership.
+    async fn update<T>(&self, f: impl FnOnce(&mut Consensus) -> T) -> Result<T> {
+        let _io = self.io.lock().await;
+        self.update_locked(f).await
+    }
+
+    /// same as `update`, for caller holding the `io` lock
+    async fn update_locked<T>(&self, f: impl FnOnce(&mut Consensus) -> T) -> Result<T> {
+        let (result, unapplied, state) = {
+            let mut consensus = self.consensus();
+            let result = f(&mut consensus);
+            self.commit.update(consensus.commit_index() as i64);
+            self.appended.update(consensus.last_index() as i64);
+            let demoted = if consensus.is_leader() {
+                None
+            } else {
+                self.active().take()
+            };
+            if let Some(demoted) = demoted {
+                warn!(
+                    id = self.config.id,
+                    term = consensus.term(),
+                    "lost leadership, stepping down to standby"
+                );
+                demoted.notify();
+            }
+            (
+                result,
+                consensus.take_unapplied(),
+                consensus.take_unpersisted(),
+            )
+        };
+        if !unapplied.is_empty() || state.is_some() {
+            let path = self.path.clone();
+            spawn_blocking(move || {
+                apply_changes(&path, &unapplied)?;
+                match state {
+                    Some(state) => persist_state(&path, &state),
+                    None => Ok(()),
+                }
+            })
+            .await?;
+        }
+        Ok(result)
+    }
+
+    /// process append request from active SC, metadata files are updated before responding
+    async fn handle_append(&self, request: AppendRequest) -> Result<AppendResponse> {
+        let _io = self.io.lock().await;
+        let pending = match self
+            .update_locked(|consensus| consensus.handle_append(request))
+            .await?
+        {
+            AppendAction::Reject(response) => return Ok(response),
+            AppendAction::Apply(pending) => pending,
+        };
+        let pending = if pending.has_changes() {
+            let path = self.path.clone();
+            spawn_blocking(move || apply_append(&path, &pending).map(|_| pending)).await?
+        } else {
+            pending
+        };
+        self.update_locked(|consensus| consensus.appended(pending))
+            .await
+    }
+
+    /// next request to replicate log to `peer`, `None` if not active
+    async fn append_request_for(&self, peer: ScId) -> Result<Option<AppendRequest>> {
+        let Some(mut request) = self.consensus().append_request_for(peer) else {
+            return Ok(None);
+        };
+        if request.snapshot.is_some() {
+            let path = self.path.clone();
+            request =
+                spawn_blocking(move || read_snapshot_files(&path, &mut request).map(|_| request))
+                    .await?;
+        }
+        Ok(Some(request))
+    }
+
+    /// wait until this instance is elected and its term is committed
+    pub async fn wait_for_leadership(&self) {
+        let mut listener = self.commit.change_listener();
+        loop {
+            if self.consensus().is_ready() {
+                info!(id = self.config.id, "SC is active");
+                return;
+            }
+            let _ = timeout(HEARTBEAT_INTERVAL, listener.listen()).await;
+        }
+    }
+
+    /// mark SC main loop as running, returned event is notified once leadership is lost
+    pub fn activate(&self) -> Arc<StickyEvent> {
+        let demoted = StickyEvent::shared();
+        *self.active() = Some(demoted.clone());
+        demoted
+    }
+
+    fn active(&self) -> MutexGuard<'_, Option<Arc<StickyEvent>>> {
+        self.active
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// wait until entry appended at `index` while active in `term` is replicated to majority.
+    /// Fails without knowing the outcome if SC stops being active before that.
+    async fn wait_committed(&self, index: u64, term: u64) -> Result<()> {
+        let mut listener = self.commit.change_listener();
+        loop {
+            let state = self.consensus().commit_state(index, term);
+            match state {
+                CommitState::Committed => return Ok(()),
+                CommitState::Unknown => {
+                    return Err(anyhow!(
+                        "SC {} stopped being active before change was committed, \
+                         change may still take effect on the new active SC",
+                        self.config.id
+                    ));
+                }
+                CommitState::Pending => {}
+            }
+            let _ = timeout(HEARTBEAT_INTERVAL, listener.listen()).await;
+        }
+    }
+}
+
+#[async_trait]
+impl LocalChangeObserver for HaNode {
+    async fn stage(&self, change: LocalMetadataChange) -> Result<()> {
+        debug!(
+            kind = %change.kind,
+            name = %change.name,
+            deleted = change.content.is_none(),
+            "metadata change"
+        );
+        let (index, term) = self
+            .update(|consensus| {
+                consensus
+                    .append_changes(vec![change])
+                    .map(|index| (index, consensus.term()))
+            })
+            .await?
+            .ok_or_else(|| anyhow!("SC {} is not active", self.config.id))?;
+        self.wait_committed(index, term).await?;
+        *self
+            .staged
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(index);
+        Ok(())
+    }
+
+    fn applied(&self) {
+        let staged = self
+            .staged
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+        if let Some(index) = staged {
+            self.consensus().mark_applied(index);
+        }
+    }
+}
+
+fn election_timeout() -> Duration {
+    Duration::from_millis(thread_rng().gen_range(ELECTION_TIMEOUT_MIN_MS..ELECTION_TIMEOUT_MAX_MS))
+}
+
+/// start elections when active SC is not heard from, and step down when majority is not reachable
+async fn election_loop(node: SharedHaNode) {
+    let mut clients: Vec<PeerClient> = node
+        .config
+        .peers
+        .iter()
+        .map(|(peer, addr)| PeerClient::new(*peer, addr.clone()))
+        .collect();
+    let mut election_after = election_timeout();
+    loop {
+        sleep(HEARTBEAT_INTERVAL).await;
+        let now = Instant::now();
+        let request = node
+            .update(|consensus| {
+                if consensus.is_leader() {
+                    if !consensus
+                        .has_quorum_contact(now, Duration::from_millis(ELECTION_TIMEOUT_MAX_MS))
+                    {
+                        warn!(id = consensus.id(), "lost contact with majority of SCs");
+                        consensus.resign();
+                    }
+                    None
+                } else if consensus.election_due(now, election_after) {
+                    Some(consensus.start_election())
+                } else {
+                    None
+                }
+            })
+            .await;
+        let request = match request {
+            Ok(Some(request)) => request,
+            Ok(None) => continue,
+            Err(err) => {
+                error!(%err, "failed to start election");
+                continue;
+            }
+        };
+        election_after = election_timeout();
+
+        // votes are counted as they arrive, so unreachable peers don't delay the election
+        let mut responses: FuturesUnordered<_> = clients
+            .iter_mut()
+            .map(|client| client.request_vote(request.clone()))
+            .collect();
+        while let Some((peer, response)) = responses.next().await {
+            match response {
+                Ok(response) => {
+                    if let Err(err) = node
+                        .update(|consensus| consensus.handle_vote_response(peer, &response))
+                        .await
+                    {
+                        error!(%err, "failed to process vote");
+                    }
+                }
+                Err(err) => debug!(peer, %err, "vote request failed"),
+            }
+        }
+    }
+}
+
+/// replicate log of active SC to a single peer
+async fn replication_loop(node: SharedHaNode, mut client: PeerClient) {
+    let mut appended = node.appended.change_listener();
+    loop {
+        let request = node.append_request_for(client.id()).await;
+        match request {
+            Ok(Some(request)) => match client.append(request).await {
+                Ok(response) => {
+                    if let Err(err) = node
+                        .update(|consensus| {
+                            consensus.handle_append_response(client.id(), &response)
+                        })
+                        .await
+                    {
+                        error!(%err, "failed to process append response");
+                    }
+                }
+                Err(err) => debug!(peer = client.id(), %err, "append request failed"),
+            },
+            Ok(None) => {}
+            Err(err) => error!(peer = client.id(), %err, "failed to build append request"),
+        }
+        let _ = timeout(HEARTBEAT_INTERVAL, appended.listen()).await;
+    }
+}
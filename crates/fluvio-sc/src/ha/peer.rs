@@ -0,0 +1,134 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use tracing::{debug, instrument, trace};
+
+use fluvio_controlplane::ha_api::api::{HaPeerKey, HaPeerRequest};
+use fluvio_controlplane::ha_api::append::{AppendRequest, AppendResponse};
+use fluvio_controlplane::ha_api::vote::{VoteRequest, VoteResponse};
+use fluvio_future::future::timeout;
+use fluvio_protocol::api::{Request, RequestMessage};
+use fluvio_service::{ConnectInfo, FluvioService};
+use fluvio_socket::FluvioSocket;
+
+use super::{ScId, SharedHaNode};
+
+const PEER_REQUEST_TIMEOUT: Duration = Duration::from_millis(500);
+/// snapshot carries whole metadata folder, which takes longer to transfer and install
+const SNAPSHOT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// serves vote and append requests from peer SCs
+#[derive(Debug)]
+pub struct HaPeerService {}
+
+impl HaPeerService {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl FluvioService for HaPeerService {
+    type Context = SharedHaNode;
+    type Request = HaPeerRequest;
+
+    #[instrument(skip(self, node))]
+    async fn respond(
+        self: Arc<Self>,
+        node: SharedHaNode,
+        socket: FluvioSocket,
+        _connection: ConnectInfo,
+    ) -> Result<()> {
+        let (mut sink, mut stream) = socket.split();
+        let mut api_stream = stream.api_stream::<HaPeerRequest, HaPeerKey>();
+
+        while let Some(request) = api_stream.next().await {
+            match request? {
+                HaPeerRequest::VoteRequest(req_msg) => {
+                    trace!(request = ?req_msg.request, "vote request");
+                    let response = node
+                        .update(|consensus| consensus.handle_vote_request(&req_msg.request))
+                        .await?;
+                    sink.send_response(
+                        &req_msg.new_response(response),
+                        req_msg.header.api_version(),
+                    )
+                    .await?;
+                }
+                HaPeerRequest::AppendRequest(mut req_msg) => {
+                    trace!(request = %req_msg.request, "append request");
+                    let api_version = req_msg.header.api_version();
+                    let request = std::mem::take(&mut req_msg.request);
+                    let response = node.handle_append(request).await?;
+                    sink.send_response(&req_msg.new_response(response), api_version)
+                        .await?;
+                }
+            }
+        }
+
+        debug!("finishing SC peer loop");
+        Ok(())
+    }
+}
+
+/// connection to peer SC, re-established on failure
+#[derive(Debug)]
+pub struct PeerClient {
+    id: ScId,
+    addr: String,
+    socket: Option<FluvioSocket>,
+}
+
+impl PeerClient {
+    pub fn new(id: ScId, addr: String) -> Self {
+        Self {
+            id,
+            addr,
+            socket: None,
+        }
+    }
+
+    pub fn id(&self) -> ScId {
+        self.id
+    }
+
+    pub async fn request_vote(&mut self, request: VoteRequest) -> (ScId, Result<VoteResponse>) {
+        (self.id, self.send(request, PEER_REQUEST_TIMEOUT).await)
+    }
+
+    pub async fn append(&mut self, request: AppendRequest) -> Result<AppendResponse> {
+        let request_timeout = if request.snapshot.is_some() {
+            SNAPSHOT_REQUEST_TIMEOUT
+        } else {
+            PEER_REQUEST_TIMEOUT
+        };
+        self.send(request, request_timeout).await
+    }
+
+    async fn send<R: Request>(
+        &mut self,
+        request: R,
+        request_timeout: Duration,
+    ) -> Result<R::Response> {
+        let result = timeout(request_timeout, self.send_inner(request))
+            .await
+            .map_err(|_| anyhow!("request to SC {} at {} timed out", self.id, self.addr))
+            .and_then(|result| result);
+        if result.is_err() {
+            self.socket = None;
+        }
+        result
+    }
+
+    async fn send_inner<R: Request>(&mut self, request: R) -> Result<R::Response> {
+        let socket = match &mut self.socket {
+            Some(socket) => socket,
+            None => self.socket.insert(FluvioSocket::connect(&self.addr).await?),
+        };
+        let response = socket.send(&RequestMessage::new_request(request)).await?;
+        Ok(response.response)
+    }
+}
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use async_lock::{Mutex, MutexGuard};
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+
+use fluvio_stream_dispatcher::metadata::MetadataClient;
+use fluvio_stream_dispatcher::metadata::local::{
+    LocalMetadataItem, LocalMetadataStorage, LocalStoreObject,
+};
+use fluvio_stream_model::core::Spec;
+use fluvio_stream_model::store::{MetadataStoreList, NameSpace, actions::LSUpdate, k8::K8ExtendedSpec};
+use fluvio_types::event::StickyEvent;
+
+use super::SharedHaNode;
+
+/// Local metadata storage of active SC.
+/// Changes are applied to the store only after they are replicated to majority of SCs.
+#[derive(Debug)]
+pub struct ReplicatedMetadataStorage {
+    inner: LocalMetadataStorage,
+    /// changes are staged one at a time
+    writes: Mutex<()>,
+    /// SC stepped down, changes are rejected from then on
+    demoted: Arc<StickyEvent>,
+}
+
+impl ReplicatedMetadataStorage {
+    pub fn new(node: SharedHaNode, demoted: Arc<StickyEvent>) -> Self {
+        let path = node.path().to_path_buf();
+        Self {
+            inner: LocalMetadataStorage::with_observer(path, node),
+            writes: Mutex::new(()),
+            demoted,
+        }
+    }
+
+    async fn lock_writes(&self) -> Result<MutexGuard<'_, ()>> {
+        let writes = self.writes.lock().await;
+        if self.demoted.is_set() {
+            return Err(anyhow!("SC stepped down, metadata is read only"));
+        }
+        Ok(writes)
+    }
+}
+
+#[async_trait]
+impl MetadataClient<LocalMetadataItem> for ReplicatedMetadataStorage {
+    async fn retrieve_items<S>(
+        &self,
+        namespace: &NameSpace,
+    ) -> Result<MetadataStoreList<S, LocalMetadataItem>>
+    where
+        S: K8ExtendedSpec,
+    {
+        self.inner.retrieve_items(namespace).await
+    }
+
+    async fn delete_item<S>(&self, metadata: LocalMetadataItem) -> Result<()>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _writes = self.lock_writes().await?;
+        self.inner.delete_item::<S>(metadata).await
+    }
+
+    async fn finalize_delete_item<S>(&self, metadata: LocalMetadataItem) -> Result<()>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _writes = self.lock_writes().await?;
+        self.inner.finalize_delete_item::<S>(metadata).await
+    }
+
+    async fn apply<S>(&self, value: LocalStoreObject<S>) -> Result<()>
+    where
+        S: K8ExtendedSpec,
+        <S as Spec>::Owner: K8ExtendedSpec,
+    {
+        let _writes = self.lock_writes().await?;
+        self.inner.apply(value).await
+    }
+
+    async fn update_spec<S>(&self, metadata: LocalMetadataItem, spec: S) -> Result<()>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _writes = self.lock_writes().await?;
+        self.inner.update_spec(metadata, spec).await
+    }
+
+    async fn update_spec_by_key<S>(
+        &self,
+        key: S::IndexKey,
+        namespace: &NameSpace,
+        spec: S,
+    ) -> Result<()>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _writes = self.lock_writes().await?;
+        self.inner.update_spec_by_key(key, namespace, spec).await
+    }
+
+    async fn update_status<S>(
+        &self,
+        metadata: LocalMetadataItem,
+        status: S::Status,
+        namespace: &NameSpace,
+    ) -> Result<LocalStoreObject<S>>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _writes = self.lock_writes().await?;
+        self.inner.update_status(metadata, status, namespace).await
+    }
+
+    fn watch_stream_since<S>(
+        &self,
+        namespace: &NameSpace,
+        resource_version: Option<String>,
+    ) -> BoxStream<'_, Result<Vec<LSUpdate<S, LocalMetadataItem>>>>
+    where
+        S: K8ExtendedSpec,
+    {
+        self.inner.watch_stream_since(namespace, resource_version)
+    }
+
+    async fn patch_status<S>(
+        &self,
+        metadata: LocalMetadataItem,
+        status: S::Status,
+        namespace: &NameSpace,
+    ) -> Result<LocalStoreObject<S>>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _writes = self.lock_writes().await?;
+        self.inner.patch_status(metadata, status, namespace).await
+    }
+}
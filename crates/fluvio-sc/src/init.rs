@@ -6,9 +6,12 @@
 //!
 use std::sync::Arc;
 
+use fluvio_future::task::spawn;
 use fluvio_sc_schema::mirror::MirrorSpec;
 use fluvio_stream_dispatcher::metadata::{SharedClient, MetadataClient};
 use fluvio_stream_model::core::MetadataItem;
+use fluvio_controlplane_metadata::policy::PolicySpec;
+use fluvio_service::metrics::start_metrics_server;
 
 use crate::controllers::mirroring::controller::RemoteMirrorController;
 use crate::core::Context;
@@ -19,10 +22,9 @@ use crate::controllers::topics::controller::{TopicController, SystemTopicControl
 use crate::config::ScConfig;
 use crate::services::start_internal_server;
 use crate::dispatcher::dispatcher::MetadataDispatcher;
-use crate::services::auth::basic::BasicRbacPolicy;
 
 pub async fn start_main_loop<C, M>(
-    sc_config_policy: (ScConfig, Option<BasicRbacPolicy>),
+    sc_config_policy: (ScConfig, Option<PolicySpec>),
     metadata_client: SharedClient<C>,
 ) -> crate::core::SharedContext<M>
 where
@@ -36,6 +38,9 @@ where
     use crate::stores::spg::SpuGroupSpec;
     use crate::stores::tableformat::TableFormatSpec;
     use crate::stores::smartmodule::SmartModuleSpec;
+    use crate::stores::schema::SchemaSpec;
+    use crate::stores::policy::PolicySpec;
+    use crate::stores::quota::QuotaSpec;
 
     let (sc_config, auth_policy) = sc_config_policy;
 
@@ -84,13 +89,31 @@ where
         ctx.mirrors().clone(),
     );
 
+    MetadataDispatcher::<SchemaSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.schemas().clone(),
+    );
+
+    MetadataDispatcher::<PolicySpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.policies().clone(),
+    );
+
+    MetadataDispatcher::<QuotaSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.quotas().clone(),
+    );
+
     start_main_loop_services(ctx, auth_policy).await
 }
 
 /// start the main loop
 async fn start_main_loop_services<C>(
     ctx: Arc<Context<C>>,
-    auth_policy: Option<BasicRbacPolicy>,
+    auth_policy: Option<PolicySpec>,
 ) -> SharedContext<C>
 where
     C: MetadataItem + 'static,
@@ -104,7 +127,12 @@ where
     whitelist!(
         config,
         "partition",
-        PartitionController::start(ctx.partitions().clone(), ctx.spus().clone())
+        PartitionController::start(
+            ctx.partitions().clone(),
+            ctx.spus().clone(),
+            ctx.topics().clone(),
+            ctx.metrics().clone()
+        )
     );
 
     whitelist!(config, "internal", start_internal_server(ctx.clone()));
@@ -119,10 +147,25 @@ where
         RemoteMirrorController::start(ctx.clone())
     );
 
+    if let Some(addr) = config.metrics_addr.clone() {
+        let metrics = ctx.metrics().clone();
+        let server_shutdown = start_metrics_server(addr, move || {
+            let metrics = metrics.clone();
+            async move { metrics.render() }
+        });
+        let shutdown = ctx.shutdown().clone();
+        spawn(async move {
+            shutdown.listen().await;
+            server_shutdown.notify();
+        });
+    }
+
     mod pub_server {
 
         use std::sync::Arc;
         use fluvio_auth::root::RootAuthorization;
+        use fluvio_auth::rbac::{RbacAuthorization, RbacPolicy};
+        use fluvio_controlplane_metadata::policy::PolicySpec;
         use tracing::info;
 
         use crate::services::start_public_server;
@@ -130,18 +173,24 @@ where
 
         use fluvio_controlplane_metadata::core::MetadataItem;
         use crate::services::auth::{AuthGlobalContext, ReadOnlyAuthorization};
-        use crate::services::auth::basic::{BasicAuthorization, BasicRbacPolicy};
+        use crate::services::auth::basic::read_policy;
+        use crate::services::auth::policy::PolicyController;
 
-        pub fn start<C>(ctx: SharedContext<C>, auth_policy_option: Option<BasicRbacPolicy>)
+        pub fn start<C>(ctx: SharedContext<C>, auth_policy_option: Option<PolicySpec>)
         where
             C: MetadataItem + 'static,
             C::UId: Send + Sync,
         {
             if let Some(policy) = auth_policy_option {
-                info!("using basic authorization");
+                info!("using rbac authorization");
+                let policy = Arc::new(RbacPolicy::new(policy));
+                if let Some(path) = ctx.config().auth_policy.clone() {
+                    policy.watch_file(path, read_policy);
+                }
+                PolicyController::start(ctx.clone(), policy.clone());
                 start_public_server(AuthGlobalContext::new(
                     ctx,
-                    Arc::new(BasicAuthorization::new(policy)),
+                    Arc::new(RbacAuthorization::new(policy)),
                 ));
             } else if ctx.config().read_only_metadata {
                 info!("using read-only authorization");
@@ -4,6 +4,7 @@ pub mod k8;
 pub mod cli;
 pub mod core;
 pub mod start;
+pub mod ha;
 
 pub mod stores;
 mod init;
@@ -1,74 +1,63 @@
-use std::sync::Arc;
+//!
+//! # Legacy Basic Policy
+//!
+//! Flat map of role to object actions, identity scopes are used as roles.
+//! Policy files in this format are converted into RBAC policy.
+//!
 
-use tracing::instrument;
-use async_trait::async_trait;
-pub use policy::BasicRbacPolicy;
+use std::io::Error as IoError;
+use std::path::Path;
+use std::convert::TryFrom;
 
-use fluvio_auth::{AuthContext, Authorization, TypeAction, InstanceAction, AuthError};
-use fluvio_controlplane_metadata::extended::ObjectType;
-use fluvio_auth::x509::X509Identity;
+use tracing::debug;
 
-#[derive(Debug, Clone)]
-pub struct BasicAuthorization {
-    policy: Arc<BasicRbacPolicy>,
-}
+pub use policy::BasicRbacPolicy;
 
-impl BasicAuthorization {
-    pub fn new(policy: BasicRbacPolicy) -> Self {
-        Self {
-            policy: Arc::new(policy),
+use fluvio_auth::rbac::read_policy_file;
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_controlplane_metadata::policy::{
+    Permission, PolicyAction, PolicyRole, PolicySpec, ResourcePattern,
+};
+
+/// read RBAC policy, falls back to legacy basic policy format
+pub fn read_policy(path: &Path) -> Result<PolicySpec, IoError> {
+    match read_policy_file(path) {
+        Ok(policy) => Ok(policy),
+        Err(err) => {
+            debug!(%err, "not rbac policy, trying basic policy");
+            BasicRbacPolicy::try_from(path.to_path_buf()).map(PolicySpec::from)
         }
     }
 }
 
-#[async_trait]
-impl Authorization for BasicAuthorization {
-    type Context = BasicAuthContext;
-
-    #[instrument(level = "trace", skip(self, socket))]
-    async fn create_auth_context(
-        &self,
-        socket: &mut fluvio_socket::FluvioSocket,
-    ) -> Result<Self::Context, AuthError> {
-        let identity = X509Identity::create_from_connection(socket)
-            .await
-            .map_err(|err| {
-                tracing::error!(%err, "failed to create x509 identity");
-                err
-            })?;
-        Ok(BasicAuthContext {
-            identity,
-            policy: self.policy.clone(),
-        })
-    }
-}
-
-#[derive(Debug)]
-pub struct BasicAuthContext {
-    identity: X509Identity,
-    policy: Arc<BasicRbacPolicy>,
-}
-
-#[async_trait]
-impl AuthContext for BasicAuthContext {
-    async fn allow_type_action(
-        &self,
-        ty: ObjectType,
-        action: TypeAction,
-    ) -> Result<bool, AuthError> {
-        self.policy
-            .evaluate(action.into(), ty, None, &self.identity)
-            .await
-    }
+impl From<BasicRbacPolicy> for PolicySpec {
+    fn from(basic: BasicRbacPolicy) -> Self {
+        let roles = basic
+            .0
+            .into_iter()
+            .map(|(name, objects)| PolicyRole {
+                name,
+                permissions: objects
+                    .into_iter()
+                    .flat_map(|(object, urns)| {
+                        urns.into_iter().map(move |urn| Permission {
+                            object: object.clone(),
+                            actions: vec![urn.action.into()],
+                            resources: vec![
+                                urn.instance
+                                    .map(ResourcePattern::from)
+                                    .unwrap_or_else(ResourcePattern::any),
+                            ],
+                        })
+                    })
+                    .collect(),
+            })
+            .collect();
 
-    /// check if specific instance of spec can be deleted
-    async fn allow_instance_action(
-        &self,
-        _ty: ObjectType,
-        _action: InstanceAction,
-        _key: &str,
-    ) -> Result<bool, AuthError> {
-        Ok(true)
+        Self {
+            roles,
+            bindings: vec![],
+        }
     }
 }
 
@@ -84,10 +73,10 @@ mod policy {
     use tracing::debug;
     use serde::{Serialize, Deserialize};
 
-    use fluvio_auth::{AuthError, TypeAction, InstanceAction};
+    use fluvio_auth::{AuthError, TypeAction};
     use fluvio_auth::x509::X509Identity;
 
-    use super::ObjectType;
+    use super::{ObjectType, PolicyAction};
 
     type Role = String;
 
@@ -161,11 +150,14 @@ mod policy {
         }
     }
 
-    impl From<InstanceAction> for Action {
-        fn from(action: InstanceAction) -> Self {
+    impl From<Action> for PolicyAction {
+        fn from(action: Action) -> Self {
             match action {
-                InstanceAction::Delete => Action::Delete,
-                InstanceAction::Update => Action::Update,
+                Action::Create => PolicyAction::Create,
+                Action::Read => PolicyAction::Read,
+                Action::Update => PolicyAction::Update,
+                Action::Delete => PolicyAction::Delete,
+                Action::All => PolicyAction::All,
             }
         }
     }
@@ -253,6 +245,9 @@ mod policy {
                 ObjectType::TableFormat,
                 vec![ActionUrn::new(Action::All, None)],
             );
+            root_policy.insert(ObjectType::Schema, vec![ActionUrn::new(Action::All, None)]);
+            root_policy.insert(ObjectType::Policy, vec![ActionUrn::new(Action::All, None)]);
+            root_policy.insert(ObjectType::Quota, vec![ActionUrn::new(Action::All, None)]);
             root_policy.insert(
                 ObjectType::Mirror,
                 vec![
@@ -410,4 +405,36 @@ mod test {
                 .expect("eval")
         );
     }
+
+    #[test]
+    fn test_basic_policy_conversion() {
+        use fluvio_controlplane_metadata::policy::{PolicyAction, PolicySpec};
+
+        let mut basic = BasicRbacPolicy::default();
+        let mut role = HashMap::new();
+        role.insert(
+            ObjectType::Topic,
+            vec![
+                ActionUrn::new(Action::Read, None),
+                ActionUrn::new(Action::Delete, Some("test".to_string())),
+            ],
+        );
+        basic.0.insert(String::from("Default"), role);
+
+        let policy = PolicySpec::from(basic);
+        let scopes = vec!["Default".to_owned()];
+        let topic = ObjectType::Topic;
+
+        assert!(policy.is_allowed("user", &scopes, &topic, PolicyAction::Read, None));
+        assert!(policy.is_allowed("user", &scopes, &topic, PolicyAction::Delete, Some("test")));
+        assert!(!policy.is_allowed("user", &scopes, &topic, PolicyAction::Delete, Some("other")));
+        assert!(!policy.is_allowed("user", &scopes, &topic, PolicyAction::Produce, Some("test")));
+        assert!(policy.is_allowed(
+            "user",
+            &["Root".to_owned()],
+            &topic,
+            PolicyAction::Produce,
+            Some("test")
+        ));
+    }
 }
@@ -1,4 +1,5 @@
 pub mod basic;
+pub mod policy;
 
 pub use common::*;
 
@@ -0,0 +1,50 @@
+//!
+//! # Managed Policy Sync
+//!
+//! Keeps policies managed as SC objects in sync with RBAC policy used by public service.
+//!
+
+use std::sync::Arc;
+
+use tracing::{debug, info, instrument};
+
+use fluvio_auth::rbac::RbacPolicy;
+use fluvio_future::task::spawn;
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::SharedContext;
+use crate::stores::StoreContext;
+use crate::stores::policy::PolicySpec;
+
+pub struct PolicyController<C: MetadataItem> {
+    policies: StoreContext<PolicySpec, C>,
+    rbac: Arc<RbacPolicy>,
+}
+
+impl<C: MetadataItem + 'static> PolicyController<C> {
+    pub fn start(ctx: SharedContext<C>, rbac: Arc<RbacPolicy>) {
+        let controller = Self {
+            policies: ctx.policies().clone(),
+            rbac,
+        };
+
+        info!("starting policy controller");
+        spawn(controller.dispatch_loop());
+    }
+
+    #[instrument(skip(self), name = "PolicyControllerLoop")]
+    async fn dispatch_loop(self) {
+        let mut listener = self.policies.change_listener();
+        let _ = listener.wait_for_initial_sync().await;
+
+        loop {
+            let policies = self.policies.store().clone_values().await;
+            debug!(count = policies.len(), "applying managed policies");
+            self.rbac
+                .set_managed_policies(policies.into_iter().map(|policy| policy.spec));
+
+            listener.listen().await;
+            listener.load_last();
+        }
+    }
+}
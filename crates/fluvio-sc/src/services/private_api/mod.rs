@@ -1,5 +1,6 @@
 mod private_server;
 
+use fluvio_future::task::spawn;
 use fluvio_stream_model::core::MetadataItem;
 use tracing::info;
 use tracing::instrument;
@@ -22,6 +23,12 @@ where
     info!("starting internal services");
 
     let addr = ctx.config().private_endpoint.clone();
+    let shutdown = ctx.shutdown().clone();
     let server = FluvioApiServer::new(addr, ctx, ScInternalService::new());
-    server.run();
+    let server_shutdown = server.run();
+    spawn(async move {
+        shutdown.listen().await;
+        info!("stopping internal services");
+        server_shutdown.notify();
+    });
 }
@@ -18,10 +18,19 @@ use fluvio_controlplane::sc_api::update_partition::UpdatePartitionStatRequest;
 use fluvio_controlplane::spu_api::update_mirror::MirrorMsg;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
 use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
+use fluvio_controlplane::spu_api::update_schema::SchemaMsg;
+use fluvio_controlplane::spu_api::update_schema::UpdateSchemaRequest;
+use fluvio_controlplane::spu_api::update_policy::PolicyMsg;
+use fluvio_controlplane::spu_api::update_policy::UpdatePolicyRequest;
+use fluvio_controlplane::spu_api::update_quota::QuotaMsg;
+use fluvio_controlplane::spu_api::update_quota::UpdateQuotaRequest;
 use fluvio_controlplane::spu_api::update_smartmodule::UpdateSmartModuleRequest;
 use fluvio_controlplane::spu_api::update_spu::UpdateSpuRequest;
 use fluvio_controlplane_metadata::message::Message;
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_sc_schema::schema::SchemaSpec;
+use fluvio_sc_schema::policy::PolicySpec;
+use fluvio_sc_schema::quota::QuotaSpec;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_stream_model::store::ChangeListener;
 use tracing::warn;
@@ -135,6 +144,9 @@ where
     let mut partition_spec_listener = context.partitions().change_listener();
     let mut sm_spec_listener = context.smartmodules().change_listener();
     let mut mirror_spec_listener = context.mirrors().change_listener();
+    let mut schema_spec_listener = context.schemas().change_listener();
+    let mut policy_spec_listener = context.policies().change_listener();
+    let mut quota_spec_listener = context.quotas().change_listener();
 
     // send initial changes
 
@@ -148,6 +160,9 @@ where
         send_smartmodule_changes(&mut sm_spec_listener, &mut sink, spu_id).await?;
         send_replica_spec_changes(&mut partition_spec_listener, &mut sink, spu_id).await?;
         send_mirror_changes(&mut mirror_spec_listener, &mut sink, spu_id).await?;
+        send_schema_changes(&mut schema_spec_listener, &mut sink, spu_id).await?;
+        send_policy_changes(&mut policy_spec_listener, &mut sink, spu_id).await?;
+        send_quota_changes(&mut quota_spec_listener, &mut sink, spu_id).await?;
 
         trace!(spu_id, "waiting for SPU channel");
 
@@ -158,6 +173,11 @@ where
                 break;
             },
 
+            _ = context.shutdown().listen() => {
+                info!(spu_id, "SC is shutting down. ending");
+                break;
+            },
+
             spu_request_msg = api_stream.next() =>  {
 
 
@@ -211,6 +231,18 @@ where
                 debug!("mirror lister changed");
             }
 
+            _ = schema_spec_listener.listen() => {
+                debug!("schema lister changed");
+            }
+
+            _ = policy_spec_listener.listen() => {
+                debug!("policy lister changed");
+            }
+
+            _ = quota_spec_listener.listen() => {
+                debug!("quota lister changed");
+            }
+
         }
     }
 
@@ -236,13 +268,14 @@ where
         if let Some(partition) = read_guard.get(&lrs_req.id) {
             let mut current_status = partition.inner().status().clone();
             let key = lrs_req.id.clone();
-            let new_status = PartitionStatus::new2(
+            let mut new_status = PartitionStatus::new2(
                 lrs_req.leader,
                 lrs_req.replicas,
                 lrs_req.size,
                 PartitionResolution::Online,
                 lrs_req.base_offset,
             );
+            new_status.corrupt_batches = lrs_req.corrupt_batches;
             current_status.merge(new_status);
 
             actions.push(WSAction::<PartitionSpec, C>::UpdateStatus((
@@ -604,3 +637,165 @@ async fn send_mirror_changes<C: MetadataItem>(
     sink.send_request(&message).await?;
     Ok(())
 }
+
+#[instrument(level = "trace", skip(sink))]
+async fn send_schema_changes<C: MetadataItem>(
+    listener: &mut ChangeListener<SchemaSpec, C>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    let changes = listener
+        .sync_changes_with_filter(&ChangeFlag {
+            spec: true,
+            status: false,
+            meta: true,
+        })
+        .await;
+    if changes.is_empty() {
+        trace!("spec changes is empty, skipping");
+        return Ok(());
+    }
+
+    let epoch = changes.epoch;
+
+    let is_sync_all = changes.is_sync_all();
+    let (updates, deletes) = changes.parts();
+
+    let request = if is_sync_all {
+        UpdateSchemaRequest::with_all(epoch, updates.into_iter().map(|s| s.into()).collect())
+    } else {
+        let mut changes: Vec<SchemaMsg> = updates
+            .into_iter()
+            .map(|s| Message::update(s.into()))
+            .collect();
+        let mut deletes = deletes
+            .into_iter()
+            .map(|s| Message::delete(s.into()))
+            .collect();
+        changes.append(&mut deletes);
+        UpdateSchemaRequest::with_changes(epoch, changes)
+    };
+
+    debug!(?request, "sending schema to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
+
+#[instrument(level = "trace", skip(sink))]
+async fn send_policy_changes<C: MetadataItem>(
+    listener: &mut ChangeListener<PolicySpec, C>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    let changes = listener
+        .sync_changes_with_filter(&ChangeFlag {
+            spec: true,
+            status: false,
+            meta: true,
+        })
+        .await;
+    if changes.is_empty() {
+        trace!("spec changes is empty, skipping");
+        return Ok(());
+    }
+
+    let epoch = changes.epoch;
+
+    let is_sync_all = changes.is_sync_all();
+    let (updates, deletes) = changes.parts();
+
+    let request = if is_sync_all {
+        UpdatePolicyRequest::with_all(epoch, updates.into_iter().map(|s| s.into()).collect())
+    } else {
+        let mut changes: Vec<PolicyMsg> = updates
+            .into_iter()
+            .map(|s| Message::update(s.into()))
+            .collect();
+        let mut deletes = deletes
+            .into_iter()
+            .map(|s| Message::delete(s.into()))
+            .collect();
+        changes.append(&mut deletes);
+        UpdatePolicyRequest::with_changes(epoch, changes)
+    };
+
+    debug!(?request, "sending policy to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
+
+#[instrument(level = "trace", skip(sink))]
+async fn send_quota_changes<C: MetadataItem>(
+    listener: &mut ChangeListener<QuotaSpec, C>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    let changes = listener
+        .sync_changes_with_filter(&ChangeFlag {
+            spec: true,
+            status: false,
+            meta: true,
+        })
+        .await;
+    if changes.is_empty() {
+        trace!("spec changes is empty, skipping");
+        return Ok(());
+    }
+
+    let epoch = changes.epoch;
+
+    let is_sync_all = changes.is_sync_all();
+    let (updates, deletes) = changes.parts();
+
+    let request = if is_sync_all {
+        UpdateQuotaRequest::with_all(epoch, updates.into_iter().map(|s| s.into()).collect())
+    } else {
+        let mut changes: Vec<QuotaMsg> = updates
+            .into_iter()
+            .map(|s| Message::update(s.into()))
+            .collect();
+        let mut deletes = deletes
+            .into_iter()
+            .map(|s| Message::delete(s.into()))
+            .collect();
+        changes.append(&mut deletes);
+        UpdateQuotaRequest::with_changes(epoch, changes)
+    };
+
+    debug!(?request, "sending quota to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
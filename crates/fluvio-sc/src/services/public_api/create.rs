@@ -7,6 +7,9 @@ use anyhow::Result;
 use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
 use fluvio_controlplane_metadata::spg::SpuGroupSpec;
 use fluvio_controlplane_metadata::spu::CustomSpuSpec;
+use fluvio_controlplane_metadata::schema::SchemaSpec;
+use fluvio_controlplane_metadata::policy::PolicySpec;
+use fluvio_controlplane_metadata::quota::QuotaSpec;
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
@@ -38,6 +41,12 @@ pub async fn handle_create_request<AC: AuthContext, C: MetadataItem>(
         super::tableformat::handle_create_tableformat_request(create, auth_context).await?
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<MirrorSpec>> {
         super::mirror::handle_register_mirror(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<SchemaSpec>> {
+        super::schema::handle_create_schema_request(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<PolicySpec>> {
+        super::policy::handle_create_policy_request(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<QuotaSpec>> {
+        super::quota::handle_create_quota_request(create, auth_context).await?
     } else {
         error!("unknown create request: {:#?}", req);
         Status::new(
@@ -14,6 +14,9 @@ use anyhow::Result;
 use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
 use fluvio_controlplane_metadata::spg::SpuGroupSpec;
 use fluvio_controlplane_metadata::spu::CustomSpuSpec;
+use fluvio_controlplane_metadata::schema::SchemaSpec;
+use fluvio_controlplane_metadata::policy::PolicySpec;
+use fluvio_controlplane_metadata::quota::QuotaSpec;
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
@@ -46,6 +49,12 @@ pub async fn handle_delete_request<AC: AuthContext, C: MetadataItem>(
         super::tableformat::handle_delete_tableformat(req.key(), auth_ctx).await?
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<MirrorSpec>> {
         super::mirror::handle_unregister_mirror(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<SchemaSpec>> {
+        super::schema::handle_delete_schema(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<PolicySpec>> {
+        super::policy::handle_delete_policy(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<QuotaSpec>> {
+        super::quota::handle_delete_quota(req.key(), auth_ctx).await?
     } else {
         error!("unknown create request: {:#?}", del_req);
         Status::new(
@@ -5,6 +5,9 @@ use fluvio_controlplane_metadata::{
     partition::PartitionSpec,
     smartmodule::SmartModuleSpec,
     tableformat::TableFormatSpec,
+    schema::SchemaSpec,
+    policy::PolicySpec,
+    quota::QuotaSpec,
 };
 use fluvio_stream_model::core::MetadataItem;
 use tracing::{debug, instrument};
@@ -76,6 +79,24 @@ pub async fn handle_list_request<AC: AuthContext, C: MetadataItem>(
             .await?,
             header.api_version(),
         )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<SchemaSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(req.name_filters, auth_ctx, auth_ctx.global_ctx.schemas())
+                .await?,
+            header.api_version(),
+        )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<PolicySpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(req.name_filters, auth_ctx, auth_ctx.global_ctx.policies())
+                .await?,
+            header.api_version(),
+        )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<QuotaSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(req.name_filters, auth_ctx, auth_ctx.global_ctx.quotas())
+                .await?,
+            header.api_version(),
+        )?
     } else if let Some(req) = req.downcast()? as Option<ListRequest<MirrorSpec>> {
         ObjectApiListResponse::try_encode_from(
             handle_list_mirror(req.name_filters, auth_ctx).await?,
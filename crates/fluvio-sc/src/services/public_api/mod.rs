@@ -14,6 +14,9 @@ mod tableformat;
 mod derivedstream;
 mod mirror;
 mod mirroring;
+mod schema;
+mod policy;
+mod quota;
 
 pub use server::start_public_server;
 
@@ -21,6 +24,7 @@ mod server {
 
     use std::fmt::Debug;
 
+    use fluvio_future::task::spawn;
     use fluvio_stream_model::core::MetadataItem;
     use tracing::debug;
 
@@ -40,8 +44,14 @@ mod server {
         <A as Authorization>::Context: Send + Sync,
     {
         let addr = ctx.global_ctx.config().public_endpoint.clone();
+        let shutdown = ctx.global_ctx.shutdown().clone();
         debug!("starting public api service");
         let server = FluvioApiServer::new(addr, ctx, PublicService::new());
-        server.run();
+        let server_shutdown = server.run();
+        spawn(async move {
+            shutdown.listen().await;
+            debug!("stopping public api service");
+            server_shutdown.notify();
+        });
     }
 }
@@ -0,0 +1,84 @@
+//!
+//! # Create Policy Request
+//!
+//! Validates policy and stores it, policy takes effect on SC and SPUs once it is propagated.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::policy::PolicySpec;
+use fluvio_sc_schema::shared::validate_resource_name;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for create policy request
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_policy_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<PolicySpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating policy");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(PolicySpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if let Err(err) = validate_resource_name(&name) {
+        return Ok(invalid(name, format!("Invalid policy name: '{err}'")));
+    }
+
+    if let Err(err) = spec.validate() {
+        return Ok(invalid(name, err));
+    }
+
+    let policies = auth_ctx.global_ctx.policies();
+    if policies.store().contains_key(&name).await {
+        debug!("policy already exists");
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::PolicyAlreadyExists,
+            Some(format!("policy '{name}' already defined")),
+        ));
+    }
+
+    let status = if let Err(err) = policies.create_spec(name.clone(), spec).await {
+        Status::new(
+            name,
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        )
+    } else {
+        info!(%name, "policy created");
+        Status::new_ok(name)
+    };
+    trace!("create policy response {:#?}", status);
+
+    Ok(status)
+}
+
+pub(super) fn invalid(name: String, reason: String) -> Status {
+    Status::new(name, ErrorCode::PolicyInvalid(reason.clone()), Some(reason))
+}
@@ -0,0 +1,62 @@
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{info, trace, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::policy::PolicySpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for delete policy request
+#[instrument(skip(name, auth_ctx))]
+pub async fn handle_delete_policy<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    info!(%name, "deleting policy");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(PolicySpec::OBJECT_TYPE, InstanceAction::Delete, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let policies = auth_ctx.global_ctx.policies();
+    if policies.store().value(&name).await.is_none() {
+        return Ok(Status::new(
+            name,
+            ErrorCode::PolicyNotFound,
+            Some("not found".to_owned()),
+        ));
+    }
+
+    let status = if let Err(err) = policies.delete(name.clone()).await {
+        Status::new(
+            name.clone(),
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        )
+    } else {
+        info!(%name, "policy deleted");
+        Status::new_ok(name)
+    };
+
+    trace!("flv delete policy resp {:#?}", status);
+
+    Ok(status)
+}
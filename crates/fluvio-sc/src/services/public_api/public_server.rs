@@ -15,6 +15,7 @@ use tracing::debug;
 use tracing::instrument;
 use async_trait::async_trait;
 use anyhow::Result;
+use futures_util::StreamExt;
 
 use fluvio_service::ConnectInfo;
 use fluvio_types::event::StickyEvent;
@@ -58,6 +59,7 @@ where
         mut socket: FluvioSocket,
         _connection: ConnectInfo,
     ) -> Result<()> {
+        let _connection = ctx.global_ctx.metrics().track_connection();
         let auth_context = ctx
             .auth
             .create_auth_context(&mut socket)
@@ -74,7 +76,10 @@ where
         ));
 
         let (sink, mut stream) = socket.split();
-        let mut api_stream = stream.api_stream::<AdminPublicDecodedRequest, AdminPublicApiKey>();
+        let shutdown = ctx.global_ctx.shutdown().clone();
+        let mut api_stream = stream
+            .api_stream::<AdminPublicDecodedRequest, AdminPublicApiKey>()
+            .take_until(shutdown.listen_pinned());
         let mut shared_sink = sink.as_shared();
 
         let end_event = StickyEvent::shared();
@@ -0,0 +1,62 @@
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{info, trace, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::quota::QuotaSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for delete quota request
+#[instrument(skip(name, auth_ctx))]
+pub async fn handle_delete_quota<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    info!(%name, "deleting quota");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(QuotaSpec::OBJECT_TYPE, InstanceAction::Delete, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let quotas = auth_ctx.global_ctx.quotas();
+    if quotas.store().value(&name).await.is_none() {
+        return Ok(Status::new(
+            name,
+            ErrorCode::QuotaNotFound,
+            Some("not found".to_owned()),
+        ));
+    }
+
+    let status = if let Err(err) = quotas.delete(name.clone()).await {
+        Status::new(
+            name.clone(),
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        )
+    } else {
+        info!(%name, "quota deleted");
+        Status::new_ok(name)
+    };
+
+    trace!("flv delete quota resp {:#?}", status);
+
+    Ok(status)
+}
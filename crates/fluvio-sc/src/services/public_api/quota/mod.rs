@@ -0,0 +1,7 @@
+mod create;
+mod update;
+mod delete;
+
+pub use create::*;
+pub use update::*;
+pub use delete::*;
@@ -0,0 +1,74 @@
+//!
+//! # Update Quota Request
+//!
+//! Replaces entity and rates of existing quota.
+//!
+
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{info, trace, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::quota::{QuotaSpec, UpdateQuotaAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, InstanceAction};
+
+use crate::services::auth::AuthServiceContext;
+
+use super::create::invalid;
+
+/// Handler for update quota request
+#[instrument(skip(action, auth_ctx))]
+pub async fn handle_update_quota_request<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    action: UpdateQuotaAction,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    info!(%name, "updating quota");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(QuotaSpec::OBJECT_TYPE, InstanceAction::Update, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let quotas = auth_ctx.global_ctx.quotas();
+    if quotas.store().value(&name).await.is_none() {
+        return Ok(Status::new(
+            name,
+            ErrorCode::QuotaNotFound,
+            Some("not found".to_owned()),
+        ));
+    }
+
+    let UpdateQuotaAction::Replace(spec) = action;
+    if let Err(err) = spec.validate() {
+        return Ok(invalid(name, err));
+    }
+
+    let status = if let Err(err) = quotas.create_spec(name.clone(), spec).await {
+        Status::new(
+            name,
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        )
+    } else {
+        info!(%name, "quota updated");
+        Status::new_ok(name)
+    };
+
+    Ok(status)
+}
@@ -0,0 +1,787 @@
+//!
+//! # Schema validation and compatibility
+//!
+//! Definitions are checked structurally, which covers the usual evolution of records:
+//! adding or removing fields, changing field types and required fields.
+//! New version is checked against latest version of subject only.
+//!
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+use fluvio_controlplane_metadata::schema::{CompatibilityMode, SchemaType};
+
+/// check that definition is well formed schema of type
+pub(crate) fn validate(schema_type: SchemaType, definition: &str) -> Result<(), String> {
+    match schema_type {
+        SchemaType::Avro => avro::validate(&parse_json(definition)?),
+        SchemaType::Json => json::validate(&parse_json(definition)?),
+        SchemaType::Protobuf => protobuf::parse(definition).map(|_| ()),
+    }
+}
+
+/// check that data written with `new` and `previous` definitions can be read as required by mode
+pub(crate) fn check_compatibility(
+    schema_type: SchemaType,
+    mode: CompatibilityMode,
+    previous: &str,
+    new: &str,
+) -> Result<(), String> {
+    let (backward, forward) = match mode {
+        CompatibilityMode::None => return Ok(()),
+        CompatibilityMode::Backward => (true, false),
+        CompatibilityMode::Forward => (false, true),
+        CompatibilityMode::Full => (true, true),
+    };
+    match schema_type {
+        SchemaType::Avro | SchemaType::Json => {
+            let previous = parse_json(previous)?;
+            let new = parse_json(new)?;
+            let can_read = if schema_type == SchemaType::Avro {
+                avro::can_read
+            } else {
+                json::can_read
+            };
+            if backward {
+                can_read(&new, &previous, "$").map_err(|err| format!("backward: {err}"))?;
+            }
+            if forward {
+                can_read(&previous, &new, "$").map_err(|err| format!("forward: {err}"))?;
+            }
+        }
+        SchemaType::Protobuf => {
+            let previous = protobuf::parse(previous)?;
+            let new = protobuf::parse(new)?;
+            if backward {
+                protobuf::can_read(&new, &previous).map_err(|err| format!("backward: {err}"))?;
+            }
+            if forward {
+                protobuf::can_read(&previous, &new).map_err(|err| format!("forward: {err}"))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_json(definition: &str) -> Result<Value, String> {
+    serde_json::from_str(definition).map_err(|err| format!("definition is not valid JSON: {err}"))
+}
+
+mod avro {
+
+    use super::*;
+
+    /// type name of schema: primitive or complex type, `union` for arrays
+    fn type_name(schema: &Value) -> Option<&str> {
+        match schema {
+            Value::String(name) => Some(name),
+            Value::Object(object) => match object.get("type") {
+                Some(Value::String(name)) => Some(name),
+                _ => None,
+            },
+            Value::Array(_) => Some("union"),
+            _ => None,
+        }
+    }
+
+    fn object(schema: &Value) -> Option<&Map<String, Value>> {
+        match schema {
+            Value::Object(object) => match object.get("type") {
+                // `{"type": {"type": "record", ..}}` wraps another schema
+                Some(inner @ (Value::Object(_) | Value::Array(_))) => inner.as_object(),
+                _ => Some(object),
+            },
+            _ => None,
+        }
+    }
+
+    /// inner schema when type is nested in object
+    fn unwrap(schema: &Value) -> &Value {
+        match schema {
+            Value::Object(object) => match object.get("type") {
+                Some(inner @ (Value::Object(_) | Value::Array(_))) => unwrap(inner),
+                _ => schema,
+            },
+            _ => schema,
+        }
+    }
+
+    pub(super) fn validate(schema: &Value) -> Result<(), String> {
+        let schema = unwrap(schema);
+        let Some(name) = type_name(schema) else {
+            return Err(format!("invalid avro schema: {schema}"));
+        };
+        match name {
+            "union" => {
+                if let Value::Array(branches) = schema {
+                    for branch in branches {
+                        validate(branch)?;
+                    }
+                }
+                Ok(())
+            }
+            "record" | "error" => {
+                let Some(record) = object(schema) else {
+                    return Err("record must be an object".to_owned());
+                };
+                if !record.get("name").is_some_and(Value::is_string) {
+                    return Err("record must have a name".to_owned());
+                }
+                let Some(Value::Array(fields)) = record.get("fields") else {
+                    return Err("record must have fields".to_owned());
+                };
+                for field in fields {
+                    if !field.get("name").is_some_and(Value::is_string) {
+                        return Err(format!("record field must have a name: {field}"));
+                    }
+                    let Some(field_type) = field.get("type") else {
+                        return Err(format!("record field must have a type: {field}"));
+                    };
+                    validate(field_type)?;
+                }
+                Ok(())
+            }
+            "enum" => match object(schema).and_then(|e| e.get("symbols")) {
+                Some(Value::Array(_)) => Ok(()),
+                _ => Err("enum must have symbols".to_owned()),
+            },
+            "array" => match object(schema).and_then(|a| a.get("items")) {
+                Some(items) => validate(items),
+                None => Err("array must have items".to_owned()),
+            },
+            "map" => match object(schema).and_then(|m| m.get("values")) {
+                Some(values) => validate(values),
+                None => Err("map must have values".to_owned()),
+            },
+            "fixed" => match object(schema).and_then(|f| f.get("size")) {
+                Some(Value::Number(_)) => Ok(()),
+                _ => Err("fixed must have size".to_owned()),
+            },
+            // primitives and references to named types
+            _ => Ok(()),
+        }
+    }
+
+    fn promotes(writer: &str, reader: &str) -> bool {
+        matches!(
+            (writer, reader),
+            ("int", "long" | "float" | "double")
+                | ("long", "float" | "double")
+                | ("float", "double")
+                | ("string", "bytes")
+                | ("bytes", "string")
+        )
+    }
+
+    /// check that reader schema can read data written with writer schema
+    pub(super) fn can_read(reader: &Value, writer: &Value, path: &str) -> Result<(), String> {
+        let reader = unwrap(reader);
+        let writer = unwrap(writer);
+        let reader_type = type_name(reader).unwrap_or_default();
+        let writer_type = type_name(writer).unwrap_or_default();
+
+        if let Value::Array(branches) = writer {
+            // every value written by writer must be readable
+            return branches
+                .iter()
+                .try_for_each(|branch| can_read(reader, branch, path));
+        }
+        if let Value::Array(branches) = reader {
+            return if branches
+                .iter()
+                .any(|branch| can_read(branch, writer, path).is_ok())
+            {
+                Ok(())
+            } else {
+                Err(format!("{path}: union doesn't accept {writer_type}"))
+            };
+        }
+
+        if reader_type != writer_type {
+            return if promotes(writer_type, reader_type) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{path}: type changed from {writer_type} to {reader_type}"
+                ))
+            };
+        }
+
+        let (Some(reader), Some(writer)) = (object(reader), object(writer)) else {
+            return Ok(());
+        };
+        match reader_type {
+            "record" | "error" => {
+                let writer_fields = fields(writer);
+                for (name, field) in fields(reader) {
+                    let field_path = format!("{path}.{name}");
+                    match writer_fields.get(name) {
+                        Some(writer_field) => can_read(
+                            field.get("type").unwrap_or(&Value::Null),
+                            writer_field.get("type").unwrap_or(&Value::Null),
+                            &field_path,
+                        )?,
+                        None if field.get("default").is_some() => {}
+                        None => {
+                            return Err(format!("{field_path}: field without default is missing"));
+                        }
+                    }
+                }
+                Ok(())
+            }
+            "enum" => {
+                if reader.get("default").is_some() {
+                    return Ok(());
+                }
+                let symbols = |schema: &Map<String, Value>| -> Vec<Value> {
+                    match schema.get("symbols") {
+                        Some(Value::Array(symbols)) => symbols.clone(),
+                        _ => vec![],
+                    }
+                };
+                let reader_symbols = symbols(reader);
+                match symbols(writer)
+                    .into_iter()
+                    .find(|symbol| !reader_symbols.contains(symbol))
+                {
+                    Some(symbol) => Err(format!("{path}: enum symbol {symbol} was removed")),
+                    None => Ok(()),
+                }
+            }
+            "array" => can_read(
+                reader.get("items").unwrap_or(&Value::Null),
+                writer.get("items").unwrap_or(&Value::Null),
+                &format!("{path}[]"),
+            ),
+            "map" => can_read(
+                reader.get("values").unwrap_or(&Value::Null),
+                writer.get("values").unwrap_or(&Value::Null),
+                &format!("{path}{{}}"),
+            ),
+            "fixed" if reader.get("size") != writer.get("size") => {
+                Err(format!("{path}: fixed size changed"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn fields(record: &Map<String, Value>) -> BTreeMap<&str, &Value> {
+        match record.get("fields") {
+            Some(Value::Array(fields)) => fields
+                .iter()
+                .filter_map(|field| Some((field.get("name")?.as_str()?, field)))
+                .collect(),
+            _ => BTreeMap::new(),
+        }
+    }
+}
+
+mod json {
+
+    use super::*;
+
+    pub(super) fn validate(schema: &Value) -> Result<(), String> {
+        match schema {
+            Value::Bool(_) => Ok(()),
+            Value::Object(object) => {
+                if let Some(properties) = object.get("properties") {
+                    let Value::Object(properties) = properties else {
+                        return Err("properties must be an object".to_owned());
+                    };
+                    for property in properties.values() {
+                        validate(property)?;
+                    }
+                }
+                if let Some(required) = object.get("required") {
+                    if !required
+                        .as_array()
+                        .is_some_and(|required| required.iter().all(Value::is_string))
+                    {
+                        return Err("required must be an array of property names".to_owned());
+                    }
+                }
+                match object.get("items") {
+                    Some(items) => validate(items),
+                    None => Ok(()),
+                }
+            }
+            _ => Err(format!("invalid JSON schema: {schema}")),
+        }
+    }
+
+    fn types(schema: &Map<String, Value>) -> Option<Vec<&str>> {
+        match schema.get("type")? {
+            Value::String(name) => Some(vec![name.as_str()]),
+            Value::Array(names) => Some(names.iter().filter_map(Value::as_str).collect()),
+            _ => None,
+        }
+    }
+
+    fn required(schema: &Map<String, Value>) -> Vec<&str> {
+        match schema.get("required") {
+            Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        }
+    }
+
+    fn closed(schema: &Map<String, Value>) -> bool {
+        schema.get("additionalProperties") == Some(&Value::Bool(false))
+    }
+
+    /// check that documents valid for writer schema are valid for reader schema
+    pub(super) fn can_read(reader: &Value, writer: &Value, path: &str) -> Result<(), String> {
+        let (Value::Object(reader), Value::Object(writer)) = (reader, writer) else {
+            return match reader {
+                Value::Bool(false) => Err(format!("{path}: no longer accepts any value")),
+                _ => Ok(()),
+            };
+        };
+
+        if let Some(reader_types) = types(reader) {
+            let accepts = |ty: &str| {
+                reader_types.contains(&ty) || (ty == "integer" && reader_types.contains(&"number"))
+            };
+            match types(writer) {
+                Some(writer_types) => {
+                    if let Some(ty) = writer_types.into_iter().find(|ty| !accepts(ty)) {
+                        return Err(format!("{path}: type {ty} is no longer accepted"));
+                    }
+                }
+                None => return Err(format!("{path}: type was restricted")),
+            }
+        }
+
+        if let Some(Value::Array(reader_values)) = reader.get("enum") {
+            match writer.get("enum") {
+                Some(Value::Array(writer_values)) => {
+                    if let Some(value) = writer_values
+                        .iter()
+                        .find(|value| !reader_values.contains(value))
+                    {
+                        return Err(format!("{path}: enum value {value} was removed"));
+                    }
+                }
+                _ => return Err(format!("{path}: values were restricted to enum")),
+            }
+        }
+
+        let writer_required = required(writer);
+        if let Some(name) = required(reader)
+            .into_iter()
+            .find(|name| !writer_required.contains(name))
+        {
+            return Err(format!("{path}.{name}: property became required"));
+        }
+
+        let empty = Map::new();
+        let reader_properties = reader
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+        let writer_properties = writer
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+        for (name, property) in reader_properties {
+            if let Some(writer_property) = writer_properties.get(name) {
+                can_read(property, writer_property, &format!("{path}.{name}"))?;
+            }
+        }
+        if closed(reader) {
+            if !closed(writer) {
+                return Err(format!(
+                    "{path}: additional properties are no longer allowed"
+                ));
+            }
+            if let Some(name) = writer_properties
+                .keys()
+                .find(|name| !reader_properties.contains_key(*name))
+            {
+                return Err(format!("{path}.{name}: property was removed"));
+            }
+        }
+
+        match (reader.get("items"), writer.get("items")) {
+            (Some(reader_items), Some(writer_items)) => {
+                can_read(reader_items, writer_items, &format!("{path}[]"))
+            }
+            (Some(reader_items), None) => can_read(reader_items, &Value::Bool(true), path),
+            _ => Ok(()),
+        }
+    }
+}
+
+mod protobuf {
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub(super) struct Field {
+        name: String,
+        label: Option<String>,
+        ty: String,
+    }
+
+    /// fields of messages by field number, messages are keyed by qualified name
+    pub(super) type Messages = BTreeMap<String, BTreeMap<u32, Field>>;
+
+    fn tokenize(definition: &str) -> Result<Vec<String>, String> {
+        let mut tokens = vec![];
+        let mut chars = definition.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '/' if chars.peek() == Some(&'/') => {
+                    for c in chars.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+                '/' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    let mut previous = ' ';
+                    loop {
+                        match chars.next() {
+                            Some('/') if previous == '*' => break,
+                            Some(c) => previous = c,
+                            None => return Err("unterminated comment".to_owned()),
+                        }
+                    }
+                }
+                '"' | '\'' => {
+                    let mut literal = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(end) if end == c => break,
+                            Some(c) => literal.push(c),
+                            None => return Err("unterminated string".to_owned()),
+                        }
+                    }
+                    tokens.push(format!("\"{literal}\""));
+                }
+                '{' | '}' | ';' | '=' | '<' | '>' | ',' | '[' | ']' | '(' | ')' => {
+                    tokens.push(c.to_string())
+                }
+                c if c.is_whitespace() => {}
+                c => {
+                    let mut word = c.to_string();
+                    while let Some(&next) = chars.peek() {
+                        if next.is_whitespace() || "{};=<>,[]()\"'/".contains(next) {
+                            break;
+                        }
+                        word.push(next);
+                        chars.next();
+                    }
+                    tokens.push(word);
+                }
+            }
+        }
+        Ok(tokens)
+    }
+
+    struct Parser {
+        tokens: Vec<String>,
+        position: usize,
+        messages: Messages,
+    }
+
+    impl Parser {
+        fn next(&mut self) -> Result<String, String> {
+            let token = self
+                .tokens
+                .get(self.position)
+                .cloned()
+                .ok_or_else(|| "unexpected end of definition".to_owned())?;
+            self.position += 1;
+            Ok(token)
+        }
+
+        fn peek(&self) -> Option<&str> {
+            self.tokens.get(self.position).map(String::as_str)
+        }
+
+        fn expect(&mut self, expected: &str) -> Result<(), String> {
+            let token = self.next()?;
+            if token == expected {
+                Ok(())
+            } else {
+                Err(format!("expected '{expected}', found '{token}'"))
+            }
+        }
+
+        /// skip statement up to `;` or whole block
+        fn skip_statement(&mut self) -> Result<(), String> {
+            loop {
+                match self.next()?.as_str() {
+                    ";" => return Ok(()),
+                    "{" => return self.skip_block(),
+                    _ => {}
+                }
+            }
+        }
+
+        fn skip_block(&mut self) -> Result<(), String> {
+            let mut depth = 1;
+            while depth > 0 {
+                match self.next()?.as_str() {
+                    "{" => depth += 1,
+                    "}" => depth -= 1,
+                    _ => {}
+                }
+            }
+            Ok(())
+        }
+
+        fn parse_file(&mut self) -> Result<(), String> {
+            while let Some(token) = self.peek() {
+                match token {
+                    "message" => {
+                        self.next()?;
+                        let name = self.next()?;
+                        self.parse_message(name)?;
+                    }
+                    ";" => {
+                        self.next()?;
+                    }
+                    _ => self.skip_statement()?,
+                }
+            }
+            Ok(())
+        }
+
+        fn parse_message(&mut self, name: String) -> Result<(), String> {
+            self.expect("{")?;
+            self.messages.entry(name.clone()).or_default();
+            self.parse_fields(&name)
+        }
+
+        /// fields of message until end of block, oneof fields belong to enclosing message
+        fn parse_fields(&mut self, message: &str) -> Result<(), String> {
+            loop {
+                let token = self.next()?;
+                match token.as_str() {
+                    "}" => return Ok(()),
+                    ";" => {}
+                    "message" => {
+                        let name = self.next()?;
+                        self.parse_message(format!("{message}.{name}"))?;
+                    }
+                    "oneof" => {
+                        self.next()?;
+                        self.expect("{")?;
+                        self.parse_fields(message)?;
+                    }
+                    "enum" | "extend" | "option" | "reserved" | "extensions" => {
+                        self.skip_statement()?
+                    }
+                    _ => {
+                        let (label, ty) = match token.as_str() {
+                            "optional" | "required" | "repeated" => (Some(token), self.next()?),
+                            _ => (None, token),
+                        };
+                        let ty = if ty == "map" {
+                            self.expect("<")?;
+                            let key = self.next()?;
+                            self.expect(",")?;
+                            let value = self.next()?;
+                            self.expect(">")?;
+                            format!("map<{key},{value}>")
+                        } else {
+                            ty
+                        };
+                        let name = self.next()?;
+                        self.expect("=")?;
+                        let number = self.next()?;
+                        let number: u32 = number
+                            .parse()
+                            .map_err(|_| format!("invalid number of field {name}: {number}"))?;
+                        self.skip_statement()?;
+                        let fields = self.messages.entry(message.to_owned()).or_default();
+                        if fields.insert(number, Field { name, label, ty }).is_some() {
+                            return Err(format!(
+                                "field number {number} is used twice in {message}"
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub(super) fn parse(definition: &str) -> Result<Messages, String> {
+        let mut parser = Parser {
+            tokens: tokenize(definition)?,
+            position: 0,
+            messages: Messages::new(),
+        };
+        parser
+            .parse_file()
+            .map_err(|err| format!("invalid protobuf definition: {err}"))?;
+        if parser.messages.is_empty() {
+            return Err("protobuf definition has no messages".to_owned());
+        }
+        Ok(parser.messages)
+    }
+
+    /// types which have same wire encoding
+    fn wire_group(ty: &str) -> &str {
+        match ty {
+            "int32" | "uint32" | "int64" | "uint64" | "bool" => "varint",
+            "sint32" | "sint64" => "zigzag",
+            "fixed32" | "sfixed32" => "fixed32",
+            "fixed64" | "sfixed64" => "fixed64",
+            "string" | "bytes" => "bytes",
+            other => other,
+        }
+    }
+
+    /// check that messages written with writer definition can be parsed by reader definition
+    pub(super) fn can_read(reader: &Messages, writer: &Messages) -> Result<(), String> {
+        for (message, reader_fields) in reader {
+            let Some(writer_fields) = writer.get(message) else {
+                continue;
+            };
+            for (number, field) in reader_fields {
+                match writer_fields.get(number) {
+                    Some(writer_field) => {
+                        if wire_group(&field.ty) != wire_group(&writer_field.ty) {
+                            return Err(format!(
+                                "{message}.{}: type of field {number} changed from {} to {}",
+                                field.name, writer_field.ty, field.ty
+                            ));
+                        }
+                        if (field.label.as_deref() == Some("repeated"))
+                            != (writer_field.label.as_deref() == Some("repeated"))
+                        {
+                            return Err(format!(
+                                "{message}.{}: field {number} changed between repeated and singular",
+                                field.name
+                            ));
+                        }
+                    }
+                    None if field.label.as_deref() == Some("required") => {
+                        return Err(format!(
+                            "{message}.{}: required field {number} is missing",
+                            field.name
+                        ));
+                    }
+                    None => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const USER_V1: &str = r#"{"type": "record", "name": "User", "fields": [
+        {"name": "name", "type": "string"},
+        {"name": "age", "type": "int"}
+    ]}"#;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(SchemaType::Avro, USER_V1).is_ok());
+        assert!(validate(SchemaType::Avro, r#""string""#).is_ok());
+        assert!(validate(SchemaType::Avro, r#"{"type": "record", "name": "User"}"#).is_err());
+        assert!(validate(SchemaType::Avro, "not json").is_err());
+
+        assert!(validate(
+            SchemaType::Json,
+            r#"{"type": "object", "properties": {"name": {"type": "string"}}, "required": ["name"]}"#
+        )
+        .is_ok());
+        assert!(validate(SchemaType::Json, r#"{"required": "name"}"#).is_err());
+
+        assert!(
+            validate(
+                SchemaType::Protobuf,
+                r#"syntax = "proto3";
+            // user
+            message User { string name = 1; int32 age = 2 [deprecated = true]; }"#
+            )
+            .is_ok()
+        );
+        assert!(validate(SchemaType::Protobuf, "syntax = \"proto3\";").is_err());
+        assert!(validate(SchemaType::Protobuf, "message User { string name = ; }").is_err());
+        assert!(
+            validate(
+                SchemaType::Protobuf,
+                "message User { string a = 1; string b = 1; }"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_avro_compatibility() {
+        // new field with default
+        let with_default = r#"{"type": "record", "name": "User", "fields": [
+            {"name": "name", "type": "string"},
+            {"name": "age", "type": "long"},
+            {"name": "email", "type": ["null", "string"], "default": null}
+        ]}"#;
+        // new field without default
+        let without_default = r#"{"type": "record", "name": "User", "fields": [
+            {"name": "name", "type": "string"},
+            {"name": "age", "type": "int"},
+            {"name": "email", "type": "string"}
+        ]}"#;
+
+        let check =
+            |mode, previous, new| check_compatibility(SchemaType::Avro, mode, previous, new);
+        assert!(check(CompatibilityMode::Backward, USER_V1, with_default).is_ok());
+        // old reader can't read long written by new writer
+        assert!(check(CompatibilityMode::Forward, USER_V1, with_default).is_err());
+        assert!(check(CompatibilityMode::Backward, USER_V1, without_default).is_err());
+        assert!(check(CompatibilityMode::Forward, USER_V1, without_default).is_ok());
+        assert!(check(CompatibilityMode::Full, USER_V1, without_default).is_err());
+        assert!(check(CompatibilityMode::None, USER_V1, without_default).is_ok());
+    }
+
+    #[test]
+    fn test_json_compatibility() {
+        let v1 = r#"{"type": "object", "properties": {
+            "name": {"type": "string"},
+            "age": {"type": "integer"}
+        }, "required": ["name"]}"#;
+        let v2 = r#"{"type": "object", "properties": {
+            "name": {"type": "string"},
+            "age": {"type": "number"},
+            "email": {"type": "string"}
+        }, "required": ["name", "email"]}"#;
+
+        let check =
+            |mode, previous, new| check_compatibility(SchemaType::Json, mode, previous, new);
+        // email became required
+        assert!(check(CompatibilityMode::Backward, v1, v2).is_err());
+        assert!(check(CompatibilityMode::Forward, v1, v2).is_err());
+        let v3 = r#"{"type": "object", "properties": {
+            "name": {"type": "string"},
+            "age": {"type": "number"}
+        }, "required": ["name"]}"#;
+        assert!(check(CompatibilityMode::Backward, v1, v3).is_ok());
+        // number written by new version is not integer
+        assert!(check(CompatibilityMode::Forward, v1, v3).is_err());
+    }
+
+    #[test]
+    fn test_protobuf_compatibility() {
+        let v1 = "message User { string name = 1; int32 age = 2; }";
+        let v2 = "message User { string name = 1; int64 age = 2; repeated string tags = 3; }";
+        let v3 = "message User { string name = 1; string age = 2; }";
+        let v4 = "message User { string name = 1; int32 age = 2; required string email = 3; }";
+
+        let check =
+            |mode, previous, new| check_compatibility(SchemaType::Protobuf, mode, previous, new);
+        assert!(check(CompatibilityMode::Full, v1, v2).is_ok());
+        assert!(check(CompatibilityMode::Backward, v1, v3).is_err());
+        assert!(check(CompatibilityMode::Backward, v1, v4).is_err());
+        assert!(check(CompatibilityMode::Forward, v1, v4).is_ok());
+    }
+}
@@ -0,0 +1,103 @@
+//!
+//! # Create Schema Request
+//!
+//! Validates definition and registers it as first version of subject.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::schema::{SchemaSpec, SchemaVersion};
+use fluvio_sc_schema::shared::validate_resource_name;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+
+use crate::services::auth::AuthServiceContext;
+
+use super::compatibility::validate;
+use super::{next_schema_id, REGISTRATION_LOCK};
+
+/// Handler for create schema request
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_schema_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<SchemaSpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating schema");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(SchemaSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if let Err(err) = validate_resource_name(&name) {
+        return Ok(invalid(name, format!("Invalid schema name: '{err}'")));
+    }
+
+    let [SchemaVersion { definition, .. }] = spec.versions.as_slice() else {
+        return Ok(invalid(
+            name,
+            "schema must be created with single definition".to_owned(),
+        ));
+    };
+    if let Err(err) = validate(spec.schema_type, definition) {
+        return Ok(invalid(name, err));
+    }
+
+    let _registration = REGISTRATION_LOCK.lock().await;
+    let schemas = auth_ctx.global_ctx.schemas();
+    if schemas.store().contains_key(&name).await {
+        debug!("schema already exists");
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::SchemaAlreadyExists,
+            Some(format!("schema '{name}' already defined")),
+        ));
+    }
+
+    let id = next_schema_id(&auth_ctx.global_ctx).await;
+    let spec = SchemaSpec {
+        versions: vec![SchemaVersion {
+            id,
+            version: 1,
+            definition: definition.clone(),
+        }],
+        ..spec
+    };
+    let status = if let Err(err) = schemas.create_spec(name.clone(), spec).await {
+        Status::new(
+            name,
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        )
+    } else {
+        info!(%name, id, "schema created");
+        Status::new_ok(name)
+    };
+    trace!("create schema response {:#?}", status);
+
+    Ok(status)
+}
+
+fn invalid(name: String, reason: String) -> Status {
+    Status::new(name, ErrorCode::SchemaInvalid(reason.clone()), Some(reason))
+}
@@ -0,0 +1,76 @@
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{info, trace, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::schema::SchemaSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for delete schema request, schema required by topic can't be deleted
+#[instrument(skip(name, auth_ctx))]
+pub async fn handle_delete_schema<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    info!(%name, "deleting schema");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(SchemaSpec::OBJECT_TYPE, InstanceAction::Delete, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let schemas = auth_ctx.global_ctx.schemas();
+    if schemas.store().value(&name).await.is_none() {
+        return Ok(Status::new(
+            name,
+            ErrorCode::SchemaNotFound,
+            Some("not found".to_owned()),
+        ));
+    }
+
+    let topic = auth_ctx
+        .global_ctx
+        .topics()
+        .store()
+        .read()
+        .await
+        .values()
+        .find(|topic| topic.spec().get_schema() == Some(&name))
+        .map(|topic| topic.key().to_owned());
+    if let Some(topic) = topic {
+        let code = ErrorCode::SchemaInUse { topic };
+        return Ok(Status::new(name, code.clone(), Some(code.to_string())));
+    }
+
+    let status = if let Err(err) = schemas.delete(name.clone()).await {
+        Status::new(
+            name.clone(),
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        )
+    } else {
+        info!(%name, "schema deleted");
+        Status::new_ok(name)
+    };
+
+    trace!("flv delete schema resp {:#?}", status);
+
+    Ok(status)
+}
@@ -0,0 +1,30 @@
+mod compatibility;
+mod create;
+mod update;
+mod delete;
+
+pub use create::*;
+pub use update::*;
+pub use delete::*;
+
+use async_lock::Mutex;
+
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::Context;
+
+/// serializes registrations, so schema ids are not assigned twice
+static REGISTRATION_LOCK: Mutex<()> = Mutex::new(());
+
+/// next free schema id in the cluster, ids start from 1
+async fn next_schema_id<C: MetadataItem>(ctx: &Context<C>) -> u32 {
+    ctx.schemas()
+        .store()
+        .read()
+        .await
+        .values()
+        .map(|schema| schema.spec().max_id())
+        .max()
+        .unwrap_or_default()
+        + 1
+}
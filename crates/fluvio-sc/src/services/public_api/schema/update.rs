@@ -0,0 +1,122 @@
+//!
+//! # Update Schema Request
+//!
+//! Registers new version of subject after compatibility check or changes compatibility mode.
+//!
+
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{info, trace, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::schema::{SchemaSpec, SchemaVersion, UpdateSchemaAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, InstanceAction};
+
+use crate::services::auth::AuthServiceContext;
+
+use super::compatibility::{check_compatibility, validate};
+use super::{next_schema_id, REGISTRATION_LOCK};
+
+/// Handler for update schema request
+#[instrument(skip(action, auth_ctx))]
+pub async fn handle_update_schema_request<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    action: UpdateSchemaAction,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    info!(%name, "updating schema");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(SchemaSpec::OBJECT_TYPE, InstanceAction::Update, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let _registration = REGISTRATION_LOCK.lock().await;
+    let schemas = auth_ctx.global_ctx.schemas();
+    let Some(current) = schemas.store().value(&name).await else {
+        return Ok(Status::new(
+            name,
+            ErrorCode::SchemaNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+    let mut spec = current.inner_owned().spec;
+
+    match action {
+        UpdateSchemaAction::Register(definition) => {
+            if spec
+                .versions
+                .iter()
+                .any(|version| version.definition == definition)
+            {
+                info!(%name, "definition is already registered");
+                return Ok(Status::new_ok(name));
+            }
+            if let Err(err) = validate(spec.schema_type, &definition) {
+                return Ok(Status::new(
+                    name,
+                    ErrorCode::SchemaInvalid(err.clone()),
+                    Some(err),
+                ));
+            }
+            if let Some(latest) = spec.latest() {
+                if let Err(err) = check_compatibility(
+                    spec.schema_type,
+                    spec.compatibility,
+                    &latest.definition,
+                    &definition,
+                ) {
+                    return Ok(Status::new(
+                        name,
+                        ErrorCode::SchemaIncompatible(err.clone()),
+                        Some(err),
+                    ));
+                }
+            }
+            let id = next_schema_id(&auth_ctx.global_ctx).await;
+            let version = spec
+                .latest()
+                .map(|latest| latest.version)
+                .unwrap_or_default()
+                + 1;
+            spec.versions.push(SchemaVersion {
+                id,
+                version,
+                definition,
+            });
+            info!(%name, id, version, "registering schema version");
+        }
+        UpdateSchemaAction::SetCompatibility(compatibility) => {
+            info!(%name, %compatibility, "changing compatibility");
+            spec.compatibility = compatibility;
+        }
+    }
+
+    let status = if let Err(err) = schemas.create_spec(name.clone(), spec).await {
+        Status::new(
+            name,
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        )
+    } else {
+        Status::new_ok(name)
+    };
+    trace!("update schema response {:#?}", status);
+
+    Ok(status)
+}
@@ -137,6 +137,16 @@ async fn validate_topic_request<C: MetadataItem>(
         }
     }
 
+    if let Some(subject) = topic_spec.get_schema() {
+        if !metadata.schemas().store().contains_key(subject).await {
+            return Status::new(
+                subject.to_string(),
+                ErrorCode::SchemaNotFound,
+                Some(format!("Schema '{subject}' is not registered")),
+            );
+        }
+    }
+
     match topic_spec.replicas() {
         ReplicaSpec::Computed(param) => {
             let next_state = validate_computed_topic_parameters::<C>(param);
@@ -0,0 +1,96 @@
+//!
+//! # Delete Records Request
+//!
+use std::io::Error;
+
+use tracing::{info, instrument};
+
+use fluvio_protocol::{link::ErrorCode, record::ReplicaKey};
+use fluvio_sc_schema::{topic::DeleteRecords, Status};
+use fluvio_stream_model::core::{MetadataItem, Spec};
+use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_auth::AuthContext;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for delete records request.
+/// Log start offset of partition is moved forward, so SPU replicas stop serving records before it.
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_delete_records<AC: AuthContext, C: MetadataItem>(
+    topic_name: String,
+    request: DeleteRecords,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    let Some(topic) = auth_ctx
+        .global_ctx
+        .topics()
+        .store()
+        .value(&topic_name)
+        .await
+    else {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+
+    if topic.spec().is_system() {
+        return Ok(Status::new(
+            topic_name.clone(),
+            ErrorCode::SystemSpecUpdatingAttempt {
+                kind: TopicSpec::LABEL.to_lowercase(),
+                name: topic_name,
+            },
+            None,
+        ));
+    }
+
+    let replica_key = ReplicaKey::new(topic_name.clone(), request.partition);
+    let Some(partition) = auth_ctx
+        .global_ctx
+        .partitions()
+        .store()
+        .value(&replica_key)
+        .await
+    else {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::PartitionNotFound,
+            Some(format!("partition {} not found", request.partition)),
+        ));
+    };
+
+    // only committed records can be deleted
+    let hw = partition.status.leader.hw;
+    if request.before_offset < 0 || request.before_offset > hw {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::OffsetOutOfRange,
+            Some(format!(
+                "offset {} must be between 0 and high watermark {hw}",
+                request.before_offset
+            )),
+        ));
+    }
+
+    let mut spec = partition.spec().clone();
+    if request.before_offset <= spec.log_start_offset {
+        info!(
+            %replica_key,
+            log_start_offset = spec.log_start_offset,
+            "records already deleted"
+        );
+        return Ok(Status::new_ok(topic_name));
+    }
+
+    info!(%replica_key, before_offset = request.before_offset, "deleting records");
+    spec.log_start_offset = request.before_offset;
+    auth_ctx
+        .global_ctx
+        .partitions()
+        .create_spec(replica_key, spec)
+        .await?;
+
+    Ok(Status::new_ok(topic_name))
+}
@@ -1,5 +1,7 @@
 mod add_partition;
 mod add_mirror;
+mod delete_records;
+mod reassign_partition;
 
 use std::io::{Error, ErrorKind};
 
@@ -49,6 +51,12 @@ pub async fn handle_topic_update_request<AC: AuthContext, C: MetadataItem>(
         UpdateTopicAction::AddMirror(req) => {
             add_mirror::handle_add_mirror(topic_name, req, auth_ctx).await?
         }
+        UpdateTopicAction::DeleteRecords(req) => {
+            delete_records::handle_delete_records(topic_name, req, auth_ctx).await?
+        }
+        UpdateTopicAction::ReassignPartition(req) => {
+            reassign_partition::handle_reassign_partition(topic_name, req, auth_ctx).await?
+        }
     };
 
     Ok(status)
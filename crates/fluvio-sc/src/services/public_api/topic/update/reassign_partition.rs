@@ -0,0 +1,105 @@
+//!
+//! # Reassign Partition Request
+//!
+use std::collections::HashSet;
+use std::io::Error;
+
+use tracing::{info, instrument};
+
+use fluvio_protocol::{link::ErrorCode, record::ReplicaKey};
+use fluvio_sc_schema::{topic::ReassignPartition, Status};
+use fluvio_stream_model::core::{MetadataItem, Spec};
+use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_auth::AuthContext;
+
+use crate::services::auth::AuthServiceContext;
+use crate::stores::spu::SpuLocalStorePolicy;
+
+/// Handler for reassign partition request.
+/// New replicas are added to partition; partition controller drops old replicas once new ones are in sync.
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_reassign_partition<AC: AuthContext, C: MetadataItem>(
+    topic_name: String,
+    request: ReassignPartition,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    let Some(topic) = auth_ctx
+        .global_ctx
+        .topics()
+        .store()
+        .value(&topic_name)
+        .await
+    else {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+
+    if topic.spec().is_system() {
+        return Ok(Status::new(
+            topic_name.clone(),
+            ErrorCode::SystemSpecUpdatingAttempt {
+                kind: TopicSpec::LABEL.to_lowercase(),
+                name: topic_name,
+            },
+            None,
+        ));
+    }
+
+    let unique: HashSet<_> = request.replicas.iter().collect();
+    if request.replicas.is_empty() || unique.len() != request.replicas.len() {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicInvalidConfiguration,
+            Some("replicas must be non empty list of distinct SPUs".to_owned()),
+        ));
+    }
+
+    let spu_ids = auth_ctx.global_ctx.spus().store().spu_ids().await;
+    if let Some(spu) = request.replicas.iter().find(|spu| !spu_ids.contains(spu)) {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::SpuNotFound,
+            Some(format!("spu {spu} not found")),
+        ));
+    }
+
+    let replica_key = ReplicaKey::new(topic_name.clone(), request.partition);
+    let Some(partition) = auth_ctx
+        .global_ctx
+        .partitions()
+        .store()
+        .value(&replica_key)
+        .await
+    else {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::PartitionNotFound,
+            Some(format!("partition {} not found", request.partition)),
+        ));
+    };
+
+    let mut spec = partition.spec().clone();
+    if spec.reassignment.is_none() && spec.replicas == request.replicas {
+        info!(%replica_key, replicas = ?spec.replicas, "replicas already assigned");
+        return Ok(Status::new_ok(topic_name));
+    }
+
+    info!(
+        %replica_key,
+        from = ?spec.replicas,
+        to = ?request.replicas,
+        throttle = request.throttle_bytes_per_sec,
+        "reassigning partition"
+    );
+    spec.start_reassignment(request.replicas, request.throttle_bytes_per_sec);
+    auth_ctx
+        .global_ctx
+        .partitions()
+        .create_spec(replica_key, spec)
+        .await?;
+
+    Ok(Status::new_ok(topic_name))
+}
@@ -10,6 +10,9 @@ use tracing::{instrument, trace, debug, error};
 
 use fluvio_protocol::link::ErrorCode;
 use fluvio_stream_model::core::MetadataItem;
+use fluvio_controlplane_metadata::schema::SchemaSpec;
+use fluvio_controlplane_metadata::policy::PolicySpec;
+use fluvio_controlplane_metadata::quota::QuotaSpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{Status, TryEncodableFrom};
@@ -31,6 +34,15 @@ pub async fn handle_update_request<AC: AuthContext, C: MetadataItem>(
     let status = if let Some(req) = del_req.downcast()? as Option<UpdateRequest<TopicSpec>> {
         let action = req.action.clone();
         super::topic::update::handle_topic_update_request(req.key(), action, auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<UpdateRequest<SchemaSpec>> {
+        let action = req.action.clone();
+        super::schema::handle_update_schema_request(req.key(), action, auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<UpdateRequest<PolicySpec>> {
+        let action = req.action.clone();
+        super::policy::handle_update_policy_request(req.key(), action, auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<UpdateRequest<QuotaSpec>> {
+        let action = req.action.clone();
+        super::quota::handle_update_quota_request(req.key(), action, auth_ctx).await?
     } else {
         error!("unknown update request: {:#?}", del_req);
         Status::new(
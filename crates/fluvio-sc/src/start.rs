@@ -1,6 +1,7 @@
 use std::{
     sync::Arc,
     path::{PathBuf, Path},
+    process,
     time::Duration,
 };
 
@@ -8,15 +9,17 @@ use anyhow::Result;
 use tracing::info;
 
 use fluvio_future::{task::run_block_on, timer::sleep};
+use fluvio_types::print_cli_err;
 use fluvio_stream_dispatcher::metadata::{SharedClient, MetadataClient, local::LocalMetadataStorage};
 use fluvio_stream_model::{store::k8::K8MetaItem, core::MetadataItem};
+use fluvio_controlplane_metadata::policy::PolicySpec;
 use k8_client::{K8Client, K8Config, memory::MemoryClient};
 
 use crate::{
     cli::{ScOpt, TlsConfig, RunMode},
-    services::auth::basic::BasicRbacPolicy,
     config::ScConfig,
     config::DEFAULT_NAMESPACE,
+    ha::{HaConfig, HaNode, ReplicatedMetadataStorage},
 };
 
 pub fn main_loop(opt: ScOpt) {
@@ -28,10 +31,28 @@ pub fn main_loop(opt: ScOpt) {
 
     match opt.mode() {
         RunMode::Local(metadata) => {
-            info!(?metadata, "Running in local mode");
-            let client = create_local_metadata_store(metadata);
-            let ((sc_config, auth_policy), tls_option) = opt.parse_cli_or_exit();
-            local_main_loop(sc_config, client, auth_policy, tls_option)
+            let ha_config = match opt.ha_config() {
+                Ok(ha_config) => ha_config,
+                Err(err) => {
+                    print_cli_err!(err);
+                    process::exit(-1);
+                }
+            };
+            if let Some(ha_config) = ha_config {
+                info!(
+                    ?metadata,
+                    ?ha_config,
+                    "Running in highly-available local mode"
+                );
+                let metadata = metadata.to_path_buf();
+                let ((sc_config, auth_policy), tls_option) = opt.parse_cli_or_exit();
+                ha_main_loop(sc_config, ha_config, metadata, auth_policy, tls_option)
+            } else {
+                info!(?metadata, "Running in local mode");
+                let client = create_local_metadata_store(metadata);
+                let ((sc_config, auth_policy), tls_option) = opt.parse_cli_or_exit();
+                local_main_loop(sc_config, client, auth_policy, tls_option)
+            }
         }
         RunMode::ReadOnly(read_only_path) => {
             let read_only_path = read_only_path.to_path_buf();
@@ -87,7 +108,7 @@ fn inspect_system() {
 fn k8_main_loop<C>(
     sc_config: ScConfig,
     client: SharedClient<C>,
-    auth_policy: Option<BasicRbacPolicy>,
+    auth_policy: Option<PolicySpec>,
     tls_option: Option<(String, TlsConfig)>,
 ) where
     C: MetadataClient<K8MetaItem> + 'static,
@@ -119,7 +140,7 @@ fn k8_main_loop<C>(
 fn local_main_loop<C, M>(
     sc_config: ScConfig,
     client: SharedClient<C>,
-    auth_policy: Option<BasicRbacPolicy>,
+    auth_policy: Option<PolicySpec>,
     tls_option: Option<(String, TlsConfig)>,
 ) where
     C: MetadataClient<M> + 'static,
@@ -140,6 +161,49 @@ fn local_main_loop<C, M>(
     });
 }
 
+/// participate in consensus as standby until elected, then run as active SC until leadership is lost
+fn ha_main_loop(
+    sc_config: ScConfig,
+    ha_config: HaConfig,
+    metadata: PathBuf,
+    auth_policy: Option<PolicySpec>,
+    tls_option: Option<(String, TlsConfig)>,
+) {
+    run_block_on(async move {
+        info!(id = ha_config.id, "starting as standby SC");
+
+        let node = match HaNode::start(ha_config, &metadata) {
+            Ok(node) => node,
+            Err(err) => {
+                print_cli_err!(format!("failed to start SC peer service: {err}"));
+                process::exit(-1);
+            }
+        };
+        let mut proxy_started = false;
+        loop {
+            node.wait_for_leadership().await;
+
+            let demoted = node.activate();
+            let client = Arc::new(ReplicatedMetadataStorage::new(
+                node.clone(),
+                demoted.clone(),
+            ));
+            let ctx =
+                crate::init::start_main_loop((sc_config.clone(), auth_policy.clone()), client)
+                    .await;
+            if !proxy_started {
+                proxy::start_if(sc_config.clone(), tls_option.clone()).await;
+                proxy_started = true;
+            }
+
+            println!("Streaming Controller started successfully as active SC");
+            demoted.listen().await;
+            ctx.shutdown().notify();
+            info!(id = node.id(), "stepped down, continuing as standby SC");
+        }
+    });
+}
+
 mod proxy {
     use std::process;
     use tracing::info;
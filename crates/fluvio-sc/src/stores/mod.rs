@@ -4,6 +4,9 @@ pub mod partition;
 pub mod spg;
 pub mod smartmodule;
 pub mod tableformat;
+pub mod schema;
+pub mod policy;
+pub mod quota;
 
 pub use crate::dispatcher::store::*;
 
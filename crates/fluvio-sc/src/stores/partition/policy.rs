@@ -23,6 +23,8 @@ pub(crate) trait ElectionPolicy {
     ) -> ElectionScoring;
 }
 
+/// Only replica which has all records committed by last known leader high watermark can become leader,
+/// replica with least lag is preferred
 pub(crate) struct SimplePolicy {}
 
 impl SimplePolicy {
@@ -37,11 +39,64 @@ impl ElectionPolicy for SimplePolicy {
         replica_status: &ReplicaStatus,
         leader: &ReplicaStatus,
     ) -> ElectionScoring {
-        let lag = leader.leo - replica_status.leo;
-        if lag < 4 {
-            ElectionScoring::Score(lag as u16)
-        } else {
+        if replica_status.leo < leader.hw {
             ElectionScoring::NotSuitable
+        } else {
+            ElectionScoring::Score(lag_score(replica_status, leader))
         }
     }
 }
+
+/// Any replica can become leader, committed records which it doesn't have are lost.
+/// Only used when topic allows unclean leader election.
+pub(crate) struct UncleanPolicy {}
+
+impl UncleanPolicy {
+    pub(crate) fn new() -> Self {
+        UncleanPolicy {}
+    }
+}
+
+impl ElectionPolicy for UncleanPolicy {
+    fn potential_leader_score(
+        &self,
+        replica_status: &ReplicaStatus,
+        leader: &ReplicaStatus,
+    ) -> ElectionScoring {
+        ElectionScoring::Score(lag_score(replica_status, leader))
+    }
+}
+
+fn lag_score(replica_status: &ReplicaStatus, leader: &ReplicaStatus) -> u16 {
+    (leader.leo - replica_status.leo).clamp(0, u16::MAX as i64) as u16
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_election_requires_committed_records() {
+        let leader = ReplicaStatus::new(0, 10, 15);
+
+        assert!(matches!(
+            SimplePolicy::new().potential_leader_score(&ReplicaStatus::new(1, 10, 15), &leader),
+            ElectionScoring::Score(0)
+        ));
+        assert!(matches!(
+            SimplePolicy::new().potential_leader_score(&ReplicaStatus::new(1, 5, 10), &leader),
+            ElectionScoring::Score(5)
+        ));
+        // replica is missing committed records
+        assert!(
+            !SimplePolicy::new()
+                .potential_leader_score(&ReplicaStatus::new(1, 5, 8), &leader)
+                .is_suitable()
+        );
+        assert!(matches!(
+            UncleanPolicy::new().potential_leader_score(&ReplicaStatus::new(1, 5, 8), &leader),
+            ElectionScoring::Score(7)
+        ));
+    }
+}
@@ -391,6 +391,7 @@ impl PartitonStatusExtension for PartitionStatus {
     fn merge(&mut self, other: Self) {
         self.resolution = other.resolution;
         self.size = other.size;
+        self.corrupt_batches = other.corrupt_batches;
         if let Some(old) = self.leader.merge(&other.leader) {
             self.replicas.push(old); // move old leader to replicas
         }
@@ -620,6 +621,19 @@ mod test {
         assert_eq!(target.replicas.len(), 1);
         assert_eq!(target.replicas[0], (5001, 0, 0).into());
     }
+
+    #[test]
+    fn test_merge_corrupt_batches() {
+        let mut target = PartitionStatus::new((5000, 100, 110), vec![]);
+
+        let mut source = PartitionStatus::new((5000, 100, 110), vec![]);
+        source.corrupt_batches = 2;
+        target.merge(source);
+        assert_eq!(target.corrupt_batches, 2);
+
+        target.merge(PartitionStatus::new((5000, 100, 110), vec![]));
+        assert_eq!(target.corrupt_batches, 0);
+    }
 }
 
 #[cfg(test)]
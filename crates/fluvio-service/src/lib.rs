@@ -1,5 +1,7 @@
 #[cfg(unix)]
 mod server;
+#[cfg(unix)]
+pub mod metrics;
 
 #[cfg(test)]
 pub mod test_request;
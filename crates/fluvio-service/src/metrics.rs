@@ -0,0 +1,310 @@
+//!
+//! # OpenMetrics exporter
+//!
+//! Minimal HTTP listener serving metrics in OpenMetrics text format,
+//! so servers can be scraped by Prometheus.
+//!
+
+use std::fmt::{self, Display, Write as _};
+use std::future::Future;
+use std::process;
+use std::sync::Arc;
+
+use futures_util::{AsyncReadExt, AsyncWriteExt, StreamExt};
+use tracing::{debug, error, info};
+
+use fluvio_future::net::{TcpListener, TcpStream};
+use fluvio_future::task::spawn;
+use fluvio_types::event::StickyEvent;
+
+pub const METRICS_PATH: &str = "/metrics";
+
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// requests with larger head are rejected
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+    Summary,
+}
+
+impl Display for MetricKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Counter => write!(f, "counter"),
+            Self::Gauge => write!(f, "gauge"),
+            Self::Summary => write!(f, "summary"),
+        }
+    }
+}
+
+/// Builds OpenMetrics text exposition.
+/// Each family must be written once, with all of its samples.
+#[derive(Debug, Default)]
+pub struct MetricsEncoder {
+    out: String,
+}
+
+impl MetricsEncoder {
+    /// start metric family, samples are written through returned family
+    pub fn family(&mut self, name: &str, kind: MetricKind, help: &str) -> MetricFamily<'_> {
+        let _ = writeln!(self.out, "# TYPE {name} {kind}");
+        let _ = writeln!(self.out, "# HELP {name} {}", escape(help, false));
+        MetricFamily {
+            out: &mut self.out,
+            name: name.to_owned(),
+            kind,
+        }
+    }
+
+    /// complete exposition
+    pub fn finish(mut self) -> String {
+        self.out.push_str("# EOF\n");
+        self.out
+    }
+}
+
+pub struct MetricFamily<'a> {
+    out: &'a mut String,
+    name: String,
+    kind: MetricKind,
+}
+
+impl MetricFamily<'_> {
+    /// add counter or gauge sample
+    pub fn sample(&mut self, labels: &[(&str, &str)], value: impl Display) -> &mut Self {
+        let suffix = match self.kind {
+            MetricKind::Counter => "_total",
+            MetricKind::Gauge => "",
+            MetricKind::Summary => "_sum",
+        };
+        self.write(suffix, labels, value);
+        self
+    }
+
+    /// add count and sum of summary
+    pub fn summary(&mut self, labels: &[(&str, &str)], count: u64, sum: f64) -> &mut Self {
+        self.write("_count", labels, count);
+        self.write("_sum", labels, sum);
+        self
+    }
+
+    fn write(&mut self, suffix: &str, labels: &[(&str, &str)], value: impl Display) {
+        let _ = write!(self.out, "{}{suffix}", self.name);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (index, (name, value)) in labels.iter().enumerate() {
+                if index > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{name}=\"{}\"", escape(value, true));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+}
+
+fn escape(value: &str, quote: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '"' if quote => escaped.push_str("\\\""),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Serve metrics rendered by `render` on `GET /metrics` at addr
+pub fn start_metrics_server<F, Fut>(addr: String, render: F) -> Arc<StickyEvent>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = String> + Send + 'static,
+{
+    let shutdown = StickyEvent::shared();
+    spawn(accept_incoming(addr, Arc::new(render), shutdown.clone()));
+    shutdown
+}
+
+async fn accept_incoming<F, Fut>(addr: String, render: Arc<F>, shutdown: Arc<StickyEvent>)
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = String> + Send + 'static,
+{
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            error!(%addr, "Error binding metrics listener: {}", err);
+            process::exit(-1);
+        }
+    };
+
+    info!(%addr, "serving metrics");
+    let mut incoming = listener.incoming().take_until(shutdown.listen_pinned());
+    while let Some(incoming) = incoming.next().await {
+        match incoming {
+            Ok(stream) => {
+                let render = render.clone();
+                spawn(async move {
+                    if let Err(err) = handle_request(stream, render.as_ref()).await {
+                        debug!("metrics request failed: {}", err);
+                    }
+                });
+            }
+            Err(err) => {
+                error!("Error from metrics TCP Stream: {:?}", err);
+            }
+        }
+    }
+    info!(%addr, "metrics listener closed");
+}
+
+async fn handle_request<F, Fut>(mut stream: TcpStream, render: &F) -> std::io::Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = String>,
+{
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            return Ok(());
+        }
+        head.extend_from_slice(&buf[..read]);
+        if head.len() > MAX_REQUEST_HEAD {
+            return respond(
+                &mut stream,
+                "431 Request Header Fields Too Large",
+                "text/plain",
+                "",
+            )
+            .await;
+        }
+    }
+
+    let request_line = String::from_utf8_lossy(&head);
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+    debug!(method, path, "metrics request");
+
+    match (method, path) {
+        ("GET", METRICS_PATH) => {
+            let body = render().await;
+            respond(&mut stream, "200 OK", CONTENT_TYPE, &body).await
+        }
+        ("GET", _) => respond(&mut stream, "404 Not Found", "text/plain", "not found\n").await,
+        _ => {
+            respond(
+                &mut stream,
+                "405 Method Not Allowed",
+                "text/plain",
+                "method not allowed\n",
+            )
+            .await
+        }
+    }
+}
+
+async fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+
+    use futures_util::{AsyncReadExt, AsyncWriteExt};
+
+    use fluvio_future::net::TcpStream;
+    use fluvio_future::timer::sleep;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_families() {
+        let mut encoder = MetricsEncoder::default();
+        encoder
+            .family("fluvio_bytes", MetricKind::Counter, "bytes written")
+            .sample(&[("topic", "orders"), ("partition", "0")], 10)
+            .sample(&[("topic", "a\"b\\c\nd"), ("partition", "1")], 20);
+        encoder
+            .family("fluvio_connections", MetricKind::Gauge, "open connections")
+            .sample(&[], 3);
+        encoder
+            .family("fluvio_latency_seconds", MetricKind::Summary, "latency")
+            .summary(&[("name", "sm")], 4, 0.5);
+
+        assert_eq!(
+            encoder.finish(),
+            r#"# TYPE fluvio_bytes counter
+# HELP fluvio_bytes bytes written
+fluvio_bytes_total{topic="orders",partition="0"} 10
+fluvio_bytes_total{topic="a\"b\\c\nd",partition="1"} 20
+# TYPE fluvio_connections gauge
+# HELP fluvio_connections open connections
+fluvio_connections 3
+# TYPE fluvio_latency_seconds summary
+# HELP fluvio_latency_seconds latency
+fluvio_latency_seconds_count{name="sm"} 4
+fluvio_latency_seconds_sum{name="sm"} 0.5
+# EOF
+"#
+        );
+    }
+
+    async fn get(addr: &str, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.expect("connect");
+        stream
+            .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+            .await
+            .expect("write");
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.expect("read");
+        response
+    }
+
+    #[fluvio_future::test]
+    async fn test_metrics_server() {
+        let port = portpicker::pick_unused_port().expect("No free ports left");
+        let addr = format!("127.0.0.1:{port}");
+
+        let shutdown = start_metrics_server(addr.clone(), || async {
+            let mut encoder = MetricsEncoder::default();
+            encoder
+                .family("fluvio_up", MetricKind::Gauge, "server is up")
+                .sample(&[], 1);
+            encoder.finish()
+        });
+        sleep(Duration::from_millis(200)).await;
+
+        let response = get(&addr, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(CONTENT_TYPE));
+        assert!(response.ends_with("fluvio_up 1\n# EOF\n"));
+
+        let response = get(&addr, "/other").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        shutdown.notify();
+    }
+}
@@ -3,10 +3,10 @@ use std::time::Duration;
 use derive_builder::Builder;
 
 use fluvio_protocol::Version;
-use fluvio_smartmodule::SMARTMODULE_TIMESTAMPS_VERSION;
-use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
+use fluvio_smartmodule::SMARTMODULE_HEADERS_VERSION;
+use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleErrorPolicy, SmartModuleExtraParams};
 
-pub const DEFAULT_SMARTENGINE_VERSION: Version = SMARTMODULE_TIMESTAMPS_VERSION;
+pub const DEFAULT_SMARTENGINE_VERSION: Version = SMARTMODULE_HEADERS_VERSION;
 
 /// Initial seed data to passed, this will be send back as part of the output
 #[derive(Debug, Clone)]
@@ -40,6 +40,11 @@ pub struct SmartModuleConfig {
     pub(crate) version: Option<i16>,
     #[builder(default)]
     pub(crate) lookback: Option<Lookback>,
+    #[builder(default)]
+    pub(crate) error_policy: SmartModuleErrorPolicy,
+    /// limit of time of single invocation
+    #[builder(default)]
+    pub(crate) timeout: Option<Duration>,
     // into makes the field required
     #[builder(setter(into))]
     pub(crate) smartmodule_names: Vec<String>,
@@ -74,22 +79,30 @@ impl SmartModuleConfig {
     pub fn set_lookback(&mut self, lookback: Option<Lookback>) {
         self.lookback = lookback;
     }
+
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
 }
 
 #[cfg(feature = "transformation")]
 impl From<crate::transformation::TransformationStep> for SmartModuleConfig {
     fn from(step: crate::transformation::TransformationStep) -> Self {
         let names = step.uses.clone();
+        let mut params: SmartModuleExtraParams = step
+            .with
+            .into_iter()
+            .map(|(k, v)| (k, v.into()))
+            .collect::<std::collections::BTreeMap<String, String>>()
+            .into();
+        params.set_window(step.window.map(Into::into));
         Self {
             initial_data: SmartModuleInitialData::None,
-            params: step
-                .with
-                .into_iter()
-                .map(|(k, v)| (k, v.into()))
-                .collect::<std::collections::BTreeMap<String, String>>()
-                .into(),
+            params,
             version: None,
             lookback: step.lookback.map(|l| l.into()),
+            error_policy: step.on_error.into(),
+            timeout: step.timeout,
             smartmodule_names: vec![names],
         }
     }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 #[derive(thiserror::Error, Debug)]
 pub enum EngineError {
     #[error("No valid smartmodule found")]
@@ -10,4 +12,6 @@ pub enum EngineError {
         requested: usize,
         max: usize,
     },
+    #[error("SmartModule exceeded time limit of {0:?}")]
+    Timeout(Duration),
 }
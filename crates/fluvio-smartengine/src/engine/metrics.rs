@@ -17,6 +17,9 @@ pub struct SmartModuleChainMetrics {
     // allow this to be missing for deserialization for legacy use
     #[serde(default)]
     cpu_ms: AtomicU64,
+    // invocations interrupted by timeout
+    #[serde(default)]
+    timeouts: AtomicU64,
     // Names of the SmartModules in the chain
     #[serde(default)]
     smartmodule_names: Vec<String>,
@@ -31,6 +34,7 @@ impl Clone for SmartModuleChainMetrics {
             invocation_count: AtomicU64::new(self.invocation_count.load(DEFAULT_ORDERING)),
             fuel_used: AtomicU64::new(self.fuel_used.load(DEFAULT_ORDERING)),
             cpu_ms: AtomicU64::new(self.cpu_ms.load(DEFAULT_ORDERING)),
+            timeouts: AtomicU64::new(self.timeouts.load(DEFAULT_ORDERING)),
             smartmodule_names: self.smartmodule_names.clone(),
         }
     }
@@ -53,6 +57,7 @@ impl SmartModuleChainMetrics {
             invocation_count: AtomicU64::new(0),
             fuel_used: AtomicU64::new(0),
             cpu_ms: AtomicU64::new(0),
+            timeouts: AtomicU64::new(0),
             smartmodule_names: names.to_vec(),
         }
     }
@@ -79,6 +84,10 @@ impl SmartModuleChainMetrics {
         self.fuel_used.fetch_add(fuel, DEFAULT_ORDERING);
     }
 
+    pub fn add_timeouts(&self, value: u64) {
+        self.timeouts.fetch_add(value, DEFAULT_ORDERING);
+    }
+
     pub fn bytes_in(&self) -> u64 {
         self.bytes_in.load(DEFAULT_ORDERING)
     }
@@ -103,6 +112,10 @@ impl SmartModuleChainMetrics {
         self.records_err.load(DEFAULT_ORDERING)
     }
 
+    pub fn timeouts(&self) -> u64 {
+        self.timeouts.load(DEFAULT_ORDERING)
+    }
+
     pub fn smartmodule_names(&self) -> &Vec<String> {
         &self.smartmodule_names
     }
@@ -122,6 +135,8 @@ impl SmartModuleChainMetrics {
         );
         self.records_err
             .fetch_add(other.records_err.load(DEFAULT_ORDERING), DEFAULT_ORDERING);
+        self.timeouts
+            .fetch_add(other.timeouts.load(DEFAULT_ORDERING), DEFAULT_ORDERING);
     }
     pub fn reset(&self) {
         self.bytes_in.store(0, DEFAULT_ORDERING);
@@ -130,6 +145,7 @@ impl SmartModuleChainMetrics {
         self.cpu_ms.store(0, DEFAULT_ORDERING);
         self.invocation_count.store(0, DEFAULT_ORDERING);
         self.records_err.store(0, DEFAULT_ORDERING);
+        self.timeouts.store(0, DEFAULT_ORDERING);
     }
 }
 
@@ -166,6 +182,7 @@ mod t_smartmodule_metrics {
             r#"{"bytes_in":0,"records_out":0,"invocation_count":0,"fuel_used":0,"records_err":0}"#;
         let metrics: SmartModuleChainMetrics = serde_json::from_str(input).expect("deserialize");
         assert_eq!(metrics.cpu_ms(), 0);
+        assert_eq!(metrics.timeouts(), 0);
 
         // check behavior w/ extra property
         let input = r#"{"bytes_in":0,"records_out":0,"invocation_count":0,"fuel_used":0, "extra": 1, "records_err": 0}"#;
@@ -20,4 +20,6 @@ pub type WasmSlice = (i32, i32, u32);
 /// SmartEngine Version
 pub type Version = i16;
 
-pub use self::wasmtime::{SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance};
+pub use self::wasmtime::{
+    SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance, StateKey, StateChange,
+};
@@ -0,0 +1,148 @@
+//! SmartModules built as WASM components.
+//! Components export interfaces of `fluvio:smartmodule` package defined in `wit/smartmodule.wit`,
+//! records are passed as WIT types instead of encoded buffers.
+
+use anyhow::Result;
+use wasmtime::AsContextMut;
+use wasmtime::component::{ComponentNamedList, ComponentType, Lift, Lower, TypedFunc};
+
+use fluvio_protocol::record::{Header, Record, RecordData, RecordKey};
+use fluvio_smartmodule::SmartModuleRecord;
+
+use super::instance::SmartModuleInstanceContext;
+
+/// version of `fluvio:smartmodule` package implemented by engine
+pub(crate) const WIT_VERSION: &str = "0.1.0";
+
+pub(crate) fn is_component(bytes: &[u8]) -> bool {
+    wasmparser::Parser::is_component(bytes)
+}
+
+/// typed function of `fluvio:smartmodule` interface, if component exports it
+pub(crate) fn typed_func<Params, Results>(
+    ctx: &SmartModuleInstanceContext,
+    store: &mut impl AsContextMut,
+    interface: &str,
+    name: &str,
+) -> Result<Option<TypedFunc<Params, Results>>>
+where
+    Params: ComponentNamedList + Lower,
+    Results: ComponentNamedList + Lift,
+{
+    let interface = format!("fluvio:smartmodule/{interface}@{WIT_VERSION}");
+    match ctx.get_component_func(store, &interface, name) {
+        Some(func) => Ok(Some(func.typed(store)?)),
+        None => Ok(None),
+    }
+}
+
+/// call function and let component clean up its returned values
+pub(crate) fn call<Params, Results>(
+    func: &TypedFunc<Params, Results>,
+    store: &mut impl AsContextMut,
+    params: Params,
+) -> Result<Results>
+where
+    Params: ComponentNamedList + Lower,
+    Results: ComponentNamedList + Lift,
+{
+    let results = func.call(&mut *store, params)?;
+    func.post_return(store)?;
+    Ok(results)
+}
+
+/// `header` of WIT types
+#[derive(ComponentType, Lift, Lower, Debug, Clone, PartialEq, Eq)]
+#[component(record)]
+pub(crate) struct WitHeader {
+    key: String,
+    value: Vec<u8>,
+}
+
+/// `sm-record` of WIT types
+#[derive(ComponentType, Lift, Lower, Debug, Clone, PartialEq, Eq)]
+#[component(record)]
+pub(crate) struct WitRecord {
+    offset: i64,
+    timestamp: i64,
+    key: Option<Vec<u8>>,
+    value: Vec<u8>,
+    headers: Vec<WitHeader>,
+}
+
+impl From<&SmartModuleRecord> for WitRecord {
+    fn from(record: &SmartModuleRecord) -> Self {
+        Self {
+            offset: record.offset(),
+            timestamp: record.timestamp(),
+            key: record.key().map(|key| key.as_ref().to_vec()),
+            value: record.value().as_ref().to_vec(),
+            headers: record
+                .headers()
+                .iter()
+                .map(|header| WitHeader {
+                    key: header.key.clone(),
+                    value: header.value.as_ref().to_vec(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl WitRecord {
+    /// record returned by SmartModule, at position of input record
+    pub(crate) fn into_record(self, input: &Record) -> Record {
+        let mut record = Record::new_key_value(
+            RecordKey::from_option(self.key.map(RecordData::from)),
+            self.value,
+        )
+        .with_headers(
+            self.headers
+                .into_iter()
+                .map(|header| Header::new(header.key, header.value))
+                .collect::<Vec<_>>(),
+        );
+        record.preamble = input.preamble.clone();
+        record
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_record_keeps_input_position() {
+        let mut input = Record::new_key_value("key", "value").with_header("a", "1");
+        input.preamble.set_offset_delta(2);
+        input.preamble.set_timestamp_delta(5);
+        let record = SmartModuleRecord::new(input.clone(), 10, 100);
+
+        let wit = WitRecord::from(&record);
+        assert_eq!(wit.offset, 12);
+        assert_eq!(wit.timestamp, 105);
+        assert_eq!(wit.key, Some(b"key".to_vec()));
+        assert_eq!(
+            wit.headers,
+            vec![WitHeader {
+                key: "a".to_string(),
+                value: b"1".to_vec()
+            }]
+        );
+
+        let output = WitRecord {
+            value: b"VALUE".to_vec(),
+            ..wit
+        }
+        .into_record(&input);
+        assert_eq!(output.value().as_ref(), b"VALUE");
+        assert_eq!(output.key().map(|key| key.as_ref()), Some(b"key".as_ref()));
+        assert_eq!(output.preamble.offset_delta(), 2);
+        assert_eq!(output.timestamp_delta(), 5);
+        assert_eq!(
+            output.headers().get("a").map(|v| v.as_ref()),
+            Some(b"1".as_ref())
+        );
+    }
+}
@@ -1,19 +1,26 @@
 use std::fmt::{self, Debug};
 use std::future::Future;
 use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
 
 use anyhow::Result;
 use fluvio_smartmodule::Record;
-use tracing::debug;
-use wasmtime::{Engine, Module};
+use tracing::{debug, warn};
+use wasmtime::{Engine, EngineWeak, Module};
+use wasmtime::component::Component;
 
-use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleInput, SmartModuleOutput};
+use fluvio_smartmodule::dataplane::smartmodule::{
+    SmartModuleInput, SmartModuleOutput, SmartModuleTransformRuntimeError,
+};
 
 use crate::SmartModuleConfig;
 use crate::engine::config::{Lookback, DEFAULT_SMARTENGINE_VERSION};
 
+use super::component::is_component;
 use super::init::SmartModuleInit;
 use super::instance::{SmartModuleInstance, SmartModuleInstanceContext};
+use super::keyed_state::{StateChange, StateKey};
 
 use super::limiter::StoreResourceLimiter;
 use super::look_back::SmartModuleLookBack;
@@ -27,15 +34,29 @@ const DEFAULT_STORE_MEMORY_LIMIT: usize = 1_000_000_000;
 // tracing target
 const TTGT_SMARTMODULE_CALL: &str = "fluvio_smartengine::smartmodule::call";
 
+/// interval of engine epoch, which is resolution of SmartModule timeouts
+pub(crate) const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// engine shared by all SmartEngines, so only one epoch ticker is running
+static SHARED_ENGINE: Mutex<Option<EngineWeak>> = Mutex::new(None);
+
 #[derive(Clone)]
 pub struct SmartEngine(Engine);
 
 #[allow(clippy::new_without_default)]
 impl SmartEngine {
     pub fn new() -> Self {
+        let mut shared = SHARED_ENGINE.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(engine) = shared.as_ref().and_then(EngineWeak::upgrade) {
+            return Self(engine);
+        }
         let mut config = wasmtime::Config::default();
         config.consume_fuel(true);
-        Self(Engine::new(&config).expect("Config is static"))
+        config.epoch_interruption(true);
+        let engine = Engine::new(&config).expect("Config is static");
+        start_epoch_ticker(&engine);
+        *shared = Some(engine.weak());
+        Self(engine)
     }
 
     pub(crate) fn new_state(&self, store_limiter: StoreResourceLimiter) -> WasmState {
@@ -43,6 +64,23 @@ impl SmartEngine {
     }
 }
 
+/// advance epoch of engine every tick, until all clones of engine are dropped
+fn start_epoch_ticker(engine: &Engine) {
+    let engine = engine.weak();
+    let ticker = std::thread::Builder::new()
+        .name("smartengine-epoch".to_string())
+        .spawn(move || {
+            while let Some(engine) = engine.upgrade() {
+                engine.increment_epoch();
+                drop(engine);
+                std::thread::sleep(EPOCH_TICK);
+            }
+        });
+    if let Err(err) = ticker {
+        warn!(%err, "failed to start epoch ticker, SmartModule timeouts are disabled");
+    }
+}
+
 impl Debug for SmartEngine {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "SmartModuleEngine")
@@ -53,6 +91,7 @@ impl Debug for SmartEngine {
 pub struct SmartModuleChainBuilder {
     smart_modules: Vec<(SmartModuleConfig, Vec<u8>)>,
     store_limiter: StoreResourceLimiter,
+    timeout: Option<Duration>,
 }
 
 impl SmartModuleChainBuilder {
@@ -65,25 +104,55 @@ impl SmartModuleChainBuilder {
         self.store_limiter.set_memory_size(max_memory_bytes);
     }
 
+    /// limit time of each SmartModule invocation in the chain.
+    /// SmartModules configured with shorter timeout keep their own.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
+
     /// stop adding smartmodule and return SmartModuleChain that can be executed
     pub fn initialize(self, engine: &SmartEngine) -> Result<SmartModuleChainInstance> {
         let mut instances = Vec::with_capacity(self.smart_modules.len());
         let mut state = engine.new_state(self.store_limiter);
-        for (config, bytes) in self.smart_modules {
-            let module = Module::new(&engine.0, bytes)?;
+        for (position, (config, bytes)) in self.smart_modules.into_iter().enumerate() {
             let version = config.version();
-            let ctx = SmartModuleInstanceContext::instantiate(
-                &mut state,
-                module,
-                config.params,
-                version,
-                config.lookback,
-                &config.smartmodule_names,
-            )?;
+            let timeout = match (config.timeout, self.timeout) {
+                (Some(timeout), Some(max)) => Some(timeout.min(max)),
+                (timeout, max) => timeout.or(max),
+            };
+            let ctx = if is_component(&bytes) {
+                SmartModuleInstanceContext::instantiate_component(
+                    &mut state,
+                    Component::new(&engine.0, bytes)?,
+                    u16::try_from(position)?,
+                    config.params,
+                    version,
+                    config.lookback,
+                    &config.smartmodule_names,
+                )?
+            } else {
+                SmartModuleInstanceContext::instantiate(
+                    &mut state,
+                    Module::new(&engine.0, bytes)?,
+                    u16::try_from(position)?,
+                    config.params,
+                    version,
+                    config.lookback,
+                    &config.smartmodule_names,
+                )?
+            };
             let init = SmartModuleInit::try_instantiate(&ctx, &mut state)?;
             let look_back = SmartModuleLookBack::try_instantiate(&ctx, &mut state)?;
             let transform = create_transform(&ctx, config.initial_data, &mut state)?;
-            let mut instance = SmartModuleInstance::new(ctx, init, look_back, transform, version);
+            let mut instance = SmartModuleInstance::new(
+                ctx,
+                init,
+                look_back,
+                transform,
+                version,
+                config.error_policy,
+                timeout,
+            );
 
             instance.call_init(&mut state)?;
             instances.push(instance);
@@ -92,6 +161,7 @@ impl SmartModuleChainBuilder {
         Ok(SmartModuleChainInstance {
             store: state,
             instances,
+            dead_letters: Vec::new(),
         })
     }
 }
@@ -103,6 +173,7 @@ impl Default for SmartModuleChainBuilder {
         Self {
             smart_modules: Default::default(),
             store_limiter,
+            timeout: None,
         }
     }
 }
@@ -119,6 +190,7 @@ impl<T: Into<Vec<u8>>> From<(SmartModuleConfig, T)> for SmartModuleChainBuilder
 pub struct SmartModuleChainInstance {
     store: WasmState,
     instances: Vec<SmartModuleInstance>,
+    dead_letters: Vec<SmartModuleTransformRuntimeError>,
 }
 
 impl Debug for SmartModuleChainInstance {
@@ -151,6 +223,8 @@ impl SmartModuleChainInstance {
             let frac_duration = std::time::Duration::from_millis(frac_ms);
             mfrac.add_fuel_used(metrics.fuel_used() / num_modules as u64, frac_duration);
             mfrac.add_invocation_count(metrics.invocation_count() / num_modules as u64);
+            // timeouts can't be attributed to single module, so each reports all of them
+            mfrac.add_timeouts(metrics.timeouts());
 
             for name in metrics.smartmodule_names() {
                 // if the name exists in the output, add the metrics to it
@@ -190,7 +264,11 @@ impl SmartModuleChainInstance {
 
             for instance in instances {
                 self.store.top_up_fuel();
-                let output = instance.process(next_input, &mut self.store)?;
+                let output = instance.process_with_policy(
+                    next_input,
+                    &mut self.store,
+                    &mut self.dead_letters,
+                )?;
                 if let Some(ref smerr) = output.error {
                     // encountered error, we stop processing and return partial output
                     tracing::error!(err=?smerr);
@@ -204,7 +282,8 @@ impl SmartModuleChainInstance {
             }
 
             self.store.top_up_fuel();
-            let output = last.process(next_input, &mut self.store)?;
+            let output =
+                last.process_with_policy(next_input, &mut self.store, &mut self.dead_letters)?;
             if let Some(ref smerr) = output.error {
                 tracing::error!(err=?smerr);
             }
@@ -223,6 +302,22 @@ impl SmartModuleChainInstance {
         }
     }
 
+    /// Replace state of SmartModules with persisted entries.
+    /// Afterwards state changes are tracked and can be taken with `take_state_changes`.
+    pub fn restore_state(&mut self, entries: impl IntoIterator<Item = (StateKey, Vec<u8>)>) {
+        self.store.keyed_state_mut().restore(entries);
+    }
+
+    /// State changes made by SmartModules since last call, empty unless state was restored
+    pub fn take_state_changes(&mut self) -> Vec<StateChange> {
+        self.store.keyed_state_mut().take_changes()
+    }
+
+    /// Errors of records dropped by SmartModules with `DeadLetter` policy since last call
+    pub fn take_dead_letters(&mut self) -> Vec<SmartModuleTransformRuntimeError> {
+        std::mem::take(&mut self.dead_letters)
+    }
+
     pub async fn look_back<F, R>(&mut self, read_fn: F) -> Result<()>
     where
         R: Future<Output = Result<Vec<Record>>>,
@@ -675,4 +770,49 @@ mod chaining_test {
             if max == max_memory
         ))
     }
+
+    #[test]
+    fn test_process_timeout() {
+        //given
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+        // filter that never returns
+        let wat = r#"
+            (module
+                (import "env" "copy_records" (func (param i32 i32)))
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32) i32.const 0)
+                (func (export "filter") (param i32 i32 i32) (result i32)
+                    (loop $forever (br $forever))
+                    i32.const 0))
+        "#;
+        let timeout = std::time::Duration::from_millis(50);
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder()
+                .smartmodule_names(&["forever".to_string()])
+                .timeout(Some(timeout))
+                .build()
+                .unwrap(),
+            wat.as_bytes().to_vec(),
+        );
+
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain");
+
+        // when
+        let input = vec![Record::new("input")];
+        let res = chain.process(
+            SmartModuleInput::try_from_records(input, DEFAULT_SMARTENGINE_VERSION).expect("input"),
+        );
+
+        // then
+        let err = res
+            .unwrap_err()
+            .downcast::<EngineError>()
+            .expect("EngineError expected");
+        assert!(matches!(err, EngineError::Timeout(t) if t == timeout));
+        let metrics = chain.metrics_export();
+        assert_eq!(metrics.get("forever").expect("metrics").timeouts(), 1);
+    }
 }
@@ -2,17 +2,25 @@ use std::convert::TryFrom;
 use std::fmt::Debug;
 
 use anyhow::{Result, Ok};
+use fluvio_protocol::link::smartmodule::SmartModuleInitRuntimeError;
+use fluvio_smartmodule::eyre;
 use fluvio_smartmodule::dataplane::smartmodule::{
     SmartModuleInitInput, SmartModuleInitOutput, SmartModuleInitErrorStatus,
 };
 use wasmtime::{AsContextMut, TypedFunc};
 
+use super::component::{call, typed_func};
 use super::instance::SmartModuleInstanceContext;
 
 pub(crate) const INIT_FN_NAME: &str = "init";
 type WasmInitFn = TypedFunc<(i32, i32, u32), i32>;
+type ComponentInitFn =
+    wasmtime::component::TypedFunc<(Vec<(String, String)>,), (Result<(), String>,)>;
 
-pub(crate) struct SmartModuleInit(WasmInitFn);
+pub(crate) enum SmartModuleInit {
+    Module(WasmInitFn),
+    Component(ComponentInitFn),
+}
 
 impl Debug for SmartModuleInit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -31,8 +39,8 @@ impl SmartModuleInit {
             Some(func) => func
                 .typed(&mut *store)
                 .or_else(|_| func.typed(store))
-                .map(|init_fn| Some(Self(init_fn))),
-            None => Ok(None),
+                .map(|init_fn| Some(Self::Module(init_fn))),
+            None => Ok(typed_func(ctx, store, INIT_FN_NAME, INIT_FN_NAME)?.map(Self::Component)),
         }
     }
 }
@@ -45,8 +53,22 @@ impl SmartModuleInit {
         ctx: &mut SmartModuleInstanceContext,
         store: &mut impl AsContextMut,
     ) -> Result<()> {
+        let init_fn = match self {
+            Self::Module(init_fn) => init_fn,
+            Self::Component(init_fn) => {
+                let params = input
+                    .params
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect();
+                return match call(init_fn, store, (params,))?.0 {
+                    Result::Ok(()) => Ok(()),
+                    Err(err) => Err(SmartModuleInitRuntimeError::new(eyre!(err)).into()),
+                };
+            }
+        };
         let slice = ctx.write_input(&input, &mut *store)?;
-        let init_output = self.0.call(&mut *store, slice)?;
+        let init_output = init_fn.call(&mut *store, slice)?;
 
         if init_output < 0 {
             let internal_error = SmartModuleInitErrorStatus::try_from(init_output)
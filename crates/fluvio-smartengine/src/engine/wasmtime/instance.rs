@@ -1,15 +1,20 @@
 use std::any::Any;
+use std::io::Cursor;
 use std::sync::{Arc, Mutex};
 use std::fmt::{self, Debug};
+use std::time::Duration;
 
-use tracing::debug;
+use tracing::{debug, warn};
 use anyhow::{Error, Result};
-use wasmtime::{Memory, Module, Caller, Extern, Instance, Func, AsContextMut, AsContext};
+use wasmtime::{Memory, Module, Caller, Extern, Instance, Func, AsContextMut, AsContext, Trap};
+use wasmtime::component::{self, Component};
 
 use fluvio_protocol::{Encoder, Decoder, Version};
 
+use fluvio_smartmodule::Record;
 use fluvio_smartmodule::dataplane::smartmodule::{
-    SmartModuleExtraParams, SmartModuleInput, SmartModuleOutput, SmartModuleInitInput,
+    SmartModuleErrorPolicy, SmartModuleExtraParams, SmartModuleInput, SmartModuleOutput,
+    SmartModuleInitInput, SmartModuleTransformRuntimeError,
 };
 
 use crate::engine::config::Lookback;
@@ -27,6 +32,8 @@ pub(crate) struct SmartModuleInstance {
     look_back: Option<SmartModuleLookBack>,
     transform: Box<dyn DowncastableTransform>,
     version: Version,
+    error_policy: SmartModuleErrorPolicy,
+    timeout: Option<Duration>,
 }
 
 impl SmartModuleInstance {
@@ -47,6 +54,8 @@ impl SmartModuleInstance {
         look_back: Option<SmartModuleLookBack>,
         transform: Box<dyn DowncastableTransform>,
         version: Version,
+        error_policy: SmartModuleErrorPolicy,
+        timeout: Option<Duration>,
     ) -> Self {
         Self {
             ctx,
@@ -54,6 +63,19 @@ impl SmartModuleInstance {
             look_back,
             transform,
             version,
+            error_policy,
+            timeout,
+        }
+    }
+
+    /// replace trap of call interrupted by deadline with [`EngineError::Timeout`]
+    fn check_timeout(&self, err: Error) -> Error {
+        match (err.downcast_ref::<Trap>(), self.timeout) {
+            (Some(Trap::Interrupt), Some(timeout)) => {
+                self.ctx.metrics().add_timeouts(1);
+                EngineError::Timeout(timeout).into()
+            }
+            _ => err,
         }
     }
 
@@ -68,7 +90,11 @@ impl SmartModuleInstance {
         self.ctx.metrics().add_invocation_count(1);
         let start_time = self.ctx.metrics_time_start();
 
-        let out = self.transform.process(input, &mut self.ctx, store);
+        store.set_deadline(self.timeout);
+        let out = self
+            .transform
+            .process(input, &mut self.ctx, store)
+            .map_err(|err| self.check_timeout(err));
 
         // post metrics
         self.ctx.metrics_time_elapsed(start_time, store);
@@ -83,14 +109,75 @@ impl SmartModuleInstance {
         out
     }
 
+    /// Process input following the error policy.
+    /// Unless policy is `Fail`, processing resumes after the failing record,
+    /// errors of `DeadLetter` policy are collected in `dead_letters`.
+    pub(crate) fn process_with_policy(
+        &mut self,
+        input: SmartModuleInput,
+        store: &mut WasmState,
+        dead_letters: &mut Vec<SmartModuleTransformRuntimeError>,
+    ) -> Result<SmartModuleOutput> {
+        if self.error_policy == SmartModuleErrorPolicy::Fail {
+            return self.process(input, store);
+        }
+
+        let base_offset = input.base_offset();
+        let base_timestamp = input.base_timestamp();
+        let raw_bytes = input.raw_bytes().to_vec();
+        let mut output = self.process(input, store)?;
+        let mut remaining: Option<Vec<Record>> = None;
+
+        while let Some(error) = output.error.take() {
+            let records = match remaining.as_mut() {
+                Some(records) => records,
+                None => remaining.insert(Decoder::decode_from(
+                    &mut Cursor::new(&raw_bytes),
+                    self.version,
+                )?),
+            };
+            let failed_offset_delta = error.offset - base_offset;
+            let count = records.len();
+            records.retain(|record| record.offset_delta() > failed_offset_delta);
+            if records.len() == count {
+                // error doesn't point to an input record, so it can't be skipped
+                output.error = Some(error);
+                break;
+            }
+
+            if self.error_policy == SmartModuleErrorPolicy::DeadLetter {
+                dead_letters.push(error);
+            } else {
+                warn!(
+                    offset = error.offset,
+                    hint = %error.hint,
+                    "skipping record SmartModule failed on"
+                );
+            }
+            if records.is_empty() {
+                break;
+            }
+
+            let mut next_input = SmartModuleInput::try_from_records(records.clone(), self.version)?;
+            next_input.set_base_offset(base_offset);
+            next_input.set_base_timestamp(base_timestamp);
+            let next_output = self.process(next_input, store)?;
+            output.successes.extend(next_output.successes);
+            output.error = next_output.error;
+        }
+        Ok(output)
+    }
+
     // TODO: Move this to SPU
 
-    pub(crate) fn call_init(&mut self, store: &mut impl AsContextMut) -> Result<(), Error> {
+    pub(crate) fn call_init(&mut self, store: &mut WasmState) -> Result<(), Error> {
         if let Some(init) = &mut self.init {
             let input = SmartModuleInitInput {
                 params: self.ctx.params.clone(),
             };
+            store.set_deadline(self.timeout);
             init.initialize(input, &mut self.ctx, store)
+                .map_err(|err| self.check_timeout(err))
         } else {
             Ok(())
         }
@@ -102,7 +189,10 @@ impl SmartModuleInstance {
         store: &mut WasmState,
     ) -> Result<()> {
         if let Some(ref mut lookback) = self.look_back {
-            lookback.call(input, &mut self.ctx, store)
+            store.set_deadline(self.timeout);
+            lookback
+                .call(input, &mut self.ctx, store)
+                .map_err(|err| self.check_timeout(err))
         } else {
             Ok(())
         }
@@ -124,8 +214,16 @@ impl SmartModuleInstance {
     }
 }
 
+/// instance of SmartModule using legacy ABI or of component
+enum WasmInstance {
+    Module(Instance),
+    Component(component::Instance),
+}
+
 pub(crate) struct SmartModuleInstanceContext {
-    instance: Instance,
+    instance: WasmInstance,
+    /// position of SmartModule in the chain
+    position: u16,
     records_cb: Arc<RecordsCallBack>,
     params: SmartModuleExtraParams,
     version: Version,
@@ -145,6 +243,7 @@ impl SmartModuleInstanceContext {
     pub(crate) fn instantiate(
         state: &mut WasmState,
         module: Module,
+        position: u16,
         params: SmartModuleExtraParams,
         version: Version,
         lookback: Option<Lookback>,
@@ -168,14 +267,15 @@ impl SmartModuleInstanceContext {
 
         debug!("instantiating WASMtime");
         let instance = state
-            .instantiate(&module, copy_records_fn)
+            .instantiate(&module, position, copy_records_fn)
             .map_err(|e| match e.downcast::<EngineError>() {
                 Ok(e) => e,
                 Err(e) => EngineError::Instantiate(e),
             })?;
         let metrics = Arc::new(SmartModuleChainMetrics::new(names));
         Ok(Self {
-            instance,
+            instance: WasmInstance::Module(instance),
+            position,
             records_cb,
             params,
             version,
@@ -184,9 +284,68 @@ impl SmartModuleInstanceContext {
         })
     }
 
+    /// instantiate component, records are exchanged as WIT types, so there is no callback
+    #[tracing::instrument(skip(state, component, params))]
+    pub(crate) fn instantiate_component(
+        state: &mut WasmState,
+        component: Component,
+        position: u16,
+        params: SmartModuleExtraParams,
+        version: Version,
+        lookback: Option<Lookback>,
+        names: &[String], // smartmodule names
+    ) -> Result<Self, EngineError> {
+        debug!("instantiating WASMtime component");
+        let instance = state
+            .instantiate_component(&component)
+            .map_err(EngineError::Instantiate)?;
+        let metrics = Arc::new(SmartModuleChainMetrics::new(names));
+        Ok(Self {
+            instance: WasmInstance::Component(instance),
+            position,
+            records_cb: Arc::new(RecordsCallBack::new()),
+            params,
+            version,
+            lookback,
+            metrics,
+        })
+    }
+
+    pub(crate) fn params(&self) -> &SmartModuleExtraParams {
+        &self.params
+    }
+
+    pub(crate) fn version(&self) -> Version {
+        self.version
+    }
+
+    pub(crate) fn position(&self) -> u16 {
+        self.position
+    }
+
     /// get wasm function from instance
     pub(crate) fn get_wasm_func(&self, store: &mut impl AsContextMut, name: &str) -> Option<Func> {
-        self.instance.get_func(store, name)
+        match &self.instance {
+            WasmInstance::Module(instance) => instance.get_func(store, name),
+            WasmInstance::Component(_) => None,
+        }
+    }
+
+    /// get function of interface exported by component
+    pub(crate) fn get_component_func(
+        &self,
+        store: &mut impl AsContextMut,
+        interface: &str,
+        name: &str,
+    ) -> Option<component::Func> {
+        match &self.instance {
+            WasmInstance::Module(_) => None,
+            WasmInstance::Component(instance) => {
+                let interface = instance.get_export_index(&mut *store, None, interface)?;
+                let func = instance.get_export_index(&mut *store, Some(&interface), name)?;
+                instance.get_func(store, func)
+            }
+        }
     }
 
     pub(crate) fn write_input<E: Encoder>(
@@ -194,6 +353,9 @@ impl SmartModuleInstanceContext {
         input: &E,
         store: &mut impl AsContextMut,
     ) -> Result<WasmSlice> {
+        let WasmInstance::Module(instance) = &self.instance else {
+            anyhow::bail!("component doesn't accept encoded input");
+        };
         self.records_cb.clear();
         let mut input_data = Vec::new();
         input.encode(&mut input_data, self.version)?;
@@ -202,7 +364,7 @@ impl SmartModuleInstanceContext {
             version = self.version,
             "input encoded"
         );
-        let array_ptr = memory::copy_memory_to_instance(store, &self.instance, &input_data)?;
+        let array_ptr = memory::copy_memory_to_instance(store, instance, &input_data)?;
         let length = input_data.len();
         Ok((array_ptr as i32, length as i32, self.version as u32))
     }
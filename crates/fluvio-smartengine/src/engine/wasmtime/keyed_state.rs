@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use tracing::debug;
+use wasmtime::{AsContextMut, Caller, Extern, Linker, Memory, Module, Val};
+
+use fluvio_protocol::{Encoder, Decoder};
+
+use super::state::Context;
+
+const STATE_GET_FN: &str = "state_get";
+const STATE_PUT_FN: &str = "state_put";
+const STATE_DELETE_FN: &str = "state_delete";
+const ALLOC_FN: &str = "alloc";
+const MEMORY: &str = "memory";
+
+const STATUS_OK: i32 = 0;
+const STATUS_FOUND: i32 = 1;
+
+/// Keys of state kept by host for SmartModule start with this byte, it is never part of UTF-8 string.
+/// SmartModules can't use such keys.
+pub(crate) const HOST_KEY_PREFIX: u8 = 0xff;
+
+/// Key of SmartModule state
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Encoder, Decoder)]
+pub struct StateKey {
+    /// position of SmartModule in the chain
+    pub instance: u16,
+    pub key: Vec<u8>,
+}
+
+impl StateKey {
+    pub fn new(instance: u16, key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            instance,
+            key: key.into(),
+        }
+    }
+}
+
+/// Update of SmartModule state, `None` value means key was deleted
+pub type StateChange = (StateKey, Option<Vec<u8>>);
+
+/// State of all SmartModules in a chain.
+/// Changes are tracked only after state is restored, so host can persist them.
+#[derive(Debug, Default)]
+pub(crate) struct KeyedState {
+    entries: HashMap<StateKey, Vec<u8>>,
+    changes: Option<Vec<StateChange>>,
+}
+
+impl KeyedState {
+    pub(crate) fn get(&self, key: &StateKey) -> Option<&Vec<u8>> {
+        self.entries.get(key)
+    }
+
+    pub(crate) fn put(&mut self, key: StateKey, value: Vec<u8>) {
+        if let Some(changes) = self.changes.as_mut() {
+            changes.push((key.clone(), Some(value.clone())));
+        }
+        self.entries.insert(key, value);
+    }
+
+    fn delete(&mut self, key: StateKey) {
+        if self.entries.remove(&key).is_some() {
+            if let Some(changes) = self.changes.as_mut() {
+                changes.push((key, None));
+            }
+        }
+    }
+
+    pub(crate) fn restore(&mut self, entries: impl IntoIterator<Item = (StateKey, Vec<u8>)>) {
+        self.entries = entries.into_iter().collect();
+        self.changes = Some(Vec::new());
+    }
+
+    /// whether changes are tracked, so state is persisted by host
+    pub(crate) fn is_tracked(&self) -> bool {
+        self.changes.is_some()
+    }
+
+    pub(crate) fn take_changes(&mut self) -> Vec<StateChange> {
+        self.changes
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+}
+
+/// link state host functions imported by the module
+pub(crate) fn add_to_linker(
+    linker: &mut Linker<Context>,
+    module: &Module,
+    instance: u16,
+) -> Result<()> {
+    for import in module.imports() {
+        match import.name() {
+            STATE_GET_FN => {
+                linker.func_wrap(
+                    import.module(),
+                    STATE_GET_FN,
+                    move |mut caller: Caller<'_, Context>,
+                          key_ptr: i32,
+                          key_len: i32,
+                          out_ptr: i32|
+                          -> Result<i32> {
+                        let memory = guest_memory(&mut caller)?;
+                        let key = state_key(&caller, memory, instance, key_ptr, key_len)?;
+                        let Some(value) = caller.data().keyed_state().get(&key).cloned() else {
+                            return Ok(STATUS_OK);
+                        };
+                        let value_ptr = guest_alloc(&mut caller, value.len())?;
+                        memory.write(&mut caller, value_ptr as usize, &value)?;
+                        let mut out = Vec::with_capacity(8);
+                        out.extend_from_slice(&value_ptr.to_le_bytes());
+                        out.extend_from_slice(&(value.len() as i32).to_le_bytes());
+                        memory.write(&mut caller, out_ptr as usize, &out)?;
+                        Ok(STATUS_FOUND)
+                    },
+                )?;
+            }
+            STATE_PUT_FN => {
+                linker.func_wrap(
+                    import.module(),
+                    STATE_PUT_FN,
+                    move |mut caller: Caller<'_, Context>,
+                          key_ptr: i32,
+                          key_len: i32,
+                          value_ptr: i32,
+                          value_len: i32|
+                          -> Result<i32> {
+                        let memory = guest_memory(&mut caller)?;
+                        let key = state_key(&caller, memory, instance, key_ptr, key_len)?;
+                        let value = read_bytes(&caller, memory, value_ptr, value_len)?;
+                        debug!(instance, len = value.len(), "state put");
+                        caller.data_mut().keyed_state_mut().put(key, value);
+                        Ok(STATUS_OK)
+                    },
+                )?;
+            }
+            STATE_DELETE_FN => {
+                linker.func_wrap(
+                    import.module(),
+                    STATE_DELETE_FN,
+                    move |mut caller: Caller<'_, Context>,
+                          key_ptr: i32,
+                          key_len: i32|
+                          -> Result<i32> {
+                        let memory = guest_memory(&mut caller)?;
+                        let key = state_key(&caller, memory, instance, key_ptr, key_len)?;
+                        debug!(instance, "state delete");
+                        caller.data_mut().keyed_state_mut().delete(key);
+                        Ok(STATUS_OK)
+                    },
+                )?;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn guest_memory(caller: &mut Caller<'_, Context>) -> Result<Memory> {
+    caller
+        .get_export(MEMORY)
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| anyhow!("Missing memory"))
+}
+
+fn read_bytes(caller: &Caller<'_, Context>, memory: Memory, ptr: i32, len: i32) -> Result<Vec<u8>> {
+    let mut bytes = vec![0u8; usize::try_from(len)?];
+    memory.read(caller, usize::try_from(ptr)?, &mut bytes)?;
+    Ok(bytes)
+}
+
+fn state_key(
+    caller: &Caller<'_, Context>,
+    memory: Memory,
+    instance: u16,
+    key_ptr: i32,
+    key_len: i32,
+) -> Result<StateKey> {
+    let key = read_bytes(caller, memory, key_ptr, key_len)?;
+    if key.first() == Some(&HOST_KEY_PREFIX) {
+        return Err(anyhow!(
+            "state key starting with {HOST_KEY_PREFIX:#x} is reserved"
+        ));
+    }
+    Ok(StateKey::new(instance, key))
+}
+
+/// allocate buffer in guest memory, owned by guest afterwards
+fn guest_alloc(caller: &mut Caller<'_, Context>, len: usize) -> Result<i32> {
+    let alloc = caller
+        .get_export(ALLOC_FN)
+        .and_then(Extern::into_func)
+        .ok_or_else(|| anyhow!("missing alloc"))?;
+    let mut result = [Val::I32(0)];
+    alloc.call(
+        caller.as_context_mut(),
+        &[Val::I32(i32::try_from(len)?)],
+        &mut result,
+    )?;
+    match result[0] {
+        Val::I32(ptr) => Ok(ptr),
+        _ => Err(anyhow!("guest pointer must be Val::I32")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_changes_tracked_after_restore() {
+        let mut state = KeyedState::default();
+        state.put(StateKey::new(0, "a"), b"1".to_vec());
+        assert!(state.take_changes().is_empty());
+
+        state.restore(vec![(StateKey::new(0, "b"), b"2".to_vec())]);
+        assert_eq!(state.get(&StateKey::new(0, "a")), None);
+        assert_eq!(state.get(&StateKey::new(0, "b")), Some(&b"2".to_vec()));
+
+        state.put(StateKey::new(0, "c"), b"3".to_vec());
+        state.delete(StateKey::new(0, "b"));
+        state.delete(StateKey::new(0, "missing"));
+        assert_eq!(
+            state.take_changes(),
+            vec![
+                (StateKey::new(0, "c"), Some(b"3".to_vec())),
+                (StateKey::new(0, "b"), None)
+            ]
+        );
+        assert!(state.take_changes().is_empty());
+    }
+}
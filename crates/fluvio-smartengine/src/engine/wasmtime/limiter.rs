@@ -2,9 +2,15 @@ use wasmtime::ResourceLimiter;
 
 use crate::engine::error::EngineError;
 
+/// Limits memory of the store.
+/// Records buffered by host for the store, like records of open windows, count against the limit too.
 #[derive(Debug, Default)]
 pub(crate) struct StoreResourceLimiter {
     pub memory_size: Option<usize>,
+    /// largest memory granted to the store
+    memory: usize,
+    /// bytes buffered by host
+    buffered: usize,
 }
 
 impl StoreResourceLimiter {
@@ -12,6 +18,27 @@ impl StoreResourceLimiter {
         self.memory_size = Some(memory_size);
         self
     }
+
+    /// account bytes buffered by host, fails if they don't fit into memory limit
+    pub(crate) fn buffer(&mut self, bytes: usize) -> Result<(), EngineError> {
+        let requested = self.memory + self.buffered + bytes;
+        match self.memory_size {
+            Some(limit) if requested > limit => Err(EngineError::StoreMemoryExceeded {
+                current: self.memory + self.buffered,
+                requested,
+                max: limit,
+            }),
+            _ => {
+                self.buffered += bytes;
+                Ok(())
+            }
+        }
+    }
+
+    /// release bytes which are no longer buffered by host
+    pub(crate) fn release(&mut self, bytes: usize) {
+        self.buffered = self.buffered.saturating_sub(bytes);
+    }
 }
 
 impl ResourceLimiter for StoreResourceLimiter {
@@ -22,7 +49,7 @@ impl ResourceLimiter for StoreResourceLimiter {
         maximum: Option<usize>,
     ) -> anyhow::Result<bool> {
         let allow = match self.memory_size {
-            Some(limit) if desired > limit => false,
+            Some(limit) if desired + self.buffered > limit => false,
             _ => !matches!(maximum, Some(max) if desired > max),
         };
         if !allow {
@@ -33,6 +60,7 @@ impl ResourceLimiter for StoreResourceLimiter {
             }
             .into())
         } else {
+            self.memory = self.memory.max(desired);
             Ok(allow)
         }
     }
@@ -2,17 +2,26 @@ use std::convert::TryFrom;
 use std::fmt::Debug;
 
 use anyhow::{Result, Ok};
+use fluvio_protocol::link::smartmodule::SmartModuleLookbackRuntimeError;
+use fluvio_smartmodule::eyre;
 use fluvio_smartmodule::dataplane::smartmodule::{
     SmartModuleLookbackOutput, SmartModuleLookbackErrorStatus, SmartModuleInput,
 };
 use wasmtime::{AsContextMut, TypedFunc};
 
+use super::component::{call, typed_func, WitRecord};
 use super::instance::SmartModuleInstanceContext;
 
 const LOOKBACK_FN_NAME: &str = "look_back";
+const COMPONENT_LOOKBACK_FN_NAME: &str = "look-back";
 type LookBackFn = TypedFunc<(i32, i32, u32), i32>;
+type ComponentLookBackFn = wasmtime::component::TypedFunc<(WitRecord,), (Result<(), String>,)>;
 
-pub(crate) struct SmartModuleLookBack(LookBackFn);
+pub(crate) enum SmartModuleLookBack {
+    Module(LookBackFn),
+    /// called for each record
+    Component(ComponentLookBackFn),
+}
 
 impl Debug for SmartModuleLookBack {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -30,9 +39,15 @@ impl SmartModuleLookBack {
             Some(func) => func
                 .typed(&mut *store)
                 .or_else(|_| func.typed(store))
-                .map(Self)
+                .map(Self::Module)
                 .map(Some),
-            None => Ok(None),
+            None => Ok(typed_func(
+                ctx,
+                store,
+                COMPONENT_LOOKBACK_FN_NAME,
+                COMPONENT_LOOKBACK_FN_NAME,
+            )?
+            .map(Self::Component)),
         }
     }
 
@@ -42,8 +57,25 @@ impl SmartModuleLookBack {
         ctx: &mut SmartModuleInstanceContext,
         store: &mut impl AsContextMut,
     ) -> Result<()> {
+        let look_back_fn = match self {
+            Self::Module(look_back_fn) => look_back_fn,
+            Self::Component(look_back_fn) => {
+                let base_offset = input.base_offset();
+                for record in input.try_into_smartmodule_records(ctx.version())? {
+                    if let Err(err) = call(look_back_fn, store, (WitRecord::from(&record),))?.0 {
+                        return Err(SmartModuleLookbackRuntimeError::new(
+                            &record,
+                            base_offset,
+                            eyre!(err),
+                        )
+                        .into());
+                    }
+                }
+                return Ok(());
+            }
+        };
         let slice = ctx.write_input(&input, &mut *store)?;
-        let output = self.0.call(&mut *store, slice)?;
+        let output = look_back_fn.call(&mut *store, slice)?;
 
         if output < 0 {
             let internal_error = SmartModuleLookbackErrorStatus::try_from(output)
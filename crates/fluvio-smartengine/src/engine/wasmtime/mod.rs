@@ -2,10 +2,13 @@ pub(crate) mod memory;
 pub(crate) mod transforms;
 pub(crate) mod init;
 pub(crate) mod state;
+pub(crate) mod keyed_state;
 pub(crate) mod engine;
 pub(crate) mod instance;
 pub(crate) mod look_back;
 pub(crate) mod limiter;
+pub(crate) mod component;
 pub use engine::{SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance};
+pub use keyed_state::{StateKey, StateChange};
 
 use super::*;
@@ -1,11 +1,16 @@
 use std::cmp::max;
+use std::time::Duration;
 
 use anyhow::Error;
 use wasmtime::{
     AsContext, AsContextMut, Engine, Instance, IntoFunc, Module, Store, StoreContext,
     StoreContextMut,
 };
+use wasmtime::component::{self, Component, ResourceTable};
+use wasmtime_wasi::p2::{IoView, WasiView};
 
+use super::engine::EPOCH_TICK;
+use super::keyed_state::{self, KeyedState};
 use super::limiter::StoreResourceLimiter;
 
 // DO NOT INCREASE THIS VALUE HIGHER THAN i64::MAX / 2.
@@ -13,12 +18,41 @@ use super::limiter::StoreResourceLimiter;
 // up to a values close to i64:MAX
 const DEFAULT_FUEL: u64 = i64::MAX as u64 / 2;
 
+// epoch deadline is added to current epoch, so keep it far from u64::MAX
+const NO_DEADLINE: u64 = u64::MAX / 2;
+
 #[derive(Debug)]
 pub struct WasmState(Store<Context>);
 
 pub struct Context {
     limiter: StoreResourceLimiter,
     wasi_ctx: wasi_common::WasiCtx,
+    /// WASI p2 of components
+    wasi_p2_ctx: wasmtime_wasi::p2::WasiCtx,
+    table: ResourceTable,
+    keyed_state: KeyedState,
+}
+
+impl Context {
+    pub(crate) fn keyed_state(&self) -> &KeyedState {
+        &self.keyed_state
+    }
+
+    pub(crate) fn keyed_state_mut(&mut self) -> &mut KeyedState {
+        &mut self.keyed_state
+    }
+}
+
+impl IoView for Context {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+}
+
+impl WasiView for Context {
+    fn ctx(&mut self) -> &mut wasmtime_wasi::p2::WasiCtx {
+        &mut self.wasi_p2_ctx
+    }
 }
 
 impl AsContext for WasmState {
@@ -43,6 +77,18 @@ impl WasmState {
         }
     }
 
+    // Calls made after this are interrupted once timeout elapses
+    pub(crate) fn set_deadline(&mut self, timeout: Option<Duration>) {
+        let ticks = match timeout {
+            // deadline can be reached up to one tick early, as current tick is already running
+            Some(timeout) => (timeout.as_millis().div_ceil(EPOCH_TICK.as_millis()) + 1)
+                .try_into()
+                .unwrap_or(NO_DEADLINE),
+            None => NO_DEADLINE,
+        };
+        self.0.set_epoch_deadline(ticks);
+    }
+
     // Get amount of fuel used since last top up
     pub fn get_used_fuel(&mut self) -> u64 {
         if let Ok(current_fuel) = self.0.get_fuel() {
@@ -59,19 +105,44 @@ impl WasmState {
             .inherit_stderr()
             .inherit_stdout()
             .build();
-        let mut s = Self(Store::new(engine, Context { limiter, wasi_ctx }));
+        let wasi_p2_ctx = wasmtime_wasi::p2::WasiCtxBuilder::new()
+            .inherit_stderr()
+            .inherit_stdout()
+            .build();
+        let mut s = Self(Store::new(
+            engine,
+            Context {
+                limiter,
+                wasi_ctx,
+                wasi_p2_ctx,
+                table: ResourceTable::new(),
+                keyed_state: KeyedState::default(),
+            },
+        ));
         s.0.limiter(|inner| &mut inner.limiter);
         s.top_up_fuel();
+        s.set_deadline(None);
         s
     }
 
+    pub(crate) fn keyed_state_mut(&mut self) -> &mut KeyedState {
+        self.0.data_mut().keyed_state_mut()
+    }
+
+    pub(crate) fn limiter_mut(&mut self) -> &mut StoreResourceLimiter {
+        &mut self.0.data_mut().limiter
+    }
+
+    /// `instance` is position of module in the chain, it separates state of modules
     pub(crate) fn instantiate<Params, Args>(
         &mut self,
         module: &Module,
+        instance: u16,
         host_fn: impl IntoFunc<<Self as AsContext>::Data, Params, Args>,
     ) -> Result<Instance, Error> {
         let mut linker = wasmtime::Linker::new(module.engine());
         wasi_common::sync::add_to_linker(&mut linker, |c: &mut Context| &mut c.wasi_ctx)?;
+        keyed_state::add_to_linker(&mut linker, module, instance)?;
         let copy_records_fn_import = module
             .imports()
             .find(|import| import.name().eq("copy_records"))
@@ -83,6 +154,16 @@ impl WasmState {
         )?;
         linker.instantiate(self, module)
     }
+
+    /// components are linked with WASI p2, state is not available to them
+    pub(crate) fn instantiate_component(
+        &mut self,
+        component: &Component,
+    ) -> Result<component::Instance, Error> {
+        let mut linker = component::Linker::new(component.engine());
+        wasmtime_wasi::p2::add_to_linker_sync(&mut linker)?;
+        linker.instantiate(self, component)
+    }
 }
 
 impl std::fmt::Debug for Context {
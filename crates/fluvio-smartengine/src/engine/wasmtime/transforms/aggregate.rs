@@ -2,7 +2,7 @@ use std::convert::TryFrom;
 use std::fmt::Debug;
 
 use tracing::{debug, instrument};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use wasmtime::{AsContextMut, TypedFunc};
 
 use fluvio_smartmodule::dataplane::smartmodule::{
@@ -15,13 +15,17 @@ use crate::engine::wasmtime::{
     state::WasmState,
 };
 
+use super::window::{ClosedWindow, Windows};
+
 const AGGREGATE_FN_NAME: &str = "aggregate";
 
 type WasmAggregateFn = TypedFunc<(i32, i32, u32), i32>;
 
 pub(crate) struct SmartModuleAggregate {
     aggregate_fn: WasmAggregateFn,
+    /// with windows, this is initial accumulator of each window
     accumulator: Vec<u8>,
+    windows: Option<Windows>,
 }
 
 impl Debug for SmartModuleAggregate {
@@ -50,6 +54,14 @@ impl SmartModuleAggregate {
             }
         };
 
+        let windows = match ctx.params().window() {
+            Some(window) => {
+                window.validate().map_err(|err| anyhow!(err))?;
+                Some(Windows::new(*window, ctx.position()))
+            }
+            None => None,
+        };
+
         match ctx.get_wasm_func(&mut *store, AGGREGATE_FN_NAME) {
             Some(func) => {
                 // check type signature
@@ -60,6 +72,7 @@ impl SmartModuleAggregate {
                         Some(Self {
                             aggregate_fn,
                             accumulator,
+                            windows,
                         })
                     })
             }
@@ -68,18 +81,17 @@ impl SmartModuleAggregate {
     }
 }
 
-impl SmartModuleTransform for SmartModuleAggregate {
-    #[instrument(skip(self,ctx,store),fields(offset = input.base_offset()))]
-    fn process(
+impl SmartModuleAggregate {
+    fn call(
         &mut self,
         input: SmartModuleInput,
+        accumulator: Vec<u8>,
         ctx: &mut SmartModuleInstanceContext,
         store: &mut WasmState,
-    ) -> Result<SmartModuleOutput> {
-        debug!("start aggregration");
+    ) -> Result<SmartModuleAggregateOutput> {
         let input = SmartModuleAggregateInput {
             base: input,
-            accumulator: self.accumulator.clone(),
+            accumulator,
         };
         let slice = ctx.write_input(&input, &mut *store)?;
         let aggregate_output = self.aggregate_fn.call(&mut *store, slice)?;
@@ -91,7 +103,72 @@ impl SmartModuleTransform for SmartModuleAggregate {
             return Err(internal_error.into());
         }
 
-        let output: SmartModuleAggregateOutput = ctx.read_output(store)?;
+        ctx.read_output(store)
+    }
+
+    /// buffer records into windows and aggregate windows which are closed.
+    /// Windows after the one which failed are aggregated with next records.
+    fn process_windows(
+        &mut self,
+        input: SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let Some(windows) = self.windows.as_mut() else {
+            return Ok(SmartModuleOutput::default());
+        };
+        windows.add_records(input.try_into_smartmodule_records(ctx.version())?, store)?;
+        let mut closed_windows = windows.take_closed().into_iter();
+
+        let output = self.aggregate_windows(&mut closed_windows, ctx, store);
+        if let Some(windows) = self.windows.as_mut() {
+            windows.reopen(closed_windows);
+            windows.sync(store)?;
+        }
+        output
+    }
+
+    /// aggregate windows until one fails
+    fn aggregate_windows(
+        &mut self,
+        closed_windows: &mut impl Iterator<Item = ClosedWindow>,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let mut output = SmartModuleOutput::default();
+        for mut closed in closed_windows {
+            let input = SmartModuleInput::try_from_records(
+                std::mem::take(&mut closed.records),
+                ctx.version(),
+            )?;
+            let window_output = self.call(input, self.accumulator.clone(), ctx, store)?;
+            if let Some(error) = window_output.base.error {
+                output.error = Some(error);
+                break;
+            }
+            output
+                .successes
+                .push(closed.into_record(window_output.accumulator));
+        }
+        Ok(output)
+    }
+}
+
+impl SmartModuleTransform for SmartModuleAggregate {
+    #[instrument(skip(self,ctx,store),fields(offset = input.base_offset()))]
+    fn process(
+        &mut self,
+        input: SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        if self.windows.is_some() {
+            debug!("start windowed aggregration");
+            return self.process_windows(input, ctx, store);
+        }
+
+        debug!("start aggregration");
+        let output = self.call(input, self.accumulator.clone(), ctx, store)?;
 
         self.accumulator = output.accumulator;
         Ok(output.base)
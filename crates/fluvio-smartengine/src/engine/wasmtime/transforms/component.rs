@@ -0,0 +1,639 @@
+use anyhow::{anyhow, Result};
+use wasmtime::AsContextMut;
+use wasmtime::component::TypedFunc;
+
+use fluvio_protocol::link::smartmodule::SmartModuleKind;
+use fluvio_protocol::record::Record;
+use fluvio_smartmodule::eyre;
+use fluvio_smartmodule::SmartModuleRecord;
+use fluvio_smartmodule::dataplane::smartmodule::{
+    SmartModuleInput, SmartModuleOutput, SmartModuleTransformRuntimeError,
+};
+
+use crate::engine::SmartModuleInitialData;
+use crate::engine::wasmtime::component::{call, typed_func, WitRecord};
+use crate::engine::wasmtime::{
+    instance::{SmartModuleInstanceContext, SmartModuleTransform},
+    state::WasmState,
+};
+
+use super::window::{ClosedWindow, Windows};
+
+type FilterFn = TypedFunc<(WitRecord,), (Result<bool, String>,)>;
+type MapFn = TypedFunc<(WitRecord,), (Result<WitRecord, String>,)>;
+type FilterMapFn = TypedFunc<(WitRecord,), (Result<Option<WitRecord>, String>,)>;
+type ArrayMapFn = TypedFunc<(WitRecord,), (Result<Vec<WitRecord>, String>,)>;
+type AggregateFn = TypedFunc<(Vec<u8>, WitRecord), (Result<Vec<u8>, String>,)>;
+
+enum ComponentFn {
+    Filter(FilterFn),
+    Map(MapFn),
+    FilterMap(FilterMapFn),
+    ArrayMap(ArrayMapFn),
+    Aggregate(AggregateFn),
+}
+
+/// Transform exported by component, called once per record
+pub(crate) struct ComponentTransform {
+    f: ComponentFn,
+    /// with windows, this is initial accumulator of each window
+    accumulator: Vec<u8>,
+    windows: Option<Windows>,
+}
+
+impl std::fmt::Debug for ComponentTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Component({})", self.name())
+    }
+}
+
+impl ComponentTransform {
+    pub(crate) fn try_instantiate(
+        ctx: &SmartModuleInstanceContext,
+        initial_data: &SmartModuleInitialData,
+        store: &mut impl AsContextMut,
+    ) -> Result<Option<Self>> {
+        let f = if let Some(f) = typed_func(ctx, store, "filter", "filter")? {
+            ComponentFn::Filter(f)
+        } else if let Some(f) = typed_func(ctx, store, "map", "map")? {
+            ComponentFn::Map(f)
+        } else if let Some(f) = typed_func(ctx, store, "filter-map", "filter-map")? {
+            ComponentFn::FilterMap(f)
+        } else if let Some(f) = typed_func(ctx, store, "array-map", "array-map")? {
+            ComponentFn::ArrayMap(f)
+        } else if let Some(f) = typed_func(ctx, store, "aggregate", "aggregate")? {
+            ComponentFn::Aggregate(f)
+        } else {
+            return Ok(None);
+        };
+
+        let accumulator = match initial_data {
+            SmartModuleInitialData::Aggregate { accumulator } => accumulator.clone(),
+            SmartModuleInitialData::None => vec![],
+        };
+        let windows = match (&f, ctx.params().window()) {
+            (ComponentFn::Aggregate(_), Some(window)) => {
+                window.validate().map_err(|err| anyhow!(err))?;
+                Some(Windows::new(*window, ctx.position()))
+            }
+            _ => None,
+        };
+
+        Ok(Some(Self {
+            f,
+            accumulator,
+            windows,
+        }))
+    }
+
+    fn kind(&self) -> SmartModuleKind {
+        match self.f {
+            ComponentFn::Filter(_) => SmartModuleKind::Filter,
+            ComponentFn::Map(_) => SmartModuleKind::Map,
+            ComponentFn::FilterMap(_) => SmartModuleKind::FilterMap,
+            ComponentFn::ArrayMap(_) => SmartModuleKind::ArrayMap,
+            ComponentFn::Aggregate(_) => SmartModuleKind::Aggregate,
+        }
+    }
+
+    /// records produced from single record, or error returned by component
+    fn call(
+        &mut self,
+        record: &SmartModuleRecord,
+        store: &mut WasmState,
+    ) -> Result<Result<Vec<Record>, String>> {
+        let input = WitRecord::from(record);
+        let records =
+            match &self.f {
+                ComponentFn::Filter(f) => call(f, store, (input,))?.0.map(|keep| {
+                    keep.then(|| record.clone().into_inner())
+                        .into_iter()
+                        .collect()
+                }),
+                ComponentFn::Map(f) => call(f, store, (input,))?
+                    .0
+                    .map(|output| vec![output.into_record(record)]),
+                ComponentFn::FilterMap(f) => call(f, store, (input,))?.0.map(|output| {
+                    output
+                        .map(|output| output.into_record(record))
+                        .into_iter()
+                        .collect()
+                }),
+                ComponentFn::ArrayMap(f) => call(f, store, (input,))?.0.map(|outputs| {
+                    outputs
+                        .into_iter()
+                        .map(|output| output.into_record(record))
+                        .collect()
+                }),
+                ComponentFn::Aggregate(f) => call(f, store, (self.accumulator.clone(), input))?
+                    .0
+                    .map(|accumulator| {
+                        let mut output = record.clone().into_inner();
+                        output.value = accumulator.clone().into();
+                        self.accumulator = accumulator;
+                        vec![output]
+                    }),
+            };
+        Ok(records)
+    }
+
+    /// call component with each record until it returns error
+    fn process_records(
+        &mut self,
+        input: SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let base_offset = input.base_offset();
+        let mut output = SmartModuleOutput::default();
+        for record in input.try_into_smartmodule_records(ctx.version())? {
+            match self.call(&record, store)? {
+                Ok(records) => output.successes.extend(records),
+                Err(err) => {
+                    output.error = Some(SmartModuleTransformRuntimeError::new(
+                        &record,
+                        base_offset,
+                        self.kind(),
+                        eyre!(err),
+                    ));
+                    break;
+                }
+            }
+        }
+        Ok(output)
+    }
+
+    /// buffer records into windows and aggregate windows which are closed.
+    /// Windows after the one which failed are aggregated with next records.
+    fn process_windows(
+        &mut self,
+        input: SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let Some(windows) = self.windows.as_mut() else {
+            return Ok(SmartModuleOutput::default());
+        };
+        windows.add_records(input.try_into_smartmodule_records(ctx.version())?, store)?;
+        let mut closed_windows = windows.take_closed().into_iter();
+
+        let initial = self.accumulator.clone();
+        let output = self.aggregate_windows(&mut closed_windows, &initial, store);
+        self.accumulator = initial;
+        if let Some(windows) = self.windows.as_mut() {
+            windows.reopen(closed_windows);
+            windows.sync(store)?;
+        }
+        output
+    }
+
+    /// aggregate windows, starting each from `initial` accumulator, until one fails
+    fn aggregate_windows(
+        &mut self,
+        closed_windows: &mut impl Iterator<Item = ClosedWindow>,
+        initial: &[u8],
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let mut output = SmartModuleOutput::default();
+        for mut closed in closed_windows {
+            self.accumulator = initial.to_vec();
+            // buffered records have absolute offset and timestamp as deltas
+            for record in std::mem::take(&mut closed.records) {
+                let record = SmartModuleRecord::new(record, 0, 0);
+                if let Err(err) = self.call(&record, store)? {
+                    output.error = Some(SmartModuleTransformRuntimeError::new(
+                        &record,
+                        0,
+                        self.kind(),
+                        eyre!(err),
+                    ));
+                    return Ok(output);
+                }
+            }
+            output
+                .successes
+                .push(closed.into_record(self.accumulator.clone()));
+        }
+        Ok(output)
+    }
+}
+
+impl SmartModuleTransform for ComponentTransform {
+    fn process(
+        &mut self,
+        input: SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let start_time = ctx.metrics_time_start();
+        let output = if self.windows.is_some() {
+            self.process_windows(input, ctx, store)?
+        } else {
+            self.process_records(input, ctx, store)?
+        };
+        ctx.metrics_time_elapsed(start_time, store);
+
+        ctx.metrics().add_records_out(output.successes.len() as u64);
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        match self.f {
+            ComponentFn::Filter(_) => "filter",
+            ComponentFn::Map(_) => "map",
+            ComponentFn::FilterMap(_) => "filter-map",
+            ComponentFn::ArrayMap(_) => "array-map",
+            ComponentFn::Aggregate(_) => "aggregate",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+
+    use wasmtime::{AsContext, AsContextMut, Trap};
+    use wasmtime::component::Component;
+
+    use fluvio_protocol::record::Record;
+    use fluvio_smartmodule::dataplane::smartmodule::{
+        SmartModuleExtraParams, SmartModuleInput, SmartModuleOutput,
+    };
+
+    use crate::engine::{
+        EngineError, SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance,
+        SmartModuleConfig, SmartModuleInitialData,
+    };
+    use crate::engine::config::DEFAULT_SMARTENGINE_VERSION;
+    use crate::engine::wasmtime::limiter::StoreResourceLimiter;
+
+    use super::*;
+
+    /// Component exporting single transform of `fluvio:smartmodule` package.
+    /// Core module has bump allocator, results are written at 16 and "empty record" error is at 256.
+    fn component(name: &str, core_func: &str, func_type: &str) -> Vec<u8> {
+        let wat = format!(
+            r#"
+            (component
+                (core module $m
+                    (memory (export "memory") 1)
+                    (data (i32.const 256) "empty record")
+                    (global $heap (mut i32) (i32.const 1024))
+                    (func $alloc (param $size i32) (result i32)
+                        (local $ptr i32)
+                        (local.set $ptr
+                            (i32.and (i32.add (global.get $heap) (i32.const 7)) (i32.const -8)))
+                        (global.set $heap (i32.add (local.get $ptr) (local.get $size)))
+                        (local.get $ptr))
+                    (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+                        (call $alloc (local.get 3)))
+                    {core_func})
+                (core instance $i (instantiate $m))
+                (type $header-type (record (field "key" string) (field "value" (list u8))))
+                (export $header "header" (type $header-type))
+                (type $sm-record-type (record
+                    (field "offset" s64)
+                    (field "timestamp" s64)
+                    (field "key" (option (list u8)))
+                    (field "value" (list u8))
+                    (field "headers" (list $header))))
+                (export $sm-record "sm-record" (type $sm-record-type))
+                (func $f {func_type}
+                    (canon lift (core func $i "{name}")
+                        (memory $i "memory") (realloc (func $i "realloc"))))
+                (instance $instance (export "{name}" (func $f)))
+                (export "fluvio:smartmodule/{name}@0.1.0" (instance $instance)))
+            "#
+        );
+        wat::parse_str(wat).expect("component")
+    }
+
+    /// keeps records with value longer than 3 bytes, fails on empty value
+    fn filter_component() -> Vec<u8> {
+        component(
+            "filter",
+            r#"
+            (func (export "filter")
+                (param i64 i64 i32 i32 i32 i32 i32 i32 i32) (result i32)
+                (if (i32.eqz (local.get 6))
+                    (then
+                        (i32.store8 (i32.const 16) (i32.const 1))
+                        (i32.store (i32.const 20) (i32.const 256))
+                        (i32.store (i32.const 24) (i32.const 12))
+                        (return (i32.const 16))))
+                (i32.store8 (i32.const 16) (i32.const 0))
+                (i32.store8 (i32.const 20) (i32.gt_u (local.get 6) (i32.const 3)))
+                (i32.const 16))
+            "#,
+            r#"(param "record" $sm-record) (result (result bool (error string)))"#,
+        )
+    }
+
+    /// repeats value twice, fails on empty value
+    fn map_component() -> Vec<u8> {
+        component(
+            "map",
+            r#"
+            (func (export "map")
+                (param i64 i64 i32 i32 i32 i32 i32 i32 i32) (result i32)
+                (local $value i32)
+                (if (i32.eqz (local.get 6))
+                    (then
+                        (i32.store8 (i32.const 16) (i32.const 1))
+                        (i32.store (i32.const 24) (i32.const 256))
+                        (i32.store (i32.const 28) (i32.const 12))
+                        (return (i32.const 16))))
+                (local.set $value (call $alloc (i32.mul (local.get 6) (i32.const 2))))
+                (memory.copy (local.get $value) (local.get 5) (local.get 6))
+                (memory.copy
+                    (i32.add (local.get $value) (local.get 6)) (local.get 5) (local.get 6))
+                (i32.store8 (i32.const 16) (i32.const 0))
+                (i64.store (i32.const 24) (local.get 0))
+                (i64.store (i32.const 32) (local.get 1))
+                (i32.store8 (i32.const 40) (local.get 2))
+                (i32.store (i32.const 44) (local.get 3))
+                (i32.store (i32.const 48) (local.get 4))
+                (i32.store (i32.const 52) (local.get $value))
+                (i32.store (i32.const 56) (i32.mul (local.get 6) (i32.const 2)))
+                (i32.store (i32.const 60) (local.get 7))
+                (i32.store (i32.const 64) (local.get 8))
+                (i32.const 16))
+            "#,
+            r#"(param "record" $sm-record) (result (result $sm-record (error string)))"#,
+        )
+    }
+
+    /// splits value into records of single byte
+    fn array_map_component() -> Vec<u8> {
+        component(
+            "array-map",
+            r#"
+            (func (export "array-map")
+                (param i64 i64 i32 i32 i32 i32 i32 i32 i32) (result i32)
+                (local $list i32) (local $index i32) (local $item i32)
+                (local.set $list (call $alloc (i32.mul (local.get 6) (i32.const 48))))
+                (block $done
+                    (loop $next
+                        (br_if $done (i32.ge_u (local.get $index) (local.get 6)))
+                        (local.set $item
+                            (i32.add (local.get $list) (i32.mul (local.get $index) (i32.const 48))))
+                        (i64.store (local.get $item) (local.get 0))
+                        (i64.store offset=8 (local.get $item) (local.get 1))
+                        (i32.store8 offset=16 (local.get $item) (local.get 2))
+                        (i32.store offset=20 (local.get $item) (local.get 3))
+                        (i32.store offset=24 (local.get $item) (local.get 4))
+                        (i32.store offset=28 (local.get $item)
+                            (i32.add (local.get 5) (local.get $index)))
+                        (i32.store offset=32 (local.get $item) (i32.const 1))
+                        (i32.store offset=36 (local.get $item) (local.get 7))
+                        (i32.store offset=40 (local.get $item) (local.get 8))
+                        (local.set $index (i32.add (local.get $index) (i32.const 1)))
+                        (br $next)))
+                (i32.store8 (i32.const 16) (i32.const 0))
+                (i32.store (i32.const 20) (local.get $list))
+                (i32.store (i32.const 24) (local.get 6))
+                (i32.const 16))
+            "#,
+            r#"(param "record" $sm-record) (result (result (list $sm-record) (error string)))"#,
+        )
+    }
+
+    /// appends value to accumulator
+    fn aggregate_component() -> Vec<u8> {
+        component(
+            "aggregate",
+            r#"
+            (func (export "aggregate")
+                (param i32 i32 i64 i64 i32 i32 i32 i32 i32 i32 i32) (result i32)
+                (local $accumulator i32)
+                (local.set $accumulator (call $alloc (i32.add (local.get 1) (local.get 8))))
+                (memory.copy (local.get $accumulator) (local.get 0) (local.get 1))
+                (memory.copy
+                    (i32.add (local.get $accumulator) (local.get 1)) (local.get 7) (local.get 8))
+                (i32.store8 (i32.const 16) (i32.const 0))
+                (i32.store (i32.const 20) (local.get $accumulator))
+                (i32.store (i32.const 24) (i32.add (local.get 1) (local.get 8)))
+                (i32.const 16))
+            "#,
+            r#"(param "accumulator" (list u8)) (param "record" $sm-record)
+                (result (result (list u8) (error string)))"#,
+        )
+    }
+
+    /// filter which never returns
+    fn forever_component() -> Vec<u8> {
+        component(
+            "filter",
+            r#"
+            (func (export "filter")
+                (param i64 i64 i32 i32 i32 i32 i32 i32 i32) (result i32)
+                (loop $forever (br $forever))
+                (i32.const 16))
+            "#,
+            r#"(param "record" $sm-record) (result (result bool (error string)))"#,
+        )
+    }
+
+    fn chain(config: SmartModuleConfig, component: Vec<u8>) -> SmartModuleChainInstance {
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+        chain_builder.add_smart_module(config, component);
+        chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain")
+    }
+
+    fn config(name: &str) -> SmartModuleConfig {
+        SmartModuleConfig::builder()
+            .smartmodule_names(&[name.to_string()])
+            .build()
+            .unwrap()
+    }
+
+    fn input(values: &[&str]) -> SmartModuleInput {
+        let records = values
+            .iter()
+            .enumerate()
+            .map(|(offset_delta, value)| {
+                let mut record = Record::new(*value);
+                record.preamble.set_offset_delta(offset_delta as i64);
+                record
+            })
+            .collect();
+        let mut input = SmartModuleInput::try_from_records(records, DEFAULT_SMARTENGINE_VERSION)
+            .expect("input");
+        input.set_base_offset(10);
+        input
+    }
+
+    fn values(output: &SmartModuleOutput) -> Vec<(i64, String)> {
+        output
+            .successes
+            .iter()
+            .map(|record| {
+                (
+                    record.preamble.offset_delta(),
+                    record.value.as_utf8_lossy_string().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_component_filter() {
+        let mut chain = chain(config("filter-component"), filter_component());
+        assert_eq!(
+            chain.instances().first().expect("first").transform().name(),
+            "filter"
+        );
+
+        let output = chain
+            .process(input(&["a", "apple", "", "banana"]))
+            .expect("process");
+        assert_eq!(values(&output), vec![(1, "apple".to_string())]);
+
+        let error = output.error.expect("error");
+        assert_eq!(error.offset, 12);
+        assert!(error.hint.contains("empty record"), "{}", error.hint);
+    }
+
+    #[test]
+    fn test_component_map() {
+        let mut chain = chain(config("map-component"), map_component());
+        assert_eq!(
+            chain.instances().first().expect("first").transform().name(),
+            "map"
+        );
+
+        let output = chain.process(input(&["a", "bc", ""])).expect("process");
+        assert_eq!(
+            values(&output),
+            vec![(0, "aa".to_string()), (1, "bcbc".to_string())]
+        );
+
+        let error = output.error.expect("error");
+        assert_eq!(error.offset, 12);
+        assert!(error.hint.contains("empty record"), "{}", error.hint);
+
+        let metrics = chain.metrics_export();
+        assert_eq!(
+            metrics.get("map-component").expect("metrics").records_out(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_component_array_map() {
+        let mut chain = chain(config("array-map-component"), array_map_component());
+        assert_eq!(
+            chain.instances().first().expect("first").transform().name(),
+            "array-map"
+        );
+
+        let output = chain.process(input(&["ab", "", "c"])).expect("process");
+        assert!(output.error.is_none());
+        assert_eq!(
+            values(&output),
+            vec![
+                (0, "a".to_string()),
+                (0, "b".to_string()),
+                (2, "c".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_component_aggregate() {
+        let mut chain = chain(
+            SmartModuleConfig::builder()
+                .smartmodule_names(&["aggregate-component".to_string()])
+                .initial_data(SmartModuleInitialData::with_aggregate(b"x".to_vec()))
+                .build()
+                .unwrap(),
+            aggregate_component(),
+        );
+        assert_eq!(
+            chain.instances().first().expect("first").transform().name(),
+            "aggregate"
+        );
+
+        let output = chain.process(input(&["a", "b"])).expect("process");
+        assert_eq!(
+            values(&output),
+            vec![(0, "xa".to_string()), (1, "xab".to_string())]
+        );
+
+        // accumulator is kept between calls
+        let output = chain.process(input(&["c"])).expect("process");
+        assert_eq!(values(&output), vec![(0, "xabc".to_string())]);
+    }
+
+    #[test]
+    fn test_component_timeout() {
+        let timeout = Duration::from_millis(50);
+        let mut chain = chain(
+            SmartModuleConfig::builder()
+                .smartmodule_names(&["forever".to_string()])
+                .timeout(Some(timeout))
+                .build()
+                .unwrap(),
+            forever_component(),
+        );
+
+        let err = chain
+            .process(input(&["a"]))
+            .unwrap_err()
+            .downcast::<EngineError>()
+            .expect("EngineError expected");
+        assert!(matches!(err, EngineError::Timeout(t) if t == timeout));
+        let metrics = chain.metrics_export();
+        assert_eq!(metrics.get("forever").expect("metrics").timeouts(), 1);
+    }
+
+    #[test]
+    fn test_component_out_of_fuel() {
+        let engine = SmartEngine::new();
+        let mut store = engine.new_state(StoreResourceLimiter::default());
+        let component =
+            Component::new(store.as_context().engine(), forever_component()).expect("component");
+        let mut ctx = SmartModuleInstanceContext::instantiate_component(
+            &mut store,
+            component,
+            0,
+            SmartModuleExtraParams::default(),
+            DEFAULT_SMARTENGINE_VERSION,
+            None,
+            &["forever".to_string()],
+        )
+        .expect("instantiate");
+        let mut transform =
+            ComponentTransform::try_instantiate(&ctx, &SmartModuleInitialData::None, &mut store)
+                .expect("transform")
+                .expect("filter");
+
+        store.as_context_mut().set_fuel(10_000).expect("fuel");
+        let err = transform
+            .process(input(&["a"]), &mut ctx, &mut store)
+            .unwrap_err();
+        assert_eq!(err.downcast_ref::<Trap>(), Some(&Trap::OutOfFuel));
+    }
+
+    #[test]
+    fn test_component_with_wasi_imports() {
+        let engine = SmartEngine::new();
+        let mut store = engine.new_state(StoreResourceLimiter::default());
+        let bytes = wat::parse_str(
+            r#"
+            (component
+                (import "wasi:random/random@0.2.0" (instance
+                    (export "get-random-u64" (func (result u64))))))
+            "#,
+        )
+        .expect("component");
+        let component = Component::new(store.as_context().engine(), bytes).expect("component");
+        store
+            .instantiate_component(&component)
+            .expect("WASI is linked");
+    }
+}
@@ -3,6 +3,8 @@ mod map;
 mod array_map;
 mod filter_map;
 mod aggregate;
+mod window;
+mod component;
 pub(crate) use instance::create_transform;
 mod simple_transform;
 
@@ -19,6 +21,7 @@ mod instance {
             SimpleTansform, FILTER_FN_NAME, MAP_FN_NAME, FILTER_MAP_FN_NAME, ARRAY_MAP_FN_NAME,
         },
         aggregate::SmartModuleAggregate,
+        component::ComponentTransform,
     };
 
     pub(crate) fn create_transform(
@@ -26,7 +29,11 @@ mod instance {
         initial_data: SmartModuleInitialData,
         store: &mut impl AsContextMut,
     ) -> Result<Box<dyn DowncastableTransform>> {
-        if let Some(tr) = SimpleTansform::try_instantiate(FILTER_FN_NAME, ctx, store)?
+        if let Some(tr) = ComponentTransform::try_instantiate(ctx, &initial_data, store)?
+            .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
+        {
+            Ok(tr)
+        } else if let Some(tr) = SimpleTansform::try_instantiate(FILTER_FN_NAME, ctx, store)?
             .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
         {
             Ok(tr)
@@ -0,0 +1,450 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use tracing::debug;
+
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_protocol::record::{Record, RecordData, RecordKey};
+use fluvio_protocol::types::Timestamp;
+use fluvio_smartmodule::SmartModuleRecord;
+use fluvio_smartmodule::dataplane::smartmodule::{
+    TimeWindow, Window, WindowKind, WINDOW_END_HEADER, WINDOW_START_HEADER,
+};
+
+use crate::engine::config::DEFAULT_SMARTENGINE_VERSION;
+use crate::engine::wasmtime::keyed_state::{StateKey, HOST_KEY_PREFIX};
+use crate::engine::wasmtime::state::WasmState;
+
+/// key of open windows in state of SmartModule
+const WINDOWS_STATE_KEY: &[u8] = &[HOST_KEY_PREFIX, b'w'];
+
+type GroupKey = Option<Vec<u8>>;
+
+/// Open windows of an Aggregate, with records buffered until window closes.
+/// Records are kept with absolute offset and timestamp as deltas, so records from different
+/// batches can be aggregated together.
+/// Buffered records count against memory limit of the store and open windows are saved
+/// to state of the SmartModule, so they survive restart when host persists state.
+#[derive(Debug)]
+pub(crate) struct Windows {
+    window: Window,
+    state_key: StateKey,
+    /// state is restored before first records are added
+    restored: bool,
+    /// bytes of buffered records, accounted in store limiter
+    buffered: usize,
+    watermark: Option<Timestamp>,
+    open: BTreeMap<GroupKey, Vec<OpenWindow>>,
+}
+
+#[derive(Debug, Default, Encoder, Decoder)]
+struct OpenWindow {
+    window: TimeWindow,
+    records: Vec<Record>,
+}
+
+impl OpenWindow {
+    fn size(&self) -> usize {
+        records_size(&self.records)
+    }
+}
+
+/// Window ready to be aggregated
+#[derive(Debug)]
+pub(crate) struct ClosedWindow {
+    pub key: GroupKey,
+    pub window: TimeWindow,
+    pub records: Vec<Record>,
+}
+
+impl ClosedWindow {
+    /// record with aggregate result of the window
+    pub(crate) fn into_record(self, accumulator: Vec<u8>) -> Record {
+        Record::new_key_value(
+            RecordKey::from_option(self.key.map(RecordData::from)),
+            accumulator,
+        )
+        .with_header(WINDOW_START_HEADER, self.window.start.to_string())
+        .with_header(WINDOW_END_HEADER, self.window.end.to_string())
+    }
+}
+
+impl Windows {
+    /// `instance` is position of SmartModule in the chain
+    pub(crate) fn new(window: Window, instance: u16) -> Self {
+        Self {
+            window,
+            state_key: StateKey::new(instance, WINDOWS_STATE_KEY),
+            restored: false,
+            buffered: 0,
+            watermark: None,
+            open: BTreeMap::new(),
+        }
+    }
+
+    /// buffer records into their windows, records of closed windows are dropped
+    pub(crate) fn add_records(
+        &mut self,
+        records: impl IntoIterator<Item = SmartModuleRecord>,
+        store: &mut WasmState,
+    ) -> Result<()> {
+        if !self.restored {
+            self.restore(store)?;
+        }
+        for record in records {
+            let offset = record.offset();
+            if !self.add(record, store)? {
+                debug!(offset, "dropping record of closed window");
+            }
+        }
+        Ok(())
+    }
+
+    /// add record to its windows, returns false if record is late and all its windows are closed
+    fn add(&mut self, record: SmartModuleRecord, store: &mut WasmState) -> Result<bool> {
+        let timestamp = record.timestamp();
+        let offset = record.offset();
+        let windows: Vec<TimeWindow> = self
+            .window
+            .assign(timestamp)
+            .into_iter()
+            .filter(|window| !self.is_closed(window))
+            .collect();
+        if windows.is_empty() {
+            return Ok(false);
+        }
+
+        let mut record = record.into_inner();
+        record.preamble.set_offset_delta(offset);
+        record.preamble.set_timestamp_delta(timestamp);
+        let size = records_size(std::slice::from_ref(&record)) * windows.len();
+        store.limiter_mut().buffer(size)?;
+        self.buffered += size;
+
+        let key = record.key().map(|key| key.as_ref().to_vec());
+        let open = self.open.entry(key).or_default();
+
+        match self.window.kind {
+            WindowKind::Tumbling { .. } | WindowKind::Hopping { .. } => {
+                for window in windows {
+                    match open.iter_mut().find(|open| open.window == window) {
+                        Some(open) => open.records.push(record.clone()),
+                        None => open.push(OpenWindow {
+                            window,
+                            records: vec![record.clone()],
+                        }),
+                    }
+                }
+            }
+            WindowKind::Session { .. } => {
+                // merge all sessions overlapping with session of the record
+                let mut session = OpenWindow {
+                    window: windows[0],
+                    records: vec![record],
+                };
+                let (overlapping, rest) = std::mem::take(open).into_iter().partition(|open| {
+                    open.window.start < session.window.end && session.window.start < open.window.end
+                });
+                *open = rest;
+                for merged in overlapping {
+                    session.window.start = session.window.start.min(merged.window.start);
+                    session.window.end = session.window.end.max(merged.window.end);
+                    session.records.extend(merged.records);
+                }
+                session
+                    .records
+                    .sort_by_key(|record| record.timestamp_delta());
+                open.push(session);
+            }
+        }
+
+        self.watermark = Some(self.watermark.map_or(timestamp, |max| max.max(timestamp)));
+        Ok(true)
+    }
+
+    /// remove windows closed by watermark, ordered by end of window
+    pub(crate) fn take_closed(&mut self) -> Vec<ClosedWindow> {
+        let mut closed = Vec::new();
+        for (key, open) in self.open.iter_mut() {
+            let (done, rest): (Vec<_>, Vec<_>) = std::mem::take(open)
+                .into_iter()
+                .partition(|open| is_closed(&self.window, self.watermark, &open.window));
+            *open = rest;
+            closed.extend(done.into_iter().map(|open| ClosedWindow {
+                key: key.clone(),
+                window: open.window,
+                records: open.records,
+            }));
+        }
+        self.open.retain(|_, open| !open.is_empty());
+        closed.sort_by(|a, b| (a.window.end, a.window.start).cmp(&(b.window.end, b.window.start)));
+        closed
+    }
+
+    /// put back windows which were not aggregated, they are taken again with next closed windows
+    pub(crate) fn reopen(&mut self, windows: impl IntoIterator<Item = ClosedWindow>) {
+        for closed in windows {
+            self.open.entry(closed.key).or_default().push(OpenWindow {
+                window: closed.window,
+                records: closed.records,
+            });
+        }
+    }
+
+    /// release memory of windows which were taken and save open windows to state
+    pub(crate) fn sync(&mut self, store: &mut WasmState) -> Result<()> {
+        let buffered = self.open.values().flatten().map(OpenWindow::size).sum();
+        store
+            .limiter_mut()
+            .release(self.buffered.saturating_sub(buffered));
+        self.buffered = buffered;
+
+        let state = store.keyed_state_mut();
+        if state.is_tracked() {
+            let bytes = self.encode_open()?;
+            if state.get(&self.state_key) != Some(&bytes) {
+                state.put(self.state_key.clone(), bytes);
+            }
+        }
+        Ok(())
+    }
+
+    /// load open windows saved to state
+    fn restore(&mut self, store: &mut WasmState) -> Result<()> {
+        self.restored = true;
+        let Some(bytes) = store.keyed_state_mut().get(&self.state_key).cloned() else {
+            return Ok(());
+        };
+        let (watermark, open) = decode_open(&bytes)?;
+
+        let buffered = open.values().flatten().map(OpenWindow::size).sum();
+        store.limiter_mut().buffer(buffered)?;
+        debug!(buffered, "restored open windows");
+        self.buffered += buffered;
+        self.watermark = watermark;
+        self.open = open;
+        Ok(())
+    }
+
+    fn is_closed(&self, window: &TimeWindow) -> bool {
+        is_closed(&self.window, self.watermark, window)
+    }
+
+    /// watermark followed by open windows of each key, there can be more keys than map encoding allows
+    fn encode_open(&self) -> Result<Vec<u8>> {
+        let version = DEFAULT_SMARTENGINE_VERSION;
+        let mut bytes = Vec::new();
+        self.watermark.encode(&mut bytes, version)?;
+        i32::try_from(self.open.len())?.encode(&mut bytes, version)?;
+        for (key, open) in &self.open {
+            key.encode(&mut bytes, version)?;
+            open.encode(&mut bytes, version)?;
+        }
+        Ok(bytes)
+    }
+}
+
+type OpenWindows = (Option<Timestamp>, BTreeMap<GroupKey, Vec<OpenWindow>>);
+
+fn decode_open(mut src: &[u8]) -> Result<OpenWindows> {
+    let version = DEFAULT_SMARTENGINE_VERSION;
+    let mut watermark: Option<Timestamp> = None;
+    watermark.decode(&mut src, version)?;
+    let mut len: i32 = 0;
+    len.decode(&mut src, version)?;
+    let mut open = BTreeMap::new();
+    for _ in 0..len {
+        let mut key = GroupKey::default();
+        key.decode(&mut src, version)?;
+        let mut windows = Vec::<OpenWindow>::new();
+        windows.decode(&mut src, version)?;
+        open.insert(key, windows);
+    }
+    Ok((watermark, open))
+}
+
+fn is_closed(window: &Window, watermark: Option<Timestamp>, time_window: &TimeWindow) -> bool {
+    watermark.is_some_and(|watermark| window.is_closed(time_window, watermark))
+}
+
+fn records_size(records: &[Record]) -> usize {
+    records
+        .iter()
+        .map(|record| record.write_size(DEFAULT_SMARTENGINE_VERSION))
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+
+    use crate::engine::SmartEngine;
+    use crate::engine::wasmtime::limiter::StoreResourceLimiter;
+
+    use super::*;
+
+    fn new_store(limiter: StoreResourceLimiter) -> WasmState {
+        SmartEngine::new().new_state(limiter)
+    }
+
+    fn record(key: &str, value: &str, timestamp: Timestamp) -> SmartModuleRecord {
+        SmartModuleRecord::new(Record::new_key_value(key, value), 0, timestamp)
+    }
+
+    fn values(closed: &ClosedWindow) -> Vec<String> {
+        closed
+            .records
+            .iter()
+            .map(|record| record.value().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_tumbling_closes_after_grace() {
+        let mut store = new_store(StoreResourceLimiter::default());
+        let mut windows = Windows::new(
+            Window::tumbling(Duration::from_millis(10)).with_grace(Duration::from_millis(5)),
+            0,
+        );
+        assert!(windows.add(record("a", "1", 1), &mut store).expect("add"));
+        assert!(windows.add(record("b", "2", 3), &mut store).expect("add"));
+        assert!(windows.add(record("a", "3", 12), &mut store).expect("add"));
+        assert!(windows.take_closed().is_empty());
+
+        // within grace period
+        assert!(windows.add(record("a", "4", 8), &mut store).expect("add"));
+        assert!(windows.add(record("a", "5", 15), &mut store).expect("add"));
+
+        let closed = windows.take_closed();
+        assert_eq!(closed.len(), 2);
+        assert_eq!(closed[0].key, Some(b"a".to_vec()));
+        assert_eq!(closed[0].window, TimeWindow::new(0, 10));
+        assert_eq!(values(&closed[0]), vec!["1", "4"]);
+        assert_eq!(closed[1].key, Some(b"b".to_vec()));
+        assert_eq!(values(&closed[1]), vec!["2"]);
+
+        // late
+        assert!(!windows.add(record("a", "6", 9), &mut store).expect("add"));
+
+        let result = closed
+            .into_iter()
+            .next()
+            .expect("closed")
+            .into_record(b"14".to_vec());
+        assert_eq!(result.value().as_ref(), b"14");
+        assert_eq!(
+            result
+                .headers()
+                .get(WINDOW_START_HEADER)
+                .map(|h| h.to_string()),
+            Some("0".to_string())
+        );
+        assert_eq!(
+            result
+                .headers()
+                .get(WINDOW_END_HEADER)
+                .map(|h| h.to_string()),
+            Some("10".to_string())
+        );
+    }
+
+    #[test]
+    fn test_hopping_record_in_multiple_windows() {
+        let mut store = new_store(StoreResourceLimiter::default());
+        let mut windows = Windows::new(
+            Window::hopping(Duration::from_millis(10), Duration::from_millis(5)),
+            0,
+        );
+        assert!(windows.add(record("a", "1", 7), &mut store).expect("add"));
+        assert!(windows.add(record("a", "2", 25), &mut store).expect("add"));
+
+        let closed = windows.take_closed();
+        assert_eq!(
+            closed
+                .iter()
+                .map(|closed| closed.window)
+                .collect::<Vec<_>>(),
+            vec![TimeWindow::new(0, 10), TimeWindow::new(5, 15)]
+        );
+        assert_eq!(values(&closed[0]), vec!["1"]);
+        assert_eq!(values(&closed[1]), vec!["1"]);
+    }
+
+    #[test]
+    fn test_sessions_merged() {
+        let mut store = new_store(StoreResourceLimiter::default());
+        let mut windows = Windows::new(Window::session(Duration::from_millis(10)), 0);
+        assert!(windows.add(record("a", "1", 0), &mut store).expect("add"));
+        assert!(windows.add(record("a", "3", 18), &mut store).expect("add"));
+        assert!(windows.add(record("a", "2", 9), &mut store).expect("add"));
+        assert!(windows.add(record("a", "4", 40), &mut store).expect("add"));
+
+        let closed = windows.take_closed();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].window, TimeWindow::new(0, 28));
+        assert_eq!(values(&closed[0]), vec!["1", "2", "3"]);
+        assert_eq!(closed[0].records[2].timestamp_delta(), 18);
+    }
+
+    #[test]
+    fn test_buffered_records_limited() {
+        let mut limiter = StoreResourceLimiter::default();
+        limiter.set_memory_size(100);
+        let mut store = new_store(limiter);
+        let mut windows = Windows::new(Window::tumbling(Duration::from_millis(10)), 0);
+        windows
+            .add_records(vec![record("a", &"x".repeat(40), 0)], &mut store)
+            .expect("add");
+        assert!(
+            windows
+                .add_records(vec![record("a", &"x".repeat(80), 1)], &mut store)
+                .is_err()
+        );
+
+        // memory of aggregated windows is released
+        windows
+            .add_records(vec![record("a", "1", 10)], &mut store)
+            .expect("add");
+        assert_eq!(windows.take_closed().len(), 1);
+        windows.sync(&mut store).expect("sync");
+        windows
+            .add_records(vec![record("a", &"x".repeat(40), 11)], &mut store)
+            .expect("add");
+    }
+
+    #[test]
+    fn test_windows_saved_to_state() {
+        let window = Window::tumbling(Duration::from_millis(10));
+        let mut store = new_store(StoreResourceLimiter::default());
+        store.keyed_state_mut().restore(vec![]);
+        let mut windows = Windows::new(window, 1);
+        windows
+            .add_records(vec![record("a", "1", 1), record("b", "2", 12)], &mut store)
+            .expect("add");
+        let closed = windows.take_closed();
+        assert_eq!(closed.len(), 1);
+        // window which was not aggregated is saved too
+        windows.reopen(closed);
+        windows.sync(&mut store).expect("sync");
+        let changes = store.keyed_state_mut().take_changes();
+        assert_eq!(changes.len(), 1);
+
+        let mut restored_store = new_store(StoreResourceLimiter::default());
+        restored_store.keyed_state_mut().restore(
+            changes
+                .into_iter()
+                .filter_map(|(key, value)| value.map(|value| (key, value))),
+        );
+        let mut restored = Windows::new(window, 1);
+        restored
+            .add_records(vec![record("b", "3", 25)], &mut restored_store)
+            .expect("add");
+        let closed = restored.take_closed();
+        assert_eq!(closed.len(), 2);
+        assert_eq!(values(&closed[0]), vec!["1"]);
+        assert_eq!(values(&closed[1]), vec!["2"]);
+        assert_eq!(closed[1].records[0].offset_delta(), 0);
+        assert_eq!(closed[1].records[0].timestamp_delta(), 12);
+    }
+}
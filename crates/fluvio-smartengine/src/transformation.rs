@@ -54,6 +54,23 @@ pub struct TransformationStep {
     pub lookback: Option<Lookback>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub with: BTreeMap<String, JsonString>,
+    #[serde(
+        default,
+        rename = "on-error",
+        alias = "on_error",
+        skip_serializing_if = "ErrorPolicy::is_fail"
+    )]
+    pub on_error: ErrorPolicy,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<Window>,
+    /// limit of time of single invocation
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(with = "Option::<String>")]
+    pub timeout: Option<Duration>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
@@ -66,6 +83,88 @@ pub struct Lookback {
     pub age: Option<Duration>,
 }
 
+/// Windowing of aggregate transformation by record timestamp
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Window {
+    #[serde(flatten)]
+    pub kind: WindowKind,
+    /// how long window is kept open for late records after its end
+    #[serde(default, with = "humantime_serde")]
+    #[schemars(with = "Option::<String>")]
+    pub grace: Option<Duration>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowKind {
+    Tumbling {
+        #[serde(with = "humantime_serde")]
+        #[schemars(with = "String")]
+        size: Duration,
+    },
+    Hopping {
+        #[serde(with = "humantime_serde")]
+        #[schemars(with = "String")]
+        size: Duration,
+        #[serde(with = "humantime_serde")]
+        #[schemars(with = "String")]
+        advance: Duration,
+    },
+    Session {
+        #[serde(with = "humantime_serde")]
+        #[schemars(with = "String")]
+        gap: Duration,
+    },
+}
+
+impl From<Window> for fluvio_smartmodule::dataplane::smartmodule::Window {
+    fn from(value: Window) -> Self {
+        use fluvio_smartmodule::dataplane::smartmodule::WindowKind as SmartModuleWindowKind;
+
+        let kind = match value.kind {
+            WindowKind::Tumbling { size } => SmartModuleWindowKind::Tumbling { size },
+            WindowKind::Hopping { size, advance } => {
+                SmartModuleWindowKind::Hopping { size, advance }
+            }
+            WindowKind::Session { gap } => SmartModuleWindowKind::Session { gap },
+        };
+        Self {
+            kind,
+            grace: value.grace.unwrap_or_default(),
+        }
+    }
+}
+
+/// What to do with a record the transformation fails on
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorPolicy {
+    /// stop and return the error
+    #[default]
+    Fail,
+    /// log the error and continue with next record
+    Skip,
+    /// send the record to dead-letter topic and continue with next record
+    DeadLetter,
+}
+
+impl ErrorPolicy {
+    fn is_fail(&self) -> bool {
+        matches!(self, Self::Fail)
+    }
+}
+
+impl From<ErrorPolicy> for fluvio_smartmodule::dataplane::smartmodule::SmartModuleErrorPolicy {
+    fn from(value: ErrorPolicy) -> Self {
+        match value {
+            ErrorPolicy::Fail => Self::Fail,
+            ErrorPolicy::Skip => Self::Skip,
+            ErrorPolicy::DeadLetter => Self::DeadLetter,
+        }
+    }
+}
+
 impl Display for TransformationStep {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{self:?}")
@@ -203,7 +302,10 @@ mod tests {
                         with: BTreeMap::from([(
                             "spec".to_string(),
                             JsonString("[{\"operation\":\"shift\",\"spec\":{\"payload\":{\"device\":\"device\"}}},{\"operation\":\"default\",\"spec\":{\"device\":{\"type\":\"mobile\"}}}]".to_string())
-                        )])
+                        )]),
+                        on_error: ErrorPolicy::Fail,
+                        window: None,
+                        timeout: None,
                     },
                     TransformationStep {
                         uses: "infinyon/jolt@0.4.1".to_string(),
@@ -211,7 +313,10 @@ mod tests {
                         with: BTreeMap::from([(
                             "spec".to_string(),
                             JsonString("[{\"operation\":\"shift\",\"spec\":{\"payload\":{\"device\":\"device\"}}},{\"operation\":\"default\",\"spec\":{\"device\":{\"type\":\"mobile\"}}}]".to_string())
-                        )])
+                        )]),
+                        on_error: ErrorPolicy::Skip,
+                        window: None,
+                        timeout: Some(Duration::from_millis(500)),
                     },
                     TransformationStep {
                         uses: "infinyon/json-sql@0.2.1".to_string(),
@@ -219,7 +324,16 @@ mod tests {
                         with: BTreeMap::from([(
                             "mapping".to_string(),
                             JsonString("{\"map-columns\":{\"device_id\":{\"json-key\":\"device.device_id\",\"value\":{\"default\":\"0\",\"required\":true,\"type\":\"int\"}},\"record\":{\"json-key\":\"$\",\"value\":{\"required\":true,\"type\":\"jsonb\"}}},\"table\":\"topic_message_demo\"}".to_string())
-                        )])
+                        )]),
+                        on_error: ErrorPolicy::DeadLetter,
+                        window: Some(Window {
+                            kind: WindowKind::Hopping {
+                                size: Duration::from_secs(60),
+                                advance: Duration::from_secs(10),
+                            },
+                            grace: Some(Duration::from_secs(5)),
+                        }),
+                        timeout: None,
                     }
                 ]
             }
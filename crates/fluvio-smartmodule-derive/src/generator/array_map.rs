@@ -24,7 +24,8 @@ pub fn generate_array_map_smartmodule(func: &SmartModuleFn) -> TokenStream {
 
                         for (output_key, output_value) in output_records {
                             let key = RecordKey::from_option(output_key);
-                            let new_record = Record::new_key_value(key, output_value);
+                            let new_record = Record::new_key_value(key, output_value)
+                                .with_headers(record.headers.clone());
                             output.successes.push(new_record.into());
                         }
                     }
@@ -3,21 +3,28 @@ use std::time::Duration;
 use std::{collections::BTreeMap, fmt::Display};
 use std::fmt;
 use fluvio_protocol::{Decoder, Encoder, Version};
-use fluvio_protocol::record::{Offset, Record};
+use fluvio_protocol::record::{Offset, Record, RECORD_HEADERS_VERSION};
 use fluvio_protocol::types::Timestamp;
 
 use crate::SmartModuleRecord;
+use crate::window::Window;
 
 /// SmartModule Version with support for Lookback with Age and Timestamps,
 /// LTA is the acronym for Lookback, Timestamps, and Age.
 /// This version is used for encoding and decoding [`SmartModuleInput`]
 pub const SMARTMODULE_TIMESTAMPS_VERSION: Version = 22;
 
+/// SmartModule Version with support for record headers,
+/// records passed to older SmartModules don't carry headers.
+pub const SMARTMODULE_HEADERS_VERSION: Version = RECORD_HEADERS_VERSION;
+
 #[derive(Debug, Default, Clone, Encoder, Decoder)]
 pub struct SmartModuleExtraParams {
     inner: BTreeMap<String, String>,
     #[fluvio(min_version = 20)]
     lookback: Option<Lookback>,
+    #[fluvio(min_version = 31)]
+    window: Option<Window>,
 }
 
 impl From<BTreeMap<String, String>> for SmartModuleExtraParams {
@@ -34,6 +41,7 @@ impl SmartModuleExtraParams {
         Self {
             inner: params,
             lookback,
+            ..Default::default()
         }
     }
 
@@ -41,6 +49,10 @@ impl SmartModuleExtraParams {
         self.inner.get(key)
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.inner.iter()
+    }
+
     pub fn insert(&mut self, key: String, value: String) {
         self.inner.insert(key, value);
     }
@@ -52,6 +64,14 @@ impl SmartModuleExtraParams {
     pub fn set_lookback(&mut self, lookback: Option<Lookback>) {
         self.lookback = lookback;
     }
+
+    pub fn window(&self) -> Option<&Window> {
+        self.window.as_ref()
+    }
+
+    pub fn set_window(&mut self, window: Option<Window>) {
+        self.window = window;
+    }
 }
 
 #[derive(Debug, Default, Clone, Encoder, Decoder, PartialEq, Eq)]
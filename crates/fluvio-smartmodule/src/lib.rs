@@ -3,6 +3,7 @@
 mod input;
 mod output;
 mod error;
+mod window;
 
 use std::ops::{Deref, DerefMut};
 
@@ -21,9 +22,12 @@ pub type Result<T> = eyre::Result<T>;
 #[cfg(feature = "smartmodule")]
 pub mod memory;
 
-pub use fluvio_protocol::record::{Offset, Record, RecordData};
+#[cfg(feature = "smartmodule")]
+pub mod state;
+
+pub use fluvio_protocol::record::{Offset, Record, RecordData, RecordHeaders};
 
-pub use crate::input::SMARTMODULE_TIMESTAMPS_VERSION;
+pub use crate::input::{SMARTMODULE_HEADERS_VERSION, SMARTMODULE_TIMESTAMPS_VERSION};
 
 /// remap to old data plane
 pub mod dataplane {
@@ -33,6 +37,7 @@ pub mod dataplane {
         pub use crate::input::*;
         pub use crate::output::*;
         pub use crate::error::*;
+        pub use crate::window::*;
         pub use crate::SmartModuleRecord;
     }
 
@@ -81,6 +86,10 @@ impl SmartModuleRecord {
     pub fn value(&self) -> &RecordData {
         self.inner_record.value()
     }
+
+    pub fn headers(&self) -> &RecordHeaders {
+        self.inner_record.headers()
+    }
 }
 
 impl Deref for SmartModuleRecord {
@@ -0,0 +1,138 @@
+//!
+//! # Keyed state
+//!
+//! State kept by the host between invocations of SmartModule, separately for each
+//! SmartModule in a chain. For consumers with an id, SPU persists state per partition,
+//! so it survives SPU restarts and consumer reconnects.
+//!
+//! ```ignore
+//! use fluvio_smartmodule::{smartmodule, Result, SmartModuleRecord, state};
+//!
+//! #[smartmodule(filter)]
+//! pub fn filter(record: &SmartModuleRecord) -> Result<bool> {
+//!     // pass only first record of each key
+//!     let key = record.key().map(|key| key.as_ref()).unwrap_or_default();
+//!     if state::get(key)?.is_some() {
+//!         return Ok(false);
+//!     }
+//!     state::put(key, b"")?;
+//!     Ok(true)
+//! }
+//! ```
+//!
+//! Outside of WASM, state is kept in a thread local map, so SmartModules can be unit tested.
+//!
+
+use crate::Result;
+
+/// value stored for key
+pub fn get(key: impl AsRef<[u8]>) -> Result<Option<Vec<u8>>> {
+    imp::get(key.as_ref())
+}
+
+/// store value for key, replacing previous one
+pub fn put(key: impl AsRef<[u8]>, value: impl AsRef<[u8]>) -> Result<()> {
+    imp::put(key.as_ref(), value.as_ref())
+}
+
+/// remove key
+pub fn delete(key: impl AsRef<[u8]>) -> Result<()> {
+    imp::delete(key.as_ref())
+}
+
+#[cfg(target_arch = "wasm32")]
+mod imp {
+    use crate::Result;
+
+    unsafe extern "C" {
+        fn state_get(key_ptr: i32, key_len: i32, out_ptr: i32) -> i32;
+        fn state_put(key_ptr: i32, key_len: i32, value_ptr: i32, value_len: i32) -> i32;
+        fn state_delete(key_ptr: i32, key_len: i32) -> i32;
+    }
+
+    pub(super) fn get(key: &[u8]) -> Result<Option<Vec<u8>>> {
+        // host allocates value with `alloc` and writes its pointer and length here
+        let mut out = [0i32; 2];
+        let status = unsafe {
+            state_get(
+                key.as_ptr() as i32,
+                key.len() as i32,
+                out.as_mut_ptr() as i32,
+            )
+        };
+        match status {
+            0 => Ok(None),
+            1 => {
+                let len = out[1] as usize;
+                Ok(Some(unsafe {
+                    Vec::from_raw_parts(out[0] as *mut u8, len, len)
+                }))
+            }
+            _ => Err(crate::eyre!("failed to read state")),
+        }
+    }
+
+    pub(super) fn put(key: &[u8], value: &[u8]) -> Result<()> {
+        let status = unsafe {
+            state_put(
+                key.as_ptr() as i32,
+                key.len() as i32,
+                value.as_ptr() as i32,
+                value.len() as i32,
+            )
+        };
+        if status < 0 {
+            return Err(crate::eyre!("failed to write state"));
+        }
+        Ok(())
+    }
+
+    pub(super) fn delete(key: &[u8]) -> Result<()> {
+        let status = unsafe { state_delete(key.as_ptr() as i32, key.len() as i32) };
+        if status < 0 {
+            return Err(crate::eyre!("failed to delete state"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod imp {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use crate::Result;
+
+    thread_local! {
+        static STATE: RefCell<HashMap<Vec<u8>, Vec<u8>>> = RefCell::new(HashMap::new());
+    }
+
+    pub(super) fn get(key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(STATE.with(|state| state.borrow().get(key).cloned()))
+    }
+
+    pub(super) fn put(key: &[u8], value: &[u8]) -> Result<()> {
+        STATE.with(|state| state.borrow_mut().insert(key.to_vec(), value.to_vec()));
+        Ok(())
+    }
+
+    pub(super) fn delete(key: &[u8]) -> Result<()> {
+        STATE.with(|state| state.borrow_mut().remove(key));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_local_state() {
+        assert_eq!(get("key").expect("get"), None);
+        put("key", "value").expect("put");
+        assert_eq!(get("key").expect("get"), Some(b"value".to_vec()));
+        delete("key").expect("delete");
+        assert_eq!(get("key").expect("get"), None);
+    }
+}
@@ -0,0 +1,209 @@
+use std::time::Duration;
+
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_protocol::types::Timestamp;
+
+/// Header with start of the window, in milliseconds since epoch, of a windowed aggregate result
+pub const WINDOW_START_HEADER: &str = "fluvio-window-start";
+/// Header with end (exclusive) of the window, in milliseconds since epoch, of a windowed aggregate result
+pub const WINDOW_END_HEADER: &str = "fluvio-window-end";
+
+/// Windowing of Aggregate SmartModule by record timestamp.
+///
+/// Records are grouped by key and window; accumulator of each group is emitted
+/// as a record when the window closes. Window closes once a record with timestamp
+/// past the end of window plus grace period is seen.
+/// Records arriving for already closed windows are dropped.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+pub struct Window {
+    pub kind: WindowKind,
+    /// how long window is kept open for late records after its end
+    pub grace: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+pub enum WindowKind {
+    /// fixed size, non overlapping windows
+    #[fluvio(tag = 0)]
+    Tumbling { size: Duration },
+    /// fixed size windows, starting every `advance`, record can belong to multiple windows
+    #[fluvio(tag = 1)]
+    Hopping { size: Duration, advance: Duration },
+    /// windows of activity, closed after no records are seen for `gap`
+    #[fluvio(tag = 2)]
+    Session { gap: Duration },
+}
+
+impl Default for WindowKind {
+    fn default() -> Self {
+        Self::Tumbling {
+            size: Duration::ZERO,
+        }
+    }
+}
+
+/// Time range of a window, in milliseconds since epoch. Start is inclusive and end is exclusive.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encoder, Decoder)]
+pub struct TimeWindow {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+impl TimeWindow {
+    pub fn new(start: Timestamp, end: Timestamp) -> Self {
+        Self { start, end }
+    }
+
+    pub fn contains(&self, timestamp: Timestamp) -> bool {
+        self.start <= timestamp && timestamp < self.end
+    }
+}
+
+impl Window {
+    pub fn tumbling(size: Duration) -> Self {
+        Self {
+            kind: WindowKind::Tumbling { size },
+            grace: Duration::ZERO,
+        }
+    }
+
+    pub fn hopping(size: Duration, advance: Duration) -> Self {
+        Self {
+            kind: WindowKind::Hopping { size, advance },
+            grace: Duration::ZERO,
+        }
+    }
+
+    pub fn session(gap: Duration) -> Self {
+        Self {
+            kind: WindowKind::Session { gap },
+            grace: Duration::ZERO,
+        }
+    }
+
+    pub fn with_grace(mut self, grace: Duration) -> Self {
+        self.grace = grace;
+        self
+    }
+
+    /// windows record with timestamp belongs to.
+    /// For session windows, this is the window of the record alone, before merging with
+    /// other sessions.
+    pub fn assign(&self, timestamp: Timestamp) -> Vec<TimeWindow> {
+        match self.kind {
+            WindowKind::Tumbling { size } => {
+                let size = millis(size);
+                let start = timestamp - timestamp.rem_euclid(size);
+                vec![TimeWindow::new(start, start + size)]
+            }
+            WindowKind::Hopping { size, advance } => {
+                let size = millis(size);
+                let advance = millis(advance);
+                let last_start = timestamp - timestamp.rem_euclid(advance);
+                let mut windows = Vec::new();
+                let mut start = last_start;
+                while start > timestamp - size {
+                    windows.push(TimeWindow::new(start, start + size));
+                    start -= advance;
+                }
+                windows.reverse();
+                windows
+            }
+            WindowKind::Session { gap } => {
+                vec![TimeWindow::new(timestamp, timestamp + millis(gap))]
+            }
+        }
+    }
+
+    /// whether window no longer accepts records, given the highest timestamp seen
+    pub fn is_closed(&self, window: &TimeWindow, watermark: Timestamp) -> bool {
+        watermark >= window.end.saturating_add(millis(self.grace))
+    }
+
+    /// check that sizes are positive, so windows can be assigned,
+    /// and that hopping windows don't leave gaps between them
+    pub fn validate(&self) -> Result<(), String> {
+        let positive = match self.kind {
+            WindowKind::Tumbling { size } => millis(size) > 0,
+            WindowKind::Hopping { size, advance } => millis(size) > 0 && millis(advance) > 0,
+            WindowKind::Session { gap } => millis(gap) > 0,
+        };
+        if !positive {
+            return Err(format!("window must be at least one millisecond: {self:?}"));
+        }
+        if matches!(self.kind, WindowKind::Hopping { size, advance } if millis(advance) > millis(size))
+        {
+            return Err(format!(
+                "hopping window advance must not exceed its size: {self:?}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn millis(duration: Duration) -> Timestamp {
+    Timestamp::try_from(duration.as_millis()).unwrap_or(Timestamp::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_tumbling_assign() {
+        let window = Window::tumbling(Duration::from_millis(10));
+        assert_eq!(window.assign(0), vec![TimeWindow::new(0, 10)]);
+        assert_eq!(window.assign(19), vec![TimeWindow::new(10, 20)]);
+        assert_eq!(window.assign(-1), vec![TimeWindow::new(-10, 0)]);
+    }
+
+    #[test]
+    fn test_hopping_assign() {
+        let window = Window::hopping(Duration::from_millis(10), Duration::from_millis(5));
+        assert_eq!(
+            window.assign(12),
+            vec![TimeWindow::new(5, 15), TimeWindow::new(10, 20)]
+        );
+        assert_eq!(
+            window.assign(10),
+            vec![TimeWindow::new(5, 15), TimeWindow::new(10, 20)]
+        );
+    }
+
+    #[test]
+    fn test_session_assign() {
+        let window = Window::session(Duration::from_millis(30));
+        assert_eq!(window.assign(100), vec![TimeWindow::new(100, 130)]);
+    }
+
+    #[test]
+    fn test_closed_after_grace() {
+        let window =
+            Window::tumbling(Duration::from_millis(10)).with_grace(Duration::from_millis(5));
+        let time_window = TimeWindow::new(0, 10);
+        assert!(!window.is_closed(&time_window, 14));
+        assert!(window.is_closed(&time_window, 15));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Window::tumbling(Duration::ZERO).validate().is_err());
+        assert!(
+            Window::hopping(Duration::from_secs(1), Duration::ZERO)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            Window::hopping(Duration::from_secs(1), Duration::from_secs(2))
+                .validate()
+                .is_err()
+        );
+        assert!(
+            Window::hopping(Duration::from_secs(2), Duration::from_secs(1))
+                .validate()
+                .is_ok()
+        );
+        assert!(Window::session(Duration::from_secs(1)).validate().is_ok());
+    }
+}
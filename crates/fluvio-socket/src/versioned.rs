@@ -1,6 +1,7 @@
 use std::default::Default;
 use std::fmt;
 use std::fmt::{Debug, Display};
+use std::io::{Error as IoError, ErrorKind};
 use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Duration;
@@ -11,11 +12,15 @@ use tracing::{debug, instrument, info};
 use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::api::Request;
 use fluvio_protocol::link::versions::{ApiVersions, ApiVersionsRequest, ApiVersionsResponse};
+use fluvio_future::future::timeout;
 use fluvio_future::net::{DomainConnector, DefaultDomainConnector};
 use fluvio_future::retry::retry_if;
 
 use crate::{SocketError, FluvioSocket, SharedMultiplexerSocket, AsyncResponse};
 
+/// max time to wait for connection to single endpoint before trying next one
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Frame with request and response
 pub trait SerialFrame: Display {
     /// client config
@@ -153,13 +158,47 @@ impl ClientConfig {
         self.addr = domain
     }
 
+    /// addresses to try in order, address may be comma separated list of endpoints
+    /// such as all instances of highly-available SC
+    pub fn addrs(&self) -> impl Iterator<Item = &str> {
+        self.addr
+            .split(',')
+            .map(str::trim)
+            .filter(|addr| !addr.is_empty())
+    }
+
     #[instrument(skip(self))]
     pub async fn connect(self) -> Result<VersionedSocket, SocketError> {
-        debug!(add = %self.addr, "try connection to");
-        let socket =
-            FluvioSocket::connect_with_connector(&self.addr, self.connector.as_ref()).await?;
-        info!(add = %self.addr, "connect to socket");
-        VersionedSocket::connect(socket, Arc::new(self)).await
+        let addrs: Vec<String> = self.addrs().map(|addr| addr.to_owned()).collect();
+        let mut last_err = None;
+        for addr in addrs {
+            debug!(add = %addr, "try connection to");
+            let connect = FluvioSocket::connect_with_connector(&addr, self.connector.as_ref());
+            let result = timeout(CONNECT_TIMEOUT, connect).await.unwrap_or_else(|_| {
+                Err(IoError::new(
+                    ErrorKind::TimedOut,
+                    format!("connection to {addr} timed out"),
+                )
+                .into())
+            });
+            match result {
+                Ok(socket) => {
+                    info!(add = %addr, "connect to socket");
+                    return VersionedSocket::connect(socket, Arc::new(self)).await;
+                }
+                Err(err) => {
+                    debug!(add = %addr, %err, "connection failed");
+                    last_err = Some(err);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            IoError::new(
+                ErrorKind::InvalidInput,
+                format!("no address in '{}'", self.addr),
+            )
+            .into()
+        }))
     }
 
     /// create new config with prefix add to domain, this is useful for SNI
@@ -398,6 +437,7 @@ mod test {
     use fluvio_protocol::link::versions::ApiVersionKey;
 
     use super::ApiVersionsResponse;
+    use super::ClientConfig;
     use super::Versions;
 
     #[derive(Encoder, Decoder, Default, Debug)]
@@ -438,4 +478,16 @@ mod test {
         assert_eq!(versions.lookup_version::<T1>(), Some(9));
         assert_eq!(versions.lookup_version::<T2>(), None);
     }
+
+    #[test]
+    fn test_client_config_addrs() {
+        let config = ClientConfig::with_addr("sc-0:9003, sc-1:9003,".to_owned());
+        assert_eq!(
+            config.addrs().collect::<Vec<_>>(),
+            vec!["sc-0:9003", "sc-1:9003"]
+        );
+
+        let config = ClientConfig::with_addr("localhost:9003".to_owned());
+        assert_eq!(config.addrs().collect::<Vec<_>>(), vec!["localhost:9003"]);
+    }
 }
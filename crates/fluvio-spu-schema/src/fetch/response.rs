@@ -7,6 +7,7 @@ use fluvio_protocol::derive::FluvioDefault;
 use fluvio_protocol::record::RecordSet;
 use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::Offset;
+use fluvio_protocol::record::BatchHeader;
 
 pub type DefaultFetchResponse = FetchResponse<RecordSet>;
 
@@ -78,6 +79,19 @@ pub struct FetchablePartitionResponse<R> {
     pub records: R,
 }
 
+impl<R> FetchablePartitionResponse<R> {
+    /// true if batch is not delivered to consumer: control batches and batches of aborted transactions
+    pub fn skip_batch(&self, header: &BatchHeader, base_offset: Offset) -> bool {
+        header.is_control()
+            || (header.is_transactional()
+                && self
+                    .aborted
+                    .iter()
+                    .flatten()
+                    .any(|txn| txn.contains(header.producer_id, base_offset)))
+    }
+}
+
 impl<R: BatchRecords> FetchablePartitionResponse<RecordSet<R>> {
     /// offset that will be use for fetching rest of offsets
     /// this will be 1 greater than last offset of previous query
@@ -95,6 +109,16 @@ impl<R: BatchRecords> FetchablePartitionResponse<RecordSet<R>> {
 pub struct AbortedTransaction {
     pub producer_id: i64,
     pub first_offset: i64,
+    /// offset of abort marker, records of producer up to this offset are aborted
+    #[fluvio(min_version = 27)]
+    pub last_offset: i64,
+}
+
+impl AbortedTransaction {
+    /// true if batch written by producer at offset belongs to this aborted transaction
+    pub fn contains(&self, producer_id: i64, offset: Offset) -> bool {
+        self.producer_id == producer_id && self.first_offset <= offset && offset <= self.last_offset
+    }
 }
 
 // -----------------------------------
@@ -378,6 +378,7 @@ mod tests {
                 kind: SmartModuleKind::Filter,
                 params,
                 name: Some(name.to_string()),
+                ..Default::default()
             }],
             data: std::marker::PhantomData,
         };
@@ -389,8 +390,8 @@ mod tests {
         let expected = vec![
             0x01, 0x00, 0x04, 0x74, 0x5f, 0x69, 0x64, 0xff, 0xff, 0x00, 0x00, 0x03, 0xe8, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x04, 0xde, 0xad,
-            0xbe, 0xef, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0a, 0x61, 0x64, 0x68, 0x6f, 0x63,
-            0x2d, 0x74, 0x65, 0x73, 0x74,
+            0xbe, 0xef, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0a, 0x61, 0x64, 0x68, 0x6f,
+            0x63, 0x2d, 0x74, 0x65, 0x73, 0x74, 0x00, 0x00,
         ];
         assert_eq!(dest, expected);
     }
@@ -401,8 +402,8 @@ mod tests {
         let bytes = vec![
             0x01, 0x00, 0x04, 0x74, 0x5f, 0x69, 0x64, 0xff, 0xff, 0x00, 0x00, 0x03, 0xe8, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x04, 0xde, 0xad,
-            0xbe, 0xef, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0a, 0x61, 0x64, 0x68, 0x6f, 0x63,
-            0x2d, 0x74, 0x65, 0x73, 0x74,
+            0xbe, 0xef, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0a, 0x61, 0x64, 0x68, 0x6f,
+            0x63, 0x2d, 0x74, 0x65, 0x73, 0x74, 0x00, 0x00,
         ];
         let mut value = DefaultProduceRequest::default();
         let version = DefaultProduceRequest::DEFAULT_API_VERSION;
@@ -449,6 +450,7 @@ mod tests {
                 kind: SmartModuleKind::Filter,
                 params,
                 name: Some(name.to_string()),
+                ..Default::default()
             }],
             data: std::marker::PhantomData,
         };
@@ -523,6 +525,7 @@ mod tests {
                 kind: SmartModuleKind::Filter,
                 params,
                 name: Some(name.to_string()),
+                ..Default::default()
             }],
             data: std::marker::PhantomData,
         };
@@ -583,7 +586,7 @@ mod tests {
             0x01, 0x00, 0x04, 0x74, 0x5f, 0x69, 0x64, 0xff, 0xff, 0x00, 0x00, 0x03, 0xe8, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x04, 0xde, 0xad,
             0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
-            0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
         ];
         let mut value = DefaultProduceRequest::default();
 
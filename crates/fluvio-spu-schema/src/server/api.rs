@@ -26,6 +26,9 @@ use super::consumer_offset::{
 };
 use super::update_offset::UpdateOffsetsRequest;
 use super::mirror::StartMirrorRequest;
+use super::producer::InitProducerIdRequest;
+use super::transaction::{AddTxnPartitionsRequest, EndTxnRequest};
+use super::consumer_group::{JoinGroupRequest, GroupHeartbeatRequest, LeaveGroupRequest};
 
 #[allow(clippy::large_enum_variant)]
 /// Request to Spu Server
@@ -41,6 +44,12 @@ pub enum SpuServerRequest {
     UpdateConsumerOffsetRequest(RequestMessage<UpdateConsumerOffsetRequest>),
     DeleteConsumerOffsetRequest(RequestMessage<DeleteConsumerOffsetRequest>),
     FetchConsumerOffsetsRequest(RequestMessage<FetchConsumerOffsetsRequest>),
+    InitProducerIdRequest(RequestMessage<InitProducerIdRequest>),
+    AddTxnPartitionsRequest(RequestMessage<AddTxnPartitionsRequest>),
+    EndTxnRequest(RequestMessage<EndTxnRequest>),
+    JoinGroupRequest(RequestMessage<JoinGroupRequest>),
+    GroupHeartbeatRequest(RequestMessage<GroupHeartbeatRequest>),
+    LeaveGroupRequest(RequestMessage<LeaveGroupRequest>),
     StartMirrorRequest(RequestMessage<StartMirrorRequest>),
 }
 
@@ -56,6 +65,12 @@ impl fmt::Display for SpuServerRequest {
             Self::UpdateConsumerOffsetRequest(_) => write!(f, "UpdateConsumerOffsetRequest"),
             Self::DeleteConsumerOffsetRequest(_) => write!(f, "DeleteConsumerOffsetRequest"),
             Self::FetchConsumerOffsetsRequest(_) => write!(f, "FetchConsumerOffsetsRequest"),
+            Self::InitProducerIdRequest(_) => write!(f, "InitProducerIdRequest"),
+            Self::AddTxnPartitionsRequest(_) => write!(f, "AddTxnPartitionsRequest"),
+            Self::EndTxnRequest(_) => write!(f, "EndTxnRequest"),
+            Self::JoinGroupRequest(_) => write!(f, "JoinGroupRequest"),
+            Self::GroupHeartbeatRequest(_) => write!(f, "GroupHeartbeatRequest"),
+            Self::LeaveGroupRequest(_) => write!(f, "LeaveGroupRequest"),
             Self::StartMirrorRequest(_) => write!(f, "StartMirrorRequest"),
         }
     }
@@ -98,6 +113,18 @@ impl ApiMessage for SpuServerRequest {
             SpuServerApiKey::FetchConsumerOffsets => {
                 api_decode!(Self, FetchConsumerOffsetsRequest, src, header)
             }
+            SpuServerApiKey::InitProducerId => {
+                api_decode!(Self, InitProducerIdRequest, src, header)
+            }
+            SpuServerApiKey::AddTxnPartitions => {
+                api_decode!(Self, AddTxnPartitionsRequest, src, header)
+            }
+            SpuServerApiKey::EndTxn => api_decode!(Self, EndTxnRequest, src, header),
+            SpuServerApiKey::JoinGroup => api_decode!(Self, JoinGroupRequest, src, header),
+            SpuServerApiKey::GroupHeartbeat => {
+                api_decode!(Self, GroupHeartbeatRequest, src, header)
+            }
+            SpuServerApiKey::LeaveGroup => api_decode!(Self, LeaveGroupRequest, src, header),
             SpuServerApiKey::StartMirror => api_decode!(Self, StartMirrorRequest, src, header),
         }
     }
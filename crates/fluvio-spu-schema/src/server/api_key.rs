@@ -23,6 +23,12 @@ pub enum SpuServerApiKey {
     UpdateConsumerOffset = 1006,
     DeleteConsumerOffset = 1007,
     FetchConsumerOffsets = 1008,
+    InitProducerId = 1009,
+    AddTxnPartitions = 1010,
+    EndTxn = 1011,
+    JoinGroup = 1012,
+    GroupHeartbeat = 1013,
+    LeaveGroup = 1014,
 
     StartMirror = 2000,
 }
@@ -0,0 +1,130 @@
+//!
+//! # Consumer groups
+//!
+//! Consumers join a named group on the group coordinator, which is the leader of the consumer offsets
+//! partition. Coordinator assigns partitions of the topic to members and rebalances when members join,
+//! leave or stop sending heartbeats, or when the number of partitions changes.
+//! Each rebalance starts a new generation, members learn about it from heartbeat responses.
+//!
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Serialize, Deserialize};
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_types::PartitionId;
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+/// first version with consumer group support
+pub const CONSUMER_GROUP_API: i16 = 28;
+
+/// How partitions of the topic are divided between members of the group
+#[derive(Debug, Encoder, Decoder, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[fluvio(encode_discriminant)]
+#[repr(u8)]
+#[derive(Default)]
+pub enum AssignmentStrategy {
+    /// each member gets a contiguous range of partitions
+    #[default]
+    Range = 0,
+    /// partitions are dealt to members one by one
+    RoundRobin = 1,
+    /// members keep their partitions across rebalances as long as the assignment stays balanced
+    Sticky = 2,
+}
+
+impl fmt::Display for AssignmentStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Range => write!(f, "range"),
+            Self::RoundRobin => write!(f, "round-robin"),
+            Self::Sticky => write!(f, "sticky"),
+        }
+    }
+}
+
+impl FromStr for AssignmentStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "range" => Ok(Self::Range),
+            "round-robin" | "roundrobin" => Ok(Self::RoundRobin),
+            "sticky" => Ok(Self::Sticky),
+            other => Err(format!("unknown assignment strategy: {other}")),
+        }
+    }
+}
+
+/// Join consumer group, creating group if it doesn't exist
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct JoinGroupRequest {
+    pub group: String,
+    /// empty for new member, otherwise id returned by previous join
+    pub member_id: String,
+    pub topic: String,
+    pub strategy: AssignmentStrategy,
+    /// member is removed from group if no heartbeat is received within timeout
+    pub session_timeout_ms: u32,
+}
+
+impl Request for JoinGroupRequest {
+    const API_KEY: u16 = SpuServerApiKey::JoinGroup as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = JoinGroupResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct JoinGroupResponse {
+    pub error_code: ErrorCode,
+    pub member_id: String,
+    pub generation: i32,
+    /// partitions assigned to member in this generation
+    pub partitions: Vec<PartitionId>,
+}
+
+/// Keep membership alive and learn about rebalances
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct GroupHeartbeatRequest {
+    pub group: String,
+    pub member_id: String,
+    /// generation known by member
+    pub generation: i32,
+}
+
+impl Request for GroupHeartbeatRequest {
+    const API_KEY: u16 = SpuServerApiKey::GroupHeartbeat as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = GroupHeartbeatResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct GroupHeartbeatResponse {
+    pub error_code: ErrorCode,
+    /// current generation of group, member must switch to partitions below if it differs from known one
+    pub generation: i32,
+    pub partitions: Vec<PartitionId>,
+}
+
+/// Leave consumer group, partitions of member are reassigned right away
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct LeaveGroupRequest {
+    pub group: String,
+    pub member_id: String,
+}
+
+impl Request for LeaveGroupRequest {
+    const API_KEY: u16 = SpuServerApiKey::LeaveGroup as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = LeaveGroupResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct LeaveGroupResponse {
+    pub error_code: ErrorCode,
+}
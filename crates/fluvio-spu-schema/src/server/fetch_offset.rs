@@ -15,6 +15,9 @@ use crate::COMMON_VERSION;
 use crate::errors::ErrorCode;
 use super::SpuServerApiKey;
 
+/// version for resolving timestamp into offset
+pub const TIMESTAMP_OFFSET_API: i16 = 26;
+
 // -----------------------------------
 // FlvFetchOffsetsRequest
 // -----------------------------------
@@ -40,11 +43,18 @@ impl Request for FetchOffsetsRequest {
 impl FetchOffsetsRequest {
     /// create request with a single topic and partition
     pub fn new(topic: String, partition: u32) -> Self {
+        Self::with_timestamp(topic, partition, None)
+    }
+
+    /// create request with a single topic and partition,
+    /// offset of first record at or after timestamp is also resolved if timestamp is set
+    pub fn with_timestamp(topic: String, partition: u32, timestamp: Option<i64>) -> Self {
         Self {
             topics: vec![FetchOffsetTopic {
                 name: topic,
                 partitions: vec![FetchOffsetPartition {
                     partition_index: partition,
+                    timestamp,
                 }],
             }],
             ..Default::default()
@@ -65,6 +75,10 @@ pub struct FetchOffsetTopic {
 pub struct FetchOffsetPartition {
     /// The partition index.
     pub partition_index: PartitionId,
+
+    /// Timestamp in milliseconds since epoch to resolve into offset
+    #[fluvio(min_version = 26)]
+    pub timestamp: Option<i64>,
 }
 
 // -----------------------------------
@@ -115,6 +129,11 @@ pub struct FetchOffsetPartitionResponse {
 
     /// Last readable offset
     pub last_stable_offset: i64,
+
+    /// Offset of first record at or after requested timestamp.
+    /// None if timestamp was not requested or all records are older.
+    #[fluvio(min_version = 26)]
+    pub timestamp_offset: Option<i64>,
 }
 
 impl fmt::Display for FetchOffsetPartitionResponse {
@@ -8,6 +8,9 @@ pub mod stream_fetch;
 pub mod update_offset;
 pub mod consumer_offset;
 pub mod mirror;
+pub mod producer;
+pub mod transaction;
+pub mod consumer_group;
 
 pub use self::api_key::*;
 
@@ -0,0 +1,28 @@
+//!
+//! # Init Producer Id
+//!
+//! Allocate producer id used by idempotent producer to stamp batches
+//!
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct InitProducerIdRequest;
+
+impl Request for InitProducerIdRequest {
+    const API_KEY: u16 = SpuServerApiKey::InitProducerId as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = InitProducerIdResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct InitProducerIdResponse {
+    pub error_code: ErrorCode,
+    /// unique producer id, only valid if there is no error
+    pub producer_id: i64,
+}
@@ -4,6 +4,7 @@ use std::fmt::{Debug, self};
 use std::io;
 use std::io::Error as IoError;
 use std::io::Read;
+use std::time::Duration;
 
 use bytes::BufMut;
 use flate2::{
@@ -12,12 +13,20 @@ use flate2::{
 };
 
 use fluvio_protocol::{Encoder, Decoder, Version};
-use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
+use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleErrorPolicy, SmartModuleExtraParams};
 
 // The fluvio COMMON_VERSION in fluvio-spu-schema/src/lib.rs
 // that introduced the smartmodule name to SmartModuleInvocations
 pub const COMMON_VERSION_HAS_SM_NAME: Version = 25;
 
+// The fluvio COMMON_VERSION in fluvio-spu-schema/src/lib.rs
+// that introduced the error policy to SmartModuleInvocations
+pub const COMMON_VERSION_HAS_SM_ERROR_POLICY: Version = 30;
+
+// The fluvio COMMON_VERSION in fluvio-spu-schema/src/lib.rs
+// that introduced the timeout to SmartModuleInvocations
+pub const COMMON_VERSION_HAS_SM_TIMEOUT: Version = 32;
+
 /// The request payload when using a Consumer SmartModule.
 ///
 /// This includes the WASM module name as well as the invocation being used.
@@ -30,6 +39,12 @@ pub struct SmartModuleInvocation {
     // only included in PROD_API_HAS_SM_NAME, or later
     // if decoding a version before this, None will be filled in
     pub name: Option<String>, // option for backward compatibility
+    /// only included in COMMON_VERSION_HAS_SM_ERROR_POLICY or later,
+    /// older SPUs always fail on error
+    pub error_policy: SmartModuleErrorPolicy,
+    /// limit of time of single invocation, SPU limit applies if shorter.
+    /// Only included in COMMON_VERSION_HAS_SM_TIMEOUT or later
+    pub timeout: Option<Duration>,
 }
 
 impl Decoder for SmartModuleInvocation {
@@ -45,6 +60,12 @@ impl Decoder for SmartModuleInvocation {
         } else {
             self.name.decode(src, version)?;
         }
+        if version >= COMMON_VERSION_HAS_SM_ERROR_POLICY {
+            self.error_policy.decode(src, version)?;
+        }
+        if version >= COMMON_VERSION_HAS_SM_TIMEOUT {
+            self.timeout.decode(src, version)?;
+        }
         Ok(())
     }
 }
@@ -57,6 +78,12 @@ impl Encoder for SmartModuleInvocation {
         if version >= COMMON_VERSION_HAS_SM_NAME {
             size += self.name.write_size(version);
         }
+        if version >= COMMON_VERSION_HAS_SM_ERROR_POLICY {
+            size += self.error_policy.write_size(version);
+        }
+        if version >= COMMON_VERSION_HAS_SM_TIMEOUT {
+            size += self.timeout.write_size(version);
+        }
         size
     }
 
@@ -70,6 +97,12 @@ impl Encoder for SmartModuleInvocation {
         if version >= COMMON_VERSION_HAS_SM_NAME {
             self.name.encode(dest, version)?;
         }
+        if version >= COMMON_VERSION_HAS_SM_ERROR_POLICY {
+            self.error_policy.encode(dest, version)?;
+        }
+        if version >= COMMON_VERSION_HAS_SM_TIMEOUT {
+            self.timeout.encode(dest, version)?;
+        }
         Ok(())
     }
 }
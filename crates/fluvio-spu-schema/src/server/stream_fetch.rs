@@ -13,7 +13,9 @@ use fluvio_protocol::record::RawRecords;
 use fluvio_protocol::{Encoder, Decoder};
 use fluvio_protocol::api::Request;
 use fluvio_protocol::record::RecordSet;
-use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
+use fluvio_smartmodule::dataplane::smartmodule::{
+    SmartModuleExtraParams, SmartModuleTransformRuntimeError,
+};
 use fluvio_types::{PartitionId, defaults::FLUVIO_CLIENT_MAX_FETCH_BYTES};
 
 use crate::COMMON_VERSION;
@@ -54,6 +56,12 @@ pub const SMARTMODULE_TIMESTAMP: i16 = 22;
 
 pub const OFFSET_MANAGEMENT_API: i16 = 23;
 
+/// first version with quota throttle time in stream response
+pub const STREAM_THROTTLE_API: i16 = 29;
+
+/// first version with key/value headers in records processed by SmartModules
+pub const RECORD_HEADERS_API: i16 = fluvio_protocol::record::RECORD_HEADERS_VERSION;
+
 /// Fetch records continuously
 /// Output will be send back as stream
 #[allow(deprecated)]
@@ -120,6 +128,13 @@ pub struct StreamFetchResponse<R> {
     pub topic: String,
     pub stream_id: u32,
     pub partition: FetchablePartitionResponse<R>,
+    /// The duration in milliseconds for which the stream is throttled due to a quota violation,
+    /// or zero if the stream did not violate any quota.
+    #[fluvio(min_version = 29)]
+    pub throttle_time_ms: i32,
+    /// Records dropped by SmartModules with dead-letter error policy, along with their errors
+    #[fluvio(min_version = 30)]
+    pub dead_letters: Vec<SmartModuleTransformRuntimeError>,
 }
 
 #[cfg(feature = "file")]
@@ -154,6 +169,12 @@ mod file {
             self.topic.encode(src, version)?;
             self.stream_id.encode(src, version)?;
             self.partition.file_encode(src, data, version)?;
+            if version >= 29 {
+                self.throttle_time_ms.encode(src, version)?;
+            }
+            if version >= 30 {
+                self.dead_letters.encode(src, version)?;
+            }
             Ok(())
         }
     }
@@ -164,7 +185,9 @@ mod tests {
 
     use fluvio_smartmodule::dataplane::smartmodule::Lookback;
 
-    use crate::server::smartmodule::{SmartModuleInvocationWasm, SmartModuleKind};
+    use crate::server::smartmodule::{
+        SmartModuleInvocationWasm, SmartModuleKind, COMMON_VERSION_HAS_SM_NAME,
+    };
 
     use super::*;
 
@@ -210,6 +233,7 @@ mod tests {
                     kind: SmartModuleKind::Filter,
                     params,
                     name: Some(name.to_string()),
+                    ..Default::default()
                 }),
             ],
             ..Default::default()
@@ -229,8 +253,8 @@ mod tests {
             0x00, 0x03, 0x6f, 0x6e, 0x65, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0, 10, 116, 101, 115, 116, 45, 97, 100, 104,
-            111, 99, 0,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x01, 0, 10, 116, 101, 115, 116, 45, 97, 100,
+            104, 111, 99, 0, 0, 0,
         ];
         assert_eq!(dest, expected);
     }
@@ -250,12 +274,13 @@ mod tests {
                     kind: SmartModuleKind::Filter,
                     params,
                     name: Some(name.to_string()),
+                    ..Default::default()
                 }),
             ],
             ..Default::default()
         };
         value
-            .encode(&mut dest, DefaultStreamFetchRequest::MAX_API_VERSION - 1)
+            .encode(&mut dest, COMMON_VERSION_HAS_SM_NAME - 1)
             .expect("should encode");
         let expected = vec![
             // Pre sm name encoding
@@ -307,7 +332,7 @@ mod tests {
             0x00, 0x03, 0x6f, 0x6e, 0x65, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
         ];
         let mut value = DefaultStreamFetchRequest::default();
         value
@@ -338,7 +363,7 @@ mod tests {
             0x00, 0x03, 0x6f, 0x6e, 0x65, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
         ];
         let mut value = DefaultStreamFetchRequest::default();
         value
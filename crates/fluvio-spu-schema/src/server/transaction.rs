@@ -0,0 +1,57 @@
+//!
+//! # Producer transactions
+//!
+//! Transactional producer registers each partition it writes to with transaction coordinator
+//! before sending data, then ends transaction with commit or abort.
+//! Coordinator is the SPU which allocated producer id.
+//!
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+/// first version with transaction support
+pub const TRANSACTION_API: i16 = 27;
+
+/// Register partitions as part of producer transaction, this starts transaction if there is none
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct AddTxnPartitionsRequest {
+    pub producer_id: i64,
+    pub partitions: Vec<ReplicaKey>,
+    /// transaction is aborted by coordinator if it is not ended within timeout
+    pub timeout_ms: u32,
+}
+
+impl Request for AddTxnPartitionsRequest {
+    const API_KEY: u16 = SpuServerApiKey::AddTxnPartitions as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = AddTxnPartitionsResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct AddTxnPartitionsResponse {
+    pub error_code: ErrorCode,
+}
+
+/// Commit or abort producer transaction.
+/// Coordinator writes transaction marker to every partition of transaction.
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct EndTxnRequest {
+    pub producer_id: i64,
+    pub commit: bool,
+}
+
+impl Request for EndTxnRequest {
+    const API_KEY: u16 = SpuServerApiKey::EndTxn as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = EndTxnResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct EndTxnResponse {
+    pub error_code: ErrorCode,
+}
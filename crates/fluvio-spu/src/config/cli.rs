@@ -5,6 +5,8 @@
 //! system parameters.
 //!
 use std::process;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::{anyhow, Result};
 use fluvio_future::openssl::SslVerifyMode;
@@ -16,6 +18,7 @@ use fluvio_types::print_cli_err;
 use fluvio_types::SpuId;
 use fluvio_future::openssl::TlsAcceptor;
 use fluvio_types::defaults::SPU_PEER_MAX_BYTES;
+use fluvio_auth::rbac::read_policy_file;
 
 use super::SpuConfig;
 
@@ -35,7 +38,7 @@ pub struct SpuOpt {
     /// Spu server for internal cluster communication
     pub bind_private: Option<String>,
 
-    /// Address of the SC Server
+    /// Address of the SC Server, comma separated list for highly-available SC
     #[arg(long, value_name = "host:port", env = "FLV_SC_PRIVATE_HOST")]
     pub sc_addr: Option<String>,
 
@@ -45,9 +48,33 @@ pub struct SpuOpt {
     #[arg(long, value_name = "log size", env = "FLV_LOG_SIZE")]
     pub log_size: Option<String>,
 
+    /// Offload closed segments to this directory, local copies are kept up to max partition size
+    #[arg(long, value_name = "dir", env = "FLV_TIERED_STORAGE_DIR")]
+    pub tiered_storage_dir: Option<String>,
+
     #[arg(long, value_name = "integer", env = "FLV_LOG_INDEX_MAX_BYTES")]
     pub index_max_bytes: Option<u32>,
 
+    /// Check crc of batches when segments are loaded
+    #[arg(long, env = "FLV_VERIFY_CRC_ON_LOAD")]
+    pub verify_crc_on_load: bool,
+
+    /// Check crc of batches before they are sent to consumers and followers
+    #[arg(long, env = "FLV_VERIFY_CRC_ON_READ")]
+    pub verify_crc_on_read: bool,
+
+    /// Seconds between background scrubs of closed segments, scrubbing is disabled if 0
+    #[arg(long, value_name = "integer", env = "FLV_SCRUB_INTERVAL_SECONDS")]
+    pub scrub_interval_seconds: Option<u32>,
+
+    /// Max bytes per second read by scrubber, unlimited if 0
+    #[arg(long, value_name = "integer", env = "FLV_SCRUB_MAX_BYTES_PER_SEC")]
+    pub scrub_max_bytes_per_sec: Option<u32>,
+
+    /// Follower which hasn't caught up with leader for this many milliseconds is out of sync
+    #[arg(long, value_name = "integer", env = "FLV_REPLICA_LAG_TIME_MAX_MS")]
+    pub replica_lag_time_max_ms: Option<u64>,
+
     #[arg(long, value_name = "integer", env = "FLV_LOG_INDEX_MAX_INTERVAL_BYTES")]
     pub index_max_interval_bytes: Option<u32>,
 
@@ -67,8 +94,32 @@ pub struct SpuOpt {
     )]
     pub smart_engine_max_memory: Option<usize>,
 
+    /// Max time in milliseconds a SmartModule can run on a single invocation
+    #[arg(long, value_name = "integer", env = "FLV_SMART_ENGINE_TIMEOUT_MS")]
+    pub smart_engine_timeout_ms: Option<u64>,
+
+    /// Serve OpenMetrics over HTTP on this address
+    #[arg(long, value_name = "host:port", env = "FLV_METRICS_ADDR")]
+    pub metrics_addr: Option<String>,
+
     #[clap(flatten)]
     tls: TlsConfig,
+
+    /// scopes assigned to principals of client certificates
+    #[arg(
+        long = "authorization-scopes",
+        value_name = "authorization scopes path",
+        env
+    )]
+    pub x509_auth_scopes: Option<PathBuf>,
+
+    /// rbac policy enforced on produce and consume
+    #[arg(
+        long = "authorization-policy",
+        value_name = "authorization policy path",
+        env
+    )]
+    pub auth_policy: Option<PathBuf>,
 }
 
 impl SpuOpt {
@@ -82,8 +133,6 @@ impl SpuOpt {
 
     #[allow(clippy::wrong_self_convention)]
     fn as_spu_config(self) -> Result<(SpuConfig, Option<String>)> {
-        use std::path::PathBuf;
-
         let mut config = SpuConfig {
             id: match self.id {
                 Some(id) => id,
@@ -105,6 +154,11 @@ impl SpuOpt {
             config.log.base_dir = PathBuf::from(log_base);
         }
 
+        if let Some(tiered_storage_dir) = self.tiered_storage_dir {
+            info!("using tiered storage at: {}", tiered_storage_dir);
+            config.log.tiered_storage_dir = Some(PathBuf::from(tiered_storage_dir));
+        }
+
         if let Some(log_size) = self.log_size {
             info!("overriding log size {}", log_size);
             config.log.size = log_size;
@@ -120,6 +174,33 @@ impl SpuOpt {
             config.log.index_max_interval_bytes = index_max_interval_bytes;
         }
 
+        config.log.verify_crc_on_load = self.verify_crc_on_load;
+        config.log.verify_crc_on_read = self.verify_crc_on_read;
+
+        if let Some(scrub_interval_seconds) = self.scrub_interval_seconds {
+            info!(
+                "scrubbing replicas every {} seconds",
+                scrub_interval_seconds
+            );
+            config.log.scrub_interval_seconds = scrub_interval_seconds;
+        }
+
+        if let Some(scrub_max_bytes_per_sec) = self.scrub_max_bytes_per_sec {
+            info!(
+                "overriding scrub max bytes per sec: {}",
+                scrub_max_bytes_per_sec
+            );
+            config.log.scrub_max_bytes_per_sec = scrub_max_bytes_per_sec;
+        }
+
+        if let Some(replica_lag_time_max_ms) = self.replica_lag_time_max_ms {
+            info!(
+                "overriding replica lag time max ms: {}",
+                replica_lag_time_max_ms
+            );
+            config.replication.replica_lag_time_max_ms = replica_lag_time_max_ms;
+        }
+
         if let Some(public_addr) = self.bind_public {
             info!("overriding public addr: {}", public_addr);
             config.public_endpoint = public_addr;
@@ -152,6 +233,27 @@ impl SpuOpt {
             config.smart_engine.store_max_memory = smart_engine_max_memory;
         }
 
+        if let Some(smart_engine_timeout_ms) = self.smart_engine_timeout_ms {
+            info!(
+                "overriding smart engine timeout: {} ms",
+                smart_engine_timeout_ms
+            );
+            config.smart_engine.timeout = Some(Duration::from_millis(smart_engine_timeout_ms));
+        }
+
+        if let Some(policy_path) = self.auth_policy {
+            // fail early on invalid policy
+            read_policy_file(&policy_path)?;
+            info!(?policy_path, "using rbac authorization");
+            config.auth_policy = Some(policy_path);
+        }
+        config.x509_auth_scopes = self.x509_auth_scopes;
+
+        if let Some(metrics_addr) = self.metrics_addr {
+            info!("serving metrics on: {}", metrics_addr);
+            config.metrics_addr = Some(metrics_addr);
+        }
+
         Ok((config, tls_port))
     }
 
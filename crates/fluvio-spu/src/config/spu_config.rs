@@ -10,6 +10,7 @@
 
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 
 // defaults values
 use fluvio_types::defaults::SPU_PUBLIC_PORT;
@@ -26,23 +27,28 @@ use fluvio_types::defaults::SPU_SMARTENGINE_STORE_MAX_BYTES;
 // environment variables
 
 use fluvio_types::defaults::SPU_MIN_IN_SYNC_REPLICAS;
+use fluvio_types::defaults::SPU_REPLICA_LAG_TIME_MAX_MS;
 use fluvio_types::defaults::FLV_LOG_BASE_DIR;
 use fluvio_types::defaults::FLV_LOG_SIZE;
 use fluvio_types::SpuId;
-use fluvio_storage::config::ReplicaConfig;
+use fluvio_storage::config::{ReplicaConfig, RemoteStoreConfig};
 use fluvio_types::defaults::{
     STORAGE_FLUSH_IDLE_MSEC, STORAGE_FLUSH_WRITE_COUNT, STORAGE_MAX_BATCH_SIZE,
+    STORAGE_SCRUB_MAX_BYTES_PER_SEC,
 };
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct ReplicationConfig {
     pub min_in_sync_replicas: u16,
+    /// follower which hasn't caught up with leader for this long is out of sync
+    pub replica_lag_time_max_ms: u64,
 }
 
 impl Default for ReplicationConfig {
     fn default() -> Self {
         Self {
             min_in_sync_replicas: SPU_MIN_IN_SYNC_REPLICAS,
+            replica_lag_time_max_ms: SPU_REPLICA_LAG_TIME_MAX_MS,
         }
     }
 }
@@ -57,6 +63,15 @@ pub struct Log {
     pub flush_write_count: u32,
     pub flush_idle_msec: u32,
     pub max_batch_size: u32,
+    /// directory where closed segments are offloaded, tiered storage is disabled if not set
+    pub tiered_storage_dir: Option<PathBuf>,
+    /// check crc of batches when segments are loaded
+    pub verify_crc_on_load: bool,
+    /// check crc of batches before they are sent to consumers and followers
+    pub verify_crc_on_read: bool,
+    /// seconds between background scrubs of closed segments, disabled if 0
+    pub scrub_interval_seconds: u32,
+    pub scrub_max_bytes_per_sec: u32,
 }
 
 impl Default for Log {
@@ -72,6 +87,11 @@ impl Default for Log {
             flush_write_count: STORAGE_FLUSH_WRITE_COUNT,
             flush_idle_msec: STORAGE_FLUSH_IDLE_MSEC,
             max_batch_size: STORAGE_MAX_BATCH_SIZE,
+            tiered_storage_dir: None,
+            verify_crc_on_load: false,
+            verify_crc_on_read: false,
+            scrub_interval_seconds: 0,
+            scrub_max_bytes_per_sec: STORAGE_SCRUB_MAX_BYTES_PER_SEC,
         }
     }
 }
@@ -79,12 +99,15 @@ impl Default for Log {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct SmartEngineConfig {
     pub store_max_memory: usize,
+    /// max time single SmartModule invocation can run, caps timeout requested by clients
+    pub timeout: Option<Duration>,
 }
 
 impl Default for SmartEngineConfig {
     fn default() -> Self {
         Self {
             store_max_memory: SPU_SMARTENGINE_STORE_MAX_BYTES,
+            timeout: None,
         }
     }
 }
@@ -111,6 +134,13 @@ pub struct SpuConfig {
     pub peer_max_bytes: u32,
 
     pub smart_engine: SmartEngineConfig,
+
+    /// scopes of X.509 identities, authenticated by TLS proxy
+    pub x509_auth_scopes: Option<PathBuf>,
+    /// rbac policy file, reloaded on change
+    pub auth_policy: Option<PathBuf>,
+    /// http address of OpenMetrics endpoint
+    pub metrics_addr: Option<String>,
 }
 
 impl Default for SpuConfig {
@@ -126,6 +156,9 @@ impl Default for SpuConfig {
             log: Log::default(),
             peer_max_bytes: fluvio_storage::FileReplica::PREFER_MAX_LEN,
             smart_engine: SmartEngineConfig::default(),
+            x509_auth_scopes: None,
+            auth_policy: None,
+            metrics_addr: None,
         }
     }
 }
@@ -140,8 +173,12 @@ impl SpuConfig {
         &self.rack
     }
 
-    pub fn sc_endpoint(&self) -> &str {
-        &self.sc_endpoint
+    /// SC endpoints in order of preference, highly-available SC has one per instance
+    pub fn sc_endpoints(&self) -> impl Iterator<Item = &str> {
+        self.sc_endpoint
+            .split(',')
+            .map(str::trim)
+            .filter(|endpoint| !endpoint.is_empty())
     }
 
     pub fn public_socket_addr(&self) -> &str {
@@ -174,6 +211,16 @@ impl From<&SpuConfig> for ReplicaConfig {
             .flush_write_count(log.flush_write_count)
             .flush_idle_msec(log.flush_idle_msec)
             .max_batch_size(log.max_batch_size)
+            .verify_crc_on_load(log.verify_crc_on_load)
+            .verify_crc_on_read(log.verify_crc_on_read)
+            .scrub_interval_seconds(log.scrub_interval_seconds)
+            .scrub_max_bytes_per_sec(log.scrub_max_bytes_per_sec)
+            .remote_store(log.tiered_storage_dir.as_ref().map(|dir| {
+                // replicas of other SPUs have different segments, so each SPU has own prefix
+                RemoteStoreConfig::LocalFs {
+                    path: dir.join(format!("spu-{}", config.id)),
+                }
+            }))
             .build()
     }
 }
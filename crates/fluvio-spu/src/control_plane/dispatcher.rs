@@ -14,6 +14,7 @@ use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
 use fluvio_controlplane::spu_api::update_smartmodule::UpdateSmartModuleRequest;
 use fluvio_controlplane::spu_api::update_spu::UpdateSpuRequest;
 use flv_util::print_cli_err;
+use fluvio_future::future::timeout;
 use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
 use fluvio_protocol::api::RequestMessage;
@@ -22,6 +23,9 @@ use fluvio_socket::{FluvioSocket, FluvioSink};
 use fluvio_storage::FileReplica;
 use fluvio_controlplane::sc_api::update_mirror::UpdateMirrorStatRequest;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
+use fluvio_controlplane::spu_api::update_schema::UpdateSchemaRequest;
+use fluvio_controlplane::spu_api::update_policy::UpdatePolicyRequest;
+use fluvio_controlplane::spu_api::update_quota::UpdateQuotaRequest;
 use fluvio_controlplane::sc_api::update_partition::UpdatePartitionStatRequest;
 
 use crate::core::SharedGlobalContext;
@@ -29,6 +33,9 @@ use crate::core::SharedGlobalContext;
 use super::message_sink::SharedLrsStatusUpdate;
 use super::{SharedMirrorStatusUpdate, SharedPartitionStatusUpdate};
 
+/// max time to wait for connection to single SC endpoint before trying next one
+const SC_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
 // keep track of various internal state of dispatcher
 #[derive(Default)]
 struct DispatcherCounter {
@@ -37,6 +44,9 @@ struct DispatcherCounter {
     pub reconnect: u64,       // number of reconnect to sc
     pub smartmodule: u64,     // number of sm updates from sc
     pub mirror: u64,          // number of mirror updates from sc
+    pub schema: u64,          // number of schema updates from sc
+    pub policy: u64,          // number of policy updates from sc
+    pub quota: u64,           // number of quota updates from sc
 }
 
 /// Controller for handling connection to SC
@@ -174,6 +184,18 @@ impl ScDispatcher<FileReplica> {
                                 break;
                             }
                         },
+                        Some(Ok(InternalSpuRequest::UpdateSchemaRequest(request))) => {
+                            self.counter.schema += 1;
+                            self.handle_update_schema_request(request);
+                        },
+                        Some(Ok(InternalSpuRequest::UpdatePolicyRequest(request))) => {
+                            self.counter.policy += 1;
+                            self.handle_update_policy_request(request);
+                        },
+                        Some(Ok(InternalSpuRequest::UpdateQuotaRequest(request))) => {
+                            self.counter.quota += 1;
+                            self.handle_update_quota_request(request);
+                        },
                         Some(Err(err)) => {
                             error!(%err, "Api error");
                             break;
@@ -296,28 +318,39 @@ impl ScDispatcher<FileReplica> {
     /// or if we received termination message
     async fn create_socket_to_sc(&mut self) -> FluvioSocket {
         let spu_id = self.ctx.local_spu_id();
-        let sc_endpoint = self.ctx.config().sc_endpoint().to_string();
+        let sc_endpoints: Vec<String> = self
+            .ctx
+            .config()
+            .sc_endpoints()
+            .map(|endpoint| endpoint.to_string())
+            .collect();
 
         let wait_interval = self.ctx.config().sc_retry_ms;
         loop {
-            info!(
-                %sc_endpoint,
-                spu_id,
-                "trying to create socket to sc",
-
-            );
-            match FluvioSocket::connect(&sc_endpoint).await {
-                Ok(socket) => {
-                    info!(spu_id, "connected to sc for spu");
-                    self.counter.reconnect += 1;
-                    return socket;
-                }
-                Err(err) => {
-                    warn!("error connecting to sc: {}", err);
-                    info!(wait_interval, spu_id, "sleeping ms");
-                    sleep(Duration::from_millis(wait_interval as u64)).await;
+            // only active SC accepts connections, try each of them
+            for sc_endpoint in &sc_endpoints {
+                info!(
+                    %sc_endpoint,
+                    spu_id,
+                    "trying to create socket to sc",
+
+                );
+                match timeout(SC_CONNECT_TIMEOUT, FluvioSocket::connect(sc_endpoint)).await {
+                    Ok(Ok(socket)) => {
+                        info!(%sc_endpoint, spu_id, "connected to sc for spu");
+                        self.counter.reconnect += 1;
+                        return socket;
+                    }
+                    Ok(Err(err)) => {
+                        warn!(%sc_endpoint, "error connecting to sc: {}", err);
+                    }
+                    Err(_) => {
+                        warn!(%sc_endpoint, "connecting to sc timed out");
+                    }
                 }
             }
+            info!(wait_interval, spu_id, "sleeping ms");
+            sleep(Duration::from_millis(wait_interval as u64)).await;
         }
     }
 
@@ -462,4 +495,96 @@ impl ScDispatcher<FileReplica> {
 
         Ok(())
     }
+
+    ///
+    /// Handle schema update sent by SC
+    ///
+    #[instrument(skip(self, req_msg), name = "update_schema_request")]
+    fn handle_update_schema_request(&mut self, req_msg: RequestMessage<UpdateSchemaRequest>) {
+        let (_, request) = req_msg.get_header_request();
+
+        let actions = if !request.all.is_empty() {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.all.len(),
+                "received schema sync all"
+            );
+            self.ctx.schemas_localstore().sync_all(request.all)
+        } else {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.changes.len(),
+                "received schema changes"
+            );
+            self.ctx.schemas_localstore().apply_changes(request.changes)
+        };
+
+        debug!(actions = actions.count(), "finished schema update");
+    }
+
+    ///
+    /// Handle policy update sent by SC
+    ///
+    #[instrument(skip(self, req_msg), name = "update_policy_request")]
+    fn handle_update_policy_request(&mut self, req_msg: RequestMessage<UpdatePolicyRequest>) {
+        let (_, request) = req_msg.get_header_request();
+
+        let actions = if !request.all.is_empty() {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.all.len(),
+                "received policy sync all"
+            );
+            self.ctx.policies_localstore().sync_all(request.all)
+        } else {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.changes.len(),
+                "received policy changes"
+            );
+            self.ctx
+                .policies_localstore()
+                .apply_changes(request.changes)
+        };
+
+        debug!(actions = actions.count(), "finished policy update");
+
+        self.ctx.rbac_policy().set_managed_policies(
+            self.ctx
+                .policies_localstore()
+                .all_values()
+                .into_iter()
+                .map(|policy| policy.spec),
+        );
+    }
+
+    ///
+    /// Handle quota update sent by SC
+    ///
+    #[instrument(skip(self, req_msg), name = "update_quota_request")]
+    fn handle_update_quota_request(&mut self, req_msg: RequestMessage<UpdateQuotaRequest>) {
+        let (_, request) = req_msg.get_header_request();
+
+        let actions = if !request.all.is_empty() {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.all.len(),
+                "received quota sync all"
+            );
+            self.ctx.quotas_localstore().sync_all(request.all)
+        } else {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.changes.len(),
+                "received quota changes"
+            );
+            self.ctx.quotas_localstore().apply_changes(request.changes)
+        };
+
+        debug!(actions = actions.count(), "finished quota update");
+
+        self.ctx
+            .quota_manager()
+            .set_quotas(self.ctx.quotas_localstore().all_values());
+    }
 }
@@ -10,6 +10,7 @@ use tracing::{debug, error, instrument};
 
 use fluvio_types::SpuId;
 use fluvio_storage::ReplicaStorage;
+use fluvio_auth::rbac::RbacPolicy;
 
 use crate::config::SpuConfig;
 use crate::control_plane::SharedMirrorStatusUpdate;
@@ -21,14 +22,24 @@ use crate::replication::follower::FollowersState;
 use crate::replication::follower::SharedFollowersState;
 use crate::replication::leader::{
     SharedReplicaLeadersState, ReplicaLeadersState, FollowerNotifier, SharedSpuUpdates,
+    ProducerIdGenerator,
 };
 use crate::control_plane::{StatusLrsMessageSink, SharedLrsStatusUpdate};
 use crate::core::metrics::SpuMetrics;
 use crate::smartengine::SmartEngine;
+use crate::transaction::TransactionCoordinator;
+use crate::group::GroupCoordinator;
 
 use super::leader_client::LeaderConnections;
 use super::mirror::MirrorLocalStore;
 use super::mirror::SharedMirrorLocalStore;
+use super::schema::SchemaLocalStore;
+use super::schema::SharedSchemaLocalStore;
+use super::policy::PolicyLocalStore;
+use super::policy::SharedPolicyLocalStore;
+use super::quota::QuotaLocalStore;
+use super::quota::SharedQuotaLocalStore;
+use super::quota::QuotaManager;
 use super::smartmodule::SmartModuleLocalStore;
 use super::spus::SharedSpuLocalStore;
 use super::SharedReplicaLocalStore;
@@ -54,8 +65,16 @@ pub struct GlobalContext<S> {
     sm_engine: SmartEngine,
     leaders: Arc<LeaderConnections>,
     mirrors: SharedMirrorLocalStore,
+    schemas: SharedSchemaLocalStore,
+    policies: SharedPolicyLocalStore,
+    rbac_policy: Arc<RbacPolicy>,
+    quotas: SharedQuotaLocalStore,
+    quota_manager: Arc<QuotaManager>,
     metrics: Arc<SpuMetrics>,
     consumer_offset: SharedConsumerOffsetStorages,
+    producer_ids: ProducerIdGenerator,
+    transactions: TransactionCoordinator,
+    groups: GroupCoordinator,
 }
 
 // -----------------------------------
@@ -74,6 +93,15 @@ where
         let spus = SpuLocalStore::new_shared();
         let replicas = ReplicaStore::new_shared();
         let metrics = Arc::new(SpuMetrics::new());
+        let producer_ids = ProducerIdGenerator::new(spu_config.id);
+        let transactions = TransactionCoordinator::new(
+            spu_config
+                .log
+                .base_dir
+                .join(format!("spu-transactions-{}", spu_config.id)),
+        );
+
+        let groups = GroupCoordinator::new(spu_config.id);
 
         GlobalContext {
             spu_localstore: spus.clone(),
@@ -89,8 +117,16 @@ where
             sm_engine: SmartEngine::new(),
             leaders: LeaderConnections::shared(spus, replicas),
             mirrors: MirrorLocalStore::new_shared(),
+            schemas: SchemaLocalStore::new_shared(),
+            policies: PolicyLocalStore::new_shared(),
+            rbac_policy: Arc::new(RbacPolicy::default()),
+            quotas: QuotaLocalStore::new_shared(),
+            quota_manager: Arc::new(QuotaManager::default()),
             metrics,
             consumer_offset: SharedConsumerOffsetStorages::default(),
+            producer_ids,
+            transactions,
+            groups,
         }
     }
 
@@ -123,6 +159,28 @@ where
         self.mirrors.clone()
     }
 
+    pub fn schemas_localstore(&self) -> &SchemaLocalStore {
+        &self.schemas
+    }
+
+    pub fn policies_localstore(&self) -> &PolicyLocalStore {
+        &self.policies
+    }
+
+    /// policy used by rbac authorization, policies managed by SC are merged into it
+    pub fn rbac_policy(&self) -> &Arc<RbacPolicy> {
+        &self.rbac_policy
+    }
+
+    pub fn quotas_localstore(&self) -> &QuotaLocalStore {
+        &self.quotas
+    }
+
+    /// measures client traffic against quotas in local store
+    pub fn quota_manager(&self) -> &Arc<QuotaManager> {
+        &self.quota_manager
+    }
+
     pub fn leaders_state(&self) -> &ReplicaLeadersState<S> {
         &self.leaders_state
     }
@@ -197,6 +255,18 @@ where
     pub(crate) fn consumer_offset(&self) -> &SharedConsumerOffsetStorages {
         &self.consumer_offset
     }
+
+    pub(crate) fn producer_ids(&self) -> &ProducerIdGenerator {
+        &self.producer_ids
+    }
+
+    pub(crate) fn transactions(&self) -> &TransactionCoordinator {
+        &self.transactions
+    }
+
+    pub(crate) fn groups(&self) -> &GroupCoordinator {
+        &self.groups
+    }
 }
 
 mod file_replica {
@@ -361,10 +431,44 @@ mod file_replica {
                                     }
                                 }
                             } else if new_replica.leader == local_id {
-                                if self.leaders_state().get(&new_replica.id).await.is_some() {
+                                if let Some(leader) =
+                                    self.leaders_state().get(&new_replica.id).await
+                                {
+                                    if new_replica.log_start_offset != old_replica.log_start_offset
+                                    {
+                                        if let Err(err) = leader
+                                            .delete_records(new_replica.log_start_offset)
+                                            .await
+                                        {
+                                            outputs.push(ReplicaChange::StorageError(err));
+                                        }
+                                    }
+                                    if new_replica.replicas != old_replica.replicas
+                                        || new_replica.reassignment != old_replica.reassignment
+                                    {
+                                        let leader = leader.update_replicas(new_replica).await;
+                                        leader.update_status().await;
+                                        self.leaders_state()
+                                            .insert(leader.id().clone(), leader)
+                                            .await;
+                                    }
                                 } else {
                                     error!("leader controller was not found: {}", new_replica.id);
                                 }
+                            } else if !new_replica.replicas.contains(&local_id) {
+                                // dropped by reassignment, partition still exists on other spus
+                                if old_replica.replicas.contains(&local_id) {
+                                    self.remove_follower_replica(new_replica).await;
+                                }
+                            } else if !old_replica.replicas.contains(&local_id) {
+                                // added by reassignment
+                                if let Err(err) = self
+                                    .followers_state_owned()
+                                    .add_replica(self, new_replica)
+                                    .await
+                                {
+                                    outputs.push(ReplicaChange::StorageError(err));
+                                }
                             } else {
                                 self.followers_state().update_replica(new_replica).await;
                             }
@@ -384,6 +488,7 @@ mod file_replica {
             } else {
                 self.remove_follower_replica(replica.clone()).await;
             }
+            self.metrics().remove_partition(&replica.id);
 
             if let Err(err) = self.delete_consumers_offset(&replica).await {
                 error!("error: {} deleting consumers offset: {}", err, replica);
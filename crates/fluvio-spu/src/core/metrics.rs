@@ -2,11 +2,12 @@ use std::{
     collections::HashMap,
     sync::{
         atomic::{AtomicU64, Ordering},
-        RwLock,
+        Arc, RwLock,
     },
     ops::AddAssign,
 };
 
+use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_protocol::record::Batch;
 #[cfg(feature = "smartengine")]
 use fluvio_smartengine::metrics::SmartModuleChainMetrics;
@@ -22,6 +23,10 @@ pub(crate) struct SpuMetrics {
     outbound: Activity,
     #[serde(skip)] // Skip serializing the RwLock wrapper
     smartmodule_metrics: RwLock<HashMap<String, SmartModuleChainMetrics>>,
+    #[serde(skip)]
+    partitions: RwLock<HashMap<ReplicaKey, Arc<PartitionActivity>>>,
+    #[serde(skip)]
+    connections: AtomicU64,
 }
 
 impl SpuMetrics {
@@ -30,6 +35,8 @@ impl SpuMetrics {
             inbound: Activity::default(),
             outbound: Activity::default(),
             smartmodule_metrics: RwLock::new(HashMap::new()),
+            partitions: RwLock::new(HashMap::new()),
+            connections: AtomicU64::new(0),
         }
     }
 
@@ -46,6 +53,44 @@ impl SpuMetrics {
         self.smartmodule_metrics.read().unwrap().clone()
     }
 
+    /// activity of partition, created on first use
+    pub fn partition(&self, replica: &ReplicaKey) -> Arc<PartitionActivity> {
+        if let Some(activity) = self.partitions.read().unwrap().get(replica) {
+            return activity.clone();
+        }
+        self.partitions
+            .write()
+            .unwrap()
+            .entry(replica.clone())
+            .or_default()
+            .clone()
+    }
+
+    /// forget activity of partition whose replica is removed from this SPU
+    pub(crate) fn remove_partition(&self, replica: &ReplicaKey) {
+        self.partitions.write().unwrap().remove(replica);
+    }
+
+    pub fn partitions(&self) -> Vec<(ReplicaKey, Arc<PartitionActivity>)> {
+        self.partitions
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(replica, activity)| (replica.clone(), activity.clone()))
+            .collect()
+    }
+
+    /// count connection as open until guard is dropped
+    pub fn track_connection(self: &Arc<Self>) -> ConnectionGuard {
+        self.connections.fetch_add(1, Ordering::SeqCst);
+        ConnectionGuard(self.clone())
+    }
+
+    /// number of open public connections
+    pub fn connections(&self) -> u64 {
+        self.connections.load(Ordering::SeqCst)
+    }
+
     pub fn update_smartmodule_metrics(
         &self,
         smartmodule_name: &str,
@@ -69,6 +114,14 @@ impl SpuMetrics {
     }
 }
 
+pub(crate) struct ConnectionGuard(Arc<SpuMetrics>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.connections.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 #[derive(Default, Debug, Serialize)]
 pub(crate) struct Record {
     records: AtomicU64,
@@ -80,6 +133,31 @@ impl Record {
         self.records.fetch_add(records, Ordering::SeqCst);
         self.bytes.fetch_add(bytes, Ordering::SeqCst);
     }
+
+    pub(crate) fn records(&self) -> u64 {
+        self.records.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::SeqCst)
+    }
+}
+
+/// Records produced to and consumed from single partition
+#[derive(Default, Debug)]
+pub(crate) struct PartitionActivity {
+    produced: Record,
+    consumed: Record,
+}
+
+impl PartitionActivity {
+    pub(crate) fn produced(&self) -> &Record {
+        &self.produced
+    }
+
+    pub(crate) fn consumed(&self) -> &Record {
+        &self.consumed
+    }
 }
 
 #[derive(Default, Debug, Serialize)]
@@ -109,19 +187,18 @@ impl Activity {
     }
 }
 
-#[cfg(test)]
 impl Activity {
     pub fn connector_records(&self) -> u64 {
-        self.connector.records.load(Ordering::SeqCst)
+        self.connector.records()
     }
     pub fn connector_bytes(&self) -> u64 {
-        self.connector.bytes.load(Ordering::SeqCst)
+        self.connector.bytes()
     }
     pub fn client_records(&self) -> u64 {
-        self.client.records.load(Ordering::SeqCst)
+        self.client.records()
     }
     pub fn client_bytes(&self) -> u64 {
-        self.client.bytes.load(Ordering::SeqCst)
+        self.client.bytes()
     }
 }
 
@@ -129,6 +206,14 @@ impl IncreaseValue {
     pub(crate) fn new(records: u64, bytes: u64) -> Self {
         Self { records, bytes }
     }
+
+    pub(crate) fn records(&self) -> u64 {
+        self.records
+    }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes
+    }
 }
 
 // Measuring of serialized data. `bytes` is length of file slice, `records` is an offset's change
@@ -205,4 +290,15 @@ mod tests {
         assert_eq!(activity.connector.records.load(Ordering::SeqCst), 1);
         assert_eq!(activity.connector.bytes.load(Ordering::SeqCst), 123);
     }
+
+    #[test]
+    fn test_remove_partition() {
+        let metrics = SpuMetrics::new();
+        let replica = ReplicaKey::new("topic", 0u32);
+        metrics.partition(&replica).produced().increase(1, 10);
+        assert_eq!(metrics.partitions().len(), 1);
+
+        metrics.remove_partition(&replica);
+        assert!(metrics.partitions().is_empty());
+    }
 }
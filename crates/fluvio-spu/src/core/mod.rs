@@ -7,6 +7,9 @@ pub mod replica;
 pub mod smartmodule;
 pub mod metrics;
 pub mod mirror;
+pub mod schema;
+pub mod policy;
+pub mod quota;
 
 pub use self::global_context::{GlobalContext, ReplicaChange};
 pub use self::store::Spec;
@@ -0,0 +1,23 @@
+use fluvio_controlplane::spu_api::update_policy::Policy;
+use std::sync::Arc;
+
+use crate::core::Spec;
+use crate::core::LocalStore;
+
+pub type PolicyLocalStore = LocalStore<Policy>;
+
+pub type SharedPolicyLocalStore = Arc<PolicyLocalStore>;
+
+impl Spec for Policy {
+    const LABEL: &'static str = "Policy";
+
+    type Key = String;
+
+    fn key(&self) -> &Self::Key {
+        &self.name
+    }
+
+    fn key_owned(&self) -> Self::Key {
+        self.name.clone()
+    }
+}
@@ -0,0 +1,274 @@
+//!
+//! # Quotas
+//!
+//! Byte and request rates of client traffic are measured per quota,
+//! client exceeding rate is throttled for time needed to get back under the rate.
+//!
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use fluvio_controlplane::spu_api::update_quota::Quota;
+use fluvio_controlplane_metadata::quota::QuotaKind;
+
+use crate::core::Spec;
+use crate::core::LocalStore;
+
+/// upper bound of throttle, so single large request doesn't stall client for long
+const MAX_THROTTLE: Duration = Duration::from_secs(30);
+
+pub type QuotaLocalStore = LocalStore<Quota>;
+
+pub type SharedQuotaLocalStore = Arc<QuotaLocalStore>;
+
+impl Spec for Quota {
+    const LABEL: &'static str = "Quota";
+
+    type Key = String;
+
+    fn key(&self) -> &Self::Key {
+        &self.name
+    }
+
+    fn key_owned(&self) -> Self::Key {
+        self.name.clone()
+    }
+}
+
+/// Identity of connection whose traffic is subject to quotas
+#[derive(Debug, Clone, Default)]
+pub struct QuotaClient {
+    pub client_id: String,
+    pub principal: Option<String>,
+}
+
+impl QuotaClient {
+    pub fn new(client_id: impl Into<String>, principal: Option<&str>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            principal: principal.map(|principal| principal.to_owned()),
+        }
+    }
+}
+
+/// Measures traffic against quotas managed by SC
+#[derive(Debug, Default)]
+pub struct QuotaManager {
+    quotas: RwLock<Vec<Quota>>,
+    buckets: Mutex<HashMap<(String, QuotaKind), RateBucket>>,
+}
+
+impl QuotaManager {
+    /// replace all quotas, usage of removed quotas is forgotten
+    pub fn set_quotas(&self, quotas: Vec<Quota>) {
+        let mut buckets = self.buckets.lock().expect("quota lock poisoned");
+        buckets.retain(|(name, _), _| quotas.iter().any(|quota| &quota.name == name));
+        *self.quotas.write().expect("quota lock poisoned") = quotas;
+    }
+
+    /// record request transferring bytes to or from topics.
+    /// returns how long client has to be throttled
+    pub fn record_request(
+        &self,
+        client: &QuotaClient,
+        kind: QuotaKind,
+        usage: &[(&str, u64)],
+    ) -> Duration {
+        self.record(client, kind, usage, true, Instant::now())
+    }
+
+    /// record bytes sent by existing stream, which doesn't count as request
+    pub fn record_bytes(
+        &self,
+        client: &QuotaClient,
+        kind: QuotaKind,
+        topic: &str,
+        bytes: u64,
+    ) -> Duration {
+        self.record(client, kind, &[(topic, bytes)], false, Instant::now())
+    }
+
+    fn record(
+        &self,
+        client: &QuotaClient,
+        kind: QuotaKind,
+        usage: &[(&str, u64)],
+        is_request: bool,
+        now: Instant,
+    ) -> Duration {
+        let quotas = self.quotas.read().expect("quota lock poisoned");
+        if quotas.is_empty() {
+            return Duration::ZERO;
+        }
+
+        let mut buckets = self.buckets.lock().expect("quota lock poisoned");
+        let mut throttle = Duration::ZERO;
+        for quota in quotas.iter() {
+            let mut matched = false;
+            let mut bytes = 0;
+            for (topic, amount) in usage {
+                if quota.spec.entity.matches(
+                    &client.client_id,
+                    client.principal.as_deref(),
+                    Some(topic),
+                ) {
+                    matched = true;
+                    bytes += amount;
+                }
+            }
+            if !matched {
+                continue;
+            }
+
+            let mut charge = |kind: QuotaKind, amount: u64, rate: u64| {
+                buckets
+                    .entry((quota.name.clone(), kind))
+                    .or_insert_with(|| RateBucket::new(rate, now))
+                    .charge(amount, rate, now)
+            };
+            if let Some(rate) = quota.spec.rate(kind) {
+                throttle = throttle.max(charge(kind, bytes, rate));
+            }
+            if let Some(rate) = quota.spec.request_rate.filter(|_| is_request) {
+                throttle = throttle.max(charge(QuotaKind::Requests, 1, rate));
+            }
+        }
+        throttle.min(MAX_THROTTLE)
+    }
+}
+
+/// Token bucket refilled at quota rate, allowing burst of one second worth of rate
+#[derive(Debug)]
+struct RateBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateBucket {
+    fn new(rate: u64, now: Instant) -> Self {
+        Self {
+            tokens: rate as f64,
+            updated: now,
+        }
+    }
+
+    /// take amount from bucket, returns time until bucket is no longer in debt
+    fn charge(&mut self, amount: u64, rate: u64, now: Instant) -> Duration {
+        let rate = rate.max(1) as f64;
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.updated = now;
+        self.tokens = (self.tokens + elapsed * rate).min(rate) - amount as f64;
+        if self.tokens < 0.0 {
+            Duration::from_secs_f64((-self.tokens / rate).min(MAX_THROTTLE.as_secs_f64()))
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::{Duration, Instant};
+
+    use fluvio_controlplane::spu_api::update_quota::Quota;
+    use fluvio_controlplane_metadata::quota::{QuotaEntity, QuotaKind, QuotaSpec};
+
+    use super::{QuotaClient, QuotaManager, MAX_THROTTLE};
+
+    fn quota(name: &str, entity: QuotaEntity, produce: u64, requests: Option<u64>) -> Quota {
+        Quota {
+            name: name.to_owned(),
+            spec: QuotaSpec {
+                entity,
+                produce_byte_rate: Some(produce),
+                request_rate: requests,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_quota_byte_rate() {
+        let manager = QuotaManager::default();
+        manager.set_quotas(vec![quota(
+            "noisy",
+            QuotaEntity::ClientId("noisy".to_owned()),
+            1000,
+            None,
+        )]);
+        let noisy = QuotaClient::new("noisy", None);
+        let quiet = QuotaClient::new("quiet", None);
+        let now = Instant::now();
+
+        // first second worth of bytes is allowed as burst
+        let throttle = manager.record(&noisy, QuotaKind::ProduceBytes, &[("t", 1000)], true, now);
+        assert_eq!(throttle, Duration::ZERO);
+
+        // half second over the rate
+        let throttle = manager.record(&noisy, QuotaKind::ProduceBytes, &[("t", 500)], true, now);
+        assert_eq!(throttle, Duration::from_millis(500));
+
+        // consume is not limited, other clients are not affected
+        let throttle = manager.record(&noisy, QuotaKind::ConsumeBytes, &[("t", 5000)], true, now);
+        assert_eq!(throttle, Duration::ZERO);
+        let throttle = manager.record(&quiet, QuotaKind::ProduceBytes, &[("t", 5000)], true, now);
+        assert_eq!(throttle, Duration::ZERO);
+
+        // bucket is refilled over time
+        let later = now + Duration::from_secs(1);
+        let throttle = manager.record(&noisy, QuotaKind::ProduceBytes, &[("t", 500)], true, later);
+        assert_eq!(throttle, Duration::ZERO);
+
+        // throttle is capped
+        let throttle = manager.record(
+            &noisy,
+            QuotaKind::ProduceBytes,
+            &[("t", 1_000_000)],
+            true,
+            later,
+        );
+        assert_eq!(throttle, MAX_THROTTLE);
+    }
+
+    #[test]
+    fn test_quota_request_rate_and_topic() {
+        let manager = QuotaManager::default();
+        manager.set_quotas(vec![
+            quota(
+                "orders",
+                QuotaEntity::Topic("orders".to_owned()),
+                1_000_000,
+                Some(2),
+            ),
+            quota("svc", QuotaEntity::Principal("svc".to_owned()), 100, None),
+        ]);
+        let client = QuotaClient::new("app", None);
+        let now = Instant::now();
+
+        let record = |topics: &[(&str, u64)], is_request| {
+            manager.record(&client, QuotaKind::ProduceBytes, topics, is_request, now)
+        };
+        assert_eq!(
+            record(&[("orders", 10), ("audit", 10)], true),
+            Duration::ZERO
+        );
+        assert_eq!(record(&[("orders", 10)], true), Duration::ZERO);
+        assert_eq!(record(&[("orders", 10)], true), Duration::from_millis(500));
+        // bytes of stream are not requests
+        assert_eq!(record(&[("orders", 10)], false), Duration::ZERO);
+        assert_eq!(record(&[("audit", 10)], true), Duration::ZERO);
+
+        // principal quota
+        let svc = QuotaClient::new("app", Some("svc"));
+        let throttle = manager.record(&svc, QuotaKind::ProduceBytes, &[("audit", 200)], true, now);
+        assert_eq!(throttle, Duration::from_secs(1));
+
+        // removed quota no longer applies
+        manager.set_quotas(vec![]);
+        let throttle = manager.record(&svc, QuotaKind::ProduceBytes, &[("audit", 200)], true, now);
+        assert_eq!(throttle, Duration::ZERO);
+        assert!(manager.buckets.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,23 @@
+use fluvio_controlplane::spu_api::update_schema::Schema;
+use std::sync::Arc;
+
+use crate::core::Spec;
+use crate::core::LocalStore;
+
+pub type SchemaLocalStore = LocalStore<Schema>;
+
+pub type SharedSchemaLocalStore = Arc<SchemaLocalStore>;
+
+impl Spec for Schema {
+    const LABEL: &'static str = "Schema";
+
+    type Key = String;
+
+    fn key(&self) -> &Self::Key {
+        &self.name
+    }
+
+    fn key_owned(&self) -> Self::Key {
+        self.name.clone()
+    }
+}
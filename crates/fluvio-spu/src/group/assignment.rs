@@ -0,0 +1,203 @@
+use std::collections::{BTreeMap, HashSet};
+
+use fluvio_spu_schema::server::consumer_group::AssignmentStrategy;
+use fluvio_types::PartitionId;
+
+/// Partitions of each member, keyed by member id
+pub(crate) type Assignment = BTreeMap<String, Vec<PartitionId>>;
+
+/// Assign partitions `0..partition_count` to members of `previous`.
+/// Every member gets either `partition_count / members` partitions or one more.
+pub(crate) fn assign(
+    strategy: AssignmentStrategy,
+    previous: &Assignment,
+    partition_count: u32,
+) -> Assignment {
+    if previous.is_empty() {
+        return Assignment::new();
+    }
+    match strategy {
+        AssignmentStrategy::Range => range(previous, partition_count),
+        AssignmentStrategy::RoundRobin => round_robin(previous, partition_count),
+        AssignmentStrategy::Sticky => sticky(previous, partition_count),
+    }
+}
+
+/// first members get one extra partition if partitions can't be divided evenly
+fn range(previous: &Assignment, partition_count: u32) -> Assignment {
+    let members = previous.len() as u32;
+    let quota = partition_count / members;
+    let extra = partition_count % members;
+    previous
+        .keys()
+        .zip(0u32..)
+        .map(|(member, index)| {
+            let start = index * quota + index.min(extra);
+            let len = quota + u32::from(index < extra);
+            (member.clone(), (start..start + len).collect())
+        })
+        .collect()
+}
+
+fn round_robin(previous: &Assignment, partition_count: u32) -> Assignment {
+    let members: Vec<&String> = previous.keys().collect();
+    let mut assignment: Assignment = members.iter().map(|m| ((*m).clone(), vec![])).collect();
+    for partition in 0..partition_count {
+        let member = members[partition as usize % members.len()];
+        if let Some(partitions) = assignment.get_mut(member) {
+            partitions.push(partition);
+        }
+    }
+    assignment
+}
+
+/// members keep previous partitions up to their quota, remaining partitions go to least loaded members
+fn sticky(previous: &Assignment, partition_count: u32) -> Assignment {
+    let members = previous.len() as u32;
+    let quota = (partition_count / members) as usize;
+    let mut extra = (partition_count % members) as usize;
+
+    let mut taken = HashSet::new();
+    let mut leftovers = BTreeMap::new();
+    let mut assignment = Assignment::new();
+    for (member, partitions) in previous {
+        let mut owned: Vec<PartitionId> = partitions
+            .iter()
+            .copied()
+            .filter(|partition| *partition < partition_count && !taken.contains(partition))
+            .collect();
+        let rest = owned.split_off(quota.min(owned.len()));
+        taken.extend(owned.iter().copied());
+        leftovers.insert(member, rest);
+        assignment.insert(member.clone(), owned);
+    }
+
+    // members which owned more than quota keep one more partition while uneven split allows it
+    for (member, rest) in leftovers {
+        if extra == 0 {
+            break;
+        }
+        if let Some(partition) = rest.into_iter().find(|p| !taken.contains(p)) {
+            taken.insert(partition);
+            if let Some(partitions) = assignment.get_mut(member) {
+                partitions.push(partition);
+            }
+            extra -= 1;
+        }
+    }
+
+    for partition in (0..partition_count).filter(|p| !taken.contains(p)) {
+        if let Some((_, partitions)) = assignment
+            .iter_mut()
+            .min_by_key(|(_, partitions)| partitions.len())
+        {
+            partitions.push(partition);
+        }
+    }
+    for partitions in assignment.values_mut() {
+        partitions.sort_unstable();
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn members(members: &[(&str, &[PartitionId])]) -> Assignment {
+        members
+            .iter()
+            .map(|(member, partitions)| (member.to_string(), partitions.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_range_assignment() {
+        let assignment = assign(
+            AssignmentStrategy::Range,
+            &members(&[("a", &[]), ("b", &[]), ("c", &[])]),
+            8,
+        );
+        assert_eq!(
+            assignment,
+            members(&[("a", &[0, 1, 2]), ("b", &[3, 4, 5]), ("c", &[6, 7])])
+        );
+
+        // more members than partitions
+        let assignment = assign(
+            AssignmentStrategy::Range,
+            &members(&[("a", &[]), ("b", &[]), ("c", &[])]),
+            2,
+        );
+        assert_eq!(assignment, members(&[("a", &[0]), ("b", &[1]), ("c", &[])]));
+    }
+
+    #[test]
+    fn test_round_robin_assignment() {
+        let assignment = assign(
+            AssignmentStrategy::RoundRobin,
+            &members(&[("a", &[]), ("b", &[]), ("c", &[])]),
+            8,
+        );
+        assert_eq!(
+            assignment,
+            members(&[("a", &[0, 3, 6]), ("b", &[1, 4, 7]), ("c", &[2, 5])])
+        );
+    }
+
+    #[test]
+    fn test_sticky_assignment() {
+        // new member takes partitions from others, others keep rest of their partitions
+        let assignment = assign(
+            AssignmentStrategy::Sticky,
+            &members(&[("a", &[0, 1, 2, 3]), ("b", &[4, 5, 6, 7]), ("c", &[])]),
+            8,
+        );
+        assert_eq!(
+            assignment,
+            members(&[("a", &[0, 1, 2]), ("b", &[4, 5, 6]), ("c", &[3, 7])])
+        );
+
+        // partitions of member which left are spread over remaining members
+        let assignment = assign(
+            AssignmentStrategy::Sticky,
+            &members(&[("a", &[0, 1, 2]), ("c", &[3, 7])]),
+            8,
+        );
+        assert_eq!(
+            assignment,
+            members(&[("a", &[0, 1, 2, 5]), ("c", &[3, 4, 6, 7])])
+        );
+
+        // partitions which no longer exist are dropped, new partitions are assigned
+        let assignment = assign(
+            AssignmentStrategy::Sticky,
+            &members(&[("a", &[0, 1, 5]), ("b", &[2, 3, 4])]),
+            4,
+        );
+        assert_eq!(assignment, members(&[("a", &[0, 1]), ("b", &[2, 3])]));
+    }
+
+    #[test]
+    fn test_assignment_is_balanced() {
+        for strategy in [
+            AssignmentStrategy::Range,
+            AssignmentStrategy::RoundRobin,
+            AssignmentStrategy::Sticky,
+        ] {
+            let mut previous = members(&[("a", &[]), ("b", &[])]);
+            for (member, partition_count) in [("c", 7), ("d", 3), ("e", 11)] {
+                previous.insert(member.to_owned(), vec![]);
+                let assignment = assign(strategy, &previous, partition_count);
+                let mut all: Vec<_> = assignment.values().flatten().copied().collect();
+                all.sort_unstable();
+                assert_eq!(all, (0..partition_count).collect::<Vec<_>>(), "{strategy}");
+                let min = assignment.values().map(Vec::len).min().unwrap();
+                let max = assignment.values().map(Vec::len).max().unwrap();
+                assert!(max - min <= 1, "{strategy}: {assignment:?}");
+                previous = assignment;
+            }
+        }
+    }
+}
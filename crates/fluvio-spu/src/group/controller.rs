@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use tracing::debug;
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+
+use crate::core::DefaultSharedGlobalContext;
+
+use super::{is_group_coordinator, partition_count};
+
+/// how often member sessions and topic partitions are checked
+const GROUP_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Expires members which stopped sending heartbeats and rebalances groups when topic partitions change
+pub struct GroupController {
+    ctx: DefaultSharedGlobalContext,
+}
+
+impl GroupController {
+    pub fn new(ctx: DefaultSharedGlobalContext) -> Self {
+        Self { ctx }
+    }
+
+    pub fn run(self) {
+        spawn(self.dispatch_loop());
+    }
+
+    async fn dispatch_loop(self) {
+        debug!("starting consumer group check loop");
+        loop {
+            if is_group_coordinator(&self.ctx).await {
+                self.ctx
+                    .groups()
+                    .check(|topic| partition_count(&self.ctx, topic))
+                    .await;
+            } else {
+                self.ctx.groups().clear().await;
+            }
+            sleep(GROUP_CHECK_INTERVAL).await;
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use async_lock::Mutex;
+use tracing::{debug, info, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::consumer_group::AssignmentStrategy;
+use fluvio_types::{PartitionId, SpuId};
+
+use super::assignment::{assign, Assignment};
+
+/// members can't ask for session timeout longer than this
+pub const MAX_SESSION_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Partitions assigned to member in generation of group
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemberAssignment {
+    pub member_id: String,
+    pub generation: i32,
+    pub partitions: Vec<PartitionId>,
+}
+
+#[derive(Debug)]
+struct GroupMember {
+    session_timeout: Duration,
+    last_heartbeat: Instant,
+}
+
+#[derive(Debug)]
+struct ConsumerGroup {
+    topic: String,
+    strategy: AssignmentStrategy,
+    generation: i32,
+    partition_count: u32,
+    members: HashMap<String, GroupMember>,
+    assignment: Assignment,
+}
+
+impl ConsumerGroup {
+    fn rebalance(&mut self, name: &str) {
+        let mut previous = std::mem::take(&mut self.assignment);
+        previous.retain(|member, _| self.members.contains_key(member));
+        for member in self.members.keys() {
+            previous.entry(member.clone()).or_default();
+        }
+        self.assignment = assign(self.strategy, &previous, self.partition_count);
+        self.generation += 1;
+        info!(
+            group = name,
+            generation = self.generation,
+            members = self.members.len(),
+            partitions = self.partition_count,
+            "consumer group rebalanced"
+        );
+    }
+
+    fn member_assignment(&self, member_id: &str) -> MemberAssignment {
+        MemberAssignment {
+            member_id: member_id.to_owned(),
+            generation: self.generation,
+            partitions: self.assignment.get(member_id).cloned().unwrap_or_default(),
+        }
+    }
+}
+
+/// Keeps members and partition assignment of consumer groups.
+/// Groups are kept in memory only, members join again when coordinator changes.
+#[derive(Debug)]
+pub struct GroupCoordinator {
+    spu_id: SpuId,
+    next_member: AtomicU64,
+    groups: Mutex<HashMap<String, ConsumerGroup>>,
+}
+
+impl GroupCoordinator {
+    pub fn new(spu_id: SpuId) -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_millis() as u64)
+            .unwrap_or_default();
+        Self {
+            spu_id,
+            next_member: AtomicU64::new(seed),
+            groups: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// add member to group, creating group if needed.
+    /// Known member joining again keeps its partitions.
+    #[instrument(skip(self))]
+    pub async fn join(
+        &self,
+        group: &str,
+        member_id: &str,
+        topic: &str,
+        strategy: AssignmentStrategy,
+        session_timeout: Duration,
+        partition_count: u32,
+    ) -> Result<MemberAssignment, ErrorCode> {
+        let mut groups = self.groups.lock().await;
+        let consumer_group = groups
+            .entry(group.to_owned())
+            .or_insert_with(|| ConsumerGroup {
+                topic: topic.to_owned(),
+                strategy,
+                generation: 0,
+                partition_count,
+                members: HashMap::new(),
+                assignment: Assignment::new(),
+            });
+        if consumer_group.topic != topic {
+            return Err(ErrorCode::InconsistentGroupProtocol(format!(
+                "group {group} consumes topic {}",
+                consumer_group.topic
+            )));
+        }
+        if consumer_group.strategy != strategy {
+            return Err(ErrorCode::InconsistentGroupProtocol(format!(
+                "group {group} uses {} assignment strategy",
+                consumer_group.strategy
+            )));
+        }
+
+        let member_id = if member_id.is_empty() {
+            let next = self.next_member.fetch_add(1, Ordering::Relaxed);
+            format!("{}-{next}", self.spu_id)
+        } else {
+            member_id.to_owned()
+        };
+        let member = GroupMember {
+            session_timeout: session_timeout.min(MAX_SESSION_TIMEOUT),
+            last_heartbeat: Instant::now(),
+        };
+        if consumer_group
+            .members
+            .insert(member_id.clone(), member)
+            .is_none()
+            || consumer_group.partition_count != partition_count
+        {
+            debug!(group, member_id, "member joined");
+            consumer_group.partition_count = partition_count;
+            consumer_group.rebalance(group);
+        }
+        Ok(consumer_group.member_assignment(&member_id))
+    }
+
+    /// keep member alive, returns current assignment of member
+    pub async fn heartbeat(
+        &self,
+        group: &str,
+        member_id: &str,
+    ) -> Result<MemberAssignment, ErrorCode> {
+        let mut groups = self.groups.lock().await;
+        let Some(consumer_group) = groups.get_mut(group) else {
+            return Err(ErrorCode::UnknownGroupMember);
+        };
+        let Some(member) = consumer_group.members.get_mut(member_id) else {
+            return Err(ErrorCode::UnknownGroupMember);
+        };
+        member.last_heartbeat = Instant::now();
+        Ok(consumer_group.member_assignment(member_id))
+    }
+
+    /// remove member from group, group is removed with its last member
+    #[instrument(skip(self))]
+    pub async fn leave(&self, group: &str, member_id: &str) -> Result<(), ErrorCode> {
+        let mut groups = self.groups.lock().await;
+        let Some(consumer_group) = groups.get_mut(group) else {
+            return Err(ErrorCode::UnknownGroupMember);
+        };
+        if consumer_group.members.remove(member_id).is_none() {
+            return Err(ErrorCode::UnknownGroupMember);
+        }
+        debug!(group, member_id, "member left");
+        if consumer_group.members.is_empty() {
+            groups.remove(group);
+        } else {
+            consumer_group.rebalance(group);
+        }
+        Ok(())
+    }
+
+    /// remove members which missed heartbeats and rebalance groups whose topic changed number of partitions
+    pub(crate) async fn check(&self, partition_count: impl Fn(&str) -> u32) {
+        let mut groups = self.groups.lock().await;
+        groups.retain(|name, consumer_group| {
+            let members = consumer_group.members.len();
+            consumer_group.members.retain(|member_id, member| {
+                let alive = member.last_heartbeat.elapsed() <= member.session_timeout;
+                if !alive {
+                    info!(group = name, member_id, "member session expired");
+                }
+                alive
+            });
+            if consumer_group.members.is_empty() {
+                return false;
+            }
+            let partitions = partition_count(&consumer_group.topic);
+            if consumer_group.members.len() != members
+                || consumer_group.partition_count != partitions
+            {
+                consumer_group.partition_count = partitions;
+                consumer_group.rebalance(name);
+            }
+            true
+        });
+    }
+
+    /// forget all groups, used when this SPU is no longer coordinator
+    pub(crate) async fn clear(&self) {
+        let mut groups = self.groups.lock().await;
+        if !groups.is_empty() {
+            info!(
+                groups = groups.len(),
+                "no longer group coordinator, dropping groups"
+            );
+            groups.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[fluvio_future::test]
+    async fn test_group_coordinator_membership() {
+        let coordinator = GroupCoordinator::new(5001);
+        let first = coordinator
+            .join("g1", "", "topic", AssignmentStrategy::Range, TIMEOUT, 4)
+            .await
+            .expect("join");
+        assert!(first.member_id.starts_with("5001-"));
+        assert_eq!(first.generation, 1);
+        assert_eq!(first.partitions, vec![0, 1, 2, 3]);
+
+        let second = coordinator
+            .join("g1", "", "topic", AssignmentStrategy::Range, TIMEOUT, 4)
+            .await
+            .expect("join");
+        assert_ne!(first.member_id, second.member_id);
+        assert_eq!(second.generation, 2);
+
+        // first member learns about rebalance from heartbeat
+        let first = coordinator
+            .heartbeat("g1", &first.member_id)
+            .await
+            .expect("heartbeat");
+        assert_eq!(first.generation, 2);
+        let mut all = [first.partitions.clone(), second.partitions.clone()].concat();
+        all.sort_unstable();
+        assert_eq!(all, vec![0, 1, 2, 3]);
+        assert_eq!(first.partitions.len(), 2);
+
+        // joining again doesn't rebalance
+        let again = coordinator
+            .join(
+                "g1",
+                &second.member_id,
+                "topic",
+                AssignmentStrategy::Range,
+                TIMEOUT,
+                4,
+            )
+            .await
+            .expect("join");
+        assert_eq!(again, second);
+
+        // group protocol must match
+        assert!(matches!(
+            coordinator
+                .join("g1", "", "other", AssignmentStrategy::Range, TIMEOUT, 4)
+                .await,
+            Err(ErrorCode::InconsistentGroupProtocol(_))
+        ));
+        assert!(matches!(
+            coordinator
+                .join("g1", "", "topic", AssignmentStrategy::Sticky, TIMEOUT, 4)
+                .await,
+            Err(ErrorCode::InconsistentGroupProtocol(_))
+        ));
+
+        coordinator
+            .leave("g1", &second.member_id)
+            .await
+            .expect("leave");
+        let first = coordinator
+            .heartbeat("g1", &first.member_id)
+            .await
+            .expect("heartbeat");
+        assert_eq!(first.generation, 3);
+        assert_eq!(first.partitions, vec![0, 1, 2, 3]);
+        assert!(matches!(
+            coordinator.heartbeat("g1", &second.member_id).await,
+            Err(ErrorCode::UnknownGroupMember)
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_group_coordinator_check() {
+        let coordinator = GroupCoordinator::new(5001);
+        let first = coordinator
+            .join("g1", "", "topic", AssignmentStrategy::Sticky, TIMEOUT, 2)
+            .await
+            .expect("join");
+        let second = coordinator
+            .join(
+                "g1",
+                "",
+                "topic",
+                AssignmentStrategy::Sticky,
+                Duration::ZERO,
+                2,
+            )
+            .await
+            .expect("join");
+        assert_eq!(second.generation, 2);
+
+        // second member expires, partitions change
+        fluvio_future::timer::sleep(Duration::from_millis(10)).await;
+        coordinator.check(|_| 3).await;
+        let first = coordinator
+            .heartbeat("g1", &first.member_id)
+            .await
+            .expect("heartbeat");
+        assert_eq!(first.generation, 3);
+        assert_eq!(first.partitions, vec![0, 1, 2]);
+
+        // nothing changed, no rebalance
+        coordinator.check(|_| 3).await;
+        let first = coordinator
+            .heartbeat("g1", &first.member_id)
+            .await
+            .expect("heartbeat");
+        assert_eq!(first.generation, 3);
+
+        coordinator.clear().await;
+        assert!(matches!(
+            coordinator.heartbeat("g1", &first.member_id).await,
+            Err(ErrorCode::UnknownGroupMember)
+        ));
+    }
+}
@@ -0,0 +1,35 @@
+//!
+//! # Consumer groups
+//!
+//! Leader of consumer offsets partition coordinates consumer groups.
+//! Members join group and send heartbeats, coordinator assigns partitions of topic to members
+//! and rebalances group when members join or leave, miss heartbeats, or topic changes number of partitions.
+//!
+
+mod assignment;
+mod coordinator;
+mod controller;
+
+pub use self::coordinator::GroupCoordinator;
+pub use self::controller::GroupController;
+
+use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
+
+use crate::core::DefaultSharedGlobalContext;
+
+/// only leader of consumer offsets partition can coordinate groups
+pub(crate) async fn is_group_coordinator(ctx: &DefaultSharedGlobalContext) -> bool {
+    ctx.leaders_state()
+        .get(&CONSUMER_REPLICA_KEY.into())
+        .await
+        .is_some()
+}
+
+/// number of partitions of topic known to this SPU
+pub(crate) fn partition_count(ctx: &DefaultSharedGlobalContext, topic: &str) -> u32 {
+    ctx.replica_localstore()
+        .read()
+        .keys()
+        .filter(|replica| replica.topic == topic)
+        .count() as u32
+}
@@ -12,6 +12,11 @@ cfg_if::cfg_if! {
         mod storage;
         mod smartengine;
         mod monitoring;
+        mod transaction;
+        mod group;
+        mod repair;
+        #[cfg(feature = "otel")]
+        mod telemetry;
         pub(crate) mod mirroring;
         pub use start::main_loop;
     }
@@ -4,8 +4,32 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 use clap::Parser;
 
 fn main() {
+    // spans are exported only when OTLP collector is configured
+    #[cfg(feature = "otel")]
+    let _tracer = init_otlp_tracer();
+    #[cfg(not(feature = "otel"))]
     fluvio_future::subscriber::init_tracer(None);
 
     let opt = fluvio_spu::SpuOpt::parse();
     fluvio_spu::main_loop(opt);
 }
+
+/// install OTLP subscriber if collector is configured, otherwise or on failure install default subscriber
+#[cfg(feature = "otel")]
+fn init_otlp_tracer() -> Option<fluvio::telemetry::OtlpTracer> {
+    if std::env::var_os(fluvio::telemetry::OTLP_ENDPOINT_ENV).is_none() {
+        fluvio_future::subscriber::init_tracer(None);
+        return None;
+    }
+    let result = fluvio_future::task::run_block_on(async {
+        fluvio::telemetry::init_otlp_tracer("fluvio-spu", None)
+    });
+    match result {
+        Ok(tracer) => Some(tracer),
+        Err(err) => {
+            fluvio_future::subscriber::init_tracer(None);
+            tracing::error!(%err, "failed to initialize OTLP exporter, spans are not exported");
+            None
+        }
+    }
+}
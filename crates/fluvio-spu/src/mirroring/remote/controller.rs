@@ -625,6 +625,7 @@ where
             replica: self.leader.id().clone(),
             leo: self.leader.leo(),
             hw: self.leader.hw(),
+            leader_epoch: None,
         };
 
         debug!(?offset_request, "sending offset to home");
@@ -1,14 +1,306 @@
+use std::fmt::Display;
 use std::io::Error as IoError;
 
 use futures_util::{StreamExt, AsyncWriteExt};
 use fluvio_types::defaults::SPU_MONITORING_UNIX_SOCKET;
 use fluvio_future::task::spawn;
 use fluvio_future::net::unix::UnixListener;
+use fluvio_service::metrics::{start_metrics_server, MetricKind, MetricsEncoder};
+use fluvio_storage::ReplicaStorage;
+use fluvio_controlplane_metadata::partition::ReplicaKey;
 use tracing::{error, info, debug};
 use serde_json::json;
 
 use crate::core::DefaultSharedGlobalContext;
 
+/// serve OpenMetrics over http if metrics address is configured
+pub(crate) fn init_metrics_server(ctx: DefaultSharedGlobalContext) {
+    if let Some(addr) = ctx.config().metrics_addr.clone() {
+        start_metrics_server(addr, move || render_metrics(ctx.clone()));
+    }
+}
+
+async fn render_metrics(ctx: DefaultSharedGlobalContext) -> String {
+    let spu = ctx.local_spu_id().to_string();
+    let spu = spu.as_str();
+    let metrics = ctx.metrics();
+    let mut encoder = MetricsEncoder::default();
+
+    encoder
+        .family(
+            "fluvio_spu_connections",
+            MetricKind::Gauge,
+            "Open client connections",
+        )
+        .sample(&[("spu", spu)], metrics.connections());
+
+    for (direction, activity) in [
+        ("produce", metrics.inbound()),
+        ("consume", metrics.outbound()),
+    ] {
+        let mut records = encoder.family(
+            &format!("fluvio_spu_{direction}_records"),
+            MetricKind::Counter,
+            &format!("Records of {direction} requests"),
+        );
+        records
+            .sample(
+                &[("spu", spu), ("origin", "client")],
+                activity.client_records(),
+            )
+            .sample(
+                &[("spu", spu), ("origin", "connector")],
+                activity.connector_records(),
+            );
+        let mut bytes = encoder.family(
+            &format!("fluvio_spu_{direction}_bytes"),
+            MetricKind::Counter,
+            &format!("Bytes of {direction} requests"),
+        );
+        bytes
+            .sample(
+                &[("spu", spu), ("origin", "client")],
+                activity.client_bytes(),
+            )
+            .sample(
+                &[("spu", spu), ("origin", "connector")],
+                activity.connector_bytes(),
+            );
+    }
+
+    let partitions = metrics.partitions();
+    replica_family(
+        &mut encoder,
+        "fluvio_partition_produce_records",
+        MetricKind::Counter,
+        "Records produced to partition",
+        spu,
+        &partitions,
+        |activity| activity.produced().records(),
+    );
+    replica_family(
+        &mut encoder,
+        "fluvio_partition_produce_bytes",
+        MetricKind::Counter,
+        "Bytes produced to partition",
+        spu,
+        &partitions,
+        |activity| activity.produced().bytes(),
+    );
+    replica_family(
+        &mut encoder,
+        "fluvio_partition_consume_records",
+        MetricKind::Counter,
+        "Records consumed from partition",
+        spu,
+        &partitions,
+        |activity| activity.consumed().records(),
+    );
+    replica_family(
+        &mut encoder,
+        "fluvio_partition_consume_bytes",
+        MetricKind::Counter,
+        "Bytes consumed from partition",
+        spu,
+        &partitions,
+        |activity| activity.consumed().bytes(),
+    );
+
+    // offsets of leader replicas and their followers
+    let leaders: Vec<_> = ctx.leaders_state().read().await.values().cloned().collect();
+    let mut offsets = Vec::with_capacity(leaders.len());
+    for leader in &leaders {
+        let followers = leader.followers_info().await;
+        offsets.push((leader.id().clone(), (leader.leo(), leader.hw(), followers)));
+    }
+    replica_family(
+        &mut encoder,
+        "fluvio_partition_leo",
+        MetricKind::Gauge,
+        "Log end offset of leader replica",
+        spu,
+        &offsets,
+        |(leo, ..)| *leo,
+    );
+    replica_family(
+        &mut encoder,
+        "fluvio_partition_hw",
+        MetricKind::Gauge,
+        "High watermark of leader replica",
+        spu,
+        &offsets,
+        |(_, hw, _)| *hw,
+    );
+    let mut family = encoder.family(
+        "fluvio_replica_lag_records",
+        MetricKind::Gauge,
+        "Records of leader log not yet replicated to follower",
+    );
+    for (replica, (leo, _, followers)) in &offsets {
+        let partition = replica.partition.to_string();
+        for (follower, info) in followers {
+            let follower = follower.to_string();
+            family.sample(
+                &[
+                    ("spu", spu),
+                    ("topic", &replica.topic),
+                    ("partition", &partition),
+                    ("follower", &follower),
+                ],
+                leo - info.leo.max(0),
+            );
+        }
+    }
+
+    // segments of all local replicas
+    let mut storages = Vec::with_capacity(leaders.len());
+    for leader in &leaders {
+        let storage = leader.read().await;
+        let segments = (
+            storage.segment_count().await,
+            storage.get_partition_size(),
+            storage.scrub_status(),
+        );
+        storages.push((leader.id().clone(), segments));
+    }
+    for (replica, follower) in ctx.followers_state().read().await.iter() {
+        let storage = follower.read().await;
+        let segments = (
+            storage.segment_count().await,
+            storage.get_partition_size(),
+            storage.scrub_status(),
+        );
+        storages.push((replica.clone(), segments));
+    }
+    replica_family(
+        &mut encoder,
+        "fluvio_replica_segments",
+        MetricKind::Gauge,
+        "Log segments of replica, including active segment",
+        spu,
+        &storages,
+        |(count, ..)| *count,
+    );
+    replica_family(
+        &mut encoder,
+        "fluvio_replica_size_bytes",
+        MetricKind::Gauge,
+        "Size of replica log and index",
+        spu,
+        &storages,
+        |(_, size, _)| *size,
+    );
+    replica_family(
+        &mut encoder,
+        "fluvio_replica_corrupt_batches",
+        MetricKind::Gauge,
+        "Batches of replica with crc mismatch, not repaired yet",
+        spu,
+        &storages,
+        |(.., scrub)| scrub.corrupt_batches.len(),
+    );
+    replica_family(
+        &mut encoder,
+        "fluvio_replica_scrubbed_bytes",
+        MetricKind::Counter,
+        "Bytes of closed segments verified by scrubber",
+        spu,
+        &storages,
+        |(.., scrub)| scrub.scrubbed_bytes,
+    );
+    replica_family(
+        &mut encoder,
+        "fluvio_replica_repaired_batches",
+        MetricKind::Counter,
+        "Corrupt batches replaced with healthy copy from other replica",
+        spu,
+        &storages,
+        |(.., scrub)| scrub.repaired_batches,
+    );
+
+    let smartmodules = metrics.smartmodule_metrics();
+    let mut smartmodules: Vec<_> = smartmodules.iter().collect();
+    smartmodules.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut family = encoder.family(
+        "fluvio_smartmodule_fuel",
+        MetricKind::Counter,
+        "Fuel used by SmartModule chain",
+    );
+    for (name, chain) in &smartmodules {
+        family.sample(&[("spu", spu), ("smartmodule", name)], chain.fuel_used());
+    }
+    let mut family = encoder.family(
+        "fluvio_smartmodule_cpu_seconds",
+        MetricKind::Summary,
+        "CPU time of SmartModule chain invocations",
+    );
+    for (name, chain) in &smartmodules {
+        family.summary(
+            &[("spu", spu), ("smartmodule", name)],
+            chain.invocation_count(),
+            chain.cpu_ms() as f64 / 1000.0,
+        );
+    }
+    let mut family = encoder.family(
+        "fluvio_smartmodule_input_bytes",
+        MetricKind::Counter,
+        "Bytes processed by SmartModule chain",
+    );
+    for (name, chain) in &smartmodules {
+        family.sample(&[("spu", spu), ("smartmodule", name)], chain.bytes_in());
+    }
+    let mut family = encoder.family(
+        "fluvio_smartmodule_output_records",
+        MetricKind::Counter,
+        "Records returned by SmartModule chain",
+    );
+    for (name, chain) in &smartmodules {
+        family.sample(&[("spu", spu), ("smartmodule", name)], chain.records_out());
+    }
+    let mut family = encoder.family(
+        "fluvio_smartmodule_errors",
+        MetricKind::Counter,
+        "Records failed in SmartModule chain",
+    );
+    for (name, chain) in &smartmodules {
+        family.sample(&[("spu", spu), ("smartmodule", name)], chain.records_err());
+    }
+    let mut family = encoder.family(
+        "fluvio_smartmodule_timeouts",
+        MetricKind::Counter,
+        "Invocations of SmartModule chain stopped by time limit",
+    );
+    for (name, chain) in &smartmodules {
+        family.sample(&[("spu", spu), ("smartmodule", name)], chain.timeouts());
+    }
+
+    encoder.finish()
+}
+
+/// family with sample per replica
+fn replica_family<T, V: Display>(
+    encoder: &mut MetricsEncoder,
+    name: &str,
+    kind: MetricKind,
+    help: &str,
+    spu: &str,
+    replicas: &[(ReplicaKey, T)],
+    value: impl Fn(&T) -> V,
+) {
+    let mut family = encoder.family(name, kind, help);
+    for (replica, item) in replicas {
+        let partition = replica.partition.to_string();
+        family.sample(
+            &[
+                ("spu", spu),
+                ("topic", &replica.topic),
+                ("partition", &partition),
+            ],
+            value(item),
+        );
+    }
+}
+
 // Add SmartEngine to init_monitoring params
 pub(crate) fn init_monitoring(ctx: DefaultSharedGlobalContext) {
     spawn(async move {
@@ -0,0 +1,130 @@
+//!
+//! # Replica repair
+//!
+//! Batches found corrupt by crc checks of local replicas are replaced with healthy
+//! copies fetched from other replicas of the same partition.
+//!
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tracing::{debug, error, info, instrument, warn};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_storage::{FileReplica, ReplicaStorage};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::services::internal::FetchBatchRequest;
+use crate::services::public::send_private_request_to_spu;
+use crate::storage::SharableReplicaStorage;
+
+/// how often local replicas are checked for corrupt batches
+const REPAIR_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Repairs corrupt batches of leader and follower replicas of this SPU
+pub struct RepairController {
+    ctx: DefaultSharedGlobalContext,
+}
+
+impl RepairController {
+    pub fn new(ctx: DefaultSharedGlobalContext) -> Self {
+        Self { ctx }
+    }
+
+    pub fn run(self) {
+        spawn(self.dispatch_loop());
+    }
+
+    async fn dispatch_loop(self) {
+        debug!("starting replica repair loop");
+        // corrupt batch count last reported in status of leader replicas
+        let mut reported: HashMap<ReplicaKey, usize> = HashMap::new();
+        loop {
+            sleep(REPAIR_CHECK_INTERVAL).await;
+            self.repair_replicas(&mut reported).await;
+        }
+    }
+
+    async fn repair_replicas(&self, reported: &mut HashMap<ReplicaKey, usize>) {
+        let leaders: Vec<_> = self
+            .ctx
+            .leaders_state()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        let followers: Vec<_> = self
+            .ctx
+            .followers_state()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+
+        for storage in followers.iter().map(|follower| &**follower) {
+            self.repair_storage(storage).await;
+        }
+
+        reported.retain(|id, _| leaders.iter().any(|leader| leader.id() == id));
+        for leader in &leaders {
+            let count = self.repair_storage(leader).await;
+            if reported.get(leader.id()).copied().unwrap_or_default() != count {
+                leader.update_status().await;
+                reported.insert(leader.id().clone(), count);
+            }
+        }
+    }
+
+    /// repair corrupt batches of replica, returns count of batches still corrupt
+    async fn repair_storage(&self, storage: &SharableReplicaStorage<FileReplica>) -> usize {
+        let corrupt_batches = storage.read().await.scrub_status().corrupt_batches;
+        for corrupt in corrupt_batches {
+            self.repair_batch(storage, corrupt.base_offset).await;
+        }
+        storage.read().await.get_corrupt_batch_count()
+    }
+
+    /// try other replicas of partition until one of them has healthy batch
+    #[instrument(skip(self, storage), fields(replica = %storage.id()))]
+    async fn repair_batch(&self, storage: &SharableReplicaStorage<FileReplica>, offset: Offset) {
+        let replica_id: &ReplicaKey = storage.id();
+        let Some(replica) = self.ctx.replica_localstore().spec(replica_id) else {
+            return;
+        };
+        let local_spu = self.ctx.local_spu_id();
+        for spu in replica.replicas.into_iter().filter(|spu| *spu != local_spu) {
+            let request = FetchBatchRequest {
+                replica_id: replica_id.clone(),
+                offset,
+            };
+            let batch = match send_private_request_to_spu(&self.ctx, spu, request).await {
+                Ok(response) if response.error_code.is_ok() => response.batch,
+                Ok(response) => {
+                    debug!(spu, error = ?response.error_code, "peer can't provide batch");
+                    continue;
+                }
+                Err(err) => {
+                    warn!(spu, %err, "failed to fetch batch from peer");
+                    continue;
+                }
+            };
+            let Some(batch) = batch else {
+                debug!(spu, "peer doesn't have healthy batch");
+                continue;
+            };
+            match storage.write().await.repair_batch(&batch).await {
+                Ok(true) => {
+                    info!(spu, "repaired batch with copy from peer");
+                    return;
+                }
+                Ok(false) => return,
+                Err(err) => error!(spu, %err, "failed to repair batch"),
+            }
+        }
+        warn!("no healthy copy of corrupt batch found");
+    }
+}
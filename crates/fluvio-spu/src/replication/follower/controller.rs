@@ -14,7 +14,7 @@ use super::api_key::FollowerPeerApiEnum;
 use super::sync::DefaultSyncRequest;
 use super::peer_api::FollowerPeerRequest;
 
-/// time to resync follower offsets to leader
+/// max time to resync follower offsets to leader
 const LEADER_RECONCILIATION_INTERVAL_SEC: u64 = 60; // 1 min
 
 #[derive(Debug)]
@@ -135,6 +135,13 @@ mod inner {
             self.config.id()
         }
 
+        /// Offsets are resynced within replica lag time max,
+        /// so follower which is idle but caught up stays in sync with leader
+        fn reconciliation_interval(&self) -> Duration {
+            Duration::from_millis(self.config.replication.replica_lag_time_max_ms / 3)
+                .min(Duration::from_secs(LEADER_RECONCILIATION_INTERVAL_SEC))
+        }
+
         #[instrument(
         skip(self),
         name = "FollowerGroupController",
@@ -193,7 +200,7 @@ mod inner {
 
             let mut counter: i32 = 0;
 
-            let mut timer = sleep(Duration::from_secs(LEADER_RECONCILIATION_INTERVAL_SEC));
+            let mut timer = sleep(self.reconciliation_interval());
 
             loop {
                 debug!(counter, "waiting request from leader");
@@ -202,7 +209,7 @@ mod inner {
                     _ = &mut timer => {
                         debug!("timer fired - kickoff sync offsets to leader");
                         self.sync_all_offsets_to_leader(&mut sink).await?;
-                        timer= sleep(Duration::from_secs(LEADER_RECONCILIATION_INTERVAL_SEC));
+                        timer= sleep(self.reconciliation_interval());
                     },
 
                     offset_value = event_listener.listen() => {
@@ -270,13 +277,17 @@ mod inner {
                     leader_leo=p.leo,
                     records = p.records.total_records(),
                     base_offset = p.records.base_offset(),
+                    truncate_offset = p.truncate_offset,
                     "update from leader");
                     if let Some(replica) = self.states.get(&replica_key).await {
-                        match replica.update_from_leader(&mut p.records, p.hw).await {
+                        match replica
+                            .update_from_leader(&mut p.records, p.hw, p.truncate_offset)
+                            .await
+                        {
                             Ok(changes) => {
                                 if changes {
                                     debug!("changes occur, need to send back offset");
-                                    offsets.replicas.push(replica.as_offset_request());
+                                    offsets.replicas.push(replica.as_offset_request().await);
                                 } else {
                                     debug!("no changes");
                                 }
@@ -384,7 +395,7 @@ mod inner {
             sink: &mut FluvioSink,
         ) -> Result<(), SocketError> {
             let spu_replicas = FollowerGroup::filter_from(&self.states, self.leader).await;
-            self.send_offsets_to_leader(sink, spu_replicas.replica_offsets())
+            self.send_offsets_to_leader(sink, spu_replicas.replica_offsets().await)
                 .await
         }
 
@@ -418,12 +429,11 @@ mod inner {
         }
 
         // generate offset requests
-        fn replica_offsets(&self) -> UpdateOffsetRequest {
-            let replicas = self
-                .0
-                .values()
-                .map(|replica| replica.as_offset_request())
-                .collect();
+        async fn replica_offsets(&self) -> UpdateOffsetRequest {
+            let mut replicas = Vec::with_capacity(self.0.len());
+            for replica in self.0.values() {
+                replicas.push(replica.as_offset_request().await);
+            }
 
             UpdateOffsetRequest { replicas }
         }
@@ -4,7 +4,7 @@ use std::collections::{HashMap, hash_map::Entry};
 use std::ops::{Deref, DerefMut};
 
 use fluvio_controlplane::replica::Replica;
-use tracing::{debug, warn, instrument};
+use tracing::{debug, error, warn, instrument};
 use async_lock::RwLock;
 use anyhow::Result;
 
@@ -99,6 +99,9 @@ impl FollowersState<FileReplica> {
 
                 let replica_state =
                     FollowerReplicaState::create(leader, replica.id, replica_config).await?;
+                replica_state
+                    .delete_records(replica.log_start_offset)
+                    .await?;
 
                 entry.insert(replica_state.clone());
                 self.groups.check_new(ctx, leader).await;
@@ -139,7 +142,14 @@ impl FollowersState<FileReplica> {
         }
     }
 
-    pub async fn update_replica(&self, _replica: Replica) {}
+    /// apply replica changes which don't require new follower state
+    pub async fn update_replica(&self, replica: Replica) {
+        if let Some(state) = self.get(&replica.id).await {
+            if let Err(err) = state.delete_records(replica.log_start_offset).await {
+                error!(%replica, "failed to delete records: {err}");
+            }
+        }
+    }
 }
 
 /// State for Follower Replica Controller
@@ -204,14 +214,27 @@ where
         self.leader
     }
 
-    /// update from leader with new record set
+    /// update from leader with new record set.
+    /// Records which diverge from leader are truncated first
     pub async fn update_from_leader<R: BatchRecords>(
         &self,
         records: &mut RecordSet<R>,
         leader_hw: Offset,
+        truncate_offset: Option<Offset>,
     ) -> Result<bool> {
         let mut changes = false;
 
+        if let Some(offset) = truncate_offset.filter(|offset| *offset < self.leo()) {
+            warn!(
+                replica = %self.id(),
+                offset,
+                leo = self.leo(),
+                "log diverges from leader, truncating"
+            );
+            self.truncate(offset).await?;
+            changes = true;
+        }
+
         if records.total_records() > 0 {
             self.write_recordsets(records).await?;
             changes = true;
@@ -262,11 +285,12 @@ where
     }
 
     /// convert to offset request
-    pub fn as_offset_request(&self) -> ReplicaOffsetRequest {
+    pub async fn as_offset_request(&self) -> ReplicaOffsetRequest {
         ReplicaOffsetRequest {
             replica: self.inner.id().to_owned(),
             leo: self.leo(),
             hw: self.hw(),
+            leader_epoch: Some(self.leader_epoch().await),
         }
     }
 
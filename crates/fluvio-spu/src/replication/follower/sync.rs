@@ -50,14 +50,14 @@ where
 }
 
 // Request trait
-// Note that DEFAULT_API_VERSION is 7 which is required in order to map all fields for file encoding
+// Note that DEFAULT_API_VERSION must be at least 7 which is required in order to map all fields for file encoding
 // TODO: come up with unify encoding
 impl<R> Request for SyncRequest<R>
 where
     R: Encoder + Decoder + Debug,
 {
     const API_KEY: u16 = FollowerPeerApiEnum::SyncRecords as u16;
-    const DEFAULT_API_VERSION: i16 = 7;
+    const DEFAULT_API_VERSION: i16 = 8;
     type Response = SyncResponse;
 }
 
@@ -110,6 +110,9 @@ where
     pub error: ErrorCode,
     pub hw: i64,
     pub leo: i64,
+    /// follower's log diverges from leader at this offset and must be truncated before records are appended
+    #[fluvio(min_version = 8)]
+    pub truncate_offset: Option<i64>,
     pub records: R,
 }
 
@@ -152,6 +155,9 @@ impl FileWrite for PeerFilePartitionResponse {
         self.error.encode(src, version)?;
         self.hw.encode(src, version)?;
         self.leo.encode(src, version)?;
+        if version >= 8 {
+            self.truncate_offset.encode(src, version)?;
+        }
         self.records.file_encode(src, data, version)?;
         Ok(())
     }
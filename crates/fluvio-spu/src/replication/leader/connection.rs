@@ -4,6 +4,8 @@ use tracing::{debug, error, warn};
 use futures_util::stream::StreamExt;
 use tracing::instrument;
 
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
 use fluvio_storage::OffsetInfo;
 use fluvio_socket::{FluvioSink, SocketError, FluvioStream};
 use fluvio_protocol::api::RequestMessage;
@@ -11,6 +13,7 @@ use fluvio_types::SpuId;
 
 use crate::{core::DefaultSharedGlobalContext, replication::follower::sync::FileSyncRequest};
 
+use super::FollowerThrottle;
 use super::LeaderPeerApiEnum;
 use super::LeaderPeerRequest;
 use super::UpdateOffsetRequest;
@@ -137,6 +140,22 @@ impl FollowerHandler {
 
         for replica in replicas {
             if let Some(leader) = leaders.get(&replica).await {
+                match leader.follower_throttle(&self.follower_id) {
+                    FollowerThrottle::Ready => {}
+                    FollowerThrottle::Delay(delay) => {
+                        debug!(%replica, ?delay, "follower throttled");
+                        let ctx = self.ctx.clone();
+                        let follower_id = self.follower_id;
+                        spawn(async move {
+                            sleep(delay).await;
+                            ctx.follower_notifier()
+                                .notify_follower(&follower_id, replica)
+                                .await;
+                        });
+                        continue;
+                    }
+                    FollowerThrottle::Scheduled => continue,
+                }
                 if let Some(topic_response) = leader
                     .follower_updates(&self.follower_id, self.max_bytes)
                     .await
@@ -174,6 +193,18 @@ impl FollowerHandler {
             debug!(?update, "request");
             let replica_key = update.replica;
             if let Some(leader) = self.ctx.leaders_state().get(&replica_key).await {
+                if leader
+                    .check_follower_divergence(
+                        self.follower_id,
+                        update.leo,
+                        update.leader_epoch,
+                        self.ctx.follower_notifier(),
+                    )
+                    .await
+                {
+                    debug!(replica = %leader.id(), "follower must truncate");
+                    continue;
+                }
                 let status = leader
                     .update_states_from_followers(
                         self.follower_id,
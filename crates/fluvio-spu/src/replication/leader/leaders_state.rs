@@ -54,7 +54,6 @@ impl<S> ReplicaLeadersState<S> {
         writer.remove(replica)
     }
 
-    #[allow(unused)]
     pub async fn insert(
         &self,
         replica: ReplicaKey,
@@ -0,0 +1,361 @@
+//!
+//! # Idempotent producer state
+//!
+//! Leader keeps last sequence number of each producer writing to the replica.
+//! Batches from producer are accepted only if they continue the sequence.
+//! Retried requests which were already written are detected and answered with original offsets.
+//! Retries older than cached requests are answered with duplicate sequence error, only gaps are rejected.
+//!
+//! State is kept in memory only. After leader change or restart, first batch of each producer is accepted.
+//!
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, Offset, increment_sequence};
+use fluvio_types::SpuId;
+
+/// number of last written requests kept for each producer to detect duplicates
+const MAX_CACHED_REQUESTS: usize = 5;
+
+/// producer state is removed if producer didn't write for this long
+const PRODUCER_EXPIRATION: Duration = Duration::from_secs(60 * 60);
+
+/// bits used for sequence part of producer id, rest is used for SPU id
+const PRODUCER_ID_SEQUENCE_BITS: u32 = 47;
+
+/// Allocates producer ids unique across the cluster.
+/// Upper bits are SPU id, lower bits are counter seeded with current time so ids are not reused after restart.
+#[derive(Debug)]
+pub struct ProducerIdGenerator {
+    spu_id: SpuId,
+    next: AtomicI64,
+}
+
+impl ProducerIdGenerator {
+    pub fn new(spu_id: SpuId) -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_millis() as i64)
+            .unwrap_or_default();
+        Self {
+            spu_id,
+            next: AtomicI64::new(seed),
+        }
+    }
+
+    pub fn next_id(&self) -> i64 {
+        let sequence_mask = (1_i64 << PRODUCER_ID_SEQUENCE_BITS) - 1;
+        let spu_part = (self.spu_id as i64 & 0xFFFF) << PRODUCER_ID_SEQUENCE_BITS;
+        spu_part | (self.next.fetch_add(1, Ordering::SeqCst) & sequence_mask)
+    }
+
+    /// check if producer id was allocated by this SPU
+    pub fn is_local(&self, producer_id: i64) -> bool {
+        producer_id >= 0 && producer_id >> PRODUCER_ID_SEQUENCE_BITS == self.spu_id as i64 & 0xFFFF
+    }
+}
+
+/// Sequence range of batches in single produce request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProducerBatches {
+    producer_id: i64,
+    epoch: i16,
+    first_sequence: i32,
+    last_sequence: i32,
+}
+
+impl ProducerBatches {
+    /// Validate that batches are from same producer and have contiguous sequence.
+    /// Return None if batches were not stamped by idempotent producer.
+    pub fn try_from_batches<R>(batches: &[Batch<R>]) -> Result<Option<Self>, ErrorCode> {
+        let Some(first) = batches.first() else {
+            return Ok(None);
+        };
+        if !first.header.is_idempotent() {
+            if batches.iter().any(|batch| batch.header.is_idempotent()) {
+                return Err(ErrorCode::Other(
+                    "produce request mixes idempotent and non idempotent batches".to_owned(),
+                ));
+            }
+            return Ok(None);
+        }
+
+        let mut producer = Self {
+            producer_id: first.header.producer_id,
+            epoch: first.header.producer_epoch,
+            first_sequence: first.header.first_sequence,
+            last_sequence: first.header.last_sequence(),
+        };
+        for batch in batches.iter().skip(1) {
+            if batch.header.producer_id != producer.producer_id
+                || batch.header.producer_epoch != producer.epoch
+            {
+                return Err(ErrorCode::Other(
+                    "produce request contains batches from different producers".to_owned(),
+                ));
+            }
+            let expected = increment_sequence(producer.last_sequence, 1);
+            if batch.header.first_sequence != expected {
+                return Err(ErrorCode::OutOfOrderSequence {
+                    expected,
+                    received: batch.header.first_sequence,
+                });
+            }
+            producer.last_sequence = batch.header.last_sequence();
+        }
+        Ok(Some(producer))
+    }
+}
+
+/// Outcome of checking batches against producer state
+#[derive(Debug, PartialEq, Eq)]
+pub enum SequenceCheck {
+    /// batches continue the sequence and must be written
+    Accept,
+    /// batches were already written, these are offsets from original write
+    Duplicate { base_offset: Offset, leo: Offset },
+    /// batches were already written but original request is no longer cached,
+    /// so offsets of the write are unknown
+    AlreadyWritten,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct WrittenRequest {
+    first_sequence: i32,
+    last_sequence: i32,
+    base_offset: Offset,
+    leo: Offset,
+}
+
+#[derive(Debug)]
+struct ProducerState {
+    epoch: i16,
+    last_sequence: i32,
+    written: VecDeque<WrittenRequest>,
+    last_update: Instant,
+}
+
+/// Idempotent producer states of a leader replica
+#[derive(Debug, Default)]
+pub struct ProducerStates {
+    producers: HashMap<i64, ProducerState>,
+}
+
+impl ProducerStates {
+    /// check if batches can be written
+    pub fn check(&self, batches: &ProducerBatches) -> Result<SequenceCheck, ErrorCode> {
+        let Some(state) = self.producers.get(&batches.producer_id) else {
+            return Ok(SequenceCheck::Accept);
+        };
+
+        if batches.epoch < state.epoch {
+            return Err(ErrorCode::InvalidProducerEpoch);
+        }
+        if batches.epoch > state.epoch {
+            return Ok(SequenceCheck::Accept);
+        }
+
+        if let Some(written) = state.written.iter().find(|written| {
+            written.first_sequence == batches.first_sequence
+                && written.last_sequence == batches.last_sequence
+        }) {
+            return Ok(SequenceCheck::Duplicate {
+                base_offset: written.base_offset,
+                leo: written.leo,
+            });
+        }
+
+        let expected = increment_sequence(state.last_sequence, 1);
+        if batches.first_sequence == expected {
+            Ok(SequenceCheck::Accept)
+        } else if sequence_at_or_before(batches.last_sequence, state.last_sequence)
+            && sequence_at_or_before(batches.first_sequence, batches.last_sequence)
+        {
+            // retry of acknowledged request which is older than cached requests
+            Ok(SequenceCheck::AlreadyWritten)
+        } else {
+            Err(ErrorCode::OutOfOrderSequence {
+                expected,
+                received: batches.first_sequence,
+            })
+        }
+    }
+
+    /// last epoch of producer written to the replica
+    pub fn epoch(&self, producer_id: i64) -> Option<i16> {
+        self.producers.get(&producer_id).map(|state| state.epoch)
+    }
+
+    /// record successful write of batches
+    pub fn update(&mut self, batches: &ProducerBatches, base_offset: Offset, leo: Offset) {
+        let now = Instant::now();
+        if !self.producers.contains_key(&batches.producer_id) {
+            self.producers
+                .retain(|_, state| now.duration_since(state.last_update) < PRODUCER_EXPIRATION);
+        }
+
+        let state = self
+            .producers
+            .entry(batches.producer_id)
+            .or_insert_with(|| ProducerState {
+                epoch: batches.epoch,
+                last_sequence: batches.last_sequence,
+                written: VecDeque::with_capacity(MAX_CACHED_REQUESTS),
+                last_update: now,
+            });
+        if state.epoch != batches.epoch {
+            state.epoch = batches.epoch;
+            state.written.clear();
+        }
+        state.last_sequence = batches.last_sequence;
+        state.last_update = now;
+        if state.written.len() == MAX_CACHED_REQUESTS {
+            state.written.pop_front();
+        }
+        state.written.push_back(WrittenRequest {
+            first_sequence: batches.first_sequence,
+            last_sequence: batches.last_sequence,
+            base_offset,
+            leo,
+        });
+    }
+}
+
+/// check if `sequence` is at or before `other`, sequences wrap around after i32::MAX
+fn sequence_at_or_before(sequence: i32, other: i32) -> bool {
+    let range = i32::MAX as i64 + 1;
+    (other as i64 - sequence as i64).rem_euclid(range) < range / 2
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::record::{Batch, Record};
+
+    use super::*;
+
+    fn batch(producer_id: i64, epoch: i16, first_sequence: i32, records: usize) -> Batch {
+        let mut batch = Batch::from(vec![Record::new("a"); records]);
+        batch.header.producer_id = producer_id;
+        batch.header.producer_epoch = epoch;
+        batch.header.first_sequence = first_sequence;
+        batch
+    }
+
+    fn producer_batches(batches: &[Batch]) -> ProducerBatches {
+        ProducerBatches::try_from_batches(batches)
+            .expect("valid batches")
+            .expect("idempotent batches")
+    }
+
+    #[test]
+    fn test_producer_batches() {
+        assert!(
+            ProducerBatches::try_from_batches(&[Batch::from(vec![Record::new("a")])])
+                .expect("valid")
+                .is_none()
+        );
+
+        let batches = producer_batches(&[batch(1, 0, 0, 2), batch(1, 0, 2, 3)]);
+        assert_eq!(batches.first_sequence, 0);
+        assert_eq!(batches.last_sequence, 4);
+
+        assert_eq!(
+            ProducerBatches::try_from_batches(&[batch(1, 0, 0, 2), batch(1, 0, 3, 1)]),
+            Err(ErrorCode::OutOfOrderSequence {
+                expected: 2,
+                received: 3
+            })
+        );
+        assert!(
+            ProducerBatches::try_from_batches(&[batch(1, 0, 0, 2), batch(2, 0, 2, 1)]).is_err()
+        );
+        assert!(
+            ProducerBatches::try_from_batches(&[
+                batch(1, 0, 0, 2),
+                Batch::from(vec![Record::new("a")])
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_producer_sequence_check() {
+        let mut states = ProducerStates::default();
+
+        let first = producer_batches(&[batch(1, 0, 0, 2)]);
+        assert_eq!(states.check(&first), Ok(SequenceCheck::Accept));
+        states.update(&first, 10, 12);
+
+        // retry of written request
+        assert_eq!(
+            states.check(&first),
+            Ok(SequenceCheck::Duplicate {
+                base_offset: 10,
+                leo: 12
+            })
+        );
+
+        // gap in sequence
+        let gap = producer_batches(&[batch(1, 0, 3, 1)]);
+        assert_eq!(
+            states.check(&gap),
+            Err(ErrorCode::OutOfOrderSequence {
+                expected: 2,
+                received: 3
+            })
+        );
+
+        let second = producer_batches(&[batch(1, 0, 2, 1)]);
+        assert_eq!(states.check(&second), Ok(SequenceCheck::Accept));
+        states.update(&second, 12, 13);
+
+        // retry of part of written request is acknowledged, not rejected
+        assert_eq!(
+            states.check(&producer_batches(&[batch(1, 0, 1, 1)])),
+            Ok(SequenceCheck::AlreadyWritten)
+        );
+
+        // other producers are independent
+        let other = producer_batches(&[batch(2, 0, 5, 1)]);
+        assert_eq!(states.check(&other), Ok(SequenceCheck::Accept));
+
+        // new epoch restarts sequence, old epoch is fenced
+        let new_epoch = producer_batches(&[batch(1, 1, 0, 1)]);
+        assert_eq!(states.check(&new_epoch), Ok(SequenceCheck::Accept));
+        states.update(&new_epoch, 13, 14);
+        assert_eq!(
+            states.check(&producer_batches(&[batch(1, 0, 3, 1)])),
+            Err(ErrorCode::InvalidProducerEpoch)
+        );
+    }
+
+    #[test]
+    fn test_sequence_at_or_before() {
+        assert!(sequence_at_or_before(1, 1));
+        assert!(sequence_at_or_before(1, 5));
+        assert!(!sequence_at_or_before(5, 1));
+        // wrap around
+        assert!(sequence_at_or_before(i32::MAX, 2));
+        assert!(!sequence_at_or_before(2, i32::MAX));
+    }
+
+    #[test]
+    fn test_producer_id_generator() {
+        let generator = ProducerIdGenerator::new(5);
+        let first = generator.next_id();
+        let second = generator.next_id();
+        assert!(first >= 0);
+        assert_ne!(first, second);
+        assert_eq!(first >> PRODUCER_ID_SEQUENCE_BITS, 5);
+        assert_ne!(
+            ProducerIdGenerator::new(6).next_id() >> PRODUCER_ID_SEQUENCE_BITS,
+            first >> PRODUCER_ID_SEQUENCE_BITS
+        );
+        assert!(generator.is_local(first));
+        assert!(!ProducerIdGenerator::new(6).is_local(first));
+    }
+}
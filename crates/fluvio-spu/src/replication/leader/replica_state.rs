@@ -6,15 +6,18 @@ use std::{
 };
 use std::iter::FromIterator;
 use std::fmt;
+use std::time::{Duration, Instant};
 
 use async_lock::Mutex;
+use chrono::Utc;
 use fluvio_controlplane::{replica::Replica, sc_api::update_lrs::LrsRequest};
 use tracing::{debug, error, warn};
 use tracing::instrument;
 use async_lock::RwLock;
 use anyhow::{Result, Context};
 
-use fluvio_protocol::record::{RecordSet, Offset, ReplicaKey, RawRecords, Batch};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{RecordSet, Offset, ReplicaKey, RawRecords, Batch, ControlRecordType};
 use fluvio_controlplane_metadata::partition::{PartitionMirrorConfig, PartitionStatus, ReplicaStatus};
 use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig};
 use fluvio_types::{
@@ -38,6 +41,7 @@ use crate::replication::follower::sync::{PeerFileTopicResponse, PeerFilePartitio
 use crate::storage::SharableReplicaStorage;
 
 use super::FollowerNotifier;
+use super::ProducerStates;
 
 pub type SharedLeaderState<S> = LeaderReplicaState<S>;
 pub type SharedFileLeaderState = LeaderReplicaState<FileReplica>;
@@ -55,6 +59,36 @@ pub struct LeaderReplicaState<S> {
     sm_ctx: Option<SharedSmartModuleContext>,
     consumer_offset_publishers: Arc<Mutex<Vec<WeakSharedOffsetPublisher>>>,
     mirror_controller_state: Option<SharedMirrorControllerState>,
+    producer_states: Arc<Mutex<ProducerStates>>,
+    throttles: Arc<std::sync::Mutex<BTreeMap<SpuId, ThrottleState>>>,
+    follower_syncs: Arc<std::sync::Mutex<BTreeMap<SpuId, FollowerSync>>>,
+    truncations: Arc<std::sync::Mutex<BTreeMap<SpuId, Offset>>>,
+}
+
+/// When follower was last caught up with leader, it is in sync if this is within replica lag time max.
+/// Follower which reports leo reached by leader at its previous report was caught up at that time.
+#[derive(Debug, Clone, Copy)]
+struct FollowerSync {
+    last_caught_up: Option<Instant>,
+    last_report: Instant,
+    leader_leo_at_last_report: Offset,
+}
+
+/// Pacing of records sent to replica which is catching up while partition is reassigned
+#[derive(Debug, Clone, Copy)]
+struct ThrottleState {
+    send_after: Instant,
+    wakeup_scheduled: bool,
+}
+
+/// whether records can be sent to follower
+#[derive(Debug, PartialEq, Eq)]
+pub enum FollowerThrottle {
+    Ready,
+    /// records are held back, follower must be notified again after delay
+    Delay(Duration),
+    /// records are held back and notification is already scheduled
+    Scheduled,
 }
 
 impl<S> Clone for LeaderReplicaState<S> {
@@ -69,6 +103,10 @@ impl<S> Clone for LeaderReplicaState<S> {
             sm_ctx: self.sm_ctx.clone(),
             consumer_offset_publishers: self.consumer_offset_publishers.clone(),
             mirror_controller_state: self.mirror_controller_state.clone(),
+            producer_states: self.producer_states.clone(),
+            throttles: self.throttles.clone(),
+            follower_syncs: self.follower_syncs.clone(),
+            truncations: self.truncations.clone(),
         }
     }
 }
@@ -105,6 +143,13 @@ fn ids_to_map(leader_id: SpuId, follower_ids: HashSet<SpuId>) -> BTreeMap<SpuId,
     followers
 }
 
+/// Replicas which must have records before high watermark moves.
+/// New replicas of reassignment are excluded until they are in sync
+fn in_sync_replica_count(replica: &Replica) -> u16 {
+    let count = replica.replicas.len() - replica.adding_replicas().len();
+    count.max(1) as u16
+}
+
 impl<S> LeaderReplicaState<S>
 where
     S: ReplicaStorage,
@@ -118,7 +163,7 @@ where
         inner: SharableReplicaStorage<S>,
     ) -> Uninit<Self> {
         debug!(?replica, "replica storage");
-        let in_sync_replica = replica.replicas.len() as u16;
+        let in_sync_replica = in_sync_replica_count(&replica);
         let follower_ids = HashSet::from_iter(replica.replicas.clone());
         let followers = ids_to_map(replica.leader, follower_ids);
         debug!(?followers, "leader followers");
@@ -140,6 +185,10 @@ where
             sm_ctx: None,
             consumer_offset_publishers: Arc::new(Mutex::new(Vec::new())),
             mirror_controller_state: None,
+            producer_states: Arc::new(Mutex::new(ProducerStates::default())),
+            throttles: Arc::new(std::sync::Mutex::new(BTreeMap::new())),
+            follower_syncs: Arc::new(std::sync::Mutex::new(BTreeMap::new())),
+            truncations: Arc::new(std::sync::Mutex::new(BTreeMap::new())),
         })
     }
 
@@ -156,6 +205,7 @@ where
         let mut replica_config: S::ReplicaConfig = config.into();
         replica_config.update_from_replica(&replica);
         let inner = SharableReplicaStorage::create(replica.id.clone(), replica_config).await?;
+        inner.delete_records(replica.log_start_offset).await?;
         let leader_replica = Self::new(replica, config.into(), status_update, inner);
         leader_replica.0.update_status().await;
         Ok(leader_replica)
@@ -182,6 +232,188 @@ where
         self.in_sync_replica = replica_count;
     }
 
+    /// Apply change of replicas by reassignment. Followers are added or dropped,
+    /// offsets of remaining followers are kept.
+    /// Returned state must replace this one in leaders state.
+    pub async fn update_replicas(&self, replica: Replica) -> Self {
+        let mut followers = self.followers.write().await;
+        followers.retain(|id, _| replica.replicas.contains(id));
+        for id in replica.replicas.iter().filter(|id| **id != replica.leader) {
+            followers.entry(*id).or_default();
+        }
+        drop(followers);
+        self.throttles
+            .lock()
+            .expect("throttles")
+            .retain(|id, _| replica.adding_replicas().contains(id));
+        self.follower_syncs
+            .lock()
+            .expect("follower syncs")
+            .retain(|id, _| replica.replicas.contains(id));
+        self.truncations
+            .lock()
+            .expect("truncations")
+            .retain(|id, _| replica.replicas.contains(id));
+
+        let mut state = self.clone();
+        state.in_sync_replica = in_sync_replica_count(&replica);
+        debug!(
+            in_sync_replica = state.in_sync_replica,
+            replica = %replica.id,
+            followers = ?replica.replicas,
+            "updated leader replicas"
+        );
+        state.replica = replica;
+        state
+    }
+
+    /// track when follower was last caught up with leader
+    fn record_follower_report(
+        &self,
+        follower_id: SpuId,
+        follower_leo: Offset,
+        leader_leo: Offset,
+        now: Instant,
+    ) {
+        let mut syncs = self.follower_syncs.lock().expect("follower syncs");
+        let sync = syncs.entry(follower_id).or_insert(FollowerSync {
+            last_caught_up: None,
+            last_report: now,
+            leader_leo_at_last_report: leader_leo,
+        });
+        if follower_leo >= leader_leo {
+            sync.last_caught_up = Some(now);
+        } else if follower_leo >= sync.leader_leo_at_last_report {
+            sync.last_caught_up = sync.last_caught_up.max(Some(sync.last_report));
+        }
+        sync.last_report = now;
+        sync.leader_leo_at_last_report = leader_leo;
+    }
+
+    /// leader and followers which have caught up with leader within replica lag time max.
+    /// Follower is not in sync until it has reported to this leader.
+    pub fn in_sync_replicas(&self) -> u16 {
+        self.in_sync_replicas_at(Instant::now())
+    }
+
+    fn in_sync_replicas_at(&self, now: Instant) -> u16 {
+        let lag_time_max = Duration::from_millis(self.config.replica_lag_time_max_ms);
+        let syncs = self.follower_syncs.lock().expect("follower syncs");
+        let in_sync_followers = self
+            .replica
+            .replicas
+            .iter()
+            .filter(|id| **id != self.leader())
+            .filter_map(|id| syncs.get(id)?.last_caught_up)
+            .filter(|caught_up| now.saturating_duration_since(*caught_up) <= lag_time_max)
+            .count();
+        1 + in_sync_followers as u16
+    }
+
+    /// Produce which waits for commit requires min in-sync replicas set by topic
+    pub fn check_min_in_sync_replicas(&self) -> Result<(), ErrorCode> {
+        let Some(min_in_sync) = self.replica.min_in_sync_replicas else {
+            return Ok(());
+        };
+        let in_sync = self.in_sync_replicas();
+        if in_sync < min_in_sync {
+            Err(ErrorCode::NotEnoughReplicas {
+                in_sync,
+                min_in_sync,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// max bytes per second sent to follower, if it is catching up during reassignment
+    fn throttle_rate(&self, follower_id: &SpuId) -> Option<u64> {
+        let reassignment = self.replica.reassignment.as_ref()?;
+        (reassignment.throttle_bytes_per_sec > 0
+            && reassignment.adding_replicas.contains(follower_id))
+        .then_some(reassignment.throttle_bytes_per_sec)
+    }
+
+    /// check if records can be sent to follower now
+    pub fn follower_throttle(&self, follower_id: &SpuId) -> FollowerThrottle {
+        if self.throttle_rate(follower_id).is_none() {
+            return FollowerThrottle::Ready;
+        }
+        let mut throttles = self.throttles.lock().expect("throttles");
+        let Some(state) = throttles.get_mut(follower_id) else {
+            return FollowerThrottle::Ready;
+        };
+        let now = Instant::now();
+        if state.send_after <= now {
+            state.wakeup_scheduled = false;
+            FollowerThrottle::Ready
+        } else if state.wakeup_scheduled {
+            FollowerThrottle::Scheduled
+        } else {
+            state.wakeup_scheduled = true;
+            FollowerThrottle::Delay(state.send_after - now)
+        }
+    }
+
+    /// hold back next records to follower until sent bytes are within rate
+    fn record_sent_bytes(&self, follower_id: &SpuId, bytes: u64) {
+        let Some(rate) = self.throttle_rate(follower_id) else {
+            return;
+        };
+        let now = Instant::now();
+        let mut throttles = self.throttles.lock().expect("throttles");
+        let state = throttles.entry(*follower_id).or_insert(ThrottleState {
+            send_after: now,
+            wakeup_scheduled: false,
+        });
+        state.send_after =
+            state.send_after.max(now) + Duration::from_secs_f64(bytes as f64 / rate as f64);
+    }
+
+    /// Find where follower's log diverges from leader using leader epoch of last batch in follower's log.
+    /// Follower's records after end of that epoch in leader's log were written by a previous leader
+    /// and never replicated here, follower must truncate them before records can be sent to it.
+    /// return true if follower must truncate
+    #[instrument(skip(self, notifier))]
+    pub async fn check_follower_divergence(
+        &self,
+        follower_id: SpuId,
+        follower_leo: Offset,
+        follower_epoch: Option<i32>,
+        notifier: &FollowerNotifier,
+    ) -> bool {
+        let Some(follower_epoch) = follower_epoch else {
+            return false;
+        };
+        let epoch_end = match self.storage.leader_epoch_end_offset(follower_epoch).await {
+            Some(end) => end.end_offset,
+            // log without epochs, only records beyond leader's log diverge
+            None => follower_leo,
+        };
+        let divergence = epoch_end.min(self.leo());
+
+        if divergence < follower_leo {
+            warn!(
+                follower_id,
+                follower_leo, follower_epoch, divergence, "follower log diverges from leader"
+            );
+            self.truncations
+                .lock()
+                .expect("truncations")
+                .insert(follower_id, divergence);
+            notifier
+                .notify_follower(&follower_id, self.id().clone())
+                .await;
+            true
+        } else {
+            self.truncations
+                .lock()
+                .expect("truncations")
+                .remove(&follower_id);
+            false
+        }
+    }
+
     /// update leader's state from follower's offset states
     /// if follower's state has been updated may result in leader's hw update
     /// return true if update has been updated, in this case, updates can be computed to followers
@@ -205,6 +437,12 @@ where
         // get follower info
         let mut followers = self.followers.write().await;
         let update = if let Some(current_follow_info) = followers.get_mut(&follower_id) {
+            self.record_follower_report(
+                follower_id,
+                follower_pos.leo,
+                leader_pos.leo,
+                Instant::now(),
+            );
             if current_follow_info.update(&follower_pos) {
                 // if our leo and hw is same there is no need to recompute hw
                 if !leader_pos.is_committed() {
@@ -248,6 +486,27 @@ where
     ) -> Option<PeerFileTopicResponse> {
         let leader_offset = self.as_offset();
 
+        let truncation = self
+            .truncations
+            .lock()
+            .expect("truncations")
+            .get(follower_id)
+            .copied();
+        if let Some(truncate_offset) = truncation {
+            debug!(truncate_offset, replica = %self.id(), "sending truncation");
+            return Some(PeerFileTopicResponse {
+                name: self.id().topic.to_owned(),
+                partitions: vec![PeerFilePartitionResponse {
+                    partition: self.id().partition,
+                    hw: leader_offset.hw,
+                    leo: leader_offset.leo,
+                    truncate_offset: Some(truncate_offset),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            });
+        }
+
         let reader = self.followers.read().await;
         if let Some(follower_info) = reader.get(follower_id) {
             if follower_info.is_valid() && !follower_info.is_same(&leader_offset) {
@@ -276,6 +535,7 @@ where
                             partition_response.hw = slice.end.hw;
                             partition_response.leo = slice.end.leo;
                             if let Some(file_slice) = slice.file_slice {
+                                self.record_sent_bytes(follower_id, file_slice.len());
                                 partition_response.records = file_slice.into();
                             }
                         }
@@ -325,8 +585,10 @@ where
             .try_into()
             .unwrap_or(PartitionStatus::SIZE_ERROR);
         let base_offset = storage_reader.get_log_start_offset();
+        let corrupt_batches = storage_reader.get_corrupt_batch_count() as u32;
 
         LrsRequest::new(self.id().to_owned(), leader, replicas, size, base_offset)
+            .with_corrupt_batches(corrupt_batches)
     }
 
     #[instrument(skip(self))]
@@ -341,6 +603,15 @@ where
         self.status_update.send(lrs).await
     }
 
+    /// delete records before offset and report new log start offset
+    #[instrument(skip(self))]
+    pub async fn delete_records(&self, offset: Offset) -> Result<()> {
+        let log_start_offset = self.storage.delete_records(offset).await?;
+        debug!(log_start_offset, "deleted records");
+        self.update_status().await;
+        Ok(())
+    }
+
     /// write records to storage
     /// then update our follower's leo
     #[instrument(skip(self, records, notifiers))]
@@ -353,6 +624,7 @@ where
         if records.total_records() == 0 {
             return Ok((self.hw(), self.leo(), 0));
         }
+        self.stamp_leader_epoch(records);
 
         let offsets = self
             .storage
@@ -365,16 +637,65 @@ where
         Ok(offsets)
     }
 
+    /// write transaction marker of producer, markers are not transformed by smartmodules.
+    /// Marker carries epoch of producer's batches in this replica.
+    #[instrument(skip(self, notifiers))]
+    pub async fn write_txn_marker(
+        &self,
+        producer_id: i64,
+        marker: ControlRecordType,
+        notifiers: &FollowerNotifier,
+    ) -> Result<Offset> {
+        let epoch = self
+            .producer_states
+            .lock()
+            .await
+            .epoch(producer_id)
+            .unwrap_or_default();
+        let timestamp = Utc::now().timestamp_millis();
+        let batch = Batch::control(producer_id, epoch, marker, timestamp);
+        let mut records = RecordSet::default().add(batch);
+        self.stamp_leader_epoch(&mut records);
+
+        let (base_offset, _, _) = self
+            .storage
+            .write_record_set(&mut records, self.in_sync_replica == 1)
+            .await?;
+
+        self.notify_followers(notifiers).await;
+        self.update_status().await;
+
+        Ok(base_offset)
+    }
+
+    /// batches written by this leader carry its epoch, so followers can find where their logs diverge
+    fn stamp_leader_epoch(&self, records: &mut RecordSet<RawRecords>) {
+        for batch in records.batches.iter_mut() {
+            batch.get_mut_header().partition_leader_epoch = self.replica.leader_epoch;
+        }
+    }
+
     async fn transform(&self, records: &mut RecordSet<RawRecords>) -> Result<()> {
         if let Some(ref sm_ctx) = self.sm_ctx {
-            let (sm_result, sm_error) =
+            let (sm_result, sm_error, _) =
                 process_record_set(sm_ctx.write().await.chain_mut(), records)?;
             if let Some(error) = sm_error {
                 return Err(error.into());
             }
+            // transaction index relies on producer of transactional batches
+            let producer = records
+                .batches
+                .first()
+                .map(|batch| batch.get_header().clone());
             records.batches.clear();
             if !sm_result.records().is_empty() {
-                let transformed_batch = Batch::<RawRecords>::try_from(sm_result)?;
+                let mut transformed_batch = Batch::<RawRecords>::try_from(sm_result)?;
+                if let Some(producer) = producer.filter(|header| header.is_transactional()) {
+                    let header = transformed_batch.get_mut_header();
+                    header.producer_id = producer.producer_id;
+                    header.producer_epoch = producer.producer_epoch;
+                    header.set_transactional();
+                }
                 records.batches.push(transformed_batch);
             }
         };
@@ -403,8 +724,7 @@ where
         self.followers.read().await.keys().cloned().collect()
     }
 
-    // get copy of followers_info for debugging
-    #[allow(unused)]
+    /// copy of offsets reported by followers
     pub async fn followers_info(&self) -> BTreeMap<SpuId, OffsetInfo> {
         self.followers.read().await.clone()
     }
@@ -414,6 +734,11 @@ where
         self.consumer_offset_publishers.clone()
     }
 
+    /// idempotent producer states, lock must be held from sequence check until write is done
+    pub fn producer_states(&self) -> Arc<Mutex<ProducerStates>> {
+        self.producer_states.clone()
+    }
+
     pub async fn register_offset_publisher(&self, offset_publisher: &SharedOffsetPublisher) {
         let mut publishers = self.consumer_offset_publishers.lock().await;
 
@@ -834,7 +1159,10 @@ mod test_leader {
     use async_trait::async_trait;
 
     use fluvio_controlplane_metadata::partition::ReplicaKey;
-    use fluvio_storage::{ReplicaStorage, ReplicaStorageConfig, OffsetInfo, ReplicaSlice};
+    use fluvio_storage::{
+        ReplicaStorage, ReplicaStorageConfig, OffsetInfo, ReplicaSlice, EpochEndOffset,
+        NO_LEADER_EPOCH,
+    };
     use fluvio_protocol::record::Offset;
     use fluvio_protocol::link::ErrorCode;
     use fluvio_protocol::record::BatchRecords;
@@ -896,6 +1224,13 @@ mod test_leader {
             })
         }
 
+        async fn find_offset_by_timestamp(
+            &self,
+            _timestamp: i64,
+        ) -> Result<Option<Offset>, ErrorCode> {
+            Ok(None)
+        }
+
         // do dummy implementations of write
         async fn write_recordset<R: BatchRecords>(
             &mut self,
@@ -915,6 +1250,10 @@ mod test_leader {
             (self.pos.hw * 100) as u64
         }
 
+        fn get_corrupt_batch_count(&self) -> usize {
+            0
+        }
+
         async fn update_high_watermark(
             &mut self,
             offset: Offset,
@@ -929,6 +1268,30 @@ mod test_leader {
             (self.pos.hw * 10) as Offset
         }
 
+        async fn delete_records(
+            &mut self,
+            _offset: Offset,
+        ) -> Result<Offset, fluvio_storage::StorageError> {
+            Ok(self.get_log_start_offset())
+        }
+
+        fn get_leader_epoch(&self) -> i32 {
+            NO_LEADER_EPOCH
+        }
+
+        fn leader_epoch_end_offset(&self, _leader_epoch: i32) -> Option<EpochEndOffset> {
+            None
+        }
+
+        async fn truncate(
+            &mut self,
+            offset: Offset,
+        ) -> Result<Offset, fluvio_storage::StorageError> {
+            self.pos.leo = self.pos.leo.min(offset);
+            self.pos.hw = self.pos.hw.min(self.pos.leo);
+            Ok(self.pos.leo)
+        }
+
         async fn remove(&self) -> Result<(), fluvio_storage::StorageError> {
             todo!()
         }
@@ -955,6 +1318,127 @@ mod test_leader {
         assert_eq!(state.in_sync_replica, 1);
     }
 
+    #[fluvio_future::test]
+    async fn test_leader_reassignment() {
+        use fluvio_controlplane_metadata::partition::PartitionReassignment;
+
+        let leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+
+        let replica: ReplicaKey = ("test", 1).into();
+        let state: LeaderReplicaState<MockStorage> = LeaderReplicaState::create(
+            Replica::new(replica.clone(), 5000, vec![5000, 5001]),
+            &leader_config,
+            StatusLrsMessageSink::shared(),
+        )
+        .await
+        .expect("state")
+        .0;
+        assert_eq!(state.in_sync_replica, 2);
+
+        // 5002 is catching up, it doesn't count for hw
+        let mut moving = Replica::new(replica.clone(), 5000, vec![5000, 5001, 5002]);
+        moving.reassignment = Some(PartitionReassignment {
+            target_replicas: vec![5000, 5002],
+            adding_replicas: vec![5002],
+            throttle_bytes_per_sec: 1000,
+        });
+        let state = state.update_replicas(moving).await;
+        assert_eq!(state.in_sync_replica, 2);
+        assert_eq!(state.live_replicas().await, vec![5001, 5002]);
+
+        // only adding replica is throttled
+        state.record_sent_bytes(&5001, 5000);
+        assert_eq!(state.follower_throttle(&5001), FollowerThrottle::Ready);
+        assert_eq!(state.follower_throttle(&5002), FollowerThrottle::Ready);
+        state.record_sent_bytes(&5002, 5000);
+        assert!(matches!(
+            state.follower_throttle(&5002),
+            FollowerThrottle::Delay(delay) if delay > Duration::from_secs(4)
+        ));
+        assert_eq!(state.follower_throttle(&5002), FollowerThrottle::Scheduled);
+
+        let state = state
+            .update_replicas(Replica::new(replica, 5000, vec![5000, 5002]))
+            .await;
+        assert_eq!(state.in_sync_replica, 2);
+        assert_eq!(state.live_replicas().await, vec![5002]);
+        assert_eq!(state.follower_throttle(&5002), FollowerThrottle::Ready);
+    }
+
+    #[fluvio_future::test]
+    async fn test_min_in_sync_replicas() {
+        use fluvio_controlplane_metadata::partition::PartitionReassignment;
+
+        let mut leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+        leader_config.replication.replica_lag_time_max_ms = 10_000;
+
+        let mut replica = Replica::new(("test", 1), 5000, vec![5000, 5001, 5002]);
+        replica.min_in_sync_replicas = Some(2);
+        let state: LeaderReplicaState<MockStorage> =
+            LeaderReplicaState::create(replica, &leader_config, StatusLrsMessageSink::shared())
+                .await
+                .expect("state")
+                .0;
+
+        // all replicas must have records before they are committed
+        assert_eq!(state.in_sync_replica, 3);
+
+        // followers are not in sync until they report
+        assert_eq!(state.in_sync_replicas(), 1);
+        assert_eq!(
+            state.check_min_in_sync_replicas(),
+            Err(ErrorCode::NotEnoughReplicas {
+                in_sync: 1,
+                min_in_sync: 2
+            })
+        );
+
+        // 5001 is caught up, 5002 is behind
+        let now = Instant::now();
+        state.record_follower_report(5001, 10, 10, now);
+        state.record_follower_report(5002, 5, 10, now);
+        assert_eq!(state.in_sync_replicas_at(now), 2);
+        assert!(state.check_min_in_sync_replicas().is_ok());
+
+        // 5002 reached leo leader had at its previous report
+        let later = now + Duration::from_secs(1);
+        state.record_follower_report(5002, 10, 20, later);
+        assert_eq!(state.in_sync_replicas_at(later), 3);
+
+        // no follower caught up within lag time max
+        let later = now + Duration::from_secs(20);
+        state.record_follower_report(5001, 15, 30, later);
+        assert_eq!(state.in_sync_replicas_at(later), 1);
+
+        // new replica of reassignment is not in sync until it catches up
+        let mut replica = Replica::new(("test", 1), 5000, vec![5000, 5001]);
+        replica.min_in_sync_replicas = Some(2);
+        replica.reassignment = Some(PartitionReassignment {
+            target_replicas: vec![5000, 5002],
+            adding_replicas: vec![5002],
+            throttle_bytes_per_sec: 0,
+        });
+        replica.replicas.push(5002);
+        let state: LeaderReplicaState<MockStorage> =
+            LeaderReplicaState::create(replica, &leader_config, StatusLrsMessageSink::shared())
+                .await
+                .expect("state")
+                .0;
+        assert_eq!(state.in_sync_replica, 2);
+        let now = Instant::now();
+        state.record_follower_report(5001, 10, 10, now);
+        state.record_follower_report(5002, 5, 10, now);
+        assert_eq!(state.in_sync_replicas_at(now), 2);
+        state.record_follower_report(5002, 10, 10, now);
+        assert_eq!(state.in_sync_replicas_at(now), 3);
+    }
+
     #[fluvio_future::test]
     async fn test_follower_update() {
         let leader_config = SpuConfig {
@@ -1206,4 +1690,66 @@ mod test_leader {
         assert!(f1.drain_replicas().await.is_empty());
         assert!(f2.drain_replicas().await.is_empty());
     }
+
+    #[fluvio_future::test]
+    async fn test_follower_divergence() {
+        use crate::core::GlobalContext;
+        use fluvio_controlplane_metadata::spu::SpuSpec;
+
+        let leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+        let gctx: Arc<GlobalContext<MockStorage>> =
+            GlobalContext::new_shared_context(leader_config);
+        gctx.spu_localstore().sync_all(vec![
+            SpuSpec::new_private_addr(5000, 9000, "localhost".to_owned()),
+            SpuSpec::new_private_addr(5001, 9001, "localhost".to_owned()),
+        ]);
+        gctx.sync_follower_update().await;
+        let notifier = gctx.follower_notifier();
+        let f1 = notifier.get(&5001).await.expect("5001");
+
+        let leader: LeaderReplicaState<MockStorage> = LeaderReplicaState::create(
+            Replica::new(("test", 1), 5000, vec![5000, 5001]),
+            gctx.config(),
+            StatusLrsMessageSink::shared(),
+        )
+        .await
+        .expect("state")
+        .0;
+        leader
+            .write_record_set(&mut create_raw_recordset(10), notifier)
+            .await
+            .expect("write");
+
+        // follower which doesn't track epochs is not checked
+        assert!(
+            !leader
+                .check_follower_divergence(5001, 12, None, notifier)
+                .await
+        );
+
+        // follower has records which leader doesn't have
+        assert!(
+            leader
+                .check_follower_divergence(5001, 12, Some(NO_LEADER_EPOCH), notifier)
+                .await
+        );
+        assert_eq!(f1.drain_replicas().await.len(), 1);
+        let updates = leader
+            .follower_updates(&5001, MAX_BYTES)
+            .await
+            .expect("truncation");
+        assert_eq!(updates.partitions[0].truncate_offset, Some(10));
+        assert_eq!(updates.partitions[0].leo, 10);
+
+        // follower has truncated
+        assert!(
+            !leader
+                .check_follower_divergence(5001, 10, Some(NO_LEADER_EPOCH), notifier)
+                .await
+        );
+        assert!(leader.follower_updates(&5001, MAX_BYTES).await.is_none());
+    }
 }
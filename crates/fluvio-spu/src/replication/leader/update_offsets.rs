@@ -14,7 +14,8 @@ pub struct UpdateOffsetRequest {
 
 impl Request for UpdateOffsetRequest {
     const API_KEY: u16 = LeaderPeerApiEnum::UpdateOffsets as u16;
-    const DEFAULT_API_VERSION: i16 = 0;
+    // leader epoch starts at version 2, since mirroring encodes ReplicaOffsetRequest at version 1
+    const DEFAULT_API_VERSION: i16 = 2;
     type Response = UpdateOffsetResponse;
 }
 
@@ -23,6 +24,9 @@ pub struct ReplicaOffsetRequest {
     pub replica: ReplicaKey,
     pub leo: Offset,
     pub hw: Offset,
+    /// leader epoch of last batch in follower's log, None if follower doesn't track epochs
+    #[fluvio(min_version = 2)]
+    pub leader_epoch: Option<i32>,
 }
 
 // no content, this is one way request
@@ -10,6 +10,8 @@ use fluvio_protocol::api::{RequestMessage, ApiMessage, RequestHeader};
 use super::fetch_consumer_offset_request::FetchConsumerOffsetRequest;
 use super::update_consumer_offset_request::UpdateConsumerOffsetRequest;
 use super::fetch_stream_request::FetchStreamRequest;
+use super::write_txn_marker_request::WriteTxnMarkerRequest;
+use super::fetch_batch_request::FetchBatchRequest;
 
 #[repr(u16)]
 #[derive(Eq, PartialEq, Debug, Encoder, Decoder, Clone, Copy)]
@@ -18,6 +20,8 @@ pub enum SPUPeerApiEnum {
     FetchStream = 0,
     FetchConsumerOffset = 1,
     UpdateConsumerOffset = 2,
+    WriteTxnMarker = 3,
+    FetchBatch = 4,
 }
 
 impl Default for SPUPeerApiEnum {
@@ -34,6 +38,10 @@ pub enum SpuPeerRequest {
     FetchConsumerOffset(RequestMessage<FetchConsumerOffsetRequest>),
     #[fluvio(tag = 2)]
     UpdateConsumerOffset(RequestMessage<UpdateConsumerOffsetRequest>),
+    #[fluvio(tag = 3)]
+    WriteTxnMarker(RequestMessage<WriteTxnMarkerRequest>),
+    #[fluvio(tag = 4)]
+    FetchBatch(RequestMessage<FetchBatchRequest>),
 }
 
 impl Default for SpuPeerRequest {
@@ -70,6 +78,13 @@ impl ApiMessage for SpuPeerRequest {
                     UpdateConsumerOffsetRequest::decode_from(src, version)?,
                 )))
             }
+            SPUPeerApiEnum::WriteTxnMarker => Ok(SpuPeerRequest::WriteTxnMarker(
+                RequestMessage::new(header, WriteTxnMarkerRequest::decode_from(src, version)?),
+            )),
+            SPUPeerApiEnum::FetchBatch => Ok(SpuPeerRequest::FetchBatch(RequestMessage::new(
+                header,
+                FetchBatchRequest::decode_from(src, version)?,
+            ))),
         }
     }
 }
@@ -0,0 +1,41 @@
+use std::io::Error as IoError;
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use tracing::{instrument, trace};
+
+use crate::core::DefaultSharedGlobalContext;
+
+use super::fetch_batch_request::{FetchBatchRequest, FetchBatchResponse};
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_fetch_batch_request(
+    req_msg: RequestMessage<FetchBatchRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<FetchBatchResponse>, IoError> {
+    let FetchBatchRequest { replica_id, offset } = &req_msg.request;
+
+    let result = if let Some(leader) = ctx.leaders_state().get(replica_id).await {
+        leader.read().await.read_batch(*offset).await
+    } else if let Some(follower) = ctx.followers_state().get(replica_id).await {
+        follower.read().await.read_batch(*offset).await
+    } else {
+        Err(ErrorCode::PartitionNotFound)
+    };
+
+    let response = match result {
+        Ok(batch) => FetchBatchResponse {
+            error_code: ErrorCode::None,
+            batch,
+        },
+        Err(error_code) => FetchBatchResponse {
+            error_code,
+            batch: None,
+        },
+    };
+    trace!(%replica_id, offset, %response, "fetch batch result");
+    Ok(RequestMessage::<FetchBatchRequest>::response_with_header(
+        &req_msg.header,
+        response,
+    ))
+}
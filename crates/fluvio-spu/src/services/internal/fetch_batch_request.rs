@@ -0,0 +1,41 @@
+use std::fmt;
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_spu_schema::COMMON_VERSION;
+
+use super::SPUPeerApiEnum;
+
+/// Sent by SPU with corrupt batch to other replicas of partition, to get healthy copy of it
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct FetchBatchRequest {
+    pub replica_id: ReplicaKey,
+    /// base offset of batch
+    pub offset: Offset,
+}
+
+impl Request for FetchBatchRequest {
+    const API_KEY: u16 = SPUPeerApiEnum::FetchBatch as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = FetchBatchResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct FetchBatchResponse {
+    pub error_code: ErrorCode,
+    /// raw batch with valid crc, none if replica doesn't have healthy batch at offset
+    pub batch: Option<Vec<u8>>,
+}
+
+impl fmt::Display for FetchBatchResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error: {:#?}, batch bytes: {:?}",
+            self.error_code,
+            self.batch.as_ref().map(|batch| batch.len())
+        )
+    }
+}
@@ -5,6 +5,10 @@ mod fetch_consumer_offset_request;
 mod fetch_consumer_offset_handler;
 mod update_consumer_offset_request;
 mod update_consumer_offset_handler;
+mod write_txn_marker_request;
+mod write_txn_marker_handler;
+mod fetch_batch_request;
+mod fetch_batch_handler;
 
 use tracing::info;
 
@@ -17,6 +21,8 @@ pub use self::fetch_stream_request::FetchStreamRequest;
 pub use self::fetch_stream_request::FetchStreamResponse;
 pub use self::fetch_consumer_offset_request::FetchConsumerOffsetRequest;
 pub use self::update_consumer_offset_request::UpdateConsumerOffsetRequest;
+pub use self::write_txn_marker_request::WriteTxnMarkerRequest;
+pub use self::fetch_batch_request::FetchBatchRequest;
 pub use self::api::SPUPeerApiEnum;
 pub use self::api::SpuPeerRequest;
 
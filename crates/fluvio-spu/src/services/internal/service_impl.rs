@@ -12,6 +12,8 @@ use crate::core::DefaultSharedGlobalContext;
 use crate::replication::leader::FollowerHandler;
 use crate::services::internal::fetch_consumer_offset_handler::handle_fetch_consumer_offset_request;
 use crate::services::internal::update_consumer_offset_handler::handle_update_consumer_offset_request;
+use crate::services::internal::write_txn_marker_handler::handle_write_txn_marker_request;
+use crate::services::internal::fetch_batch_handler::handle_fetch_batch_request;
 use super::SpuPeerRequest;
 use super::SPUPeerApiEnum;
 use super::FetchStreamResponse;
@@ -96,6 +98,18 @@ impl FluvioService for InternalService {
                 let api_version = req_msg.header.api_version();
                 let response = handle_update_consumer_offset_request(req_msg, ctx).await?;
                 sink.send_response(&response, api_version).await?;
+            },
+            SpuPeerRequest::WriteTxnMarker(req_msg) => {
+                trace!(producer_id = req_msg.request.producer_id, replica = %req_msg.request.replica_id, "write transaction marker request");
+                let api_version = req_msg.header.api_version();
+                let response = handle_write_txn_marker_request(req_msg, ctx).await?;
+                sink.send_response(&response, api_version).await?;
+            },
+            SpuPeerRequest::FetchBatch(req_msg) => {
+                trace!(offset = req_msg.request.offset, replica = %req_msg.request.replica_id, "fetch batch request");
+                let api_version = req_msg.header.api_version();
+                let response = handle_fetch_batch_request(req_msg, ctx).await?;
+                sink.send_response(&response, api_version).await?;
             }
 
         );
@@ -0,0 +1,36 @@
+use std::io::Error as IoError;
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ControlRecordType;
+use tracing::{instrument, trace};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::transaction::write_local_marker;
+
+use super::write_txn_marker_request::{WriteTxnMarkerRequest, WriteTxnMarkerResponse};
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_write_txn_marker_request(
+    req_msg: RequestMessage<WriteTxnMarkerRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<WriteTxnMarkerResponse>, IoError> {
+    let WriteTxnMarkerRequest {
+        replica_id,
+        producer_id,
+        commit,
+    } = &req_msg.request;
+
+    let marker = if *commit {
+        ControlRecordType::Commit
+    } else {
+        ControlRecordType::Abort
+    };
+    let error_code = match write_local_marker(&ctx, replica_id, *producer_id, marker).await {
+        Ok(_) => ErrorCode::None,
+        Err(err) => err,
+    };
+    trace!(%replica_id, producer_id, ?error_code, "transaction marker write result");
+    let response = WriteTxnMarkerResponse { error_code };
+    Ok(RequestMessage::<WriteTxnMarkerRequest>::response_with_header(&req_msg.header, response))
+}
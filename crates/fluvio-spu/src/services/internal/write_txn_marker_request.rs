@@ -0,0 +1,34 @@
+use std::fmt;
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_spu_schema::COMMON_VERSION;
+
+use super::SPUPeerApiEnum;
+
+/// Sent by transaction coordinator to leader of partition which is part of transaction
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct WriteTxnMarkerRequest {
+    pub replica_id: ReplicaKey,
+    pub producer_id: i64,
+    pub commit: bool,
+}
+
+impl Request for WriteTxnMarkerRequest {
+    const API_KEY: u16 = SPUPeerApiEnum::WriteTxnMarker as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = WriteTxnMarkerResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct WriteTxnMarkerResponse {
+    pub error_code: ErrorCode,
+}
+
+impl fmt::Display for WriteTxnMarkerResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error: {:#?}", self.error_code)
+    }
+}
@@ -6,8 +6,13 @@ use fluvio_spu_schema::produce::DefaultProduceRequest;
 use fluvio_spu_schema::fetch::DefaultFetchRequest;
 use fluvio_protocol::link::versions::ApiVersionKey;
 use fluvio_spu_schema::server::SpuServerApiKey;
+use fluvio_spu_schema::server::consumer_group::{
+    JoinGroupRequest, GroupHeartbeatRequest, LeaveGroupRequest, CONSUMER_GROUP_API,
+};
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
+use fluvio_spu_schema::server::producer::InitProducerIdRequest;
 use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchRequest;
+use fluvio_spu_schema::server::transaction::{AddTxnPartitionsRequest, EndTxnRequest, TRANSACTION_API};
 use fluvio_spu_schema::server::update_offset::UpdateOffsetsRequest;
 use fluvio_spu_schema::{ApiVersionsRequest, ApiVersionsResponse};
 
@@ -47,6 +52,36 @@ pub async fn handle_api_version_request(
         0,
         UpdateOffsetsRequest::DEFAULT_API_VERSION,
     ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::InitProducerId,
+        InitProducerIdRequest::DEFAULT_API_VERSION,
+        InitProducerIdRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::AddTxnPartitions,
+        TRANSACTION_API,
+        AddTxnPartitionsRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::EndTxn,
+        TRANSACTION_API,
+        EndTxnRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::JoinGroup,
+        CONSUMER_GROUP_API,
+        JoinGroupRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::GroupHeartbeat,
+        CONSUMER_GROUP_API,
+        GroupHeartbeatRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::LeaveGroup,
+        CONSUMER_GROUP_API,
+        LeaveGroupRequest::DEFAULT_API_VERSION,
+    ));
 
     trace!("Returning ApiVersionsResponse: {:#?}", &response);
     Ok(request.new_response(response))
@@ -1,14 +1,18 @@
+use std::time::Duration;
+
 use crate::services::public::StreamPublishers;
 
 #[derive(Debug)]
 pub(crate) struct ConnectionContext {
     stream_publishers: StreamPublishers,
+    throttle: Duration,
 }
 
 impl ConnectionContext {
     pub(crate) fn new() -> Self {
         Self {
             stream_publishers: StreamPublishers::new(),
+            throttle: Duration::ZERO,
         }
     }
 
@@ -19,4 +23,13 @@ impl ConnectionContext {
     pub(crate) fn stream_publishers_mut(&mut self) -> &mut StreamPublishers {
         &mut self.stream_publishers
     }
+
+    /// delay processing of next request on this connection due to quota violation
+    pub(crate) fn throttle(&mut self, delay: Duration) {
+        self.throttle = self.throttle.max(delay);
+    }
+
+    pub(crate) fn take_throttle(&mut self) -> Duration {
+        std::mem::take(&mut self.throttle)
+    }
 }
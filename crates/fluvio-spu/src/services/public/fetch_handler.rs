@@ -11,32 +11,75 @@ use fluvio_spu_schema::fetch::{
     FetchablePartitionResponse, FetchPartition, FetchableTopic, FetchableTopicResponse,
 };
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_controlplane_metadata::quota::QuotaKind;
+use fluvio_auth::{AuthContext, InstanceAction};
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::quota::QuotaClient;
 use crate::traffic::TrafficType;
 
+use super::conn_context::ConnectionContext;
+
 /// perform log fetch request using zero copy write
 #[instrument(
-    skip(request, ctx, sink),
+    skip(request, ctx, sink, auth, conn_ctx),
     fields(
         max_bytes = request.request.max_bytes,
     ),
 )]
-pub async fn handle_fetch_request(
+pub async fn handle_fetch_request<AC: AuthContext>(
     request: RequestMessage<FileFetchRequest>,
     ctx: DefaultSharedGlobalContext,
     sink: ExclusiveFlvSink,
+    auth: &AC,
+    conn_ctx: &mut ConnectionContext,
 ) -> Result<()> {
     let (header, fetch_request) = request.get_header_request();
     trace!("Handling FileFetchRequest: {:#?}", fetch_request);
     let mut fetch_response = FileFetchResponse::default();
 
     for topic_request in &fetch_request.topics {
+        if !auth
+            .allow_instance_action(
+                ObjectType::Topic,
+                InstanceAction::Consume,
+                &topic_request.name,
+            )
+            .await?
+        {
+            debug!(topic = %topic_request.name, "consume is not authorized");
+            fetch_response.topics.push(permission_denied(topic_request));
+            continue;
+        }
         let topic_response =
             handle_fetch_topic(&ctx, &fetch_request, topic_request, header.is_connector()).await?;
         fetch_response.topics.push(topic_response);
     }
 
+    let usage: Vec<(&str, u64)> = fetch_response
+        .topics
+        .iter()
+        .map(|topic| {
+            let bytes = topic
+                .partitions
+                .iter()
+                .map(|partition| partition.records.len() as u64)
+                .sum();
+            (topic.name.as_str(), bytes)
+        })
+        .collect();
+    let throttle = ctx.quota_manager().record_request(
+        &QuotaClient::new(header.client_id(), auth.principal()),
+        QuotaKind::ConsumeBytes,
+        &usage,
+    );
+    if !throttle.is_zero() {
+        debug!(throttle_ms = throttle.as_millis(), "consume quota exceeded");
+        fetch_response.throttle_time_ms = throttle.as_millis() as i32;
+        conn_ctx.throttle(throttle);
+    }
+
     let response =
         RequestMessage::<FileFetchRequest>::response_with_header(&header, fetch_response);
     trace!("Sending FileFetchResponse: {:#?}", response);
@@ -52,6 +95,22 @@ pub async fn handle_fetch_request(
     Ok(())
 }
 
+fn permission_denied(topic_request: &FetchableTopic) -> FetchableTopicResponse<FileRecordSet> {
+    FileTopicResponse {
+        name: topic_request.name.clone(),
+        partitions: topic_request
+            .fetch_partitions
+            .iter()
+            .map(|partition| FilePartitionResponse {
+                partition_index: partition.partition_index,
+                error_code: ErrorCode::PermissionDenied,
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
 #[instrument(
     skip(ctx, fetch_request, topic_request),
     fields(topic = %topic_request.name),
@@ -126,6 +185,7 @@ async fn handle_fetch_partition(
         Ok(slice) => {
             partition_response.high_watermark = slice.end.hw;
             partition_response.log_start_offset = slice.start;
+            partition_response.aborted = slice.aborted;
 
             if let Some(file_slice) = slice.file_slice {
                 metrics.outbound().increase(
@@ -133,6 +193,10 @@ async fn handle_fetch_partition(
                     (slice.end.hw - slice.start) as u64,
                     file_slice.len(),
                 );
+                metrics
+                    .partition(&replica_id)
+                    .consumed()
+                    .increase((slice.end.hw - slice.start) as u64, file_slice.len());
                 partition_response.records = file_slice.into();
             }
         }
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use tracing::{debug, instrument};
+use anyhow::Result;
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::consumer_group::{
+    JoinGroupRequest, JoinGroupResponse, GroupHeartbeatRequest, GroupHeartbeatResponse,
+    LeaveGroupRequest, LeaveGroupResponse,
+};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::group::{is_group_coordinator, partition_count};
+
+/// add consumer to group and return its partitions
+#[instrument(skip(request, ctx))]
+pub async fn handle_join_group_request(
+    request: RequestMessage<JoinGroupRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<JoinGroupResponse>> {
+    let JoinGroupRequest {
+        group,
+        member_id,
+        topic,
+        strategy,
+        session_timeout_ms,
+    } = &request.request;
+
+    let response = if !is_group_coordinator(&ctx).await {
+        JoinGroupResponse {
+            error_code: ErrorCode::NotGroupCoordinator,
+            ..Default::default()
+        }
+    } else {
+        let partitions = partition_count(&ctx, topic);
+        if partitions == 0 {
+            JoinGroupResponse {
+                error_code: ErrorCode::TopicNotFound,
+                ..Default::default()
+            }
+        } else {
+            match ctx
+                .groups()
+                .join(
+                    group,
+                    member_id,
+                    topic,
+                    *strategy,
+                    Duration::from_millis(*session_timeout_ms as u64),
+                    partitions,
+                )
+                .await
+            {
+                Ok(assignment) => {
+                    debug!(?assignment, "member joined group");
+                    JoinGroupResponse {
+                        error_code: ErrorCode::None,
+                        member_id: assignment.member_id,
+                        generation: assignment.generation,
+                        partitions: assignment.partitions,
+                    }
+                }
+                Err(error_code) => JoinGroupResponse {
+                    error_code,
+                    ..Default::default()
+                },
+            }
+        }
+    };
+    Ok(request.new_response(response))
+}
+
+/// keep membership alive, response carries current assignment of member
+#[instrument(skip(request, ctx))]
+pub async fn handle_group_heartbeat_request(
+    request: RequestMessage<GroupHeartbeatRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<GroupHeartbeatResponse>> {
+    let GroupHeartbeatRequest {
+        group, member_id, ..
+    } = &request.request;
+
+    let response = if !is_group_coordinator(&ctx).await {
+        GroupHeartbeatResponse {
+            error_code: ErrorCode::NotGroupCoordinator,
+            ..Default::default()
+        }
+    } else {
+        match ctx.groups().heartbeat(group, member_id).await {
+            Ok(assignment) => GroupHeartbeatResponse {
+                error_code: ErrorCode::None,
+                generation: assignment.generation,
+                partitions: assignment.partitions,
+            },
+            Err(error_code) => GroupHeartbeatResponse {
+                error_code,
+                ..Default::default()
+            },
+        }
+    };
+    Ok(request.new_response(response))
+}
+
+/// remove consumer from group
+#[instrument(skip(request, ctx))]
+pub async fn handle_leave_group_request(
+    request: RequestMessage<LeaveGroupRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<LeaveGroupResponse>> {
+    let LeaveGroupRequest { group, member_id } = &request.request;
+
+    let error_code = if !is_group_coordinator(&ctx).await {
+        ErrorCode::NotGroupCoordinator
+    } else {
+        match ctx.groups().leave(group, member_id).await {
+            Ok(_) => ErrorCode::None,
+            Err(err) => err,
+        }
+    };
+    Ok(request.new_response(LeaveGroupResponse { error_code }))
+}
@@ -5,6 +5,8 @@ mod offset_request;
 mod offset_update;
 mod stream_fetch;
 mod consumer_handler;
+mod transaction_handler;
+mod group_handler;
 
 #[cfg(test)]
 mod tests;
@@ -27,6 +29,8 @@ use fluvio_service::{FluvioApiServer, FluvioService, ConnectInfo, call_service};
 use fluvio_spu_schema::server::SpuServerRequest;
 use fluvio_spu_schema::server::SpuServerApiKey;
 use fluvio_types::event::StickyEvent;
+use fluvio_types::SpuId;
+use fluvio_future::timer::sleep;
 
 use crate::core::DefaultSharedGlobalContext;
 use crate::mirroring::home::connection::MirrorHomeHandler;
@@ -36,7 +40,11 @@ use crate::services::public::consumer_handler::handle_delete_consumer_offset_req
 use crate::services::public::consumer_handler::handle_fetch_consumer_offsets_request;
 use crate::services::public::consumer_handler::handle_update_consumer_offset_request;
 use self::api_versions::handle_api_version_request;
-use self::produce_handler::handle_produce_request;
+use self::produce_handler::{handle_produce_request, handle_init_producer_id_request};
+use self::transaction_handler::{handle_add_txn_partitions_request, handle_end_txn_request};
+use self::group_handler::{
+    handle_join_group_request, handle_group_heartbeat_request, handle_leave_group_request,
+};
 use self::fetch_handler::handle_fetch_request;
 use self::offset_request::handle_offset_request;
 use self::offset_update::handle_offset_update;
@@ -94,6 +102,7 @@ where
         mut socket: FluvioSocket,
         _connection: ConnectInfo,
     ) -> Result<()> {
+        let _connection = context.global_ctx.metrics().track_connection();
         let auth_context = context
             .auth
             .create_auth_context(&mut socket)
@@ -114,6 +123,7 @@ where
             let mut conn_ctx = ConnectionContext::new();
 
             let context = &context.global_ctx;
+            let auth = &service_context.auth;
 
             loop {
                 let event = event_stream.next().await;
@@ -135,13 +145,24 @@ where
                             ),
                             SpuServerRequest::ProduceRequest(request) => call_service!(
                                 request,
-                                handle_produce_request(request, context.clone()),
+                                handle_produce_request(
+                                    request,
+                                    context.clone(),
+                                    auth,
+                                    &mut conn_ctx
+                                ),
                                 shared_sink,
                                 "ProduceRequest"
                             ),
                             SpuServerRequest::FileFetchRequest(request) => {
-                                handle_fetch_request(request, context.clone(), shared_sink.clone())
-                                    .await?
+                                handle_fetch_request(
+                                    request,
+                                    context.clone(),
+                                    shared_sink.clone(),
+                                    auth,
+                                    &mut conn_ctx,
+                                )
+                                .await?
                             }
                             SpuServerRequest::FetchOffsetsRequest(request) => call_service!(
                                 request,
@@ -156,6 +177,7 @@ where
                                     &mut conn_ctx,
                                     shared_sink.clone(),
                                     shutdown.clone(),
+                                    auth,
                                 )
                                 .await?;
                             }
@@ -193,12 +215,55 @@ where
                                     "FetchConsumersRequest"
                                 )
                             }
+                            SpuServerRequest::InitProducerIdRequest(request) => call_service!(
+                                request,
+                                handle_init_producer_id_request(request, context.clone()),
+                                shared_sink,
+                                "InitProducerIdRequest"
+                            ),
+                            SpuServerRequest::AddTxnPartitionsRequest(request) => call_service!(
+                                request,
+                                handle_add_txn_partitions_request(request, context.clone()),
+                                shared_sink,
+                                "AddTxnPartitionsRequest"
+                            ),
+                            SpuServerRequest::EndTxnRequest(request) => call_service!(
+                                request,
+                                handle_end_txn_request(request, context.clone()),
+                                shared_sink,
+                                "EndTxnRequest"
+                            ),
+                            SpuServerRequest::JoinGroupRequest(request) => call_service!(
+                                request,
+                                handle_join_group_request(request, context.clone()),
+                                shared_sink,
+                                "JoinGroupRequest"
+                            ),
+                            SpuServerRequest::GroupHeartbeatRequest(request) => call_service!(
+                                request,
+                                handle_group_heartbeat_request(request, context.clone()),
+                                shared_sink,
+                                "GroupHeartbeatRequest"
+                            ),
+                            SpuServerRequest::LeaveGroupRequest(request) => call_service!(
+                                request,
+                                handle_leave_group_request(request, context.clone()),
+                                shared_sink,
+                                "LeaveGroupRequest"
+                            ),
                             SpuServerRequest::StartMirrorRequest(request) => {
                                 // send mirror mode, afer that mirror cycle will be started
                                 mirror_request = Some(request);
                                 break;
                             }
                         }
+
+                        // connection is muted while client is throttled by quota
+                        let throttle = conn_ctx.take_throttle();
+                        if !throttle.is_zero() {
+                            debug!(throttle_ms = throttle.as_millis(), "throttling connection");
+                            sleep(throttle).await;
+                        }
                     }
                     Some(Err(e)) => {
                         debug!(
@@ -225,7 +290,7 @@ where
     }
 }
 
-async fn send_private_request_to_leader<R: Request>(
+pub(crate) async fn send_private_request_to_leader<R: Request>(
     ctx: &DefaultSharedGlobalContext,
     replica_id: &ReplicaKey,
     req: R,
@@ -234,15 +299,20 @@ async fn send_private_request_to_leader<R: Request>(
         Some(replica) => replica.leader,
         None => return Err(ErrorCode::TopicNotFound),
     };
+    send_private_request_to_spu(ctx, spu, req).await
+}
+
+pub(crate) async fn send_private_request_to_spu<R: Request>(
+    ctx: &DefaultSharedGlobalContext,
+    spu: SpuId,
+    req: R,
+) -> Result<R::Response, ErrorCode> {
     let Some(spu_spec) = ctx.spu_localstore().spec(&spu) else {
         return Err(ErrorCode::SpuNotFound);
     };
-    let leader_endpoint = spu_spec.private_endpoint.to_string();
-    debug!(
-        spu,
-        leader_endpoint, "send private request to replica leader"
-    );
-    let mut socket = FluvioSocket::connect(&leader_endpoint)
+    let endpoint = spu_spec.private_endpoint.to_string();
+    debug!(spu, endpoint, "send private request to spu");
+    let mut socket = FluvioSocket::connect(&endpoint)
         .await
         .map_err(|e| ErrorCode::Other(e.to_string()))?;
 
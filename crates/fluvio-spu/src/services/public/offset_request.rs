@@ -50,6 +50,19 @@ pub async fn handle_offset_request(
                 partition_response.start_offset = start_offset;
                 partition_response.last_stable_offset = hw;
 
+                if let Some(timestamp) = partition_req.timestamp {
+                    match replica.find_offset_by_timestamp(timestamp).await {
+                        Ok(offset) => {
+                            debug!(timestamp, ?offset, "timestamp offset");
+                            partition_response.timestamp_offset = offset;
+                        }
+                        Err(e) => {
+                            error!(timestamp, "timestamp offset lookup failed: {e:?}");
+                            partition_response.error_code = e;
+                        }
+                    }
+                }
+
                 // This is only for compatibility with older clients
                 // now we're usign `FetchConsumerOffsetsRequest` to fetch consumer offset
                 #[allow(deprecated)]
@@ -7,6 +7,7 @@ use tracing::instrument;
 use anyhow::{anyhow, Result};
 
 use fluvio_protocol::api::{RequestKind, RequestHeader};
+use fluvio_protocol::Encoder;
 use fluvio_spu_schema::Isolation;
 use fluvio_protocol::record::{BatchRecords, Offset, Batch, RawRecords};
 use fluvio::Compression;
@@ -17,15 +18,21 @@ use fluvio_spu_schema::produce::{
     DefaultProduceRequest, DefaultTopicRequest,
 };
 use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
+use fluvio_smartmodule::dataplane::smartmodule::SmartModuleErrorPolicy;
+use fluvio_spu_schema::server::producer::{InitProducerIdRequest, InitProducerIdResponse};
 use fluvio_protocol::{api::RequestMessage, link::ErrorCode};
 use fluvio_protocol::api::ResponseMessage;
 use fluvio_protocol::record::RecordSet;
 use fluvio_controlplane_metadata::partition::{PartitionResolution, ReplicaKey};
 
 use fluvio_future::timer::sleep;
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_controlplane_metadata::quota::QuotaKind;
 
 use crate::core::DefaultSharedGlobalContext;
-use crate::replication::leader::SharedFileLeaderState;
+use crate::core::quota::QuotaClient;
+use crate::replication::leader::{SharedFileLeaderState, ProducerBatches, SequenceCheck};
 use crate::smartengine::batch::process_batch;
 use crate::smartengine::context::SmartModuleContext;
 use crate::smartengine::EngineError;
@@ -34,6 +41,8 @@ use crate::smartengine::produce_batch::ProduceBatchIterator;
 
 use crate::traffic::TrafficType;
 
+use super::conn_context::ConnectionContext;
+
 struct TopicWriteResult {
     topic: String,
     partitions: Vec<PartitionWriteResult>,
@@ -48,15 +57,17 @@ struct PartitionWriteResult {
 }
 
 #[instrument(
-    skip(request,ctx),
+    skip(request,ctx,auth,conn_ctx),
     fields(
         id = request.header.correlation_id(),
         client = %request.header.client_id()
     )
 )]
-pub async fn handle_produce_request(
+pub async fn handle_produce_request<AC: AuthContext>(
     request: RequestMessage<DefaultProduceRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+    conn_ctx: &mut ConnectionContext,
 ) -> Result<ResponseMessage<ProduceResponse>> {
     let (header, produce_request) = request.get_header_request();
     trace!("Handling ProduceRequest: {:#?}", produce_request);
@@ -64,9 +75,35 @@ pub async fn handle_produce_request(
     let smartmodules = produce_request.smartmodules;
 
     let mut topic_results = Vec::with_capacity(produce_request.topics.len());
+    let mut quota_usage = Vec::with_capacity(produce_request.topics.len());
     for topic_request in produce_request.topics.into_iter() {
-        let topic_result =
-            handle_produce_topic(&ctx, topic_request, &smartmodules, &header).await?;
+        if !auth
+            .allow_instance_action(
+                ObjectType::Topic,
+                InstanceAction::Produce,
+                &topic_request.name,
+            )
+            .await?
+        {
+            debug!(topic = %topic_request.name, "produce is not authorized");
+            topic_results.push(TopicWriteResult::permission_denied(topic_request));
+            continue;
+        }
+        // only bytes of authorized topics count against quota
+        let bytes = topic_request
+            .partitions
+            .iter()
+            .map(|partition| partition.records.write_size(header.api_version()) as u64)
+            .sum::<u64>();
+        quota_usage.push((topic_request.name.clone(), bytes));
+        let topic_result = handle_produce_topic(
+            &ctx,
+            topic_request,
+            produce_request.isolation,
+            &smartmodules,
+            &header,
+        )
+        .await?;
         topic_results.push(topic_result);
     }
     wait_for_acks(
@@ -76,11 +113,42 @@ pub async fn handle_produce_request(
         &ctx,
     )
     .await;
-    let response = into_response(topic_results);
+    let mut response = into_response(topic_results);
+
+    let usage: Vec<(&str, u64)> = quota_usage
+        .iter()
+        .map(|(topic, bytes)| (topic.as_str(), *bytes))
+        .collect();
+    let throttle = ctx.quota_manager().record_request(
+        &QuotaClient::new(header.client_id(), auth.principal()),
+        QuotaKind::ProduceBytes,
+        &usage,
+    );
+    if !throttle.is_zero() {
+        debug!(throttle_ms = throttle.as_millis(), "produce quota exceeded");
+        response.throttle_time_ms = throttle.as_millis() as i32;
+        conn_ctx.throttle(throttle);
+    }
+
     trace!("Returning ProduceResponse: {:#?}", &response);
     Ok(RequestMessage::<DefaultProduceRequest>::response_with_header(&header, response))
 }
 
+/// allocate producer id for idempotent producer
+#[instrument(skip(request, ctx))]
+pub async fn handle_init_producer_id_request(
+    request: RequestMessage<InitProducerIdRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<InitProducerIdResponse>> {
+    let producer_id = ctx.producer_ids().next_id();
+    debug!(producer_id, "allocated producer id");
+    let response = InitProducerIdResponse {
+        error_code: ErrorCode::None,
+        producer_id,
+    };
+    Ok(request.new_response(response))
+}
+
 #[instrument(
     skip(ctx, topic_request, smartmodules, header),
     fields(topic = %topic_request.name),
@@ -88,6 +156,7 @@ pub async fn handle_produce_request(
 async fn handle_produce_topic(
     ctx: &DefaultSharedGlobalContext,
     topic_request: DefaultTopicRequest,
+    isolation: Isolation,
     smartmodules: &[SmartModuleInvocation],
     header: &RequestHeader,
 ) -> Result<TopicWriteResult> {
@@ -125,6 +194,72 @@ async fn handle_produce_topic(
             }
         }
 
+        // records waiting for commit must be written to min in-sync replicas
+        if isolation == Isolation::ReadCommitted {
+            if let Err(err) = leader_state.check_min_in_sync_replicas() {
+                debug!(%replica_id, "produce rejected: {err}");
+                topic_result
+                    .partitions
+                    .push(PartitionWriteResult::error(replica_id, err));
+                continue;
+            }
+        }
+
+        if let Err(err) = validate_schema(ctx, &replica_id, &partition_request.records) {
+            debug!(%replica_id, "batch rejected: {err}");
+            topic_result
+                .partitions
+                .push(PartitionWriteResult::error(replica_id, err));
+            continue;
+        }
+
+        let producer_batches =
+            match ProducerBatches::try_from_batches(&partition_request.records.batches) {
+                Ok(producer_batches) => producer_batches,
+                Err(err) => {
+                    debug!(%replica_id, "invalid idempotent producer batches: {err}");
+                    topic_result
+                        .partitions
+                        .push(PartitionWriteResult::error(replica_id, err));
+                    continue;
+                }
+            };
+
+        // producer state stays locked until write is done, so concurrent retry is not written twice
+        let mut producer_states = match &producer_batches {
+            Some(batches) => {
+                let producer_states = leader_state.producer_states().lock_arc().await;
+                match producer_states.check(batches) {
+                    Ok(SequenceCheck::Accept) => Some(producer_states),
+                    Ok(SequenceCheck::Duplicate { base_offset, leo }) => {
+                        debug!(%replica_id, base_offset, "duplicate batches, already written");
+                        topic_result.partitions.push(PartitionWriteResult::ok(
+                            replica_id,
+                            base_offset,
+                            leo,
+                        ));
+                        continue;
+                    }
+                    Ok(SequenceCheck::AlreadyWritten) => {
+                        debug!(%replica_id, "duplicate batches, original offsets unknown");
+                        topic_result.partitions.push(PartitionWriteResult::error(
+                            replica_id,
+                            ErrorCode::DuplicateSequenceNumber,
+                        ));
+                        continue;
+                    }
+                    Err(err) => {
+                        debug!(%replica_id, "producer sequence rejected: {err}");
+                        topic_result
+                            .partitions
+                            .push(PartitionWriteResult::error(replica_id, err));
+                        continue;
+                    }
+                }
+            }
+            None => None,
+        };
+
         if let Err(err) = apply_smartmodules(
             &mut partition_request,
             smartmodules,
@@ -158,6 +293,16 @@ async fn handle_produce_topic(
             .await
         };
 
+        if let (Some(producer_states), Some(batches)) = (&mut producer_states, &producer_batches) {
+            if partition_response.error_code.is_ok() {
+                producer_states.update(
+                    batches,
+                    partition_response.base_offset,
+                    partition_response.leo,
+                );
+            }
+        }
+
         topic_result.partitions.push(partition_response);
     }
     Ok(topic_result)
@@ -176,6 +321,9 @@ async fn handle_produce_partition(
 ) -> PartitionWriteResult {
     trace!("Handling produce request for partition:");
 
+    #[cfg(feature = "otel")]
+    crate::telemetry::link_record_traces(&partition_request.records);
+
     let replica_metadata = match ctx.replica_localstore().spec(&replica_key) {
         Some(replica_metadata) => replica_metadata,
         None => {
@@ -201,6 +349,10 @@ async fn handle_produce_partition(
             metrics
                 .inbound()
                 .increase(is_connector, (leo - base_offset) as u64, bytes as u64);
+            metrics
+                .partition(&replica_key)
+                .produced()
+                .increase((leo - base_offset) as u64, bytes as u64);
 
             PartitionWriteResult::ok(replica_key, base_offset, leo)
         }
@@ -259,6 +411,17 @@ async fn apply_smartmodules(
     leader_state: &SharedFileLeaderState,
     ctx: &DefaultSharedGlobalContext,
 ) -> Result<(), ErrorCode> {
+    // SPU has no dead-letter topic for records of producer, producer must apply such SmartModules
+    if let Some(sm) = smartmodules
+        .iter()
+        .find(|sm| sm.error_policy == SmartModuleErrorPolicy::DeadLetter)
+    {
+        return Err(ErrorCode::SmartModuleInvalid {
+            error: "dead-letter error policy is not supported for SmartModules applied by SPU on produce".to_owned(),
+            name: sm.name.clone(),
+        });
+    }
+
     let Some(mut sm_ctx) =
         SmartModuleContext::try_from(smartmodules.to_vec(), api_version, ctx).await?
     else {
@@ -269,6 +432,10 @@ async fn apply_smartmodules(
 
     let records = &partition_request.records;
     let batches = &records.batches;
+    let schema_id = batches
+        .first()
+        .filter(|batch| batch.header.has_schema())
+        .map(Batch::schema_id);
 
     let mut batches = ProduceBatchIterator::new(batches);
 
@@ -278,12 +445,11 @@ async fn apply_smartmodules(
         usize::MAX,
         //
     ) {
-        Ok((result, sm_runtime_error)) => {
+        Ok((result, sm_runtime_error, _dead_letters)) => {
             if let Some(error) = sm_runtime_error {
                 return Err(ErrorCode::SmartModuleRuntimeError(Box::new(error)));
-            } else {
-                result
             }
+            result
         }
         Err(general_error) => {
             return Err(ErrorCode::Other(format!(
@@ -292,8 +458,11 @@ async fn apply_smartmodules(
         }
     };
 
-    let smartmoduled_records = Batch::<RawRecords>::try_from(sm_result)
+    let mut smartmoduled_records = Batch::<RawRecords>::try_from(sm_result)
         .map_err(|e| ErrorCode::Other(format!("Compression Error: {:?}", e)))?;
+    if let Some(schema_id) = schema_id {
+        smartmoduled_records.set_schema_id(schema_id);
+    }
 
     partition_request.records = RecordSet {
         batches: vec![smartmoduled_records],
@@ -302,6 +471,33 @@ async fn apply_smartmodules(
     Ok(())
 }
 
+/// topic which requires schema accepts only batches with schema id registered for its subject
+fn validate_schema<R: BatchRecords>(
+    ctx: &DefaultSharedGlobalContext,
+    replica_key: &ReplicaKey,
+    records: &RecordSet<R>,
+) -> Result<(), ErrorCode> {
+    let Some(subject) = ctx
+        .replica_localstore()
+        .spec(replica_key)
+        .and_then(|replica| replica.schema)
+    else {
+        return Ok(());
+    };
+    let schemas = ctx.schemas_localstore().read();
+    let schema = schemas.get(&subject);
+    for batch in &records.batches {
+        if !batch.header.has_schema() {
+            return Err(ErrorCode::SchemaRequired { subject });
+        }
+        let id = batch.schema_id().id();
+        if !schema.is_some_and(|schema| schema.spec.is_registered(id)) {
+            return Err(ErrorCode::SchemaNotRegistered { id, subject });
+        }
+    }
+    Ok(())
+}
+
 fn validate_records<R: BatchRecords>(
     records: &RecordSet<R>,
     compression: CompressionAlgorithm,
@@ -400,6 +596,25 @@ impl From<TopicWriteResult> for TopicProduceResponse {
     }
 }
 
+impl TopicWriteResult {
+    fn permission_denied(topic_request: DefaultTopicRequest) -> Self {
+        let partitions = topic_request
+            .partitions
+            .iter()
+            .map(|partition| {
+                PartitionWriteResult::error(
+                    ReplicaKey::new(topic_request.name.clone(), partition.partition_index),
+                    ErrorCode::PermissionDenied,
+                )
+            })
+            .collect();
+        Self {
+            topic: topic_request.name,
+            partitions,
+        }
+    }
+}
+
 impl PartitionWriteResult {
     fn error(replica_id: ReplicaKey, error_code: ErrorCode) -> Self {
         Self {
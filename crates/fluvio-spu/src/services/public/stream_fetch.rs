@@ -1,19 +1,23 @@
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use tracing::{debug, error, instrument, trace, warn};
 use tokio::select;
 
 use fluvio_compression::CompressionError;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_controlplane_metadata::quota::QuotaKind;
+use fluvio_auth::{AuthContext, InstanceAction};
 use fluvio_types::event::{
     offsets::{OffsetPublisher, INIT_OFFSET, TOPIC_DELETED},
     StickyEvent,
 };
 use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
 use fluvio_protocol::{
     api::{RequestMessage, RequestHeader},
-    record::{RecordSet, Offset, RawRecords},
+    record::{RecordSet, Offset, RawRecords, RecordHeaders},
 };
 use fluvio_protocol::link::{ErrorCode, smartmodule::SmartModuleTransformRuntimeError};
 use fluvio_protocol::record::Batch;
@@ -22,6 +26,7 @@ use fluvio_storage::iterators::FileBatchIterator;
 use fluvio_spu_schema::{
     server::stream_fetch::{
         DefaultStreamFetchRequest, FileStreamFetchRequest, StreamFetchRequest, StreamFetchResponse,
+        RECORD_HEADERS_API,
     },
     fetch::{FilePartitionResponse, FetchablePartitionResponse},
     Isolation,
@@ -35,6 +40,7 @@ use crate::services::public::conn_context::ConnectionContext;
 use crate::smartengine::context::SmartModuleContext;
 use crate::smartengine::batch::process_batch;
 use crate::core::metrics::SpuMetrics;
+use crate::core::quota::{QuotaClient, QuotaManager};
 use crate::traffic::TrafficType;
 
 /// Fetch records as stream
@@ -50,21 +56,44 @@ pub struct StreamFetchHandler {
     leader_state: SharedFileLeaderState,
     stream_id: u32,
     metrics: Arc<SpuMetrics>,
+    quota_client: QuotaClient,
+    quotas: Arc<QuotaManager>,
 }
 
 impl StreamFetchHandler {
     /// handle fluvio continuous fetch request
-    pub(crate) async fn start(
+    pub(crate) async fn start<AC: AuthContext>(
         request: RequestMessage<FileStreamFetchRequest>,
         ctx: DefaultSharedGlobalContext,
         conn_ctx: &mut ConnectionContext,
         sink: ExclusiveFlvSink,
         end_event: Arc<StickyEvent>,
+        auth: &AC,
     ) -> Result<(), SocketError> {
         let (header, msg) = request.get_header_request();
         let replica = ReplicaKey::new(msg.topic.clone(), msg.partition);
 
-        if let Some(leader_state) = ctx.leaders_state().get(&replica).await {
+        let authorized = auth
+            .allow_instance_action(ObjectType::Topic, InstanceAction::Consume, &replica.topic)
+            .await
+            .map_err(std::io::Error::from)?;
+
+        let leader_state = if authorized {
+            ctx.leaders_state().get(&replica).await
+        } else {
+            debug!(topic = %replica.topic, "consume is not authorized");
+            None
+        };
+
+        if let Some(leader_state) = leader_state {
+            let quota_client = QuotaClient::new(header.client_id(), auth.principal());
+            let throttle = ctx.quota_manager().record_request(
+                &quota_client,
+                QuotaKind::ConsumeBytes,
+                &[(replica.topic.as_str(), 0)],
+            );
+            conn_ctx.throttle(throttle);
+
             let (stream_id, offset_publisher) = conn_ctx
                 .stream_publishers_mut()
                 .create_new_publisher(msg.topic.clone(), msg.partition, msg.consumer_id.clone())
@@ -86,6 +115,7 @@ impl StreamFetchHandler {
                     replica,
                     consumer_offset_listener,
                     msg,
+                    quota_client,
                 )
                 .await
                 {
@@ -94,15 +124,21 @@ impl StreamFetchHandler {
                 }
             });
         } else {
-            debug!(topic = %replica.topic," no leader found, returning");
+            let error_code = if authorized {
+                debug!(topic = %replica.topic," no leader found, returning");
+                ErrorCode::NotLeaderForPartition
+            } else {
+                ErrorCode::PermissionDenied
+            };
             let response = StreamFetchResponse {
                 topic: replica.topic,
                 stream_id: 0,
                 partition: FilePartitionResponse {
                     partition_index: replica.partition,
-                    error_code: ErrorCode::NotLeaderForPartition,
+                    error_code,
                     ..Default::default()
                 },
+                ..Default::default()
             };
 
             let response_msg =
@@ -121,7 +157,7 @@ impl StreamFetchHandler {
 
     #[allow(clippy::too_many_arguments)]
     #[instrument(
-        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener),
+        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener,quota_client),
         fields(
             replica = %replica,
             sink = sink.id()
@@ -137,18 +173,27 @@ impl StreamFetchHandler {
         replica: ReplicaKey,
         consumer_offset_listener: OffsetChangeListener,
         msg: StreamFetchRequest<FileRecordSet>,
+        quota_client: QuotaClient,
     ) -> Result<(), SocketError> {
         debug!("request: {:#?}", msg);
         let version = header.api_version();
 
         let sm_ctx = match SmartModuleContext::try_from(msg.smartmodules, version, &ctx).await {
-            Ok(Some(mut ctx)) => {
-                if let Err(error_code) = ctx.look_back(&leader_state).await {
+            Ok(Some(mut sm_ctx)) => {
+                if let Some(consumer_id) = &msg.consumer_id {
+                    if let Err(error_code) = sm_ctx.restore_state(&replica, consumer_id, &ctx).await
+                    {
+                        warn!("smartmodule state restore failed: {:?}", error_code);
+                        send_back_error(&sink, &replica, &header, stream_id, error_code).await?;
+                        return Ok(());
+                    }
+                }
+                if let Err(error_code) = sm_ctx.look_back(&leader_state).await {
                     warn!("smartmodule look_back failed: {:?}", error_code);
                     send_back_error(&sink, &replica, &header, stream_id, error_code).await?;
                     return Ok(());
                 }
-                Some(ctx)
+                Some(sm_ctx)
             }
             Ok(None) => None,
             Err(error_code) => {
@@ -190,6 +235,8 @@ impl StreamFetchHandler {
             leader_state,
             max_fetch_bytes,
             metrics: ctx.metrics(),
+            quota_client,
+            quotas: ctx.quota_manager().clone(),
         };
 
         if let Err(err) = handler.process(starting_offset, sm_ctx).await {
@@ -370,6 +417,7 @@ impl StreamFetchHandler {
             Ok(slice) => {
                 file_partition_response.high_watermark = slice.end.hw;
                 file_partition_response.log_start_offset = slice.start;
+                file_partition_response.aborted = slice.aborted;
 
                 if let Some(file_slice) = slice.file_slice {
                     file_partition_response.records = file_slice.into();
@@ -400,16 +448,22 @@ impl StreamFetchHandler {
             return Ok((starting_offset, false));
         }
 
-        let (offset, wait, metrics_update) = match sm_ctx {
+        let (offset, wait, metrics_update, throttle) = match sm_ctx {
             Some(sm_ctx) => {
                 // If a SmartModule is provided, we need to read records from file to memory
                 // In-memory records are then processed by SmartModule and returned to consumer
 
                 let records = &file_partition_response.records;
                 let mut file_batch_iterator =
-                    FileBatchIterator::from_raw_slice(records.raw_slice());
-
-                let (batch, smartmodule_error) = process_batch(
+                    FileBatchIterator::from_raw_slice(records.raw_slice()).filter(|file_batch| {
+                        match file_batch {
+                            Ok(file_batch) => !file_partition_response
+                                .skip_batch(&file_batch.batch.header, file_batch.batch.base_offset),
+                            Err(_) => true,
+                        }
+                    });
+
+                let (batch, smartmodule_error, dead_letters) = process_batch(
                     sm_ctx.chain_mut(),
                     &mut file_batch_iterator,
                     self.max_bytes as usize,
@@ -417,29 +471,39 @@ impl StreamFetchHandler {
                 .map_err(|err| {
                     StreamFetchError::Fetch(ErrorCode::Other(format!("SmartModule err {err}")))
                 })?;
+                sm_ctx
+                    .persist_state()
+                    .await
+                    .map_err(StreamFetchError::Fetch)?;
                 let metrics_update = IncreaseValue::from(&batch);
 
                 sm_ctx.update_global_metrics();
 
+                let throttle = self.record_bytes(metrics_update.bytes());
                 let (offset, wait) = self
                     .send_processed_response(
                         file_partition_response,
                         next_offset,
                         batch,
                         smartmodule_error,
+                        dead_letters,
+                        throttle,
                     )
                     .await?;
-                (offset, wait, metrics_update)
+                (offset, wait, metrics_update, throttle)
             }
             None => {
                 // If no SmartModule is provided, respond using raw file records
                 debug!("No SmartModule, sending back entire log");
                 let metrics_update = IncreaseValue::from(&file_partition_response);
+                let throttle = self.record_bytes(metrics_update.bytes());
 
                 let response = StreamFetchResponse {
                     topic: self.replica.topic.clone(),
                     stream_id: self.stream_id,
                     partition: file_partition_response,
+                    throttle_time_ms: throttle.as_millis() as i32,
+                    ..Default::default()
                 };
 
                 let response_msg = RequestMessage::<FileStreamFetchRequest>::response_with_header(
@@ -462,22 +526,51 @@ impl StreamFetchHandler {
                     read_end_offset.isolation(&self.isolation),
                     true,
                     metrics_update,
+                    throttle,
                 )
             }
         };
+        self.metrics
+            .partition(&self.replica)
+            .consumed()
+            .increase(metrics_update.records(), metrics_update.bytes());
         self.metrics
             .outbound()
             .increase_by_value(self.header.is_connector(), metrics_update);
+
+        // stream is paused until consumer is back under its quota
+        if !throttle.is_zero() {
+            debug!(throttle_ms = throttle.as_millis(), "consume quota exceeded");
+            sleep(throttle).await;
+        }
         Ok((offset, wait))
     }
 
-    #[instrument(skip(self, file_partition_response, batch, smartmodule_error))]
+    fn record_bytes(&self, bytes: u64) -> Duration {
+        self.quotas.record_bytes(
+            &self.quota_client,
+            QuotaKind::ConsumeBytes,
+            &self.replica.topic,
+            bytes,
+        )
+    }
+
+    #[instrument(skip(
+        self,
+        file_partition_response,
+        batch,
+        smartmodule_error,
+        dead_letters,
+        throttle
+    ))]
     async fn send_processed_response(
         &self,
         file_partition_response: FilePartitionResponse,
         next_offset: Offset,
-        batch: Batch,
+        mut batch: Batch,
         smartmodule_error: Option<SmartModuleTransformRuntimeError>,
+        dead_letters: Vec<SmartModuleTransformRuntimeError>,
+        throttle: Duration,
     ) -> Result<(Offset, bool), StreamFetchError> {
         type DefaultPartitionResponse = FetchablePartitionResponse<RecordSet<RawRecords>>;
 
@@ -490,7 +583,7 @@ impl StreamFetchHandler {
         let has_error = !matches!(error_code, ErrorCode::None);
         let has_records = !batch.records().is_empty();
 
-        if !has_records && !has_error {
+        if !has_records && !has_error && dead_letters.is_empty() {
             debug!(next_offset, "No records to send back, skipping");
             return Ok((next_offset, false));
         }
@@ -505,11 +598,19 @@ impl StreamFetchHandler {
         debug!(
             next_offset,
             records = batch.records().len(),
+            dead_letters = dead_letters.len(),
             "sending back to consumer"
         );
 
         //trace!("batch: {:#?}",batch);
 
+        // older clients can't decode record headers
+        if self.header.api_version() < RECORD_HEADERS_API {
+            for record in batch.mut_records() {
+                *record.headers_mut() = RecordHeaders::default();
+            }
+        }
+
         let records = RecordSet::default().add(batch);
         let partition_response = DefaultPartitionResponse {
             partition_index: self.replica.partition,
@@ -526,6 +627,8 @@ impl StreamFetchHandler {
             topic: self.replica.topic.clone(),
             stream_id: self.stream_id,
             partition: partition_response,
+            throttle_time_ms: throttle.as_millis() as i32,
+            dead_letters,
         };
 
         let response_msg = RequestMessage::<DefaultStreamFetchRequest>::response_with_header(
@@ -562,6 +665,7 @@ async fn send_back_error(
         topic: replica.topic.clone(),
         stream_id,
         partition: partition_response,
+        ..Default::default()
     };
 
     let response_msg =
@@ -0,0 +1,122 @@
+use std::{env::temp_dir, time::Duration};
+
+use fluvio_controlplane::replica::Replica;
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::consumer_group::{
+    AssignmentStrategy, GroupHeartbeatRequest, JoinGroupRequest, LeaveGroupRequest,
+};
+use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
+
+use flv_util::fixture::ensure_clean_dir;
+
+use fluvio_future::timer::sleep;
+use fluvio_socket::{FluvioSocket, MultiplexerSocket};
+use crate::services::public::tests::create_public_server_with_root_auth;
+use crate::core::GlobalContext;
+use crate::config::SpuConfig;
+use crate::replication::leader::LeaderReplicaState;
+
+#[fluvio_future::test(ignore)]
+async fn test_consumer_group() {
+    let test_path = temp_dir().join("test_consumer_group");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "test";
+    ctx.replica_localstore().sync_all(vec![
+        Replica::new((topic.to_owned(), 0), 5001, vec![5001]),
+        Replica::new((topic.to_owned(), 1), 5001, vec![5001]),
+        Replica::new((topic.to_owned(), 2), 5001, vec![5001]),
+    ]);
+
+    let join_request = |member_id: &str| JoinGroupRequest {
+        group: "group".to_owned(),
+        member_id: member_id.to_owned(),
+        topic: topic.to_owned(),
+        strategy: AssignmentStrategy::RoundRobin,
+        session_timeout_ms: 10000,
+    };
+
+    // only leader of consumer offsets partition coordinates groups
+    let response = client_socket
+        .send_and_receive(RequestMessage::new_request(join_request("")))
+        .await
+        .expect("join");
+    assert_eq!(response.error_code, ErrorCode::NotGroupCoordinator);
+
+    let consumer_replica = Replica::new(CONSUMER_REPLICA_KEY.to_owned(), 5001, vec![5001]);
+    let consumer_replica =
+        LeaderReplicaState::create(consumer_replica, ctx.config(), ctx.status_update_owned())
+            .await
+            .expect("replica")
+            .init(&ctx)
+            .await
+            .expect("init succeeded");
+    ctx.leaders_state()
+        .insert(CONSUMER_REPLICA_KEY.into(), consumer_replica)
+        .await;
+
+    let first = client_socket
+        .send_and_receive(RequestMessage::new_request(join_request("")))
+        .await
+        .expect("join");
+    assert_eq!(first.error_code, ErrorCode::None);
+    assert_eq!(first.generation, 1);
+    assert_eq!(first.partitions, vec![0, 1, 2]);
+
+    let second = client_socket
+        .send_and_receive(RequestMessage::new_request(join_request("")))
+        .await
+        .expect("join");
+    assert_eq!(second.error_code, ErrorCode::None);
+    assert_eq!(second.generation, 2);
+
+    let heartbeat = client_socket
+        .send_and_receive(RequestMessage::new_request(GroupHeartbeatRequest {
+            group: "group".to_owned(),
+            member_id: first.member_id.clone(),
+            generation: first.generation,
+        }))
+        .await
+        .expect("heartbeat");
+    assert_eq!(heartbeat.error_code, ErrorCode::None);
+    assert_eq!(heartbeat.generation, 2);
+    let mut all = [heartbeat.partitions.clone(), second.partitions.clone()].concat();
+    all.sort_unstable();
+    assert_eq!(all, vec![0, 1, 2]);
+
+    let response = client_socket
+        .send_and_receive(RequestMessage::new_request(LeaveGroupRequest {
+            group: "group".to_owned(),
+            member_id: second.member_id.clone(),
+        }))
+        .await
+        .expect("leave");
+    assert_eq!(response.error_code, ErrorCode::None);
+
+    let heartbeat = client_socket
+        .send_and_receive(RequestMessage::new_request(GroupHeartbeatRequest {
+            group: "group".to_owned(),
+            member_id: second.member_id.clone(),
+            generation: second.generation,
+        }))
+        .await
+        .expect("heartbeat");
+    assert_eq!(heartbeat.error_code, ErrorCode::UnknownGroupMember);
+
+    server_end_event.notify();
+}
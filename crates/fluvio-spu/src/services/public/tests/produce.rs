@@ -17,6 +17,8 @@ use fluvio_controlplane_metadata::topic::{
 use fluvio_future::timer::sleep;
 use fluvio_socket::{MultiplexerSocket, FluvioSocket};
 use fluvio_spu_schema::{
+    server::producer::InitProducerIdRequest,
+    server::transaction::{AddTxnPartitionsRequest, EndTxnRequest},
     produce::{
         DefaultProduceRequest, DefaultPartitionRequest, TopicProduceData, PartitionProduceData,
     },
@@ -147,6 +149,248 @@ async fn test_produce_basic() {
     debug!("terminated controller");
 }
 
+#[fluvio_future::test(ignore)]
+async fn test_produce_idempotent() {
+    let test_path = temp_dir().join("produce_idempotent");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "test_produce_idempotent";
+    let test = Replica::new((topic, 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    let producer_id = client_socket
+        .send_and_receive(RequestMessage::new_request(InitProducerIdRequest))
+        .await
+        .expect("init producer id")
+        .producer_id;
+    assert!(producer_id >= 0);
+
+    let produce = |first_sequence: i32| {
+        let mut records = create_filter_records(2);
+        for batch in records.batches.iter_mut() {
+            let header = batch.get_mut_header();
+            header.producer_id = producer_id;
+            header.producer_epoch = 0;
+            header.first_sequence = first_sequence;
+        }
+        let partition_produce = DefaultPartitionRequest {
+            partition_index: 0,
+            records: records.try_into().expect("raw records"),
+        };
+        let mut produce_request = DefaultProduceRequest::default();
+        produce_request.topics.push(TopicProduceData {
+            name: topic.to_owned(),
+            partitions: vec![partition_produce],
+            ..Default::default()
+        });
+        RequestMessage::new_request(produce_request)
+    };
+
+    let first = client_socket
+        .send_and_receive(produce(0))
+        .await
+        .expect("produce");
+    assert_eq!(first.responses[0].partitions[0].error_code, ErrorCode::None);
+    assert_eq!(first.responses[0].partitions[0].base_offset, 0);
+
+    // retry of the same batch is not written again
+    let retry = client_socket
+        .send_and_receive(produce(0))
+        .await
+        .expect("produce");
+    assert_eq!(retry.responses[0].partitions[0].error_code, ErrorCode::None);
+    assert_eq!(retry.responses[0].partitions[0].base_offset, 0);
+    assert_eq!(replica.leo(), 2);
+
+    // gap in sequence is rejected
+    let gap = client_socket
+        .send_and_receive(produce(5))
+        .await
+        .expect("produce");
+    assert_eq!(
+        gap.responses[0].partitions[0].error_code,
+        ErrorCode::OutOfOrderSequence {
+            expected: 2,
+            received: 5
+        }
+    );
+
+    let next = client_socket
+        .send_and_receive(produce(2))
+        .await
+        .expect("produce");
+    assert_eq!(next.responses[0].partitions[0].error_code, ErrorCode::None);
+    assert_eq!(next.responses[0].partitions[0].base_offset, 2);
+    assert_eq!(replica.leo(), 4);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_produce_transactional() {
+    let test_path = temp_dir().join("produce_transactional");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "test_produce_transactional";
+    let test = Replica::new((topic, 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+    ctx.leaders_state()
+        .insert(test_id.clone(), replica.clone())
+        .await;
+
+    let producer_id = client_socket
+        .send_and_receive(RequestMessage::new_request(InitProducerIdRequest))
+        .await
+        .expect("init producer id")
+        .producer_id;
+
+    let produce = || {
+        let mut records = create_filter_records(2);
+        for batch in records.batches.iter_mut() {
+            let header = batch.get_mut_header();
+            header.producer_id = producer_id;
+            header.set_transactional();
+        }
+        let partition_produce = DefaultPartitionRequest {
+            partition_index: 0,
+            records: records.try_into().expect("raw records"),
+        };
+        let mut produce_request = DefaultProduceRequest::default();
+        produce_request.topics.push(TopicProduceData {
+            name: topic.to_owned(),
+            partitions: vec![partition_produce],
+            ..Default::default()
+        });
+        RequestMessage::new_request(produce_request)
+    };
+    let add_partitions = || {
+        RequestMessage::new_request(AddTxnPartitionsRequest {
+            producer_id,
+            partitions: vec![test_id.clone()],
+            timeout_ms: 60000,
+        })
+    };
+
+    // aborted transaction
+    let response = client_socket
+        .send_and_receive(add_partitions())
+        .await
+        .expect("add partitions");
+    assert_eq!(response.error_code, ErrorCode::None);
+    let produced = client_socket
+        .send_and_receive(produce())
+        .await
+        .expect("produce");
+    assert_eq!(
+        produced.responses[0].partitions[0].error_code,
+        ErrorCode::None
+    );
+
+    // open transaction is not visible to read committed consumers
+    let slice = replica
+        .read_records(0, 1000, Isolation::ReadCommitted)
+        .await
+        .expect("read");
+    assert_eq!(slice.end.hw, 0);
+
+    let response = client_socket
+        .send_and_receive(RequestMessage::new_request(EndTxnRequest {
+            producer_id,
+            commit: false,
+        }))
+        .await
+        .expect("end txn");
+    assert_eq!(response.error_code, ErrorCode::None);
+    // abort marker is written after data
+    assert_eq!(replica.leo(), 3);
+
+    // committed transaction
+    client_socket
+        .send_and_receive(add_partitions())
+        .await
+        .expect("add partitions");
+    client_socket
+        .send_and_receive(produce())
+        .await
+        .expect("produce");
+    let response = client_socket
+        .send_and_receive(RequestMessage::new_request(EndTxnRequest {
+            producer_id,
+            commit: true,
+        }))
+        .await
+        .expect("end txn");
+    assert_eq!(response.error_code, ErrorCode::None);
+    assert_eq!(replica.leo(), 6);
+
+    let slice = replica
+        .read_records(0, 1000, Isolation::ReadCommitted)
+        .await
+        .expect("read");
+    assert_eq!(slice.end.hw, 6);
+    let aborted = slice.aborted.expect("aborted");
+    assert_eq!(aborted.len(), 1);
+    assert!(aborted[0].contains(producer_id, 0));
+    assert!(!aborted[0].contains(producer_id, 3));
+
+    // only coordinator accepts transaction requests
+    let response = client_socket
+        .send_and_receive(RequestMessage::new_request(EndTxnRequest {
+            producer_id: producer_id + (1 << 47),
+            commit: true,
+        }))
+        .await
+        .expect("end txn");
+    assert_eq!(response.error_code, ErrorCode::NotTxnCoordinator);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
 #[fluvio_future::test(ignore)]
 async fn test_produce_invalid_compression() {
     let test_path = temp_dir().join("produce_invalid_compression");
@@ -445,7 +689,9 @@ async fn test_produce_metrics() {
         .await
         .expect("init succeeded");
 
-    ctx.leaders_state().insert(test_id, replica.clone()).await;
+    ctx.leaders_state()
+        .insert(test_id.clone(), replica.clone())
+        .await;
 
     assert_eq!(ctx.metrics().inbound().client_bytes(), 0);
     assert_eq!(ctx.metrics().inbound().client_records(), 0);
@@ -513,6 +759,11 @@ async fn test_produce_metrics() {
         assert_eq!(ctx.metrics().inbound().connector_bytes(), 606);
         assert_eq!(ctx.metrics().inbound().connector_records(), 5);
     }
+    let partition = ctx.metrics().partition(&test_id);
+    assert_eq!(partition.produced().records(), 15);
+    assert_eq!(partition.produced().bytes(), 1757);
+    assert_eq!(partition.consumed().records(), 0);
+    assert_eq!(ctx.metrics().connections(), 1);
     server_end_event.notify();
     debug!("terminated controller");
 }
@@ -535,6 +786,7 @@ async fn test_produce_basic_with_smartmodule_with_lookback() {
         kind: SmartModuleKind::Filter,
         params: Default::default(),
         name: Some(FLUVIO_WASM_FILTER_WITH_LOOKBACK.to_owned()),
+        ..Default::default()
     };
     smartmodule.params.set_lookback(Some(Lookback::last(1)));
     let mut smartmodules = vec![smartmodule];
@@ -4,7 +4,7 @@ use std::sync::Arc;
 use chrono::{Utc, Days};
 use fluvio_controlplane::replica::Replica;
 use fluvio_controlplane::spu_api::update_smartmodule::SmartModule;
-use fluvio_smartmodule::dataplane::smartmodule::Lookback;
+use fluvio_smartmodule::dataplane::smartmodule::{Lookback, SmartModuleErrorPolicy};
 use tracing::{debug, info};
 
 use fluvio_controlplane_metadata::smartmodule::{
@@ -1395,6 +1395,150 @@ async fn test_stream_fetch_map_error(
     debug!("terminated controller");
 }
 
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_map_error_skip_adhoc() {
+    adhoc_test(
+        "test_stream_fetch_map_error_skip_adhoc",
+        FLUVIO_WASM_MAP_DOUBLE,
+        SmartModuleKind::Map,
+        |ctx, test_path, smartmodules| {
+            test_stream_fetch_map_error_policy(
+                ctx,
+                test_path,
+                smartmodules,
+                SmartModuleErrorPolicy::Skip,
+            )
+        },
+    )
+    .await;
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_map_error_dead_letter_adhoc() {
+    adhoc_test(
+        "test_stream_fetch_map_error_dead_letter_adhoc",
+        FLUVIO_WASM_MAP_DOUBLE,
+        SmartModuleKind::Map,
+        |ctx, test_path, smartmodules| {
+            test_stream_fetch_map_error_policy(
+                ctx,
+                test_path,
+                smartmodules,
+                SmartModuleErrorPolicy::DeadLetter,
+            )
+        },
+    )
+    .await;
+}
+
+/// record in the middle of batch fails, stream continues with following records
+async fn test_stream_fetch_map_error_policy(
+    ctx: Arc<GlobalContext<FileReplica>>,
+    test_path: PathBuf,
+    mut smartmodules: Vec<SmartModuleInvocation>,
+    error_policy: SmartModuleErrorPolicy,
+) {
+    ensure_clean_dir(&test_path);
+
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "test_map_error_policy";
+    let test = Replica::new((topic.to_owned(), 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    for smartmodule in smartmodules.iter_mut() {
+        smartmodule.error_policy = error_policy;
+    }
+    let stream_request = DefaultStreamFetchRequest::builder()
+        .topic(topic.to_owned())
+        .max_bytes(10000)
+        .smartmodules(smartmodules)
+        .build()
+        .expect("stream request");
+
+    let mut stream = client_socket
+        .create_stream(RequestMessage::new_request(stream_request), 11)
+        .await
+        .expect("create stream");
+
+    let mut records = BatchProducer::builder()
+        .records(6u16)
+        .record_generator(Arc::new(|i, _| {
+            if i == 3 {
+                Record::new("three".to_string())
+            } else {
+                Record::new(i.to_string())
+            }
+        }))
+        .build()
+        .expect("batch")
+        .records()
+        .try_into()
+        .expect("raw");
+
+    replica
+        .write_record_set(&mut records, ctx.follower_notifier())
+        .await
+        .expect("write");
+
+    let response = stream.next().await.expect("first").expect("response");
+
+    assert_eq!(response.partition.error_code, ErrorCode::None);
+    assert_eq!(response.partition.next_offset_for_fetch(), Some(6));
+    let values: Vec<Vec<u8>> = response
+        .partition
+        .records
+        .batches
+        .iter()
+        .flat_map(|batch| batch.memory_records().expect("records"))
+        .map(|record| record.value.as_ref().to_vec())
+        .collect();
+    assert_eq!(
+        values,
+        vec![
+            b"0".to_vec(),
+            b"2".to_vec(),
+            b"4".to_vec(),
+            b"8".to_vec(),
+            b"10".to_vec()
+        ]
+    );
+
+    match error_policy {
+        SmartModuleErrorPolicy::DeadLetter => {
+            assert_eq!(response.dead_letters.len(), 1);
+            let dead_letter = &response.dead_letters[0];
+            assert_eq!(dead_letter.offset, 3);
+            assert_eq!(dead_letter.kind, SmartModuleKindError::Map);
+            assert_eq!(dead_letter.record_value.as_ref(), "three".as_bytes());
+        }
+        _ => assert!(response.dead_letters.is_empty()),
+    }
+
+    drop(response);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
 const FLUVIO_WASM_AGGREGATE: &str = "fluvio_smartmodule_aggregate";
 
 #[fluvio_future::test(ignore)]
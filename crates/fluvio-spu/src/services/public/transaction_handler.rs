@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use tracing::{debug, instrument};
+use anyhow::Result;
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::transaction::{
+    AddTxnPartitionsRequest, AddTxnPartitionsResponse, EndTxnRequest, EndTxnResponse,
+};
+
+use crate::core::DefaultSharedGlobalContext;
+
+/// register partitions in transaction of producer
+#[instrument(skip(request, ctx))]
+pub async fn handle_add_txn_partitions_request(
+    request: RequestMessage<AddTxnPartitionsRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<AddTxnPartitionsResponse>> {
+    let AddTxnPartitionsRequest {
+        producer_id,
+        partitions,
+        timeout_ms,
+    } = &request.request;
+
+    let error_code = if !ctx.producer_ids().is_local(*producer_id) {
+        ErrorCode::NotTxnCoordinator
+    } else {
+        debug!(producer_id, ?partitions, "add partitions to transaction");
+        match ctx
+            .transactions()
+            .add_partitions(
+                *producer_id,
+                partitions.clone(),
+                Duration::from_millis(*timeout_ms as u64),
+            )
+            .await
+        {
+            Ok(_) => ErrorCode::None,
+            Err(err) => err,
+        }
+    };
+    Ok(request.new_response(AddTxnPartitionsResponse { error_code }))
+}
+
+/// commit or abort transaction of producer
+#[instrument(skip(request, ctx))]
+pub async fn handle_end_txn_request(
+    request: RequestMessage<EndTxnRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<EndTxnResponse>> {
+    let EndTxnRequest {
+        producer_id,
+        commit,
+    } = &request.request;
+
+    let error_code = if !ctx.producer_ids().is_local(*producer_id) {
+        ErrorCode::NotTxnCoordinator
+    } else {
+        match ctx.transactions().end(&ctx, *producer_id, *commit).await {
+            Ok(_) => ErrorCode::None,
+            Err(err) => err,
+        }
+    };
+    Ok(request.new_response(EndTxnResponse { error_code }))
+}
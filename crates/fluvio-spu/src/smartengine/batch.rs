@@ -28,10 +28,18 @@ pub(crate) trait SmartModuleInputBatch {
     fn get_compression(&self) -> Result<Compression, CompressionError>;
 }
 
+/// SmartModule output: processed records, error which stopped processing
+/// and errors of records dropped with dead-letter policy
+pub(crate) type ProcessedBatch = (
+    Batch,
+    Option<SmartModuleTransformRuntimeError>,
+    Vec<SmartModuleTransformRuntimeError>,
+);
+
 pub(crate) fn process_record_set(
     sm_chain: &mut SmartModuleChainInstance,
     records: &mut RecordSet<RawRecords>,
-) -> Result<(Batch, Option<SmartModuleTransformRuntimeError>), Error> {
+) -> Result<ProcessedBatch, Error> {
     let mut batches = ProduceBatchIterator::new(&records.batches);
 
     process_batch(sm_chain, &mut batches, usize::MAX)
@@ -42,12 +50,13 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
     sm_chain_instance: &mut SmartModuleChainInstance,
     input_batches: &mut impl Iterator<Item = Result<R, IoError>>,
     max_bytes: usize,
-) -> Result<(Batch, Option<SmartModuleTransformRuntimeError>), Error> {
+) -> Result<ProcessedBatch, Error> {
     let mut smartmodule_batch = Batch::<MemoryRecords>::default();
     smartmodule_batch.base_offset = -1; // indicate this is uninitialized
     smartmodule_batch.set_offset_delta(-1); // make add_to_offset_delta correctly
 
     let mut total_bytes = 0;
+    let mut dead_letters = vec![];
 
     for batch_result in input_batches {
         let input_batch = batch_result?;
@@ -69,11 +78,15 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
             input_batch.base_timestamp(),
         );
         let output = sm_chain_instance.process(input)?;
+        // dropped with the batch if it doesn't fit, batch is processed again by next fetch
+        let mut batch_dead_letters = sm_chain_instance.take_dead_letters();
 
         debug!(smartmodule_execution_time = %now.elapsed().as_millis());
 
         let maybe_error = output.error;
         let mut records = output.successes;
+        #[cfg(feature = "otel")]
+        crate::telemetry::restore_trace_context(input_batch.records(), &mut records);
 
         trace!("smartmodule processed records: {:#?}", records);
 
@@ -105,7 +118,7 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
                     total_bytes = total_bytes + record_bytes,
                     max_bytes, "Total SmartModuleInstance bytes reached"
                 );
-                return Ok((smartmodule_batch, maybe_error));
+                return Ok((smartmodule_batch, maybe_error, dead_letters));
             }
 
             total_bytes += record_bytes;
@@ -126,9 +139,11 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
             smartmodule_batch.add_to_offset_delta(input_batch.offset_delta() + 1);
         }
 
+        dead_letters.append(&mut batch_dead_letters);
+
         // If we had a processing error, return current batch and error
         if maybe_error.is_some() {
-            return Ok((smartmodule_batch, maybe_error));
+            return Ok((smartmodule_batch, maybe_error, dead_letters));
         }
     }
 
@@ -137,7 +152,7 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
         "No more batches, SmartModuleInstance end"
     );
 
-    Ok((smartmodule_batch, None))
+    Ok((smartmodule_batch, None, dead_letters))
 }
 
 fn set_compression(
@@ -63,6 +63,8 @@ pub(crate) fn build_chain(
                 .version(version)
                 .lookback(lookback)
                 .initial_data(initial_data)
+                .error_policy(invocation.error_policy)
+                .timeout(invocation.timeout)
                 .build()
                 .map_err(|err| ErrorCode::SmartModuleInvalid {
                     error: err.to_string(),
@@ -83,6 +85,9 @@ pub(crate) fn build_chain(
                 requested: *requested as u64,
                 max: *max as u64,
             },
+            Some(EngineError::Timeout(timeout)) => ErrorCode::SmartModuleTimeout {
+                timeout_ms: timeout.as_millis() as u64,
+            },
             _ => ErrorCode::SmartModuleChainInitError(err.to_string()),
         }
     })?;
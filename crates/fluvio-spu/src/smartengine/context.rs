@@ -1,9 +1,11 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_lock::RwLock;
 use chrono::Utc;
 use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
 use fluvio_smartmodule::Record;
 use fluvio_spu_schema::server::smartmodule::{SmartModuleInvocation, SmartModuleInvocationWasm};
 use fluvio_storage::ReplicaStorage;
@@ -11,11 +13,13 @@ use fluvio_storage::iterators::{FileBatch, FileBatchIterator, FileRecordIterator
 use fluvio_types::Timestamp;
 use tracing::{debug, trace, error};
 
+use crate::config::SpuConfig;
 use crate::core::GlobalContext;
 use crate::core::metrics::SpuMetrics;
 use crate::replication::leader::LeaderReplicaState;
 
 use crate::smartengine::chain;
+use crate::smartengine::state::SmartModuleStateStorage;
 use crate::smartengine::Lookback;
 use crate::smartengine::SmartModuleChainBuilder;
 use crate::smartengine::SmartModuleChainInstance;
@@ -26,6 +30,7 @@ pub struct SmartModuleContext {
     chain: SmartModuleChainInstance,
     version: Version,
     spu_metrics: Arc<SpuMetrics>,
+    state: Option<SmartModuleStateStorage>,
 }
 
 pub type SharedSmartModuleContext = Arc<RwLock<SmartModuleContext>>;
@@ -56,6 +61,41 @@ impl SmartModuleContext {
             })
     }
 
+    /// Restore state of SmartModules from changelog of consumer,
+    /// afterwards state changes are saved there by `persist_state`
+    pub async fn restore_state<R: ReplicaStorage>(
+        &mut self,
+        replica: &ReplicaKey,
+        consumer_id: &str,
+        ctx: &GlobalContext<R>,
+    ) -> Result<(), ErrorCode> {
+        let path = state_path(ctx.config(), replica, consumer_id);
+        debug!(path = %path.display(), "restoring SmartModule state");
+        let state = SmartModuleStateStorage::open(path).await.map_err(|err| {
+            ErrorCode::Other(format!("failed to restore SmartModule state: {err}"))
+        })?;
+        let entries = state.entries().await.map_err(|err| {
+            ErrorCode::Other(format!("failed to restore SmartModule state: {err}"))
+        })?;
+        self.chain.restore_state(entries);
+        self.state = Some(state);
+        Ok(())
+    }
+
+    /// save state changes made since last call
+    pub async fn persist_state(&mut self) -> Result<(), ErrorCode> {
+        let Some(state) = self.state.as_mut() else {
+            return Ok(());
+        };
+        state
+            .apply(self.chain.take_state_changes())
+            .await
+            .map_err(|err| {
+                error!("failed to persist SmartModule state: {err:#}");
+                ErrorCode::Other(format!("failed to persist SmartModule state: {err}"))
+            })
+    }
+
     /// given SmartModule invocation and context, generate execution context
     async fn build_smartmodule_context<R: ReplicaStorage>(
         invocations: Vec<SmartModuleInvocation>,
@@ -72,6 +112,9 @@ impl SmartModuleContext {
         }
         let mut chain_builder = SmartModuleChainBuilder::default();
         chain_builder.set_store_memory_limit(ctx.config().smart_engine.store_max_memory);
+        if let Some(timeout) = ctx.config().smart_engine.timeout {
+            chain_builder.set_timeout(timeout);
+        }
 
         let chain = chain::build_chain(
             chain_builder,
@@ -84,6 +127,7 @@ impl SmartModuleContext {
             chain,
             version,
             spu_metrics: ctx.metrics(),
+            state: None,
         }))
     }
 
@@ -96,6 +140,26 @@ impl SmartModuleContext {
     }
 }
 
+/// changelog of SmartModule state for consumer of partition
+fn state_path(config: &SpuConfig, replica: &ReplicaKey, consumer_id: &str) -> PathBuf {
+    let file_name: String = consumer_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    config
+        .log
+        .base_dir
+        .join(format!("spu-smartmodule-state-{}", config.id))
+        .join(replica.to_string())
+        .join(format!("{file_name}.log"))
+}
+
 fn resolve_invocation<R: ReplicaStorage>(
     invocation: SmartModuleInvocation,
     ctx: &GlobalContext<R>,
@@ -10,11 +10,13 @@ pub(crate) mod batch;
 pub(crate) mod file_batch;
 pub(crate) mod produce_batch;
 pub(crate) mod context;
+pub(crate) mod state;
 mod chain;
 
 #[cfg(feature = "smartengine")]
 pub(crate) use fluvio_smartengine::{
     EngineError, Lookback, SmartModuleChainBuilder, SmartEngine, SmartModuleChainInstance, Version,
+    StateKey, StateChange,
 };
 
 // Stub structures to support a null smartengine config
@@ -28,8 +30,11 @@ mod null_smartengine {
     use anyhow::Result;
     use serde::{Deserialize, Serialize};
 
+    use fluvio_protocol::{Encoder, Decoder};
+
     use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
     use fluvio_smartmodule::dataplane::smartmodule::SmartModuleOutput;
+    use fluvio_smartmodule::dataplane::smartmodule::SmartModuleTransformRuntimeError;
     use fluvio_smartmodule::Record;
 
     // refactor to use more widely as a "flow" metric?
@@ -45,6 +50,8 @@ mod null_smartengine {
         // allow this to be missing for deserialization for legacy use
         #[serde(default)]
         cpu_ms: AtomicU64,
+        #[serde(default)]
+        timeouts: AtomicU64,
         // Names of the SmartModules in the chain
         #[serde(default)]
         smartmodule_names: Vec<String>,
@@ -80,6 +87,18 @@ mod null_smartengine {
             self.invocation_count.load(Ordering::SeqCst)
         }
 
+        pub fn cpu_ms(&self) -> u64 {
+            self.cpu_ms.load(Ordering::SeqCst)
+        }
+
+        pub fn records_err(&self) -> u64 {
+            self.records_err.load(Ordering::SeqCst)
+        }
+
+        pub fn timeouts(&self) -> u64 {
+            self.timeouts.load(Ordering::SeqCst)
+        }
+
         // Added append method
         pub fn append(&self, other: &Self) {
             self.bytes_in.fetch_add(other.bytes_in(), Ordering::SeqCst);
@@ -103,6 +122,7 @@ mod null_smartengine {
                 invocation_count: AtomicU64::new(self.invocation_count.load(DEFAULT_ORDERING)),
                 fuel_used: AtomicU64::new(self.fuel_used.load(DEFAULT_ORDERING)),
                 cpu_ms: AtomicU64::new(self.cpu_ms.load(DEFAULT_ORDERING)),
+                timeouts: AtomicU64::new(self.timeouts.load(DEFAULT_ORDERING)),
                 smartmodule_names: self.smartmodule_names.clone(),
             }
         }
@@ -122,6 +142,8 @@ mod null_smartengine {
 
     impl SmartModuleChainBuilder {
         pub fn set_store_memory_limit(&mut self, _max_memory_bytes: usize) {}
+
+        pub fn set_timeout(&mut self, _timeout: Duration) {}
     }
 
     #[derive(Debug)]
@@ -137,8 +159,8 @@ mod null_smartengine {
         }
 
         pub fn process(&mut self, input: SmartModuleInput) -> Result<SmartModuleOutput> {
-            use fluvio_smartmodule::SMARTMODULE_TIMESTAMPS_VERSION;
-            const DEFAULT_SMARTENGINE_VERSION: Version = SMARTMODULE_TIMESTAMPS_VERSION;
+            use fluvio_smartmodule::SMARTMODULE_HEADERS_VERSION;
+            const DEFAULT_SMARTENGINE_VERSION: Version = SMARTMODULE_HEADERS_VERSION;
 
             #[allow(deprecated)]
             let records = input.try_into_records(DEFAULT_SMARTENGINE_VERSION)?;
@@ -146,6 +168,16 @@ mod null_smartengine {
             Ok(out)
         }
 
+        pub fn take_dead_letters(&mut self) -> Vec<SmartModuleTransformRuntimeError> {
+            Vec::new()
+        }
+
+        pub fn restore_state(&mut self, _entries: impl IntoIterator<Item = (StateKey, Vec<u8>)>) {}
+
+        pub fn take_state_changes(&mut self) -> Vec<StateChange> {
+            Vec::new()
+        }
+
         // Added metrics_export method
         pub fn metrics_export(&self) -> HashMap<String, SmartModuleChainMetrics> {
             HashMap::<String, SmartModuleChainMetrics>::new()
@@ -154,6 +186,24 @@ mod null_smartengine {
 
     pub type Version = i16;
 
+    #[derive(Debug, Default, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Encoder, Decoder)]
+    pub struct StateKey {
+        pub instance: u16,
+        pub key: Vec<u8>,
+    }
+
+    impl StateKey {
+        #[allow(dead_code)]
+        pub fn new(instance: u16, key: impl Into<Vec<u8>>) -> Self {
+            Self {
+                instance,
+                key: key.into(),
+            }
+        }
+    }
+
+    pub type StateChange = (StateKey, Option<Vec<u8>>);
+
     // copied from SmartEngine crate, refactor to remove this and config smartengine crate to export w/o specific engine later
     #[allow(dead_code)]
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -175,6 +225,8 @@ mod null_smartengine {
             requested: usize,
             max: usize,
         },
+        #[error("SmartModule exceeded time limit of {0:?}")]
+        Timeout(Duration),
     }
 }
 
@@ -201,6 +253,8 @@ pub(crate) fn dedup_to_invocation(dedup: &Deduplication) -> SmartModuleInvocatio
         kind: SmartModuleKind::Filter,
         params: SmartModuleExtraParams::new(params, Some(lookback)),
         name: Some(dedup.filter.transform.uses.clone()),
+        error_policy: Default::default(),
+        timeout: None,
     }
 }
 
@@ -216,6 +270,9 @@ pub(crate) fn map_engine_error(err: &EngineError) -> ErrorCode {
             requested: *requested as u64,
             max: *max as u64,
         },
+        EngineError::Timeout(timeout) => ErrorCode::SmartModuleTimeout {
+            timeout_ms: timeout.as_millis() as u64,
+        },
     }
 }
 
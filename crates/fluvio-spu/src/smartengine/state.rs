@@ -0,0 +1,357 @@
+//!
+//! # SmartModule state
+//!
+//! Keyed state of SmartModules applied to stream of a consumer with an id.
+//! Changes made by SmartModules are kept in a changelog file per consumer and partition,
+//! so state survives SPU restarts and consumer reconnects.
+//! Only one stream of a consumer can use its changelog at a time.
+//!
+
+use std::collections::HashSet;
+use std::io::{Cursor, ErrorKind};
+use std::ops::AddAssign;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Result, anyhow};
+use futures_util::{AsyncWriteExt, Stream};
+use once_cell::sync::Lazy;
+use tracing::{debug, warn};
+
+use fluvio_future::fs::{create_dir_all, read, rename, File, OpenOptions};
+use fluvio_kv_storage::{KVStorage, Log, LogBasedKVStorage};
+use fluvio_protocol::{Decoder, Encoder, Version};
+
+use crate::smartengine::{StateChange, StateKey};
+
+const CHANGELOG_VERSION: Version = 0;
+
+/// changelog is compacted after this many changes
+const DEFAULT_FLUSH_THRESHOLD: usize = 1000;
+
+/// changelogs opened by streams of this SPU
+static OPEN_CHANGELOGS: Lazy<Mutex<HashSet<PathBuf>>> = Lazy::new(Default::default);
+
+#[derive(Debug)]
+pub(crate) struct SmartModuleStateStorage {
+    kv: LogBasedKVStorage<StateKey, Vec<u8>, ChangelogFile>,
+    compact: Arc<AtomicBool>,
+    flush_threshold: usize,
+    changes_since_flush: usize,
+    _guard: ChangelogGuard,
+}
+
+impl SmartModuleStateStorage {
+    /// open changelog at path, replaying existing changes
+    pub(crate) async fn open(path: PathBuf) -> Result<Self> {
+        Self::with(path, DEFAULT_FLUSH_THRESHOLD).await
+    }
+
+    pub(crate) async fn with(path: PathBuf, flush_threshold: usize) -> Result<Self> {
+        let guard = ChangelogGuard::acquire(path.clone())?;
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).await?;
+        }
+        let compact = Arc::new(AtomicBool::new(false));
+        let mut kv = LogBasedKVStorage::new(ChangelogFile {
+            path,
+            compact: compact.clone(),
+            file: None,
+            pending: Vec::new(),
+        });
+        kv.sync_from_log().await?;
+        Ok(Self {
+            kv,
+            compact,
+            flush_threshold,
+            changes_since_flush: Default::default(),
+            _guard: guard,
+        })
+    }
+
+    pub(crate) async fn entries(&self) -> Result<Vec<(StateKey, Vec<u8>)>> {
+        self.kv.entries().await
+    }
+
+    /// changes are synced to changelog file together
+    pub(crate) async fn apply(&mut self, changes: Vec<StateChange>) -> Result<()> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        debug!(count = changes.len(), "applying state changes");
+        for (key, value) in changes {
+            match value {
+                Some(value) => self.kv.put(key, value).await?,
+                None => self.kv.delete(&key).await?,
+            }
+            self.changes_since_flush.add_assign(1);
+        }
+        self.maybe_flush().await?;
+        self.kv.sync().await
+    }
+
+    /// flush writes snapshot of all entries, which replaces the changelog
+    async fn maybe_flush(&mut self) -> Result<()> {
+        if self.changes_since_flush > self.flush_threshold {
+            self.compact.store(true, Ordering::SeqCst);
+            self.kv.flush().await?;
+            self.changes_since_flush = Default::default();
+        }
+        Ok(())
+    }
+}
+
+/// Registration of changelog path, so streams of consumers with same id don't write to same file
+#[derive(Debug)]
+struct ChangelogGuard(PathBuf);
+
+impl ChangelogGuard {
+    fn acquire(path: PathBuf) -> Result<Self> {
+        let mut open = OPEN_CHANGELOGS.lock().expect("open changelogs");
+        if !open.insert(path.clone()) {
+            return Err(anyhow!(
+                "state of consumer is used by another stream: {}",
+                path.display()
+            ));
+        }
+        Ok(Self(path))
+    }
+}
+
+impl Drop for ChangelogGuard {
+    fn drop(&mut self) {
+        OPEN_CHANGELOGS
+            .lock()
+            .expect("open changelogs")
+            .remove(&self.0);
+    }
+}
+
+/// [`Log`] stored in a file as sequence of encoded entries.
+/// Appended entries are written to file by `sync`.
+#[derive(Debug)]
+struct ChangelogFile {
+    path: PathBuf,
+    /// next append replaces file content
+    compact: Arc<AtomicBool>,
+    file: Option<File>,
+    pending: Vec<u8>,
+}
+
+impl Log for ChangelogFile {
+    async fn read_from_end(&self) -> Result<impl Stream<Item = Result<Vec<u8>>>> {
+        let entries = read_entries(&self.path).await?;
+        Ok(futures_util::stream::iter(
+            entries.into_iter().rev().map(Ok),
+        ))
+    }
+
+    async fn append_batch(&mut self, entries: Vec<Vec<u8>>) -> Result<()> {
+        if self.compact.swap(false, Ordering::SeqCst) {
+            // snapshot contains all entries, pending changes are not needed
+            let mut bytes = Vec::new();
+            for entry in entries {
+                entry.encode(&mut bytes, CHANGELOG_VERSION)?;
+            }
+            let tmp = self.path.with_extension("tmp");
+            let mut file = File::create(&tmp).await?;
+            file.write_all(&bytes).await?;
+            file.sync_all().await?;
+            drop(file);
+            rename(&tmp, &self.path).await?;
+            self.file = None;
+            self.pending.clear();
+            debug!(path = %self.path.display(), "changelog compacted");
+            return Ok(());
+        }
+
+        for entry in entries {
+            entry.encode(&mut self.pending, CHANGELOG_VERSION)?;
+        }
+        Ok(())
+    }
+
+    async fn sync(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let file = match self.file.as_mut() {
+            Some(file) => file,
+            None => self.file.insert(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+                    .await?,
+            ),
+        };
+        file.write_all(&self.pending).await?;
+        file.sync_data().await?;
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+/// read entries of changelog, incomplete entry at the end is truncated
+async fn read_entries(path: &Path) -> Result<Vec<Vec<u8>>> {
+    let bytes = match read(path).await {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let len = bytes.len() as u64;
+    let mut cursor = Cursor::new(bytes);
+    let mut entries = Vec::new();
+    while cursor.position() < len {
+        let valid_len = cursor.position();
+        match Vec::<u8>::decode_from(&mut cursor, CHANGELOG_VERSION) {
+            Ok(entry) => entries.push(entry),
+            Err(err) => {
+                // partially written entry, when SPU stopped during append.
+                // It is removed so appended entries are not read as part of it
+                warn!(path = %path.display(), %err, valid_len, "truncating incomplete changelog entry");
+                OpenOptions::new()
+                    .write(true)
+                    .open(path)
+                    .await?
+                    .set_len(valid_len)
+                    .await?;
+                break;
+            }
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_future::fs::metadata;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_state_restored_from_changelog() {
+        let dir = std::env::temp_dir().join(format!("sm-state-{}", std::process::id()));
+        let path = dir.join("restored.log");
+        let _ = std::fs::remove_file(&path);
+
+        let mut storage = SmartModuleStateStorage::open(path.clone())
+            .await
+            .expect("open");
+        storage
+            .apply(vec![
+                (StateKey::new(0, "a"), Some(b"1".to_vec())),
+                (StateKey::new(0, "b"), Some(b"2".to_vec())),
+                (StateKey::new(0, "a"), None),
+            ])
+            .await
+            .expect("apply");
+        drop(storage);
+
+        let storage = SmartModuleStateStorage::open(path).await.expect("reopen");
+        assert_eq!(
+            storage.entries().await.expect("entries"),
+            vec![(StateKey::new(0, "b"), b"2".to_vec())]
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_changelog_compacted() {
+        let dir = std::env::temp_dir().join(format!("sm-state-{}", std::process::id()));
+        let path = dir.join("compacted.log");
+        let _ = std::fs::remove_file(&path);
+
+        let mut storage = SmartModuleStateStorage::with(path.clone(), 10)
+            .await
+            .expect("open");
+        for count in 0..10u32 {
+            storage
+                .apply(vec![(
+                    StateKey::new(0, "count"),
+                    Some(count.to_be_bytes().to_vec()),
+                )])
+                .await
+                .expect("apply");
+        }
+        let before = metadata(&path).await.expect("metadata").len();
+        storage
+            .apply(vec![(
+                StateKey::new(0, "count"),
+                Some(10u32.to_be_bytes().to_vec()),
+            )])
+            .await
+            .expect("apply");
+        let after = metadata(&path).await.expect("metadata").len();
+        assert!(after < before, "{after} < {before}");
+        drop(storage);
+
+        let storage = SmartModuleStateStorage::open(path).await.expect("reopen");
+        assert_eq!(
+            storage.entries().await.expect("entries"),
+            vec![(StateKey::new(0, "count"), 10u32.to_be_bytes().to_vec())]
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_incomplete_entry_truncated() {
+        let dir = std::env::temp_dir().join(format!("sm-state-{}", std::process::id()));
+        let path = dir.join("incomplete.log");
+        let _ = std::fs::remove_file(&path);
+
+        let mut storage = SmartModuleStateStorage::open(path.clone())
+            .await
+            .expect("open");
+        storage
+            .apply(vec![(StateKey::new(0, "a"), Some(b"1".to_vec()))])
+            .await
+            .expect("apply");
+        drop(storage);
+        let len = metadata(&path).await.expect("metadata").len();
+
+        // entry which was partially written when SPU stopped
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .expect("open file");
+        file.write_all(&[0, 0, 0, 100, 1, 2]).await.expect("write");
+        drop(file);
+
+        let mut storage = SmartModuleStateStorage::open(path.clone())
+            .await
+            .expect("reopen");
+        assert_eq!(metadata(&path).await.expect("metadata").len(), len);
+        storage
+            .apply(vec![(StateKey::new(0, "b"), Some(b"2".to_vec()))])
+            .await
+            .expect("apply");
+        drop(storage);
+
+        let storage = SmartModuleStateStorage::open(path).await.expect("reopen");
+        let mut entries = storage.entries().await.expect("entries");
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                (StateKey::new(0, "a"), b"1".to_vec()),
+                (StateKey::new(0, "b"), b"2".to_vec())
+            ]
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_changelog_used_by_one_stream() {
+        let dir = std::env::temp_dir().join(format!("sm-state-{}", std::process::id()));
+        let path = dir.join("exclusive.log");
+        let _ = std::fs::remove_file(&path);
+
+        let storage = SmartModuleStateStorage::open(path.clone())
+            .await
+            .expect("open");
+        assert!(SmartModuleStateStorage::open(path.clone()).await.is_err());
+        drop(storage);
+        SmartModuleStateStorage::open(path).await.expect("reopen");
+    }
+}
@@ -1,6 +1,10 @@
+use std::process;
 use std::sync::Arc;
 
+use flv_util::print_cli_err;
+
 use fluvio_auth::root::RootAuthorization;
+use fluvio_auth::rbac::{RbacAuthorization, read_policy_file};
 use fluvio_storage::FileReplica;
 
 use crate::config::{SpuConfig, SpuOpt};
@@ -10,6 +14,9 @@ use crate::services::public::create_public_server;
 use crate::core::DefaultSharedGlobalContext;
 use crate::core::GlobalContext;
 use crate::control_plane::ScDispatcher;
+use crate::transaction::TransactionController;
+use crate::group::GroupController;
+use crate::repair::RepairController;
 
 type FileReplicaContext = GlobalContext<FileReplica>;
 
@@ -24,7 +31,7 @@ pub fn main_loop(opt: SpuOpt) {
     use fluvio_future::task::run_block_on;
     use fluvio_future::timer::sleep;
 
-    use crate::monitoring::init_monitoring;
+    use crate::monitoring::{init_monitoring, init_metrics_server};
 
     // parse configuration (program exits on error)
     let (spu_config, tls_acceptor_option) = opt.process_spu_cli_or_exit();
@@ -47,6 +54,7 @@ pub fn main_loop(opt: SpuOpt) {
     run_block_on(async move {
         let ctx = create_services(spu_config.clone(), true, true);
 
+        init_metrics_server(ctx.clone());
         init_monitoring(ctx);
 
         if let Some(tls_config) = tls_acceptor_option {
@@ -74,10 +82,33 @@ pub fn create_services(
     let private_ep_addr = ctx.config().private_socket_addr().to_owned();
 
     if public {
-        let authorization = Arc::new(RootAuthorization::new());
-        let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), authorization);
-        let pub_server = create_public_server(public_ep_addr, auth_global_ctx);
-        pub_server.run();
+        // policies managed by SC are enforced whenever client identities are forwarded by TLS proxy,
+        // even if there is no policy file
+        let config = ctx.config();
+        if config.auth_policy.is_some() || config.x509_auth_scopes.is_some() {
+            if let Some(policy_path) = config.auth_policy.clone() {
+                match read_policy_file(&policy_path) {
+                    Ok(policy) => ctx.rbac_policy().set_file_policy(policy),
+                    Err(err) => {
+                        print_cli_err!(format!(
+                            "invalid policy file {}: {err}",
+                            policy_path.display()
+                        ));
+                        process::exit(-1);
+                    }
+                }
+                ctx.rbac_policy().watch_file(policy_path, read_policy_file);
+            }
+            let authorization = Arc::new(RbacAuthorization::new(ctx.rbac_policy().clone()));
+            let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), authorization);
+            let pub_server = create_public_server(public_ep_addr, auth_global_ctx);
+            pub_server.run();
+        } else {
+            let authorization = Arc::new(RootAuthorization::new());
+            let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), authorization);
+            let pub_server = create_public_server(public_ep_addr, auth_global_ctx);
+            pub_server.run();
+        }
     };
 
     if internal {
@@ -88,6 +119,10 @@ pub fn create_services(
     let sc_dispatcher = ScDispatcher::new(ctx.clone());
     sc_dispatcher.run();
 
+    TransactionController::new(ctx.clone()).run();
+    GroupController::new(ctx.clone()).run();
+    RepairController::new(ctx.clone()).run();
+
     ctx
 }
 
@@ -98,7 +133,10 @@ mod proxy {
 
     use flv_util::print_cli_err;
     use fluvio_future::openssl::TlsAcceptor;
-    use flv_tls_proxy::start as proxy_start;
+    use fluvio_auth::x509::X509Authenticator;
+    use flv_tls_proxy::{
+        start as proxy_start, start_with_authenticator as proxy_start_with_authenticator,
+    };
 
     use crate::config::SpuConfig;
 
@@ -107,8 +145,14 @@ mod proxy {
         let target = config.public_endpoint;
         info!("starting TLS proxy: {}", proxy_addr);
 
-        //TODO: add X509Authenticator
-        if let Err(err) = proxy_start(&proxy_addr, tls_acceptor, target).await {
+        let result = if let Some(x509_auth_scopes) = config.x509_auth_scopes {
+            let authenticator = Box::new(X509Authenticator::new(&x509_auth_scopes));
+            proxy_start_with_authenticator(&proxy_addr, tls_acceptor, target, authenticator).await
+        } else {
+            proxy_start(&proxy_addr, tls_acceptor, target).await
+        };
+
+        if let Err(err) = result {
             print_cli_err!(err);
             process::exit(-1);
         } else {
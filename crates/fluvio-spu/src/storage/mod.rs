@@ -12,7 +12,7 @@ use fluvio_spu_schema::Isolation;
 use fluvio_protocol::Encoder;
 use fluvio_protocol::record::{Offset, RecordSet};
 use fluvio_protocol::link::ErrorCode;
-use fluvio_storage::{ReplicaStorage, StorageError, OffsetInfo, ReplicaSlice};
+use fluvio_storage::{ReplicaStorage, StorageError, OffsetInfo, ReplicaSlice, EpochEndOffset};
 use fluvio_types::event::offsets::OffsetChangeListener;
 use fluvio_types::event::offsets::OffsetPublisher;
 
@@ -118,6 +118,15 @@ where
             .await
     }
 
+    /// find offset of first record at or after timestamp
+    pub async fn find_offset_by_timestamp(
+        &self,
+        timestamp: i64,
+    ) -> Result<Option<Offset>, ErrorCode> {
+        let read_storage = self.read().await;
+        read_storage.find_offset_by_timestamp(timestamp).await
+    }
+
     pub async fn update_hw(&self, hw: Offset) -> Result<bool, StorageError> {
         let mut writer = self.write().await;
         if writer.update_high_watermark(hw).await? {
@@ -128,6 +137,33 @@ where
         }
     }
 
+    /// move log start offset forward, return new log start offset
+    pub async fn delete_records(&self, offset: Offset) -> Result<Offset, StorageError> {
+        let mut writer = self.write().await;
+        writer.delete_records(offset).await
+    }
+
+    /// leader epoch of last batch in the log
+    pub async fn leader_epoch(&self) -> i32 {
+        self.read().await.get_leader_epoch()
+    }
+
+    /// last leader epoch at or before given epoch and offset where it ends
+    pub async fn leader_epoch_end_offset(&self, leader_epoch: i32) -> Option<EpochEndOffset> {
+        self.read().await.leader_epoch_end_offset(leader_epoch)
+    }
+
+    /// remove records at and after offset, return new log end offset
+    #[instrument(skip(self))]
+    pub async fn truncate(&self, offset: Offset) -> Result<Offset, StorageError> {
+        let mut writer = self.write().await;
+        let leo = writer.truncate(offset).await?;
+        debug!(leo, "truncated");
+        self.leo.update(leo);
+        self.hw.update(writer.get_hw());
+        Ok(leo)
+    }
+
     #[instrument(skip(self, records, hw_update))]
     pub async fn write_record_set<R: BatchRecords>(
         &self,
@@ -0,0 +1,109 @@
+//!
+//! # Telemetry
+//!
+//! Links SPU spans exported over OTLP to traces of records.
+//!
+
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+
+use tracing::{debug, Span};
+
+use fluvio::telemetry::add_link;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::record::{Offset, RawRecords, Record, RecordData, RecordSet, TRACEPARENT_HEADER};
+
+/// link current span to traces of producers which sent the records
+pub(crate) fn link_record_traces(records: &RecordSet<RawRecords>) {
+    let span = Span::current();
+    if span.is_disabled() {
+        return;
+    }
+
+    let mut linked = HashSet::new();
+    for batch in &records.batches {
+        let Ok(memory_records) = batch.memory_records() else {
+            continue;
+        };
+        for record in memory_records {
+            if let Some(context) = record.trace_context() {
+                if linked.insert(context) {
+                    add_link(&span, &context);
+                }
+            }
+        }
+    }
+}
+
+/// SmartModules built with SDK older than record headers drop them.
+/// Trace context of input record is carried over to outputs with same offset,
+/// so traces are not broken by transforms.
+pub(crate) fn restore_trace_context(input_records: &[u8], output: &mut [Record]) {
+    let is_missing = |record: &Record| record.headers().get(TRACEPARENT_HEADER).is_none();
+    if !output.iter().any(is_missing)
+        || !input_records
+            .windows(TRACEPARENT_HEADER.len())
+            .any(|window| window == TRACEPARENT_HEADER.as_bytes())
+    {
+        return;
+    }
+
+    let mut input: Vec<Record> = vec![];
+    if let Err(err) = input.decode(&mut Cursor::new(input_records), 0) {
+        debug!(%err, "unable to decode input records");
+        return;
+    }
+    let contexts: HashMap<Offset, RecordData> = input
+        .into_iter()
+        .filter_map(|record| {
+            let offset_delta = record.offset_delta();
+            record
+                .headers()
+                .get(TRACEPARENT_HEADER)
+                .map(|context| (offset_delta, context.clone()))
+        })
+        .collect();
+    for record in output.iter_mut().filter(|record| is_missing(record)) {
+        if let Some(context) = contexts.get(&record.offset_delta()) {
+            record
+                .headers_mut()
+                .insert(TRACEPARENT_HEADER, context.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_protocol::Encoder;
+    use fluvio_protocol::record::{Record, TraceContext};
+
+    use super::restore_trace_context;
+
+    #[test]
+    fn test_restore_trace_context() {
+        let context: TraceContext = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+            .parse()
+            .expect("parse");
+        let mut input = vec![
+            Record::new("a").with_trace_context(&context),
+            Record::new("b"),
+            Record::new("c").with_header("other", "x"),
+        ];
+        for (offset_delta, record) in input.iter_mut().enumerate() {
+            record
+                .get_mut_header()
+                .set_offset_delta(offset_delta as i64);
+        }
+        let mut input_bytes = vec![];
+        input.encode(&mut input_bytes, 0).expect("encode");
+
+        // SmartModule without header support returned new records for a and c
+        let mut output = vec![Record::new("A"), Record::new("C")];
+        output[1].get_mut_header().set_offset_delta(2);
+        restore_trace_context(&input_bytes, &mut output);
+
+        assert_eq!(output[0].trace_context(), Some(context));
+        assert_eq!(output[1].trace_context(), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use tracing::{debug, error, info, warn};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::record::ControlRecordType;
+
+use crate::core::DefaultSharedGlobalContext;
+
+use super::coordinator::MAX_TRANSACTION_TIMEOUT;
+use super::write_local_marker;
+
+/// how often expired transactions are checked
+const EXPIRATION_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// partition leader aborts transactions left open by coordinator, e.g. when coordinator SPU is lost.
+/// Longer than coordinator timeout, so coordinator normally ends transaction first.
+const LEADER_TRANSACTION_TIMEOUT: Duration =
+    MAX_TRANSACTION_TIMEOUT.saturating_add(Duration::from_secs(60));
+
+/// Loads saved transactions and aborts transactions which were not ended within timeout,
+/// both coordinated by this SPU and open in partitions led by this SPU
+pub struct TransactionController {
+    ctx: DefaultSharedGlobalContext,
+}
+
+impl TransactionController {
+    pub fn new(ctx: DefaultSharedGlobalContext) -> Self {
+        Self { ctx }
+    }
+
+    pub fn run(self) {
+        spawn(self.dispatch_loop());
+    }
+
+    async fn dispatch_loop(self) {
+        if let Err(err) = self.ctx.transactions().load().await {
+            error!("failed to load transactions: {err:#}");
+        }
+        debug!("starting transaction expiration loop");
+        loop {
+            self.ctx.transactions().expire(&self.ctx).await;
+            self.abort_expired_partition_transactions().await;
+            sleep(EXPIRATION_CHECK_INTERVAL).await;
+        }
+    }
+
+    async fn abort_expired_partition_transactions(&self) {
+        let leaders: Vec<_> = self
+            .ctx
+            .leaders_state()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for leader in leaders {
+            let expired = leader
+                .read()
+                .await
+                .expired_transactions(LEADER_TRANSACTION_TIMEOUT);
+            for producer_id in expired {
+                info!(
+                    replica = %leader.id(),
+                    producer_id,
+                    "transaction open in partition timed out, aborting"
+                );
+                if let Err(err) = write_local_marker(
+                    &self.ctx,
+                    leader.id(),
+                    producer_id,
+                    ControlRecordType::Abort,
+                )
+                .await
+                {
+                    warn!(replica = %leader.id(), producer_id, ?err, "failed to abort transaction");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_lock::Mutex;
+use futures_util::StreamExt;
+use tracing::{debug, info, warn, instrument};
+use anyhow::Result;
+
+use fluvio_future::fs::{create_dir_all, read, read_dir, remove_file, rename, write};
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{ControlRecordType, ReplicaKey};
+
+use crate::core::DefaultSharedGlobalContext;
+
+use super::write_marker;
+
+/// transactions are aborted if not ended within this time, regardless of timeout requested by producer
+pub const MAX_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+const TRANSACTION_FILE_EXTENSION: &str = "txn";
+
+#[derive(Debug, Default, Clone, Encoder, Decoder)]
+struct TransactionMetadata {
+    producer_id: i64,
+    partitions: Vec<ReplicaKey>,
+    /// milliseconds since epoch after which transaction is aborted
+    deadline_ms: i64,
+    /// outcome after transaction was ended, true for commit
+    ending: Option<bool>,
+}
+
+/// transaction is locked while its file or markers are written
+type SharedTransaction = Arc<Mutex<TransactionMetadata>>;
+
+/// Keeps open transactions of producers allocated by this SPU.
+/// Each transaction is saved to its own file, so transactions survive restart.
+#[derive(Debug)]
+pub struct TransactionCoordinator {
+    dir: PathBuf,
+    transactions: std::sync::Mutex<HashMap<i64, SharedTransaction>>,
+}
+
+impl TransactionCoordinator {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            transactions: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, producer_id: i64) -> Option<SharedTransaction> {
+        self.transactions
+            .lock()
+            .expect("transactions")
+            .get(&producer_id)
+            .cloned()
+    }
+
+    /// transaction is still open after its lock was acquired, it is removed when completed
+    fn is_current(&self, producer_id: i64, txn: &SharedTransaction) -> bool {
+        self.get(producer_id)
+            .is_some_and(|current| Arc::ptr_eq(&current, txn))
+    }
+
+    /// load saved transactions, transactions which were ending are completed at next expiration check
+    pub(crate) async fn load(&self) -> Result<()> {
+        let mut entries = match read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut loaded = HashMap::new();
+        while let Some(entry) = entries.next().await {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(TRANSACTION_FILE_EXTENSION) {
+                continue;
+            }
+            let bytes = read(&path).await?;
+            let mut txn = TransactionMetadata::decode_from(&mut bytes.as_slice(), 0)?;
+            if txn.ending.is_some() {
+                txn.deadline_ms = 0;
+            }
+            loaded.insert(txn.producer_id, Arc::new(Mutex::new(txn)));
+        }
+        if !loaded.is_empty() {
+            info!(count = loaded.len(), "transactions loaded");
+        }
+        self.transactions
+            .lock()
+            .expect("transactions")
+            .extend(loaded);
+        Ok(())
+    }
+
+    /// add partitions to transaction of producer, starting new transaction if there is none
+    #[instrument(skip(self))]
+    pub async fn add_partitions(
+        &self,
+        producer_id: i64,
+        partitions: Vec<ReplicaKey>,
+        timeout: Duration,
+    ) -> Result<(), ErrorCode> {
+        let mut txn = loop {
+            let shared = self
+                .transactions
+                .lock()
+                .expect("transactions")
+                .entry(producer_id)
+                .or_insert_with(|| {
+                    debug!(producer_id, "transaction started");
+                    Arc::new(Mutex::new(TransactionMetadata {
+                        producer_id,
+                        deadline_ms: now_ms()
+                            + timeout.min(MAX_TRANSACTION_TIMEOUT).as_millis() as i64,
+                        ..Default::default()
+                    }))
+                })
+                .clone();
+            let txn = shared.lock_arc().await;
+            // transaction was completed while waiting, start new one
+            if self.is_current(producer_id, &shared) {
+                break txn;
+            }
+        };
+        if txn.ending.is_some() {
+            return Err(ErrorCode::InvalidTxnState(
+                "previous transaction is still ending".to_owned(),
+            ));
+        }
+        let mut changed = false;
+        for partition in partitions {
+            if !txn.partitions.contains(&partition) {
+                txn.partitions.push(partition);
+                changed = true;
+            }
+        }
+        if changed {
+            self.save(&txn).await?;
+        }
+        Ok(())
+    }
+
+    /// commit or abort transaction of producer.
+    /// Ending transaction which is already ending with same outcome retries writing of markers.
+    #[instrument(skip(self, ctx))]
+    pub async fn end(
+        &self,
+        ctx: &DefaultSharedGlobalContext,
+        producer_id: i64,
+        commit: bool,
+    ) -> Result<(), ErrorCode> {
+        let Some(shared) = self.get(producer_id) else {
+            debug!(producer_id, "no partitions were written in transaction");
+            return Ok(());
+        };
+        let mut txn = shared.lock_arc().await;
+        if !self.is_current(producer_id, &shared) {
+            debug!(producer_id, "transaction was already completed");
+            return Ok(());
+        }
+        match txn.ending {
+            Some(ending) if ending != commit => {
+                return Err(ErrorCode::InvalidTxnState(format!(
+                    "transaction is already {}",
+                    outcome(ending)
+                )));
+            }
+            Some(_) => {}
+            None => {
+                txn.ending = Some(commit);
+                self.save(&txn).await?;
+            }
+        }
+        self.complete(ctx, &txn).await
+    }
+
+    /// abort transactions which were not ended in time and retry transactions which failed to end
+    pub(crate) async fn expire(&self, ctx: &DefaultSharedGlobalContext) {
+        let now = now_ms();
+        let transactions: Vec<_> = self
+            .transactions
+            .lock()
+            .expect("transactions")
+            .values()
+            .cloned()
+            .collect();
+        for shared in transactions {
+            let mut txn = shared.lock_arc().await;
+            let producer_id = txn.producer_id;
+            if txn.deadline_ms > now || !self.is_current(producer_id, &shared) {
+                continue;
+            }
+            if txn.ending.is_none() {
+                info!(producer_id, "transaction timed out, aborting");
+                txn.ending = Some(false);
+                if let Err(err) = self.save(&txn).await {
+                    warn!(producer_id, ?err, "failed to save transaction");
+                }
+            }
+            if let Err(err) = self.complete(ctx, &txn).await {
+                warn!(producer_id, ?err, "failed to end transaction, will retry");
+            }
+        }
+    }
+
+    /// write markers to all partitions of ending transaction and forget it.
+    /// Markers written again on retry are ignored by consumers.
+    async fn complete(
+        &self,
+        ctx: &DefaultSharedGlobalContext,
+        txn: &TransactionMetadata,
+    ) -> Result<(), ErrorCode> {
+        let commit = txn.ending.unwrap_or_default();
+        let marker = if commit {
+            ControlRecordType::Commit
+        } else {
+            ControlRecordType::Abort
+        };
+        for replica_id in &txn.partitions {
+            write_marker(ctx, replica_id, txn.producer_id, marker).await?;
+        }
+        if let Err(err) = remove_file(self.path(txn.producer_id)).await {
+            warn!(
+                producer_id = txn.producer_id,
+                ?err,
+                "failed to remove transaction file"
+            );
+        }
+        self.transactions
+            .lock()
+            .expect("transactions")
+            .remove(&txn.producer_id);
+        debug!(
+            producer_id = txn.producer_id,
+            "transaction {}",
+            outcome(commit)
+        );
+        Ok(())
+    }
+
+    async fn save(&self, txn: &TransactionMetadata) -> Result<(), ErrorCode> {
+        let inner = async {
+            let mut bytes = Vec::new();
+            txn.encode(&mut bytes, 0)?;
+            create_dir_all(&self.dir).await?;
+            let path = self.path(txn.producer_id);
+            let tmp_path = path.with_extension("tmp");
+            write(&tmp_path, bytes).await?;
+            rename(&tmp_path, path).await?;
+            Ok::<_, std::io::Error>(())
+        };
+        inner
+            .await
+            .map_err(|err| ErrorCode::Other(format!("failed to save transaction: {err}")))
+    }
+
+    fn path(&self, producer_id: i64) -> PathBuf {
+        self.dir
+            .join(format!("{producer_id}.{TRANSACTION_FILE_EXTENSION}"))
+    }
+}
+
+fn outcome(commit: bool) -> &'static str {
+    if commit { "committed" } else { "aborted" }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_transaction_coordinator_add_partitions() {
+        let dir = temp_dir().join("test_transaction_coordinator_add_partitions");
+        ensure_new_dir(&dir).expect("new");
+
+        let coordinator = TransactionCoordinator::new(dir.clone());
+        coordinator
+            .add_partitions(
+                1,
+                vec![ReplicaKey::new("a", 0u32), ReplicaKey::new("b", 1u32)],
+                Duration::from_secs(60),
+            )
+            .await
+            .expect("add");
+        coordinator
+            .add_partitions(1, vec![ReplicaKey::new("a", 0u32)], Duration::from_secs(60))
+            .await
+            .expect("add");
+
+        // ending transaction doesn't accept new partitions
+        let shared = coordinator.get(1).expect("txn");
+        let mut txn = shared.lock().await;
+        txn.ending = Some(true);
+        coordinator.save(&txn).await.expect("save");
+        drop(txn);
+        assert!(matches!(
+            coordinator
+                .add_partitions(1, vec![ReplicaKey::new("c", 0u32)], Duration::from_secs(60))
+                .await,
+            Err(ErrorCode::InvalidTxnState(_))
+        ));
+
+        // transactions survive restart, ending transactions are completed right away
+        let loaded = TransactionCoordinator::new(dir);
+        loaded.load().await.expect("load");
+        let shared = loaded.get(1).expect("txn");
+        let txn = shared.lock().await;
+        assert_eq!(txn.partitions.len(), 2);
+        assert_eq!(txn.ending, Some(true));
+        assert_eq!(txn.deadline_ms, 0);
+    }
+}
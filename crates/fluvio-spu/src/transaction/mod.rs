@@ -0,0 +1,62 @@
+//!
+//! # Producer transactions
+//!
+//! SPU which allocated producer id coordinates transactions of that producer.
+//! Coordinator remembers partitions written in transaction and writes commit or abort marker
+//! to each of them when transaction ends. Markers are written by leader of partition.
+//!
+
+mod coordinator;
+mod controller;
+
+pub use self::coordinator::TransactionCoordinator;
+pub use self::controller::TransactionController;
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{ControlRecordType, ReplicaKey};
+use tracing::debug;
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::services::internal::WriteTxnMarkerRequest;
+use crate::services::public::send_private_request_to_leader;
+
+/// write transaction marker to partition led by this SPU
+pub(crate) async fn write_local_marker(
+    ctx: &DefaultSharedGlobalContext,
+    replica_id: &ReplicaKey,
+    producer_id: i64,
+    marker: ControlRecordType,
+) -> Result<(), ErrorCode> {
+    let Some(leader) = ctx.leaders_state().get(replica_id).await else {
+        return Err(ErrorCode::NotLeaderForPartition);
+    };
+    leader
+        .write_txn_marker(producer_id, marker, ctx.follower_notifier())
+        .await
+        .map_err(|err| ErrorCode::Other(err.to_string()))?;
+    Ok(())
+}
+
+/// write transaction marker to partition, forwarding it to leader of partition if needed
+async fn write_marker(
+    ctx: &DefaultSharedGlobalContext,
+    replica_id: &ReplicaKey,
+    producer_id: i64,
+    marker: ControlRecordType,
+) -> Result<(), ErrorCode> {
+    if ctx.leaders_state().get(replica_id).await.is_some() {
+        return write_local_marker(ctx, replica_id, producer_id, marker).await;
+    }
+    debug!(%replica_id, producer_id, ?marker, "forward transaction marker to leader");
+    let request = WriteTxnMarkerRequest {
+        replica_id: replica_id.clone(),
+        producer_id,
+        commit: marker == ControlRecordType::Commit,
+    };
+    let response = send_private_request_to_leader(ctx, replica_id, request).await?;
+    if response.error_code.is_error() {
+        Err(response.error_code)
+    } else {
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use std::io::Error as IoError;
 use std::io::Cursor;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::mem::size_of;
 use std::path::Path;
 
 use fluvio_protocol::record::BatchHeader;
@@ -15,9 +16,11 @@ use bytes::Bytes;
 
 use fluvio_future::fs::File;
 use fluvio_protocol::record::{
-    Batch, BatchRecords, BATCH_HEADER_SIZE, BATCH_FILE_HEADER_SIZE, MemoryRecords,
+    Batch, BatchRecords, BATCH_HEADER_SIZE, BATCH_FILE_HEADER_SIZE, BATCH_PREAMBLE_SIZE,
+    MemoryRecords,
 };
 use fluvio_protocol::record::Size;
+use fluvio_protocol::Decoder;
 
 use crate::file::FileBytesIterator;
 
@@ -41,6 +44,82 @@ pub enum BatchHeaderError {
     },
 }
 
+/// Batch which content doesn't match crc stored in its header
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+#[error(
+    "Corrupt batch {base_offset}..={last_offset} at {pos}, crc: {actual:#x} expected: {expected:#x}"
+)]
+pub struct CorruptBatch {
+    pub base_offset: Offset,
+    pub last_offset: Offset,
+    /// position in segment file
+    pub pos: Size,
+    /// length including preamble
+    pub len: Size,
+    pub expected: u32,
+    pub actual: u32,
+}
+
+/// crc covers bytes after crc field: preamble, partition leader epoch, magic and crc are excluded
+const BATCH_CRC_POS: usize = BATCH_PREAMBLE_SIZE + size_of::<i32>() + size_of::<i8>();
+const BATCH_CRC_END: usize = BATCH_CRC_POS + size_of::<u32>();
+const BATCH_LAST_OFFSET_DELTA_POS: usize = BATCH_CRC_END + size_of::<i16>();
+
+/// check crc of complete batches in buffer starting at batch boundary at `pos` of segment.
+/// Returns length of verified batches, partial batch at the end is not checked.
+pub fn verify_batches_crc(bytes: &[u8], pos: Size) -> Result<usize, CorruptBatch> {
+    let mut verified = 0;
+    while let Some(len) = batch_len_at(&bytes[verified..]) {
+        if verified + len > bytes.len() {
+            break;
+        }
+        verify_batch_crc(&bytes[verified..verified + len], pos + verified as Size)?;
+        verified += len;
+    }
+    Ok(verified)
+}
+
+/// check crc of single batch, bytes must contain whole batch
+pub(crate) fn verify_batch_crc(bytes: &[u8], pos: Size) -> Result<(), CorruptBatch> {
+    let expected = read_u32(bytes, BATCH_CRC_POS);
+    let actual = crc32c::crc32c(&bytes[BATCH_CRC_END..]);
+    if expected == actual {
+        return Ok(());
+    }
+    Err(CorruptBatch {
+        base_offset: batch_base_offset(bytes),
+        last_offset: batch_last_offset(bytes),
+        pos,
+        len: bytes.len() as Size,
+        expected,
+        actual,
+    })
+}
+
+pub(crate) fn batch_base_offset(bytes: &[u8]) -> Offset {
+    i64::from_be_bytes(bytes[..size_of::<Offset>()].try_into().expect("preamble"))
+}
+
+/// last offset of batch, bytes must contain whole header
+pub(crate) fn batch_last_offset(bytes: &[u8]) -> Offset {
+    let last_offset_delta = read_u32(bytes, BATCH_LAST_OFFSET_DELTA_POS) as i32;
+    batch_base_offset(bytes) + last_offset_delta as Offset
+}
+
+/// length of batch including preamble, if buffer contains whole header
+pub(crate) fn batch_len_at(bytes: &[u8]) -> Option<usize> {
+    if bytes.len() < BATCH_FILE_HEADER_SIZE {
+        return None;
+    }
+    // corrupt length still covers header, so crc check fails instead of reading past it
+    let batch_len = (read_u32(bytes, size_of::<Offset>()) as i32).max(BATCH_HEADER_SIZE as i32);
+    Some(BATCH_PREAMBLE_SIZE + batch_len as usize)
+}
+
+fn read_u32(bytes: &[u8], pos: usize) -> u32 {
+    u32::from_be_bytes(bytes[pos..pos + 4].try_into().expect("4 bytes"))
+}
+
 /// hold information about position of batch in the file
 pub struct FileBatchPos<R>
 where
@@ -48,6 +127,7 @@ where
 {
     inner: Batch<R>,
     pos: Size,
+    corrupt: Option<CorruptBatch>,
 }
 
 impl<R> Unpin for FileBatchPos<R> where R: BatchRecords {}
@@ -56,10 +136,12 @@ impl<R> FileBatchPos<R>
 where
     R: BatchRecords,
 {
-    /// read from storage byt iterator
+    /// read from storage byt iterator.
+    /// With `verify_crc`, records of corrupt batch are not decoded and corruption is recorded instead.
     #[instrument(skip(file))]
     pub(crate) async fn read_from<S: StorageBytesIterator>(
         file: &mut S,
+        verify_crc: bool,
     ) -> Result<Option<FileBatchPos<R>>> {
         let pos = file.get_pos();
         trace!(pos, "reading from pos");
@@ -89,6 +171,7 @@ where
             .into());
         }
 
+        let header_bytes = bytes.clone();
         let mut cursor = Cursor::new(bytes);
         let mut batch: Batch<R> = Batch::default();
         batch.decode_from_file_buf(&mut cursor, 0)?;
@@ -142,10 +225,46 @@ where
             .into());
         }
 
+        if verify_crc {
+            let actual = crc32c::crc32c_append(
+                crc32c::crc32c(&header_bytes[BATCH_CRC_END..]),
+                &bytes[..content_len],
+            );
+            if actual != batch.header.crc {
+                let corrupt = CorruptBatch {
+                    base_offset: batch.get_base_offset(),
+                    last_offset: batch.get_last_offset(),
+                    pos,
+                    len: (BATCH_FILE_HEADER_SIZE + content_len) as Size,
+                    expected: batch.header.crc,
+                    actual,
+                };
+                error!(%corrupt, "batch crc mismatch");
+                return Ok(Some(FileBatchPos {
+                    inner: batch,
+                    pos,
+                    corrupt: Some(corrupt),
+                }));
+            }
+        }
+
         let mut cursor = Cursor::new(bytes);
+        // schema id is stored in front of the records
+        if batch.header.has_schema() {
+            batch.schema_id.decode(&mut cursor, 0)?;
+        }
         batch.mut_records().decode(&mut cursor, 0)?;
 
-        Ok(Some(FileBatchPos { inner: batch, pos }))
+        Ok(Some(FileBatchPos {
+            inner: batch,
+            pos,
+            corrupt: None,
+        }))
+    }
+
+    /// crc mismatch found when batch was read with crc verification
+    pub fn corrupt(&self) -> Option<&CorruptBatch> {
+        self.corrupt.as_ref()
     }
 
     #[inline(always)]
@@ -166,6 +285,7 @@ where
 // Stream to iterate over batches in a file
 pub struct FileBatchStream<R = MemoryRecords, S = FileBytesIterator> {
     invalid: bool,
+    verify_crc: bool,
     byte_iterator: S,
     data: PhantomData<R>,
 }
@@ -180,6 +300,11 @@ where
         self.invalid
     }
 
+    /// check crc of batches, corrupt batches are returned without records
+    pub fn set_verify_crc(&mut self, verify_crc: bool) {
+        self.verify_crc = verify_crc;
+    }
+
     #[inline(always)]
     pub fn get_pos(&self) -> Size {
         self.byte_iterator.get_pos()
@@ -210,6 +335,7 @@ where
         Ok(Self {
             byte_iterator,
             invalid: false,
+            verify_crc: false,
             data: PhantomData,
         })
     }
@@ -220,6 +346,7 @@ where
         Ok(Self {
             byte_iterator,
             invalid: false,
+            verify_crc: false,
             data: PhantomData,
         })
     }
@@ -231,7 +358,7 @@ where
         if self.invalid {
             return Err(anyhow!("stream has been invalidated"));
         }
-        match FileBatchPos::read_from(&mut self.byte_iterator).await {
+        match FileBatchPos::read_from(&mut self.byte_iterator, self.verify_crc).await {
             Ok(batch_res) => Ok(batch_res),
             Err(err) => {
                 error!("error getting batch: {}, invalidating", err);
@@ -271,6 +398,7 @@ mod tests {
     use flv_util::fixture::ensure_new_dir;
     use fluvio_protocol::fixture::create_batch;
     use fluvio_protocol::fixture::create_batch_with_producer;
+    use fluvio_protocol::record::SchemaId;
 
     use crate::config::ReplicaConfig;
     use crate::segment::MutableSegment;
@@ -341,4 +469,39 @@ mod tests {
         let batch2 = batch_stream.try_next().await.expect("ok").expect("batch");
         assert_eq!(batch2.get_batch().get_last_offset(), 303);
     }
+
+    #[fluvio_future::test]
+    async fn test_batch_stream_schema_id() {
+        let test_dir = temp_dir().join("batch-stream-schema-id");
+        ensure_new_dir(&test_dir).expect("new");
+
+        let option = default_option(test_dir.clone()).shared();
+
+        let mut active_segment = MutableSegment::create(300, option).await.expect("create");
+
+        let mut batch = create_batch();
+        batch.set_schema_id(SchemaId::from(42));
+        active_segment
+            .append_batch(&mut batch)
+            .await
+            .expect("write");
+        active_segment
+            .append_batch(&mut create_batch_with_producer(25, 2, TEST_RECORD))
+            .await
+            .expect("batch");
+
+        let mut batch_stream = active_segment
+            .open_default_batch_stream()
+            .await
+            .expect("open file batch stream");
+
+        let batch1 = batch_stream.try_next().await.expect("ok").expect("batch");
+        let batch1 = batch1.get_batch();
+        assert_eq!(batch1.schema_id(), SchemaId::from(42));
+        assert_eq!(batch1.records().len(), 2);
+        assert_eq!(batch1.records()[0].value().as_ref(), TEST_RECORD);
+        let batch2 = batch_stream.try_next().await.expect("ok").expect("batch");
+        assert_eq!(batch2.get_batch().get_last_offset(), 303);
+        assert_eq!(batch2.get_batch().records().len(), 2);
+    }
 }
@@ -1,21 +1,28 @@
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 use std::ops::Div;
 use std::ops::Rem;
 
-use tracing::{debug, info, instrument};
+use tracing::{debug, error, info, instrument};
 
 use fluvio_future::task::spawn;
+use fluvio_protocol::record::Offset;
 use fluvio_future::timer::sleep;
 use fluvio_types::event::StickyEvent;
 
+use crate::compaction::{compact_segments, purge_before};
 use crate::config::{SharedReplicaConfig, StorageConfig};
 use crate::replica::ReplicaSize;
 use crate::segments::SharedSegments;
 
 /// Replica cleaner. This is a background task that periodically checks for expired segments and
 /// removes them. It also enforces max partition size by removing first segments if replica size is
-/// exceeded. In the future, this may be done by a central cleaner pool instead of per a replica.
+/// exceeded. Segments before log start offset moved by delete records are removed as well, and
+/// closed segment containing log start offset is rewritten without records before it.
+/// If compaction is enabled, closed segments are compacted by key.
+/// With tiered storage, closed segments are uploaded to remote store and max partition size
+/// is enforced by evicting local copies of uploaded segments instead of removing them.
+/// In the future, this may be done by a central cleaner pool instead of per a replica.
 #[derive(Debug)]
 pub(crate) struct Cleaner {
     config: Arc<StorageConfig>,
@@ -23,6 +30,25 @@ pub(crate) struct Cleaner {
     segments: Arc<SharedSegments>,
     replica_size: Arc<ReplicaSize>,
     end_event: Arc<StickyEvent>,
+    compaction: Mutex<CompactionState>,
+    /// log start offset up to which records were purged from segment containing it
+    purged_before: Mutex<Offset>,
+}
+
+/// state of last compaction, used to skip compaction if nothing has changed
+#[derive(Debug, Default)]
+struct CompactionState {
+    segments: Vec<Offset>,
+    next_tombstone_expiry: Option<SystemTime>,
+}
+
+impl CompactionState {
+    fn is_stale(&self, segments: &[Offset]) -> bool {
+        self.segments != segments
+            || self
+                .next_tombstone_expiry
+                .is_some_and(|expiry| expiry <= SystemTime::now())
+    }
 }
 
 impl Cleaner {
@@ -39,6 +65,8 @@ impl Cleaner {
             segments,
             replica_size,
             end_event,
+            compaction: Mutex::new(CompactionState::default()),
+            purged_before: Mutex::new(0),
         });
 
         let cleaner_ref = cleaner.clone();
@@ -72,8 +100,12 @@ impl Cleaner {
                     break;
                 },
                 _ = sleep(sleep_period) => {
+                    self.enforce_log_start().await;
+                    self.purge_log_start().await;
+                    self.offload().await;
                     self.enforce_size().await;
                     self.enforce_ttl().await;
+                    self.enforce_compaction().await;
                 }
             }
         }
@@ -81,6 +113,19 @@ impl Cleaner {
         info!("cleaner end");
     }
 
+    #[instrument(skip(self))]
+    async fn offload(&self) {
+        if !self.segments.is_tiered() {
+            return;
+        }
+        if let Err(err) = self.segments.offload().await {
+            error!(?err, "segment offload failed");
+        }
+        // segments fetched back from remote store count to local size as well
+        let read = self.segments.read().await;
+        self.replica_size.store_prev(read.occupied_memory());
+    }
+
     #[instrument(skip(self))]
     async fn enforce_size(&self) {
         let replica_size = self.replica_size.get();
@@ -98,18 +143,75 @@ impl Cleaner {
                 segments_to_remove = count_to_remove,
                 "replica size exceeded max partition size"
             );
-            let segments_to_remove = self
-                .segments
-                .read()
-                .await
-                .find_first(count_to_remove as usize);
-            self.segments.remove_segments(&segments_to_remove).await;
+            if self.segments.is_tiered() {
+                let segments_to_evict = self
+                    .segments
+                    .read()
+                    .await
+                    .find_first_uploaded(count_to_remove as usize);
+                self.segments.evict_segments(&segments_to_evict).await;
+            } else {
+                let segments_to_remove = self
+                    .segments
+                    .read()
+                    .await
+                    .find_first(count_to_remove as usize);
+                self.segments.remove_segments(&segments_to_remove).await;
+            }
 
             let read = self.segments.read().await;
             self.replica_size.store_prev(read.occupied_memory());
         }
     }
 
+    /// remove segments which only contain records before log start offset
+    #[instrument(skip(self))]
+    pub(crate) async fn enforce_log_start(&self) {
+        let log_start_offset = self.segments.log_start_offset();
+        let deleted_segments = self
+            .segments
+            .read()
+            .await
+            .find_segments_before(log_start_offset);
+        if !deleted_segments.is_empty() {
+            debug!(
+                log_start_offset,
+                deleted = deleted_segments.len(),
+                "segments before log start offset"
+            );
+            self.segments.remove_segments(&deleted_segments).await;
+            let read = self.segments.read().await;
+            self.replica_size.store_prev(read.occupied_memory());
+        }
+    }
+
+    /// rewrite segment containing log start offset, records in active segment are purged after roll over
+    #[instrument(skip(self))]
+    async fn purge_log_start(&self) {
+        let log_start_offset = self.segments.log_start_offset();
+        if log_start_offset <= *self.purged_before.lock().expect("purge state") {
+            return;
+        }
+        match purge_before(&self.segments, &self.replica_config, log_start_offset).await {
+            Ok(Some(removed)) => {
+                *self.purged_before.lock().expect("purge state") = log_start_offset;
+                if removed > 0 {
+                    let read = self.segments.read().await;
+                    self.replica_size.store_prev(read.occupied_memory());
+                }
+            }
+            Ok(None) => {
+                debug!(
+                    log_start_offset,
+                    "log start offset is not in closed segment"
+                );
+            }
+            Err(err) => {
+                error!(?err, "purge before log start offset failed");
+            }
+        }
+    }
+
     #[instrument(skip(self))]
     async fn enforce_ttl(&self) {
         let retention_secs =
@@ -130,6 +232,44 @@ impl Cleaner {
             self.replica_size.store_prev(read.occupied_memory());
         }
     }
+
+    #[instrument(skip(self))]
+    async fn enforce_compaction(&self) {
+        if !self.replica_config.compact {
+            return;
+        }
+        let segments = self.segments.read().await.find_first(usize::MAX);
+        if !self
+            .compaction
+            .lock()
+            .expect("compaction state")
+            .is_stale(&segments)
+        {
+            debug!("no change since last compaction");
+            return;
+        }
+
+        match compact_segments(&self.segments, &self.replica_config).await {
+            Ok(result) => {
+                debug!(
+                    compacted = result.compacted_segments,
+                    removed = result.removed_records,
+                    "compaction done"
+                );
+                *self.compaction.lock().expect("compaction state") = CompactionState {
+                    segments,
+                    next_tombstone_expiry: result.next_tombstone_expiry,
+                };
+                if result.compacted_segments > 0 {
+                    let read = self.segments.read().await;
+                    self.replica_size.store_prev(read.occupied_memory());
+                }
+            }
+            Err(err) => {
+                error!(?err, "compaction failed");
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -145,18 +285,25 @@ mod tests {
     use fluvio_future::timer::sleep;
     use flv_util::fixture::ensure_new_dir;
     use fluvio_protocol::fixture::create_batch;
-    use fluvio_protocol::record::Offset;
+    use fluvio_protocol::record::{Batch, Offset, RawRecords, Record};
 
+    use crate::batch::FileBatchStream;
     use crate::config::SharedReplicaConfig;
+    use crate::records::FileRecords;
     use crate::segment::MutableSegment;
     use crate::segment::ReadSegment;
     use crate::replica::ReplicaSize;
-    use crate::config::{ReplicaConfig, StorageConfig};
+    use crate::config::{ReplicaConfig, RemoteStoreConfig, StorageConfig};
+    use crate::tiered::RemoteTier;
     use fluvio_types::event::StickyEvent;
 
     use crate::segments::{SegmentList, SharedSegments};
 
     use crate::cleaner::Cleaner;
+    use crate::compaction::{COMPACTION_DIR, recover_staged_segments};
+    use crate::index::EXTENSION as INDEX_EXTENSION;
+    use crate::records::MESSAGE_LOG_EXTENSION;
+    use crate::util::generate_file_name;
 
     #[fluvio_future::test]
     async fn test_enforce_size_delete_one() {
@@ -200,6 +347,65 @@ mod tests {
         assert_eq!(read.occupied_memory(), replica_size.get());
     }
 
+    #[fluvio_future::test]
+    async fn test_enforce_size_evicts_offloaded_segments() {
+        //given
+        let rep_dir = temp_dir().join("cleaner-enforce-size-tiered");
+        ensure_new_dir(&rep_dir).expect("new");
+        let config = ReplicaConfig {
+            base_dir: rep_dir.join("topic-0"),
+            max_partition_size: 150,
+            segment_max_bytes: 80,
+            remote_store: Some(RemoteStoreConfig::LocalFs {
+                path: rep_dir.join("remote"),
+            }),
+            ..default_option()
+        };
+        ensure_new_dir(&config.base_dir).expect("new");
+        let option = config.clone().shared();
+        let segments =
+            SharedSegments::with_tier(SegmentList::new(), RemoteTier::new(option.clone()));
+        for (start, end) in [(100, 600), (600, 1200)] {
+            segments
+                .add_segment(
+                    create_segment(option.clone(), start, end)
+                        .await
+                        .expect("create"),
+                )
+                .await;
+        }
+        let replica_size = Arc::new(ReplicaSize::default());
+        replica_size.store_prev(151);
+        let cleaner = test_cleaner(config, segments.clone(), replica_size.clone());
+
+        //when
+        cleaner.enforce_size().await;
+
+        //then segments which are not uploaded are kept
+        assert_eq!(segments.read().await.find_first(10), vec![100, 600]);
+
+        //when
+        cleaner.offload().await;
+        replica_size.store_prev(151);
+        cleaner.enforce_size().await;
+
+        //then
+        assert_eq!(segments.read().await.find_first(10), vec![600]);
+        assert_eq!(segments.min_offset(), 100);
+
+        //when evicted segment is read
+        assert!(
+            segments
+                .find_slice(100, None)
+                .await
+                .expect("slice")
+                .is_some()
+        );
+
+        //then it is fetched back
+        assert_eq!(segments.read().await.find_first(10), vec![100, 600]);
+    }
+
     #[fluvio_future::test]
     async fn test_enforce_ttl() {
         //given
@@ -221,6 +427,240 @@ mod tests {
         assert_eq!(read.occupied_memory(), replica_size.get());
     }
 
+    #[fluvio_future::test]
+    async fn test_enforce_compaction_keeps_latest_per_key() {
+        //given
+        let mut config = compaction_option("cleaner-enforce-compaction-latest");
+        config.tombstone_retention_seconds = 3600;
+        let option = config.clone().shared();
+        let segments = SharedSegments::from(SegmentList::new());
+        segments
+            .add_segment(
+                create_keyed_segment(
+                    option.clone(),
+                    0,
+                    vec![
+                        vec![("k1", "a"), ("k2", "b"), ("k1", "c")],
+                        vec![("k3", "d")],
+                    ],
+                )
+                .await,
+            )
+            .await;
+        segments
+            .add_segment(
+                create_keyed_segment(option, 4, vec![vec![("k2", "e"), ("k1", "f")]]).await,
+            )
+            .await;
+        let replica_size = Arc::new(ReplicaSize::default());
+        replica_size.store_prev(segments.read().await.occupied_memory());
+        let cleaner = test_cleaner(config, segments.clone(), replica_size.clone());
+
+        //when
+        cleaner.enforce_compaction().await;
+
+        //then
+        assert_eq!(
+            read_records(&segments).await,
+            vec![
+                (3, "k3".to_string(), "d".to_string()),
+                (4, "k2".to_string(), "e".to_string()),
+                (5, "k1".to_string(), "f".to_string()),
+            ]
+        );
+        let read = segments.read().await;
+        assert_eq!(read.find_first(10), vec![0, 4]);
+        assert_eq!(read.find_segment(0).expect("segment").1.get_end_offset(), 4);
+        assert_eq!(read.occupied_memory(), replica_size.get());
+        drop(read);
+        assert!(segments.find_slice(0, None).await.expect("slice").is_some());
+    }
+
+    #[fluvio_future::test]
+    async fn test_enforce_compaction_removes_expired_tombstones() {
+        //given
+        let config = compaction_option("cleaner-enforce-compaction-tombstone");
+        let option = config.clone().shared();
+        let segments = SharedSegments::from(SegmentList::new());
+        segments
+            .add_segment(
+                create_keyed_segment(
+                    option.clone(),
+                    0,
+                    vec![
+                        vec![("k1", "a"), ("k2", "b")],
+                        vec![("k1", ""), ("k3", "c")],
+                    ],
+                )
+                .await,
+            )
+            .await;
+        segments
+            .add_segment(create_keyed_segment(option.clone(), 4, vec![vec![("k4", "d")]]).await)
+            .await;
+        let replica_size = Arc::new(ReplicaSize::default());
+        let cleaner = test_cleaner(config, segments.clone(), replica_size);
+
+        //when
+        cleaner.enforce_compaction().await;
+
+        //then
+        assert_eq!(
+            read_records(&segments).await,
+            vec![
+                (1, "k2".to_string(), "b".to_string()),
+                (3, "k3".to_string(), "c".to_string()),
+                (4, "k4".to_string(), "d".to_string()),
+            ]
+        );
+        let reopened = ReadSegment::open_unknown(0, option).await.expect("open");
+        assert_eq!(reopened.get_end_offset(), 4);
+    }
+
+    #[fluvio_future::test]
+    async fn test_purge_log_start() {
+        //given
+        let config = compaction_option("cleaner-purge-log-start");
+        let option = config.clone().shared();
+        let segments = SharedSegments::from(SegmentList::new());
+        segments
+            .add_segment(
+                create_keyed_segment(
+                    option.clone(),
+                    0,
+                    vec![
+                        vec![("k1", "a"), ("k2", "b")],
+                        vec![("k3", "c"), ("k4", "d"), ("k5", "e")],
+                    ],
+                )
+                .await,
+            )
+            .await;
+        segments
+            .add_segment(create_keyed_segment(option, 5, vec![vec![("k6", "f")]]).await)
+            .await;
+        let replica_size = Arc::new(ReplicaSize::default());
+        let cleaner = test_cleaner(config, segments.clone(), replica_size.clone());
+
+        //when
+        segments.set_log_start_offset(3);
+        cleaner.purge_log_start().await;
+
+        //then
+        assert_eq!(
+            read_records(&segments).await,
+            vec![
+                (3, "k4".to_string(), "d".to_string()),
+                (4, "k5".to_string(), "e".to_string()),
+                (5, "k6".to_string(), "f".to_string()),
+            ]
+        );
+        let read = segments.read().await;
+        assert_eq!(read.find_first(10), vec![0, 5]);
+        assert_eq!(read.find_segment(3).expect("segment").1.get_end_offset(), 5);
+        assert_eq!(read.occupied_memory(), replica_size.get());
+        drop(read);
+        assert!(segments.find_slice(3, None).await.expect("slice").is_some());
+    }
+
+    fn compaction_option(path: &str) -> ReplicaConfig {
+        let rep_dir = temp_dir().join(path);
+        ensure_new_dir(&rep_dir).expect("new");
+        ReplicaConfig {
+            base_dir: rep_dir,
+            segment_max_bytes: 1000,
+            compact: true,
+            tombstone_retention_seconds: 0,
+            ..default_option()
+        }
+    }
+
+    async fn create_keyed_segment(
+        option: Arc<SharedReplicaConfig>,
+        start: Offset,
+        batches: Vec<Vec<(&str, &str)>>,
+    ) -> ReadSegment {
+        let mut mut_segment = MutableSegment::create(start, option).await.expect("create");
+        for records in batches {
+            let mut batch = Batch::new();
+            for (key, value) in records {
+                batch.add_record(Record::new_key_value(key, value));
+            }
+            mut_segment.append_batch(&mut batch).await.expect("append");
+        }
+        mut_segment.convert_to_segment().await.expect("convert")
+    }
+
+    async fn read_records(segments: &SharedSegments) -> Vec<(Offset, String, String)> {
+        let mut records = vec![];
+        let read = segments.read().await;
+        for segment in read.iter() {
+            let mut stream: FileBatchStream<RawRecords> =
+                FileBatchStream::open(segment.get_msg_log().get_path())
+                    .await
+                    .expect("open");
+            while let Some(batch_pos) = stream.try_next().await.expect("batch") {
+                let batch = batch_pos.inner();
+                for (index, record) in batch
+                    .memory_records()
+                    .expect("records")
+                    .into_iter()
+                    .enumerate()
+                {
+                    records.push((
+                        batch.get_base_offset() + index as Offset,
+                        record
+                            .key()
+                            .expect("key")
+                            .as_utf8_lossy_string()
+                            .to_string(),
+                        record.value().as_utf8_lossy_string().to_string(),
+                    ));
+                }
+            }
+        }
+        records
+    }
+
+    #[fluvio_future::test]
+    async fn test_recover_staged_segments() {
+        let base_dir = temp_dir().join("cleaner-recover-staged-segments");
+        ensure_new_dir(&base_dir).expect("new");
+        let compaction_dir = base_dir.join(COMPACTION_DIR);
+        let purge_dir = base_dir.join("purge");
+        std::fs::create_dir_all(&compaction_dir).expect("dir");
+        std::fs::create_dir_all(&purge_dir).expect("dir");
+
+        // log was moved before crash, indexes were not
+        let log = generate_file_name(&base_dir, 100, MESSAGE_LOG_EXTENSION);
+        let index = generate_file_name(&base_dir, 100, INDEX_EXTENSION);
+        std::fs::write(&log, "compacted").expect("write");
+        std::fs::write(&index, "original").expect("write");
+        std::fs::write(
+            generate_file_name(&compaction_dir, 100, INDEX_EXTENSION),
+            "compacted",
+        )
+        .expect("write");
+        std::fs::write(generate_file_name(&compaction_dir, 100, "commit"), "").expect("write");
+
+        // rewrite which was not committed is discarded
+        let purged = generate_file_name(&base_dir, 600, MESSAGE_LOG_EXTENSION);
+        std::fs::write(&purged, "original").expect("write");
+        std::fs::write(
+            generate_file_name(&purge_dir, 600, MESSAGE_LOG_EXTENSION),
+            "purged",
+        )
+        .expect("write");
+
+        recover_staged_segments(&base_dir).await.expect("recover");
+
+        assert_eq!(std::fs::read_to_string(&log).expect("read"), "compacted");
+        assert_eq!(std::fs::read_to_string(&index).expect("read"), "compacted");
+        assert_eq!(std::fs::read_to_string(&purged).expect("read"), "original");
+        assert!(!compaction_dir.exists());
+        assert!(!purge_dir.exists());
+    }
+
     async fn shared_segments(
         path: &str,
         count: usize,
@@ -287,6 +727,8 @@ mod tests {
             segments,
             replica_size,
             end_event: StickyEvent::shared(),
+            compaction: Default::default(),
+            purged_before: Default::default(),
         }
     }
 }
@@ -0,0 +1,569 @@
+//! Key based compaction of closed segments.
+//!
+//! Only the latest record of each key is kept. Records without key are never removed.
+//! Records with empty value (tombstones) are removed once they are older than tombstone retention.
+//!
+//! Offsets are preserved. If records are removed from the middle of a batch, the batch is split so
+//! that each new batch covers contiguous offsets. Last record of each segment is always kept,
+//! so offset lookup for any offset inside segment still finds a batch.
+//! Active segment is not compacted. Control batches with transaction markers are never removed.
+//!
+//! Closed segment containing log start offset moved by delete records is rewritten the same way,
+//! so records before log start offset don't stay on disk until whole segment is removed.
+//!
+//! Rewritten segment is staged in its own directory and synced. Commit marker is written before
+//! its files are moved over the original segment, so segment replaced partially before crash is
+//! completed by `recover_staged_segments` when replica is loaded.
+
+use std::collections::HashMap;
+use std::fs::File as StdFile;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::{debug, info, instrument};
+use anyhow::{Result, anyhow};
+
+use fluvio_future::fs::{create_dir_all, metadata, remove_dir_all, remove_file, rename, File};
+use fluvio_protocol::record::{Batch, MemoryRecords, Offset, RawRecords, Record};
+
+use crate::batch::FileBatchStream;
+use crate::config::SharedReplicaConfig;
+use crate::index::{EXTENSION as INDEX_EXTENSION, TIME_INDEX_EXTENSION};
+use crate::records::{FileRecords, MESSAGE_LOG_EXTENSION};
+use crate::segment::{MutableSegment, ReadSegment};
+use crate::segments::SharedSegments;
+use crate::util::generate_file_name;
+
+/// directory under replica where compacted segments are written before replacing originals
+pub(crate) const COMPACTION_DIR: &str = "compaction";
+
+/// directory under replica where segment containing log start offset is rewritten
+const PURGE_DIR: &str = "purge";
+
+/// extension of marker of staged segment which replaces the original
+const COMMIT_EXTENSION: &str = "commit";
+
+const SEGMENT_EXTENSIONS: [&str; 3] =
+    [MESSAGE_LOG_EXTENSION, INDEX_EXTENSION, TIME_INDEX_EXTENSION];
+
+#[derive(Debug, Default)]
+pub(crate) struct CompactionResult {
+    pub compacted_segments: usize,
+    pub removed_records: usize,
+    /// earliest time when kept tombstone can be removed
+    pub next_tombstone_expiry: Option<SystemTime>,
+}
+
+#[derive(Debug)]
+struct ClosedSegment {
+    base_offset: Offset,
+    end_offset: Offset,
+    modified: SystemTime,
+}
+
+impl ClosedSegment {
+    /// None if segment was removed after it was listed
+    async fn new(base_offset: Offset, end_offset: Offset, path: &Path) -> Result<Option<Self>> {
+        let modified = match metadata(path).await {
+            Ok(metadata) => metadata.modified()?,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                debug!(base_offset, "segment removed, skipping");
+                return Ok(None);
+            }
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Some(Self {
+            base_offset,
+            end_offset,
+            modified,
+        }))
+    }
+}
+
+/// decides which records survive compaction
+struct RetentionRules {
+    latest: HashMap<Vec<u8>, Offset>,
+    tombstone_retention: Duration,
+    now: SystemTime,
+}
+
+impl RetentionRules {
+    fn keep(
+        &self,
+        record: &Record,
+        offset: Offset,
+        timestamp: SystemTime,
+        next_expiry: &mut Option<SystemTime>,
+    ) -> bool {
+        let Some(key) = record.key() else {
+            return true;
+        };
+
+        let key: &[u8] = key.as_ref();
+        if self.latest.get(key) != Some(&offset) {
+            return false;
+        }
+
+        if !record.value().is_empty() {
+            return true;
+        }
+
+        let expiry = timestamp + self.tombstone_retention;
+        if expiry <= self.now {
+            false
+        } else {
+            *next_expiry = Some(next_expiry.map_or(expiry, |next| next.min(expiry)));
+            true
+        }
+    }
+}
+
+/// compact all closed segments
+#[instrument(skip(segments, option))]
+pub(crate) async fn compact_segments(
+    segments: &SharedSegments,
+    option: &Arc<SharedReplicaConfig>,
+) -> Result<CompactionResult> {
+    let listed: Vec<_> = segments
+        .read()
+        .await
+        .iter()
+        .map(|segment| {
+            (
+                segment.get_base_offset(),
+                segment.get_end_offset(),
+                segment.get_msg_log().get_path().to_owned(),
+            )
+        })
+        .collect();
+    let mut closed_segments = Vec::with_capacity(listed.len());
+    for (base_offset, end_offset, path) in listed {
+        closed_segments.extend(ClosedSegment::new(base_offset, end_offset, &path).await?);
+    }
+
+    let mut result = CompactionResult::default();
+    if closed_segments.is_empty() {
+        return Ok(result);
+    }
+
+    let rules = RetentionRules {
+        latest: latest_key_offsets(&closed_segments, option).await?,
+        tombstone_retention: Duration::from_secs(option.tombstone_retention_seconds.get() as u64),
+        now: SystemTime::now(),
+    };
+
+    let compaction_dir = option.base_dir.join(COMPACTION_DIR);
+    if compaction_dir.exists() {
+        remove_dir_all(&compaction_dir).await?;
+    }
+    create_dir_all(&compaction_dir).await?;
+    let compaction_option = Arc::new(option.with_base_dir(compaction_dir.clone()));
+    // compacted segment is never bigger than original
+    compaction_option.segment_max_bytes.set(u32::MAX);
+
+    for segment in closed_segments {
+        let Some(removed) =
+            compact_segment(&segment, &rules, option, &compaction_option, &mut result).await?
+        else {
+            continue;
+        };
+        sync_staged(&compaction_dir, segment.base_offset).await?;
+
+        let mut list = segments.write().await;
+        if !list.iter().any(|s| {
+            s.get_base_offset() == segment.base_offset && s.get_end_offset() == segment.end_offset
+        }) {
+            debug!(
+                segment.base_offset,
+                "segment changed during compaction, skipping"
+            );
+            continue;
+        }
+
+        replace_with_staged(&compaction_dir, &option.base_dir, segment.base_offset).await?;
+        let compacted =
+            ReadSegment::open_for_read(segment.base_offset, segment.end_offset, option.clone())
+                .await?;
+        let remote = list.replace_segment(compacted);
+        drop(list);
+        segments.delete_remote(remote).await;
+
+        info!(
+            base_offset = segment.base_offset,
+            removed, "segment compacted"
+        );
+        result.compacted_segments += 1;
+        result.removed_records += removed;
+    }
+
+    remove_dir_all(&compaction_dir).await?;
+
+    Ok(result)
+}
+
+/// scan closed segments and find offset of the latest record for each key
+async fn latest_key_offsets(
+    closed_segments: &[ClosedSegment],
+    option: &SharedReplicaConfig,
+) -> Result<HashMap<Vec<u8>, Offset>> {
+    let mut latest = HashMap::new();
+    for segment in closed_segments {
+        let path = generate_file_name(&option.base_dir, segment.base_offset, MESSAGE_LOG_EXTENSION);
+        let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(&path).await?;
+        while let Some(batch_pos) = stream.try_next().await? {
+            let batch = batch_pos.inner();
+            if batch.header.is_control() {
+                continue;
+            }
+            for (index, record) in batch.memory_records()?.iter().enumerate() {
+                if let Some(key) = record.key() {
+                    latest.insert(key.to_vec(), batch.get_base_offset() + index as Offset);
+                }
+            }
+        }
+    }
+    Ok(latest)
+}
+
+/// Rewrite segment into compaction directory.
+/// Return number of removed records or None if nothing can be removed from the segment.
+async fn compact_segment(
+    segment: &ClosedSegment,
+    rules: &RetentionRules,
+    option: &SharedReplicaConfig,
+    compaction_option: &Arc<SharedReplicaConfig>,
+    result: &mut CompactionResult,
+) -> Result<Option<usize>> {
+    let path = generate_file_name(&option.base_dir, segment.base_offset, MESSAGE_LOG_EXTENSION);
+
+    // first pass, find records to keep for each batch. None if batch is kept as it is
+    let mut masks: Vec<Option<Vec<bool>>> = vec![];
+    let mut removed = 0;
+    let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(&path).await?;
+    while let Some(batch_pos) = stream.try_next().await? {
+        let batch = batch_pos.inner();
+        // transaction markers are always kept
+        if batch.header.is_control() {
+            masks.push(None);
+            continue;
+        }
+        let records = batch.memory_records()?;
+        let batch_time = batch_timestamp(&batch);
+        let mask: Vec<bool> = records
+            .iter()
+            .enumerate()
+            .map(|(index, record)| {
+                let offset = batch.get_base_offset() + index as Offset;
+                let timestamp = match batch_time {
+                    Some(batch_time) => {
+                        batch_time + Duration::from_millis(record.timestamp_delta().max(0) as u64)
+                    }
+                    None => segment.modified,
+                };
+                rules.keep(record, offset, timestamp, &mut result.next_tombstone_expiry)
+            })
+            .collect();
+        let batch_removed = mask.iter().filter(|keep| !**keep).count();
+        if batch_removed == 0 {
+            masks.push(None);
+        } else {
+            removed += batch_removed;
+            masks.push(Some(mask));
+        }
+    }
+
+    // always keep last record of segment
+    if let Some(last) = masks.last_mut() {
+        let all_kept = match last {
+            Some(mask) => {
+                if let Some(keep) = mask.last_mut().filter(|keep| !**keep) {
+                    *keep = true;
+                    removed -= 1;
+                }
+                mask.iter().all(|keep| *keep)
+            }
+            None => false,
+        };
+        if all_kept {
+            *last = None;
+        }
+    }
+
+    if removed == 0 {
+        return Ok(None);
+    }
+
+    debug!(
+        base_offset = segment.base_offset,
+        removed, "rewriting segment"
+    );
+
+    // second pass, write kept records
+    let mut compacted =
+        MutableSegment::create(segment.base_offset, compaction_option.clone()).await?;
+    let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(&path).await?;
+    let mut masks = masks.into_iter();
+    while let Some(batch_pos) = stream.try_next().await? {
+        let batch = batch_pos.inner();
+        match masks.next().flatten() {
+            None => append(&mut compacted, &batch).await?,
+            Some(mask) => {
+                for split in split_batch(&batch, batch.memory_records()?, &mask) {
+                    let raw: Batch<RawRecords> = split.try_into()?;
+                    append(&mut compacted, &raw).await?;
+                }
+            }
+        }
+    }
+    compacted.close().await?;
+    drop(compacted);
+
+    // keep modification time so time based retention is not affected by compaction
+    let compacted_path = generate_file_name(
+        &compaction_option.base_dir,
+        segment.base_offset,
+        MESSAGE_LOG_EXTENSION,
+    );
+    set_modified(&compacted_path, segment.modified)?;
+
+    Ok(Some(removed))
+}
+
+/// Rewrite closed segment containing log start offset without records before it.
+/// Batch spanning log start offset is split. Segment keeps its base offset and end offset.
+/// Return number of removed records or None if log start offset is not in local closed segment.
+#[instrument(skip(segments, option))]
+pub(crate) async fn purge_before(
+    segments: &SharedSegments,
+    option: &Arc<SharedReplicaConfig>,
+    log_start_offset: Offset,
+) -> Result<Option<usize>> {
+    let Some((base_offset, end_offset, path)) = segments
+        .read()
+        .await
+        .find_segment(log_start_offset)
+        .map(|(_, segment)| {
+            (
+                segment.get_base_offset(),
+                segment.get_end_offset(),
+                segment.get_msg_log().get_path().to_owned(),
+            )
+        })
+    else {
+        return Ok(None);
+    };
+    let Some(segment) = ClosedSegment::new(base_offset, end_offset, &path).await? else {
+        return Ok(None);
+    };
+    if segment.base_offset == log_start_offset {
+        return Ok(Some(0));
+    }
+
+    let purge_dir = option.base_dir.join(PURGE_DIR);
+    if purge_dir.exists() {
+        remove_dir_all(&purge_dir).await?;
+    }
+    create_dir_all(&purge_dir).await?;
+    let purge_option = Arc::new(option.with_base_dir(purge_dir.clone()));
+    purge_option.segment_max_bytes.set(u32::MAX);
+
+    let path = generate_file_name(&option.base_dir, segment.base_offset, MESSAGE_LOG_EXTENSION);
+    let mut purged = MutableSegment::create(segment.base_offset, purge_option.clone()).await?;
+    let mut removed = 0;
+    let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(&path).await?;
+    while let Some(batch_pos) = stream.try_next().await? {
+        let batch = batch_pos.inner();
+        if batch.get_base_offset() >= log_start_offset {
+            append(&mut purged, &batch).await?;
+        } else if batch.get_last_offset() < log_start_offset {
+            removed += (batch.get_last_offset() - batch.get_base_offset() + 1) as usize;
+        } else {
+            let records = batch.memory_records()?;
+            let mask: Vec<bool> = (0..records.len())
+                .map(|index| batch.get_base_offset() + index as Offset >= log_start_offset)
+                .collect();
+            removed += mask.iter().filter(|keep| !**keep).count();
+            for split in split_batch(&batch, records, &mask) {
+                let raw: Batch<RawRecords> = split.try_into()?;
+                append(&mut purged, &raw).await?;
+            }
+        }
+    }
+    purged.close().await?;
+    drop(purged);
+
+    if removed > 0 {
+        set_modified(
+            &generate_file_name(&purge_dir, segment.base_offset, MESSAGE_LOG_EXTENSION),
+            segment.modified,
+        )?;
+        sync_staged(&purge_dir, segment.base_offset).await?;
+        let mut list = segments.write().await;
+        if list.iter().any(|s| {
+            s.get_base_offset() == segment.base_offset && s.get_end_offset() == segment.end_offset
+        }) {
+            replace_with_staged(&purge_dir, &option.base_dir, segment.base_offset).await?;
+            let rewritten =
+                ReadSegment::open_for_read(segment.base_offset, segment.end_offset, option.clone())
+                    .await?;
+            let remote = list.replace_segment(rewritten);
+            drop(list);
+            segments.delete_remote(remote).await;
+            info!(
+                base_offset = segment.base_offset,
+                log_start_offset, removed, "records before log start offset purged"
+            );
+        } else {
+            debug!(
+                segment.base_offset,
+                "segment changed during purge, skipping"
+            );
+            removed = 0;
+        }
+    }
+
+    remove_dir_all(&purge_dir).await?;
+    Ok(Some(removed))
+}
+
+/// Complete replacing of segments committed before crash and discard unfinished rewrites.
+/// Must be called before segments of replica are loaded.
+pub(crate) async fn recover_staged_segments(base_dir: &Path) -> Result<()> {
+    for staging_dir in [base_dir.join(COMPACTION_DIR), base_dir.join(PURGE_DIR)] {
+        if !staging_dir.exists() {
+            continue;
+        }
+        let committed: Vec<PathBuf> = staging_dir
+            .read_dir()?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<_, _>>()?;
+        for path in committed {
+            if path.extension().and_then(|ext| ext.to_str()) != Some(COMMIT_EXTENSION) {
+                continue;
+            }
+            let Some(base_offset) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<Offset>().ok())
+            else {
+                continue;
+            };
+            info!(base_offset, "completing replace of segment");
+            move_staged(&staging_dir, base_dir, base_offset).await?;
+        }
+        remove_dir_all(&staging_dir).await?;
+    }
+    Ok(())
+}
+
+/// flush staged segment to disk, so it can be committed
+async fn sync_staged(staging_dir: &Path, base_offset: Offset) -> Result<()> {
+    for extension in SEGMENT_EXTENSIONS {
+        File::open(generate_file_name(staging_dir, base_offset, extension))
+            .await?
+            .sync_all()
+            .await?;
+    }
+    sync_dir(staging_dir).await
+}
+
+/// commit synced staged segment, then move its files over the original segment
+async fn replace_with_staged(
+    staging_dir: &Path,
+    base_dir: &Path,
+    base_offset: Offset,
+) -> Result<()> {
+    File::create(generate_file_name(
+        staging_dir,
+        base_offset,
+        COMMIT_EXTENSION,
+    ))
+    .await?
+    .sync_all()
+    .await?;
+    sync_dir(staging_dir).await?;
+    move_staged(staging_dir, base_dir, base_offset).await
+}
+
+/// move files of committed segment which were not moved yet, then remove commit marker
+async fn move_staged(staging_dir: &Path, base_dir: &Path, base_offset: Offset) -> Result<()> {
+    for extension in SEGMENT_EXTENSIONS {
+        let staged = generate_file_name(staging_dir, base_offset, extension);
+        if metadata(&staged).await.is_ok() {
+            rename(staged, generate_file_name(base_dir, base_offset, extension)).await?;
+        }
+    }
+    sync_dir(base_dir).await?;
+    remove_file(generate_file_name(
+        staging_dir,
+        base_offset,
+        COMMIT_EXTENSION,
+    ))
+    .await?;
+    Ok(())
+}
+
+async fn sync_dir(dir: &Path) -> Result<()> {
+    File::open(dir).await?.sync_all().await?;
+    Ok(())
+}
+
+async fn append(segment: &mut MutableSegment, batch: &Batch<RawRecords>) -> Result<()> {
+    if segment.append_batch_at_base_offset(batch).await? {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "no room in compacted segment for batch: {}",
+            batch.get_base_offset()
+        ))
+    }
+}
+
+/// split batch into batches of kept records with contiguous offsets
+fn split_batch(batch: &Batch<RawRecords>, records: MemoryRecords, mask: &[bool]) -> Vec<Batch> {
+    let last_index = records.len().saturating_sub(1);
+    let mut batches = vec![];
+    let mut current: Option<Batch> = None;
+    for (index, (record, keep)) in records.into_iter().zip(mask).enumerate() {
+        if !keep {
+            if let Some(split) = current.take() {
+                batches.push(split);
+            }
+            continue;
+        }
+        let split = current.get_or_insert_with(|| {
+            let mut split = Batch::default();
+            split.header = batch.header.clone();
+            split.base_offset = batch.get_base_offset() + index as Offset;
+            split
+        });
+        split.add_record(record);
+        if index == last_index {
+            // keep offset range of original batch
+            split.header.last_offset_delta = (batch.get_last_offset() - split.base_offset) as i32;
+        }
+    }
+    if let Some(split) = current {
+        batches.push(split);
+    }
+    batches
+}
+
+fn batch_timestamp<R>(batch: &Batch<R>) -> Option<SystemTime> {
+    let timestamp = batch.get_base_timestamp();
+    if timestamp <= 0 {
+        None
+    } else {
+        Some(UNIX_EPOCH + Duration::from_millis(timestamp as u64))
+    }
+}
+
+fn set_modified(path: &Path, modified: SystemTime) -> Result<()> {
+    StdFile::options()
+        .write(true)
+        .open(path)?
+        .set_modified(modified)?;
+    Ok(())
+}
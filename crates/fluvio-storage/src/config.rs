@@ -12,13 +12,14 @@ use fluvio_controlplane_metadata::topic::CleanupPolicy;
 use fluvio_types::defaults::{
     SPU_LOG_INDEX_MAX_BYTES, SPU_LOG_BASE_DIR, STORAGE_FLUSH_WRITE_COUNT, STORAGE_FLUSH_IDLE_MSEC,
     STORAGE_MAX_BATCH_SIZE, STORAGE_MAX_REQUEST_SIZE, STORAGE_RETENTION_SECONDS,
-    SPU_PARTITION_MAX_BYTES,
+    SPU_PARTITION_MAX_BYTES, STORAGE_TOMBSTONE_RETENTION_SECONDS, STORAGE_SCRUB_MAX_BYTES_PER_SEC,
 };
 use fluvio_types::defaults::SPU_LOG_INDEX_MAX_INTERVAL_BYTES;
 use fluvio_types::defaults::SPU_LOG_SEGMENT_MAX_BYTES;
 use fluvio_protocol::record::{Size, Size64};
 
 use crate::ReplicaStorageConfig;
+use crate::tiered::{LocalFsStore, RemoteStore};
 
 // Replica specific config
 #[derive(Builder, Clone, Debug, Eq, PartialEq, Deserialize)]
@@ -57,8 +58,71 @@ pub struct ReplicaConfig {
     #[builder(default = "default_max_partition_size()")]
     #[serde(default = "default_max_partition_size")]
     pub max_partition_size: Size64,
+    #[builder(default)]
+    #[serde(default)]
+    pub compact: bool, // if true, closed segments are compacted by key
+    #[builder(default = "default_tombstone_retention_seconds()")]
+    #[serde(default = "default_tombstone_retention_seconds")]
+    pub tombstone_retention_seconds: Size,
+    /// if set, closed segments are offloaded to remote store and
+    /// max partition size limits only local copies
+    #[builder(default)]
+    #[serde(default)]
+    pub remote_store: Option<RemoteStoreConfig>,
+    /// if true, crc of batches is checked when segments are loaded
+    #[builder(default)]
+    #[serde(default)]
+    pub verify_crc_on_load: bool,
+    /// if true, crc of batches is checked before they are served to consumers and followers
+    #[builder(default)]
+    #[serde(default)]
+    pub verify_crc_on_read: bool,
+    /// seconds between background scrubs of closed segments, scrubbing is disabled if 0
+    #[builder(default)]
+    #[serde(default)]
+    pub scrub_interval_seconds: Size,
+    /// max bytes read per second by scrubber, unlimited if 0
+    #[builder(default = "default_scrub_max_bytes_per_sec()")]
+    #[serde(default = "default_scrub_max_bytes_per_sec")]
+    pub scrub_max_bytes_per_sec: Size,
 }
 
+/// Remote store for tiered storage
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteStoreConfig {
+    /// objects are files in local directory, e.g. mounted network file system
+    LocalFs { path: PathBuf },
+    /// store implemented outside of this crate, e.g. object store client
+    #[serde(skip)]
+    Custom(CustomRemoteStore),
+}
+
+impl RemoteStoreConfig {
+    pub fn custom(store: Arc<dyn RemoteStore>) -> Self {
+        Self::Custom(CustomRemoteStore(store))
+    }
+
+    pub fn store(&self) -> Arc<dyn RemoteStore> {
+        match self {
+            Self::LocalFs { path } => Arc::new(LocalFsStore::new(path.clone())),
+            Self::Custom(custom) => custom.0.clone(),
+        }
+    }
+}
+
+/// Remote store provided by embedder, configs are equal if they share same store
+#[derive(Clone, Debug)]
+pub struct CustomRemoteStore(pub Arc<dyn RemoteStore>);
+
+impl PartialEq for CustomRemoteStore {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CustomRemoteStore {}
+
 impl fmt::Display for ReplicaConfig {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "storage config at: {:#?}", self.base_dir)
@@ -67,11 +131,16 @@ impl fmt::Display for ReplicaConfig {
 
 impl ReplicaStorageConfig for ReplicaConfig {
     fn update_from_replica(&mut self, replica: &Replica) {
+        self.compact = matches!(replica.cleanup_policy, Some(CleanupPolicy::Compact(_)));
         if let Some(policy) = &replica.cleanup_policy {
             match policy {
                 CleanupPolicy::Segment(segment) => {
                     self.retention_seconds = segment.retention_secs();
                 }
+                CleanupPolicy::Compact(compact) => {
+                    self.retention_seconds = compact.retention_secs();
+                    self.tombstone_retention_seconds = compact.tombstone_retention_secs;
+                }
             }
         }
 
@@ -136,6 +205,14 @@ const fn default_max_partition_size() -> Size64 {
     SPU_PARTITION_MAX_BYTES
 }
 
+const fn default_tombstone_retention_seconds() -> Size {
+    STORAGE_TOMBSTONE_RETENTION_SECONDS
+}
+
+const fn default_scrub_max_bytes_per_sec() -> Size {
+    STORAGE_SCRUB_MAX_BYTES_PER_SEC
+}
+
 impl ReplicaConfig {
     // Used to get a [`ConfigOptionBuilder`].
     pub fn builder() -> ReplicaConfigBuilder {
@@ -161,6 +238,13 @@ impl Default for ReplicaConfig {
             retention_seconds: default_retention_seconds(),
             max_partition_size: default_max_partition_size(),
             update_hw: true,
+            compact: false,
+            tombstone_retention_seconds: default_tombstone_retention_seconds(),
+            remote_store: None,
+            verify_crc_on_load: false,
+            verify_crc_on_read: false,
+            scrub_interval_seconds: 0,
+            scrub_max_bytes_per_sec: default_scrub_max_bytes_per_sec(),
         }
     }
 }
@@ -238,6 +322,13 @@ pub struct SharedReplicaConfig {
     pub update_hw: bool, // if true, enable hw update
     pub retention_seconds: SharedConfigU32Value,
     pub max_partition_size: SharedConfigU64Value,
+    pub compact: bool,
+    pub tombstone_retention_seconds: SharedConfigU32Value,
+    pub remote_store: Option<RemoteStoreConfig>,
+    pub verify_crc_on_load: bool,
+    pub verify_crc_on_read: bool,
+    pub scrub_interval_seconds: SharedConfigU32Value,
+    pub scrub_max_bytes_per_sec: SharedConfigU32Value,
 }
 
 impl From<ReplicaConfig> for SharedReplicaConfig {
@@ -254,6 +345,45 @@ impl From<ReplicaConfig> for SharedReplicaConfig {
             update_hw: config.update_hw,
             retention_seconds: SharedConfigU32Value::new(config.retention_seconds),
             max_partition_size: SharedConfigU64Value::new(config.max_partition_size),
+            compact: config.compact,
+            tombstone_retention_seconds: SharedConfigU32Value::new(
+                config.tombstone_retention_seconds,
+            ),
+            remote_store: config.remote_store,
+            verify_crc_on_load: config.verify_crc_on_load,
+            verify_crc_on_read: config.verify_crc_on_read,
+            scrub_interval_seconds: SharedConfigU32Value::new(config.scrub_interval_seconds),
+            scrub_max_bytes_per_sec: SharedConfigU32Value::new(config.scrub_max_bytes_per_sec),
+        }
+    }
+}
+
+impl SharedReplicaConfig {
+    /// snapshot of current values with different base directory
+    pub(crate) fn with_base_dir(&self, base_dir: PathBuf) -> Self {
+        SharedReplicaConfig {
+            base_dir,
+            index_max_bytes: SharedConfigU32Value::new(self.index_max_bytes.get()),
+            index_max_interval_bytes: SharedConfigU32Value::new(
+                self.index_max_interval_bytes.get(),
+            ),
+            segment_max_bytes: SharedConfigU32Value::new(self.segment_max_bytes.get()),
+            flush_write_count: SharedConfigU32Value::new(self.flush_write_count.get()),
+            flush_idle_msec: SharedConfigU32Value::new(self.flush_idle_msec.get()),
+            max_batch_size: SharedConfigU32Value::new(self.max_batch_size.get()),
+            max_request_size: SharedConfigU32Value::new(self.max_request_size.get()),
+            update_hw: self.update_hw,
+            retention_seconds: SharedConfigU32Value::new(self.retention_seconds.get()),
+            max_partition_size: SharedConfigU64Value::new(self.max_partition_size.get()),
+            compact: self.compact,
+            tombstone_retention_seconds: SharedConfigU32Value::new(
+                self.tombstone_retention_seconds.get(),
+            ),
+            remote_store: self.remote_store.clone(),
+            verify_crc_on_load: self.verify_crc_on_load,
+            verify_crc_on_read: self.verify_crc_on_read,
+            scrub_interval_seconds: SharedConfigU32Value::new(self.scrub_interval_seconds.get()),
+            scrub_max_bytes_per_sec: SharedConfigU32Value::new(self.scrub_max_bytes_per_sec.get()),
         }
     }
 }
@@ -281,4 +411,29 @@ mod tests {
 
         assert_eq!(ReplicaConfig::default(), config);
     }
+
+    #[test]
+    fn test_compact_follows_replica_policy() {
+        use fluvio_controlplane_metadata::topic::{CompactPolicy, SegmentBasedPolicy};
+
+        let mut config = ReplicaConfig::default();
+        let mut replica = Replica {
+            cleanup_policy: Some(CleanupPolicy::Compact(CompactPolicy::default())),
+            ..Default::default()
+        };
+
+        config.update_from_replica(&replica);
+        assert!(config.compact);
+
+        replica.cleanup_policy = Some(CleanupPolicy::Segment(SegmentBasedPolicy {
+            time_in_seconds: 60,
+        }));
+        config.update_from_replica(&replica);
+        assert!(!config.compact);
+        assert_eq!(config.retention_seconds, 60);
+
+        replica.cleanup_policy = None;
+        config.update_from_replica(&replica);
+        assert!(!config.compact);
+    }
 }
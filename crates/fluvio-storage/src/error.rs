@@ -3,6 +3,7 @@ use std::io::Error as IoError;
 use fluvio_future::fs::BoundedFileSinkError;
 use fluvio_future::zero_copy::SendFileError;
 
+use crate::batch::CorruptBatch;
 use crate::util::OffsetError;
 use crate::validator::LogValidationError;
 
@@ -30,6 +31,8 @@ pub enum StorageError {
     EmptyBatch,
     #[error("Storage is short-circuited")]
     ShortCircuited,
+    #[error("{0}")]
+    CorruptBatch(#[from] CorruptBatch),
 }
 
 impl From<BoundedFileSinkError> for StorageError {
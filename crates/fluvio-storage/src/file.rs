@@ -13,6 +13,7 @@ use nix::Result as NixResult;
 use tracing::{debug, instrument, trace};
 
 use fluvio_future::fs::File;
+use fluvio_future::file_slice::AsyncFileSlice;
 
 use crate::batch::StorageBytesIterator;
 
@@ -101,6 +102,18 @@ impl ReadOutput {
     }
 }
 
+/// read content of file slice
+pub(crate) async fn read_file_slice(slice: &AsyncFileSlice) -> Result<Bytes, IoError> {
+    let (fd, position, len) = (slice.fd(), slice.position(), slice.len());
+    match unblock(move || pread(fd, position as i64, len as usize))
+        .await
+        .map_err(|e| IoError::other(format!("pread error: {e:#?}")))?
+    {
+        ReadOutput::Some { buffer, .. } => Ok(buffer),
+        ReadOutput::Empty => Ok(Bytes::new()),
+    }
+}
+
 /// read number of bytes into shared buffer at offset
 #[instrument(level = "trace", fields(fd, offset, len))]
 fn pread(fd: RawFd, offset: i64, len: usize) -> NixResult<ReadOutput> {
@@ -29,8 +29,17 @@ pub const INDEX_ENTRY_SIZE: u64 = size_of::<Entry>() as u64;
 
 pub const EXTENSION: &str = "index";
 
+/// size of time index entry
+pub const TIME_INDEX_ENTRY_SIZE: u64 = size_of::<TimeEntry>() as u64;
+
+pub const TIME_INDEX_EXTENSION: &str = "timeindex";
+
 pub type Entry = (Size, Size);
 
+/// (max timestamp, relative offset)
+/// Timestamp is max record timestamp of all batches up to and including batch at relative offset
+pub type TimeEntry = (i64, Size64);
+
 pub trait Index {
     /// Find Offset position in the index
     /// This will find index entry with least and min offset.
@@ -193,6 +202,121 @@ impl Deref for LogIndex {
     }
 }
 
+pub trait TimeIndex {
+    /// Find last entry which has timestamp less than given timestamp.
+    /// All records up to and including batch at entry's relative offset are older than timestamp.
+    /// For example, if we have time index entries:
+    /// 0: [1000,0]
+    /// 1: [2000,10]
+    /// 2: [3000,25]
+    ///
+    /// find_timestamp(2500) will return Some((2000,10))
+    /// find_timestamp(1000) will return None
+    fn find_timestamp(&self, timestamp: i64) -> Option<TimeEntry>;
+}
+
+/// Segment time index
+///
+/// Maps timestamp into relative offset. Timestamps in the index never decrease.
+///
+/// It is backed by memory mapped file.
+/// Segments written before time index existed don't have the file, in that case index is empty.
+pub struct LogTimeIndex {
+    mmap: Option<MemoryMappedFile>,
+    path: PathBuf,
+    ptr: *mut c_void,
+    len: Size,
+}
+
+unsafe impl Send for LogTimeIndex {}
+
+unsafe impl Sync for LogTimeIndex {}
+
+impl LogTimeIndex {
+    pub async fn open_from_offset(
+        base_offset: Offset,
+        option: Arc<SharedReplicaConfig>,
+    ) -> Result<Self, IoError> {
+        let path = generate_file_name(&option.base_dir, base_offset, TIME_INDEX_EXTENSION);
+
+        if !path.exists() {
+            debug!(?path, "no time index");
+            return Ok(LogTimeIndex {
+                mmap: None,
+                path,
+                ptr: std::ptr::null_mut(),
+                len: 0,
+            });
+        }
+
+        debug!(?path, "opening time index");
+        let (m_file, file) = MemoryMappedFile::open(&path, TIME_INDEX_ENTRY_SIZE).await?;
+
+        let len = (file.metadata().await?).len();
+        if len > u32::MAX as u64 {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "time index file should not exceed u32",
+            ));
+        }
+        let ptr = {
+            let b_slices: &[u8] = &m_file.inner();
+            b_slices.as_ptr() as *mut libc::c_void
+        };
+
+        Ok(LogTimeIndex {
+            mmap: Some(m_file),
+            path,
+            ptr,
+            len: len as Size,
+        })
+    }
+
+    /// return file path to be removed, None if there is no file
+    pub fn clean(self) -> Option<PathBuf> {
+        self.mmap.map(|_| self.path)
+    }
+}
+
+impl TimeIndex for LogTimeIndex {
+    fn find_timestamp(&self, timestamp: i64) -> Option<TimeEntry> {
+        lookup_time_entry(self, timestamp).map(|idx| time_entry_to_be(self[idx]))
+    }
+}
+
+impl Deref for LogTimeIndex {
+    type Target = [TimeEntry];
+
+    #[inline]
+    fn deref(&self) -> &[TimeEntry] {
+        if self.ptr.is_null() {
+            return &[];
+        }
+        unsafe {
+            slice::from_raw_parts(
+                self.ptr as *const TimeEntry,
+                (self.len as u64 / TIME_INDEX_ENTRY_SIZE) as usize,
+            )
+        }
+    }
+}
+
+/// convert time entry to big endian format, same conversion is used for read and write
+#[inline(always)]
+pub(crate) fn time_entry_to_be(entry: TimeEntry) -> TimeEntry {
+    (entry.0.to_be(), entry.1.to_be())
+}
+
+/// find last entry with timestamp less than given timestamp.
+/// Empty slots have zero timestamp and are never returned.
+pub(crate) fn lookup_time_entry(entries: &[TimeEntry], timestamp: i64) -> Option<usize> {
+    let idx = entries.partition_point(|entry| {
+        let entry_timestamp = entry.0.to_be();
+        entry_timestamp > 0 && entry_timestamp < timestamp
+    });
+    idx.checked_sub(1)
+}
+
 /// perform binary search given
 pub(crate) fn lookup_entry(offsets: &[Entry], offset: Size) -> Option<usize> {
     let first_entry = offsets[0].to_be();
@@ -215,6 +339,8 @@ pub(crate) fn lookup_entry(offsets: &[Entry], offset: Size) -> Option<usize> {
 mod tests {
 
     use super::lookup_entry;
+    use super::lookup_time_entry;
+    use super::time_entry_to_be;
     use super::OffsetPosition;
 
     #[test]
@@ -238,4 +364,23 @@ mod tests {
         assert_eq!(lookup_entry(&indexes, 14), Some(3)); // (13,600)
         assert_eq!(lookup_entry(&indexes, 50), Some(5)); // (21,12000) max
     }
+
+    #[test]
+    fn test_time_index_search() {
+        let entries = [
+            time_entry_to_be((1000, 0)),
+            time_entry_to_be((2000, 10)),
+            time_entry_to_be((2000, 15)),
+            time_entry_to_be((3000, 25)),
+            (0, 0), // empty slot
+        ];
+
+        assert!(lookup_time_entry(&entries, 500).is_none());
+        assert!(lookup_time_entry(&entries, 1000).is_none());
+        assert_eq!(lookup_time_entry(&entries, 1001), Some(0));
+        assert_eq!(lookup_time_entry(&entries, 2000), Some(0));
+        assert_eq!(lookup_time_entry(&entries, 2500), Some(2)); // (2000,15)
+        assert_eq!(lookup_time_entry(&entries, 5000), Some(3)); // (3000,25) max
+        assert!(lookup_time_entry(&[], 5000).is_none());
+    }
 }
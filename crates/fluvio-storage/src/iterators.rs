@@ -132,6 +132,18 @@ impl Iterator for FileBatchIterator {
             )));
         }
 
+        // schema id is stored in front of the records
+        if batch.header.has_schema() {
+            let mut cursor = Cursor::new(&raw_records);
+            if let Err(err) = batch.schema_id.decode(&mut cursor, 0) {
+                return Some(Err(IoError::other(format!(
+                    "decoding batch schema id error {err}"
+                ))));
+            }
+            let schema_len = cursor.position() as usize;
+            raw_records.drain(..schema_len);
+        }
+
         let compression = match batch.get_compression() {
             Ok(compression) => compression,
             Err(err) => {
@@ -224,7 +236,7 @@ mod test {
     use std::os::unix::io::AsRawFd;
 
     use fluvio_future::task::run_block_on;
-    use fluvio_protocol::record::RecordSet;
+    use fluvio_protocol::record::{RecordSet, SchemaId};
     use crate::{FileReplica, ReplicaStorage};
     use crate::config::{StorageConfigBuilder, ReplicaConfigBuilder};
 
@@ -304,6 +316,60 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_file_record_iterator_schema_id() -> anyhow::Result<()> {
+        //given
+        let base_dir = temp_dir().join("test_file_record_iterator_schema_id");
+        let mut replica = run_block_on(FileReplica::create_or_load_inner(
+            format!(
+                "test_file_record_iterator_schema_id_{}",
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)?
+                    .as_millis()
+            ),
+            Default::default(),
+            Default::default(),
+            ReplicaConfigBuilder::default().base_dir(base_dir).build(),
+            Arc::new(StorageConfigBuilder::default().build()?),
+        ))?;
+
+        let mut batch = Batch::default();
+        batch.set_schema_id(SchemaId::from(7));
+        batch.add_record(Record::new("1"));
+        batch.add_record(Record::new("2"));
+
+        let mut records = RecordSet {
+            batches: vec![batch],
+        };
+        run_block_on(replica.write_recordset(&mut records, false))?;
+
+        //when
+        let slice = run_block_on(replica.read_partition_slice(
+            0,
+            u32::MAX,
+            fluvio_spu_schema::Isolation::ReadUncommitted,
+        ))?;
+        let file_slice = slice
+            .file_slice
+            .ok_or_else(|| anyhow::anyhow!("expected file slice"))?;
+
+        let mut batch_iter = FileBatchIterator::from_raw_slice(file_slice);
+        let file_batch = batch_iter.next().expect("batch")?;
+        assert_eq!(file_batch.batch.schema_id(), SchemaId::from(7));
+
+        let record_iter = FileRecordIterator::new(std::iter::once(Ok(file_batch)), 0);
+        let records: Vec<RecordItem> =
+            record_iter.collect::<Result<Vec<RecordItem>, std::io::Error>>()?;
+
+        //then
+        assert_eq!(records.len(), 2);
+        assert_eq!(std::str::from_utf8(records[0].record.value())?, "1");
+        assert_eq!(std::str::from_utf8(records[1].record.value())?, "2");
+        assert_eq!(records[1].offset, 1);
+
+        Ok(())
+    }
+
     #[test]
     fn test_file_record_iterator_error_propagated() -> anyhow::Result<()> {
         //given
@@ -0,0 +1,199 @@
+//! Leader epoch index of replica.
+//!
+//! Leader stamps every batch it writes with leader epoch assigned by SC, so batches keep epoch of the leader which wrote them
+//! as they are replicated. Index tracks first offset of each epoch in the log and answers where an epoch ends.
+//! Follower compares its last epoch with leader's index to find records which diverge from leader and must be truncated.
+//!
+//! Index is updated as batches are appended, on leader and followers, and saved to replica directory whenever it changes.
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use tracing::{debug, info};
+use anyhow::Result;
+
+use fluvio_future::fs::{read, rename, write};
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_protocol::record::{Batch, BatchRecords, Offset};
+
+pub(crate) const LEADER_EPOCH_FILE_NAME: &str = "leader_epoch.index";
+const LEADER_EPOCH_TMP_FILE_NAME: &str = "leader_epoch.index.tmp";
+
+/// batches written before leader epochs were tracked
+pub const NO_LEADER_EPOCH: i32 = -1;
+
+/// Last leader epoch at or before requested epoch and offset where it ends
+#[derive(Debug, Default, Clone, Copy, Encoder, Decoder, PartialEq, Eq)]
+pub struct EpochEndOffset {
+    pub leader_epoch: i32,
+    pub end_offset: Offset,
+}
+
+#[derive(Debug, Default, Clone, Copy, Encoder, Decoder, PartialEq)]
+struct EpochStart {
+    leader_epoch: i32,
+    start_offset: Offset,
+}
+
+#[derive(Debug)]
+pub(crate) struct LeaderEpochIndex {
+    dir: PathBuf,
+    epochs: Vec<EpochStart>,
+}
+
+impl LeaderEpochIndex {
+    /// load index from replica directory.
+    /// Epochs which start at or after end of the log are dropped.
+    pub(crate) async fn load(dir: &Path, leo: Offset) -> Result<Self> {
+        let mut epochs = match read(dir.join(LEADER_EPOCH_FILE_NAME)).await {
+            Ok(bytes) => Vec::<EpochStart>::decode_from(&mut bytes.as_slice(), 0)?,
+            Err(err) if err.kind() == ErrorKind::NotFound => vec![],
+            Err(err) => return Err(err.into()),
+        };
+        epochs.retain(|epoch| epoch.start_offset < leo);
+        if let Some(last) = epochs.last() {
+            info!(leader_epoch = last.leader_epoch, "leader epochs loaded");
+        }
+        Ok(Self {
+            dir: dir.to_owned(),
+            epochs,
+        })
+    }
+
+    /// update index with batch which was appended to the log
+    pub(crate) async fn append<R: BatchRecords>(&mut self, batch: &Batch<R>) -> Result<()> {
+        let leader_epoch = batch.get_header().partition_leader_epoch;
+        if leader_epoch <= self.latest() {
+            return Ok(());
+        }
+        debug!(
+            leader_epoch,
+            start_offset = batch.get_base_offset(),
+            "new leader epoch"
+        );
+        self.epochs.push(EpochStart {
+            leader_epoch,
+            start_offset: batch.get_base_offset(),
+        });
+        self.save().await
+    }
+
+    /// epoch of last batch in the log
+    pub(crate) fn latest(&self) -> i32 {
+        self.epochs
+            .last()
+            .map(|epoch| epoch.leader_epoch)
+            .unwrap_or(NO_LEADER_EPOCH)
+    }
+
+    /// Find last epoch at or before requested epoch and offset where next epoch starts.
+    /// Last epoch ends at log end offset. Epoch older than any known ends where first known epoch starts.
+    /// Return None if log doesn't have epochs.
+    pub(crate) fn end_offset(&self, leader_epoch: i32, leo: Offset) -> Option<EpochEndOffset> {
+        let first = self.epochs.first()?;
+        let next = self
+            .epochs
+            .iter()
+            .position(|epoch| epoch.leader_epoch > leader_epoch);
+        let end = match next {
+            Some(0) => EpochEndOffset {
+                leader_epoch: NO_LEADER_EPOCH,
+                end_offset: first.start_offset,
+            },
+            Some(next) => EpochEndOffset {
+                leader_epoch: self.epochs[next - 1].leader_epoch,
+                end_offset: self.epochs[next].start_offset,
+            },
+            None => EpochEndOffset {
+                leader_epoch: self.latest(),
+                end_offset: leo,
+            },
+        };
+        Some(end)
+    }
+
+    /// drop epochs which start at or after new end of the log
+    pub(crate) async fn truncate(&mut self, leo: Offset) -> Result<()> {
+        let len = self.epochs.len();
+        self.epochs.retain(|epoch| epoch.start_offset < leo);
+        if self.epochs.len() != len {
+            self.save().await?;
+        }
+        Ok(())
+    }
+
+    async fn save(&self) -> Result<()> {
+        let mut bytes = Vec::new();
+        self.epochs.encode(&mut bytes, 0)?;
+        let tmp_path = self.dir.join(LEADER_EPOCH_TMP_FILE_NAME);
+        write(&tmp_path, bytes).await?;
+        rename(&tmp_path, self.dir.join(LEADER_EPOCH_FILE_NAME)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::record::Record;
+
+    use super::*;
+
+    fn batch(leader_epoch: i32, base_offset: Offset) -> Batch {
+        let mut batch = Batch::from(vec![Record::new("a"), Record::new("b")]);
+        batch.header.partition_leader_epoch = leader_epoch;
+        batch.set_base_offset(base_offset);
+        batch
+    }
+
+    #[fluvio_future::test]
+    async fn test_leader_epoch_index() {
+        let dir = temp_dir().join("test_leader_epoch_index");
+        ensure_new_dir(&dir).expect("new");
+
+        let mut index = LeaderEpochIndex::load(&dir, 0).await.expect("load");
+        assert_eq!(index.latest(), NO_LEADER_EPOCH);
+        assert_eq!(index.end_offset(0, 0), None);
+
+        // batches of legacy leader are not tracked
+        index.append(&batch(-1, 0)).await.expect("append");
+        assert_eq!(index.latest(), NO_LEADER_EPOCH);
+
+        for (leader_epoch, base_offset) in [(1, 2), (1, 4), (3, 6), (4, 8)] {
+            index
+                .append(&batch(leader_epoch, base_offset))
+                .await
+                .expect("append");
+        }
+        assert_eq!(index.latest(), 4);
+
+        let end = |leader_epoch, end_offset| {
+            Some(EpochEndOffset {
+                leader_epoch,
+                end_offset,
+            })
+        };
+        assert_eq!(index.end_offset(0, 10), end(NO_LEADER_EPOCH, 2));
+        assert_eq!(index.end_offset(1, 10), end(1, 6));
+        // epoch which leader never wrote ends with previous epoch
+        assert_eq!(index.end_offset(2, 10), end(1, 6));
+        assert_eq!(index.end_offset(3, 10), end(3, 8));
+        assert_eq!(index.end_offset(4, 10), end(4, 10));
+        assert_eq!(index.end_offset(5, 10), end(4, 10));
+
+        // state survives reload
+        let mut index = LeaderEpochIndex::load(&dir, 10).await.expect("load");
+        assert_eq!(index.latest(), 4);
+
+        index.truncate(7).await.expect("truncate");
+        assert_eq!(index.latest(), 3);
+        assert_eq!(index.end_offset(4, 7), end(3, 7));
+
+        // epochs outside of log are dropped
+        let index = LeaderEpochIndex::load(&dir, 6).await.expect("load");
+        assert_eq!(index.latest(), 1);
+    }
+}
@@ -19,12 +19,19 @@ pub mod iterators;
 #[cfg(feature = "fixture")]
 pub mod fixture;
 mod cleaner;
+mod scrubber;
+mod compaction;
+mod transaction;
+mod leader_epoch;
+pub mod tiered;
 
 pub use crate::error::StorageError;
 pub use crate::records::FileRecordsSlice;
 pub use crate::index::LogIndex;
 pub use crate::index::OffsetPosition;
 pub use crate::replica::FileReplica;
+pub use crate::scrubber::ScrubStatus;
+pub use crate::leader_epoch::{EpochEndOffset, NO_LEADER_EPOCH};
 
 pub use inner::*;
 mod inner {
@@ -35,6 +42,7 @@ mod inner {
     use fluvio_protocol::record::BatchRecords;
     use fluvio_protocol::link::ErrorCode;
     use fluvio_spu_schema::Isolation;
+    use fluvio_spu_schema::fetch::AbortedTransaction;
     use fluvio_protocol::record::{Offset, ReplicaKey, Size64};
     use fluvio_protocol::record::RecordSet;
     use fluvio_future::file_slice::AsyncFileSlice;
@@ -111,6 +119,7 @@ mod inner {
     }
 
     use crate::StorageError;
+    use crate::EpochEndOffset;
 
     /// Contain information about slice of Replica
     #[derive(Debug, Default)]
@@ -118,6 +127,8 @@ mod inner {
         pub start: Offset,   // start offset
         pub end: OffsetInfo, // end offset
         pub file_slice: Option<AsyncFileSlice>,
+        /// aborted transactions in the slice, only for `ReadCommitted` reads
+        pub aborted: Option<Vec<AbortedTransaction>>,
     }
 
     /// some storage configuration
@@ -156,6 +167,16 @@ mod inner {
 
         fn get_partition_size(&self) -> Size64;
 
+        /// batches found corrupt by crc checks and not repaired yet
+        fn get_corrupt_batch_count(&self) -> usize;
+
+        /// find offset of first record which has timestamp (milliseconds since epoch) at or after given timestamp
+        /// return None if all records are older
+        async fn find_offset_by_timestamp(
+            &self,
+            timestamp: i64,
+        ) -> Result<Option<Offset>, ErrorCode>;
+
         /// write record set
         async fn write_recordset<R: BatchRecords>(
             &mut self,
@@ -165,6 +186,21 @@ mod inner {
 
         async fn update_high_watermark(&mut self, offset: Offset) -> Result<bool, StorageError>;
 
+        /// move log start offset forward, records before offset are no longer readable.
+        /// return new log start offset
+        async fn delete_records(&mut self, offset: Offset) -> Result<Offset, StorageError>;
+
+        /// leader epoch of last batch in the log
+        fn get_leader_epoch(&self) -> i32;
+
+        /// last leader epoch at or before given epoch and offset where it ends in the log,
+        /// None if log doesn't track leader epochs
+        fn leader_epoch_end_offset(&self, leader_epoch: i32) -> Option<EpochEndOffset>;
+
+        /// remove records at and after offset, log is truncated at batch boundary.
+        /// return new log end offset
+        async fn truncate(&mut self, offset: Offset) -> Result<Offset, StorageError>;
+
         /// permanently remove
         async fn remove(&self) -> Result<(), StorageError>;
     }
@@ -19,7 +19,10 @@ use fluvio_protocol::record::{Offset, Size, Size64};
 use crate::config::SharedReplicaConfig;
 use crate::util::generate_file_name;
 use crate::index::{Entry, INDEX_ENTRY_SIZE, lookup_entry};
+use crate::index::{TimeEntry, TIME_INDEX_ENTRY_SIZE, TIME_INDEX_EXTENSION};
+use crate::index::{lookup_time_entry, time_entry_to_be};
 use crate::index::Index;
+use crate::index::TimeIndex;
 use crate::index::OffsetPosition;
 
 pub const EXTENSION: &str = "index";
@@ -284,6 +287,181 @@ impl DerefMut for MutLogIndex {
     }
 }
 
+/// Time index for active segment
+/// Each entry in index consist of pair of (max_timestamp, relative_offset)
+pub struct MutLogTimeIndex {
+    _mmap: MemoryMappedMutFile,
+    file: File,
+    base_offset: Offset,         // base offset of segment
+    accumulated_batch_len: Size, // accumulated batches len
+    max_timestamp: i64,          // max timestamp of all batches written so far
+    first_empty_slot: Size,      // track of the current write slot
+    entries: usize,              // entries capacity of mapped file
+    max_index_interval: Size,
+    ptr: *mut c_void,
+}
+
+unsafe impl Sync for MutLogTimeIndex {}
+unsafe impl Send for MutLogTimeIndex {}
+
+impl MutLogTimeIndex {
+    #[instrument(skip(option))]
+    pub async fn create(
+        base_offset: Offset,
+        option: Arc<SharedReplicaConfig>,
+    ) -> Result<Self, IoError> {
+        let index = Self::map(base_offset, option).await?;
+        info!(base_offset, "created time index file");
+        Ok(index)
+    }
+
+    pub async fn open(
+        base_offset: Offset,
+        option: Arc<SharedReplicaConfig>,
+    ) -> Result<Self, IoError> {
+        let mut index = Self::map(base_offset, option).await?;
+
+        index.first_empty_slot = index.find_first_empty_index();
+        if let Some(last) = index.first_empty_slot.checked_sub(1) {
+            index.max_timestamp = time_entry_to_be(index[last as usize]).0;
+        }
+        debug!(
+            index.first_empty_slot,
+            index.max_timestamp, "next time index slot"
+        );
+
+        Ok(index)
+    }
+
+    async fn map(base_offset: Offset, option: Arc<SharedReplicaConfig>) -> Result<Self, IoError> {
+        let index_file_path =
+            generate_file_name(&option.base_dir, base_offset, TIME_INDEX_EXTENSION);
+
+        if option.index_max_bytes.get() == 0 {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "index max bytes must be greater than 0",
+            ));
+        }
+
+        debug!(?index_file_path, "mapping time index");
+        let (m_file, file) =
+            MemoryMappedMutFile::create(&index_file_path, option.index_max_bytes.get() as u64)
+                .await?;
+
+        let ptr = {
+            let b_slices: &[u8] = &m_file.mut_inner();
+            b_slices.as_ptr() as *mut libc::c_void
+        };
+
+        Ok(MutLogTimeIndex {
+            max_index_interval: option.index_max_interval_bytes.get_consistent(),
+            _mmap: m_file,
+            file,
+            base_offset,
+            accumulated_batch_len: 0,
+            max_timestamp: 0,
+            first_empty_slot: 0,
+            entries: (option.index_max_bytes.get() as u64 / TIME_INDEX_ENTRY_SIZE) as usize,
+            ptr,
+        })
+    }
+
+    // shrink index file to last know position
+    pub async fn shrink(&mut self) -> Result<(), IoError> {
+        let target_len = self.first_empty_slot as u64 * TIME_INDEX_ENTRY_SIZE;
+        debug!(
+            target_len,
+            base_offset = self.base_offset,
+            "shrinking time index"
+        );
+        self.file.set_len(target_len).await
+    }
+
+    /// empty slot has zero timestamp, since batches without timestamp are not indexed
+    fn find_first_empty_index(&self) -> Size {
+        self.iter()
+            .position(|entry| entry.0 == 0)
+            .unwrap_or(self.len()) as Size
+    }
+
+    pub fn max_timestamp(&self) -> i64 {
+        self.max_timestamp
+    }
+
+    /// account timestamp of batch which is already in the segment but not in the index
+    pub fn update_max_timestamp(&mut self, timestamp: i64) {
+        self.max_timestamp = self.max_timestamp.max(timestamp);
+    }
+
+    /// write time index entry
+    /// timestamp: max timestamp of the batch
+    /// offset_delta: relative offset of the batch in the segment
+    /// batch_size: size of the batch
+    #[instrument(skip(self))]
+    pub async fn write_time_index(
+        &mut self,
+        timestamp: i64,
+        offset_delta: Size,
+        batch_size: Size,
+    ) -> Result<(), IoError> {
+        self.update_max_timestamp(timestamp);
+
+        // batches without timestamp are not indexed
+        if self.max_timestamp <= 0 {
+            return Ok(());
+        }
+
+        // same interval as offset index
+        if self.accumulated_batch_len < self.max_index_interval {
+            self.accumulated_batch_len += batch_size;
+            return Ok(());
+        }
+
+        if (self.first_empty_slot as usize) < self.len() {
+            let slot_index = self.first_empty_slot as usize;
+            debug!(
+                slot_index,
+                self.max_timestamp, offset_delta, "add new time entry at"
+            );
+            self[slot_index] = time_entry_to_be((self.max_timestamp, offset_delta as u64));
+            self.accumulated_batch_len = 0;
+            self.first_empty_slot += 1;
+        } else {
+            error!(
+                "time index position: {} is greater than max entries: {}, ignoring",
+                self.first_empty_slot,
+                self.len()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl TimeIndex for MutLogTimeIndex {
+    fn find_timestamp(&self, timestamp: i64) -> Option<TimeEntry> {
+        let (lower, _) = self.split_at(self.first_empty_slot as usize);
+        lookup_time_entry(lower, timestamp).map(|idx| time_entry_to_be(self[idx]))
+    }
+}
+
+impl Deref for MutLogTimeIndex {
+    type Target = [TimeEntry];
+
+    #[inline]
+    fn deref(&self) -> &[TimeEntry] {
+        unsafe { slice::from_raw_parts(self.ptr as *const TimeEntry, self.entries) }
+    }
+}
+
+impl DerefMut for MutLogTimeIndex {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [TimeEntry] {
+        unsafe { slice::from_raw_parts_mut(self.ptr as *mut TimeEntry, self.entries) }
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "fixture")]
 mod tests {
@@ -295,8 +473,9 @@ mod tests {
     use flv_util::fixture::ensure_clean_file;
 
     use super::MutLogIndex;
+    use super::MutLogTimeIndex;
     use crate::LogIndex;
-    use crate::index::Index;
+    use crate::index::{Index, LogTimeIndex, TimeIndex};
     use crate::fixture::default_option;
 
     #[fluvio_future::test]
@@ -429,4 +608,52 @@ mod tests {
         assert_eq!(index.find_offset(600), Some((500, 200)));
         assert_eq!(index.find_offset(2000), Some((1000, 200)));
     }
+
+    #[fluvio_future::test]
+    async fn test_time_index_write() {
+        const TEST_FILE4: &str = "00000000000000000124.timeindex";
+        let option = default_option(100).shared();
+        let test_file = option.base_dir.join(TEST_FILE4);
+        ensure_clean_file(&test_file);
+
+        let mut index = MutLogTimeIndex::create(124, option.clone())
+            .await
+            .expect("create");
+
+        index.write_time_index(1000, 0, 50).await.expect("write"); // ignored, less than interval
+        index.write_time_index(-1, 5, 60).await.expect("write"); // no timestamp, ignored
+        assert_eq!(index.first_empty_slot, 0);
+        assert_eq!(index.max_timestamp(), 1000);
+
+        index.write_time_index(2000, 10, 50).await.expect("write"); // trigger write
+        index.write_time_index(1500, 20, 150).await.expect("write"); // ignored, less than interval
+        index.write_time_index(1800, 30, 50).await.expect("write"); // trigger write, keeps max
+        assert_eq!(index.first_empty_slot, 2);
+
+        assert_eq!(index.find_timestamp(1000), None);
+        assert_eq!(index.find_timestamp(2001), Some((2000, 30)));
+
+        drop(index);
+
+        let mut index = MutLogTimeIndex::open(124, option.clone())
+            .await
+            .expect("open");
+        assert_eq!(index.first_empty_slot, 2);
+        assert_eq!(index.max_timestamp(), 2000);
+        index.shrink().await.expect("shrink");
+        drop(index);
+
+        let index = LogTimeIndex::open_from_offset(124, option.clone())
+            .await
+            .expect("open");
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.find_timestamp(2000), None);
+        assert_eq!(index.find_timestamp(3000), Some((2000, 30)));
+
+        let missing = LogTimeIndex::open_from_offset(125, option)
+            .await
+            .expect("open");
+        assert!(missing.is_empty());
+        assert_eq!(missing.find_timestamp(3000), None);
+    }
 }
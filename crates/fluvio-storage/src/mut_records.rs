@@ -116,8 +116,12 @@ impl MutFileRecords {
         Ok(())
     }
 
-    pub(crate) async fn validate(&mut self, index: &MutLogIndex) -> Result<LogValidator> {
-        LogValidator::default_validate(&self.path, Some(index)).await
+    pub(crate) async fn validate(
+        &mut self,
+        index: &MutLogIndex,
+        verify_crc: bool,
+    ) -> Result<LogValidator> {
+        LogValidator::default_validate(&self.path, Some(index), verify_crc).await
     }
 
     /// get current file position
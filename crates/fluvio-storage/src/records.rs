@@ -89,8 +89,8 @@ impl FileRecordsSlice {
         self.base_offset
     }
 
-    pub async fn validate(&self, index: &LogIndex) -> Result<LogValidator> {
-        LogValidator::default_validate(&self.path, Some(index)).await
+    pub async fn validate(&self, index: &LogIndex, verify_crc: bool) -> Result<LogValidator> {
+        LogValidator::default_validate(&self.path, Some(index), verify_crc).await
     }
 
     pub fn modified_time_elapsed(&self) -> Result<Duration, SystemTimeError> {
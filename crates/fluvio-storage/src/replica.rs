@@ -2,21 +2,22 @@ use std::cmp::min;
 use std::{fmt, mem};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
-use tracing::{debug, trace, warn, instrument, info};
+use tracing::{debug, error, trace, warn, instrument, info};
 use async_trait::async_trait;
 use anyhow::Result;
 
 use fluvio_future::file_slice::AsyncFileSlice;
 use fluvio_protocol::Encoder;
-use fluvio_future::fs::{create_dir_all, remove_dir_all};
+use fluvio_future::fs::{create_dir_all, remove_dir_all, remove_file, rename, File};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_spu_schema::Isolation;
 use fluvio_protocol::record::{Offset, ReplicaKey, Size, Size64};
-use fluvio_protocol::record::{Batch, BatchRecords};
+use fluvio_protocol::record::{Batch, BatchRecords, RawRecords, BATCH_FILE_HEADER_SIZE};
 use fluvio_protocol::record::RecordSet;
 
-use crate::checkpoint::HW_CHECKPOINT_FILE_NAME;
+use crate::checkpoint::{HW_CHECKPOINT_FILE_NAME, LOG_START_CHECKPOINT_FILE_NAME};
 use crate::{OffsetInfo, checkpoint::CheckPoint};
 use crate::segments::SharedSegments;
 use crate::segment::MutableSegment;
@@ -24,6 +25,24 @@ use crate::config::{ReplicaConfig, SharedReplicaConfig, StorageConfig};
 use crate::ReplicaSlice;
 use crate::{StorageError, ReplicaStorage};
 use crate::cleaner::Cleaner;
+use crate::compaction::recover_staged_segments;
+use crate::transaction::TransactionIndex;
+use crate::leader_epoch::{EpochEndOffset, LeaderEpochIndex};
+use crate::batch::{
+    batch_base_offset, batch_len_at, verify_batch_crc, verify_batches_crc, FileBatchStream,
+};
+use crate::index::{EXTENSION as INDEX_EXTENSION, TIME_INDEX_EXTENSION};
+use crate::records::MESSAGE_LOG_EXTENSION;
+use crate::util::generate_file_name;
+use crate::file::read_file_slice;
+use crate::records::FileRecords;
+use crate::scrubber::{overwrite_batch, CorruptionReport, Scrubber, ScrubStatus};
+
+/// directory under replica where truncated segment is written before replacing original
+const TRUNCATION_DIR: &str = "truncation";
+
+/// max bytes read at once when crc of read slice is checked
+const VERIFY_CHUNK_SIZE: u64 = 1024 * 1024;
 
 /// Replica is public abstraction for commit log which are distributed.
 /// Internally it is stored as list of segments.  Each segment contains finite sets of record batches.
@@ -38,8 +57,14 @@ pub struct FileReplica {
     active_segment: MutableSegment,
     prev_segments: Arc<SharedSegments>,
     commit_checkpoint: CheckPoint,
+    /// only created when records are deleted
+    log_start_checkpoint: Option<CheckPoint>,
     cleaner: Arc<Cleaner>,
+    scrubber: Arc<Scrubber>,
+    corruption: Arc<CorruptionReport>,
     size: Arc<ReplicaSize>,
+    transactions: TransactionIndex,
+    leader_epochs: LeaderEpochIndex,
     short_circuit: bool, // if this is true, last append failed, should not append again
     max_request_size: usize,
     max_segment_size: usize,
@@ -89,11 +114,14 @@ impl ReplicaStorage for FileReplica {
     /// earliest offset
     fn get_log_start_offset(&self) -> Offset {
         let min_base_offset = self.prev_segments.min_offset();
-        if min_base_offset < 0 {
+        let base_offset = if min_base_offset < 0 {
             self.active_segment.get_base_offset()
         } else {
             min_base_offset
-        }
+        };
+        // deleted records may end in the middle of segment
+        let deleted_before = self.prev_segments.log_start_offset().min(self.get_leo());
+        base_offset.max(deleted_before)
     }
 
     /// read partition slice
@@ -107,8 +135,13 @@ impl ReplicaStorage for FileReplica {
     ) -> Result<ReplicaSlice, ErrorCode> {
         match isolation {
             Isolation::ReadCommitted => {
-                self.read_records(offset, Some(self.get_hw()), max_len)
-                    .await
+                let stable_offset = self.get_last_stable_offset();
+                let mut slice = self
+                    .read_records(offset, Some(stable_offset), max_len)
+                    .await?;
+                slice.end.hw = stable_offset;
+                slice.aborted = Some(self.transactions.aborted(offset, stable_offset));
+                Ok(slice)
             }
             Isolation::ReadUncommitted => self.read_records(offset, None, max_len).await,
         }
@@ -127,6 +160,29 @@ impl ReplicaStorage for FileReplica {
         total_prev_segments_len + active_len
     }
 
+    fn get_corrupt_batch_count(&self) -> usize {
+        self.corruption.count()
+    }
+
+    #[instrument(skip(self))]
+    async fn find_offset_by_timestamp(&self, timestamp: i64) -> Result<Option<Offset>, ErrorCode> {
+        let offset = match self
+            .prev_segments
+            .find_offset_by_timestamp(timestamp)
+            .await
+            .map_err(|err| ErrorCode::Other(format!("timestamp lookup error: {err:#?}")))?
+        {
+            Some(offset) => Some(offset),
+            None => self
+                .active_segment
+                .find_offset_by_timestamp(timestamp)
+                .await
+                .map_err(|err| ErrorCode::Other(format!("timestamp lookup error: {err:#?}")))?,
+        };
+        debug!(timestamp, ?offset, "resolved timestamp");
+        Ok(offset)
+    }
+
     /// write records to this replica
     /// if update_highwatermark is set, set high watermark is end
     //  this is used when LRS = 1
@@ -184,13 +240,121 @@ impl ReplicaStorage for FileReplica {
         }
     }
 
+    #[instrument(skip(self))]
+    async fn delete_records(&mut self, offset: Offset) -> Result<Offset, StorageError> {
+        if offset <= self.prev_segments.log_start_offset() {
+            return Ok(self.get_log_start_offset());
+        }
+        info!(offset, "deleting records");
+        let checkpoint = match &mut self.log_start_checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => self.log_start_checkpoint.insert(
+                CheckPoint::create(self.option.clone(), LOG_START_CHECKPOINT_FILE_NAME, 0).await?,
+            ),
+        };
+        checkpoint.write(offset);
+        self.prev_segments.set_log_start_offset(offset);
+        self.cleaner.enforce_log_start().await;
+        self.prune_transactions()
+            .await
+            .map_err(|err| StorageError::Other(format!("failed to prune transactions: {err}")))?;
+        Ok(self.get_log_start_offset())
+    }
+
+    fn get_leader_epoch(&self) -> i32 {
+        self.leader_epochs.latest()
+    }
+
+    fn leader_epoch_end_offset(&self, leader_epoch: i32) -> Option<EpochEndOffset> {
+        self.leader_epochs.end_offset(leader_epoch, self.get_leo())
+    }
+
+    /// Segment containing offset is rewritten with batches before offset and becomes active segment,
+    /// later segments are removed
+    #[instrument(skip(self))]
+    async fn truncate(&mut self, offset: Offset) -> Result<Offset, StorageError> {
+        if offset >= self.get_leo() {
+            return Ok(self.get_leo());
+        }
+        let active_base = self.active_segment.get_base_offset();
+        let segment_base = if offset >= active_base {
+            active_base
+        } else {
+            self.prev_segments
+                .read()
+                .await
+                .find_segment(offset)
+                .map(|(base_offset, _)| *base_offset)
+                .unwrap_or(offset)
+        };
+        info!(offset, segment_base, leo = self.get_leo(), "truncating log");
+
+        self.write_segment_before(segment_base, offset)
+            .await
+            .map_err(|err| StorageError::Other(format!("failed to truncate: {err}")))?;
+
+        // drop everything after the truncated segment first, so crash before the
+        // truncated segment is in place leaves only extra batches to truncate again
+        let removed: Vec<Offset> = self
+            .prev_segments
+            .read()
+            .await
+            .iter()
+            .map(|segment| segment.get_base_offset())
+            .filter(|base_offset| *base_offset > segment_base)
+            .collect();
+        self.prev_segments.remove_segments(&removed).await;
+        self.prev_segments.detach_segment(segment_base).await;
+        if active_base != segment_base {
+            for extension in [MESSAGE_LOG_EXTENSION, INDEX_EXTENSION, TIME_INDEX_EXTENSION] {
+                let path = generate_file_name(&self.option.base_dir, active_base, extension);
+                if path.exists() {
+                    remove_file(path).await?;
+                }
+            }
+        }
+
+        self.replace_with_truncated_segment(segment_base)
+            .await
+            .map_err(|err| StorageError::Other(format!("failed to truncate: {err}")))?;
+
+        let mut active_segment =
+            MutableSegment::open_for_write(segment_base, self.option.clone()).await?;
+        active_segment.validate_and_repair().await.map_err(|err| {
+            StorageError::Other(format!("failed to open truncated segment: {err}"))
+        })?;
+        let old_segment = mem::replace(&mut self.active_segment, active_segment);
+        drop(old_segment);
+
+        let leo = self.get_leo();
+        if self.get_hw() > leo {
+            self.commit_checkpoint.write(leo);
+        }
+        self.size
+            .store_active(self.active_segment.occupied_memory());
+        self.size
+            .store_prev(self.prev_segments.read().await.occupied_memory());
+        self.transactions
+            .truncate(leo)
+            .await
+            .and(self.leader_epochs.truncate(leo).await)
+            .map_err(|err| StorageError::Other(format!("failed to truncate indexes: {err}")))?;
+        self.short_circuit = false;
+        info!(leo, "log truncated");
+        Ok(leo)
+    }
+
     #[instrument(skip(self))]
     async fn remove(&self) -> Result<(), StorageError> {
+        self.prev_segments.remove_remote().await.map_err(|err| {
+            StorageError::Other(format!("failed to remove remote segments: {err}"))
+        })?;
         remove_dir_all(&self.option.base_dir)
             .await
             .map_err(StorageError::Io)?;
 
         self.cleaner.shutdown();
+        self.scrubber.shutdown();
         Ok(())
     }
 }
@@ -235,6 +399,7 @@ impl FileReplica {
 
         let shared_config: Arc<SharedReplicaConfig> = Arc::new(rep_option.into());
 
+        recover_staged_segments(&shared_config.base_dir).await?;
         let (segments, last_offset_res) = SharedSegments::from_dir(shared_config.clone()).await?;
 
         let active_segment = if let Some(last_offset) = last_offset_res {
@@ -248,7 +413,9 @@ impl FileReplica {
             );
             last_segment
         } else {
-            info!("no existing segment found, creating new one");
+            // local log may be lost while offloaded segments are still in remote store
+            let base_offset = base_offset.max(segments.read().await.end_offset());
+            info!(base_offset, "no existing segment found, creating new one");
             MutableSegment::create(base_offset, shared_config.clone()).await?
         };
 
@@ -261,6 +428,20 @@ impl FileReplica {
         )
         .await?;
 
+        let log_start_checkpoint = if shared_config
+            .base_dir
+            .join(LOG_START_CHECKPOINT_FILE_NAME)
+            .exists()
+        {
+            let checkpoint =
+                CheckPoint::create(shared_config.clone(), LOG_START_CHECKPOINT_FILE_NAME, 0)
+                    .await?;
+            segments.set_log_start_offset(checkpoint.get_offset());
+            Some(checkpoint)
+        } else {
+            None
+        };
+
         // ensure checkpoint is valid
         let hw = commit_checkpoint.get_offset();
         let leo = active_segment.get_end_offset();
@@ -275,6 +456,14 @@ impl FileReplica {
         let size = Arc::new(ReplicaSize::default());
         size.store_active(active_segment.occupied_memory());
 
+        let log_start_offset = match segments.min_offset() {
+            min_offset if min_offset < 0 => last_base_offset,
+            min_offset => min_offset,
+        };
+        let transactions =
+            TransactionIndex::load(&shared_config.base_dir, log_start_offset, leo).await?;
+        let leader_epochs = LeaderEpochIndex::load(&shared_config.base_dir, leo).await?;
+
         let cleaner = Cleaner::start_new(
             storage_config,
             shared_config.clone(),
@@ -282,6 +471,20 @@ impl FileReplica {
             size.clone(),
         );
 
+        let corruption = Arc::new(CorruptionReport::default());
+        for segment in segments.read().await.iter() {
+            segment
+                .corrupt_batches()
+                .iter()
+                .for_each(|batch| corruption.add(batch.clone()));
+        }
+        active_segment
+            .corrupt_batches()
+            .iter()
+            .for_each(|batch| corruption.add(batch.clone()));
+        let scrubber =
+            Scrubber::start_new(shared_config.clone(), segments.clone(), corruption.clone());
+
         let max_request_size = shared_config.max_request_size.get_consistent() as usize;
         let max_segment_size = shared_config.segment_max_bytes.get_consistent() as usize;
 
@@ -292,8 +495,13 @@ impl FileReplica {
             active_segment,
             prev_segments: segments,
             commit_checkpoint,
+            log_start_checkpoint,
             cleaner,
+            scrubber,
+            corruption,
             size,
+            transactions,
+            leader_epochs,
             short_circuit: false,
             max_request_size,
             max_segment_size,
@@ -319,6 +527,111 @@ impl FileReplica {
         self.update_high_watermark(self.get_leo()).await
     }
 
+    /// number of segments including active segment
+    pub async fn segment_count(&self) -> usize {
+        self.prev_segments.read().await.len() + 1
+    }
+
+    /// corrupt batches found by crc checks and progress of background scrubbing
+    pub fn scrub_status(&self) -> ScrubStatus {
+        self.corruption.status()
+    }
+
+    /// check crc of all closed segments now, without waiting for scrub interval
+    pub async fn scrub(&self) {
+        self.scrubber.scrub().await;
+    }
+
+    /// bytes of batch starting at offset, if its crc is valid.
+    /// Used to repair corrupt batch of other replica of the partition.
+    #[instrument(skip(self))]
+    pub async fn read_batch(&self, base_offset: Offset) -> Result<Option<Vec<u8>>, ErrorCode> {
+        let slice = self
+            .read_records(base_offset, None, BATCH_FILE_HEADER_SIZE as u32)
+            .await?;
+        let Some(file_slice) = slice.file_slice else {
+            return Ok(None);
+        };
+        let read_err = |err: std::io::Error| ErrorCode::Other(format!("batch read error: {err}"));
+        let header = read_file_slice(&file_slice).await.map_err(read_err)?;
+        let Some(len) = batch_len_at(&header) else {
+            return Ok(None);
+        };
+        if batch_base_offset(&header) != base_offset {
+            return Ok(None);
+        }
+        let batch = read_file_slice(&AsyncFileSlice::new(
+            file_slice.fd(),
+            file_slice.position(),
+            len as u64,
+        ))
+        .await
+        .map_err(read_err)?;
+        if batch.len() != len {
+            return Ok(None);
+        }
+        match verify_batch_crc(&batch, file_slice.position() as Size) {
+            Ok(()) => Ok(Some(batch.to_vec())),
+            Err(corrupt) => {
+                self.corruption.add(corrupt);
+                Ok(None)
+            }
+        }
+    }
+
+    /// overwrite corrupt batch with healthy copy of it from other replica.
+    /// Return false if batch is not known to be corrupt.
+    #[instrument(skip(self, batch))]
+    pub async fn repair_batch(&mut self, batch: &[u8]) -> Result<bool, StorageError> {
+        if batch_len_at(batch) != Some(batch.len()) {
+            return Err(StorageError::Other(
+                "repair requires exactly one batch".to_owned(),
+            ));
+        }
+        verify_batch_crc(batch, 0)?;
+        let base_offset = batch_base_offset(batch);
+        let Some(corrupt) = self.corruption.get(base_offset) else {
+            return Ok(false);
+        };
+
+        let path = if base_offset >= self.active_segment.get_base_offset() {
+            Some(self.active_segment.get_msg_log().get_path().to_owned())
+        } else {
+            self.prev_segments
+                .read()
+                .await
+                .find_segment(base_offset)
+                .map(|(_, segment)| segment.get_msg_log().get_path().to_owned())
+        };
+        let Some(path) = path else {
+            // segment is gone
+            self.corruption.remove(base_offset);
+            return Ok(false);
+        };
+
+        // batch stays reported on failure, next scrub of segment refreshes it
+        overwrite_batch(path, corrupt.clone(), batch.to_vec())
+            .await
+            .map_err(|err| StorageError::Other(format!("batch repair failed: {err}")))?;
+        info!(%corrupt, "repaired batch");
+        self.corruption.mark_repaired(base_offset);
+        Ok(true)
+    }
+
+    /// offset up to which records can be read with `ReadCommitted` isolation.
+    /// This is high watermark unless there is open transaction which started before it.
+    pub fn get_last_stable_offset(&self) -> Offset {
+        let hw = self.get_hw();
+        self.transactions
+            .first_open_offset()
+            .map_or(hw, |first_open| min(first_open, hw))
+    }
+
+    /// producers of transactions which are open in this replica longer than timeout
+    pub fn expired_transactions(&self, timeout: Duration) -> Vec<i64> {
+        self.transactions.expired(timeout)
+    }
+
     /// read all uncommitted records
     #[allow(unused)]
     #[instrument(skip(self, max_len))]
@@ -346,9 +659,17 @@ impl FileReplica {
         let leo = self.get_leo();
         debug!(hw, leo, "starting read records",);
 
+        let log_start_offset = self.get_log_start_offset();
+        if start_offset < log_start_offset {
+            return Err(ErrorCode::OffsetEvicted {
+                offset: start_offset,
+                next_available: log_start_offset,
+            });
+        }
+
         let mut slice = ReplicaSlice {
             end: OffsetInfo { hw, leo },
-            start: self.get_log_start_offset(),
+            start: log_start_offset,
             ..Default::default()
         };
 
@@ -385,12 +706,16 @@ impl FileReplica {
                 })?
         };
 
-        let limited_slice = AsyncFileSlice::new(
+        let mut limited_slice = AsyncFileSlice::new(
             file_slice.fd(),
             file_slice.position(),
             min(file_slice.len(), max_len as u64),
         );
 
+        if self.option.verify_crc_on_read {
+            limited_slice = self.verify_slice(limited_slice).await?;
+        }
+
         debug!(
             fd = limited_slice.fd(),
             pos = limited_slice.position(),
@@ -402,6 +727,46 @@ impl FileReplica {
         Ok(slice)
     }
 
+    /// check crc of complete batches in slice, slice is cut before first corrupt batch.
+    /// Slice is read in chunks, so only single chunk or batch is held in memory.
+    async fn verify_slice(&self, slice: AsyncFileSlice) -> Result<AsyncFileSlice, ErrorCode> {
+        let read_err = |err: std::io::Error| ErrorCode::Other(format!("slice read error: {err}"));
+        let end = slice.position() + slice.len();
+        let mut pos = slice.position();
+        while pos < end {
+            let chunk_len = (end - pos).min(VERIFY_CHUNK_SIZE);
+            let mut bytes = read_file_slice(&AsyncFileSlice::new(slice.fd(), pos, chunk_len))
+                .await
+                .map_err(read_err)?;
+            let Some(batch_len) = batch_len_at(&bytes) else {
+                break;
+            };
+            // batch bigger than chunk is read whole
+            if batch_len > bytes.len() && pos + batch_len as u64 <= end {
+                bytes = read_file_slice(&AsyncFileSlice::new(slice.fd(), pos, batch_len as u64))
+                    .await
+                    .map_err(read_err)?;
+            }
+            match verify_batches_crc(&bytes, pos as Size) {
+                // partial batch at end of slice is not checked
+                Ok(0) => break,
+                Ok(verified) => pos += verified as u64,
+                Err(corrupt) => {
+                    error!(%corrupt, "corrupt batch in read slice");
+                    let valid_len = corrupt.pos as u64 - slice.position();
+                    let offset = corrupt.base_offset;
+                    self.corruption.add(corrupt);
+                    return if valid_len == 0 {
+                        Err(ErrorCode::CorruptRecordBatch { offset })
+                    } else {
+                        Ok(AsyncFileSlice::new(slice.fd(), slice.position(), valid_len))
+                    };
+                }
+            }
+        }
+        Ok(slice)
+    }
+
     #[instrument(skip(self, item))]
     async fn write_batch<R: BatchRecords>(&mut self, item: &mut Batch<R>) -> Result<()> {
         if self.short_circuit {
@@ -448,6 +813,54 @@ impl FileReplica {
 
         self.size
             .store_active(self.active_segment.occupied_memory());
+        self.transactions.append(item).await?;
+        self.leader_epochs.append(item).await
+    }
+
+    /// Write batches of segment which end before offset into segment files in truncation directory.
+    async fn write_segment_before(&self, segment_base: Offset, offset: Offset) -> Result<()> {
+        let truncation_dir = self.option.base_dir.join(TRUNCATION_DIR);
+        if truncation_dir.exists() {
+            remove_dir_all(&truncation_dir).await?;
+        }
+        create_dir_all(&truncation_dir).await?;
+        let truncation_option = Arc::new(self.option.with_base_dir(truncation_dir.clone()));
+
+        let mut truncated = MutableSegment::create(segment_base, truncation_option).await?;
+        let path = generate_file_name(&self.option.base_dir, segment_base, MESSAGE_LOG_EXTENSION);
+        if path.exists() {
+            let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(&path).await?;
+            while let Some(batch_pos) = stream.try_next().await? {
+                let batch = batch_pos.inner();
+                if batch.get_last_offset() >= offset {
+                    break;
+                }
+                truncated.append_batch_at_base_offset(&batch).await?;
+            }
+        }
+        truncated.flush().await?;
+        drop(truncated);
+
+        for extension in [MESSAGE_LOG_EXTENSION, INDEX_EXTENSION, TIME_INDEX_EXTENSION] {
+            let path = generate_file_name(&truncation_dir, segment_base, extension);
+            File::open(path).await?.sync_all().await?;
+        }
+        Ok(())
+    }
+
+    /// Move segment files written by `write_segment_before` over original segment files.
+    /// Indexes are moved first since kept batches are at same position in original log,
+    /// so crash in between leaves consistent segment.
+    async fn replace_with_truncated_segment(&self, segment_base: Offset) -> Result<()> {
+        let truncation_dir = self.option.base_dir.join(TRUNCATION_DIR);
+        for extension in [TIME_INDEX_EXTENSION, INDEX_EXTENSION, MESSAGE_LOG_EXTENSION] {
+            rename(
+                generate_file_name(&truncation_dir, segment_base, extension),
+                generate_file_name(&self.option.base_dir, segment_base, extension),
+            )
+            .await?;
+        }
+        remove_dir_all(&truncation_dir).await?;
         Ok(())
     }
 
@@ -470,7 +883,14 @@ impl FileReplica {
         let old_segment = old_mut_segment.as_segment().await?;
         self.size.add_prev(old_segment.occupied_memory());
         self.prev_segments.add_segment(old_segment).await;
-        Ok(())
+        // segments removed by cleaner since last roll over may have taken aborted transactions
+        self.prune_transactions().await
+    }
+
+    /// forget aborted transactions which are no longer in the log
+    async fn prune_transactions(&mut self) -> Result<()> {
+        let log_start_offset = self.get_log_start_offset();
+        self.transactions.remove_before(log_start_offset).await
     }
 }
 
@@ -525,7 +945,7 @@ mod tests {
 
     use fluvio_spu_schema::Isolation;
     use fluvio_protocol::link::ErrorCode;
-    use fluvio_protocol::record::Batch;
+    use fluvio_protocol::record::{Batch, ControlRecordType};
     use fluvio_protocol::record::Offset;
     use fluvio_protocol::{Decoder, Encoder};
     use fluvio_protocol::record::{Record, RecordSet};
@@ -536,7 +956,7 @@ mod tests {
 
     use crate::config::{ReplicaConfig, StorageConfig};
     use crate::StorageError;
-    use crate::ReplicaStorage;
+    use crate::{EpochEndOffset, ReplicaStorage};
     use crate::fixture::storage_config;
 
     use super::FileReplica;
@@ -749,7 +1169,8 @@ mod tests {
         assert_eq!(replica.get_log_start_offset(), START_OFFSET);
         let replica_dir = &option.base_dir.join("test-1");
         let dir_contents = fs::read_dir(replica_dir).expect("read_dir");
-        assert_eq!(dir_contents.count(), 5, "should be 5 files");
+        // log, index and time index for each segment, plus checkpoint
+        assert_eq!(dir_contents.count(), 7, "should be 7 files");
 
         let seg2_file = replica_dir.join(TEST_SE2_NAME);
         let bytes = read_bytes_from_file(seg2_file).expect("file read");
@@ -909,6 +1330,63 @@ mod tests {
         assert_eq!(slice.file_slice.unwrap().len() as usize, batch_len);
     }
 
+    #[fluvio_future::test]
+    async fn test_transactional_committed_fetch() {
+        let option = base_option("test_transactional_committed_fetch");
+        let mut replica = create_replica("test", 0, option.clone()).await;
+
+        let mut batch = create_batch();
+        let batch_len = batch.write_size(0);
+        replica.write_batch(&mut batch).await.expect("write");
+
+        // open transaction holds back committed reads
+        let mut txn_batch = create_batch();
+        txn_batch.header.set_transactional();
+        txn_batch.header.producer_id = 7;
+        replica.write_batch(&mut txn_batch).await.expect("write");
+        replica
+            .update_high_watermark_to_end()
+            .await
+            .expect("update high watermark");
+        assert_eq!(replica.get_last_stable_offset(), 2);
+
+        let slice = replica
+            .read_partition_slice(0, FileReplica::PREFER_MAX_LEN, Isolation::ReadCommitted)
+            .await
+            .expect("read");
+        assert_eq!(slice.end.hw, 2);
+        assert_eq!(slice.file_slice.unwrap().len() as usize, batch_len);
+        assert!(slice.aborted.expect("aborted").is_empty());
+
+        let mut marker = Batch::control(7, 0, ControlRecordType::Abort, 0);
+        replica.write_batch(&mut marker).await.expect("write");
+        replica
+            .update_high_watermark_to_end()
+            .await
+            .expect("update high watermark");
+        assert_eq!(replica.get_last_stable_offset(), replica.get_hw());
+
+        let slice = replica
+            .read_partition_slice(0, FileReplica::PREFER_MAX_LEN, Isolation::ReadCommitted)
+            .await
+            .expect("read");
+        assert_eq!(slice.end.hw, replica.get_hw());
+        let aborted = slice.aborted.expect("aborted");
+        assert_eq!(aborted.len(), 1);
+        assert_eq!(aborted[0].producer_id, 7);
+        assert_eq!(aborted[0].first_offset, 2);
+        assert_eq!(aborted[0].last_offset, 4);
+
+        // index is restored after reload
+        drop(replica);
+        let replica = create_replica("test", 0, option).await;
+        let slice = replica
+            .read_partition_slice(0, FileReplica::PREFER_MAX_LEN, Isolation::ReadCommitted)
+            .await
+            .expect("read");
+        assert_eq!(slice.aborted.expect("aborted").len(), 1);
+    }
+
     #[fluvio_future::test]
     async fn test_replica_delete() {
         let mut option = base_option("test_delete");
@@ -1020,6 +1498,90 @@ mod tests {
         assert_eq!(segment.get_end_offset(), 4);
     }
 
+    #[fluvio_future::test]
+    async fn test_replica_truncate() {
+        let mut option = base_option("test_replica_truncate");
+        // enough for 2 batch (2 records per batch)
+        option.segment_max_bytes = 160;
+        option.index_max_interval_bytes = 50;
+
+        let producer = BatchProducer::builder()
+            .records(2u16)
+            .record_generator(Arc::new(|_, _| Record::new("1")))
+            .build()
+            .expect("batch");
+        let batch = |leader_epoch| {
+            let mut batch = producer.generate_batch();
+            batch.header.partition_leader_epoch = leader_epoch;
+            batch
+        };
+
+        let mut replica = create_replica("test", 0, option.clone()).await;
+        for leader_epoch in [1, 1, 2, 2, 3] {
+            replica
+                .write_batch(&mut batch(leader_epoch))
+                .await
+                .expect("write");
+        }
+        replica.update_high_watermark_to_end().await.expect("hw");
+        assert_eq!(replica.get_leo(), 10);
+        assert_eq!(replica.segment_count().await, 3);
+        assert_eq!(replica.get_leader_epoch(), 3);
+        assert_eq!(
+            replica.leader_epoch_end_offset(1),
+            Some(EpochEndOffset {
+                leader_epoch: 1,
+                end_offset: 4
+            })
+        );
+
+        // offset in closed segment after its first batch, batches before offset are kept
+        assert_eq!(replica.truncate(7).await.expect("truncate"), 6);
+        assert_eq!(replica.get_leo(), 6);
+        assert_eq!(replica.get_hw(), 6);
+        assert_eq!(replica.segment_count().await, 2);
+        assert_eq!(replica.get_leader_epoch(), 2);
+        let slice = replica
+            .read_partition_slice(4, 1000, Isolation::ReadUncommitted)
+            .await
+            .expect("read");
+        assert_eq!(slice.end.leo, 6);
+        assert!(slice.file_slice.is_some());
+
+        // offset in the middle of batch, truncated at start of the batch
+        assert_eq!(replica.truncate(5).await.expect("truncate"), 4);
+        assert_eq!(replica.get_leo(), 4);
+        assert_eq!(replica.get_hw(), 4);
+        assert_eq!(replica.segment_count().await, 2);
+        assert_eq!(replica.get_leader_epoch(), 1);
+
+        for leader_epoch in [4, 4] {
+            replica
+                .write_batch(&mut batch(leader_epoch))
+                .await
+                .expect("write");
+        }
+        assert_eq!(replica.get_leo(), 8);
+        assert_eq!(replica.get_leader_epoch(), 4);
+
+        // truncate active segment
+        assert_eq!(replica.truncate(6).await.expect("truncate"), 6);
+        assert_eq!(replica.truncate(10).await.expect("truncate"), 6);
+        let slice = replica
+            .read_partition_slice(4, 1000, Isolation::ReadUncommitted)
+            .await
+            .expect("read");
+        assert_eq!(slice.end.leo, 6);
+        assert!(slice.file_slice.is_some());
+        drop(replica);
+
+        let replica = create_replica("test", 0, option).await;
+        assert_eq!(replica.get_leo(), 6);
+        assert_eq!(replica.get_hw(), 4);
+        assert_eq!(replica.segment_count().await, 2);
+        assert_eq!(replica.get_leader_epoch(), 4);
+    }
+
     /// test replica with purging segments
     #[fluvio_future::test]
     async fn test_replica_segment_purge() {
@@ -1078,8 +1640,9 @@ mod tests {
 
         sleep(Duration::from_millis(1000)).await; // clear should purge
 
+        // replica, cleaner and scrubber
         let segments = new_replica.prev_segments.clone();
-        assert_eq!(Arc::strong_count(&segments), 3);
+        assert_eq!(Arc::strong_count(&segments), 4);
         let reader = new_replica.prev_segments.read().await;
         assert_eq!(reader.len(), 0);
         drop(reader);
@@ -1098,6 +1661,57 @@ mod tests {
         assert_eq!(Arc::strong_count(&segments), 1);
     }
 
+    #[fluvio_future::test]
+    async fn test_replica_delete_records() {
+        let mut option = base_option("test_replica_delete_records");
+        // enough for 2 batch (2 records per batch)
+        option.segment_max_bytes = 160;
+
+        let producer = BatchProducer::builder()
+            .records(2u16)
+            .record_generator(Arc::new(|_, _| Record::new("1")))
+            .build()
+            .expect("batch");
+
+        let mut replica = create_replica("test", 0, option.clone()).await;
+        for _ in 0..3 {
+            replica
+                .write_batch(&mut producer.generate_batch())
+                .await
+                .expect("write");
+        }
+        replica
+            .update_high_watermark_to_end()
+            .await
+            .expect("update");
+        assert_eq!(replica.prev_segments.read().await.len(), 1);
+        assert_eq!(replica.get_log_start_offset(), 0);
+
+        // delete inside of first segment, segment is still needed
+        assert_eq!(replica.delete_records(2).await.expect("delete"), 2);
+        assert_eq!(replica.prev_segments.read().await.len(), 1);
+        assert!(matches!(
+            replica.read_records(1, None, 1024).await,
+            Err(ErrorCode::OffsetEvicted {
+                offset: 1,
+                next_available: 2,
+            })
+        ));
+        assert!(replica.read_records(2, None, 1024).await.is_ok());
+
+        // log start offset doesn't go back
+        assert_eq!(replica.delete_records(1).await.expect("delete"), 2);
+
+        // first segment only has deleted records
+        assert_eq!(replica.delete_records(5).await.expect("delete"), 5);
+        assert_eq!(replica.prev_segments.read().await.len(), 0);
+        drop(replica);
+
+        // log start offset is restored
+        let replica = create_replica("test", 0, option).await;
+        assert_eq!(replica.get_log_start_offset(), 5);
+    }
+
     #[fluvio_future::test]
     async fn test_replica_size_enforced() {
         //given
@@ -1214,6 +1828,160 @@ mod tests {
         // reopen replica
     }
 
+    /// flip last byte of batch which ends at `end` in segment file
+    fn corrupt_batch_at(path: &std::path::Path, end: u64) {
+        flip_byte_at(path, end - 1);
+    }
+
+    fn flip_byte_at(path: &std::path::Path, pos: u64) {
+        use std::os::unix::fs::FileExt;
+
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .expect("open log");
+        let mut byte = [0; 1];
+        file.read_exact_at(&mut byte, pos).expect("read");
+        byte[0] ^= 0xff;
+        file.write_all_at(&byte, pos).expect("write");
+    }
+
+    #[fluvio_future::test]
+    async fn test_replica_crc_verify_and_repair() {
+        let option = base_option("test_replica_crc_verify_and_repair");
+        let mut replica = create_replica("test", START_OFFSET, option.clone()).await;
+        replica
+            .write_batch(&mut create_batch())
+            .await
+            .expect("write");
+        replica
+            .write_batch(&mut create_batch())
+            .await
+            .expect("write");
+        let batch_len = replica
+            .read_all_uncommitted_records(FileReplica::PREFER_MAX_LEN)
+            .await
+            .expect("read")
+            .file_slice
+            .unwrap()
+            .len()
+            / 2;
+        let healthy = replica
+            .read_batch(START_OFFSET + 2)
+            .await
+            .expect("read batch")
+            .expect("batch");
+        assert_eq!(healthy.len() as u64, batch_len);
+        drop(replica);
+
+        corrupt_batch_at(
+            &option.base_dir.join("test-0").join(TEST_SEG_NAME),
+            batch_len * 2,
+        );
+
+        // corrupt batch is kept, but reported
+        let mut replica = create_replica(
+            "test",
+            START_OFFSET,
+            ReplicaConfig {
+                verify_crc_on_load: true,
+                verify_crc_on_read: true,
+                ..option.clone()
+            },
+        )
+        .await;
+        assert_eq!(replica.get_leo(), START_OFFSET + 4);
+        let status = replica.scrub_status();
+        assert_eq!(status.corrupt_batches.len(), 1);
+        assert_eq!(status.corrupt_batches[0].base_offset, START_OFFSET + 2);
+        assert_eq!(status.corrupt_batches[0].pos as u64, batch_len);
+
+        // read stops before corrupt batch
+        let slice = replica
+            .read_all_uncommitted_records(FileReplica::PREFER_MAX_LEN)
+            .await
+            .expect("read");
+        assert_eq!(slice.file_slice.unwrap().len(), batch_len);
+        assert!(matches!(
+            replica
+                .read_records(START_OFFSET + 2, None, FileReplica::PREFER_MAX_LEN)
+                .await,
+            Err(ErrorCode::CorruptRecordBatch { offset }) if offset == START_OFFSET + 2
+        ));
+        assert!(
+            replica
+                .read_batch(START_OFFSET + 2)
+                .await
+                .expect("read batch")
+                .is_none()
+        );
+
+        // batch which is not healthy or not known to be corrupt is not written
+        let mut broken = healthy.clone();
+        broken[batch_len as usize - 1] ^= 0xff;
+        assert!(replica.repair_batch(&broken).await.is_err());
+        let other = replica
+            .read_batch(START_OFFSET)
+            .await
+            .expect("read batch")
+            .expect("batch");
+        assert!(!replica.repair_batch(&other).await.expect("repair"));
+
+        // header of corrupt batch may be corrupt too, so healthy copy is written
+        // only if it ends at batch boundary, otherwise batch stays reported
+        let segment_path = option.base_dir.join("test-0").join(TEST_SEG_NAME);
+        flip_byte_at(&segment_path, batch_len + 7);
+        flip_byte_at(&segment_path, batch_len + 8);
+        let segment_file = fs::OpenOptions::new()
+            .write(true)
+            .open(&segment_path)
+            .expect("open log");
+        segment_file.set_len(batch_len * 2 + 1).expect("extend");
+        assert!(replica.repair_batch(&healthy).await.is_err());
+        assert_eq!(replica.scrub_status().corrupt_batches.len(), 1);
+        segment_file.set_len(batch_len * 2).expect("truncate");
+
+        assert!(replica.repair_batch(&healthy).await.expect("repair"));
+        let status = replica.scrub_status();
+        assert!(status.corrupt_batches.is_empty());
+        assert_eq!(status.repaired_batches, 1);
+        let slice = replica
+            .read_all_uncommitted_records(FileReplica::PREFER_MAX_LEN)
+            .await
+            .expect("read");
+        assert_eq!(slice.file_slice.unwrap().len(), batch_len * 2);
+    }
+
+    #[fluvio_future::test]
+    async fn test_replica_scrub() {
+        let option = rollover_option("test_replica_scrub");
+        let mut replica = create_replica("test", START_OFFSET, option.clone()).await;
+        for _ in 0..3 {
+            replica
+                .write_batch(&mut create_batch())
+                .await
+                .expect("write");
+        }
+        assert_eq!(replica.prev_segments.read().await.len(), 2);
+
+        replica.scrub().await;
+        let status = replica.scrub_status();
+        assert!(status.corrupt_batches.is_empty());
+        assert_eq!(status.completed_scrubs, 1);
+        assert!(status.scrubbed_bytes > 0);
+
+        let segment_path = option.base_dir.join("test-0").join(TEST_SE2_NAME);
+        let segment_len = metadata(&segment_path).expect("metadata").len();
+        corrupt_batch_at(&segment_path, segment_len);
+
+        replica.scrub().await;
+        let status = replica.scrub_status();
+        assert_eq!(status.completed_scrubs, 2);
+        assert_eq!(status.corrupt_batches.len(), 1);
+        assert_eq!(status.corrupt_batches[0].base_offset, START_OFFSET + 2);
+    }
+
     #[fluvio_future::test]
     async fn test_replica_batch_exceeded_segment_size() {
         let mut option = base_option("test_batch_limit");
@@ -1247,4 +2015,53 @@ mod tests {
             }
         ));
     }
+
+    /// batch of 2 records, second record is 500ms after first one
+    fn timestamped_batch(first_timestamp: i64) -> Batch {
+        let mut batch = Batch::default();
+        for delta in [0, 500] {
+            let mut record = Record::new("v");
+            record.preamble.set_timestamp_delta(delta);
+            batch.add_record(record);
+        }
+        batch.header.first_timestamp = first_timestamp;
+        batch.header.max_time_stamp = first_timestamp + 500;
+        batch
+    }
+
+    #[fluvio_future::test]
+    async fn test_replica_find_offset_by_timestamp() {
+        let mut option = rollover_option("test_find_offset_by_timestamp");
+        option.segment_max_bytes = 200;
+
+        let mut replica = create_replica("test", 0, option.clone()).await;
+        // offsets 0..10, timestamps 1000,1500,2000,2500,...
+        for i in 1..=5 {
+            replica
+                .write_batch(&mut timestamped_batch(i * 1000))
+                .await
+                .expect("write");
+        }
+        assert_eq!(replica.get_leo(), 10);
+        assert!(replica.prev_segments.read().await.len() > 0);
+
+        async fn assert_lookup(replica: &FileReplica) {
+            let lookup = |timestamp| replica.find_offset_by_timestamp(timestamp);
+            assert_eq!(lookup(0).await.expect("lookup"), Some(0));
+            assert_eq!(lookup(1000).await.expect("lookup"), Some(0));
+            assert_eq!(lookup(1300).await.expect("lookup"), Some(1));
+            assert_eq!(lookup(1600).await.expect("lookup"), Some(2));
+            assert_eq!(lookup(3500).await.expect("lookup"), Some(5));
+            assert_eq!(lookup(4500).await.expect("lookup"), Some(7));
+            assert_eq!(lookup(5001).await.expect("lookup"), Some(9));
+            assert_eq!(lookup(5600).await.expect("lookup"), None);
+        }
+
+        assert_lookup(&replica).await;
+        drop(replica);
+
+        // reload replica, closed segments use read only time index
+        let replica = create_replica("test", 0, option).await;
+        assert_lookup(&replica).await;
+    }
 }
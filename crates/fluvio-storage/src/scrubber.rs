@@ -0,0 +1,308 @@
+use std::collections::BTreeMap;
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use blocking::unblock;
+use tracing::{debug, error, info, instrument};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::record::{Offset, BATCH_FILE_HEADER_SIZE};
+use fluvio_types::event::StickyEvent;
+
+use crate::batch::{batch_base_offset, batch_last_offset, batch_len_at, CorruptBatch};
+use crate::batch_header::BatchHeaderStream;
+use crate::config::SharedReplicaConfig;
+use crate::records::FileRecords;
+use crate::segments::SharedSegments;
+
+/// Corrupt batches of replica found when segments are loaded, read or scrubbed.
+/// Batches are removed once they are repaired or their segment is gone.
+#[derive(Debug, Default)]
+pub(crate) struct CorruptionReport {
+    batches: Mutex<BTreeMap<Offset, CorruptBatch>>,
+    scrubbed_bytes: AtomicU64,
+    completed_scrubs: AtomicU64,
+    repaired_batches: AtomicU64,
+}
+
+/// Integrity of replica as seen by crc checks
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScrubStatus {
+    /// corrupt batches not repaired yet, in offset order
+    pub corrupt_batches: Vec<CorruptBatch>,
+    /// bytes verified by scrubber since replica was loaded
+    pub scrubbed_bytes: u64,
+    /// scrubs which went through all closed segments
+    pub completed_scrubs: u64,
+    /// batches overwritten with healthy copy from other replica
+    pub repaired_batches: u64,
+}
+
+impl CorruptionReport {
+    pub(crate) fn add(&self, batch: CorruptBatch) {
+        self.batches
+            .lock()
+            .expect("corruption report")
+            .insert(batch.base_offset, batch);
+    }
+
+    pub(crate) fn get(&self, base_offset: Offset) -> Option<CorruptBatch> {
+        self.batches
+            .lock()
+            .expect("corruption report")
+            .get(&base_offset)
+            .cloned()
+    }
+
+    pub(crate) fn remove(&self, base_offset: Offset) {
+        self.batches
+            .lock()
+            .expect("corruption report")
+            .remove(&base_offset);
+    }
+
+    pub(crate) fn mark_repaired(&self, base_offset: Offset) {
+        self.remove(base_offset);
+        self.repaired_batches.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// replace batches in segment with result of its scan
+    fn replace_segment(&self, base_offset: Offset, end_offset: Offset, found: Vec<CorruptBatch>) {
+        let mut batches = self.batches.lock().expect("corruption report");
+        batches.retain(|offset, _| *offset < base_offset || *offset >= end_offset);
+        batches.extend(found.into_iter().map(|batch| (batch.base_offset, batch)));
+    }
+
+    /// keep only batches matching filter, used to forget batches of removed segments
+    fn retain(&self, keep: impl Fn(Offset) -> bool) {
+        self.batches
+            .lock()
+            .expect("corruption report")
+            .retain(|offset, _| keep(*offset));
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        self.batches.lock().expect("corruption report").len()
+    }
+
+    pub(crate) fn status(&self) -> ScrubStatus {
+        ScrubStatus {
+            corrupt_batches: self
+                .batches
+                .lock()
+                .expect("corruption report")
+                .values()
+                .cloned()
+                .collect(),
+            scrubbed_bytes: self.scrubbed_bytes.load(Ordering::SeqCst),
+            completed_scrubs: self.completed_scrubs.load(Ordering::SeqCst),
+            repaired_batches: self.repaired_batches.load(Ordering::SeqCst),
+        }
+    }
+}
+
+/// Replica scrubber. This is a low priority background task which periodically reads all closed
+/// segments and checks crc of their batches, so silent disk corruption is found before records
+/// are consumed. Reads are throttled by max scrub bytes per second.
+/// Active segment is checked when replica is loaded and when it is read.
+#[derive(Debug)]
+pub(crate) struct Scrubber {
+    replica_config: Arc<SharedReplicaConfig>,
+    segments: Arc<SharedSegments>,
+    report: Arc<CorruptionReport>,
+    end_event: Arc<StickyEvent>,
+}
+
+impl Scrubber {
+    pub(crate) fn start_new(
+        replica_config: Arc<SharedReplicaConfig>,
+        segments: Arc<SharedSegments>,
+        report: Arc<CorruptionReport>,
+    ) -> Arc<Self> {
+        let scrubber = Arc::new(Self {
+            replica_config,
+            segments,
+            report,
+            end_event: StickyEvent::shared(),
+        });
+
+        // scrub can still be requested on demand when background scrubbing is disabled
+        let interval_secs = scrubber.replica_config.scrub_interval_seconds.get();
+        if interval_secs > 0 {
+            let scrubber_ref = scrubber.clone();
+            spawn(async move {
+                scrubber_ref
+                    .scrub_loop(Duration::from_secs(interval_secs as u64))
+                    .await;
+            });
+        }
+        scrubber
+    }
+
+    pub(crate) fn shutdown(&self) {
+        self.end_event.notify();
+    }
+
+    #[instrument(skip(self))]
+    async fn scrub_loop(&self, interval: Duration) {
+        use tokio::select;
+
+        loop {
+            if self.end_event.is_set() {
+                break;
+            }
+
+            select! {
+                _ = self.end_event.listen() => {
+                    break;
+                },
+                _ = sleep(interval) => {
+                    self.scrub().await;
+                }
+            }
+        }
+
+        info!("scrubber end");
+    }
+
+    /// check all closed segments
+    #[instrument(skip(self))]
+    pub(crate) async fn scrub(&self) {
+        let segments: Vec<(Offset, Offset, PathBuf)> = self
+            .segments
+            .read()
+            .await
+            .iter()
+            .map(|segment| {
+                (
+                    segment.get_base_offset(),
+                    segment.get_end_offset(),
+                    segment.get_msg_log().get_path().to_owned(),
+                )
+            })
+            .collect();
+
+        let start = Instant::now();
+        let mut scrubbed: u64 = 0;
+        for (base_offset, end_offset, path) in &segments {
+            if self.end_event.is_set() {
+                return;
+            }
+            match self.scrub_segment(path, start, &mut scrubbed).await {
+                Ok(found) => {
+                    if !found.is_empty() {
+                        error!(
+                            base_offset,
+                            corrupt = found.len(),
+                            "scrubber found corrupt batches"
+                        );
+                    }
+                    self.report
+                        .replace_segment(*base_offset, *end_offset, found);
+                }
+                // segment may have been removed by cleaner in meantime
+                Err(err) => error!(base_offset, %err, "failed to scrub segment"),
+            }
+        }
+
+        let active_start = segments.last().map_or(Offset::MIN, |(_, end, _)| *end);
+        self.report.retain(|offset| {
+            offset >= active_start
+                || segments.iter().any(|(base_offset, end_offset, _)| {
+                    *base_offset <= offset && offset < *end_offset
+                })
+        });
+        self.report.completed_scrubs.fetch_add(1, Ordering::SeqCst);
+        debug!(
+            segments = segments.len(),
+            bytes = scrubbed,
+            time_ms = start.elapsed().as_millis(),
+            "scrub completed"
+        );
+    }
+
+    async fn scrub_segment(
+        &self,
+        path: &Path,
+        start: Instant,
+        scrubbed: &mut u64,
+    ) -> Result<Vec<CorruptBatch>> {
+        let mut stream = BatchHeaderStream::open(path).await?;
+        stream.set_verify_crc(true);
+        let mut found = vec![];
+        while let Some(batch_pos) = stream.try_next().await? {
+            if let Some(corrupt) = batch_pos.corrupt() {
+                found.push(corrupt.clone());
+            }
+            let len = (stream.get_pos() - batch_pos.get_pos()) as u64;
+            *scrubbed += len;
+            self.report.scrubbed_bytes.fetch_add(len, Ordering::SeqCst);
+            self.throttle(start, *scrubbed).await;
+        }
+        Ok(found)
+    }
+
+    /// sleep until read bytes are within max rate
+    async fn throttle(&self, start: Instant, scrubbed: u64) {
+        let max_bytes_per_sec = self.replica_config.scrub_max_bytes_per_sec.get();
+        if max_bytes_per_sec == 0 {
+            return;
+        }
+        let budget = Duration::from_secs_f64(scrubbed as f64 / max_bytes_per_sec as f64);
+        let elapsed = start.elapsed();
+        if budget > elapsed {
+            sleep(budget - elapsed).await;
+        }
+    }
+}
+
+/// overwrite corrupt batch in segment file with healthy copy.
+/// Offset or length in header at the position may be corrupt as well, in which case healthy copy
+/// is written only if it ends at batch boundary: end of file or start of batch following it.
+/// Fails otherwise, e.g. when segment was compacted since it was scanned.
+pub(crate) async fn overwrite_batch(
+    path: PathBuf,
+    corrupt: CorruptBatch,
+    batch: Vec<u8>,
+) -> Result<()> {
+    unblock(move || {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)?;
+        let mut header = [0; BATCH_FILE_HEADER_SIZE];
+        file.read_exact_at(&mut header, corrupt.pos as u64)?;
+        let header_matches = batch_base_offset(&header) == corrupt.base_offset
+            && batch_len_at(&header) == Some(batch.len());
+        if !header_matches && !ends_at_batch_boundary(&file, corrupt.pos as u64, &batch)? {
+            anyhow::bail!(
+                "healthy copy of batch doesn't fit at position {}",
+                corrupt.pos
+            );
+        }
+        file.write_all_at(&batch, corrupt.pos as u64)?;
+        file.sync_data()?;
+        Ok(())
+    })
+    .await
+}
+
+/// true if batch written at position ends where file ends or next batch starts
+fn ends_at_batch_boundary(file: &std::fs::File, pos: u64, batch: &[u8]) -> Result<bool> {
+    let end = pos + batch.len() as u64;
+    let file_len = file.metadata()?.len();
+    if end == file_len {
+        return Ok(true);
+    }
+    if end + BATCH_FILE_HEADER_SIZE as u64 > file_len {
+        return Ok(false);
+    }
+    let mut next = [0; BATCH_FILE_HEADER_SIZE];
+    file.read_exact_at(&mut next, end)?;
+    Ok(batch_base_offset(&next) == batch_last_offset(batch) + 1)
+}
@@ -9,41 +9,44 @@ use anyhow::Result;
 
 use fluvio_future::fs::remove_file;
 use fluvio_future::file_slice::AsyncFileSlice;
-use fluvio_protocol::record::{Batch, BatchRecords};
+use fluvio_protocol::record::{Batch, BatchHeader, BatchRecords, RawRecords};
 use fluvio_protocol::record::{Offset, Size, Size64};
 use fluvio_protocol::link::ErrorCode;
 
 use crate::batch_header::{BatchHeaderStream, FileEmptyRecords};
-use crate::mut_index::MutLogIndex;
-use crate::index::LogIndex;
-use crate::index::Index;
+use crate::mut_index::{MutLogIndex, MutLogTimeIndex};
+use crate::index::{LogIndex, LogTimeIndex};
+use crate::index::{Index, TimeIndex};
 use crate::records::FileRecords;
 use crate::mut_records::MutFileRecords;
 use crate::records::FileRecordsSlice;
 use crate::config::SharedReplicaConfig;
 use crate::StorageError;
-use crate::batch::FileBatchStream;
+use crate::batch::{CorruptBatch, FileBatchStream};
 use crate::index::OffsetPosition;
 use crate::validator::LogValidationError;
 
-pub type MutableSegment = Segment<MutLogIndex, MutFileRecords>;
-pub type ReadSegment = Segment<LogIndex, FileRecordsSlice>;
+pub type MutableSegment = Segment<MutLogIndex, MutFileRecords, MutLogTimeIndex>;
+pub type ReadSegment = Segment<LogIndex, FileRecordsSlice, LogTimeIndex>;
 
 pub(crate) struct BatchPosition {
     batch: Batch<FileEmptyRecords>,
     pos: Size,
 }
 
-/// Segment contains both message log and index
-pub struct Segment<I, L> {
+/// Segment contains message log, offset index and time index
+pub struct Segment<I, L, T> {
     option: Arc<SharedReplicaConfig>,
     msg_log: L,
     index: I,
+    time_index: T,
     base_offset: Offset,
     end_offset: Offset,
+    /// batches with crc mismatch found when segment was loaded
+    corrupt_batches: Vec<CorruptBatch>,
 }
 
-impl<I, L> fmt::Debug for Segment<I, L> {
+impl<I, L, T> fmt::Debug for Segment<I, L, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
@@ -54,7 +57,7 @@ impl<I, L> fmt::Debug for Segment<I, L> {
     }
 }
 
-impl<I, L> Segment<I, L> {
+impl<I, L, T> Segment<I, L, T> {
     /// end offset, this always starts as baseoffset which indicates empty records
     pub fn get_end_offset(&self) -> Offset {
         self.end_offset
@@ -69,13 +72,18 @@ impl<I, L> Segment<I, L> {
     pub fn get_base_offset(&self) -> Offset {
         self.base_offset
     }
+
+    pub(crate) fn corrupt_batches(&self) -> &[CorruptBatch] {
+        &self.corrupt_batches
+    }
 }
 
-impl<I, L> Segment<I, L>
+impl<I, L, T> Segment<I, L, T>
 where
     I: Index,
     I: Deref<Target = [(Size, Size)]>,
     L: FileRecords,
+    T: TimeIndex,
 {
     #[allow(dead_code)]
     pub fn get_index(&self) -> &I {
@@ -226,12 +234,67 @@ where
         Ok(None)
     }
 
+    /// find offset of first record which has timestamp at or after given timestamp
+    /// return None if there is no such record in the segment
+    #[instrument(skip(self))]
+    pub(crate) async fn find_offset_by_timestamp(&self, timestamp: i64) -> Result<Option<Offset>> {
+        // all batches up to time index entry are older, start from there
+        let position = match self.time_index.find_timestamp(timestamp) {
+            Some((_, relative_offset)) => self
+                .find_offset_position(self.base_offset + relative_offset as Offset)
+                .await?
+                .map(|batch_pos| batch_pos.pos)
+                .unwrap_or(0),
+            None => 0,
+        };
+        debug!(position, "scanning batches for timestamp");
+
+        let mut header_stream = self.open_batch_header_stream(position).await?;
+        while let Some(batch_pos) = header_stream.try_next().await? {
+            let pos = batch_pos.get_pos();
+            let batch = batch_pos.inner();
+            if batch.get_base_offset() >= self.end_offset {
+                break;
+            }
+            if batch_max_timestamp(&batch.header) >= timestamp {
+                return self
+                    .find_record_by_timestamp(pos, timestamp)
+                    .await
+                    .map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// find offset of first record in the batch at position which has timestamp at or after given timestamp
+    async fn find_record_by_timestamp(&self, pos: Size, timestamp: i64) -> Result<Offset> {
+        let mut stream: FileBatchStream<RawRecords> =
+            FileBatchStream::open(self.msg_log.get_path()).await?;
+        stream.set_absolute(pos).await?;
+        let batch = stream
+            .try_next()
+            .await?
+            .ok_or(StorageError::Other(format!("no batch at position: {pos}")))?
+            .inner();
+
+        let base_timestamp = batch.get_base_timestamp();
+        if base_timestamp >= 0 {
+            for (index, record) in batch.memory_records()?.iter().enumerate() {
+                if base_timestamp + record.timestamp_delta() >= timestamp {
+                    return Ok(batch.get_base_offset() + index as Offset);
+                }
+            }
+        }
+        Ok(batch.get_base_offset())
+    }
+
     pub(crate) fn occupied_memory(&self) -> Size64 {
         self.index.len() + self.msg_log.len()
     }
 }
 
-impl Segment<LogIndex, FileRecordsSlice> {
+impl Segment<LogIndex, FileRecordsSlice, LogTimeIndex> {
     /// open read only segments if base and end offset are known
     pub async fn open_for_read(
         base_offset: Offset,
@@ -243,13 +306,16 @@ impl Segment<LogIndex, FileRecordsSlice> {
         let base_offset = msg_log.get_base_offset();
         debug!(base_offset, end_offset, "offset from msg log");
         let index = LogIndex::open_from_offset(base_offset, option.clone()).await?;
+        let time_index = LogTimeIndex::open_from_offset(base_offset, option.clone()).await?;
 
         Ok(Segment {
             msg_log,
             index,
+            time_index,
             option,
             base_offset,
             end_offset,
+            corrupt_batches: vec![],
         })
     }
 
@@ -261,22 +327,32 @@ impl Segment<LogIndex, FileRecordsSlice> {
     ) -> Result<Self> {
         let msg_log = FileRecordsSlice::open(base_offset, option.clone()).await?;
         let index = LogIndex::open_from_offset(base_offset, option.clone()).await?;
+        let time_index = LogTimeIndex::open_from_offset(base_offset, option.clone()).await?;
         let base_offset = msg_log.get_base_offset();
-        match msg_log.validate(&index).await {
+        match msg_log.validate(&index, option.verify_crc_on_load).await {
             Ok(val) => {
                 // check if validation is successful
                 if let Some(err) = val.error {
                     error!(err = ?err, "segment validation failed");
                     return Err(err.into());
                 }
+                if !val.corrupt_batches.is_empty() {
+                    error!(
+                        base_offset,
+                        corrupt = val.corrupt_batches.len(),
+                        "segment has corrupt batches"
+                    );
+                }
 
                 info!(end_offset = val.leo(), base_offset = val.base_offset, time_ms = %val.duration.as_millis(), "segment validated");
                 Ok(Segment {
                     msg_log,
                     index,
+                    time_index,
                     option,
                     base_offset,
                     end_offset: val.leo(),
+                    corrupt_batches: val.corrupt_batches,
                 })
             }
             Err(err) => {
@@ -295,12 +371,16 @@ impl Segment<LogIndex, FileRecordsSlice> {
         let index_file_path = self.index.clean();
         info!(index_path = %index_file_path.display(),"removing index file");
         remove_file(&index_file_path).await?;
+        if let Some(time_index_file_path) = self.time_index.clean() {
+            info!(time_index_path = %time_index_file_path.display(),"removing time index file");
+            remove_file(&time_index_file_path).await?;
+        }
         Ok(())
     }
 }
 
 /// Implementation for Active segment
-impl Segment<MutLogIndex, MutFileRecords> {
+impl Segment<MutLogIndex, MutFileRecords, MutLogTimeIndex> {
     // create segment on base directory
 
     #[instrument(skip(option))]
@@ -312,13 +392,16 @@ impl Segment<MutLogIndex, MutFileRecords> {
         let msg_log = MutFileRecords::create(base_offset, option.clone()).await?;
 
         let index = MutLogIndex::create(base_offset, option.clone()).await?;
+        let time_index = MutLogTimeIndex::create(base_offset, option.clone()).await?;
 
         Ok(MutableSegment {
             option: option.to_owned(),
             msg_log,
             index,
+            time_index,
             base_offset,
             end_offset: base_offset,
+            corrupt_batches: vec![],
         })
     }
 
@@ -334,14 +417,17 @@ impl Segment<MutLogIndex, MutFileRecords> {
         let msg_log = MutFileRecords::create(base_offset, option.clone()).await?;
         let base_offset = msg_log.get_base_offset();
         let index = MutLogIndex::open(base_offset, option.clone()).await?;
+        let time_index = MutLogTimeIndex::open(base_offset, option.clone()).await?;
 
         let base_offset = msg_log.get_base_offset();
         Ok(MutableSegment {
             option,
             msg_log,
             index,
+            time_index,
             base_offset,
             end_offset: base_offset,
+            corrupt_batches: vec![],
         })
     }
 
@@ -352,7 +438,10 @@ impl Segment<MutLogIndex, MutFileRecords> {
 
     /// validate and repair if necessary
     pub async fn validate_and_repair(&mut self) -> Result<Offset> {
-        let validation = self.msg_log.validate(&self.index).await?;
+        let validation = self
+            .msg_log
+            .validate(&self.index, self.option.verify_crc_on_load)
+            .await?;
         let leo = validation.leo();
         // check for error and see if it's recoverable
         if let Some(err) = validation.error {
@@ -376,10 +465,45 @@ impl Segment<MutLogIndex, MutFileRecords> {
                 }
             }
         }
+        if !validation.corrupt_batches.is_empty() {
+            // complete batches with bad crc are kept, they can be repaired from other replica
+            error!(
+                corrupt = validation.corrupt_batches.len(),
+                "active segment has corrupt batches"
+            );
+        }
+        self.corrupt_batches = validation.corrupt_batches;
         self.end_offset = leo;
+        self.restore_max_timestamp().await?;
         Ok(self.end_offset)
     }
 
+    /// batches written after last time index entry are not reflected in the index, scan them
+    async fn restore_max_timestamp(&mut self) -> Result<()> {
+        let position = match self.time_index.find_timestamp(i64::MAX) {
+            Some((_, relative_offset)) => self
+                .find_offset_position(self.base_offset + relative_offset as Offset)
+                .await?
+                .map(|batch_pos| batch_pos.pos)
+                .unwrap_or(0),
+            None => 0,
+        };
+        let mut header_stream = self.open_batch_header_stream(position).await?;
+        while let Some(batch_pos) = header_stream.try_next().await? {
+            let batch = batch_pos.inner();
+            if batch.get_base_offset() >= self.end_offset {
+                break;
+            }
+            self.time_index
+                .update_max_timestamp(batch_max_timestamp(&batch.header));
+        }
+        debug!(
+            max_timestamp = self.time_index.max_timestamp(),
+            "restored max timestamp"
+        );
+        Ok(())
+    }
+
     // shrink index
     #[cfg(test)]
     async fn shrink_index(&mut self) -> Result<(), IoError> {
@@ -388,7 +512,8 @@ impl Segment<MutLogIndex, MutFileRecords> {
 
     // close this segment as writeable
     pub async fn close(&mut self) -> Result<(), IoError> {
-        self.index.shrink().await
+        self.index.shrink().await?;
+        self.time_index.shrink().await
     }
 
     /// convert to immutable segment
@@ -419,10 +544,26 @@ impl Segment<MutLogIndex, MutFileRecords> {
 
         batch.set_base_offset(self.end_offset);
 
+        self.append_batch_at_base_offset(batch).await
+    }
+
+    /// Append batch without changing its base offset, which must not be less than current end offset.
+    /// This is used when segment is rewritten and offsets must be preserved.
+    pub(crate) async fn append_batch_at_base_offset<R: BatchRecords>(
+        &mut self,
+        batch: &Batch<R>,
+    ) -> Result<bool> {
+        if batch.get_base_offset() < self.end_offset {
+            return Err(LogValidationError::InvalidBaseOffsetMinimum {
+                invalid_batch_offset: batch.get_base_offset(),
+            }
+            .into());
+        }
+
         let next_end_offset = batch.get_last_offset();
 
         // relative offset of the batch to segment
-        let relative_offset_in_segment = (self.end_offset - self.base_offset) as i32;
+        let relative_offset_in_segment = (batch.get_base_offset() - self.base_offset) as i32;
         let start_file_pos = self.msg_log.get_pos();
         debug!(
             base_offset = batch.get_base_offset(),
@@ -446,6 +587,13 @@ impl Segment<MutLogIndex, MutFileRecords> {
                     batch_len as u32,
                 )
                 .await?;
+            self.time_index
+                .write_time_index(
+                    batch_max_timestamp(&batch.header),
+                    relative_offset_in_segment as u32,
+                    batch_len as u32,
+                )
+                .await?;
             self.end_offset = next_end_offset + 1;
             debug!(end_offset = self.end_offset, "updated leo");
             Ok(true)
@@ -460,6 +608,11 @@ impl Segment<MutLogIndex, MutFileRecords> {
     }
 }
 
+/// max timestamp of the batch, producers which don't set max timestamp only have first timestamp
+fn batch_max_timestamp(header: &BatchHeader) -> i64 {
+    header.max_time_stamp.max(header.first_timestamp)
+}
+
 #[cfg(test)]
 mod tests {
 
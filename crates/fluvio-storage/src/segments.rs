@@ -1,11 +1,10 @@
 use std::collections::BTreeMap;
-use std::ops::Bound::Included;
 use std::ffi::OsStr;
 use std::sync::Arc;
 use std::sync::atomic::AtomicI64;
 use std::time::Duration;
 
-use async_lock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use async_lock::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tracing::{debug, trace, error, instrument, info};
 use anyhow::Result;
 
@@ -16,6 +15,7 @@ use fluvio_future::file_slice::AsyncFileSlice;
 
 use crate::config::SharedReplicaConfig;
 use crate::segment::ReadSegment;
+use crate::tiered::{RemoteSegment, RemoteTier};
 use crate::util::log_path_get_offset;
 
 const MEM_ORDER: std::sync::atomic::Ordering = std::sync::atomic::Ordering::SeqCst;
@@ -26,14 +26,27 @@ const MEM_ORDER: std::sync::atomic::Ordering = std::sync::atomic::Ordering::SeqC
 pub(crate) struct SharedSegments {
     inner: Arc<RwLock<SegmentList>>,
     min_offset: AtomicI64,
+    /// records before this offset are deleted by request
+    log_start_offset: AtomicI64,
+    tier: Option<RemoteTier>,
+    /// only one remote segment is fetched at time
+    fetch_lock: Mutex<()>,
 }
 
 impl SharedSegments {
+    #[cfg(test)]
     pub(crate) fn from(list: SegmentList) -> Arc<Self> {
+        Self::with_tier(list, None)
+    }
+
+    pub(crate) fn with_tier(list: SegmentList, tier: Option<RemoteTier>) -> Arc<Self> {
         let min = list.min_offset;
         Arc::new(Self {
             inner: Arc::new(RwLock::new(list)),
             min_offset: AtomicI64::new(min),
+            log_start_offset: AtomicI64::new(0),
+            tier,
+            fetch_lock: Mutex::new(()),
         })
     }
 
@@ -87,7 +100,19 @@ impl SharedSegments {
             }
         }
 
-        let shared_segments = SharedSegments::from(segments);
+        let tier = RemoteTier::new(option.clone());
+        if let Some(tier) = &tier {
+            for remote in tier.list_segments().await? {
+                // remote segment can't overlap active segment
+                if last_offset.is_some_and(|last_offset| remote.base_offset >= last_offset) {
+                    debug!(base_offset = remote.base_offset, "ignoring remote segment");
+                    continue;
+                }
+                segments.add_remote(remote);
+            }
+        }
+
+        let shared_segments = SharedSegments::with_tier(segments, tier);
 
         Ok((shared_segments, last_offset))
     }
@@ -104,12 +129,41 @@ impl SharedSegments {
         self.min_offset.load(MEM_ORDER)
     }
 
+    pub(crate) fn log_start_offset(&self) -> Offset {
+        self.log_start_offset.load(MEM_ORDER)
+    }
+
+    /// move log start offset forward, it never goes back
+    pub(crate) fn set_log_start_offset(&self, offset: Offset) {
+        self.log_start_offset.fetch_max(offset, MEM_ORDER);
+    }
+
     pub async fn add_segment(&self, segment: ReadSegment) {
         let mut writer = self.write().await;
         let min_offset = writer.add_segment(segment);
         self.min_offset.store(min_offset, MEM_ORDER);
     }
 
+    /// take segment out of the list without removing its local files,
+    /// remote copy is deleted since files are about to be rewritten
+    pub(crate) async fn detach_segment(&self, base_offset: Offset) {
+        let mut write = self.write().await;
+        if let Some((_, remote, min_offset)) = write.remove_segment(&base_offset) {
+            drop(write);
+            self.min_offset.store(min_offset, MEM_ORDER);
+            self.delete_remote(remote).await;
+        }
+    }
+
+    /// delete remote copy of segment which is no longer valid
+    pub(crate) async fn delete_remote(&self, remote: Option<RemoteSegment>) {
+        if let (Some(remote), Some(tier)) = (remote, &self.tier) {
+            if let Err(err) = tier.delete(&remote).await {
+                error!("failed to remove remote segment: {:#?}", err);
+            }
+        }
+    }
+
     #[instrument(skip(self))]
     pub(crate) async fn remove_segments(&self, base_offsets: &[Offset]) {
         for offset in base_offsets {
@@ -118,13 +172,21 @@ impl SharedSegments {
         }
     }
 
-    /// find slice in the segments
+    /// true if closed segments are offloaded to remote store
+    pub(crate) fn is_tiered(&self) -> bool {
+        self.tier.is_some()
+    }
+
+    /// find slice in the segments, segment evicted to remote store is fetched back
     /// if not found, return OutOfRange error
     pub async fn find_slice(
         &self,
         start_offset: Offset,
         max_offset: Option<Offset>,
     ) -> Result<Option<AsyncFileSlice>, ErrorCode> {
+        self.fetch_remote(start_offset)
+            .await
+            .map_err(|err| ErrorCode::Other(format!("failed to fetch remote segment: {err:#?}")))?;
         let reader = self.read().await;
         if let Some((_offset, segment)) = reader.find_segment(start_offset) {
             if let Some(slice) = segment.records_slice(start_offset, max_offset).await? {
@@ -139,15 +201,100 @@ impl SharedSegments {
         }
     }
 
+    /// find offset of first record which has timestamp at or after given timestamp.
+    /// Evicted segments are fetched back while searching.
+    pub(crate) async fn find_offset_by_timestamp(&self, timestamp: i64) -> Result<Option<Offset>> {
+        let base_offsets = self.read().await.base_offsets();
+        for base_offset in base_offsets {
+            self.fetch_remote(base_offset).await?;
+            let reader = self.read().await;
+            if let Some((_, segment)) = reader.find_segment(base_offset) {
+                if let Some(offset) = segment.find_offset_by_timestamp(timestamp).await? {
+                    return Ok(Some(offset));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// download segment containing offset if it is only in remote store
+    async fn fetch_remote(&self, offset: Offset) -> Result<()> {
+        let Some(tier) = &self.tier else {
+            return Ok(());
+        };
+        if self.read().await.find_remote_only(offset).is_none() {
+            return Ok(());
+        }
+        let _guard = self.fetch_lock.lock().await;
+        // other reader may have fetched it while waiting for lock
+        let Some(remote) = self.read().await.find_remote_only(offset).cloned() else {
+            return Ok(());
+        };
+        let segment = tier.download(&remote).await?;
+        self.add_segment(segment).await;
+        Ok(())
+    }
+
+    /// upload closed segments which are not in remote store yet
+    #[instrument(skip(self))]
+    pub(crate) async fn offload(&self) -> Result<()> {
+        let Some(tier) = &self.tier else {
+            return Ok(());
+        };
+        let pending = self.read().await.find_not_uploaded();
+        for (base_offset, end_offset) in pending {
+            let remote = tier.upload(base_offset, end_offset).await?;
+            self.write().await.add_remote(remote);
+        }
+        Ok(())
+    }
+
+    /// remove local copies of segments which are in remote store
+    #[instrument(skip(self))]
+    pub(crate) async fn evict_segments(&self, base_offsets: &[Offset]) {
+        for offset in base_offsets {
+            let mut write = self.write().await;
+            if !write.remote.contains_key(offset) {
+                continue;
+            }
+            if let Some(segment) = write.segments.remove(offset) {
+                drop(write);
+                info!(offset, "evicting local copy of segment");
+                if let Err(err) = segment.remove().await {
+                    error!("failed to evict segment: {:#?}", err);
+                }
+            }
+        }
+    }
+
+    /// delete all segments from remote store
+    pub(crate) async fn remove_remote(&self) -> Result<()> {
+        let Some(tier) = &self.tier else {
+            return Ok(());
+        };
+        let remote: Vec<RemoteSegment> = self.read().await.remote.values().cloned().collect();
+        for segment in remote {
+            tier.delete(&segment).await?;
+        }
+        Ok(())
+    }
+
     #[instrument(skip(self))]
     async fn remove_segment(&self, base_offset: &Offset) {
         let mut write = self.write().await;
 
-        if let Some((old_segment, min_offset)) = write.remove_segment(base_offset) {
+        if let Some((old_segment, remote, min_offset)) = write.remove_segment(base_offset) {
             drop(write);
             self.min_offset.store(min_offset, MEM_ORDER);
-            if let Err(err) = old_segment.remove().await {
-                error!("failed to remove segment: {:#?}", err);
+            if let Some(old_segment) = old_segment {
+                if let Err(err) = old_segment.remove().await {
+                    error!("failed to remove segment: {:#?}", err);
+                }
+            }
+            if let (Some(remote), Some(tier)) = (remote, &self.tier) {
+                if let Err(err) = tier.delete(&remote).await {
+                    error!("failed to remove remote segment: {:#?}", err);
+                }
             }
         }
     }
@@ -156,6 +303,7 @@ impl SharedSegments {
 #[derive(Debug)]
 pub struct SegmentList {
     segments: BTreeMap<Offset, ReadSegment>, // max base offset of all segments
+    remote: BTreeMap<Offset, RemoteSegment>, // segments uploaded to remote store, may have local copy
     min_offset: Offset,
     max_offset: Offset,
 }
@@ -164,11 +312,17 @@ impl SegmentList {
     pub fn new() -> Self {
         SegmentList {
             segments: BTreeMap::new(),
+            remote: BTreeMap::new(),
             max_offset: 0,
             min_offset: -1,
         }
     }
 
+    /// end offset of last segment, 0 if there are no segments
+    pub(crate) fn end_offset(&self) -> Offset {
+        self.max_offset
+    }
+
     // load segments
     pub fn len(&self) -> usize {
         self.segments.len()
@@ -193,12 +347,29 @@ impl SegmentList {
         self.min_offset
     }
 
+    pub(crate) fn add_remote(&mut self, segment: RemoteSegment) -> Offset {
+        debug!(
+            base_offset = segment.base_offset,
+            end_offset = segment.end_offset,
+            "inserting remote"
+        );
+        self.remote.insert(segment.base_offset, segment);
+        self.update_min_max();
+        self.min_offset
+    }
+
     fn update_min_max(&mut self) {
         let mut max_offset = 0;
         let mut min_offset = -1;
-        self.segments.values().for_each(|segment| {
-            let base_offset = segment.get_base_offset();
-            let end_offset = segment.get_end_offset();
+        let local = self
+            .segments
+            .values()
+            .map(|segment| (segment.get_base_offset(), segment.get_end_offset()));
+        let remote = self
+            .remote
+            .values()
+            .map(|segment| (segment.base_offset, segment.end_offset));
+        local.chain(remote).for_each(|(base_offset, end_offset)| {
             if end_offset > max_offset {
                 max_offset = end_offset;
             }
@@ -210,14 +381,33 @@ impl SegmentList {
         self.min_offset = min_offset;
     }
 
-    /// remove segment and return min offset
-    fn remove_segment(&mut self, offset: &Offset) -> Option<(ReadSegment, Offset)> {
-        if let Some(segment) = self.segments.remove(offset) {
-            self.update_min_max();
-            Some((segment, self.min_offset))
-        } else {
-            None
+    /// remove local and remote segment, return them with min offset
+    #[allow(clippy::type_complexity)]
+    fn remove_segment(
+        &mut self,
+        offset: &Offset,
+    ) -> Option<(Option<ReadSegment>, Option<RemoteSegment>, Offset)> {
+        let segment = self.segments.remove(offset);
+        let remote = self.remote.remove(offset);
+        if segment.is_none() && remote.is_none() {
+            return None;
         }
+        self.update_min_max();
+        Some((segment, remote, self.min_offset))
+    }
+
+    /// replace segment with same base offset, return remote copy of previous segment.
+    /// New segment has to be uploaded again, remote copy must be deleted by caller.
+    pub(crate) fn replace_segment(&mut self, segment: ReadSegment) -> Option<RemoteSegment> {
+        let remote = self.remote.remove(&segment.get_base_offset());
+        self.segments.insert(segment.get_base_offset(), segment);
+        self.update_min_max();
+        remote
+    }
+
+    /// iterate segments in the offset order
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &ReadSegment> {
+        self.segments.values()
     }
 
     #[cfg(test)]
@@ -226,27 +416,49 @@ impl SegmentList {
         self.segments.get(&offset)
     }
 
+    /// find local segment containing offset
     pub fn find_segment(&self, offset: Offset) -> Option<(&Offset, &ReadSegment)> {
-        if offset < self.min_offset {
-            None
-        } else if offset == self.min_offset {
-            self.segments
-                .range((Included(offset), Included(offset)))
-                .next_back()
-        } else if offset >= self.max_offset {
-            None
-        } else {
-            let range = (
-                Included(offset - self.max_offset + self.min_offset + 1),
-                Included(offset),
-            );
-            //  println!("range: {:?}", range);
-            self.segments.range(range).next_back()
-        }
+        // with evicted segments, local segments don't have to be contiguous
+        self.segments
+            .range(..=offset)
+            .next_back()
+            .filter(|(_, segment)| offset < segment.get_end_offset())
     }
 
-    pub(crate) fn find_expired_segments(&self, expired_duration: &Duration) -> Vec<Offset> {
+    /// find remote segment containing offset which doesn't have local copy
+    pub(crate) fn find_remote_only(&self, offset: Offset) -> Option<&RemoteSegment> {
+        self.remote
+            .range(..=offset)
+            .next_back()
+            .map(|(_, segment)| segment)
+            .filter(|segment| segment.contains(offset) && self.find_segment(offset).is_none())
+    }
+
+    /// base offsets of local and remote segments in offset order
+    fn base_offsets(&self) -> Vec<Offset> {
+        let mut offsets: Vec<Offset> = self
+            .segments
+            .keys()
+            .chain(self.remote.keys())
+            .copied()
+            .collect();
+        offsets.sort_unstable();
+        offsets.dedup();
+        offsets
+    }
+
+    /// local segments which are not uploaded, with their end offsets
+    fn find_not_uploaded(&self) -> Vec<(Offset, Offset)> {
         self.segments
+            .iter()
+            .filter(|(base_offset, _)| !self.remote.contains_key(base_offset))
+            .map(|(base_offset, segment)| (*base_offset, segment.get_end_offset()))
+            .collect()
+    }
+
+    pub(crate) fn find_expired_segments(&self, expired_duration: &Duration) -> Vec<Offset> {
+        let mut expired: Vec<Offset> = self
+            .segments
             .iter()
             .filter_map(|(base_offset, segment)| {
                 if segment.is_expired(expired_duration) {
@@ -255,13 +467,52 @@ impl SegmentList {
                     None
                 }
             })
-            .collect()
+            .chain(
+                self.remote
+                    .values()
+                    .filter(|segment| segment.is_expired(expired_duration))
+                    .map(|segment| segment.base_offset),
+            )
+            .collect();
+        expired.sort_unstable();
+        expired.dedup();
+        expired
+    }
+
+    /// local and remote segments which only contain records before offset
+    pub(crate) fn find_segments_before(&self, offset: Offset) -> Vec<Offset> {
+        let local = self
+            .segments
+            .values()
+            .map(|segment| (segment.get_base_offset(), segment.get_end_offset()));
+        let remote = self
+            .remote
+            .values()
+            .map(|segment| (segment.base_offset, segment.end_offset));
+        let mut before: Vec<Offset> = local
+            .chain(remote)
+            .filter_map(|(base_offset, end_offset)| (end_offset <= offset).then_some(base_offset))
+            .collect();
+        before.sort_unstable();
+        before.dedup();
+        before
     }
 
     #[instrument(skip(self))]
     pub(crate) fn find_first(&self, count: usize) -> Vec<Offset> {
         self.segments.keys().take(count).copied().collect()
     }
+
+    /// first local segments which have copy in remote store
+    #[instrument(skip(self))]
+    pub(crate) fn find_first_uploaded(&self, count: usize) -> Vec<Offset> {
+        self.segments
+            .keys()
+            .filter(|base_offset| self.remote.contains_key(base_offset))
+            .take(count)
+            .copied()
+            .collect()
+    }
 }
 
 #[cfg(test)]
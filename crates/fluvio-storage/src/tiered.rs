@@ -0,0 +1,468 @@
+//!
+//! # Tiered storage
+//!
+//! Closed segments are uploaded to remote store, after which local copies can be evicted.
+//! Evicted segments are fetched back when they are read.
+//!
+//! Every segment file is stored as object with key `<replica>/<base offset>-<end offset>.<extension>`.
+//! Message log is uploaded last, so existence of its object means that segment is complete.
+//!
+
+use std::fmt::Debug;
+use std::fs::{self, File};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use blocking::unblock;
+use fluvio_future::fs::metadata;
+use tracing::{debug, info};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::record::Offset;
+
+use crate::config::SharedReplicaConfig;
+use crate::index::{EXTENSION as INDEX_EXTENSION, TIME_INDEX_EXTENSION};
+use crate::records::MESSAGE_LOG_EXTENSION;
+use crate::segment::ReadSegment;
+use crate::util::generate_file_name;
+
+/// Object in remote store
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteObject {
+    pub key: String,
+    pub modified: SystemTime,
+}
+
+/// Object store where closed segments are offloaded
+#[async_trait]
+pub trait RemoteStore: Debug + Send + Sync {
+    /// upload local file as object, existing object is replaced
+    async fn put(&self, key: &str, source: &Path) -> Result<()>;
+
+    /// download object to local file
+    async fn get(&self, key: &str, target: &Path) -> Result<()>;
+
+    /// delete object, deleting missing object is not an error
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    /// list objects whose key starts with prefix
+    async fn list(&self, prefix: &str) -> Result<Vec<RemoteObject>>;
+}
+
+/// Remote store backed by local directory.
+/// Objects keep modification time of files they were copied from.
+#[derive(Debug, Clone)]
+pub struct LocalFsStore {
+    root: PathBuf,
+}
+
+impl LocalFsStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+/// copy file through temporary file, so target never contains partial content
+fn copy_file(source: &Path, target: &Path) -> std::io::Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let modified = fs::metadata(source)?.modified()?;
+    let mut partial = target.as_os_str().to_owned();
+    partial.push(".part");
+    let partial = PathBuf::from(partial);
+    fs::copy(source, &partial)?;
+    File::options()
+        .write(true)
+        .open(&partial)?
+        .set_modified(modified)?;
+    fs::rename(&partial, target)
+}
+
+#[async_trait]
+impl RemoteStore for LocalFsStore {
+    async fn put(&self, key: &str, source: &Path) -> Result<()> {
+        let source = source.to_owned();
+        let target = self.root.join(key);
+        unblock(move || copy_file(&source, &target)).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str, target: &Path) -> Result<()> {
+        let source = self.root.join(key);
+        let target = target.to_owned();
+        unblock(move || copy_file(&source, &target)).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.root.join(key);
+        match unblock(move || fs::remove_file(path)).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<RemoteObject>> {
+        // keys are relative paths, so prefix up to last separator is directory
+        let (dir, name_prefix) = match prefix.rsplit_once('/') {
+            Some((dir, name)) => (self.root.join(dir), name.to_owned()),
+            None => (self.root.clone(), prefix.to_owned()),
+        };
+        let key_dir = prefix
+            .rsplit_once('/')
+            .map(|(dir, _)| format!("{dir}/"))
+            .unwrap_or_default();
+        unblock(move || {
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+                Err(err) => return Err(err.into()),
+            };
+            let mut objects = vec![];
+            for entry in entries {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                let name = entry.file_name().to_string_lossy().to_string();
+                if metadata.is_file() && name.starts_with(&name_prefix) && !name.ends_with(".part")
+                {
+                    objects.push(RemoteObject {
+                        key: format!("{key_dir}{name}"),
+                        modified: metadata.modified()?,
+                    });
+                }
+            }
+            Ok(objects)
+        })
+        .await
+    }
+}
+
+/// Closed segment stored in remote store
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RemoteSegment {
+    pub base_offset: Offset,
+    pub end_offset: Offset,
+    pub modified: SystemTime,
+    has_time_index: bool,
+}
+
+impl RemoteSegment {
+    pub(crate) fn contains(&self, offset: Offset) -> bool {
+        self.base_offset <= offset && offset < self.end_offset
+    }
+
+    pub(crate) fn is_expired(&self, expired_duration: &Duration) -> bool {
+        self.modified
+            .elapsed()
+            .is_ok_and(|elapsed| elapsed > *expired_duration)
+    }
+}
+
+/// Remote store of single replica
+#[derive(Debug)]
+pub(crate) struct RemoteTier {
+    store: Arc<dyn RemoteStore>,
+    prefix: String,
+    option: Arc<SharedReplicaConfig>,
+}
+
+impl RemoteTier {
+    /// remote tier of replica, if remote store is configured
+    pub(crate) fn new(option: Arc<SharedReplicaConfig>) -> Option<Self> {
+        let store = option.remote_store.as_ref()?.store();
+        let prefix = option
+            .base_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Some(Self {
+            store,
+            prefix,
+            option,
+        })
+    }
+
+    fn key(&self, base_offset: Offset, end_offset: Offset, extension: &str) -> String {
+        format!(
+            "{}/{base_offset:020}-{end_offset:020}.{extension}",
+            self.prefix
+        )
+    }
+
+    fn local_path(&self, base_offset: Offset, extension: &str) -> PathBuf {
+        generate_file_name(&self.option.base_dir, base_offset, extension)
+    }
+
+    /// complete segments in remote store
+    pub(crate) async fn list_segments(&self) -> Result<Vec<RemoteSegment>> {
+        let objects = self.store.list(&format!("{}/", self.prefix)).await?;
+        let time_indexes: Vec<&str> = objects
+            .iter()
+            .filter_map(|object| object.key.strip_suffix(TIME_INDEX_EXTENSION))
+            .collect();
+        let mut segments = vec![];
+        for object in &objects {
+            let Some(stem) = object.key.strip_suffix(MESSAGE_LOG_EXTENSION) else {
+                continue;
+            };
+            let name = stem.rsplit('/').next().unwrap_or_default();
+            let Some((base_offset, end_offset)) = name
+                .trim_end_matches('.')
+                .split_once('-')
+                .and_then(|(base, end)| Some((base.parse().ok()?, end.parse().ok()?)))
+            else {
+                debug!(key = object.key, "not a segment, skipping");
+                continue;
+            };
+            segments.push(RemoteSegment {
+                base_offset,
+                end_offset,
+                modified: object.modified,
+                has_time_index: time_indexes.contains(&stem),
+            });
+        }
+        segments.sort_by_key(|segment| segment.base_offset);
+        Ok(segments)
+    }
+
+    /// upload closed segment, message log goes last
+    pub(crate) async fn upload(
+        &self,
+        base_offset: Offset,
+        end_offset: Offset,
+    ) -> Result<RemoteSegment> {
+        let index = self.local_path(base_offset, INDEX_EXTENSION);
+        self.store
+            .put(&self.key(base_offset, end_offset, INDEX_EXTENSION), &index)
+            .await?;
+
+        let time_index = self.local_path(base_offset, TIME_INDEX_EXTENSION);
+        let has_time_index = match metadata(&time_index).await {
+            Ok(_) => true,
+            Err(err) if err.kind() == ErrorKind::NotFound => false,
+            Err(err) => return Err(err.into()),
+        };
+        if has_time_index {
+            self.store
+                .put(
+                    &self.key(base_offset, end_offset, TIME_INDEX_EXTENSION),
+                    &time_index,
+                )
+                .await?;
+        }
+
+        let log = self.local_path(base_offset, MESSAGE_LOG_EXTENSION);
+        let modified = metadata(&log).await?.modified()?;
+        self.store
+            .put(
+                &self.key(base_offset, end_offset, MESSAGE_LOG_EXTENSION),
+                &log,
+            )
+            .await?;
+
+        info!(base_offset, end_offset, "segment uploaded");
+        Ok(RemoteSegment {
+            base_offset,
+            end_offset,
+            modified,
+            has_time_index,
+        })
+    }
+
+    /// download segment into replica directory and open it
+    pub(crate) async fn download(&self, segment: &RemoteSegment) -> Result<ReadSegment> {
+        let mut extensions = vec![INDEX_EXTENSION];
+        if segment.has_time_index {
+            extensions.push(TIME_INDEX_EXTENSION);
+        }
+        extensions.push(MESSAGE_LOG_EXTENSION);
+        for extension in extensions {
+            self.store
+                .get(
+                    &self.key(segment.base_offset, segment.end_offset, extension),
+                    &self.local_path(segment.base_offset, extension),
+                )
+                .await
+                .map_err(|err| {
+                    anyhow!(
+                        "failed to download {extension} of segment {}: {err}",
+                        segment.base_offset
+                    )
+                })?;
+        }
+        info!(
+            base_offset = segment.base_offset,
+            end_offset = segment.end_offset,
+            "segment downloaded"
+        );
+        ReadSegment::open_for_read(segment.base_offset, segment.end_offset, self.option.clone())
+            .await
+    }
+
+    pub(crate) async fn delete(&self, segment: &RemoteSegment) -> Result<()> {
+        // message log goes first, so partially deleted segment is not listed
+        for extension in [MESSAGE_LOG_EXTENSION, INDEX_EXTENSION, TIME_INDEX_EXTENSION] {
+            self.store
+                .delete(&self.key(segment.base_offset, segment.end_offset, extension))
+                .await?;
+        }
+        info!(base_offset = segment.base_offset, "remote segment deleted");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::fixture::create_batch;
+
+    use crate::config::{ReplicaConfig, RemoteStoreConfig};
+    use crate::segment::MutableSegment;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_local_fs_store() {
+        let root = temp_dir().join("tiered-local-fs-store");
+        ensure_new_dir(&root).expect("new");
+        let source = root.join("source.txt");
+        fs::write(&source, "hello").expect("write");
+
+        let store = LocalFsStore::new(root.join("remote"));
+        assert!(store.list("replica/").await.expect("list").is_empty());
+
+        store.put("replica/a.log", &source).await.expect("put");
+        store.put("other/b.log", &source).await.expect("put");
+        let objects = store.list("replica/").await.expect("list");
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].key, "replica/a.log");
+        assert_eq!(
+            objects[0].modified,
+            fs::metadata(&source).unwrap().modified().unwrap()
+        );
+
+        let target = root.join("target.txt");
+        store.get("replica/a.log", &target).await.expect("get");
+        assert_eq!(fs::read_to_string(&target).expect("read"), "hello");
+
+        store.delete("replica/a.log").await.expect("delete");
+        store.delete("replica/a.log").await.expect("delete missing");
+        assert!(store.list("replica/").await.expect("list").is_empty());
+        assert!(store.get("replica/a.log", &target).await.is_err());
+    }
+
+    #[fluvio_future::test]
+    async fn test_upload_and_download_segment() {
+        let root = temp_dir().join("tiered-upload-download");
+        ensure_new_dir(&root).expect("new");
+        let option = ReplicaConfig {
+            base_dir: root.join("topic-0"),
+            remote_store: Some(RemoteStoreConfig::LocalFs {
+                path: root.join("remote"),
+            }),
+            ..Default::default()
+        }
+        .shared();
+        ensure_new_dir(&option.base_dir).expect("new");
+
+        let mut segment = MutableSegment::create(10, option.clone())
+            .await
+            .expect("create");
+        segment
+            .append_batch(&mut create_batch())
+            .await
+            .expect("append");
+        let segment = segment.convert_to_segment().await.expect("convert");
+        let end_offset = segment.get_end_offset();
+
+        let tier = RemoteTier::new(option.clone()).expect("tier");
+        let uploaded = tier.upload(10, end_offset).await.expect("upload");
+        assert_eq!(
+            tier.list_segments().await.expect("list"),
+            vec![uploaded.clone()]
+        );
+
+        segment.remove().await.expect("remove");
+        let downloaded = tier.download(&uploaded).await.expect("download");
+        assert_eq!(downloaded.get_base_offset(), 10);
+        assert_eq!(downloaded.get_end_offset(), end_offset);
+        assert!(
+            downloaded
+                .records_slice(10, None)
+                .await
+                .expect("slice")
+                .is_some()
+        );
+
+        tier.delete(&uploaded).await.expect("delete");
+        assert!(tier.list_segments().await.expect("list").is_empty());
+    }
+
+    /// store which records keys of uploaded objects
+    #[derive(Debug)]
+    struct RecordingStore {
+        inner: LocalFsStore,
+        puts: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl RemoteStore for RecordingStore {
+        async fn put(&self, key: &str, source: &Path) -> Result<()> {
+            self.puts.lock().unwrap().push(key.to_owned());
+            self.inner.put(key, source).await
+        }
+
+        async fn get(&self, key: &str, target: &Path) -> Result<()> {
+            self.inner.get(key, target).await
+        }
+
+        async fn delete(&self, key: &str) -> Result<()> {
+            self.inner.delete(key).await
+        }
+
+        async fn list(&self, prefix: &str) -> Result<Vec<RemoteObject>> {
+            self.inner.list(prefix).await
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_custom_remote_store() {
+        let root = temp_dir().join("tiered-custom-store");
+        ensure_new_dir(&root).expect("new");
+        let store = Arc::new(RecordingStore {
+            inner: LocalFsStore::new(root.join("remote")),
+            puts: Default::default(),
+        });
+        let option = ReplicaConfig {
+            base_dir: root.join("topic-0"),
+            remote_store: Some(RemoteStoreConfig::custom(store.clone())),
+            ..Default::default()
+        }
+        .shared();
+        ensure_new_dir(&option.base_dir).expect("new");
+
+        let mut segment = MutableSegment::create(0, option.clone())
+            .await
+            .expect("create");
+        segment
+            .append_batch(&mut create_batch())
+            .await
+            .expect("append");
+        let segment = segment.convert_to_segment().await.expect("convert");
+
+        let tier = RemoteTier::new(option).expect("tier");
+        tier.upload(0, segment.get_end_offset())
+            .await
+            .expect("upload");
+        let puts = store.puts.lock().unwrap();
+        assert!(!puts.is_empty());
+        assert!(puts.last().unwrap().ends_with(MESSAGE_LOG_EXTENSION));
+    }
+}
@@ -0,0 +1,349 @@
+//! Transaction index of replica.
+//!
+//! Tracks producer transactions which are still open and offset ranges of aborted transactions.
+//! `ReadCommitted` reads stop at last stable offset, which is first offset of the earliest open transaction,
+//! and consumers use aborted ranges to skip records of aborted transactions.
+//!
+//! Index is updated as batches are appended, on leader and followers, and saved to replica directory whenever it changes.
+//! Aborted ranges are not considered by key based compaction, they are pruned once log start moves past them.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info};
+use anyhow::Result;
+
+use fluvio_future::fs::{read, rename, write};
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_protocol::record::{Batch, BatchRecords, ControlRecordType, MemoryRecords, Offset};
+use fluvio_spu_schema::fetch::AbortedTransaction;
+
+pub(crate) const TXN_INDEX_FILE_NAME: &str = "transaction.index";
+const TXN_INDEX_TMP_FILE_NAME: &str = "transaction.index.tmp";
+
+#[derive(Debug, Default, Encoder, Decoder, PartialEq)]
+struct OpenTransaction {
+    producer_id: i64,
+    first_offset: Offset,
+}
+
+#[derive(Debug, Default, Encoder, Decoder, PartialEq)]
+struct AbortedRange {
+    producer_id: i64,
+    first_offset: Offset,
+    last_offset: Offset,
+}
+
+#[derive(Debug, Default, Encoder, Decoder)]
+struct TransactionIndexState {
+    open: Vec<OpenTransaction>,
+    aborted: Vec<AbortedRange>,
+}
+
+#[derive(Debug)]
+pub(crate) struct TransactionIndex {
+    dir: PathBuf,
+    state: TransactionIndexState,
+    /// when open transactions were first seen by this process, not saved
+    started: HashMap<i64, Instant>,
+}
+
+impl TransactionIndex {
+    /// load index from replica directory.
+    /// Entries outside of the log (removed by retention or repair) are dropped.
+    pub(crate) async fn load(dir: &Path, log_start_offset: Offset, leo: Offset) -> Result<Self> {
+        let mut state = match read(dir.join(TXN_INDEX_FILE_NAME)).await {
+            Ok(bytes) => TransactionIndexState::decode_from(&mut bytes.as_slice(), 0)?,
+            Err(err) if err.kind() == ErrorKind::NotFound => TransactionIndexState::default(),
+            Err(err) => return Err(err.into()),
+        };
+        state.open.retain(|txn| txn.first_offset < leo);
+        state
+            .aborted
+            .retain(|txn| txn.last_offset >= log_start_offset && txn.last_offset < leo);
+        if !state.open.is_empty() {
+            info!(open = state.open.len(), "open transactions loaded");
+        }
+        // loaded transactions get full timeout again
+        let now = Instant::now();
+        let started = state
+            .open
+            .iter()
+            .map(|txn| (txn.producer_id, now))
+            .collect();
+        Ok(Self {
+            dir: dir.to_owned(),
+            state,
+            started,
+        })
+    }
+
+    /// update index with batch which was appended to the log
+    pub(crate) async fn append<R: BatchRecords>(&mut self, batch: &Batch<R>) -> Result<()> {
+        let header = batch.get_header();
+        if !header.is_transactional() {
+            return Ok(());
+        }
+        let producer_id = header.producer_id;
+        let open = self
+            .state
+            .open
+            .iter()
+            .position(|txn| txn.producer_id == producer_id);
+
+        if header.is_control() {
+            let Some(marker) = control_record_type(batch)? else {
+                return Ok(());
+            };
+            let first_offset = match open {
+                Some(index) => self.state.open.remove(index).first_offset,
+                None => batch.get_base_offset(),
+            };
+            self.started.remove(&producer_id);
+            debug!(producer_id, first_offset, ?marker, "transaction ended");
+            if marker == ControlRecordType::Abort {
+                self.state.aborted.push(AbortedRange {
+                    producer_id,
+                    first_offset,
+                    last_offset: batch.get_base_offset(),
+                });
+            }
+        } else if open.is_none() {
+            debug!(
+                producer_id,
+                first_offset = batch.get_base_offset(),
+                "transaction started"
+            );
+            self.state.open.push(OpenTransaction {
+                producer_id,
+                first_offset: batch.get_base_offset(),
+            });
+            self.started.insert(producer_id, Instant::now());
+        } else {
+            return Ok(());
+        }
+
+        self.save().await
+    }
+
+    /// drop transactions which start at or after new end of the log, aborted ranges must end before it
+    pub(crate) async fn truncate(&mut self, leo: Offset) -> Result<()> {
+        self.state.open.retain(|txn| txn.first_offset < leo);
+        self.state.aborted.retain(|txn| txn.last_offset < leo);
+        let open = &self.state.open;
+        self.started
+            .retain(|producer_id, _| open.iter().any(|txn| txn.producer_id == *producer_id));
+        self.save().await
+    }
+
+    /// drop aborted ranges which end before log start offset, their records are no longer in the log
+    pub(crate) async fn remove_before(&mut self, log_start_offset: Offset) -> Result<()> {
+        let count = self.state.aborted.len();
+        self.state
+            .aborted
+            .retain(|txn| txn.last_offset >= log_start_offset);
+        if self.state.aborted.len() == count {
+            return Ok(());
+        }
+        debug!(
+            log_start_offset,
+            removed = count - self.state.aborted.len(),
+            "aborted transactions pruned"
+        );
+        self.save().await
+    }
+
+    /// producers of transactions which are open longer than timeout
+    pub(crate) fn expired(&self, timeout: Duration) -> Vec<i64> {
+        self.started
+            .iter()
+            .filter(|(_, started)| started.elapsed() >= timeout)
+            .map(|(producer_id, _)| *producer_id)
+            .collect()
+    }
+
+    /// first offset of earliest open transaction
+    pub(crate) fn first_open_offset(&self) -> Option<Offset> {
+        self.state.open.iter().map(|txn| txn.first_offset).min()
+    }
+
+    /// aborted transactions which have records in range between start (inclusive) and end (exclusive)
+    pub(crate) fn aborted(&self, start: Offset, end: Offset) -> Vec<AbortedTransaction> {
+        self.state
+            .aborted
+            .iter()
+            .filter(|txn| txn.first_offset < end && txn.last_offset >= start)
+            .map(|txn| AbortedTransaction {
+                producer_id: txn.producer_id,
+                first_offset: txn.first_offset,
+                last_offset: txn.last_offset,
+            })
+            .collect()
+    }
+
+    async fn save(&self) -> Result<()> {
+        let mut bytes = Vec::new();
+        self.state.encode(&mut bytes, 0)?;
+        let tmp_path = self.dir.join(TXN_INDEX_TMP_FILE_NAME);
+        write(&tmp_path, bytes).await?;
+        rename(&tmp_path, self.dir.join(TXN_INDEX_FILE_NAME)).await?;
+        Ok(())
+    }
+}
+
+/// decode marker of control batch, control batches are never compressed
+fn control_record_type<R: BatchRecords>(batch: &Batch<R>) -> Result<Option<ControlRecordType>> {
+    let mut bytes = Vec::new();
+    batch.records().encode(&mut bytes, 0)?;
+    let mut control = Batch::from(MemoryRecords::decode_from(&mut bytes.as_slice(), 0)?);
+    control.header = batch.get_header().clone();
+    Ok(control.control_record_type())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::record::{Batch, Record};
+
+    use super::*;
+
+    fn transactional_batch(producer_id: i64, base_offset: Offset) -> Batch {
+        let mut batch = Batch::from(vec![Record::new("a")]);
+        batch.header.set_transactional();
+        batch.header.producer_id = producer_id;
+        batch.set_base_offset(base_offset);
+        batch
+    }
+
+    fn marker(producer_id: i64, marker: ControlRecordType, base_offset: Offset) -> Batch {
+        let mut batch = Batch::control(producer_id, 0, marker, 0);
+        batch.set_base_offset(base_offset);
+        batch
+    }
+
+    #[fluvio_future::test]
+    async fn test_transaction_index() {
+        let dir = temp_dir().join("test_transaction_index");
+        ensure_new_dir(&dir).expect("new");
+
+        let mut index = TransactionIndex::load(&dir, 0, 0).await.expect("load");
+        assert_eq!(index.first_open_offset(), None);
+
+        index
+            .append(&Batch::from(vec![Record::new("a")]).base_offset(0))
+            .await
+            .expect("append");
+        assert_eq!(index.first_open_offset(), None);
+
+        index
+            .append(&transactional_batch(1, 1))
+            .await
+            .expect("append");
+        index
+            .append(&transactional_batch(2, 2))
+            .await
+            .expect("append");
+        index
+            .append(&transactional_batch(1, 3))
+            .await
+            .expect("append");
+        assert_eq!(index.first_open_offset(), Some(1));
+
+        index
+            .append(&marker(1, ControlRecordType::Abort, 4))
+            .await
+            .expect("append");
+        assert_eq!(index.first_open_offset(), Some(2));
+        index
+            .append(&marker(2, ControlRecordType::Commit, 5))
+            .await
+            .expect("append");
+        assert_eq!(index.first_open_offset(), None);
+
+        let aborted = index.aborted(0, 6);
+        assert_eq!(aborted.len(), 1);
+        assert_eq!(aborted[0].producer_id, 1);
+        assert_eq!(aborted[0].first_offset, 1);
+        assert_eq!(aborted[0].last_offset, 4);
+        assert!(aborted[0].contains(1, 3));
+        assert!(!aborted[0].contains(2, 2));
+        assert!(index.aborted(5, 6).is_empty());
+
+        index
+            .append(&transactional_batch(3, 6))
+            .await
+            .expect("append");
+
+        // state survives reload
+        let index = TransactionIndex::load(&dir, 0, 7).await.expect("load");
+        assert_eq!(index.first_open_offset(), Some(6));
+        assert_eq!(index.aborted(0, 7).len(), 1);
+
+        // entries outside of log are dropped
+        let index = TransactionIndex::load(&dir, 5, 6).await.expect("load");
+        assert_eq!(index.first_open_offset(), None);
+        assert!(index.aborted(0, 7).is_empty());
+    }
+
+    #[fluvio_future::test]
+    async fn test_transaction_index_remove_before() {
+        let dir = temp_dir().join("test_transaction_index_remove_before");
+        ensure_new_dir(&dir).expect("new");
+
+        let mut index = TransactionIndex::load(&dir, 0, 0).await.expect("load");
+        index
+            .append(&transactional_batch(1, 0))
+            .await
+            .expect("append");
+        index
+            .append(&marker(1, ControlRecordType::Abort, 1))
+            .await
+            .expect("append");
+        index
+            .append(&transactional_batch(2, 2))
+            .await
+            .expect("append");
+        index
+            .append(&marker(2, ControlRecordType::Abort, 3))
+            .await
+            .expect("append");
+        assert_eq!(index.aborted(0, 4).len(), 2);
+
+        index.remove_before(1).await.expect("remove");
+        assert_eq!(index.aborted(0, 4).len(), 2);
+
+        index.remove_before(2).await.expect("remove");
+        let aborted = index.aborted(0, 4);
+        assert_eq!(aborted.len(), 1);
+        assert_eq!(aborted[0].producer_id, 2);
+
+        // pruned ranges are not loaded again
+        let index = TransactionIndex::load(&dir, 0, 4).await.expect("load");
+        assert_eq!(index.aborted(0, 4).len(), 1);
+    }
+
+    #[fluvio_future::test]
+    async fn test_transaction_index_expired() {
+        let dir = temp_dir().join("test_transaction_index_expired");
+        ensure_new_dir(&dir).expect("new");
+
+        let mut index = TransactionIndex::load(&dir, 0, 0).await.expect("load");
+        index
+            .append(&transactional_batch(1, 0))
+            .await
+            .expect("append");
+        assert!(index.expired(Duration::from_secs(60)).is_empty());
+        assert_eq!(index.expired(Duration::ZERO), vec![1]);
+
+        index
+            .append(&marker(1, ControlRecordType::Abort, 1))
+            .await
+            .expect("append");
+        assert!(index.expired(Duration::ZERO).is_empty());
+    }
+}
@@ -14,6 +14,7 @@ use anyhow::{Result, anyhow};
 use fluvio_protocol::record::Offset;
 
 use crate::batch::BatchHeaderError;
+use crate::batch::CorruptBatch;
 use crate::batch::FileBatchStream;
 use crate::batch::StorageBytesIterator;
 use crate::batch_header::FileEmptyRecords;
@@ -52,10 +53,16 @@ pub struct LogValidator {
     pub duration: Duration,
     pub error: Option<LogValidationError>,
     pub index_error: Option<InvalidIndexError>,
+    /// batches with crc mismatch, only checked if crc verification is enabled
+    pub corrupt_batches: Vec<CorruptBatch>,
 }
 
 impl LogValidator {
-    async fn validate_core<I, S, R>(path: impl AsRef<Path>, index: Option<&I>) -> Result<Self>
+    async fn validate_core<I, S, R>(
+        path: impl AsRef<Path>,
+        index: Option<&I>,
+        verify_crc: bool,
+    ) -> Result<Self>
     where
         I: Index,
         S: StorageBytesIterator,
@@ -76,16 +83,18 @@ impl LogValidator {
         );
 
         let start_time = std::time::Instant::now();
-        let batch_stream: FileBatchStream<R, S> = match FileBatchStream::open(&val.file_path).await
-        {
-            Ok(batch_stream) => batch_stream,
-            Err(err) => match err.kind() {
-                ErrorKind::UnexpectedEof => {
-                    return Err(anyhow!("empty file with base offset: {}", val.base_offset));
-                }
-                _ => return Err(err.into()),
-            },
-        };
+        let mut batch_stream: FileBatchStream<R, S> =
+            match FileBatchStream::open(&val.file_path).await {
+                Ok(batch_stream) => batch_stream,
+                Err(err) => match err.kind() {
+                    ErrorKind::UnexpectedEof => {
+                        return Err(anyhow!("empty file with base offset: {}", val.base_offset));
+                    }
+                    _ => return Err(err.into()),
+                },
+            };
+
+        batch_stream.set_verify_crc(verify_crc);
 
         // find recoverable error
 
@@ -122,6 +131,9 @@ impl LogValidator {
         let mut last_index_pos = 0;
 
         while let Some(batch_pos) = batch_stream.try_next().await? {
+            if let Some(corrupt) = batch_pos.corrupt() {
+                self.corrupt_batches.push(corrupt.clone());
+            }
             let current_batch_pos = batch_pos.get_pos();
             let current_batch = batch_pos.inner();
 
@@ -240,23 +252,28 @@ impl LogValidator {
 
     /// public facing entry point
     #[instrument(skip(index, path))]
-    pub(crate) async fn validate<I, S>(path: impl AsRef<Path>, index: Option<&I>) -> Result<Self>
+    pub(crate) async fn validate<I, S>(
+        path: impl AsRef<Path>,
+        index: Option<&I>,
+        verify_crc: bool,
+    ) -> Result<Self>
     where
         I: Index,
         S: StorageBytesIterator,
     {
-        Self::validate_core::<I, S, FileEmptyRecords>(path, index).await
+        Self::validate_core::<I, S, FileEmptyRecords>(path, index, verify_crc).await
     }
 
     #[instrument(skip(index, path))]
     pub(crate) async fn default_validate<I>(
         path: impl AsRef<Path>,
         index: Option<&I>,
+        verify_crc: bool,
     ) -> Result<Self>
     where
         I: Index,
     {
-        Self::validate::<I, FileBytesIterator>(path, index).await
+        Self::validate::<I, FileBytesIterator>(path, index, verify_crc).await
     }
 }
 
@@ -301,7 +318,7 @@ mod tests {
         let log_path = log_records.get_path().to_owned();
         drop(log_records);
 
-        let validator = LogValidator::default_validate::<LogIndex>(&log_path, None)
+        let validator = LogValidator::default_validate::<LogIndex>(&log_path, None, false)
             .await
             .expect("validate");
         assert_eq!(validator.leo(), BASE_OFFSET);
@@ -343,7 +360,7 @@ mod tests {
 
         let original_fs_len = std::fs::metadata(&log_path).expect("get metadata").len();
 
-        let validator = LogValidator::default_validate::<LogIndex>(&log_path, None)
+        let validator = LogValidator::default_validate::<LogIndex>(&log_path, None, false)
             .await
             .expect("validate");
         assert_eq!(validator.leo(), BASE_OFFSET + 5);
@@ -391,7 +408,7 @@ mod tests {
         let test_fs_path = msg_sink.get_path().to_owned();
         drop(msg_sink);
 
-        let validator = LogValidator::default_validate::<LogIndex>(&test_fs_path, None)
+        let validator = LogValidator::default_validate::<LogIndex>(&test_fs_path, None, false)
             .await
             .expect("validate");
 
@@ -419,7 +436,7 @@ mod tests {
         assert_eq!(invalid_fs_len, original_fs_len + 3);
 
         debug!("checking invalid contents");
-        let validator = LogValidator::default_validate::<LogIndex>(&test_fs_path, None)
+        let validator = LogValidator::default_validate::<LogIndex>(&test_fs_path, None, false)
             .await
             .expect("validate");
 
@@ -449,7 +466,7 @@ mod perf {
 
         println!("starting test");
         let header_time = Instant::now();
-        let msm_result = LogValidator::default_validate::<LogIndex>(TEST_PATH, None)
+        let msm_result = LogValidator::default_validate::<LogIndex>(TEST_PATH, None, false)
             .await
             .expect("validate");
         println!("header only took: {:#?}", header_time.elapsed());
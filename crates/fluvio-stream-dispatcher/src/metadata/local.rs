@@ -104,7 +104,7 @@ cfg_if::cfg_if! {
         use async_channel::{Sender, Receiver, bounded};
         use parking_lot::RwLock;
         use futures_util::{stream::BoxStream, StreamExt};
-        use serde::{de::DeserializeOwned};
+        use serde::{Serialize, Deserialize, de::DeserializeOwned};
         use tracing::{warn, debug, trace};
 
         use fluvio_stream_model::{
@@ -121,9 +121,32 @@ cfg_if::cfg_if! {
         pub struct LocalMetadataStorage {
             path: PathBuf,
             stores: RwLock<HashMap<&'static str, Arc<SpecStore>>>,
+            observer: Option<SharedChangeObserver>,
         }
         pub type LocalStoreObject<S> = MetadataStoreObject<S, LocalMetadataItem>;
 
+        /// Change of a single spec file in the local metadata folder
+        #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+        pub struct LocalMetadataChange {
+            pub kind: String,
+            pub name: String,
+            /// yaml content of the spec file, `None` if the file was deleted
+            pub content: Option<String>,
+        }
+
+        /// Consulted by the storage every time a spec file is written or removed.
+        /// The change is applied to the store, the file and the watchers only after it is staged.
+        #[async_trait::async_trait]
+        pub trait LocalChangeObserver: std::fmt::Debug + Send + Sync {
+            /// Waits until the change can be applied, the change is dropped on error
+            async fn stage(&self, change: LocalMetadataChange) -> Result<()>;
+
+            /// The last staged change was applied
+            fn applied(&self);
+        }
+
+        pub type SharedChangeObserver = Arc<dyn LocalChangeObserver>;
+
         #[async_trait::async_trait]
         impl MetadataClient<LocalMetadataItem> for LocalMetadataStorage {
             async fn retrieve_items<S>(
@@ -148,7 +171,7 @@ cfg_if::cfg_if! {
                         self.unlink_parent::<S>(owner, item.ctx().item()).await?;
                     }
                     self.delete_children(item).await?;
-                    store.delete_item(&metadata).await
+                    store.delete_item(&metadata).await?;
                 };
                 Ok(())
             }
@@ -284,9 +307,13 @@ cfg_if::cfg_if! {
         struct SpecStore {
             version: AtomicU64,
             data: RwLock<HashMap<String, SpecPointer>>,
+            /// serializes changes, which are staged without holding the data lock
+            writes: async_lock::Mutex<()>,
             sender: Sender<SpecUpdate>,
             receiver: Receiver<SpecUpdate>,
             path: PathBuf,
+            kind: &'static str,
+            observer: Option<SharedChangeObserver>,
         }
 
         #[derive(Debug, Clone)]
@@ -306,7 +333,96 @@ cfg_if::cfg_if! {
             pub fn new<P: AsRef<Path>>(path: P) -> Self {
                 let path = path.as_ref().to_path_buf();
                 let stores = Default::default();
-                Self { path, stores }
+                Self {
+                    path,
+                    stores,
+                    observer: None,
+                }
+            }
+
+            /// Creates storage which stages every change with `observer`
+            pub fn with_observer<P: AsRef<Path>>(path: P, observer: SharedChangeObserver) -> Self {
+                let mut storage = Self::new(path);
+                storage.observer = Some(observer);
+                storage
+            }
+
+            /// Reads content of all spec files in the metadata folder
+            pub fn snapshot<P: AsRef<Path>>(path: P) -> Result<Vec<LocalMetadataChange>> {
+                let mut changes = vec![];
+                if !path.as_ref().exists() {
+                    return Ok(changes);
+                }
+                for kind_entry in std::fs::read_dir(&path)? {
+                    let kind_path = kind_entry?.path();
+                    if !kind_path.is_dir() {
+                        continue;
+                    }
+                    let Some(kind) = kind_path.file_name().and_then(OsStr::to_str) else {
+                        continue;
+                    };
+                    for entry in std::fs::read_dir(&kind_path)? {
+                        let file_path = entry?.path();
+                        if !file_path.extension().eq(&Some(OsStr::new("yaml"))) {
+                            continue;
+                        }
+                        let Some(name) = file_path.file_stem().and_then(OsStr::to_str) else {
+                            continue;
+                        };
+                        changes.push(LocalMetadataChange {
+                            kind: kind.to_string(),
+                            name: name.to_string(),
+                            content: Some(std::fs::read_to_string(&file_path)?),
+                        });
+                    }
+                }
+                changes.sort_by(|a, b| (&a.kind, &a.name).cmp(&(&b.kind, &b.name)));
+                Ok(changes)
+            }
+
+            /// Writes or removes the spec file described by `change`.
+            /// Must not be used while the folder is loaded by a running storage.
+            pub fn apply_change<P: AsRef<Path>>(path: P, change: &LocalMetadataChange) -> Result<()> {
+                let file_path = change_file_path(path.as_ref(), change)?;
+                match &change.content {
+                    Some(content) => {
+                        if let Some(parent) = file_path.parent() {
+                            std::fs::create_dir_all(parent)?;
+                        }
+                        std::fs::write(&file_path, content)?;
+                    }
+                    None => {
+                        if file_path.exists() {
+                            std::fs::remove_file(&file_path)?;
+                        }
+                    }
+                }
+                Ok(())
+            }
+
+            /// Replaces all spec files in the metadata folder by the `snapshot`
+            pub fn install_snapshot<P: AsRef<Path>>(
+                path: P,
+                snapshot: &[LocalMetadataChange],
+            ) -> Result<()> {
+                for existing in Self::snapshot(&path)? {
+                    let kept = snapshot
+                        .iter()
+                        .any(|change| change.kind == existing.kind && change.name == existing.name);
+                    if !kept {
+                        Self::apply_change(
+                            &path,
+                            &LocalMetadataChange {
+                                content: None,
+                                ..existing
+                            },
+                        )?;
+                    }
+                }
+                for change in snapshot {
+                    Self::apply_change(&path, change)?;
+                }
+                Ok(())
             }
 
             fn get_store<S: Spec + DeserializeOwned>(&self) -> Result<Arc<SpecStore>> {
@@ -317,7 +433,10 @@ cfg_if::cfg_if! {
                     None => {
                         drop(read);
                         let mut write = self.stores.write();
-                        let store = Arc::new(SpecStore::load::<S, _>(self.path.join(key))?);
+                        let store = Arc::new(SpecStore::load::<S, _>(
+                            self.path.join(key),
+                            self.observer.clone(),
+                        )?);
                         write.insert(key, store.clone());
                         drop(write);
                         store
@@ -331,7 +450,7 @@ cfg_if::cfg_if! {
                         let child_store = self.get_store_by_key(kind).await?;
                         for child in children {
                             trace!(?item, ?child, "delete child");
-                            child_store.delete_item(child).await;
+                            child_store.delete_item(child).await?;
                         }
                     }
                 }
@@ -377,8 +496,21 @@ cfg_if::cfg_if! {
             }
         }
 
+        fn change_file_path(path: &Path, change: &LocalMetadataChange) -> Result<PathBuf> {
+            let valid = |segment: &str| {
+                !segment.is_empty() && segment != ".." && !segment.contains(['/', '\\'])
+            };
+            if !valid(&change.kind) || !valid(&change.name) {
+                anyhow::bail!("invalid metadata file: {}/{}", change.kind, change.name);
+            }
+            Ok(path.join(&change.kind).join(format!("{}.yaml", change.name)))
+        }
+
         impl SpecStore {
-            fn load<S: Spec, P: AsRef<Path>>(path: P) -> Result<Self> {
+            fn load<S: Spec, P: AsRef<Path>>(
+                path: P,
+                observer: Option<SharedChangeObserver>,
+            ) -> Result<Self> {
                 std::fs::create_dir_all(&path)?;
                 let version = Default::default();
                 let mut data: HashMap<String, SpecPointer> = Default::default();
@@ -404,9 +536,12 @@ cfg_if::cfg_if! {
                 Ok(Self {
                     version,
                     data: RwLock::new(data),
+                    writes: async_lock::Mutex::new(()),
                     sender,
                     receiver,
                     path,
+                    kind: S::LABEL,
+                    observer,
                 })
             }
 
@@ -449,46 +584,44 @@ cfg_if::cfg_if! {
                     .ok_or_else(|| anyhow!("'{}' not found", metadata.uid()))
             }
 
-            async fn delete_item(&self, metadata: &LocalMetadataItem) {
-                let removed = {
-                    let mut write = self.data.write();
-                    if let Some(removed) = write.remove(metadata.uid()) {
-                        removed.delete();
-                        drop(write);
-                        Some(removed)
-                    } else {
-                        None
-                    }
-                };
-
+            async fn delete_item(&self, metadata: &LocalMetadataItem) -> Result<()> {
+                let writes = self.writes.lock().await;
+                if !self.data.read().contains_key(metadata.uid()) {
+                    return Ok(());
+                }
+                self.stage(metadata.uid(), None).await?;
+                let removed = self.data.write().remove(metadata.uid());
                 if let Some(removed) = removed {
+                    removed.delete();
+                    self.applied();
+                    drop(writes);
                     self.send_update(SpecUpdate::Delete(removed)).await;
                 }
+                Ok(())
             }
 
             async fn apply<S>(&self, mut value: LocalStoreObject<S>) -> Result<()>
             where
                 S: Spec + Serialize,
             {
+                let writes = self.writes.lock().await;
                 let id = value.ctx().item().uid().to_owned();
-                let pointer =
-                {
-                    let mut write = self.data.write();
-                    if let Some(prev) = write.get(&id) {
-                        let prev_meta = prev.downcast_ref::<S>()?.ctx().item();
-                        let prev_rev = prev_meta.revision;
-                        if prev_meta.is_newer(value.ctx().item()) {
-                            let new_rev = value.ctx().item().revision;
-                            anyhow::bail!("attempt to update by stale value: current version: {prev_rev}, proposed: {new_rev}");
-                        }
-                        value.ctx_mut().item_mut().revision = prev_rev + 1;
-                    };
-                    let pointer = SpecPointer::new(self.spec_file_name(&id), value);
-                    write.insert(id, pointer.clone());
-                    pointer.flush::<S>()?;
-                    drop(write);
-                    pointer
+                if let Some(prev) = self.data.read().get(&id) {
+                    let prev_meta = prev.downcast_ref::<S>()?.ctx().item();
+                    let prev_rev = prev_meta.revision;
+                    if prev_meta.is_newer(value.ctx().item()) {
+                        let new_rev = value.ctx().item().revision;
+                        anyhow::bail!("attempt to update by stale value: current version: {prev_rev}, proposed: {new_rev}");
+                    }
+                    value.ctx_mut().item_mut().revision = prev_rev + 1;
                 };
+                let pointer = SpecPointer::new(self.spec_file_name(&id), value);
+                let content = pointer.content::<S>()?;
+                self.stage(&id, Some(content.clone())).await?;
+                self.data.write().insert(id, pointer.clone());
+                pointer.flush(&content)?;
+                self.applied();
+                drop(writes);
                 self.send_update(SpecUpdate::Mod(pointer)).await;
                 Ok(())
             }
@@ -521,6 +654,27 @@ cfg_if::cfg_if! {
                 }
             }
 
+            async fn stage(&self, name: &str, content: Option<String>) -> Result<()> {
+                match &self.observer {
+                    Some(observer) => {
+                        observer
+                            .stage(LocalMetadataChange {
+                                kind: self.kind.to_string(),
+                                name: name.to_string(),
+                                content,
+                            })
+                            .await
+                    }
+                    None => Ok(()),
+                }
+            }
+
+            fn applied(&self) {
+                if let Some(observer) = &self.observer {
+                    observer.applied();
+                }
+            }
+
             fn spec_file_name(&self, name: &str) -> PathBuf {
                 self.path.join(format!("{name}.yaml"))
             }
@@ -573,9 +727,13 @@ cfg_if::cfg_if! {
                 }
             }
 
-            fn flush<S: Spec>(&self) -> Result<()> {
+            fn content<S: Spec>(&self) -> Result<String> {
                 let storage: VersionedSpecStorage<S> = self.try_into()?;
-                serde_yaml::to_writer(std::fs::File::create(&self.path)?, &storage)?;
+                Ok(serde_yaml::to_string(&storage)?)
+            }
+
+            fn flush(&self, content: &str) -> Result<()> {
+                std::fs::write(&self.path, content)?;
                 Ok(())
             }
         }
@@ -783,6 +941,133 @@ spec:
                 drop(meta_folder)
             }
 
+            #[derive(Debug, Default)]
+            struct RecordingObserver(parking_lot::Mutex<Vec<LocalMetadataChange>>);
+
+            #[async_trait::async_trait]
+            impl LocalChangeObserver for RecordingObserver {
+                async fn stage(&self, change: LocalMetadataChange) -> Result<()> {
+                    self.0.lock().push(change);
+                    Ok(())
+                }
+
+                fn applied(&self) {}
+            }
+
+            #[derive(Debug)]
+            struct RejectingObserver;
+
+            #[async_trait::async_trait]
+            impl LocalChangeObserver for RejectingObserver {
+                async fn stage(&self, _change: LocalMetadataChange) -> Result<()> {
+                    Err(anyhow!("rejected"))
+                }
+
+                fn applied(&self) {
+                    panic!("rejected change applied");
+                }
+            }
+
+            #[fluvio_future::test]
+            async fn test_rejected_change_is_not_applied() {
+                //given
+                let meta_folder = tempfile::tempdir().expect("temp dir created");
+                let meta_store = LocalMetadataStorage::with_observer(&meta_folder, Arc::new(RejectingObserver));
+                let obj = default_test_store_obj();
+                let name = obj.ctx().item().uid().clone();
+
+                //when
+                let result = meta_store.apply(obj).await;
+
+                //then
+                assert!(result.is_err());
+                assert!(!meta_folder.as_ref().join(TestSpec::LABEL).join(format!("{name}.yaml")).exists());
+                let items = meta_store
+                    .retrieve_items::<TestSpec>(&NameSpace::All)
+                    .await
+                    .expect("retrieved");
+                assert!(items.items.is_empty());
+
+                drop(meta_folder)
+            }
+
+            #[fluvio_future::test]
+            async fn test_replicate_changes_to_another_folder() {
+                //given
+                let meta_folder = tempfile::tempdir().expect("temp dir created");
+                let replica_folder = tempfile::tempdir().expect("temp dir created");
+                let observer = Arc::new(RecordingObserver::default());
+                let meta_store = LocalMetadataStorage::with_observer(&meta_folder, observer.clone());
+                let obj = default_test_store_obj();
+                let other = test_store_obj("other");
+
+                //when
+                meta_store.apply(obj.clone()).await.expect("applied");
+                meta_store.apply(other.clone()).await.expect("applied");
+                meta_store
+                    .delete_item::<TestSpec>(other.ctx_owned().item_owned())
+                    .await
+                    .expect("deleted");
+                for change in observer.0.lock().iter() {
+                    LocalMetadataStorage::apply_change(&replica_folder, change).expect("change applied");
+                }
+
+                //then
+                let changes = observer.0.lock().clone();
+                assert_eq!(changes.len(), 3);
+                assert_eq!(changes[2].name, "other");
+                assert!(changes[2].content.is_none());
+                assert_eq!(
+                    LocalMetadataStorage::snapshot(&meta_folder).expect("snapshot"),
+                    LocalMetadataStorage::snapshot(&replica_folder).expect("snapshot")
+                );
+                let items = LocalMetadataStorage::new(&replica_folder)
+                    .retrieve_items::<TestSpec>(&NameSpace::All)
+                    .await
+                    .expect("retrieved");
+                assert_eq!(items.items.len(), 1);
+                assert_eq!(items.items[0].key(), obj.key());
+
+                drop(meta_folder);
+                drop(replica_folder)
+            }
+
+            #[fluvio_future::test]
+            async fn test_install_snapshot_replaces_files() {
+                //given
+                let meta_folder = tempfile::tempdir().expect("temp dir created");
+                let replica_folder = tempfile::tempdir().expect("temp dir created");
+                let meta_store = LocalMetadataStorage::new(&meta_folder);
+                let stale_store = LocalMetadataStorage::new(&replica_folder);
+                meta_store.apply(default_test_store_obj()).await.expect("applied");
+                stale_store.apply(test_store_obj("stale")).await.expect("applied");
+
+                //when
+                let snapshot = LocalMetadataStorage::snapshot(&meta_folder).expect("snapshot");
+                LocalMetadataStorage::install_snapshot(&replica_folder, &snapshot).expect("installed");
+
+                //then
+                assert_eq!(snapshot.len(), 1);
+                assert_eq!(
+                    LocalMetadataStorage::snapshot(&replica_folder).expect("snapshot"),
+                    snapshot
+                );
+                assert!(
+                    LocalMetadataStorage::apply_change(
+                        &replica_folder,
+                        &LocalMetadataChange {
+                            kind: "..".to_string(),
+                            name: "meta".to_string(),
+                            content: None,
+                        }
+                    )
+                    .is_err()
+                );
+
+                drop(meta_folder);
+                drop(replica_folder)
+            }
+
             #[fluvio_future::test]
             async fn test_update_status() {
                 //given
@@ -34,6 +34,7 @@ pub const SPU_PRIVATE_HOSTNAME: &str = "0.0.0.0";
 pub const SPU_CREDENTIALS_FILE: &str = "/etc/fluvio/.credentials/token_secret";
 pub const SPU_RETRY_SC_TIMEOUT_MS: u16 = 3000;
 pub const SPU_MIN_IN_SYNC_REPLICAS: u16 = 1;
+pub const SPU_REPLICA_LAG_TIME_MAX_MS: u64 = 30_000;
 pub const SPU_LOG_BASE_DIR: &str = "/var/lib/fluvio/data";
 pub const SPU_LOG_SIZE: &str = "10Gi";
 pub const SPU_LOG_INDEX_MAX_BYTES: u32 = 10485760;
@@ -49,10 +50,12 @@ pub const SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN: u32 = 1024; // crd
 pub const STORAGE_RETENTION_SECONDS: u32 = 7 * 24 * 3600;
 
 pub const STORAGE_RETENTION_SECONDS_MIN: u32 = 10; // crd
+pub const STORAGE_TOMBSTONE_RETENTION_SECONDS: u32 = 24 * 3600;
 pub const STORAGE_FLUSH_WRITE_COUNT: u32 = 1;
 pub const STORAGE_FLUSH_IDLE_MSEC: u32 = 0;
 pub const STORAGE_MAX_BATCH_SIZE: u32 = 2_097_152;
 pub const STORAGE_MAX_REQUEST_SIZE: u32 = 33_554_432;
+pub const STORAGE_SCRUB_MAX_BYTES_PER_SEC: u32 = 10_485_760; //10mb
 
 pub const SPU_SMARTENGINE_STORE_MAX_BYTES: usize = 1_073_741_824; //1Gb
 pub const SPU_PEER_MAX_BYTES: u32 = 10_485_760; //10mb
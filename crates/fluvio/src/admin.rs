@@ -127,7 +127,8 @@ impl FluvioAdmin {
         let (socket, config, versions) = inner_client.split();
         if let Some(watch_version) = versions.lookup_version::<ObjectApiWatchRequest>() {
             let socket = MultiplexerSocket::shared(socket);
-            let metadata = MetadataStores::start(socket.clone(), watch_version).await?;
+            let metadata =
+                MetadataStores::start(socket.clone(), config.clone(), watch_version).await?;
             let versioned_socket = VersionedSerialSocket::new(socket, config, versions);
 
             Ok(Self {
@@ -4,6 +4,8 @@ use anyhow::Result;
 use derive_builder::Builder;
 
 use fluvio_spu_schema::{server::smartmodule::SmartModuleInvocation, Isolation};
+use fluvio_smartmodule::dataplane::smartmodule::SmartModuleErrorPolicy;
+use fluvio_spu_schema::server::consumer_group::AssignmentStrategy;
 use fluvio_types::PartitionId;
 
 use crate::{FluvioError, Offset};
@@ -13,6 +15,7 @@ use super::MAX_FETCH_BYTES;
 const DEFAULT_OFFSET_FLUSH_PERIOD: Duration = Duration::from_secs(10);
 const DEFAULT_OFFSET_FLUSHER_CHECK_PERIOD: Duration = Duration::from_millis(100);
 const DEFAULT_RETRY_MODE: RetryMode = RetryMode::TryUntil(100);
+const DEFAULT_GROUP_SESSION_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Configures the behavior of consumer fetching and streaming
 #[derive(Debug, Builder, Clone)]
@@ -104,8 +107,19 @@ pub struct ConsumerConfigExt {
     pub isolation: Isolation,
     #[builder(default)]
     pub smartmodule: Vec<SmartModuleInvocation>,
+    /// topic receiving records which SmartModules with `DeadLetter` error policy fail on
+    #[builder(default, setter(strip_option, into))]
+    pub dead_letter_topic: Option<String>,
     #[builder(default = "DEFAULT_RETRY_MODE")]
     pub retry_mode: RetryMode,
+    /// consumer group to join, partitions are then assigned by group coordinator
+    #[builder(default, setter(strip_option, into))]
+    pub group: Option<String>,
+    #[builder(default)]
+    pub group_strategy: AssignmentStrategy,
+    /// member is removed from group if it doesn't send heartbeat within this time
+    #[builder(default = "DEFAULT_GROUP_SESSION_TIMEOUT")]
+    pub group_session_timeout: Duration,
 }
 
 impl ConsumerConfigExt {
@@ -133,10 +147,14 @@ impl ConsumerConfigExt {
             max_bytes,
             isolation,
             smartmodule,
+            dead_letter_topic: _,
             offset_strategy,
             offset_flush,
             offset_flusher_check_period,
             retry_mode: _,
+            group: _,
+            group_strategy: _,
+            group_session_timeout: _,
         } = self;
 
         let config = ConsumerConfig {
@@ -172,6 +190,26 @@ impl ConsumerConfigExtBuilder {
             .into());
         }
 
+        if config.dead_letter_topic.is_none()
+            && config
+                .smartmodule
+                .iter()
+                .any(|sm| sm.error_policy == SmartModuleErrorPolicy::DeadLetter)
+        {
+            return Err((FluvioError::ConsumerConfig(
+                "Dead-letter topic is required when using dead-letter error policy".to_owned(),
+            ))
+            .into());
+        }
+
+        if config.group.is_some() && !config.partition.is_empty() {
+            return Err((FluvioError::ConsumerConfig(
+                "Partitions are assigned by group coordinator when consumer group is used"
+                    .to_owned(),
+            ))
+            .into());
+        }
+
         Ok(config)
     }
 
@@ -196,7 +234,11 @@ impl From<ConsumerConfigExt> for ConsumerConfig {
             max_bytes,
             isolation,
             smartmodule,
+            dead_letter_topic: _,
             retry_mode: _,
+            group: _,
+            group_strategy: _,
+            group_session_timeout: _,
         } = value;
 
         Self {
@@ -0,0 +1,378 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_channel::{Receiver, Sender};
+use futures_util::{Stream, StreamExt};
+use tokio::select;
+use tracing::{debug, info, warn};
+
+use fluvio_future::timer::sleep;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::consumer_group::{
+    AssignmentStrategy, GroupHeartbeatRequest, JoinGroupRequest, JoinGroupResponse,
+    LeaveGroupRequest,
+};
+use fluvio_types::PartitionId;
+use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
+use fluvio_types::event::StickyEvent;
+
+use crate::spu::{SpuDirectory, SpuSocketPool};
+use crate::{Fluvio, FluvioError};
+
+use super::{
+    BoxConsumerStream, ConsumerBoxFuture, ConsumerConfigExt, ConsumerStream,
+    OffsetManagementStrategy, Record,
+};
+
+type SwitchOutput = Result<Option<BoxConsumerStream>, ErrorCode>;
+
+#[cfg(target_arch = "wasm32")]
+type BoxSwitchFuture = Pin<Box<dyn Future<Output = SwitchOutput> + 'static>>;
+#[cfg(not(target_arch = "wasm32"))]
+type BoxSwitchFuture = Pin<Box<dyn Future<Output = SwitchOutput> + Send + 'static>>;
+
+type AssignmentReceiver = Receiver<Result<Vec<PartitionId>, ErrorCode>>;
+
+/// Consumer stream of a consumer group member.
+///
+/// Partitions of the topic are assigned by group coordinator. Background task keeps membership
+/// alive with heartbeats and the stream switches to new partitions when group is rebalanced.
+/// Offsets are saved under group name, so new owner of partition continues where previous one stopped.
+/// Partition can be consumed by two members until the previous owner learns about rebalance,
+/// so records may be delivered more than once.
+pub struct ConsumerGroupStream {
+    inner: Arc<GroupStreamInner>,
+    assignments: Pin<Box<AssignmentReceiver>>,
+    /// stream of assigned partitions, none if member has no partitions or is switching
+    stream: Option<BoxConsumerStream>,
+    /// switching from revoked partitions to newly assigned ones
+    switch: Option<BoxSwitchFuture>,
+    terminated: bool,
+    shutdown: Arc<StickyEvent>,
+}
+
+struct GroupStreamInner {
+    fluvio: Fluvio,
+    config: ConsumerConfigExt,
+}
+
+impl GroupStreamInner {
+    /// stream of assigned partitions, none if member has no partitions
+    async fn create_stream(
+        &self,
+        partitions: Vec<PartitionId>,
+    ) -> Result<Option<BoxConsumerStream>, ErrorCode> {
+        if partitions.is_empty() {
+            info!("no partitions assigned");
+            return Ok(None);
+        }
+        info!(?partitions, "consuming assigned partitions");
+        let mut config = self.config.clone();
+        config.group = None;
+        config.partition = partitions;
+        let stream = self
+            .fluvio
+            .consumer_with_config(config)
+            .await
+            .map_err(|err| ErrorCode::Other(format!("{err:#}")))?;
+        Ok(Some(Box::pin(stream)))
+    }
+
+    /// flush offsets of revoked partitions and start consuming new assignment
+    async fn switch(
+        self: Arc<Self>,
+        previous: Option<BoxConsumerStream>,
+        partitions: Vec<PartitionId>,
+    ) -> SwitchOutput {
+        if let Some(mut previous) =
+            previous.filter(|_| self.config.offset_strategy != OffsetManagementStrategy::None)
+        {
+            if let Err(err) = previous.offset_flush().await {
+                warn!(%err, "failed to flush offsets of revoked partitions");
+            }
+        }
+        self.create_stream(partitions).await
+    }
+}
+
+impl ConsumerGroupStream {
+    /// join group and start consuming partitions assigned to this member
+    pub(crate) async fn new(fluvio: Fluvio, config: ConsumerConfigExt) -> Result<Self> {
+        let Some(group) = config.group.clone() else {
+            return Err(
+                FluvioError::ConsumerConfig("consumer group is not configured".to_owned()).into(),
+            );
+        };
+        let spu_pool = fluvio.spu_pool().await?;
+        let mut membership = GroupMembership {
+            spu_pool,
+            group,
+            topic: config.topic.clone(),
+            member_id: String::new(),
+            generation: 0,
+            strategy: config.group_strategy,
+            session_timeout: config.group_session_timeout,
+        };
+        let partitions = membership.join().await?;
+
+        let (sender, assignments) = async_channel::unbounded();
+        let shutdown = StickyEvent::shared();
+        let heartbeat_interval = config.group_session_timeout / 3;
+        fluvio_future::task::spawn(membership.heartbeat_loop(
+            sender,
+            heartbeat_interval,
+            shutdown.clone(),
+        ));
+
+        let inner = GroupStreamInner { fluvio, config };
+        let stream = inner.create_stream(partitions).await?;
+        Ok(Self {
+            inner: Arc::new(inner),
+            assignments: Box::pin(assignments),
+            stream,
+            switch: None,
+            terminated: false,
+            shutdown,
+        })
+    }
+}
+
+impl Drop for ConsumerGroupStream {
+    fn drop(&mut self) {
+        self.shutdown.notify();
+    }
+}
+
+impl Stream for ConsumerGroupStream {
+    type Item = Result<Record, ErrorCode>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.terminated {
+            return Poll::Ready(None);
+        }
+        loop {
+            if let Some(switch) = this.switch.as_mut() {
+                let switched = match switch.as_mut().poll(cx) {
+                    Poll::Ready(switched) => switched,
+                    Poll::Pending => return Poll::Pending,
+                };
+                this.switch = None;
+                match switched {
+                    Ok(stream) => this.stream = stream,
+                    Err(err) => return Poll::Ready(Some(Err(err))),
+                }
+            }
+
+            match this.assignments.as_mut().poll_next(cx) {
+                Poll::Ready(Some(mut assignment)) => {
+                    // only latest assignment matters
+                    while let Ok(next) = this.assignments.try_recv() {
+                        assignment = next;
+                    }
+                    let partitions = match assignment {
+                        Ok(partitions) => partitions,
+                        Err(err) => return Poll::Ready(Some(Err(err))),
+                    };
+                    let previous = this.stream.take();
+                    this.switch = Some(Box::pin(this.inner.clone().switch(previous, partitions)));
+                    continue;
+                }
+                Poll::Ready(None) => {
+                    return Poll::Ready(Some(Err(ErrorCode::Other(
+                        "consumer group membership ended".to_owned(),
+                    ))));
+                }
+                Poll::Pending => {}
+            }
+
+            let Some(stream) = this.stream.as_mut() else {
+                return Poll::Pending;
+            };
+            let next = stream.poll_next_unpin(cx);
+            if let Poll::Ready(None) = next {
+                this.terminated = true;
+            }
+            return next;
+        }
+    }
+}
+
+impl ConsumerStream for ConsumerGroupStream {
+    fn offset_commit(&mut self) -> ConsumerBoxFuture {
+        // offsets of revoked partitions are flushed while switching
+        Box::pin(async move {
+            match self.stream.as_mut() {
+                Some(stream) => stream.offset_commit().await,
+                None => Ok(()),
+            }
+        })
+    }
+
+    fn offset_flush(&mut self) -> ConsumerBoxFuture {
+        Box::pin(async move {
+            match self.stream.as_mut() {
+                Some(stream) => stream.offset_flush().await,
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+/// Membership of consumer in group, kept by heartbeat task
+struct GroupMembership {
+    spu_pool: Arc<SpuSocketPool>,
+    group: String,
+    topic: String,
+    member_id: String,
+    generation: i32,
+    strategy: AssignmentStrategy,
+    session_timeout: Duration,
+}
+
+impl GroupMembership {
+    /// join group, returns assigned partitions
+    async fn join(&mut self) -> Result<Vec<PartitionId>> {
+        let socket = self
+            .spu_pool
+            .create_serial_socket(&CONSUMER_REPLICA_KEY.into())
+            .await?;
+        if socket
+            .versions()
+            .lookup_version::<JoinGroupRequest>()
+            .is_none()
+        {
+            return Err(
+                FluvioError::Other("SPU does not support consumer groups".to_owned()).into(),
+            );
+        }
+        let JoinGroupResponse {
+            error_code,
+            member_id,
+            generation,
+            partitions,
+        } = socket
+            .send_receive(JoinGroupRequest {
+                group: self.group.clone(),
+                member_id: self.member_id.clone(),
+                topic: self.topic.clone(),
+                strategy: self.strategy,
+                session_timeout_ms: self.session_timeout.as_millis() as u32,
+            })
+            .await?;
+        if error_code.is_error() {
+            return Err(error_code.into());
+        }
+        info!(
+            group = self.group,
+            member_id,
+            generation,
+            ?partitions,
+            "joined consumer group"
+        );
+        self.member_id = member_id;
+        self.generation = generation;
+        Ok(partitions)
+    }
+
+    /// assigned partitions if group was rebalanced since last heartbeat
+    async fn heartbeat(&mut self) -> Result<Option<Vec<PartitionId>>> {
+        let socket = self
+            .spu_pool
+            .create_serial_socket(&CONSUMER_REPLICA_KEY.into())
+            .await?;
+        let response = socket
+            .send_receive(GroupHeartbeatRequest {
+                group: self.group.clone(),
+                member_id: self.member_id.clone(),
+                generation: self.generation,
+            })
+            .await?;
+        if response.error_code.is_error() {
+            return Err(response.error_code.into());
+        }
+        if response.generation == self.generation {
+            return Ok(None);
+        }
+        debug!(
+            group = self.group,
+            generation = response.generation,
+            "consumer group rebalanced"
+        );
+        self.generation = response.generation;
+        Ok(Some(response.partitions))
+    }
+
+    async fn leave(&self) -> Result<()> {
+        let socket = self
+            .spu_pool
+            .create_serial_socket(&CONSUMER_REPLICA_KEY.into())
+            .await?;
+        let response = socket
+            .send_receive(LeaveGroupRequest {
+                group: self.group.clone(),
+                member_id: self.member_id.clone(),
+            })
+            .await?;
+        if response.error_code.is_error() {
+            return Err(response.error_code.into());
+        }
+        Ok(())
+    }
+
+    /// send heartbeats until stream is dropped, joining group again if membership is lost
+    async fn heartbeat_loop(
+        mut self,
+        assignments: Sender<Result<Vec<PartitionId>, ErrorCode>>,
+        interval: Duration,
+        shutdown: Arc<StickyEvent>,
+    ) {
+        loop {
+            select! {
+                _ = shutdown.listen() => break,
+                _ = sleep(interval) => {}
+            }
+            let assignment = match self.heartbeat().await {
+                Ok(assignment) => assignment,
+                Err(err) => {
+                    warn!(
+                        group = self.group,
+                        "heartbeat failed, joining group again: {err:#}"
+                    );
+                    match self.join().await {
+                        Ok(partitions) => Some(partitions),
+                        Err(err) => match err.downcast::<ErrorCode>() {
+                            Ok(
+                                code @ (ErrorCode::InconsistentGroupProtocol(_)
+                                | ErrorCode::TopicNotFound),
+                            ) => {
+                                let _ = assignments.send(Err(code)).await;
+                                return;
+                            }
+                            Ok(code) => {
+                                warn!(group = self.group, %code, "failed to join group");
+                                None
+                            }
+                            Err(err) => {
+                                warn!(group = self.group, "failed to join group: {err:#}");
+                                None
+                            }
+                        },
+                    }
+                }
+            };
+            if let Some(partitions) = assignment {
+                if assignments.send(Ok(partitions)).await.is_err() {
+                    break;
+                }
+            }
+        }
+        if let Err(err) = self.leave().await {
+            debug!(group = self.group, "failed to leave group: {err:#}");
+        }
+    }
+}
@@ -4,6 +4,7 @@ mod config;
 mod stream;
 mod offset;
 mod retry;
+mod group;
 
 use std::future::Future;
 use std::pin::Pin;
@@ -52,7 +53,10 @@ pub use stream::{
     ConsumerBoxFuture,
 };
 pub use offset::ConsumerOffset;
+use crate::dead_letter::DeadLetterProducer;
 pub use retry::ConsumerRetryStream;
+pub use group::ConsumerGroupStream;
+pub use fluvio_spu_schema::server::consumer_group::AssignmentStrategy;
 pub use fluvio_protocol::record::ConsumerRecord;
 
 pub use fluvio_protocol::record::ConsumerRecord as Record;
@@ -60,7 +64,10 @@ pub use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
 pub use fluvio_spu_schema::server::smartmodule::SmartModuleInvocationWasm;
 pub use fluvio_spu_schema::server::smartmodule::SmartModuleKind;
 pub use fluvio_spu_schema::server::smartmodule::SmartModuleContextData;
-pub use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
+pub use fluvio_smartmodule::dataplane::smartmodule::{
+    SmartModuleErrorPolicy, SmartModuleExtraParams, Window, WindowKind, WINDOW_START_HEADER,
+    WINDOW_END_HEADER,
+};
 
 const STREAM_TO_SERVER_CHANNEL_SIZE: usize = 100;
 const MAX_ATTEMPTS_CONSUMER_OFFSET: usize = 30;
@@ -247,7 +254,7 @@ where
         config: ConsumerConfig,
     ) -> Result<impl Stream<Item = Result<Record, ErrorCode>> + use<P>> {
         let (stream, start_offset, _) = self
-            .inner_stream_batches_with_config(offset, config, None)
+            .inner_stream_batches_with_config(offset, config, None, None)
             .await?;
         let partition = self.partition;
         let flattened = stream.flat_map(move |result: Result<Batch, _>| match result {
@@ -302,7 +309,7 @@ where
         config: ConsumerConfig,
     ) -> Result<impl Stream<Item = Result<Batch, ErrorCode>> + use<P>> {
         let (stream, _start_offset, _) = self
-            .inner_stream_batches_with_config(offset, config, None)
+            .inner_stream_batches_with_config(offset, config, None, None)
             .await?;
         Ok(stream)
     }
@@ -315,6 +322,7 @@ where
         offset: Offset,
         config: ConsumerConfig,
         consumer_id: Option<String>,
+        dead_letter: Option<DeadLetterProducer>,
     ) -> Result<(
         impl Stream<Item = Result<Batch, ErrorCode>> + use<P>,
         fluvio_protocol::record::Offset,
@@ -322,10 +330,41 @@ where
     )> {
         let (stream, start_offset, stream_to_server) =
             self.request_stream(offset, config, consumer_id).await?;
+        let partition = self.partition;
+        // records are delivered only after records before them are stored in dead-letter topic
+        let stream = stream.then(move |response_result| {
+            let dead_letter = dead_letter.clone();
+            Box::pin(async move {
+                let mut response = response_result?;
+                let dead_letters = std::mem::take(&mut response.dead_letters);
+                if dead_letters.is_empty() {
+                    return Ok(response);
+                }
+                match dead_letter {
+                    Some(dead_letter) => {
+                        dead_letter
+                            .send(Some(partition), dead_letters)
+                            .await
+                            .map_err(|err| {
+                                error!("failed to send to dead-letter topic: {err:?}");
+                                ErrorCode::Other(format!(
+                                    "failed to send to dead-letter topic: {err}"
+                                ))
+                            })?;
+                    }
+                    None => {
+                        for error in dead_letters {
+                            warn!(offset = error.offset, hint = %error.hint, "dropping record SmartModule failed on");
+                        }
+                    }
+                }
+                Ok(response)
+            })
+        });
         let metrics = self.metrics.clone();
         let flattened =
             stream.flat_map(move |batch_result: Result<DefaultStreamFetchResponse, _>| {
-                let response = match batch_result {
+                let mut response = match batch_result {
                     Ok(response) => response,
                     Err(e) => return Either::Right(once(err(e))),
                 };
@@ -335,30 +374,32 @@ where
                 // This way the consumer always gets to read all records that were properly
                 // processed before hitting an error, so that the error does not obscure those records.
 
-                let inner_metrics = metrics.clone();
-                let batches =
-                    response
+                // transaction markers and records of aborted transactions are not delivered
+                let mut raw_batches = std::mem::take(&mut response.partition.records.batches);
+                raw_batches.retain(|raw_batch| {
+                    !response
                         .partition
-                        .records
-                        .batches
-                        .into_iter()
-                        .map(move |raw_batch| {
-                            inner_metrics
-                                .consumer()
-                                .add_records(raw_batch.records_len() as u64);
-                            inner_metrics
-                                .consumer()
-                                .add_bytes(raw_batch.batch_len() as u64);
-
-                            let batch: Result<Batch, _> = raw_batch.try_into();
-                            match batch {
-                                Ok(batch) => Ok(batch),
-                                Err(err) => {
-                                    tracing::error!("{err:?}");
-                                    Err(ErrorCode::Other(err.to_string()))
-                                }
-                            }
-                        });
+                        .skip_batch(raw_batch.get_header(), raw_batch.get_base_offset())
+                });
+
+                let inner_metrics = metrics.clone();
+                let batches = raw_batches.into_iter().map(move |raw_batch| {
+                    inner_metrics
+                        .consumer()
+                        .add_records(raw_batch.records_len() as u64);
+                    inner_metrics
+                        .consumer()
+                        .add_bytes(raw_batch.batch_len() as u64);
+
+                    let batch: Result<Batch, _> = raw_batch.try_into();
+                    match batch {
+                        Ok(batch) => Ok(batch),
+                        Err(err) => {
+                            tracing::error!("{err:?}");
+                            Err(ErrorCode::Other(err.to_string()))
+                        }
+                    }
+                });
                 let error = {
                     let code = response.partition.error_code;
                     match code {
@@ -417,7 +458,7 @@ where
             None
         };
 
-        let offsets = fetch_offsets(&mut serial_socket, &replica).await?;
+        let offsets = fetch_offsets(&mut serial_socket, &replica, offset.timestamp()).await?;
 
         let start_absolute_offset = offset.resolve(&offsets, consumer_offset).await?;
         let end_absolute_offset = offsets.last_stable_offset;
@@ -606,12 +647,13 @@ where
     pub(crate) async fn consumer_stream_with_config(
         self,
         config: ConsumerConfigExt,
+        dead_letter: Option<DeadLetterProducer>,
     ) -> Result<SinglePartitionConsumerStream<impl Stream<Item = Result<Record, ErrorCode>> + use<P>>>
     {
         let (offset, config, consumer_id, strategy, flush_period, flusher_check_period) =
             config.into_parts();
         let (stream, start_offset, stream_to_server) = self
-            .inner_stream_batches_with_config(offset, config, consumer_id)
+            .inner_stream_batches_with_config(offset, config, consumer_id, dead_letter)
             .await?;
         let partition = self.partition;
         let flattened = stream.flat_map(move |result: Result<Batch, _>| match result {
@@ -994,4 +1036,27 @@ mod tests {
     fn test_consumer_config_default() {
         let _config = ConsumerConfig::builder().build().unwrap();
     }
+
+    #[test]
+    fn test_consumer_config_dead_letter_topic() {
+        use fluvio_smartmodule::dataplane::smartmodule::SmartModuleErrorPolicy;
+        use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
+
+        let smartmodule = vec![SmartModuleInvocation {
+            error_policy: SmartModuleErrorPolicy::DeadLetter,
+            ..Default::default()
+        }];
+        let mut builder = ConsumerConfigExt::builder();
+        builder
+            .topic("orders")
+            .offset_start(Offset::beginning())
+            .smartmodule(smartmodule);
+        assert!(builder.build().is_err());
+
+        let config = builder
+            .dead_letter_topic("orders-dlq")
+            .build()
+            .expect("config");
+        assert_eq!(config.dead_letter_topic.as_deref(), Some("orders-dlq"));
+    }
 }
@@ -0,0 +1,138 @@
+//!
+//! # Dead-letter topic
+//!
+//! Records which SmartModules with `DeadLetter` error policy fail on are dropped
+//! from the consumer stream or producer and sent to the dead-letter topic along with the error.
+//!
+
+use std::fmt;
+
+use anyhow::Result;
+use futures_util::future::{BoxFuture, FutureExt};
+use tracing::debug;
+
+use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
+use fluvio_protocol::record::Record;
+use fluvio_types::PartitionId;
+
+use crate::TopicProducerPool;
+
+/// topic the failing record was consumed from or produced to
+pub const DEAD_LETTER_TOPIC_HEADER: &str = "fluvio-dead-letter-topic";
+/// partition the failing record was consumed from, not set for produced records
+pub const DEAD_LETTER_PARTITION_HEADER: &str = "fluvio-dead-letter-partition";
+/// offset of the failing record
+pub const DEAD_LETTER_OFFSET_HEADER: &str = "fluvio-dead-letter-offset";
+/// kind of SmartModule which failed
+pub const DEAD_LETTER_SMARTMODULE_KIND_HEADER: &str = "fluvio-dead-letter-smartmodule-kind";
+/// error returned by SmartModule
+pub const DEAD_LETTER_ERROR_HEADER: &str = "fluvio-dead-letter-error";
+
+#[derive(Clone)]
+pub(crate) struct DeadLetterProducer {
+    topic: String,
+    producer: TopicProducerPool,
+}
+
+impl DeadLetterProducer {
+    /// `topic` is the topic being consumed or produced to
+    pub(crate) fn new(topic: String, producer: TopicProducerPool) -> Self {
+        Self { topic, producer }
+    }
+
+    /// Send failing records and wait until they are stored,
+    /// so consumer offset is not committed past records which are lost.
+    /// Future is boxed because producer sends its own dead letters through here.
+    pub(crate) fn send(
+        &self,
+        partition: Option<PartitionId>,
+        errors: Vec<SmartModuleTransformRuntimeError>,
+    ) -> BoxFuture<'_, Result<()>> {
+        async move {
+            debug!(
+                topic = %self.topic,
+                ?partition,
+                records = errors.len(),
+                "sending records to dead-letter topic"
+            );
+            for error in errors {
+                self.producer
+                    .send_record(dead_letter_record(&self.topic, partition, error))
+                    .await?;
+            }
+            self.producer.flush().await
+        }
+        .boxed()
+    }
+}
+
+impl fmt::Debug for DeadLetterProducer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeadLetterProducer")
+            .field("topic", &self.topic)
+            .field("dead_letter_topic", &self.producer.topic())
+            .finish()
+    }
+}
+
+fn dead_letter_record(
+    topic: &str,
+    partition: Option<PartitionId>,
+    error: SmartModuleTransformRuntimeError,
+) -> Record {
+    let mut record = Record::new(error.record_value);
+    record.key = error.record_key;
+    record = record.with_header(DEAD_LETTER_TOPIC_HEADER, topic.to_owned());
+    if let Some(partition) = partition {
+        record = record.with_header(DEAD_LETTER_PARTITION_HEADER, partition.to_string());
+    }
+    record
+        .with_header(DEAD_LETTER_OFFSET_HEADER, error.offset.to_string())
+        .with_header(DEAD_LETTER_SMARTMODULE_KIND_HEADER, error.kind.to_string())
+        .with_header(DEAD_LETTER_ERROR_HEADER, error.hint)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::link::smartmodule::SmartModuleKind;
+    use fluvio_protocol::record::RecordData;
+
+    use super::*;
+
+    #[test]
+    fn test_dead_letter_record() {
+        let error = SmartModuleTransformRuntimeError {
+            hint: "invalid digit found in string".to_owned(),
+            offset: 42,
+            kind: SmartModuleKind::Map,
+            record_key: Some(RecordData::from("key")),
+            record_value: RecordData::from("abc"),
+        };
+
+        let record = dead_letter_record("orders", Some(2), error.clone());
+
+        assert_eq!(record.key().map(|key| key.as_ref()), Some(b"key".as_ref()));
+        assert_eq!(record.value().as_ref(), b"abc");
+        let header = |name| {
+            record
+                .headers()
+                .get(name)
+                .map(|value| value.as_utf8_lossy_string().to_string())
+        };
+        assert_eq!(header(DEAD_LETTER_TOPIC_HEADER).as_deref(), Some("orders"));
+        assert_eq!(header(DEAD_LETTER_PARTITION_HEADER).as_deref(), Some("2"));
+        assert_eq!(header(DEAD_LETTER_OFFSET_HEADER).as_deref(), Some("42"));
+        assert_eq!(
+            header(DEAD_LETTER_SMARTMODULE_KIND_HEADER).as_deref(),
+            Some("Map")
+        );
+        assert_eq!(
+            header(DEAD_LETTER_ERROR_HEADER).as_deref(),
+            Some("invalid digit found in string")
+        );
+
+        let record = dead_letter_record("orders", None, error);
+        assert!(record.headers().get(DEAD_LETTER_PARTITION_HEADER).is_none());
+    }
+}
@@ -18,9 +18,11 @@ use fluvio_socket::{
 
 use crate::admin::FluvioAdmin;
 use crate::consumer::{
-    ConsumerConfigExt, ConsumerOffset, ConsumerRetryStream, ConsumerStream,
-    MultiplePartitionConsumer, MultiplePartitionConsumerStream, PartitionSelectionStrategy, Record,
+    ConsumerConfigExt, ConsumerGroupStream, ConsumerOffset, ConsumerRetryStream, ConsumerStream,
+    OffsetManagementStrategy, MultiplePartitionConsumer, MultiplePartitionConsumerStream,
+    PartitionSelectionStrategy, Record,
 };
+use crate::dead_letter::DeadLetterProducer;
 use crate::error::anyhow_version_error;
 use crate::metrics::ClientMetrics;
 use crate::producer::{TopicProducerPool, TopicProducerConfig};
@@ -136,7 +138,8 @@ impl Fluvio {
             check_platform_compatible(versions.platform_version())?;
 
             let socket = MultiplexerSocket::shared(socket);
-            let metadata = MetadataStores::start(socket.clone(), watch_version).await?;
+            let metadata =
+                MetadataStores::start(socket.clone(), config.clone(), watch_version).await?;
 
             let spu_pool = OnceCell::new();
             Ok(Self {
@@ -156,11 +159,15 @@ impl Fluvio {
     }
 
     /// lazy get spu pool
-    async fn spu_pool(&self) -> Result<Arc<SpuSocketPool>> {
+    pub(crate) async fn spu_pool(&self) -> Result<Arc<SpuSocketPool>> {
         self.spu_pool
             .get_or_try_init(|| async {
-                let metadata =
-                    MetadataStores::start(self.socket.clone(), self.watch_version).await?;
+                let metadata = MetadataStores::start(
+                    self.socket.clone(),
+                    self.config.clone(),
+                    self.watch_version,
+                )
+                .await?;
                 let pool = SpuSocketPool::start(self.config.clone(), metadata);
                 Ok(Arc::new(pool?))
             })
@@ -355,9 +362,63 @@ impl Fluvio {
         impl ConsumerStream<Item = std::result::Result<Record, fluvio_protocol::link::ErrorCode>>
         + use<>,
     > {
+        if config.group.is_some() {
+            return Err(FluvioError::ConsumerConfig(
+                "use consumer_group to consume as member of consumer group".to_owned(),
+            )
+            .into());
+        }
         ConsumerRetryStream::new(self, self.cluster_config.clone(), config).await
     }
 
+    /// Creates a new [ConsumerStream] as member of the consumer group set in config.
+    ///
+    /// Partitions of the topic are divided between members of the group by the group coordinator
+    /// and reassigned when members join or leave the group, or the topic gets new partitions.
+    /// Offsets are saved with the group name as consumer id, unless other consumer id is set,
+    /// and are committed automatically unless other offset strategy is set.
+    ///
+    /// Example:
+    /// ```no_run
+    /// use fluvio::{consumer::ConsumerConfigExtBuilder, Fluvio, Offset};
+    /// use futures_util::StreamExt;
+    /// async fn do_consume_in_group(fluvio: &Fluvio) -> anyhow::Result<()> {
+    ///    let mut stream = fluvio
+    ///        .consumer_group(
+    ///            ConsumerConfigExtBuilder::default()
+    ///                .topic("my-topic".to_string())
+    ///                .group("my-group".to_string())
+    ///                .offset_start(Offset::beginning())
+    ///                .build()?,
+    ///        )
+    ///        .await?;
+    ///    while let Some(Ok(record)) = stream.next().await {
+    ///        println!("{}", String::from_utf8_lossy(record.as_ref()));
+    ///    }
+    ///    Ok(())
+    /// }
+    /// ```
+    pub async fn consumer_group(
+        &self,
+        mut config: ConsumerConfigExt,
+    ) -> Result<ConsumerGroupStream> {
+        let Some(group) = &config.group else {
+            return Err(
+                FluvioError::ConsumerConfig("consumer group is not configured".to_owned()).into(),
+            );
+        };
+        if config.offset_consumer.is_none() {
+            config.offset_consumer = Some(group.clone());
+        }
+        if config.offset_strategy == OffsetManagementStrategy::None {
+            config.offset_strategy = OffsetManagementStrategy::Auto;
+        }
+        let fluvio =
+            Fluvio::connect_with_connector(self.config.connector().clone(), &self.cluster_config)
+                .await?;
+        ConsumerGroupStream::new(fluvio, config).await
+    }
+
     /// Creates a new [ConsumerStream] instance without retry logic.
     pub(crate) async fn consumer_with_config_inner(
         &self,
@@ -414,11 +475,22 @@ impl Fluvio {
         } else {
             config.partition.clone()
         };
+        let dead_letter = match &config.dead_letter_topic {
+            Some(dead_letter_topic) => Some(DeadLetterProducer::new(
+                topic.clone(),
+                self.topic_producer(dead_letter_topic).await?,
+            )),
+            None => None,
+        };
         let mut partition_streams = Vec::with_capacity(partitions.len());
         for partition in partitions {
             let consumer =
                 PartitionConsumer::new(topic.clone(), partition, spu_pool.clone(), self.metrics());
-            partition_streams.push(consumer.consumer_stream_with_config(config.clone()).await?);
+            partition_streams.push(
+                consumer
+                    .consumer_stream_with_config(config.clone(), dead_letter.clone())
+                    .await?,
+            );
         }
         Ok(MultiplePartitionConsumerStream::new(partition_streams))
     }
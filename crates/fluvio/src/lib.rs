@@ -5,6 +5,7 @@
 #![doc = include_str!("../README.md")]
 
 mod admin;
+mod dead_letter;
 mod error;
 mod fluvio;
 mod offset;
@@ -15,14 +16,16 @@ pub mod config;
 pub mod consumer;
 pub mod metrics;
 pub mod spu;
+#[cfg(all(feature = "otel", not(target_arch = "wasm32")))]
+pub mod telemetry;
 
 pub use error::FluvioError;
 pub use config::{FluvioClusterConfig, FluvioConfig};
 pub use producer::{
     ProducerCallback, SharedProducerCallback, ProduceCompletionBatchEvent,
     TopicProducerConfigBuilder, TopicProducerConfig, TopicProducer, TopicProducerPool, RecordKey,
-    ProduceOutput, FutureRecordMetadata, RecordMetadata, DeliverySemantic, RetryPolicy,
-    RetryStrategy, Partitioner, PartitionerConfig, ProducerError,
+    RecordHeaders, ProduceOutput, FutureRecordMetadata, RecordMetadata, DeliverySemantic,
+    RetryPolicy, RetryStrategy, Partitioner, PartitionerConfig, ProducerError,
 };
 #[cfg(feature = "smartengine")]
 pub use producer::{SmartModuleChainBuilder, SmartModuleConfig, SmartModuleInitialData};
@@ -32,10 +35,16 @@ pub use fluvio_spu_schema::Isolation;
 pub use consumer::{
     PartitionConsumer, ConsumerConfig, MultiplePartitionConsumer, PartitionSelectionStrategy,
     SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind, SmartModuleContextData,
-    SmartModuleExtraParams,
+    SmartModuleExtraParams, SmartModuleErrorPolicy, Window, WindowKind, WINDOW_START_HEADER,
+    WINDOW_END_HEADER,
 };
 pub use offset::Offset;
 
+pub use dead_letter::{
+    DEAD_LETTER_TOPIC_HEADER, DEAD_LETTER_PARTITION_HEADER, DEAD_LETTER_OFFSET_HEADER,
+    DEAD_LETTER_SMARTMODULE_KIND_HEADER, DEAD_LETTER_ERROR_HEADER,
+};
+
 pub use crate::admin::FluvioAdmin;
 pub use crate::fluvio::Fluvio;
 
@@ -196,6 +205,18 @@ pub mod metadata {
         pub use fluvio_sc_schema::tableformat::*;
     }
 
+    pub mod schema {
+        pub use fluvio_sc_schema::schema::*;
+    }
+
+    pub mod policy {
+        pub use fluvio_sc_schema::policy::*;
+    }
+
+    pub mod quota {
+        pub use fluvio_sc_schema::quota::*;
+    }
+
     pub mod core {
         pub use fluvio_sc_schema::core::*;
     }
@@ -5,6 +5,7 @@ use tracing::{debug, trace};
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetPartitionResponse;
+use fluvio_spu_schema::server::fetch_offset::TIMESTAMP_OFFSET_API;
 
 use crate::FluvioError;
 use fluvio_socket::VersionedSerialSocket;
@@ -14,6 +15,7 @@ pub(crate) enum OffsetInner {
     Absolute(i64),
     FromBeginning(i64),
     FromEnd(i64),
+    Timestamp(i64),
 }
 
 impl OffsetInner {
@@ -36,6 +38,10 @@ impl OffsetInner {
                 };
                 resolved.clamp(offsets.start_offset, offsets.last_stable_offset)
             }
+            Self::Timestamp(_) => offsets
+                .timestamp_offset
+                .unwrap_or(offsets.last_stable_offset)
+                .clamp(offsets.start_offset, offsets.last_stable_offset),
         }
     }
 }
@@ -95,6 +101,10 @@ impl OffsetInner {
 ///        FromEnd Offset:    9,  8,  7,  6,  5,  4,  3,  2,  1,  0
 /// ```
 ///
+/// An offset may also be selected by time. A `Timestamp` offset points
+/// to the first event which was produced at or after the given time. If all
+/// events are older, it points to the end of the log, just like `FromEnd(0)`.
+///
 /// # Example
 ///
 /// All offsets must be constructed with a positive index. Negative
@@ -279,6 +289,37 @@ impl Offset {
         }
     }
 
+    /// Creates an offset pointing to the first log entry at or after the given time
+    ///
+    /// The timestamp is in milliseconds since Unix epoch, same as the
+    /// timestamp of a consumed record. The offset is resolved by the SPU
+    /// when the stream is started. If every entry in the log is older than
+    /// the timestamp, the offset points to the end of the log.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio::Offset;
+    /// # use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    /// // Creates an offset pointing to the first entry produced in the last hour
+    /// let hour_ago = SystemTime::now() - Duration::from_secs(3600);
+    /// let timestamp = hour_ago.duration_since(UNIX_EPOCH).unwrap().as_millis() as i64;
+    /// let offset: Offset = Offset::from_timestamp(timestamp);
+    /// ```
+    pub fn from_timestamp(timestamp: i64) -> Offset {
+        Self {
+            inner: OffsetInner::Timestamp(timestamp),
+        }
+    }
+
+    /// timestamp that must be resolved by SPU
+    pub(crate) fn timestamp(&self) -> Option<i64> {
+        match self.inner {
+            OffsetInner::Timestamp(timestamp) => Some(timestamp),
+            _ => None,
+        }
+    }
+
     /// Converts this offset into an absolute offset
     ///
     /// If this offset is relative from the beginning (i.e. it was created
@@ -312,13 +353,27 @@ impl Offset {
 pub(crate) async fn fetch_offsets(
     client: &mut VersionedSerialSocket,
     replica: &ReplicaKey,
+    timestamp: Option<i64>,
 ) -> Result<FetchOffsetPartitionResponse, FluvioError> {
     debug!("fetching offset for replica: {}", replica);
 
+    if timestamp.is_some() {
+        let version = client
+            .versions()
+            .lookup_version::<FetchOffsetsRequest>()
+            .unwrap_or_default();
+        if version < TIMESTAMP_OFFSET_API {
+            return Err(FluvioError::Other(
+                "SPU does not support timestamp offsets".to_owned(),
+            ));
+        }
+    }
+
     let response = client
-        .send_receive(FetchOffsetsRequest::new(
+        .send_receive(FetchOffsetsRequest::with_timestamp(
             replica.topic.to_owned(),
             replica.partition,
+            timestamp,
         ))
         .await?;
 
@@ -352,6 +407,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
@@ -366,6 +422,7 @@ mod tests {
             partition_index: 0,
             start_offset: 5,
             last_stable_offset: 10,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
@@ -380,6 +437,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::FromBeginning(15);
@@ -394,6 +452,7 @@ mod tests {
             partition_index: 0,
             start_offset: 5,
             last_stable_offset: 10,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::FromBeginning(15);
@@ -408,6 +467,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::FromEnd(3);
@@ -422,6 +482,7 @@ mod tests {
             partition_index: 0,
             start_offset: 6,
             last_stable_offset: 10,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::FromEnd(6);
@@ -436,6 +497,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::FromEnd(100);
@@ -452,6 +514,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::Absolute(4);
@@ -467,6 +530,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 15,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
@@ -482,6 +546,7 @@ mod tests {
             partition_index: 0,
             start_offset: 10,
             last_stable_offset: 22,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::FromBeginning(5);
@@ -497,6 +562,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 15,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::FromEnd(3);
@@ -512,6 +578,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 15,
+            timestamp_offset: None,
         };
 
         let offset_inner = OffsetInner::FromEnd(10);
@@ -519,4 +586,34 @@ mod tests {
         let absolute = offset_inner.resolve(&offsets, Some(5));
         assert_eq!(absolute, 0);
     }
+
+    #[test]
+    fn test_offset_timestamp() {
+        let offsets = FetchOffsetPartitionResponse {
+            error_code: Default::default(),
+            partition_index: 0,
+            start_offset: 5,
+            last_stable_offset: 15,
+            timestamp_offset: Some(8),
+        };
+
+        let offset_inner = OffsetInner::Timestamp(1_700_000_000_000);
+        // consumer offset is ignored for timestamp
+        assert_eq!(offset_inner.resolve(&offsets, Some(12)), 8);
+    }
+
+    #[test]
+    fn test_offset_timestamp_not_found() {
+        let offsets = FetchOffsetPartitionResponse {
+            error_code: Default::default(),
+            partition_index: 0,
+            start_offset: 5,
+            last_stable_offset: 15,
+            timestamp_offset: None,
+        };
+
+        // all records are older, start from end
+        let offset_inner = OffsetInner::Timestamp(1_700_000_000_000);
+        assert_eq!(offset_inner.resolve(&offsets, None), 15);
+    }
 }
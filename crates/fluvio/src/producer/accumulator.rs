@@ -377,6 +377,11 @@ impl ProducePartitionResponseFuture {
         }
     }
 
+    /// Returns true if response is already available and contains error.
+    pub(crate) fn is_ready_error(&self) -> bool {
+        matches!(&self.inner, Either::Right(Some((_, error))) if error.is_error())
+    }
+
     /// Returns a future that firstly will resolve [`ProduceResponse`] from the given `response_fut`,
     /// and then will look up the partition response using `num`. [`ProduceResponseFuture`] is usually
     /// shared between other [`ProducePartitionResponseFuture`] and will be resolved only once and
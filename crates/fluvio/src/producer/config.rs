@@ -29,6 +29,8 @@ const DEFAULT_INITIAL_DELAY: Duration = Duration::from_millis(20);
 const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(200);
 const DEFAULT_MAX_RETRIES: usize = 4;
 
+const DEFAULT_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(60);
+
 fn default_batch_size() -> usize {
     DEFAULT_BATCH_SIZE_BYTES
 }
@@ -61,6 +63,10 @@ fn default_delivery() -> DeliverySemantic {
     DeliverySemantic::default()
 }
 
+fn default_transaction_timeout() -> Duration {
+    DEFAULT_TRANSACTION_TIMEOUT
+}
+
 // This is needed only to bypass the partitioner property when debugging
 impl fmt::Debug for Box<dyn Partitioner + Send + Sync> {
     fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -124,6 +130,32 @@ pub struct TopicProducerConfig {
     /// Callback that will be called after the record is sent to the server.
     #[builder(setter(into, strip_option), default)]
     pub(crate) callback: Option<SharedProducerCallback>,
+
+    /// Idempotent producer stamps batches with producer id and sequence numbers, so
+    /// the SPU can detect batches which were already written and not write them twice on retry.
+    /// Requires [`DeliverySemantic::AtLeastOnce`].
+    #[builder(default)]
+    pub(crate) idempotent: bool,
+
+    /// Transactional producer writes records between [`TopicProducer::begin_transaction`] and
+    /// [`TopicProducer::commit_transaction`] atomically, across partitions and topics.
+    /// Records of aborted transactions are not delivered to consumers.
+    /// Implies idempotent producer.
+    ///
+    /// [`TopicProducer::begin_transaction`]: crate::TopicProducer::begin_transaction
+    /// [`TopicProducer::commit_transaction`]: crate::TopicProducer::commit_transaction
+    #[builder(default)]
+    pub(crate) transactional: bool,
+
+    /// Transaction which is not ended within this time is aborted by the SPU.
+    /// SPU limits it to 15 minutes.
+    #[builder(default = "default_transaction_timeout()")]
+    pub(crate) transaction_timeout: Duration,
+
+    /// Id of registered schema written to every batch.
+    /// Topic which requires schema only accepts batches with id registered for its subject.
+    #[builder(setter(into, strip_option), default)]
+    pub(crate) schema_id: Option<u32>,
 }
 
 impl TopicProducerConfigBuilder {
@@ -168,6 +200,22 @@ impl TopicProducerConfig {
     pub fn smartmodules(&self) -> &Vec<SmartModuleInvocation> {
         &self.smartmodules
     }
+
+    pub fn idempotent(&self) -> bool {
+        self.idempotent || self.transactional
+    }
+
+    pub fn transactional(&self) -> bool {
+        self.transactional
+    }
+
+    pub fn transaction_timeout(&self) -> Duration {
+        self.transaction_timeout
+    }
+
+    pub fn schema_id(&self) -> Option<u32> {
+        self.schema_id
+    }
 }
 
 impl Default for TopicProducerConfig {
@@ -184,6 +232,10 @@ impl Default for TopicProducerConfig {
             delivery_semantic: default_delivery(),
             smartmodules: vec![],
             callback: None,
+            idempotent: false,
+            transactional: false,
+            transaction_timeout: default_transaction_timeout(),
+            schema_id: None,
         }
     }
 }
@@ -15,10 +15,12 @@ use anyhow::Result;
 
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::record::Record;
+use fluvio_spu_schema::server::producer::InitProducerIdRequest;
+use fluvio_spu_schema::server::transaction::EndTxnRequest;
 use fluvio_compression::Compression;
 #[cfg(feature = "compress")]
 use fluvio_sc_schema::topic::CompressionAlgorithm;
-use fluvio_types::PartitionId;
+use fluvio_types::{PartitionId, SpuId};
 use fluvio_types::event::StickyEvent;
 
 mod accumulator;
@@ -29,10 +31,11 @@ mod record;
 mod partitioning;
 mod partition_producer;
 mod memory_batch;
+mod transaction;
 
 pub mod event;
 
-pub use fluvio_protocol::record::{RecordKey, RecordData};
+pub use fluvio_protocol::record::{RecordKey, RecordData, RecordHeaders};
 
 use crate::spu::SpuPool;
 use crate::spu::SpuSocketPool;
@@ -56,6 +59,7 @@ pub use self::error::ProducerError;
 use self::event::EventHandler;
 pub use self::output::ProduceOutput;
 use self::partition_producer::PartitionProducer;
+use self::transaction::ProducerTransaction;
 pub use self::record::{FutureRecordMetadata, RecordMetadata};
 
 /// Pool of producers for a given topic. There is a producer per partition
@@ -79,16 +83,16 @@ where
     batch_events: Arc<BatchEvents>,
     client_metric: Arc<ClientMetrics>,
     callback: Option<SharedProducerCallback>,
+    producer_id: Option<i64>,
+    transaction: Option<Arc<ProducerTransaction>>,
 }
 
 impl ProducerPool {
+    /// start producer for each partition, batches of partitions replace batches of `params`
     fn new<S>(
-        config: Arc<TopicProducerConfig>,
+        params: PartitionProducerParams<S>,
         topic: String,
-        spu_pool: Arc<S>,
         batches: Arc<HashMap<PartitionId, BatchHandler>>,
-        client_metric: Arc<ClientMetrics>,
-        callback: Option<SharedProducerCallback>,
     ) -> Self
     where
         S: SpuPool + Send + Sync + 'static,
@@ -102,17 +106,19 @@ impl ProducerPool {
             let replica = ReplicaKey::new(topic.clone(), *partition_id);
             let error = Arc::new(RwLock::new(None));
 
-            let params = PartitionProducerParams {
-                config: config.clone(),
-                spu_pool: spu_pool.clone(),
+            let partition_params = PartitionProducerParams {
+                config: params.config.clone(),
+                spu_pool: params.spu_pool.clone(),
                 batches_deque: batch_list.clone(),
                 batch_events: batch_events.clone(),
-                client_metric: client_metric.clone(),
-                callback: callback.clone(),
+                client_metric: params.client_metric.clone(),
+                callback: params.callback.clone(),
+                producer_id: params.producer_id,
+                transaction: params.transaction.clone(),
             };
 
             PartitionProducer::start(
-                params,
+                partition_params,
                 error.clone(),
                 end_event.clone(),
                 flush_event.clone(),
@@ -222,6 +228,8 @@ where
     inner: Arc<InnerTopicProducer<S>>,
     #[cfg(feature = "smartengine")]
     sm_chain: Option<Arc<RwLock<fluvio_smartengine::SmartModuleChainInstance>>>,
+    #[cfg(feature = "smartengine")]
+    dead_letter: Option<Arc<crate::dead_letter::DeadLetterProducer>>,
     #[allow(unused)]
     metrics: Arc<ClientMetrics>,
 }
@@ -236,6 +244,8 @@ where
     record_accumulator: Arc<RecordAccumulator>,
     producer_pool: Arc<RwLock<ProducerPool>>,
     metrics: Arc<ClientMetrics>,
+    producer_id: Option<i64>,
+    transaction: Option<Arc<ProducerTransaction>>,
 }
 
 impl<S> InnerTopicProducer<S>
@@ -249,6 +259,15 @@ where
     }
 
     async fn push_record(self: Arc<Self>, record: Record) -> Result<PushRecord> {
+        if let Some(transaction) = &self.transaction {
+            if !transaction.is_active().await {
+                return Err(FluvioError::Producer(ProducerError::Transaction(
+                    "transactional producer requires active transaction".to_owned(),
+                ))
+                .into());
+            }
+        }
+
         let topics = self.spu_pool.topics();
 
         let topic_spec = topics
@@ -279,6 +298,8 @@ where
             batch_events: BatchEvents::shared(),
             client_metric: self.metrics.clone(),
             callback: self.config.callback.clone(),
+            producer_id: self.producer_id,
+            transaction: self.transaction.clone(),
         };
 
         let _ = producer_pool
@@ -330,6 +351,16 @@ cfg_if::cfg_if! {
                 Ok(self)
             }
 
+            /// Sends records which SmartModules with `DeadLetter` error policy fail on
+            /// to the topic of `dead_letter` producer, otherwise send fails on such records
+            pub fn with_dead_letter(mut self, dead_letter: TopicProducerPool) -> Self {
+                self.dead_letter = Some(Arc::new(crate::dead_letter::DeadLetterProducer::new(
+                    self.inner.topic.clone(),
+                    dead_letter,
+                )));
+                self
+            }
+
             /// Adds a SmartModule filter to this TopicProducer
             pub async fn with_filter(
                 self,
@@ -411,6 +442,27 @@ where
         spu_pool: Arc<S>,
         config: Arc<TopicProducerConfig>,
         metrics: Arc<ClientMetrics>,
+    ) -> Result<Self> {
+        let transaction = if config.transactional {
+            let (producer_id, coordinator) =
+                init_producer_id(spu_pool.as_ref(), &topic, &config).await?;
+            Some(Arc::new(ProducerTransaction::new(
+                producer_id,
+                coordinator,
+                config.transaction_timeout,
+            )))
+        } else {
+            None
+        };
+        Self::new_with_transaction(topic, spu_pool, config, metrics, transaction).await
+    }
+
+    async fn new_with_transaction(
+        topic: String,
+        spu_pool: Arc<S>,
+        config: Arc<TopicProducerConfig>,
+        metrics: Arc<ClientMetrics>,
+        transaction: Option<Arc<ProducerTransaction>>,
     ) -> Result<Self> {
         let topics = spu_pool.topics();
         let topic_spec: fluvio_sc_schema::topic::TopicSpec = topics
@@ -428,6 +480,18 @@ where
             }
         }
 
+        let producer_id = if let Some(transaction) = &transaction {
+            Some(transaction.producer_id())
+        } else if config.idempotent {
+            Some(
+                init_producer_id(spu_pool.as_ref(), &topic, &config)
+                    .await?
+                    .0,
+            )
+        } else {
+            None
+        };
+
         let record_accumulator = RecordAccumulator::new(
             config.batch_size,
             config.max_request_size,
@@ -435,26 +499,40 @@ where
             partition_count,
             compression,
         );
-        let producer_pool = ProducerPool::new(
-            config.clone(),
+        let params = PartitionProducerParams {
+            config: config.clone(),
+            spu_pool: spu_pool.clone(),
+            batches_deque: BatchesDeque::shared(),
+            batch_events: BatchEvents::shared(),
+            client_metric: metrics.clone(),
+            callback: config.callback.clone(),
+            producer_id,
+            transaction: transaction.clone(),
+        };
+        let producer_pool = Arc::new(RwLock::new(ProducerPool::new(
+            params,
             topic.clone(),
-            spu_pool.clone(),
             Arc::new(record_accumulator.batches().await),
-            metrics.clone(),
-            config.callback.clone(),
-        );
+        )));
+        if let Some(transaction) = &transaction {
+            transaction.add_producer_pool(&producer_pool).await;
+        }
 
         Ok(Self {
             inner: Arc::new(InnerTopicProducer {
                 config,
                 topic,
                 spu_pool,
-                producer_pool: Arc::new(RwLock::new(producer_pool)),
+                producer_pool,
                 record_accumulator: Arc::new(record_accumulator),
                 metrics: metrics.clone(),
+                producer_id,
+                transaction,
             }),
             #[cfg(feature = "smartengine")]
             sm_chain: Default::default(),
+            #[cfg(feature = "smartengine")]
+            dead_letter: Default::default(),
             metrics,
         })
     }
@@ -512,7 +590,36 @@ where
     ) -> Result<ProduceOutput> {
         let record_key = key.into();
         let record_value = value.into();
-        let record = Record::from((record_key, record_value));
+        self.send_record(Record::from((record_key, record_value)))
+            .await
+    }
+
+    /// Sends a record, including its headers, to this producer's Topic.
+    ///
+    /// This behaves like `TopicProducer::send` but accepts a fully built `Record`,
+    /// which allows headers to be attached. Trace context for consumers can be attached
+    /// with `Record::with_trace_context`; with the `otel` feature, context of the current
+    /// span is attached when record doesn't have one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio::{TopicProducerPool, FluvioError};
+    /// # use fluvio::dataplane::record::Record;
+    /// # async fn example(producer: &TopicProducerPool) -> anyhow::Result<()> {
+    /// let record = Record::new_key_value("Key", "Value")
+    ///     .with_header("content-type", "text/plain");
+    /// producer.send_record(record).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(
+        skip(self, record),
+        fields(topic = %self.inner.topic),
+    )]
+    pub async fn send_record(&self, record: Record) -> Result<ProduceOutput> {
+        #[cfg(all(feature = "otel", not(target_arch = "wasm32")))]
+        let record = crate::telemetry::propagate_current_context(record);
 
         cfg_if::cfg_if! {
             if #[cfg(feature = "smartengine")] {
@@ -532,11 +639,23 @@ where
 
                     sm_input.set_base_timestamp(current_time);
                     let output = sm_chain.process(sm_input).map_err(|e| FluvioError::Other(format!("SmartEngine - {e:?}")))?;
+                    let dead_letters = sm_chain.take_dead_letters();
 
                     // update_smartmodule metrics needs to access the sm_chain
                     // w/ a read lock so we need to drop the write lock first
                     drop(sm_chain);
                     self.update_smartmodule_metrics().await?;
+                    if !dead_letters.is_empty() {
+                        match &self.dead_letter {
+                            Some(dead_letter) => dead_letter.send(None, dead_letters).await?,
+                            None => {
+                                return Err(FluvioError::Other(format!(
+                                    "SmartModule with dead-letter error policy failed on {} records, producer has no dead-letter topic",
+                                    dead_letters.len()
+                                )).into());
+                            }
+                        }
+                    }
                     entries = output.successes;
                 }
             } else {
@@ -569,6 +688,65 @@ where
         Ok(results)
     }
 
+    /// Start transaction of transactional producer.
+    /// Records sent until the transaction is committed are not visible to `ReadCommitted` consumers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio::{TopicProducerPool, FluvioError};
+    /// # async fn example(producer: &TopicProducerPool) -> anyhow::Result<()> {
+    /// producer.begin_transaction().await?;
+    /// producer.send("Key", "Value").await?;
+    /// producer.commit_transaction().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn begin_transaction(&self) -> Result<()> {
+        self.transaction()?.begin().await?;
+        Ok(())
+    }
+
+    /// Send all pending records of transaction and commit it.
+    /// Transaction includes records sent by producers created with [`TopicProducer::transaction_producer`].
+    pub async fn commit_transaction(&self) -> Result<()> {
+        self.transaction()?
+            .end(self.inner.spu_pool.as_ref(), true)
+            .await?;
+        Ok(())
+    }
+
+    /// Abort transaction, its records are not delivered to `ReadCommitted` consumers.
+    /// Transaction is aborted even if its pending records can't be sent, that error is returned afterwards.
+    pub async fn abort_transaction(&self) -> Result<()> {
+        self.transaction()?
+            .end(self.inner.spu_pool.as_ref(), false)
+            .await?;
+        Ok(())
+    }
+
+    /// Create producer for another topic which writes in the same transactions as this producer.
+    pub async fn transaction_producer(&self, topic: impl Into<String>) -> Result<Self> {
+        let transaction = self.transaction()?.clone();
+        Self::new_with_transaction(
+            topic.into(),
+            self.inner.spu_pool.clone(),
+            self.inner.config.clone(),
+            self.metrics.clone(),
+            Some(transaction),
+        )
+        .await
+    }
+
+    fn transaction(&self) -> Result<&Arc<ProducerTransaction>> {
+        self.inner.transaction.as_ref().ok_or_else(|| {
+            FluvioError::Producer(ProducerError::Transaction(
+                "producer is not transactional".to_owned(),
+            ))
+            .into()
+        })
+    }
+
     /// Clear partition producers errors in order to make partition producers available.
     /// This is needed once an error is present in order to send new records again.
     pub async fn clear_errors(&self) {
@@ -593,6 +771,63 @@ where
     }
 }
 
+/// Get producer id for idempotent producer from the leader of the first partition.
+/// Leader which allocated producer id coordinates transactions of the producer.
+async fn init_producer_id<S>(
+    spu_pool: &S,
+    topic: &str,
+    config: &TopicProducerConfig,
+) -> Result<(i64, SpuId)>
+where
+    S: SpuPool + Send + Sync + 'static,
+{
+    if !matches!(config.delivery_semantic, DeliverySemantic::AtLeastOnce(_)) {
+        return Err(FluvioError::Other(
+            "idempotent producer requires AtLeastOnce delivery semantic".to_owned(),
+        )
+        .into());
+    }
+
+    let replica = ReplicaKey::new(topic, 0_u32);
+    let leader = spu_pool
+        .partitions()
+        .lookup_by_key(&replica)
+        .await?
+        .ok_or_else(|| FluvioError::PartitionNotFound(topic.to_string(), 0))?
+        .spec
+        .leader;
+    let socket = spu_pool.create_serial_socket_from_leader(leader).await?;
+    if socket
+        .versions()
+        .lookup_version::<InitProducerIdRequest>()
+        .is_none()
+    {
+        return Err(
+            FluvioError::Other("SPU does not support idempotent producer".to_owned()).into(),
+        );
+    }
+    if config.transactional
+        && socket
+            .versions()
+            .lookup_version::<EndTxnRequest>()
+            .is_none()
+    {
+        return Err(
+            FluvioError::Other("SPU does not support transactional producer".to_owned()).into(),
+        );
+    }
+
+    let response = socket.send_receive(InitProducerIdRequest).await?;
+    if response.error_code.is_error() {
+        return Err(FluvioError::Other(format!(
+            "failed to init producer id: {}",
+            response.error_code
+        ))
+        .into());
+    }
+    Ok((response.producer_id, leader))
+}
+
 #[cfg(feature = "compress")]
 fn determine_producer_compression_algo(
     config: Arc<TopicProducerConfig>,
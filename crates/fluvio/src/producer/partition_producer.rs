@@ -1,16 +1,22 @@
 use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
 
 use adaptive_backoff::prelude::{
     Backoff, BackoffBuilder, ExponentialBackoff, ExponentialBackoffBuilder,
 };
-use async_lock::RwLock;
+use async_lock::{Mutex, RwLock};
 use fluvio_types::defaults::{
     RECONNECT_BACKOFF_FACTOR, RECONNECT_BACKOFF_MAX_DURATION, RECONNECT_BACKOFF_MIN_DURATION,
 };
 use tracing::{debug, info, instrument, error, trace};
 
 use fluvio_protocol::record::ReplicaKey;
-use fluvio_protocol::record::{RawRecords, Batch};
+use fluvio_protocol::record::{RawRecords, Batch, increment_sequence};
 use fluvio_spu_schema::produce::{DefaultPartitionRequest, DefaultTopicRequest, DefaultProduceRequest};
 use fluvio_future::timer::sleep;
 use fluvio_types::SpuId;
@@ -29,6 +35,7 @@ use super::{
 };
 use super::accumulator::{BatchEvents, BatchesDeque};
 use super::event::EventHandler;
+use super::transaction::ProducerTransaction;
 
 /// Struct that is responsible for sending produce requests to the SPU in a given partition.
 pub(crate) struct PartitionProducer<S>
@@ -43,6 +50,79 @@ where
     last_error: Arc<RwLock<Option<ProducerError>>>,
     metrics: Arc<ClientMetrics>,
     callback: Option<SharedProducerCallback>,
+    sequence: Option<Mutex<ProducerSequence>>,
+    transaction: Option<Arc<ProducerTransaction>>,
+    throttle: Arc<ProduceThrottle>,
+}
+
+/// Sequence numbers of idempotent producer for a partition
+#[derive(Debug)]
+struct ProducerSequence {
+    producer_id: i64,
+    /// none when all epochs were used
+    epoch: Option<i16>,
+    next_sequence: i32,
+}
+
+impl ProducerSequence {
+    fn new(producer_id: i64) -> Self {
+        Self {
+            producer_id,
+            epoch: Some(0),
+            next_sequence: 0,
+        }
+    }
+
+    fn stamp(&mut self, batch: &mut Batch<RawRecords>) -> Result<()> {
+        let epoch = self.epoch.ok_or_else(|| self.exhausted())?;
+        let header = batch.get_mut_header();
+        header.producer_id = self.producer_id;
+        header.producer_epoch = epoch;
+        header.first_sequence = self.next_sequence;
+        self.next_sequence = increment_sequence(header.last_sequence(), 1);
+        Ok(())
+    }
+
+    /// Batches of failed request may not be written, so following batches can't continue the sequence.
+    /// Start new epoch with sequence from zero.
+    /// Epoch can't wrap around, SPU would take batches of new epoch as stale.
+    fn bump_epoch(&mut self) -> Result<()> {
+        self.epoch = self.epoch.and_then(|epoch| epoch.checked_add(1));
+        self.next_sequence = 0;
+        match self.epoch {
+            Some(_) => Ok(()),
+            None => Err(self.exhausted()),
+        }
+    }
+
+    fn exhausted(&self) -> FluvioError {
+        ProducerError::Internal(format!(
+            "epochs of idempotent producer {} are exhausted",
+            self.producer_id
+        ))
+        .into()
+    }
+}
+
+/// Quota throttle reported by SPU, no request is sent to SPU until it expires
+#[derive(Debug, Default)]
+struct ProduceThrottle(std::sync::Mutex<Option<Instant>>);
+
+impl ProduceThrottle {
+    fn set(&self, throttle_time_ms: i32) {
+        if throttle_time_ms > 0 {
+            let until = Instant::now() + Duration::from_millis(throttle_time_ms as u64);
+            *self.0.lock().expect("throttle lock poisoned") = Some(until);
+        }
+    }
+
+    async fn wait(&self) {
+        let until = self.0.lock().expect("throttle lock poisoned").take();
+        if let Some(delay) = until.and_then(|until| until.checked_duration_since(Instant::now())) {
+            debug!(delay_ms = delay.as_millis(), "producer throttled by quota");
+            sleep(delay).await;
+        }
+    }
 }
 
 impl<S> PartitionProducer<S>
@@ -63,6 +143,11 @@ where
             last_error,
             metrics: params.client_metric,
             callback: params.callback,
+            sequence: params
+                .producer_id
+                .map(|producer_id| Mutex::new(ProducerSequence::new(producer_id))),
+            transaction: params.transaction,
+            throttle: Arc::new(ProduceThrottle::default()),
         }
     }
 
@@ -165,8 +250,18 @@ where
     /// Flush all the batches that are full or have reached the linger time.
     /// If force is set to true, flush all batches regardless of linger time.
     pub(crate) async fn flush(&self, force: bool) -> Result<()> {
+        self.throttle.wait().await;
         let spu_socket = self.connect_spu_with_reconnect().await?;
 
+        // partition is registered with coordinator before any record of transaction is written
+        if let Some(transaction) = &self.transaction {
+            if !self.batches_lock.batches.read().await.is_empty() {
+                transaction
+                    .add_partition(self.spu_pool.as_ref(), &self.replica)
+                    .await?;
+            }
+        }
+
         let mut batches_ready = vec![];
         {
             let mut batches = self.batches_lock.batches.write().await;
@@ -196,6 +291,12 @@ where
 
         let mut batch_notifiers = vec![];
 
+        // keep sequence locked until response, so batches are sent in sequence order
+        let mut sequence = match &self.sequence {
+            Some(sequence) => Some(sequence.lock().await),
+            None => None,
+        };
+
         let mut events_to_callback = vec![];
 
         for p_batch in batches_ready {
@@ -207,7 +308,16 @@ where
             let metadata = p_batch.metadata().clone();
             let batch = p_batch.batch();
 
-            let raw_batch: Batch<RawRecords> = batch.try_into()?;
+            let mut raw_batch: Batch<RawRecords> = batch.try_into()?;
+            if let Some(sequence) = sequence.as_mut() {
+                sequence.stamp(&mut raw_batch)?;
+            }
+            if self.transaction.is_some() {
+                raw_batch.get_mut_header().set_transactional();
+            }
+            if let Some(schema_id) = self.config.schema_id {
+                raw_batch.set_schema_id(schema_id.into());
+            }
 
             let producer_metrics = self.metrics.producer_client();
             let records_len = raw_batch.records_len() as u64;
@@ -239,7 +349,21 @@ where
         request.smartmodules.clone_from(&self.config.smartmodules);
         request.topics.push(topic_request);
 
-        let (response, _) = self.send_to_socket(spu_socket, request).await?;
+        let response = self.send_to_socket(spu_socket, request).await;
+        let mut bumped = Ok(());
+        if let Some(sequence) = sequence.as_mut() {
+            let failed = match &response {
+                Ok((partition_responses, _)) => partition_responses
+                    .iter()
+                    .any(|partition| partition.is_ready_error()),
+                Err(_) => true,
+            };
+            if failed {
+                bumped = sequence.bump_epoch();
+            }
+        }
+        drop(sequence);
+        let (response, _) = response?;
 
         for (batch_notifier, partition_response_fut) in
             batch_notifiers.into_iter().zip(response.into_iter())
@@ -257,7 +381,7 @@ where
             }
         }
 
-        Ok(())
+        bumped
     }
 
     async fn connect_spu(&self) -> Result<VersionedSerialSocket> {
@@ -294,6 +418,13 @@ where
                 use futures_util::FutureExt;
                 let async_response = socket.send_async(request).await?;
                 let shared = FutureExt::map(async_response, Arc::new).boxed().shared();
+                let throttle = self.throttle.clone();
+                let response = shared.clone();
+                fluvio_future::task::spawn(async move {
+                    if let Ok(response) = response.await.as_ref() {
+                        throttle.set(response.throttle_time_ms);
+                    }
+                });
                 (0..partition_count)
                     .map(|index| ProducePartitionResponseFuture::from(shared.clone(), index))
                     .collect()
@@ -306,6 +437,8 @@ where
                     .await
                     .map_err(|timeout_err| FluvioError::Producer(timeout_err.into()))??;
 
+                self.throttle.set(produce_response.throttle_time_ms);
+
                 let mut futures = Vec::with_capacity(partition_count);
                 for topic in produce_response.responses.into_iter() {
                     for partition in topic.partitions {
@@ -342,3 +475,47 @@ async fn backoff_and_wait(backoff: &mut ExponentialBackoff) {
     let _ = sleep(wait_duration).await;
     debug!("Resuming after backoff");
 }
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::{Batch, RawRecords, Record};
+
+    use super::ProducerSequence;
+
+    fn raw_batch(records: usize) -> Batch<RawRecords> {
+        Batch::from(vec![Record::new("a"); records])
+            .try_into()
+            .expect("raw batch")
+    }
+
+    #[test]
+    fn test_producer_sequence_stamp() {
+        let mut sequence = ProducerSequence::new(7);
+
+        let mut first = raw_batch(3);
+        sequence.stamp(&mut first).expect("stamp");
+        assert_eq!(first.header.producer_id, 7);
+        assert_eq!(first.header.producer_epoch, 0);
+        assert_eq!(first.header.first_sequence, 0);
+        assert!(first.header.is_idempotent());
+
+        let mut second = raw_batch(2);
+        sequence.stamp(&mut second).expect("stamp");
+        assert_eq!(second.header.first_sequence, 3);
+
+        sequence.bump_epoch().expect("bump");
+        let mut third = raw_batch(1);
+        sequence.stamp(&mut third).expect("stamp");
+        assert_eq!(third.header.producer_epoch, 1);
+        assert_eq!(third.header.first_sequence, 0);
+    }
+
+    #[test]
+    fn test_producer_sequence_epoch_exhausted() {
+        let mut sequence = ProducerSequence::new(7);
+        sequence.epoch = Some(i16::MAX);
+
+        assert!(sequence.bump_epoch().is_err());
+        assert!(sequence.stamp(&mut raw_batch(1)).is_err());
+    }
+}
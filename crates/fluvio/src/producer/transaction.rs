@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use async_lock::{Mutex, RwLock};
+use tracing::debug;
+
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_spu_schema::server::transaction::{AddTxnPartitionsRequest, EndTxnRequest};
+use fluvio_types::SpuId;
+
+use crate::error::{Result, FluvioError};
+use crate::spu::SpuPool;
+
+use super::{ProducerError, ProducerPool};
+
+/// Transaction shared by producers created from the same transactional producer.
+/// Coordinator is the SPU which allocated producer id.
+/// State is not locked while requests are sent to coordinator.
+pub(crate) struct ProducerTransaction {
+    producer_id: i64,
+    coordinator: SpuId,
+    /// transaction is aborted by coordinator if not ended within this time
+    timeout: Duration,
+    state: Mutex<TransactionState>,
+    producer_pools: Mutex<Vec<Weak<RwLock<ProducerPool>>>>,
+}
+
+#[derive(Default)]
+struct TransactionState {
+    active: bool,
+    /// end request is being sent to coordinator
+    ending: bool,
+    partitions: HashSet<ReplicaKey>,
+}
+
+impl ProducerTransaction {
+    pub(crate) fn new(producer_id: i64, coordinator: SpuId, timeout: Duration) -> Self {
+        Self {
+            producer_id,
+            coordinator,
+            timeout,
+            state: Mutex::new(TransactionState::default()),
+            producer_pools: Mutex::new(vec![]),
+        }
+    }
+
+    pub(crate) fn producer_id(&self) -> i64 {
+        self.producer_id
+    }
+
+    /// producer pool which must be flushed before transaction ends
+    pub(crate) async fn add_producer_pool(&self, pool: &Arc<RwLock<ProducerPool>>) {
+        let mut pools = self.producer_pools.lock().await;
+        pools.retain(|pool| pool.strong_count() > 0);
+        pools.push(Arc::downgrade(pool));
+    }
+
+    pub(crate) async fn begin(&self) -> Result<()> {
+        let mut state = self.state.lock().await;
+        if state.active {
+            return Err(transaction_error("transaction is already active"));
+        }
+        state.active = true;
+        Ok(())
+    }
+
+    /// records can be sent in transaction until it starts ending
+    pub(crate) async fn is_active(&self) -> bool {
+        let state = self.state.lock().await;
+        state.active && !state.ending
+    }
+
+    /// register partition with coordinator before first batch is sent to it
+    pub(crate) async fn add_partition<S>(&self, spu_pool: &S, replica: &ReplicaKey) -> Result<()>
+    where
+        S: SpuPool + Send + Sync + 'static,
+    {
+        if self.state.lock().await.partitions.contains(replica) {
+            return Ok(());
+        }
+        // coordinator ignores partition which was already added, concurrent requests are harmless
+        debug!(producer_id = self.producer_id, %replica, "add partition to transaction");
+        let request = AddTxnPartitionsRequest {
+            producer_id: self.producer_id,
+            partitions: vec![replica.clone()],
+            timeout_ms: self.timeout.as_millis().try_into().unwrap_or(u32::MAX),
+        };
+        let socket = spu_pool
+            .create_serial_socket_from_leader(self.coordinator)
+            .await?;
+        let response = socket.send_receive(request).await?;
+        if response.error_code.is_error() {
+            return Err(FluvioError::Producer(response.error_code.into()));
+        }
+        self.state.lock().await.partitions.insert(replica.clone());
+        Ok(())
+    }
+
+    /// send all pending records, then commit or abort transaction.
+    /// Commit fails if records can't be sent, transaction stays active so it can be aborted.
+    /// Abort ends transaction even then, error of sending records is returned afterwards.
+    pub(crate) async fn end<S>(&self, spu_pool: &S, commit: bool) -> Result<()>
+    where
+        S: SpuPool + Send + Sync + 'static,
+    {
+        {
+            let mut state = self.state.lock().await;
+            if !state.active {
+                return Err(transaction_error("no active transaction"));
+            }
+            if state.ending {
+                return Err(transaction_error("transaction is already ending"));
+            }
+            state.ending = true;
+        }
+        let flushed = self.flush().await;
+        let ended = if commit && flushed.is_err() {
+            None
+        } else {
+            Some(self.send_end(spu_pool, commit).await)
+        };
+
+        let mut state = self.state.lock().await;
+        state.ending = false;
+        if let Some(Ok(())) = ended {
+            state.active = false;
+            state.partitions.clear();
+        }
+        drop(state);
+
+        match ended {
+            Some(Err(err)) => Err(err),
+            _ => flushed,
+        }
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let pools: Vec<_> = self
+            .producer_pools
+            .lock()
+            .await
+            .iter()
+            .filter_map(|pool| pool.upgrade())
+            .collect();
+        for pool in pools {
+            pool.read().await.flush_all_batches().await.map_err(|err| {
+                FluvioError::Other(format!("failed to send records of transaction: {err}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    async fn send_end<S>(&self, spu_pool: &S, commit: bool) -> Result<()>
+    where
+        S: SpuPool + Send + Sync + 'static,
+    {
+        let socket = spu_pool
+            .create_serial_socket_from_leader(self.coordinator)
+            .await?;
+        let response = socket
+            .send_receive(EndTxnRequest {
+                producer_id: self.producer_id,
+                commit,
+            })
+            .await?;
+        if response.error_code.is_error() {
+            return Err(FluvioError::Producer(response.error_code.into()));
+        }
+        debug!(producer_id = self.producer_id, commit, "transaction ended");
+        Ok(())
+    }
+}
+
+fn transaction_error(message: &str) -> FluvioError {
+    FluvioError::Producer(ProducerError::Transaction(message.to_owned()))
+}
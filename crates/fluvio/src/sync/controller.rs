@@ -1,11 +1,15 @@
 use std::convert::{TryFrom, TryInto};
 use std::io::{Error as IoError, ErrorKind};
 use std::fmt::Display;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use adaptive_backoff::prelude::{Backoff, BackoffBuilder, ExponentialBackoffBuilder};
 use fluvio_sc_schema::message::MsgType;
-use tracing::{error, debug, instrument};
+use fluvio_types::defaults::{
+    RECONNECT_BACKOFF_FACTOR, RECONNECT_BACKOFF_MAX_DURATION, RECONNECT_BACKOFF_MIN_DURATION,
+};
+use tracing::{error, debug, info, instrument};
 use event_listener::{Event, EventListener};
 use futures_util::stream::StreamExt;
 use anyhow::Result;
@@ -18,6 +22,7 @@ use fluvio_sc_schema::{AdminSpec, TryEncodableFrom};
 
 use super::StoreContext;
 use super::CacheMetadataStoreObject;
+use super::MetadataConnection;
 use crate::metadata::store::actions::LSUpdate;
 
 pub(crate) struct SimpleEvent {
@@ -42,6 +47,7 @@ impl SimpleEvent {
     }
 
     pub(crate) fn notify(&self) {
+        self.flag.store(true, Ordering::SeqCst);
         self.event.notify(usize::MAX);
     }
 }
@@ -49,6 +55,7 @@ impl SimpleEvent {
 /// Synchronize metadata from SC
 pub(crate) struct MetadataSyncController<S: AdminSpec> {
     store: StoreContext<S>,
+    connection: Weak<MetadataConnection>,
     shutdown: Arc<SimpleEvent>,
 }
 
@@ -65,11 +72,16 @@ where
     pub(crate) fn start(
         store: StoreContext<S>,
         watch_response: AsyncResponse<ObjectApiWatchRequest>,
+        connection: Weak<MetadataConnection>,
         shutdown: Arc<SimpleEvent>,
     ) {
         use fluvio_future::task::spawn;
 
-        let controller = Self { store, shutdown };
+        let controller = Self {
+            store,
+            connection,
+            shutdown,
+        };
 
         debug!(spec = %S::LABEL, "spawning sync controller");
         spawn(controller.dispatch_loop(watch_response));
@@ -82,10 +94,25 @@ where
         )
     )]
     async fn dispatch_loop(mut self, mut response: AsyncResponse<ObjectApiWatchRequest>) {
-        use tokio::select;
-
         debug!("{} starting dispatch loop", S::LABEL);
 
+        loop {
+            self.sync_loop(&mut response).await;
+
+            // stream ended without shutdown, SC may have failed over so watch again
+            match self.reconnect().await {
+                Some(new_response) => response = new_response,
+                None => break,
+            }
+        }
+
+        debug!("{} terminated", S::LABEL);
+    }
+
+    /// apply updates from stream until it ends or shutdown is requested
+    async fn sync_loop(&mut self, response: &mut AsyncResponse<ObjectApiWatchRequest>) {
+        use tokio::select;
+
         loop {
             // check if shutdown is set
             if self.shutdown.is_set() {
@@ -132,8 +159,48 @@ where
                 }
             }
         }
+    }
 
-        debug!("{} terminated", S::LABEL);
+    /// create new metadata stream, retrying with backoff until it succeeds or shutdown is requested
+    async fn reconnect(&self) -> Option<AsyncResponse<ObjectApiWatchRequest>> {
+        use tokio::select;
+        use fluvio_future::timer::sleep;
+
+        let mut backoff = match ExponentialBackoffBuilder::default()
+            .factor(RECONNECT_BACKOFF_FACTOR)
+            .min(RECONNECT_BACKOFF_MIN_DURATION)
+            .max(RECONNECT_BACKOFF_MAX_DURATION)
+            .build()
+        {
+            Ok(backoff) => backoff,
+            Err(err) => {
+                error!("creating backoff: {}", err);
+                return None;
+            }
+        };
+
+        loop {
+            if self.shutdown.is_set() {
+                return None;
+            }
+
+            // stores are dropped, no one is interested in metadata anymore
+            let result = self.connection.upgrade()?.watch_stream::<S>().await;
+
+            match result {
+                Ok(response) => {
+                    info!("{} metadata stream reconnected", S::LABEL);
+                    return Some(response);
+                }
+                Err(err) => {
+                    debug!("{} reconnecting metadata stream: {}", S::LABEL, err);
+                    select! {
+                        _ = self.shutdown.listen() => return None,
+                        _ = sleep(backoff.wait()) => {}
+                    }
+                }
+            }
+        }
     }
 
     // process updates from sc
@@ -12,7 +12,10 @@ use fluvio_protocol::Encoder;
 use fluvio_sc_schema::AdminSpec;
 use fluvio_sc_schema::objects::Metadata;
 use fluvio_sc_schema::objects::ObjectApiWatchRequest;
+use async_lock::Mutex;
 use fluvio_socket::AsyncResponse;
+use fluvio_socket::ClientConfig;
+use fluvio_socket::MultiplexerSocket;
 use fluvio_socket::SharedMultiplexerSocket;
 
 use crate::metadata::topic::TopicSpec;
@@ -30,23 +33,30 @@ pub struct MetadataStores {
     spus: StoreContext<SpuSpec>,
     partitions: StoreContext<PartitionSpec>,
     topics: StoreContext<TopicSpec>,
-    socket: SharedMultiplexerSocket,
-    watch_version: i16,
+    connection: Arc<MetadataConnection>,
 }
 
 impl MetadataStores {
     /// start synchronization
 
-    #[instrument(skip(socket))]
-    pub(crate) async fn start(socket: SharedMultiplexerSocket, watch_version: i16) -> Result<Self> {
+    #[instrument(skip(socket, config))]
+    pub(crate) async fn start(
+        socket: SharedMultiplexerSocket,
+        config: Arc<ClientConfig>,
+        watch_version: i16,
+    ) -> Result<Self> {
         debug!(watch_version, "starting metadata store");
+        let connection = MetadataConnection {
+            socket: Mutex::new(socket),
+            config,
+            watch_version,
+        };
         let store = Self {
             shutdown: SimpleEvent::shared(),
             spus: StoreContext::new(),
             partitions: StoreContext::new(),
             topics: StoreContext::new(),
-            socket,
-            watch_version,
+            connection: Arc::new(connection),
         };
 
         store.start_watch_for_spu().await?;
@@ -106,6 +116,33 @@ impl MetadataStores {
         S::IndexKey: Display + Sync + Send,
         CacheMetadataStoreObject<S>: TryFrom<Metadata<S>>,
         <Metadata<S> as TryInto<CacheMetadataStoreObject<S>>>::Error: Display,
+    {
+        let async_response = self.connection.watch_stream::<S>().await?;
+
+        MetadataSyncController::<S>::start(
+            store,
+            async_response,
+            Arc::downgrade(&self.connection),
+            self.shutdown.clone(),
+        );
+
+        Ok(())
+    }
+}
+
+/// Connection to SC used for metadata streams.
+/// Controllers only hold weak reference so that dropping stores closes connection.
+pub(crate) struct MetadataConnection {
+    socket: Mutex<SharedMultiplexerSocket>,
+    config: Arc<ClientConfig>,
+    watch_version: i16,
+}
+
+impl MetadataConnection {
+    /// create metadata stream for spec, reconnecting to SC if current connection is closed
+    pub(crate) async fn watch_stream<S>(&self) -> Result<AsyncResponse<ObjectApiWatchRequest>>
+    where
+        S: AdminSpec + Encoder + Decoder,
     {
         use fluvio_protocol::api::RequestMessage;
         use fluvio_sc_schema::objects::WatchRequest;
@@ -115,11 +152,21 @@ impl MetadataStores {
         let mut req_msg = RequestMessage::new_request(watch_req);
         req_msg.get_mut_header().set_api_version(self.watch_version);
 
+        let socket = self.socket().await?;
         debug!(watch_version = self.watch_version, obj = %S::LABEL, "create metadata stream");
-        let async_response = self.socket.create_stream(req_msg, 10).await?;
+        let async_response = socket.create_stream(req_msg, 10).await?;
 
-        MetadataSyncController::<S>::start(store, async_response, self.shutdown.clone());
+        Ok(async_response)
+    }
 
-        Ok(())
+    /// current socket to SC, if it is closed then connect again using any of SC endpoints
+    async fn socket(&self) -> Result<SharedMultiplexerSocket> {
+        let mut socket = self.socket.lock().await;
+        if socket.is_stale() {
+            debug!(addr = %self.config.addr(), "SC connection is closed, reconnecting");
+            let (new_socket, _, _) = self.config.recreate().connect().await?.split();
+            *socket = MultiplexerSocket::shared(new_socket);
+        }
+        Ok(socket.clone())
     }
 }
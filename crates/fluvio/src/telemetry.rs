@@ -0,0 +1,155 @@
+//!
+//! # OpenTelemetry
+//!
+//! Exports `tracing` spans to an OTLP collector and converts between
+//! span contexts and [`TraceContext`] carried in record headers.
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! let _tracer = fluvio::telemetry::init_otlp_tracer("my-app", None)?;
+//! let producer = fluvio::producer("my-topic").await?;
+//! // records are tagged with context of current span
+//! producer.send(fluvio::RecordKey::NULL, "Hello").await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+
+use anyhow::Result;
+use opentelemetry::trace::{
+    SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, TracerProvider as _,
+};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::{runtime, Resource};
+use tracing::{error, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+use fluvio_protocol::record::Record;
+
+pub use fluvio_protocol::record::{TraceContext, TRACEPARENT_HEADER};
+
+/// Standard environment variable for collector endpoint
+pub const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+/// Flushes pending spans when dropped
+pub struct OtlpTracer {
+    provider: TracerProvider,
+}
+
+impl Drop for OtlpTracer {
+    fn drop(&mut self) {
+        if let Err(err) = self.provider.shutdown() {
+            error!(%err, "failed to flush spans");
+        }
+    }
+}
+
+/// Install global `tracing` subscriber logging to stderr and exporting spans over OTLP/HTTP.
+/// Endpoint defaults to `OTEL_EXPORTER_OTLP_ENDPOINT` or `http://localhost:4318`.
+/// Must be called within tokio runtime.
+pub fn init_otlp_tracer(service_name: &str, endpoint: Option<&str>) -> Result<OtlpTracer> {
+    let mut exporter = SpanExporter::builder().with_http();
+    if let Some(endpoint) = endpoint {
+        exporter = exporter.with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')));
+    }
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter.build()?, runtime::Tokio)
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            service_name.to_owned(),
+        )]))
+        .build();
+    let tracer = provider.tracer(service_name.to_owned());
+
+    tracing_subscriber::registry()
+        .with(EnvFilter::from_default_env())
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .try_init()?;
+
+    Ok(OtlpTracer { provider })
+}
+
+/// Context of current span, if it is exported
+pub fn current_trace_context() -> Option<TraceContext> {
+    let context = Span::current().context();
+    let span = context.span();
+    let span_context = span.span_context();
+    if !span_context.is_valid() {
+        return None;
+    }
+    TraceContext::new(
+        span_context.trace_id().to_bytes(),
+        span_context.span_id().to_bytes(),
+        span_context.is_sampled(),
+    )
+}
+
+/// attach context of current span to record which doesn't carry one already
+pub(crate) fn propagate_current_context(mut record: Record) -> Record {
+    if record.trace_context().is_none() {
+        if let Some(context) = current_trace_context() {
+            record.set_trace_context(&context);
+        }
+    }
+    record
+}
+
+/// Make span child of remote span which produced the record
+pub fn set_parent(span: &Span, context: &TraceContext) {
+    span.set_parent(Context::new().with_remote_span_context(span_context(context)));
+}
+
+/// Link span to remote span which produced the record,
+/// used when span processes records of many traces
+pub fn add_link(span: &Span, context: &TraceContext) {
+    span.add_link(span_context(context));
+}
+
+fn span_context(context: &TraceContext) -> SpanContext {
+    SpanContext::new(
+        TraceId::from_bytes(context.trace_id()),
+        SpanId::from_bytes(context.span_id()),
+        TraceFlags::new(context.flags()),
+        true,
+        TraceState::default(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::trace::TracerProvider;
+    use tracing::info_span;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[test]
+    fn test_span_trace_context() {
+        let provider = TracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let remote: TraceContext = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .expect("parse");
+
+            let span = info_span!("consume");
+            set_parent(&span, &remote);
+            let _entered = span.enter();
+
+            let current = current_trace_context().expect("context");
+            assert_eq!(current.trace_id(), remote.trace_id());
+            assert_ne!(current.span_id(), remote.span_id());
+            assert!(current.is_sampled());
+        });
+    }
+}